@@ -0,0 +1,141 @@
+use regex::{Captures, Regex};
+
+use crate::shell::shell_quote;
+
+/// What an action does with its expanded template.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionKind {
+    /// Runs the template as a shell command.
+    Command(String),
+    /// Copies the template to the clipboard.
+    Copy(String),
+}
+
+/// Named action on a log line, with a template filled in from the regex captures of the line.
+///
+/// Placeholders are `{name}` for named capture groups, `{1}` for numbered ones and `{line}` for the whole line.
+#[derive(Debug, Clone)]
+pub struct LineAction {
+    pub name: String,
+    regex: Option<Regex>,
+    kind: ActionKind,
+}
+
+impl LineAction {
+    /// Creates an action. Without a regex, the action applies to every line and only `{line}` can be used.
+    pub fn new(name: &str, regex: Option<Regex>, kind: ActionKind) -> Self {
+        Self {
+            name: name.to_string(),
+            regex,
+            kind,
+        }
+    }
+
+    pub fn kind(&self) -> &ActionKind {
+        &self.kind
+    }
+
+    /// Expands the template for a line. Returns `None` if the regex doesn't match the line or a placeholder
+    /// has no value. Values substituted into commands are quoted for the shell.
+    pub fn expand(&self, line: &str) -> Option<String> {
+        let captures = match &self.regex {
+            Some(regex) => Some(regex.captures(line)?),
+            None => None,
+        };
+        match &self.kind {
+            ActionKind::Command(template) => expand_template(template, captures.as_ref(), line, shell_quote),
+            ActionKind::Copy(template) => expand_template(template, captures.as_ref(), line, str::to_string),
+        }
+    }
+}
+
+/// Replaces the placeholders of a template with the captures of a line, passing each value through `quote`.
+/// `{{` and `}}` are kept as literal braces.
+fn expand_template(
+    template: &str,
+    captures: Option<&Captures>,
+    line: &str,
+    quote: impl Fn(&str) -> String,
+) -> Option<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        result.push_str(&rest[..start]);
+        let brace = rest.as_bytes()[start];
+        rest = &rest[start + 1..];
+
+        if rest.as_bytes().first() == Some(&brace) {
+            result.push(brace as char);
+            rest = &rest[1..];
+            continue;
+        }
+        if brace == b'}' {
+            result.push('}');
+            continue;
+        }
+
+        let end = rest.find('}')?;
+        let placeholder = &rest[..end];
+        rest = &rest[end + 1..];
+
+        let value = if placeholder == "line" {
+            line
+        } else {
+            let captures = captures?;
+            let group = match placeholder.parse::<usize>() {
+                Ok(index) => captures.get(index),
+                Err(_) => captures.name(placeholder),
+            };
+            group?.as_str()
+        };
+        result.push_str(&quote(value));
+    }
+    result.push_str(rest);
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_command_quotes_values() {
+        let action = LineAction::new(
+            "Open trace",
+            Some(Regex::new(r"trace_id=(?P<trace_id>\S+)").unwrap()),
+            ActionKind::Command("xdg-open https://trace.example.com/{trace_id}".to_string()),
+        );
+
+        assert_eq!(
+            action.expand("GET /api trace_id=4bf92f3577b3"),
+            Some("xdg-open https://trace.example.com/'4bf92f3577b3'".to_string())
+        );
+        assert_eq!(
+            action.expand("trace_id=x';rm${IFS}-rf"),
+            Some(r"xdg-open https://trace.example.com/'x'\'';rm${IFS}-rf'".to_string())
+        );
+        assert_eq!(action.expand("no trace here"), None);
+    }
+
+    #[test]
+    fn test_expand_copy() {
+        let action = LineAction::new(
+            "Copy request id",
+            Some(Regex::new(r"req=(\w+)").unwrap()),
+            ActionKind::Copy("{1}".to_string()),
+        );
+        assert_eq!(action.expand("INFO req=abc123 done"), Some("abc123".to_string()));
+
+        let whole_line = LineAction::new("Copy", None, ActionKind::Copy("{{{line}}}".to_string()));
+        assert_eq!(whole_line.expand("a b"), Some("{a b}".to_string()));
+
+        let missing_group = LineAction::new(
+            "Missing",
+            Some(Regex::new(r"req=(\w+)").unwrap()),
+            ActionKind::Copy("{user}".to_string()),
+        );
+        assert_eq!(missing_group.expand("req=abc"), None);
+    }
+}
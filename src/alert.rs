@@ -0,0 +1,258 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use regex::Regex;
+
+/// Time a rule stays quiet after ringing the bell or sending a desktop notification.
+pub const ANNOUNCE_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Fires when more than `count` lines match within `window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlertThreshold {
+    pub count: usize,
+    pub window: Duration,
+}
+
+/// Pattern that raises an alert when it matches streamed lines.
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    pub name: String,
+    regex: Regex,
+    threshold: Option<AlertThreshold>,
+    /// Whether to ring the terminal bell when the alert fires.
+    pub bell: bool,
+    /// Whether to send a desktop notification when the alert fires.
+    pub notify: bool,
+    /// Times of the matches within the threshold window, oldest first.
+    recent: VecDeque<Instant>,
+}
+
+impl AlertRule {
+    pub fn new(name: &str, regex: Regex, threshold: Option<AlertThreshold>) -> Self {
+        Self {
+            name: name.to_string(),
+            regex,
+            threshold,
+            bell: false,
+            notify: false,
+            recent: VecDeque::new(),
+        }
+    }
+
+    pub fn with_bell(mut self, bell: bool) -> Self {
+        self.bell = bell;
+        self
+    }
+
+    pub fn with_notify(mut self, notify: bool) -> Self {
+        self.notify = notify;
+        self
+    }
+
+    pub fn pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    pub fn threshold(&self) -> Option<AlertThreshold> {
+        self.threshold
+    }
+
+    /// Records a matching line. Returns true if the alert fires.
+    ///
+    /// Without a threshold every match fires. With a threshold the alert fires once the number of matches
+    /// within the window exceeds the count, and then waits for the count to be exceeded again.
+    fn process_line(&mut self, line: &str, now: Instant) -> bool {
+        if !self.regex.is_match(line) {
+            return false;
+        }
+        let Some(threshold) = self.threshold else {
+            return true;
+        };
+
+        while self
+            .recent
+            .front()
+            .is_some_and(|&time| now.duration_since(time) > threshold.window)
+        {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(now);
+        if self.recent.len() > threshold.count {
+            self.recent.clear();
+            return true;
+        }
+        false
+    }
+}
+
+/// A fired alert.
+#[derive(Debug, Clone)]
+pub struct AlertFiring {
+    /// Index of the rule in [`Alerts::rules`].
+    pub rule_index: usize,
+    /// Log line index of the line that fired the alert.
+    pub line_index: usize,
+    pub time: DateTime<Local>,
+}
+
+/// Alert rules and the alerts they fired.
+#[derive(Debug, Default)]
+pub struct Alerts {
+    rules: Vec<AlertRule>,
+    firings: Vec<AlertFiring>,
+}
+
+impl Alerts {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            rules,
+            firings: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    /// Returns the fired alerts, oldest first.
+    pub fn firings(&self) -> &[AlertFiring] {
+        &self.firings
+    }
+
+    /// Checks a streamed line against all rules. Returns the number of alerts it fired.
+    pub fn process_line(&mut self, line: &str, line_index: usize, now: Instant) -> usize {
+        let mut fired = 0;
+        for (rule_index, rule) in self.rules.iter_mut().enumerate() {
+            if rule.process_line(line, now) {
+                self.firings.push(AlertFiring {
+                    rule_index,
+                    line_index,
+                    time: Local::now(),
+                });
+                fired += 1;
+            }
+        }
+        fired
+    }
+
+    /// Forgets alerts fired on lines before `count` and shifts the line indices of the rest.
+    pub fn drop_lines_before(&mut self, count: usize) {
+        self.firings
+            .retain_mut(|firing| match firing.line_index.checked_sub(count) {
+                Some(line_index) => {
+                    firing.line_index = line_index;
+                    true
+                }
+                None => false,
+            });
+    }
+
+    /// Forgets all fired alerts and the matches counted towards thresholds.
+    pub fn clear(&mut self) {
+        self.firings.clear();
+        for rule in &mut self.rules {
+            rule.recent.clear();
+        }
+    }
+}
+
+/// Limits how often each rule rings the bell or sends a desktop notification, so a burst of matching lines
+/// doesn't flood the terminal or the desktop.
+#[derive(Debug, Default)]
+pub struct AnnounceCooldown {
+    /// When each rule last announced, by key.
+    last_announced: HashMap<String, Instant>,
+}
+
+impl AnnounceCooldown {
+    /// Returns true if the rule with the given key may announce at `now`, and starts its cooldown if so.
+    pub fn try_announce(&mut self, key: &str, now: Instant) -> bool {
+        if self
+            .last_announced
+            .get(key)
+            .is_some_and(|&last| now.duration_since(last) < ANNOUNCE_COOLDOWN)
+        {
+            return false;
+        }
+        self.last_announced.insert(key.to_string(), now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, threshold: Option<AlertThreshold>) -> AlertRule {
+        AlertRule::new(pattern, Regex::new(pattern).unwrap(), threshold)
+    }
+
+    #[test]
+    fn test_rule_without_threshold_fires_on_every_match() {
+        let mut alerts = Alerts::new(vec![rule("ERROR", None)]);
+        let now = Instant::now();
+        assert_eq!(alerts.process_line("INFO ok", 0, now), 0);
+        assert_eq!(alerts.process_line("ERROR a", 1, now), 1);
+        assert_eq!(alerts.process_line("ERROR b", 2, now), 1);
+        let lines: Vec<usize> = alerts.firings().iter().map(|f| f.line_index).collect();
+        assert_eq!(lines, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_threshold_fires_when_count_exceeded_within_window() {
+        let threshold = AlertThreshold {
+            count: 2,
+            window: Duration::from_secs(10),
+        };
+        let mut alerts = Alerts::new(vec![rule("timeout", Some(threshold))]);
+        let start = Instant::now();
+
+        assert_eq!(alerts.process_line("timeout", 0, start), 0);
+        assert_eq!(alerts.process_line("timeout", 1, start + Duration::from_secs(1)), 0);
+        // The first match has left the window
+        assert_eq!(alerts.process_line("timeout", 2, start + Duration::from_secs(12)), 0);
+        assert_eq!(alerts.process_line("timeout", 3, start + Duration::from_secs(13)), 0);
+        assert_eq!(alerts.process_line("timeout", 4, start + Duration::from_secs(14)), 1);
+        // Counting starts over after firing
+        assert_eq!(alerts.process_line("timeout", 5, start + Duration::from_secs(15)), 0);
+        assert_eq!(alerts.firings().len(), 1);
+        assert_eq!(alerts.firings()[0].line_index, 4);
+    }
+
+    #[test]
+    fn test_several_rules_fire_on_one_line() {
+        let mut alerts = Alerts::new(vec![rule("disk", None), rule("full", None), rule("cpu", None)]);
+        assert_eq!(alerts.process_line("disk full", 7, Instant::now()), 2);
+        let rules: Vec<usize> = alerts.firings().iter().map(|f| f.rule_index).collect();
+        assert_eq!(rules, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_drop_lines_before() {
+        let mut alerts = Alerts::new(vec![rule("ERROR", None)]);
+        let now = Instant::now();
+        alerts.process_line("ERROR", 2, now);
+        alerts.process_line("ERROR", 5, now);
+        alerts.drop_lines_before(3);
+        let lines: Vec<usize> = alerts.firings().iter().map(|f| f.line_index).collect();
+        assert_eq!(lines, vec![2]);
+
+        alerts.clear();
+        assert!(alerts.firings().is_empty());
+    }
+
+    #[test]
+    fn test_announce_cooldown() {
+        let mut cooldown = AnnounceCooldown::default();
+        let start = Instant::now();
+        assert!(cooldown.try_announce("alert:disk", start));
+        assert!(!cooldown.try_announce("alert:disk", start + Duration::from_secs(1)));
+        assert!(cooldown.try_announce("alert:cpu", start + Duration::from_secs(1)));
+        assert!(cooldown.try_announce("alert:disk", start + ANNOUNCE_COOLDOWN));
+    }
+}
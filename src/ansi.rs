@@ -0,0 +1,202 @@
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::highlighter::PatternStyle;
+
+/// How ANSI escape sequences in log lines are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnsiMode {
+    /// Show the colors of SGR sequences and remove all other sequences.
+    #[default]
+    Colors,
+    /// Remove all escape sequences.
+    Strip,
+}
+
+/// The 16 basic ANSI colors, normal followed by bright.
+const BASIC_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// Reads the escape sequence at the start of `text`, which must start with ESC.
+///
+/// Returns the length of the sequence in bytes and, if it is an SGR sequence, its parameters.
+pub fn escape_sequence(text: &str) -> (usize, Option<&str>) {
+    let bytes = text.as_bytes();
+    match bytes.get(1) {
+        // CSI: parameter and intermediate bytes followed by a final byte
+        Some(b'[') => {
+            let Some(end) = bytes[2..]
+                .iter()
+                .position(|b| !(0x20..=0x3f).contains(b))
+                .map(|p| p + 2)
+            else {
+                return (bytes.len(), None);
+            };
+            if !(0x40..=0x7e).contains(&bytes[end]) {
+                return (2, None);
+            }
+            let params = (bytes[end] == b'm').then(|| &text[2..end]);
+            (end + 1, params)
+        }
+        // OSC, e.g. hyperlinks: terminated by BEL or ESC \
+        Some(b']') => {
+            for i in 2..bytes.len() {
+                if bytes[i] == 0x07 {
+                    return (i + 1, None);
+                }
+                if bytes[i] == 0x1b && bytes.get(i + 1) == Some(&b'\\') {
+                    return (i + 2, None);
+                }
+            }
+            (bytes.len(), None)
+        }
+        Some(b) if b.is_ascii() && !b.is_ascii_control() => (2, None),
+        _ => (1, None),
+    }
+}
+
+/// Applies the parameters of an SGR sequence to a style.
+pub fn apply_sgr(style: &mut PatternStyle, params: &str) {
+    let mut codes = params.split([';', ':']).map(|code| code.parse::<u16>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        match code {
+            0 => *style = PatternStyle::default(),
+            1 => style.bold = true,
+            22 => style.bold = false,
+            30..=37 => style.fg_color = Some(BASIC_COLORS[(code - 30) as usize]),
+            90..=97 => style.fg_color = Some(BASIC_COLORS[(code - 90 + 8) as usize]),
+            39 => style.fg_color = None,
+            40..=47 => style.bg_color = Some(BASIC_COLORS[(code - 40) as usize]),
+            100..=107 => style.bg_color = Some(BASIC_COLORS[(code - 100 + 8) as usize]),
+            49 => style.bg_color = None,
+            38 | 48 => {
+                // Colors with a component out of range are ignored
+                let component = |value: Option<u16>| value.and_then(|value| u8::try_from(value).ok());
+                let color = match codes.next() {
+                    Some(5) => component(codes.next()).map(Color::Indexed),
+                    Some(2) => match (
+                        component(codes.next()),
+                        component(codes.next()),
+                        component(codes.next()),
+                    ) {
+                        (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match (code, color) {
+                    (38, Some(color)) => style.fg_color = Some(color),
+                    (48, Some(color)) => style.bg_color = Some(color),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the parameters of an SGR sequence that sets a style, the reverse of [`apply_sgr`].
+pub fn sgr_params(style: &PatternStyle) -> String {
+    let mut params = Vec::new();
+    if style.bold {
+        params.push("1".to_string());
+    }
+    if let Some(fg) = style.fg_color.and_then(|color| sgr_color(color, 30, 38)) {
+        params.push(fg);
+    }
+    if let Some(bg) = style.bg_color.and_then(|color| sgr_color(color, 40, 48)) {
+        params.push(bg);
+    }
+    params.join(";")
+}
+
+/// Returns the SGR parameters of a color, given the codes of the first basic color and of extended colors.
+fn sgr_color(color: Color, basic: u16, extended: u16) -> Option<String> {
+    match color {
+        Color::Indexed(index) => Some(format!("{};5;{}", extended, index)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", extended, r, g, b)),
+        color => {
+            let position = BASIC_COLORS.iter().position(|&c| c == color)? as u16;
+            let code = if position < 8 {
+                basic + position
+            } else {
+                basic + 60 + position - 8
+            };
+            Some(code.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_sequence() {
+        assert_eq!(escape_sequence("\x1b[1;31mERROR"), (7, Some("1;31")));
+        assert_eq!(escape_sequence("\x1b[mtext"), (3, Some("")));
+        assert_eq!(escape_sequence("\x1b[2Ktext"), (4, None));
+        assert_eq!(escape_sequence("\x1b]8;;http://x\x1b\\link"), (15, None));
+        assert_eq!(escape_sequence("\x1b(Btext"), (2, None));
+        assert_eq!(escape_sequence("\x1b[31"), (4, None));
+        assert_eq!(escape_sequence("\x1b"), (1, None));
+    }
+
+    #[test]
+    fn test_apply_sgr() {
+        let mut style = PatternStyle::default();
+        apply_sgr(&mut style, "1;31;44");
+        assert_eq!(style, PatternStyle::new(Some(Color::Red), Some(Color::Blue), true));
+
+        apply_sgr(&mut style, "38;5;208;48;2;1;2;3");
+        assert_eq!(style.fg_color, Some(Color::Indexed(208)));
+        assert_eq!(style.bg_color, Some(Color::Rgb(1, 2, 3)));
+
+        // Out of range colors are ignored rather than wrapped around
+        apply_sgr(&mut style, "38;5;300;48;2;1;256;3");
+        assert_eq!(style.fg_color, Some(Color::Indexed(208)));
+        assert_eq!(style.bg_color, Some(Color::Rgb(1, 2, 3)));
+
+        apply_sgr(&mut style, "39;49;22;92");
+        assert_eq!(style, PatternStyle::new(Some(Color::LightGreen), None, false));
+
+        apply_sgr(&mut style, "");
+        assert_eq!(style, PatternStyle::default());
+    }
+
+    #[test]
+    fn test_sgr_params() {
+        let style = PatternStyle::new(Some(Color::LightRed), Some(Color::Blue), true);
+        assert_eq!(sgr_params(&style), "1;91;44");
+        assert_eq!(
+            sgr_params(&PatternStyle::new(
+                Some(Color::Indexed(208)),
+                Some(Color::Rgb(1, 2, 3)),
+                false
+            )),
+            "38;5;208;48;2;1;2;3"
+        );
+        assert_eq!(sgr_params(&PatternStyle::new(Some(Color::Reset), None, false)), "");
+
+        let mut parsed = PatternStyle::default();
+        apply_sgr(&mut parsed, &sgr_params(&style));
+        assert_eq!(parsed, style);
+    }
+}
@@ -1,44 +1,149 @@
+use crate::action::{ActionKind, LineAction};
+use crate::alert::{AlertFiring, Alerts, AnnounceCooldown};
+use crate::child_process::{ChildProcess, ChildStatus};
+use crate::clipboard::{self, CopyFormat, CopySource};
+use crate::correlation::Correlation;
+use crate::debug_stats::DebugStats;
+use crate::dedup::{RepeatMatch, RepeatedLines, RepeatedLinesRule};
+use crate::event_pairs::EventPairs;
 use crate::file_manager::FileFilterRule;
-use crate::filter::FilterRule;
+use crate::filter::{FieldPattern, FilterRule};
+use crate::filter_task::{BACKGROUND_FILTER_MIN_LINES, FilterMask, FilterMaskRule, FilterTask, filter_key};
+use crate::inspector::LineInspector;
+use crate::interval::Interval;
+use crate::level::{LevelDetector, LevelVisibilityRule, LogLevel, count_levels};
 use crate::list_view_state::ListViewState;
-use crate::marking::{Mark, MarkOnlyVisibilityRule, MarkTagRule};
+use crate::marking::{Mark, MarkOnlyVisibilityRule, MarkTagRule, group_marks_by_name};
+use crate::pager::StartupCommand;
+use crate::registers::{RegisterAction, RegisterPosition, Registers};
+use crate::report::{ReportFormat, ReportRow, format_report};
+use crate::restarts::Restarts;
+use crate::script::{Script, ScriptAnnotation, StreamingScript, encode_lines, parse_annotations};
+use crate::sticky_header::StickyHeader;
+use crate::stream_stats::StreamStats;
+use crate::watch::{WatchExpression, Watches};
 use crate::{
+    ansi::AnsiMode,
     cli::Cli,
     completion::CompletionEngine,
-    config::{Config, Filters},
-    event::{AppEvent, Event, EventHandler},
+    config::{Config, ConfigSnippet, EventConfig, Filters},
+    config_watch::{SectionChanges, describe_changes, spawn_config_watcher},
+    event::{AppEvent, Event, EventHandler, StreamSource},
     event_mark_view::{EventMarkView, EventOrMark},
-    expansion::Expansions,
+    expansion::{Expansions, FoldRecordsRule},
+    fields::{ColumnView, align_columns, detect_fields},
     file_manager::FileManager,
-    filter::{ActiveFilterMode, Filter, FilterPattern},
+    filter::{ActiveFilterMode, Filter, FilterPattern, unmatched_patterns},
+    format::{DETECTION_LINES, FormatChoice, LogFormat},
+    global_history::{DEFAULT_MAX_HISTORY, GlobalHistory, HistoryRecord, QueryKind, merge_history},
     help::Help,
-    highlighter::{Highlighter, PatternStyle},
+    hex::{HexDump, read_raw_line},
+    highlighter::{HighlightPattern, Highlighter, PatternStyle},
     keybindings::KeybindingRegistry,
     live_processor::ProcessingContext,
-    log::LogBuffer,
+    load_task::{BACKGROUND_LOAD_MIN_BYTES, FIRST_PAINT_BYTES, LoadTask, LoadedLines},
+    log::{FrozenDisplayRule, HiddenLinesRule, LogBuffer, LogLine, match_lines_by_content},
     log_event::{LogEvent, LogEventTracker},
     marking::Marking,
+    matcher::PatternMatcher,
+    minimap::{Minimap, MinimapCache, MinimapKey, MinimapTick},
+    notes::Notes,
     options::{AppOption, AppOptions},
-    persistence::{PersistedState, clear_all_state, load_state, save_state},
-    resolver::{Tag, ViewportResolver},
-    search::Search,
-    ui::colors::{FILTER_MODE_BG, FILTER_MODE_FG, SEARCH_MODE_BG, SEARCH_MODE_FG},
-    viewport::Viewport,
+    persistence::{PersistedState, autosave_state, clear_all_state, load_state, save_state},
+    pipe_task::{PipeTarget, PipeTask},
+    preview_task::{
+        BACKGROUND_PREVIEW_MIN_LINES, PreviewInput, PreviewRules, PreviewTask, SearchMatchRule, count_lines,
+    },
+    query::{Query, QueryResult},
+    quick_filter::{QuickFilter, QuickFilterKind, quick_filters},
+    regex_tester::RegexTester,
+    resolver::{Tag, ViewportResolver, VisibilityRule, VisibleLine},
+    search::{Search, SearchScope},
+    search_task::{BACKGROUND_SEARCH_MIN_LINES, SearchTask},
+    session::{Session, list_sessions, load_session, save_session},
+    shell::{editor_command, preferred_editor, shell_quote, spawn_command, suspend_job},
+    template::{TemplateCount, count_templates, line_template, noise_suggestions, template_regex},
+    theme::Theme,
+    timeline::{TIMELINE_MAX_BUCKETS, Timeline},
+    timestamp::{TimeTarget, TimestampParser},
+    ui::{NOTES_POPUP_HEIGHT, NOTES_POPUP_WIDTH, popup_area},
+    utils::{
+        CaseFolding, byte_to_column, column_slice, contains_ignore_case, display_width, word_at_column, wrap_columns,
+    },
+    viewport::{DEFAULT_PAGE_OVERLAP, Viewport, split_heights, split_widths},
 };
+use chrono::{DateTime, Utc};
 use crossterm::event::Event::Key;
 use ratatui::{
     Terminal,
     backend::Backend,
-    crossterm::event::{KeyCode, KeyEvent, KeyEventKind},
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+    layout::{Position, Rect},
+    style::Color,
 };
 use ratatui_explorer::FileExplorer;
 use regex::Regex;
-use std::collections::HashSet;
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::time::{Duration, Instant};
 use tracing::{debug, trace};
 use tui_input::{Input, InputRequest, backend::crossterm::EventHandler as TuiEventHandler};
 
+/// Time without changes after which the state is autosaved, so it survives a crash.
+const AUTOSAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Time without typing after which the lines a filter or search would give are counted, unless counted in the
+/// background.
+const PREVIEW_DELAY: Duration = Duration::from_millis(150);
+
+/// Time after which a message is closed automatically.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Interval between redraws while the progress of a background task is shown.
+const PROGRESS_REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Default minimum interval between processing streamed lines, so fast streams redraw at most once per interval.
+const DEFAULT_TICK_RATE: Duration = Duration::from_millis(33);
+
+/// Interval between redraws of the stream stats in the footer while streaming.
+const STREAM_STATS_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Time without received lines after which the footer shows the idle time of the stream in hours and minutes, so the
+/// stream stats are only redrawn once a minute.
+const STREAM_IDLE_SLOWDOWN: Duration = Duration::from_secs(3600);
+
+/// Maximum number of streamed lines appended per update, so bursts don't block input handling.
+const MAX_LINES_PER_UPDATE: usize = 50_000;
+
+/// Fraction of the maximum line count dropped beyond the excess once the buffer is full, so the remaining lines
+/// are re-indexed once per chunk rather than for every appended line.
+const DROP_CHUNK_DIVISOR: usize = 10;
+
+/// Time an alert is shown in the corner of the log view and highlights the title bar.
+pub const ALERT_TOAST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Messages with more lines than this are not closed automatically.
+const MESSAGE_AUTO_CLOSE_MAX_LINES: usize = 5;
+
+/// Number of lines to scroll a message overlay by when paging.
+const MESSAGE_PAGE_LINES: usize = 10;
+
+/// Number of bytes of a line shown in the log view if not configured. Longer lines are cut off so rendering and
+/// highlighting them stays fast.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 10_000;
+
+/// Minimum log view height for splitting it into two panes.
+const MIN_SPLIT_HEIGHT: usize = 7;
+
+/// Minimum log view width for pinning a pane beside it.
+const MIN_PIN_WIDTH: usize = 40;
+
 /// Represents the main views.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ViewState {
@@ -48,6 +153,8 @@ pub enum ViewState {
     ActiveSearchMode,
     /// Active goto line mode where the user can input a line number to jump to.
     GotoLineMode,
+    /// Active goto time mode where the user can input an absolute or relative time to jump to.
+    GotoTimeMode,
     /// Active filter mode where the user can input a filter pattern to filter log lines.
     ActiveFilterMode,
     /// View for managing existing filter patterns.
@@ -60,6 +167,58 @@ pub enum ViewState {
     MarksView,
     /// View for listing opened files in multi-file sessions.
     FilesView,
+    /// View for displaying the output of a shell command the log lines were piped through.
+    PipeOutputView,
+    /// View for listing the saved sessions.
+    SessionsView,
+    /// View for choosing an action to run on the selected line.
+    ActionsView,
+    /// View for choosing a script to run over the log lines.
+    ScriptsView,
+    /// View for displaying a time-bucketed histogram of events.
+    TimelineView,
+    /// View for editing the session notes.
+    NotesView,
+    /// View for choosing the minimum log level to show.
+    LevelsView,
+    /// View for showing, hiding and sorting the columns of the column view.
+    ColumnsView,
+    /// View for displaying the result table of a query.
+    QueryResultsView,
+    /// View for displaying the message patterns of the visible lines with their counts.
+    PatternsView,
+    /// View for suggesting exclude filters for the most frequent message patterns.
+    NoiseView,
+    /// View for picking a search or filter query from the global history.
+    HistoryView,
+    /// View for listing the alerts fired while streaming.
+    AlertsView,
+    /// View for listing the lines sharing an id with the selected line.
+    CorrelationView,
+    /// View for listing the registers to save the position in or jump to.
+    RegistersView,
+    /// View for listing the positions in the navigation history.
+    JumpHistoryView,
+    /// View for listing the runs of the application, split by the restart pattern.
+    RestartsView,
+    /// View for showing the selected line in full, wrapped over multiple rows.
+    FullLineView,
+    /// View for showing the bytes of the selected line in hex and ASCII.
+    HexView,
+    /// View for choosing the log format the lines are parsed with.
+    FormatView,
+    /// View listing restored filters that match no line, offering to disable or delete them.
+    StaleFiltersView,
+    /// View offering filters derived from the selected line, like its level or template.
+    QuickFilterView,
+    /// View for switching between the profiles of the config.
+    ProfilesView,
+    /// View for choosing the event type the event navigation jumps between.
+    EventScopeView,
+    /// View for inspecting the details of the selected line.
+    InspectorView,
+    /// View for choosing the format to copy lines in.
+    CopyFormatView,
     /// Visual selection mode for selecting a range of lines.
     SelectionMode,
 }
@@ -71,12 +230,28 @@ pub enum Overlay {
     EditFilter,
     /// Filter selection for events view.
     EventsFilter,
+    /// Active mode for typing text to filter the items of the events or marks list.
+    ListFilter,
     /// Active mode for entering a name/tag for a mark.
     MarkName,
     /// Active mode for entering a file name for saving the current log buffer to a file.
     SaveToFile,
+    /// Active mode for entering the name to save the session under.
+    SaveSession,
     /// Active mode for entering a custom event pattern.
     AddCustomEvent,
+    /// Active mode for entering a file name for exporting the filters as a config snippet.
+    ExportFilters,
+    /// Active mode for entering a file name for exporting the listed events and marks as CSV or JSON.
+    ExportReport,
+    /// Active mode for entering a shell command to pipe log lines through.
+    PipeCommand,
+    /// Active mode for entering a query over the fields of the visible lines.
+    Query,
+    /// Active mode for entering the pattern capturing the id to correlate lines by.
+    Correlate,
+    /// Regex sandbox for testing a pattern against the visible lines.
+    RegexTester,
     /// Active mode for entering a file path to add at runtime.
     AddFile,
     /// Display a message to the user.
@@ -90,21 +265,56 @@ pub enum Overlay {
 impl Overlay {
     pub fn popup_size(&self) -> Option<(u16, u16)> {
         match self {
-            Overlay::EditFilter | Overlay::MarkName | Overlay::SaveToFile | Overlay::AddCustomEvent => Some((60, 3)),
+            Overlay::EditFilter
+            | Overlay::MarkName
+            | Overlay::SaveToFile
+            | Overlay::SaveSession
+            | Overlay::AddCustomEvent
+            | Overlay::ExportFilters
+            | Overlay::ExportReport
+            | Overlay::PipeCommand => Some((60, 3)),
+            Overlay::Query | Overlay::Correlate => Some((80, 3)),
             Overlay::AddFile => Some((70, 20)),
+            Overlay::RegexTester => Some((118, 35)),
             Overlay::EventsFilter => Some((50, 25)),
-            Overlay::Message(_) | Overlay::Error(_) | Overlay::Fatal(_) => None,
+            // Typed into the title of the list
+            Overlay::ListFilter | Overlay::Message(_) | Overlay::Error(_) | Overlay::Fatal(_) => None,
         }
     }
 
     pub fn has_text_input(&self) -> bool {
         matches!(
             self,
-            Overlay::EditFilter | Overlay::MarkName | Overlay::SaveToFile | Overlay::AddCustomEvent
+            Overlay::EditFilter
+                | Overlay::ListFilter
+                | Overlay::MarkName
+                | Overlay::SaveToFile
+                | Overlay::SaveSession
+                | Overlay::AddCustomEvent
+                | Overlay::ExportFilters
+                | Overlay::ExportReport
+                | Overlay::PipeCommand
+                | Overlay::Query
+                | Overlay::Correlate
+                | Overlay::RegexTester
         )
     }
 }
 
+/// Field entered next in the add custom event popup, with the fields entered so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CustomEventStep {
+    #[default]
+    Pattern,
+    Name {
+        pattern: String,
+    },
+    Color {
+        pattern: String,
+        name: String,
+    },
+}
+
 /// Application.
 #[derive(Debug)]
 pub struct App {
@@ -112,6 +322,16 @@ pub struct App {
     pub running: bool,
     /// Application configuration.
     pub config: Config,
+    /// Config file that is reloaded when it changes: the one given on the command line or the one loaded.
+    config_path: Option<String>,
+    /// Profile of the config whose highlights, events and filters are used on top of the shared ones.
+    pub profile: Option<String>,
+    /// Filters file given on the command line, reloaded when it changes.
+    filters_path: Option<String>,
+    /// Filters of the config and filters file, replaced on reload without touching filters added at runtime.
+    configured_filters: Vec<FilterPattern>,
+    /// Colors of the UI.
+    pub theme: Theme,
     /// Current view being displayed.
     pub view_state: ViewState,
     /// Optional overlay on top of the view.
@@ -120,8 +340,15 @@ pub struct App {
     pub events: EventHandler,
     /// Log buffer containing the log lines.
     pub log_buffer: LogBuffer,
-    /// Viewport for displaying log lines.
+    /// Viewport for displaying log lines. In split view, this is the focused pane.
     pub viewport: Viewport,
+    /// Viewport of the unfocused pane in split view.
+    pub split_viewport: Option<Viewport>,
+    /// Whether the focused pane is the top pane in split view.
+    pub split_focus_top: bool,
+    /// Whether the unfocused pane is pinned to the right of the focused one, keeping its position to compare
+    /// against and scrolling horizontally with the focused pane.
+    pub split_pinned: bool,
     /// Help menu state.
     pub help: Help,
     /// Search state.
@@ -138,44 +365,297 @@ pub struct App {
     pub input: Input,
     /// Indicates whether streaming is paused (only relevant in stdin/streaming mode).
     pub streaming_paused: bool,
+    /// Whether the terminal is drawn to stderr, as in streaming mode, rather than stdout.
+    draws_to_stderr: bool,
+    /// Log index of the first line received since the display was frozen. Lines keep being buffered
+    /// but are only shown once the display is unfrozen.
+    pub display_frozen_at: Option<usize>,
+    /// Whether streamed lines are waiting to be processed on the next tick.
+    new_lines_pending: bool,
+    /// Throughput of the stream, shown in the footer.
+    pub stream_stats: StreamStats,
+    /// Time of the last screen draw.
+    last_draw: Instant,
+    /// Minimum interval between processing streamed lines.
+    tick_rate: Duration,
     /// Log event tracker for managing log events.
     pub event_tracker: LogEventTracker,
     /// Log line marking manager
     pub marking: Marking,
     /// Markings list state
     pub marking_list_state: ListViewState,
+    /// Line indices of the marks selected in the marks view for bulk operations.
+    pub selected_marks: HashSet<usize>,
+    /// Whether the marks view groups the marks by name.
+    pub marks_grouped: bool,
     /// Events list state
     pub events_list_state: ListViewState,
+    /// Line indices of events tagged in the events view.
+    pub tagged_events: HashSet<usize>,
+    /// Text the items of the events or marks list are filtered by.
+    pub list_query: String,
+    /// How case-insensitive matching folds the case of characters.
+    case_folding: CaseFolding,
+    /// Whether the lines of the listed items contain the list query, by line index, with the buffer generation and
+    /// the query they were checked for.
+    list_query_matches: RefCell<(u64, String, HashMap<usize, bool>)>,
+    /// Event type of the selected events view tab, or `None` for all events.
+    pub events_tab: Option<String>,
     /// Event filter list state
     pub event_filter_list_state: ListViewState,
     /// File manager for multi-file sessions
     pub file_manager: FileManager,
     /// Files list state
     pub files_list_state: ListViewState,
+    /// Name of the open session, saved on quit.
+    pub session_name: Option<String>,
+    /// Names of the saved sessions.
+    pub sessions: Vec<String>,
+    /// Sessions list state
+    pub sessions_list_state: ListViewState,
     /// Options list state
     pub options_list_state: ListViewState,
+    /// Search and filter queries shared by all sessions.
+    global_history: GlobalHistory,
+    /// History list state
+    pub history_list_state: ListViewState,
     /// Viewport resolver for determining visible lines
     pub resolver: ViewportResolver,
     /// Expansion state for showing otherwise filtered lines
     expansion: Expansions,
     /// Selection range for visual selection mode.
     selection_range: Option<(usize, usize)>,
+    /// Anchor and cursor column of a block selection, None if whole lines are selected.
+    selection_columns: Option<(usize, usize)>,
     /// Timestamp when a message was shown.
     message_timestamp: Option<std::time::Instant>,
+    /// Scroll offset of the message, error or fatal error overlay.
+    pub message_scroll: usize,
+    /// Largest scroll offset of the message overlay (updated when rendering).
+    pub message_max_scroll: Cell<usize>,
+    /// Area of the minimap of the focused viewport as last rendered, if shown.
+    pub minimap_area: Cell<Option<Rect>>,
+    /// Width of the longest line of the events or marks popup, with the buffer generation and the hash of the
+    /// listed lines it was measured for.
+    pub list_content_width: Cell<Option<((u64, u64), usize)>>,
+    /// Display width of the selected row of the focused pane as last rendered, shown in the footer.
+    pub selected_row_width: Cell<Option<usize>>,
+    /// Minimaps last built, which are only rebuilt when what they show changed.
+    minimap_cache: MinimapCache,
+    /// Areas of the event severity badges in the footer as last rendered.
+    pub severity_badge_areas: RefCell<Vec<(Rect, LogLevel)>>,
+    /// Whether mouse events are captured, which is only done while the minimap or severity badges are shown so
+    /// selecting text with the mouse keeps working otherwise.
+    mouse_captured: bool,
+    /// Status shown in the message overlay, e.g. after copying it to the clipboard.
+    pub message_status: Option<String>,
     /// Tab completion.
     completion: CompletionEngine,
     /// Keybinding registry for all keybindings.
     keybindings: KeybindingRegistry,
     /// Whether persistence is enabled.
     persist_enabled: bool,
+    /// Time the state is autosaved, once no further changes were made for [`AUTOSAVE_DELAY`].
+    autosave_due: Option<Instant>,
+    /// The state last autosaved, so an unchanged state isn't written again.
+    autosaved_state: Option<String>,
     /// Whether timestamp parsing is enabled.
     pub parse_timestamps: bool,
+    /// How ANSI escape sequences in log lines are handled.
+    ansi_mode: AnsiMode,
+    /// Maximum number of lines to keep when streaming. The oldest lines are dropped when exceeded.
+    max_lines: Option<usize>,
     /// Whether to only show marked lines
     pub show_marked_lines_only: bool,
     /// Compiled context capture regex for correlated line navigation.
     pub context_capture: Option<Regex>,
     /// File explorer for browsing the filesystem when adding a file.
     pub file_explorer: Option<FileExplorer>,
+    /// Shell command the log lines were last piped through.
+    pub pipe_command: String,
+    /// Output lines of the last piped shell command.
+    pub pipe_output: Vec<String>,
+    /// Pipe output list state
+    pub pipe_output_list_state: ListViewState,
+    /// Command the lines are piped through in the background.
+    pub pipe_task: Option<PipeTask>,
+    /// Number of bytes of a line shown in the log view.
+    pub max_line_length: usize,
+    /// Rows of the wrapped line shown in full in the full line view.
+    pub full_line_rows: Vec<String>,
+    /// Full line view list state
+    pub full_line_list_state: ListViewState,
+    /// Bytes of the line shown in the hex view.
+    pub hex_dump: HexDump,
+    /// Whether the hex view shows the line as stored in its file, rather than as shown in the log view.
+    pub hex_dump_raw: bool,
+    /// Hex view list state
+    pub hex_list_state: ListViewState,
+    /// How the log format is chosen.
+    pub format_choice: FormatChoice,
+    /// Format detected from the first lines of the log, used when the format is chosen automatically.
+    pub detected_format: Option<LogFormat>,
+    /// Whether the format of the stream is detected once enough lines arrived.
+    format_detection_pending: bool,
+    /// Format view list state
+    pub format_list_state: ListViewState,
+    /// Indices of the restored filters matching no line, listed in the stale filters view.
+    pub stale_filters: Vec<usize>,
+    /// Stale filters view list state
+    pub stale_filters_list_state: ListViewState,
+    /// Filters derived from the selected line, listed in the quick filter view.
+    pub quick_filters: Vec<QuickFilter>,
+    /// Whether the quick filter view adds exclude filters instead of include filters.
+    pub quick_filter_mode: ActiveFilterMode,
+    /// Quick filter view list state
+    pub quick_filter_list_state: ListViewState,
+    /// Profiles view list state, listing using no profile before the profiles.
+    pub profiles_list_state: ListViewState,
+    /// Last query run over the visible lines.
+    pub query: String,
+    /// Result table of the last query.
+    pub query_result: QueryResult,
+    /// Result table of the last query formatted as aligned lines, headers first.
+    pub query_table: Vec<String>,
+    /// Query results list state
+    pub query_results_list_state: ListViewState,
+    /// Message patterns of the visible lines, most frequent first.
+    pub templates: Vec<TemplateCount>,
+    /// Patterns view list state
+    pub templates_list_state: ListViewState,
+    /// Most frequent message patterns of the visible lines, suggested as exclude filters.
+    pub noise_suggestions: Vec<TemplateCount>,
+    /// Number of lines the noise suggestions were counted from.
+    pub noise_line_count: usize,
+    /// Noise view list state
+    pub noise_list_state: ListViewState,
+    /// Actions on lines from the config.
+    pub actions: Vec<LineAction>,
+    /// Actions that apply to the selected line as (index into `actions`, expanded template).
+    pub action_choices: Vec<(usize, String)>,
+    /// Actions list state
+    pub actions_list_state: ListViewState,
+    /// Scripts from the config.
+    pub scripts: Vec<Script>,
+    /// Scripts list state
+    pub scripts_list_state: ListViewState,
+    /// Scripts running alongside the stream.
+    streaming_scripts: Vec<StreamingScript>,
+    /// Added to the line indices sent to streaming scripts, so annotations for lines dropped or cleared since can
+    /// be told apart.
+    script_line_offset: usize,
+    /// Fields computed by scripts per log line index, shown in the line inspector.
+    pub script_fields: HashMap<usize, Vec<(String, String)>>,
+    /// Patterns highlighted by scripts, so repeated annotations don't add them again.
+    script_highlights: HashSet<String>,
+    /// Details of the line shown in the line inspector.
+    pub line_inspector: LineInspector,
+    /// Line inspector list state
+    pub inspector_list_state: ListViewState,
+    /// Lines to copy once a format is chosen in the copy format popup.
+    pub copy_source: CopySource,
+    /// Copy format list state
+    pub copy_format_list_state: ListViewState,
+    /// Regex sandbox state.
+    pub regex_tester: RegexTester,
+    /// Regex tester results list state
+    pub regex_tester_list_state: ListViewState,
+    /// Lines below this level are hidden.
+    pub min_level: Option<LogLevel>,
+    /// Number of lines per level, updated when the levels view is opened.
+    pub level_counts: [usize; 6],
+    /// Levels view list state
+    pub levels_list_state: ListViewState,
+    /// Lines parsed into fields and shown as aligned columns.
+    pub column_view: ColumnView,
+    /// Columns view list state
+    pub columns_list_state: ListViewState,
+    /// Numeric values extracted from streamed lines.
+    pub watches: Watches,
+    /// Whether the watch sparklines are shown.
+    pub show_watches: bool,
+    /// Finds the line shown in the sticky header above the log view.
+    pub sticky_header: StickyHeader,
+    /// Runs of repeated lines, collapsed into their first line when enabled in the options.
+    pub repeated_lines: RepeatedLines,
+    /// Lines where the application writing the log was restarted.
+    pub restarts: Restarts,
+    /// Durations between paired events, shown in the events view.
+    pub event_pairs: EventPairs,
+    /// Restarts view list state
+    pub restarts_list_state: ListViewState,
+    /// Log line index scrolled to horizontally for a search match, with the horizontal offset it was scrolled to.
+    pub search_scroll: Option<(usize, usize)>,
+    /// Timings shown in the debug overlay.
+    pub debug_stats: DebugStats,
+    /// Whether the debug overlay is available, which it is when debug logging is enabled with `--debug`.
+    pub debug_mode: bool,
+    /// Whether the debug overlay is shown.
+    pub show_debug_overlay: bool,
+    /// Commands given on the command line like `+G`, run once the lines are loaded.
+    pub startup_commands: Vec<StartupCommand>,
+    /// Alert rules and the alerts they fired while streaming.
+    pub alerts: Alerts,
+    /// Alerts view list state
+    pub alerts_list_state: ListViewState,
+    /// Pattern the lines were last correlated by, starting with the context capture pattern.
+    correlation_pattern: String,
+    /// Lines sharing an id with the line they were correlated from.
+    pub correlation: Option<Correlation>,
+    /// Correlation view list state
+    pub correlation_list_state: ListViewState,
+    /// Positions saved under a letter.
+    pub registers: Registers,
+    /// Whether the registers view saves the position or jumps to it.
+    pub register_action: RegisterAction,
+    /// Registers view list state
+    pub registers_list_state: ListViewState,
+    /// Navigation history view list state, listing the most recent position first
+    pub jump_history_list_state: ListViewState,
+    /// Message of the most recently fired alert and when it fired, shown until [`ALERT_TOAST_TIMEOUT`].
+    pub alert_toast: Option<(String, Instant)>,
+    /// Limits how often alerts and events ring the bell or send desktop notifications.
+    announce_cooldown: AnnounceCooldown,
+    /// Histogram of events over time.
+    pub timeline: Option<Timeline>,
+    /// Timeline bucket selection state
+    pub timeline_list_state: ListViewState,
+    /// Free-form notes for the session.
+    pub notes: Notes,
+    /// File path and line number to open in the external editor on the next loop iteration.
+    pending_editor: Option<(String, usize)>,
+    /// Whether to suspend to the shell on the next loop iteration.
+    pending_suspend: bool,
+    /// Event type the event navigation jumps between, or any event if None.
+    pub event_scope: Option<String>,
+    /// Event scope list state
+    pub event_scope_list_state: ListViewState,
+    /// Step of adding a custom event in the add custom event popup.
+    pub custom_event_step: CustomEventStep,
+    /// Command spawned with `lazylog -- cmd`, whose output is streamed into the log buffer.
+    pub child_process: Option<ChildProcess>,
+    /// Filter running in the background for large buffers.
+    pub filter_task: Option<FilterTask>,
+    /// Result of the last completed background filter run.
+    filter_mask: Option<FilterMask>,
+    /// Whether the enabled filters hide all lines the other visibility rules show.
+    filters_hide_all: bool,
+    /// Search running in the background for large buffers.
+    pub search_task: Option<SearchTask>,
+    /// Generation of the last background search, used to ignore results of replaced searches.
+    search_generation: usize,
+    /// Whether the result of a submitted search is still to be reported, once its matches are found.
+    search_result_pending: bool,
+    /// Filter or search being typed, with the number of lines it would give once counted.
+    preview: Option<(PreviewInput, Option<usize>)>,
+    /// Count of the lines for the preview running in the background for large buffers.
+    pub preview_task: Option<PreviewTask>,
+    /// Generation of the last background preview count, used to ignore results of replaced counts.
+    preview_generation: usize,
+    /// Time the preview is counted, once no further keys were typed for [`PREVIEW_DELAY`].
+    preview_due: Option<Instant>,
+    /// Loading of a large file in the background, while the start of the file is shown.
+    pub load_task: Option<LoadTask>,
 }
 
 impl App {
@@ -183,7 +663,10 @@ impl App {
     fn is_input_view(&self) -> bool {
         matches!(
             self.view_state,
-            ViewState::ActiveSearchMode | ViewState::ActiveFilterMode | ViewState::GotoLineMode
+            ViewState::ActiveSearchMode
+                | ViewState::ActiveFilterMode
+                | ViewState::GotoLineMode
+                | ViewState::GotoTimeMode
         )
     }
 
@@ -192,14 +675,22 @@ impl App {
         matches!(
             self.overlay,
             Some(Overlay::EditFilter)
+                | Some(Overlay::ListFilter)
                 | Some(Overlay::MarkName)
                 | Some(Overlay::SaveToFile)
+                | Some(Overlay::SaveSession)
                 | Some(Overlay::AddCustomEvent)
+                | Some(Overlay::ExportFilters)
+                | Some(Overlay::ExportReport)
+                | Some(Overlay::PipeCommand)
+                | Some(Overlay::Query)
+                | Some(Overlay::Correlate)
+                | Some(Overlay::RegexTester)
         )
     }
 
     /// Constructs a new instance of [`App`].
-    pub fn new(args: Cli) -> Self {
+    pub fn new(mut args: Cli) -> Self {
         let initial_overlay = if args.clear_state {
             match clear_all_state() {
                 Ok(msg) => Some(Overlay::Message(msg)),
@@ -209,11 +700,17 @@ impl App {
             None
         };
 
-        let use_stdin = args.should_use_stdin();
+        let stream_source = if args.should_run_command() {
+            StreamSource::Command
+        } else if args.should_use_stdin() {
+            StreamSource::Stdin
+        } else {
+            StreamSource::None
+        };
 
-        let events = EventHandler::new(use_stdin);
+        let events = EventHandler::new(stream_source, std::mem::take(&mut args.stdin_lines));
 
-        let (config, initial_overlay) = match Config::load(&args.config) {
+        let (mut config, initial_overlay) = match Config::load(&args.config) {
             Ok(config) => (config, initial_overlay),
             Err(err) => {
                 let overlay = initial_overlay.or(Some(Overlay::Message(err)));
@@ -221,81 +718,337 @@ impl App {
             }
         };
         debug!("Loaded config {:?}", config.get_path());
+        let mut profile = args.profile.clone();
+        let initial_overlay = match profile.as_deref().map(|name| config.apply_profile(name)) {
+            Some(Err(err)) => {
+                profile = None;
+                initial_overlay.or(Some(Overlay::Message(err)))
+            }
+            _ => initial_overlay,
+        };
 
+        let mut config_issues = config.validate();
         let mut filter_patterns = config.parse_filter_patterns();
         if let Some(filters_file) = Filters::load(&args.filters) {
+            config_issues.extend(filters_file.validate());
             filter_patterns.extend(filters_file.parse_filter_patterns());
         }
+        let configured_filters = filter_patterns.clone();
+        let config_path = args.config.clone().or_else(|| config.get_path().cloned());
+        let (arg_patterns, arg_issues) = args.parse_filter_args();
+        filter_patterns.extend(arg_patterns);
+        config_issues.extend(arg_issues);
+
+        let initial_overlay = if config_issues.is_empty() {
+            initial_overlay
+        } else {
+            debug!("Configuration issues: {:?}", config_issues);
+            initial_overlay.or(Some(Overlay::Message(format!(
+                "Warning: Some configuration entries were ignored:\n\n{}",
+                config_issues.join("\n")
+            ))))
+        };
+
+        let mut session_name = args.session.clone();
+        let (session, initial_overlay) = match session_name.as_deref().map(load_session) {
+            Some(Ok(session)) => (session, initial_overlay),
+            Some(Err(err)) => {
+                session_name = None;
+                (None, initial_overlay.or(Some(Overlay::Message(err))))
+            }
+            None => (None, initial_overlay),
+        };
+        let file_paths = match &session {
+            Some(session) => session.files().to_vec(),
+            None => args.files.clone(),
+        };
+        let initial_overlay = if session.is_some() && !args.files.is_empty() {
+            initial_overlay.or(Some(Overlay::Message(
+                "Warning: Opened the files of the session instead of the given files".to_string(),
+            )))
+        } else {
+            initial_overlay
+        };
 
         let keybindings = KeybindingRegistry::new();
         let mut help = Help::new();
         help.build_from_registry(&keybindings);
 
-        let filter = Filter::with_patterns(filter_patterns);
+        let case_folding = config.case_folding();
+        let filter = Filter::with_patterns(filter_patterns).with_case_folding(case_folding);
         let filter_count = filter.count();
 
         let highlight_patterns = config.parse_highlight_patterns();
         let highlight_events = config.parse_highlight_event_patterns();
-        let highlighter = Highlighter::new(highlight_patterns, highlight_events);
+        let highlighter = Highlighter::new(highlight_patterns, highlight_events).with_case_folding(case_folding);
 
         let event_patterns = config.parse_log_event_patterns();
-        let event_tracker = LogEventTracker::new(event_patterns);
+        let event_tracker = LogEventTracker::new(event_patterns).with_case_folding(case_folding);
 
+        let theme = config.parse_theme();
+        let column_view = ColumnView::new(config.parse_field_extractor());
+        let format_choice = config.parse_format_choice();
+        let column_count = column_view.columns().len();
         let context_capture = config.parse_context_capture();
+        let correlation_pattern = context_capture
+            .as_ref()
+            .map(|regex| regex.as_str().to_string())
+            .unwrap_or_default();
+        let watches = Watches::new(config.parse_watches());
+        let alerts = Alerts::new(config.parse_alerts());
+        let actions = config.parse_actions();
+        let scripts = config.parse_scripts();
+        let sticky_header = StickyHeader::new(config.parse_section_pattern());
+        let restarts = Restarts::new(config.parse_restart_pattern());
+        let event_pairs = EventPairs::new(config.parse_event_pairs());
+        let max_line_length = config.max_line_length.unwrap_or(DEFAULT_MAX_LINE_LENGTH);
+        let global_history = if args.no_persist {
+            GlobalHistory::default()
+        } else {
+            GlobalHistory::load(config.max_history.unwrap_or(DEFAULT_MAX_HISTORY))
+        };
         let disable_timestamps = config.disable_timestamp_parsing.unwrap_or(false);
         let no_timestamps = args.no_timestamps;
         let parse_timestamps = if no_timestamps { false } else { !disable_timestamps };
+        let max_lines = args.max_lines.or(config.max_lines).filter(|&max| max > 0);
+        let tick_rate = config
+            .tick_rate_ms
+            .filter(|&ms| ms > 0)
+            .map_or(DEFAULT_TICK_RATE, Duration::from_millis);
+        let ansi_mode = if args.strip_ansi {
+            AnsiMode::Strip
+        } else {
+            config.ansi.unwrap_or_default()
+        };
 
         let mut app = Self {
             running: true,
             config,
+            config_path,
+            profile,
+            filters_path: args.filters.clone(),
+            configured_filters,
+            theme,
             help,
             view_state: ViewState::LogView,
             overlay: initial_overlay,
             events,
             log_buffer: LogBuffer::default(),
             viewport: Viewport::default(),
+            split_viewport: None,
+            split_focus_top: true,
+            split_pinned: false,
             input: Input::default(),
-            search: Search::default(),
+            search: Search::default().with_case_folding(case_folding),
             filter,
             filter_list_state: ListViewState::new_with_count(filter_count),
             options: AppOptions::default(),
             highlighter,
             streaming_paused: false,
+            draws_to_stderr: stream_source != StreamSource::None,
+            display_frozen_at: None,
+            new_lines_pending: false,
+            stream_stats: StreamStats::default(),
+            last_draw: Instant::now(),
+            tick_rate,
             event_tracker,
             marking: Marking::default(),
             marking_list_state: ListViewState::new(),
+            selected_marks: HashSet::new(),
+            marks_grouped: false,
             events_list_state: ListViewState::new(),
+            tagged_events: HashSet::new(),
+            list_query: String::new(),
+            case_folding,
+            list_query_matches: RefCell::new((0, String::new(), HashMap::new())),
+            events_tab: None,
             event_filter_list_state: ListViewState::new(),
-            file_manager: FileManager::new(&args.files),
+            file_manager: FileManager::new(&file_paths),
             files_list_state: ListViewState::new(),
+            session_name,
+            sessions: Vec::new(),
+            sessions_list_state: ListViewState::new(),
             options_list_state: ListViewState::new(),
+            global_history,
+            history_list_state: ListViewState::new(),
             resolver: ViewportResolver::new(),
             expansion: Expansions::new(),
             selection_range: None,
+            selection_columns: None,
             message_timestamp: None,
+            message_scroll: 0,
+            message_max_scroll: Cell::new(0),
+            minimap_area: Cell::new(None),
+            minimap_cache: MinimapCache::default(),
+            selected_row_width: Cell::new(None),
+            list_content_width: Cell::new(None),
+            severity_badge_areas: RefCell::new(Vec::new()),
+            mouse_captured: false,
+            message_status: None,
             completion: CompletionEngine::default(),
             keybindings,
             persist_enabled: !args.no_persist,
+            autosave_due: None,
+            autosaved_state: None,
             parse_timestamps,
+            ansi_mode,
+            max_lines,
             show_marked_lines_only: false,
             context_capture,
             file_explorer: None,
+            pipe_command: String::new(),
+            pipe_output: Vec::new(),
+            pipe_output_list_state: ListViewState::new(),
+            pipe_task: None,
+            max_line_length,
+            full_line_rows: Vec::new(),
+            full_line_list_state: ListViewState::new(),
+            hex_dump: HexDump::default(),
+            hex_dump_raw: false,
+            hex_list_state: ListViewState::new(),
+            format_choice,
+            detected_format: None,
+            format_detection_pending: false,
+            format_list_state: ListViewState::new_with_count(FormatChoice::all().len()),
+            stale_filters: Vec::new(),
+            stale_filters_list_state: ListViewState::new(),
+            quick_filters: Vec::new(),
+            quick_filter_mode: ActiveFilterMode::Include,
+            quick_filter_list_state: ListViewState::new(),
+            profiles_list_state: ListViewState::new(),
+            query: String::new(),
+            query_result: QueryResult::default(),
+            query_table: Vec::new(),
+            query_results_list_state: ListViewState::new(),
+            templates: Vec::new(),
+            templates_list_state: ListViewState::new(),
+            noise_suggestions: Vec::new(),
+            noise_line_count: 0,
+            noise_list_state: ListViewState::new(),
+            actions,
+            action_choices: Vec::new(),
+            actions_list_state: ListViewState::new(),
+            scripts,
+            scripts_list_state: ListViewState::new(),
+            streaming_scripts: Vec::new(),
+            script_line_offset: 0,
+            script_fields: HashMap::new(),
+            script_highlights: HashSet::new(),
+            line_inspector: LineInspector::default(),
+            inspector_list_state: ListViewState::new(),
+            copy_source: CopySource::default(),
+            copy_format_list_state: ListViewState::new(),
+            regex_tester: RegexTester::new(),
+            regex_tester_list_state: ListViewState::new(),
+            min_level: args.level,
+            level_counts: [0; 6],
+            levels_list_state: ListViewState::new_with_count(LogLevel::ALL.len() + 1),
+            column_view,
+            columns_list_state: ListViewState::new_with_count(column_count),
+            watches,
+            show_watches: false,
+            sticky_header,
+            repeated_lines: RepeatedLines::default(),
+            restarts,
+            event_pairs,
+            restarts_list_state: ListViewState::new(),
+            search_scroll: None,
+            debug_stats: DebugStats::default(),
+            debug_mode: args.debug.is_some(),
+            show_debug_overlay: false,
+            startup_commands: args.startup_commands.clone(),
+            alerts,
+            alerts_list_state: ListViewState::new(),
+            correlation_pattern,
+            correlation: None,
+            correlation_list_state: ListViewState::new(),
+            registers: Registers::default(),
+            register_action: RegisterAction::default(),
+            registers_list_state: ListViewState::new(),
+            jump_history_list_state: ListViewState::new(),
+            alert_toast: None,
+            announce_cooldown: AnnounceCooldown::default(),
+            timeline: None,
+            timeline_list_state: ListViewState::new(),
+            notes: Notes::new(),
+            pending_editor: None,
+            pending_suspend: false,
+            event_scope: None,
+            event_scope_list_state: ListViewState::new(),
+            custom_event_step: CustomEventStep::default(),
+            child_process: args
+                .should_run_command()
+                .then(|| ChildProcess::new(args.command.clone())),
+            filter_task: None,
+            filter_mask: None,
+            filters_hide_all: false,
+            search_task: None,
+            search_generation: 0,
+            search_result_pending: false,
+            preview: None,
+            preview_task: None,
+            preview_generation: 0,
+            preview_due: None,
+            load_task: None,
         };
 
+        let watched_paths = app
+            .config_path
+            .iter()
+            .chain(&app.filters_path)
+            .map(PathBuf::from)
+            .collect();
+        spawn_config_watcher(watched_paths, app.events.app_sender());
+
         // Set item counts for list states
         app.files_list_state.set_item_count(app.file_manager.count());
         app.options_list_state.set_item_count(app.options.count());
+        if app.format_choice == FormatChoice::Auto {
+            if stream_source != StreamSource::None {
+                app.format_detection_pending = true;
+            } else if let Some(path) = app.file_manager.paths().first() {
+                app.detected_format = LogFormat::detect_in_file(path);
+            }
+        }
+        app.log_buffer = app.new_log_buffer();
+        app.search.history.restore(app.global_history.search_history());
+        app.filter.history.restore(app.global_history.filter_history());
 
-        if use_stdin {
+        if stream_source != StreamSource::None {
             app.log_buffer.init_stdin_mode();
-            app.viewport.follow_mode = true;
+            // A pager starts at the top, the startup commands run once stdin ended or the command exited
+            app.viewport.follow_mode = args.follow || !args.is_pager();
             app.update_processor_context();
             app.update_view();
+            app.start_child_process();
+            app.start_streaming_scripts();
+            return app;
+        }
+
+        if app.file_manager.is_empty() {
             return app;
         }
 
-        if !use_stdin && app.file_manager.is_empty() {
+        // A large file is shown from its start while the rest is loaded. Sessions are restored once fully loaded.
+        let paths = app.file_manager.paths();
+        if session.is_none()
+            && let [path] = paths.as_slice()
+            && std::fs::metadata(path).is_ok_and(|metadata| metadata.len() >= BACKGROUND_LOAD_MIN_BYTES)
+        {
+            let path = path.to_string();
+            match app
+                .log_buffer
+                .load_file_prefix(&path, FIRST_PAINT_BYTES, parse_timestamps)
+            {
+                Ok(()) => {
+                    app.start_background_load(path);
+                    app.update_format_columns();
+                    app.update_view();
+                    app.event_tracker.scan_all_lines(&app.log_buffer);
+                    app.update_events_view_count();
+                }
+                Err(e) => app.show_fatal(format!("Failed to load file(s): {}\nError: {}", path, e).as_str()),
+            }
             return app;
         }
 
@@ -303,10 +1056,13 @@ impl App {
 
         match load_result {
             Ok(skipped_lines) => {
+                app.update_format_columns();
                 app.update_view();
                 app.update_completion_words();
 
-                if app.persist_enabled
+                if let Some(session) = session {
+                    app.restore_session(session);
+                } else if app.persist_enabled
                     && let Some(state) = load_state(&app.file_manager.paths())
                 {
                     app.restore_state(state);
@@ -314,6 +1070,7 @@ impl App {
 
                 app.event_tracker.scan_all_lines(&app.log_buffer);
                 app.update_events_view_count();
+                app.run_startup_commands();
 
                 if skipped_lines > 0 {
                     app.show_message(format!(
@@ -323,33 +1080,180 @@ impl App {
                 }
             }
             Err(e) => {
-                app.show_fatal(format!("Failed to load file(s): {}\nError: {}", args.files.join(", "), e).as_str())
+                app.show_fatal(format!("Failed to load file(s): {}\nError: {}", file_paths.join(", "), e).as_str())
             }
         }
 
         app
     }
 
+    /// Loads all lines of the file in the background, replacing the start of the file shown meanwhile.
+    fn start_background_load(&mut self, path: String) {
+        let log_buffer = self.new_log_buffer();
+        let on_complete = self.events.app_sender();
+        self.load_task = Some(LoadTask::spawn(
+            path,
+            log_buffer,
+            self.parse_timestamps,
+            move |loaded| on_complete(AppEvent::LoadComplete(loaded)),
+        ));
+    }
+
+    /// Takes over the lines loaded in the background. Filters, searches and marks made on the start of the file
+    /// meanwhile are kept and applied to all lines, and the state saved for the file is restored.
+    fn finish_background_load(&mut self, loaded: LoadedLines) {
+        let lines = match loaded.lines {
+            Ok(lines) => lines,
+            Err(e) => {
+                self.show_error(&format!("Failed to load the rest of the file: {}", e));
+                return;
+            }
+        };
+        debug!("Loaded {} lines in the background in {:?}", lines.len(), loaded.elapsed);
+        self.log_buffer.finish_loading(lines);
+        // The start of the file was scanned for events already
+        self.filter_mask = None;
+        self.update_view();
+        self.update_completion_words();
+
+        if self.persist_enabled
+            && let Some(state) = load_state(&self.file_manager.paths())
+        {
+            self.restore_state(state);
+        }
+
+        self.event_tracker.update(&self.log_buffer);
+        self.update_events_view_count();
+        self.run_startup_commands();
+        self.report_search_result();
+    }
+
     fn update_view(&mut self) {
         let update_start = Instant::now();
 
+        let patterns = Arc::new(self.filter.get_filter_patterns().to_vec());
+        let marked_indices = Arc::new(self.marking.get_marked_indices());
+        let always_visible = self.always_visible_lines(&marked_indices);
+
+        let filter_rule = FilterRule::new(patterns.clone(), Arc::new(always_visible.clone()))
+            .with_extractor(self.column_view.extractor())
+            .with_file_names(self.file_manager.file_names())
+            .with_case_folding(self.case_folding);
+        let filter_in_background = self.should_filter_in_background(&patterns);
+        let filter_rule: Box<dyn VisibilityRule> = if filter_in_background {
+            let key = filter_key(&patterns, &always_visible, self.log_buffer.get_total_lines_count());
+            match &self.filter_mask {
+                Some(mask) if mask.key == key => {
+                    self.filter_task = None;
+                    Box::new(FilterMaskRule::new(mask.visible.clone()))
+                }
+                _ => {
+                    if self.filter_task.as_ref().is_none_or(|task| task.key() != key) {
+                        let on_complete = self.events.app_sender();
+                        self.filter_task = Some(FilterTask::spawn(
+                            key,
+                            self.log_buffer.shared_lines(),
+                            filter_rule,
+                            move |mask| on_complete(AppEvent::FilterComplete(mask)),
+                        ));
+                    }
+                    // Keep showing the current view until the background filter completes
+                    return;
+                }
+            }
+        } else {
+            self.filter_task = None;
+            Box::new(filter_rule)
+        };
+
         let all_lines = self.log_buffer.all_lines();
         let log_line_index = self.resolver.viewport_to_log(self.viewport.selected_line, all_lines);
+        let split_log_line_index = self
+            .split_viewport
+            .as_ref()
+            .and_then(|split| self.resolver.viewport_to_log(split.selected_line, all_lines));
 
         self.resolver.clear_rules();
 
-        if self.file_manager.is_multi_file() {
-            let enabled_ids = self.file_manager.enabled_file_ids();
-            self.resolver
-                .add_visibility_rule(Box::new(FileFilterRule::new(Arc::new(enabled_ids))));
+        self.restarts.update(all_lines, self.log_buffer.base_generation());
+        for rule in self.base_visibility_rules(&marked_indices) {
+            self.resolver.add_visibility_rule(rule);
+        }
+        self.resolver.add_visibility_rule(filter_rule);
+
+        // Repeats are found among the lines shown by the other rules, so a repeat is never hidden with the line
+        // starting its run
+        match self.repeat_match() {
+            Some(repeat_match) => {
+                let visible_lines = self.resolver.get_visible_lines(all_lines);
+                self.repeated_lines.update(
+                    all_lines,
+                    self.log_buffer.base_generation(),
+                    repeat_match,
+                    visible_lines.iter().map(|vl| vl.log_index),
+                );
+                self.resolver
+                    .add_visibility_rule(Box::new(RepeatedLinesRule::new(self.repeated_lines.repeats())));
+            }
+            None => self.repeated_lines.clear(),
         }
 
-        let patterns = Arc::new(self.filter.get_filter_patterns().to_vec());
+        self.resolver
+            .add_tag_rule(Box::new(MarkTagRule::new(marked_indices.clone())));
+
+        if let Some(sort_rule) = self.column_view.sort_rule() {
+            self.resolver.set_sort_rule(Box::new(sort_rule));
+        }
+
+        self.resolver.set_expanded_lines(self.expansion.get_all_expanded());
+
+        let filter_start = Instant::now();
+        let num_lines = self.resolver.get_visible_lines(all_lines).len();
+        // Background filters report their own time when they complete
+        if !filter_in_background {
+            self.debug_stats.filter_time = Some(filter_start.elapsed());
+        }
+        // Only the filters are blamed for an empty view, not e.g. the minimum level
+        self.filters_hide_all = num_lines == 0 && self.filter.enabled_count() > 0 && {
+            let base_rules = self.base_visibility_rules(&marked_indices);
+            all_lines
+                .iter()
+                .any(|line| base_rules.iter().all(|rule| rule.is_visible(line)))
+        };
+        self.update_search_matches();
+
+        self.viewport.set_total_lines(num_lines);
+        if let Some(split) = &mut self.split_viewport {
+            split.set_total_lines(num_lines);
+        }
+
+        // Call after the all_lines scope ends
+        self.update_events_view_count();
+
+        if self.log_buffer.streaming {
+            self.update_processor_context();
+        }
+
+        let all_lines = self.log_buffer.all_lines();
+        let streaming = self.log_buffer.streaming;
+        restore_selection(
+            &mut self.resolver,
+            all_lines,
+            &mut self.viewport,
+            log_line_index,
+            streaming,
+        );
+        if let Some(split) = &mut self.split_viewport {
+            restore_selection(&mut self.resolver, all_lines, split, split_log_line_index, streaming);
+        }
+        trace!("update_view took: {:?}", update_start.elapsed());
+    }
 
+    /// Returns the log indices of the lines shown regardless of the text filters.
+    fn always_visible_lines(&self, marked_indices: &HashSet<usize>) -> HashSet<usize> {
         let mut always_visible = HashSet::new();
-        let marked_indices = self.marking.get_marked_indices();
         if self.options.is_enabled(AppOption::AlwaysShowMarkedLines) {
-            always_visible.extend(&marked_indices);
+            always_visible.extend(marked_indices);
         }
         if self.options.is_enabled(AppOption::AlwaysShowCriticalEvents) {
             always_visible.extend(self.event_tracker.get_critical_event_indices());
@@ -357,80 +1261,258 @@ impl App {
         if self.options.is_enabled(AppOption::AlwaysShowCustomEvents) {
             always_visible.extend(self.event_tracker.get_custom_event_indices());
         }
+        if self.search.reveals_hidden_matches() {
+            let all_lines = self.log_buffer.all_lines();
+            always_visible.extend(
+                self.search
+                    .matching_line_indices(all_lines.iter().map(|line| (line.index, line.content()))),
+            );
+        }
+        always_visible
+    }
 
-        self.resolver
-            .add_visibility_rule(Box::new(FilterRule::new(patterns, Arc::new(always_visible))));
-
-        let marked_indices = Arc::new(marked_indices);
+    /// Returns how repeated lines are found, or None if they are not collapsed.
+    fn repeat_match(&self) -> Option<RepeatMatch> {
+        if self.options.is_enabled(AppOption::CollapseSimilarRepeatedLines) {
+            Some(RepeatMatch::Template)
+        } else if self.options.is_enabled(AppOption::CollapseRepeatedLines) {
+            Some(RepeatMatch::Identical)
+        } else {
+            None
+        }
+    }
 
+    /// Returns the visibility rules other than the text filters and repeated lines: enabled files, marked lines
+    /// only, minimum level, folded records, hidden lines and lines received while the display is frozen.
+    fn base_visibility_rules(&self, marked_indices: &Arc<HashSet<usize>>) -> PreviewRules {
+        let mut rules: PreviewRules = Vec::new();
+        if self.file_manager.is_multi_file() {
+            let enabled_ids = self.file_manager.enabled_file_ids();
+            rules.push(Box::new(FileFilterRule::new(Arc::new(enabled_ids))));
+        }
         if self.show_marked_lines_only {
-            self.resolver
-                .add_visibility_rule(Box::new(MarkOnlyVisibilityRule::new(marked_indices.clone())));
+            rules.push(Box::new(MarkOnlyVisibilityRule::new(marked_indices.clone())));
+        }
+        if let Some(min_level) = self.min_level {
+            rules.push(Box::new(LevelVisibilityRule::new(min_level)));
         }
+        if self.options.is_enabled(AppOption::FoldMultiLineRecords) {
+            rules.push(Box::new(FoldRecordsRule));
+        }
+        if self.log_buffer.hidden_count() > 0 {
+            rules.push(Box::new(HiddenLinesRule::new(self.log_buffer.hidden_lines())));
+        }
+        if let Some(first_new_line) = self.display_frozen_at {
+            rules.push(Box::new(FrozenDisplayRule::new(first_new_line)));
+        }
+        rules
+    }
 
-        self.resolver.add_tag_rule(Box::new(MarkTagRule::new(marked_indices)));
+    /// Counts the lines the filter or search being typed would give, so the footer can show it before
+    /// the input is submitted. Large buffers are counted in the background, others once typing pauses.
+    fn update_input_preview(&mut self) {
+        let pattern = self.input.value();
+        let input = match self.view_state {
+            ViewState::ActiveSearchMode if !pattern.is_empty() => Some(PreviewInput::Search {
+                pattern: pattern.to_string(),
+                case_sensitive: self.search.is_case_sensitive(),
+                fuzzy: self.search.is_fuzzy(),
+                whole_word: self.search.is_whole_word(),
+                scope: self.search.scope(),
+            }),
+            ViewState::ActiveFilterMode if !pattern.is_empty() => Some(PreviewInput::Filter {
+                pattern: pattern.to_string(),
+                mode: self.filter.get_mode(),
+                case_sensitive: self.filter.is_case_sensitive(),
+                whole_word: self.filter.is_whole_word(),
+            }),
+            _ => None,
+        };
+        if self.preview.as_ref().map(|(previewed, _)| previewed) == input.as_ref() {
+            return;
+        }
 
-        self.resolver.set_expanded_lines(self.expansion.get_all_expanded());
+        self.preview_task = None;
+        self.preview = None;
+        self.preview_due = None;
+        let Some(input) = input else {
+            return;
+        };
 
-        let num_lines = {
-            let visible_lines = self.resolver.get_visible_lines(all_lines);
-            let num_lines = visible_lines.len();
+        // A background task would keep a snapshot of the streamed lines, copied on the next append
+        let lines = self.log_buffer.shared_lines();
+        if self.log_buffer.streaming || lines.len() < BACKGROUND_PREVIEW_MIN_LINES {
+            self.preview = Some((input, None));
+            self.preview_due = Some(Instant::now() + PREVIEW_DELAY);
+            return;
+        }
 
-            // Update search matches if there's an active search
-            if let Some(pattern) = self.search.get_active_pattern().map(str::to_string) {
-                let visible_content = visible_lines.iter().map(|v| all_lines[v.log_index].content());
-                let all_content = all_lines.iter().map(|log_line| log_line.content());
-                self.search.update_matches(&pattern, visible_content, all_content);
-            }
+        let rules = self.preview_rules(&input);
+        self.preview = Some((input, None));
+        self.preview_generation += 1;
+        let on_complete = self.events.app_sender();
+        self.preview_task = Some(PreviewTask::spawn(
+            self.preview_generation,
+            lines,
+            rules,
+            move |count| on_complete(AppEvent::PreviewCount(count)),
+        ));
+    }
 
-            num_lines
+    /// Counts the lines of the preview that waited for typing to pause.
+    fn count_input_preview(&mut self) {
+        self.preview_due = None;
+        let Some((input, None)) = &self.preview else {
+            return;
         };
+        let rules = self.preview_rules(input);
+        let count = count_lines(
+            self.log_buffer.all_lines(),
+            &rules,
+            &AtomicBool::new(false),
+            &AtomicUsize::new(0),
+        );
+        if let Some((_, preview_count)) = &mut self.preview {
+            *preview_count = count;
+        }
+    }
 
-        self.viewport.set_total_lines(num_lines);
+    /// Returns the rules the lines counted for the preview of the filter or search being typed must pass.
+    fn preview_rules(&self, input: &PreviewInput) -> PreviewRules {
+        let marked_indices = Arc::new(self.marking.get_marked_indices());
+        let always_visible = Arc::new(self.always_visible_lines(&marked_indices));
+        let mut rules = self.base_visibility_rules(&marked_indices);
+        if self.repeat_match().is_some() {
+            rules.push(Box::new(RepeatedLinesRule::new(self.repeated_lines.repeats())));
+        }
+        let mut patterns = self.filter.get_filter_patterns().to_vec();
+        match input {
+            PreviewInput::Search { pattern, scope, .. } => {
+                if *scope == SearchScope::Filtered {
+                    rules.push(Box::new(
+                        FilterRule::new(Arc::new(patterns), always_visible)
+                            .with_extractor(self.column_view.extractor())
+                            .with_file_names(self.file_manager.file_names())
+                            .with_case_folding(self.case_folding),
+                    ));
+                }
+                rules.push(Box::new(SearchMatchRule::new(self.search.matcher(pattern))));
+            }
+            PreviewInput::Filter {
+                pattern,
+                mode,
+                case_sensitive,
+                whole_word,
+            } => {
+                patterns.push(
+                    FilterPattern::new(pattern.clone(), *mode, *case_sensitive, true).with_whole_word(*whole_word),
+                );
+                rules.push(Box::new(
+                    FilterRule::new(Arc::new(patterns), always_visible)
+                        .with_extractor(self.column_view.extractor())
+                        .with_file_names(self.file_manager.file_names())
+                        .with_case_folding(self.case_folding),
+                ));
+            }
+        }
+        rules
+    }
 
-        // Call after the all_lines scope ends
-        self.update_events_view_count();
+    /// Returns the number of lines the filter or search being typed would give, if counted.
+    pub fn preview_count(&self) -> Option<usize> {
+        self.preview.as_ref().and_then(|(_, count)| *count)
+    }
 
-        if self.log_buffer.streaming {
-            self.update_processor_context();
+    /// Finds the matches of the active search pattern in the visible lines. Large buffers are searched
+    /// in the background, adding the matches as they are found.
+    fn update_search_matches(&mut self) {
+        self.search_task = None;
+        let Some(pattern) = self.search.get_active_pattern().map(str::to_string) else {
+            return;
+        };
+
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let visible: Vec<usize> = visible_lines.iter().map(|v| v.log_index).collect();
+        if self.log_buffer.streaming || all_lines.len() < BACKGROUND_SEARCH_MIN_LINES {
+            // Streamed lines are appended, so only the new lines are searched
+            let base_generation = self.log_buffer.base_generation();
+            self.search
+                .update_log_line_matches(&pattern, all_lines, &visible, base_generation);
+            return;
         }
 
-        if num_lines == 0 {
-            self.viewport.selected_line = 0;
+        self.search.clear_match_results();
+        self.search_generation += 1;
+        let on_progress = self.events.app_sender();
+        self.search_task = Some(SearchTask::spawn(
+            self.search_generation,
+            self.log_buffer.shared_lines(),
+            visible,
+            self.search.matcher(&pattern),
+            move |progress| on_progress(AppEvent::SearchProgress(progress)),
+        ));
+    }
+
+    /// Reports the result of a submitted search: jumps to the first match after the cursor, or shows
+    /// a message if nothing was found. Waits while a background search may still find the match.
+    fn report_search_result(&mut self) {
+        if !self.search_result_pending {
             return;
         }
+        // Matches may still be found in the part of a large file being loaded
+        let searching = self.search_task.is_some() || self.load_task.is_some();
+        let (_, visible_matches, total_matches) = self.search.get_match_info();
+        let pattern = self.search.get_active_pattern().unwrap_or_default().to_string();
 
-        if self.log_buffer.streaming && self.viewport.follow_mode {
-            self.viewport.goto_bottom();
-        } else {
-            let new_selected_line = if let Some(target_log_line_index) = log_line_index {
-                // Find closest visible line to the target
-                let all_lines = self.log_buffer.all_lines();
-                self.resolver
-                    .log_to_viewport(target_log_line_index, all_lines)
-                    .unwrap_or_else(|| {
-                        // Find closest visible line
-                        let visible = self.resolver.get_visible_lines(all_lines);
-                        visible
-                            .iter()
-                            .enumerate()
-                            .min_by_key(|(_, v)| v.log_index.abs_diff(target_log_line_index))
-                            .map(|(idx, _)| idx)
-                            .unwrap_or(self.viewport.selected_line.min(num_lines - 1))
-                    })
+        if visible_matches == 0 {
+            if searching {
+                return;
+            }
+            self.search_result_pending = false;
+            if total_matches > 0 {
+                self.show_message(
+                    format!(
+                        "0 hits for '{}' ({} in filtered lines, Alt+n: reveal them)",
+                        pattern, total_matches
+                    )
+                    .as_str(),
+                );
             } else {
-                self.viewport.selected_line.min(num_lines - 1)
-            };
+                self.show_message(format!("0 hits for '{}'", pattern).as_str());
+            }
+            return;
+        }
 
-            self.viewport.goto_line(new_selected_line, false);
+        if self.options.is_disabled(AppOption::SearchDisableJumping) && !self.viewport.follow_mode {
+            let selected_line = self.viewport.selected_line;
+            if searching && self.search.get_match_indices().last() < Some(&selected_line) {
+                return;
+            }
+            if let Some(line) = self.search.first_match_from(selected_line) {
+                self.push_viewport_line_to_history(line);
+                self.viewport.goto_line(line, false);
+                self.scroll_to_search_match();
+            }
+            self.viewport.follow_mode = false;
         }
-        trace!("update_view took: {:?}", update_start.elapsed());
+        self.search_result_pending = false;
+    }
+
+    /// Returns whether filtering should run in the background, which is the case for large files
+    /// with active filters. Streamed lines are filtered as they arrive instead.
+    fn should_filter_in_background(&self, patterns: &[FilterPattern]) -> bool {
+        !self.log_buffer.streaming
+            && self.log_buffer.get_total_lines_count() >= BACKGROUND_FILTER_MIN_LINES
+            && patterns.iter().any(|pattern| pattern.enabled)
     }
 
     fn update_processor_context(&self) {
         if let Some(processor) = &self.events.processor {
             let context = ProcessingContext {
                 filter_patterns: self.filter.get_filter_patterns().to_vec(),
+                field_extractor: self.column_view.extractor().cloned(),
+                case_folding: self.case_folding,
                 search_pattern: self.search.get_active_pattern().map(|p| p.to_string()),
                 search_case_sensitive: self.search.is_case_sensitive(),
             };
@@ -463,12 +1545,49 @@ impl App {
 
     pub fn show_overlay(&mut self, overlay: Overlay) {
         debug!("Show overlay: {:?}", overlay);
-        if matches!(overlay, Overlay::Message(_)) {
-            self.message_timestamp = Some(std::time::Instant::now());
-        }
+        // Long messages stay open until dismissed so they can be read and scrolled
+        self.message_timestamp = match &overlay {
+            Overlay::Message(message) if message.lines().count() <= MESSAGE_AUTO_CLOSE_MAX_LINES => {
+                Some(std::time::Instant::now())
+            }
+            _ => None,
+        };
+        self.message_scroll = 0;
+        self.message_status = None;
         self.overlay = Some(overlay);
     }
 
+    /// Returns the text of the message, error or fatal error overlay, if one is shown.
+    fn message_text(&self) -> Option<&str> {
+        match &self.overlay {
+            Some(Overlay::Message(text)) | Some(Overlay::Error(text)) | Some(Overlay::Fatal(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Scrolls the message overlay by the given number of lines.
+    fn scroll_message(&mut self, lines: isize) {
+        // Keep the message open while it is being read
+        self.message_timestamp = None;
+        self.message_scroll = self
+            .message_scroll
+            .saturating_add_signed(lines)
+            .min(self.message_max_scroll.get());
+    }
+
+    /// Copies the text of the message overlay to the clipboard.
+    pub fn copy_message_to_clipboard(&mut self) {
+        let Some(text) = self.message_text().map(str::to_string) else {
+            return;
+        };
+        self.message_timestamp = None;
+        let status = match self.copy_to_clipboard(&text) {
+            Ok(()) => "Copied to clipboard".to_string(),
+            Err(e) => e,
+        };
+        self.message_status = Some(status);
+    }
+
     pub fn close_overlay(&mut self) {
         self.overlay = None;
         self.message_timestamp = None;
@@ -490,13 +1609,35 @@ impl App {
             return;
         }
 
-        if let Some(completion) = self.completion.find_completion(self.input.value()) {
-            let full_text = format!("{}{}", self.input.value(), completion);
+        if let Some(full_text) = self.completion.complete(self.input.value()) {
             self.input = Input::new(full_text);
             self.update_temporary_highlights();
         }
     }
 
+    /// Offers the search and filter history as completions, the history of the active mode first.
+    fn update_completion_history(&mut self) {
+        let search_history = self.search.history.get_history().iter().rev().map(String::as_str);
+        let filter_history = self
+            .filter
+            .history
+            .get_history()
+            .iter()
+            .rev()
+            .map(|entry| entry.pattern.as_str());
+
+        if self.view_state == ViewState::ActiveFilterMode {
+            self.completion.set_history(filter_history.chain(search_history));
+        } else {
+            self.completion.set_history(search_history.chain(filter_history));
+        }
+    }
+
+    /// Returns a hint about the completion candidates while cycling through them with Tab.
+    pub fn completion_hint(&self) -> Option<String> {
+        self.completion.hint(self.input.value())
+    }
+
     /// Returns the input prefix for the current state.
     /// This is the single source of truth for input prefixes used in both rendering and cursor positioning.
     pub fn get_input_prefix(&self) -> String {
@@ -510,7 +1651,13 @@ impl App {
         match self.view_state {
             ViewState::ActiveSearchMode => {
                 let case_sensitive = if self.search.is_case_sensitive() { "Aa" } else { "aa" };
-                format!("Search: [{}] ", case_sensitive)
+                let scope = match self.search.scope() {
+                    SearchScope::Filtered => "filtered",
+                    SearchScope::All => "all",
+                };
+                let fuzzy = if self.search.is_fuzzy() { "[fuzzy] " } else { "" };
+                let whole_word = if self.search.is_whole_word() { "[word] " } else { "" };
+                format!("Search: [{}] [{}] {}{}", case_sensitive, scope, fuzzy, whole_word)
             }
             ViewState::ActiveFilterMode => {
                 let filter_mode = match self.filter.get_mode() {
@@ -518,9 +1665,16 @@ impl App {
                     ActiveFilterMode::Exclude => "EX",
                 };
                 let case_sensitive = if self.filter.is_case_sensitive() { "Aa" } else { "aa" };
-                format!("Filter: [{}] [{}] ", case_sensitive, filter_mode)
+                let whole_word = if self.filter.is_whole_word() { "[word] " } else { "" };
+                let field = if FieldPattern::parse(self.input.value()).is_some() {
+                    "[field] "
+                } else {
+                    ""
+                };
+                format!("Filter: [{}] [{}] {}{}", case_sensitive, filter_mode, whole_word, field)
             }
             ViewState::GotoLineMode => "Go to line: ".to_string(),
+            ViewState::GotoTimeMode => "Go to time: ".to_string(),
             _ => String::new(),
         }
     }
@@ -534,30 +1688,38 @@ impl App {
         {
             self.highlighter.add_temporary_highlight(
                 self.input.value(),
-                PatternStyle::new(Some(FILTER_MODE_FG), Some(FILTER_MODE_BG), true),
+                PatternStyle::new(Some(self.theme.filter_fg), Some(self.theme.filter_bg), true),
                 self.filter.is_case_sensitive(),
+                self.filter.is_whole_word(),
             );
         }
 
         // Add search mode preview highlight
         if self.view_state == ViewState::ActiveSearchMode && self.input.value().chars().count() >= 2 {
-            self.highlighter.add_temporary_highlight(
-                self.input.value(),
-                PatternStyle::new(Some(SEARCH_MODE_FG), Some(SEARCH_MODE_BG), true),
-                self.search.is_case_sensitive(),
-            );
+            let pattern = self.input.value().to_string();
+            let style = PatternStyle::new(Some(self.theme.search_fg), Some(self.theme.search_bg), true);
+            self.add_search_highlight(&pattern, style);
         }
 
         // Add active search highlight
-        if let Some(pattern) = self.search.get_active_pattern()
+        if let Some(pattern) = self.search.get_active_pattern().map(str::to_string)
             && !pattern.is_empty()
             && self.view_state != ViewState::ActiveSearchMode
         {
-            self.highlighter.add_temporary_highlight(
-                pattern,
-                PatternStyle::new(Some(SEARCH_MODE_FG), Some(SEARCH_MODE_BG), false),
-                self.search.is_case_sensitive(),
-            );
+            let style = PatternStyle::new(Some(self.theme.search_fg), Some(self.theme.search_bg), false);
+            self.add_search_highlight(&pattern, style);
+        }
+    }
+
+    /// Highlights the matches of a search pattern, or the matched characters when matching fuzzily.
+    fn add_search_highlight(&mut self, pattern: &str, style: PatternStyle) {
+        let case_sensitive = self.search.is_case_sensitive();
+        if self.search.is_fuzzy() {
+            self.highlighter
+                .add_temporary_fuzzy_highlight(pattern, style, case_sensitive);
+        } else {
+            self.highlighter
+                .add_temporary_highlight(pattern, style, case_sensitive, self.search.is_whole_word());
         }
     }
 
@@ -569,12 +1731,20 @@ impl App {
             let prefix_width = self.get_input_prefix().len();
             let cursor_x = (prefix_width + self.input.visual_cursor()) as u16;
             Some((cursor_x, footer_y))
+        } else if self.view_state == ViewState::NotesView && self.overlay.is_none() {
+            let area = popup_area(Rect::new(0, 0, width, height), NOTES_POPUP_WIDTH, NOTES_POPUP_HEIGHT);
+            let (row, col) = self.notes.cursor();
+            let (top, left) = self.notes.scroll_offset();
+            let cursor_x = area.x + 1 + col.saturating_sub(left) as u16;
+            let cursor_y = area.y + 1 + row.saturating_sub(top) as u16;
+            Some((cursor_x, cursor_y))
         } else if let Some(overlay) = &self.overlay
             && overlay.has_text_input()
             && let Some((popup_width, popup_height)) = overlay.popup_size()
         {
-            let cursor_x = (width - popup_width) / 2 + 1 + self.input.visual_cursor() as u16;
-            let cursor_y = (height - popup_height) / 2 + 1;
+            let area = popup_area(Rect::new(0, 0, width, height), popup_width, popup_height);
+            let cursor_x = area.x + 1 + self.input.visual_cursor() as u16;
+            let cursor_y = area.y + 1;
             Some((cursor_x, cursor_y))
         } else {
             None
@@ -582,93 +1752,378 @@ impl App {
     }
 
     /// Run the application's main loop.
-    pub async fn run<B: Backend>(mut self, mut terminal: Terminal<B>) -> color_eyre::Result<()>
+    pub async fn run<B: Backend + std::io::Write>(mut self, mut terminal: Terminal<B>) -> color_eyre::Result<()>
     where
         B::Error: Send + Sync + 'static,
     {
         let terminal_size = terminal.size()?;
-        self.viewport.resize(
+        self.resize_viewports(
             terminal_size.width.saturating_sub(1) as usize,
             terminal_size.height.saturating_sub(2) as usize,
         );
         self.viewport.scroll_margin = 2;
+        self.viewport.page_overlap = self.config.page_overlap.unwrap_or(DEFAULT_PAGE_OVERLAP);
 
+        // Only redraw after events that may have changed what is shown, e.g. not after mouse moves
+        let mut needs_redraw = true;
         while self.running {
-            let draw_start = Instant::now();
-            terminal.draw(|frame| {
-                frame.render_widget(&self, frame.area());
-                if let Some((x, y)) = self.calculate_cursor_pos(frame.area().width, frame.area().height) {
-                    frame.set_cursor_position((x, y));
-                }
-            })?;
-            let draw_elapsed = draw_start.elapsed();
-            trace!("Screen draw took: {:?}", draw_elapsed);
+            // While streamed lines are pending, draw at most once per refresh interval
+            if needs_redraw && (!self.new_lines_pending || self.last_draw.elapsed() >= self.tick_rate) {
+                let draw_start = Instant::now();
+                terminal.draw(|frame| {
+                    frame.render_widget(&self, frame.area());
+                    if let Some((x, y)) = self.calculate_cursor_pos(frame.area().width, frame.area().height) {
+                        frame.set_cursor_position((x, y));
+                    }
+                })?;
+                self.last_draw = Instant::now();
+                self.debug_stats.record_frame(draw_start.elapsed());
+                trace!("Screen draw took: {:?}", draw_start.elapsed());
+            }
 
-            match self.events.next().await? {
+            needs_redraw = true;
+            match self.events.next(self.next_timer()).await? {
                 Event::Tick => self.tick(),
-                Event::Crossterm(event) => match event {
-                    Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                        debug!("Key Event: {:?}", key_event);
-                        if matches!(self.overlay, Some(Overlay::AddFile)) {
-                            self.handle_file_explorer_event(key_event);
-                        } else {
-                            self.handle_key_events(key_event)?;
-                        }
-                    }
-                    crossterm::event::Event::Resize(x, y) => {
-                        self.viewport
-                            .resize(x.saturating_sub(1) as usize, y.saturating_sub(2) as usize);
-                    }
-                    _ => {}
-                },
+                Event::Crossterm(event) => needs_redraw = self.handle_crossterm_event(event)?,
                 Event::App(app_event) => {
                     self.handle_app_event(app_event)?;
                 }
             }
+            self.follow_search_scroll();
+
+            let capture_mouse = self.options.is_enabled(AppOption::ShowMinimap) || self.event_tracker.has_severities();
+            if capture_mouse != self.mouse_captured {
+                self.set_mouse_capture(terminal.backend_mut(), capture_mouse)?;
+            }
+
+            if let Some((path, line)) = self.pending_editor.take() {
+                self.run_editor(&mut terminal, &path, line)?;
+            }
+            if std::mem::take(&mut self.pending_suspend) {
+                self.suspend_to_shell(&mut terminal)?;
+            }
+        }
+        if self.mouse_captured {
+            self.set_mouse_capture(terminal.backend_mut(), false)?;
         }
         Ok(())
     }
 
-    /// Handles the tick event of the terminal.
-    ///
-    /// The tick event is where you can update the state of your application with any logic that
-    /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
-    pub fn tick(&mut self) {
-        if let Some(timestamp) = self.message_timestamp
-            && timestamp.elapsed().as_secs() >= 3
-            && matches!(self.overlay, Some(Overlay::Message(_)))
-        {
-            self.set_view_state(ViewState::LogView);
+    /// Enables or disables capturing mouse events.
+    fn set_mouse_capture(&mut self, writer: &mut impl std::io::Write, enabled: bool) -> color_eyre::Result<()> {
+        if enabled {
+            crossterm::execute!(writer, crossterm::event::EnableMouseCapture)?;
+        } else {
+            crossterm::execute!(writer, crossterm::event::DisableMouseCapture)?;
         }
+        self.mouse_captured = enabled;
+        Ok(())
     }
 
-    /// Set running to false to quit the application.
+    /// Handles a terminal event. Returns whether what is shown may have changed and needs to be redrawn.
+    fn handle_crossterm_event(&mut self, event: crossterm::event::Event) -> color_eyre::Result<bool> {
+        match event {
+            Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                debug!("Key Event: {:?}", key_event);
+                if matches!(self.overlay, Some(Overlay::AddFile)) {
+                    self.handle_file_explorer_event(key_event);
+                } else {
+                    self.handle_key_events(key_event)?;
+                }
+                self.schedule_autosave();
+            }
+            crossterm::event::Event::Resize(x, y) => {
+                self.resize_viewports(x.saturating_sub(1) as usize, y.saturating_sub(2) as usize);
+            }
+            crossterm::event::Event::Mouse(mouse_event) => {
+                let needs_redraw = self.handle_mouse_event(mouse_event);
+                if needs_redraw {
+                    self.schedule_autosave();
+                }
+                return Ok(needs_redraw);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Jumps to the line of the clicked minimap row, or to the next event of the clicked severity badge.
     ///
-    /// If not in streaming mode, persist current state to disk.
-    pub fn quit(&mut self) {
-        if self.persist_enabled && !self.log_buffer.streaming {
-            save_state(&self.file_manager.paths(), self);
+    /// Returns false if the event was ignored, e.g. a mouse move, so the screen doesn't need to be redrawn.
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> bool {
+        if mouse_event.kind != MouseEventKind::Down(MouseButton::Left)
+            || self.view_state != ViewState::LogView
+            || self.overlay.is_some()
+        {
+            return false;
+        }
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        let clicked_severity = self
+            .severity_badge_areas
+            .borrow()
+            .iter()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, severity)| *severity);
+        if let Some(severity) = clicked_severity {
+            self.severity_event_next(severity);
+            return true;
+        }
+        let Some(area) = self.minimap_area.get() else {
+            return false;
+        };
+        if !area.contains(Position::new(mouse_event.column, mouse_event.row)) {
+            return false;
         }
 
-        self.running = false;
+        let minimap = self.build_minimap(&self.viewport, area.height as usize);
+        let row = (mouse_event.row - area.y) as usize;
+        let Some(line) = minimap
+            .row_to_line(row)
+            .or_else(|| self.viewport.total_lines.checked_sub(1))
+        else {
+            return false;
+        };
+        self.push_viewport_line_to_history(line);
+        self.viewport.goto_line(line, true);
+        true
     }
 
-    /// Restores application state from a persisted state.
-    fn restore_state(&mut self, state: PersistedState) {
-        self.options.restore(&state.options());
+    /// Suspends the TUI, opens the file in the user's editor and restores the TUI when the editor exits.
+    fn run_editor<B: Backend + std::io::Write>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        path: &str,
+        line: usize,
+    ) -> color_eyre::Result<()>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        let editor = preferred_editor();
+        let Some(mut command) = editor_command(&editor, path, line) else {
+            self.show_error("No editor configured");
+            return Ok(());
+        };
+
+        self.release_terminal(terminal)?;
+        let status = command.status();
+        self.reclaim_terminal(terminal)?;
+
+        match status {
+            Ok(status) if !status.success() => {
+                self.show_error(format!("{} exited with {}", editor, status).as_str());
+            }
+            Err(e) => {
+                self.show_error(format!("Failed to run {}:\n{}", editor, e).as_str());
+            }
+            Ok(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Stops lazylog like Ctrl+Z does in a shell, giving the terminal back until the job is continued with `fg`.
+    fn suspend_to_shell<B: Backend + std::io::Write>(&mut self, terminal: &mut Terminal<B>) -> color_eyre::Result<()>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        self.release_terminal(terminal)?;
+        let result = suspend_job();
+        self.reclaim_terminal(terminal)?;
+
+        if let Err(e) = result {
+            self.show_error(&e);
+        }
+        Ok(())
+    }
+
+    /// Gives the terminal to another program: stops reading terminal events and leaves raw mode and the
+    /// alternate screen. Escape sequences go to the stream the terminal draws to, which is stderr in streaming mode.
+    fn release_terminal<B: Backend + std::io::Write>(&mut self, terminal: &mut Terminal<B>) -> color_eyre::Result<()> {
+        self.events.suspend();
+        crossterm::terminal::disable_raw_mode()?;
+        let writer = terminal.backend_mut();
+        if self.mouse_captured {
+            crossterm::execute!(writer, crossterm::event::DisableMouseCapture)?;
+        }
+        crossterm::execute!(
+            writer,
+            crossterm::terminal::LeaveAlternateScreen,
+            crossterm::cursor::Show
+        )?;
+        Ok(())
+    }
+
+    /// Takes the terminal back after [`App::release_terminal`]. Resize events sent meanwhile were not read, so the
+    /// view is resized to the current size of the terminal.
+    fn reclaim_terminal<B: Backend + std::io::Write>(&mut self, terminal: &mut Terminal<B>) -> color_eyre::Result<()>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        let writer = terminal.backend_mut();
+        crossterm::execute!(writer, crossterm::terminal::EnterAlternateScreen)?;
+        if self.mouse_captured {
+            crossterm::execute!(writer, crossterm::event::EnableMouseCapture)?;
+        }
+        crossterm::terminal::enable_raw_mode()?;
+        terminal.autoresize()?;
+        terminal.clear()?;
+        let size = terminal.size()?;
+        self.resize_viewports(
+            size.width.saturating_sub(1) as usize,
+            size.height.saturating_sub(2) as usize,
+        );
+        self.events.resume();
+        Ok(())
+    }
+
+    /// Returns when the next tick is due, or `None` if nothing needs to happen until the next event.
+    fn next_timer(&self) -> Option<Instant> {
+        let message_timeout = self
+            .message_timestamp
+            .filter(|_| matches!(self.overlay, Some(Overlay::Message(_))))
+            .map(|timestamp| timestamp + MESSAGE_TIMEOUT);
+        // Redraw while filtering, searching or running a command in the background to update the progress
+        let filter_progress = (self.filter_task.is_some()
+            || self.search_task.is_some()
+            || self.preview_task.is_some()
+            || self.load_task.is_some()
+            || self.pipe_task.is_some())
+        .then(|| Instant::now() + PROGRESS_REFRESH_INTERVAL);
+        let new_lines = self.new_lines_pending.then(|| self.last_draw + self.tick_rate);
+        // The stats change every second while lines arrive, but only when the next minute of idle time starts
+        // once the stream is idle
+        let stream_stats = self.log_buffer.streaming.then(|| {
+            let now = Instant::now();
+            match self.stream_stats.idle_time(now) {
+                Some(idle) if idle >= STREAM_IDLE_SLOWDOWN => now + Duration::from_secs(60 - idle.as_secs() % 60),
+                _ => self.last_draw + STREAM_STATS_REFRESH_INTERVAL,
+            }
+        });
+        let alert_toast = self.alert_toast.as_ref().map(|(_, fired)| *fired + ALERT_TOAST_TIMEOUT);
+
+        message_timeout
+            .into_iter()
+            .chain(filter_progress)
+            .chain(new_lines)
+            .chain(stream_stats)
+            .chain(alert_toast)
+            .chain(self.autosave_due)
+            .chain(self.preview_due)
+            .min()
+    }
+
+    /// Handles the tick event of the terminal.
+    ///
+    /// The tick event is emitted when the timer returned by [`App::next_timer`] is due. E.g. hiding a
+    /// message after a timeout.
+    pub fn tick(&mut self) {
+        trace!("Tick");
+        if self.new_lines_pending && self.last_draw.elapsed() >= self.tick_rate {
+            self.process_new_lines();
+        }
+        if let Some(timestamp) = self.message_timestamp
+            && timestamp.elapsed() >= MESSAGE_TIMEOUT
+            && matches!(self.overlay, Some(Overlay::Message(_)))
+        {
+            self.set_view_state(ViewState::LogView);
+        }
+        if self
+            .alert_toast
+            .as_ref()
+            .is_some_and(|(_, fired)| fired.elapsed() >= ALERT_TOAST_TIMEOUT)
+        {
+            self.alert_toast = None;
+        }
+        if self.autosave_due.is_some_and(|due| due <= Instant::now()) {
+            self.autosave();
+        }
+        if self.preview_due.is_some_and(|due| due <= Instant::now()) {
+            self.count_input_preview();
+        }
+    }
+
+    /// Set running to false to quit the application.
+    ///
+    /// If not in streaming mode, persist current state to disk. The state saved for a file still being loaded is
+    /// kept, as it was not restored yet.
+    pub fn quit(&mut self) {
+        if self.persists_state() {
+            save_state(&self.file_manager.paths(), self);
+        }
+        self.autosave_due = None;
+        if let Some(name) = &self.session_name
+            && !self.log_buffer.streaming
+        {
+            let _ = save_session(name, &Session::from_app(self));
+        }
+
+        self.running = false;
+    }
 
-        self.search.history.restore(state.search_history().to_vec());
-        self.filter.history.restore(state.filter_history().to_vec());
+    /// Returns true if the state is saved for the files, i.e. not when streaming or while a file is still loaded.
+    fn persists_state(&self) -> bool {
+        self.persist_enabled && !self.log_buffer.streaming && self.load_task.is_none()
+    }
+
+    /// Autosaves the state once no further changes are made for a moment.
+    fn schedule_autosave(&mut self) {
+        if self.persists_state() {
+            self.autosave_due = Some(Instant::now() + AUTOSAVE_DELAY);
+        }
+    }
+
+    /// Saves the state if it changed since the last autosave, so marks and filters survive a crash.
+    fn autosave(&mut self) {
+        self.autosave_due = None;
+        if !self.persists_state() {
+            return;
+        }
+        if let Some(state) = autosave_state(&self.file_manager.paths(), self, self.autosaved_state.as_deref()) {
+            trace!("Autosaved state");
+            self.autosaved_state = Some(state);
+        }
+    }
 
+    /// Restores application state from a persisted state.
+    fn restore_state(&mut self, state: PersistedState) {
+        let recovered = state.autosaved();
+        let height = self.log_view_height();
+        self.options.restore(&state.options());
+        self.resize_viewports(self.log_view_width(), height);
+
+        self.search.history.restore(merge_history(
+            state.search_history(),
+            self.global_history.search_history(),
+        ));
+        self.filter.history.restore(merge_history(
+            state.filter_history(),
+            self.global_history.filter_history(),
+        ));
+
+        self.search.set_whole_word(state.search_whole_word());
         for filter_state in state.filters() {
-            let new_filter = FilterPattern::new(
-                filter_state.pattern().to_string(),
-                filter_state.mode(),
-                filter_state.case_sensitive(),
-                filter_state.enabled(),
-            );
+            let new_filter = if filter_state.regex() {
+                FilterPattern::new_regex(
+                    filter_state.pattern().to_string(),
+                    filter_state.mode(),
+                    filter_state.case_sensitive(),
+                    filter_state.enabled(),
+                )
+            } else {
+                Some(FilterPattern::new(
+                    filter_state.pattern().to_string(),
+                    filter_state.mode(),
+                    filter_state.case_sensitive(),
+                    filter_state.enabled(),
+                ))
+            };
 
-            self.filter.add_filter(&new_filter);
+            if let Some(new_filter) = new_filter.map(|filter| {
+                filter
+                    .with_whole_word(filter_state.whole_word())
+                    .with_file(filter_state.file().map(str::to_string))
+            }) {
+                self.filter.add_filter(&new_filter);
+            }
         }
 
         self.filter_list_state.set_item_count(self.filter.count());
@@ -683,16 +2138,35 @@ impl App {
             }
         }
 
+        for register_state in state.registers() {
+            let position = register_state.position();
+            if position.line_index < self.log_buffer.get_total_lines_count() {
+                self.registers.set(register_state.name(), position);
+            }
+        }
+
+        self.notes.set_text(state.notes());
+
         for custom_event in state.custom_events() {
             let pattern = custom_event.pattern();
-            self.event_tracker.add_custom_event(pattern);
-
-            let style = PatternStyle {
-                fg_color: None,
-                bg_color: Some(self.config.custom_event_bg_color()),
-                bold: false,
-            };
-            self.highlighter.add_custom_event(pattern, style);
+            let style = self.custom_event_style(custom_event.color());
+            if !custom_event.is_regex() {
+                let case_sensitive = custom_event.is_case_sensitive();
+                if self.event_tracker.add_custom_event(
+                    pattern,
+                    case_sensitive,
+                    custom_event.name(),
+                    custom_event.color(),
+                ) {
+                    self.highlighter.add_custom_event(pattern, case_sensitive, style);
+                }
+            } else if let Ok(regex) = Regex::new(pattern)
+                && self
+                    .event_tracker
+                    .add_custom_regex_event(regex.clone(), custom_event.name(), custom_event.color())
+            {
+                self.highlighter.add_custom_regex_event(regex, style);
+            }
         }
 
         let event_filter_states: Vec<(String, bool)> = state
@@ -717,59 +2191,242 @@ impl App {
 
         self.update_temporary_highlights();
         self.update_view();
+        self.show_stale_filters();
+
+        if recovered {
+            self.show_message("Recovered the state autosaved before lazylog last exited unexpectedly");
+        }
+    }
+
+    /// Restores the state of a session after its files have been loaded.
+    fn restore_session(&mut self, session: Session) {
+        let (state, search, split) = session.into_parts();
+        self.restore_state(state);
+
+        if let Some(search) = search {
+            if search.case_sensitive() != self.search.is_case_sensitive() {
+                self.search.toggle_case_sensitivity();
+            }
+            self.search.set_pattern(search.pattern());
+            if self.search.reveals_hidden_matches() {
+                self.update_view();
+            } else {
+                self.update_search_matches();
+            }
+        }
+
+        if let Some(split) = split {
+            let height = self.log_view_height();
+            let last_line = self.viewport.total_lines.saturating_sub(1);
+            let mut split_viewport = self.viewport.clone();
+            split_viewport.selected_line = split.selected_line().min(last_line);
+            split_viewport.top_line = split.top_line().min(last_line);
+            self.split_viewport = Some(split_viewport);
+            self.split_focus_top = split.focus_top();
+            self.resize_viewports(self.log_view_width(), height);
+        }
     }
 
     /// Handles application events and updates the state of [`App`].
     fn handle_app_event(&mut self, app_event: AppEvent) -> color_eyre::Result<()> {
         match app_event {
-            AppEvent::NewLines(processed_lines) => {
-                if self.streaming_paused {
-                    return Ok(());
+            AppEvent::PipeComplete(output) => {
+                // Ignore output of a command that has been replaced in the meantime
+                if let Some(task) = self.pipe_task.take() {
+                    self.finish_pipe_command(task, output);
                 }
-
-                let mut should_select = false;
-                for pl in processed_lines {
-                    let log_line_index = self.log_buffer.append_line(pl.line_content);
-                    let log_line = self.log_buffer.get_line(log_line_index).unwrap();
-
-                    let active_event = self.event_tracker.scan_single_line(log_line);
-                    if active_event && self.viewport.follow_mode {
-                        should_select = true;
+            }
+            AppEvent::CommandExited(generation, status) => {
+                if let Some(child_process) = &mut self.child_process {
+                    child_process.handle_exit(generation, status);
+                }
+                if !self.startup_commands.is_empty() {
+                    self.finish_stream_input();
+                }
+            }
+            AppEvent::StdinEnded => self.finish_stream_input(),
+            AppEvent::Terminate => {
+                debug!("Received a termination signal, quitting");
+                self.quit();
+            }
+            AppEvent::ConfigChanged => {
+                self.reload_config(None);
+            }
+            AppEvent::ScriptAnnotations => {
+                let annotations: Vec<ScriptAnnotation> = self
+                    .streaming_scripts
+                    .iter()
+                    .flat_map(StreamingScript::take_annotations)
+                    .collect();
+                self.apply_script_annotations(annotations, self.script_line_offset);
+            }
+            AppEvent::LoadComplete(loaded) => {
+                // Ignore lines of a load that has been replaced in the meantime, e.g. by reloading the file
+                if self.load_task.take().is_some() {
+                    self.finish_background_load(loaded);
+                }
+            }
+            AppEvent::FilterComplete(mask) => {
+                // Ignore results from a task that has been replaced in the meantime
+                if self.filter_task.as_ref().is_some_and(|task| task.key() == mask.key) {
+                    self.debug_stats.filter_time = Some(mask.elapsed);
+                    self.filter_mask = Some(mask);
+                    self.update_view();
+                }
+            }
+            AppEvent::SearchProgress(progress) => {
+                // Ignore results from a search that has been replaced in the meantime
+                if self
+                    .search_task
+                    .as_ref()
+                    .is_some_and(|task| task.generation() == progress.generation)
+                {
+                    self.search.append_matches(&progress.matches);
+                    if let Some(total_matches) = progress.total_matches {
+                        self.search.set_total_match_count(total_matches);
+                        self.search_task = None;
                     }
-
-                    if pl.passes_filter {
-                        let lines = self.log_buffer.all_lines();
-                        let viewport_index = self.resolver.log_to_viewport(log_line_index, lines).unwrap_or(0);
-                        self.completion.append_line(log_line);
-                        self.search.append_line(viewport_index, log_line.content());
+                    self.report_search_result();
+                }
+            }
+            AppEvent::PreviewCount(count) => {
+                // Ignore counts for input that has changed in the meantime
+                if self
+                    .preview_task
+                    .as_ref()
+                    .is_some_and(|task| task.generation() == count.generation)
+                {
+                    self.preview_task = None;
+                    if let Some((_, preview_count)) = &mut self.preview {
+                        *preview_count = Some(count.count);
                     }
                 }
+            }
+            AppEvent::NewLines => {
+                // Process the lines on the next tick if the screen was drawn recently, coalescing
+                // the lines arriving until then into one update
+                if self.last_draw.elapsed() < self.tick_rate {
+                    self.new_lines_pending = true;
+                } else {
+                    self.process_new_lines();
+                }
+            }
+        }
+        Ok(())
+    }
 
-                self.update_view();
+    /// Appends the streamed lines received since the last call and updates the view once for all of them.
+    fn process_new_lines(&mut self) {
+        let processed_lines = self.events.take_new_lines(MAX_LINES_PER_UPDATE);
+        // The rest of a burst is processed on the next tick
+        self.new_lines_pending = self.events.has_new_lines();
+        self.stream_stats.record(processed_lines.len(), Instant::now());
+        if self.streaming_paused {
+            self.stream_stats.record_dropped(processed_lines.len());
+        }
+        if self.streaming_paused || processed_lines.is_empty() {
+            return;
+        }
 
-                if should_select {
-                    self.events_list_state.select_last();
-                }
+        let first_index = self.log_buffer.get_total_lines_count();
+        let mut passing_lines = Vec::new();
+        for pl in processed_lines {
+            let log_line_index = self.log_buffer.append_stream_line(pl.line_content, pl.stream);
+            if pl.passes_filter {
+                passing_lines.push(log_line_index);
+            }
+        }
+        if self.format_detection_pending && self.log_buffer.get_total_lines_count() >= DETECTION_LINES {
+            self.detect_stream_format();
+        }
 
-                if self.viewport.follow_mode {
-                    self.viewport.goto_bottom();
-                }
+        let new_lines = &self.log_buffer.all_lines()[first_index..];
+        let should_select = self.event_tracker.update(&self.log_buffer) && self.viewport.follow_mode;
+        let first_alert = self.alerts.firings().len();
+        let now = Instant::now();
+        for log_line in new_lines {
+            self.watches.process_line(log_line.content());
+            self.alerts.process_line(log_line.content(), log_line.index, now);
+            for script in &self.streaming_scripts {
+                script.send_line(log_line.index + self.script_line_offset, log_line.content());
             }
         }
-        Ok(())
+        for log_line_index in passing_lines {
+            if let Some(log_line) = self.log_buffer.get_line(log_line_index) {
+                self.completion.append_line(log_line);
+            }
+        }
+
+        self.announce_alerts(first_alert);
+        self.announce_events(first_index);
+        self.drop_lines_over_max();
+        self.update_view();
+
+        if should_select {
+            self.events_list_state.select_last();
+        }
+
+        if self.viewport.follow_mode {
+            self.viewport.goto_bottom();
+        }
+        if let Some(split) = &mut self.split_viewport
+            && split.follow_mode
+        {
+            split.goto_bottom();
+        }
     }
 
     /// Handles the key events and updates the state of [`App`].
     pub fn handle_key_events(&mut self, key_event: KeyEvent) -> color_eyre::Result<()> {
+        if self.view_state == ViewState::NotesView
+            && !self.help.is_visible()
+            && self.overlay.is_none()
+            && self.handle_notes_input(key_event)
+        {
+            return Ok(());
+        }
+
+        if self.view_state == ViewState::NoiseView
+            && !self.help.is_visible()
+            && self.overlay.is_none()
+            && key_event.modifiers.is_empty()
+            && let KeyCode::Char(c @ '1'..='9') = key_event.code
+        {
+            self.exclude_noise_suggestion(c as usize - '1' as usize);
+            return Ok(());
+        }
+
+        if self.view_state == ViewState::RegistersView
+            && !self.help.is_visible()
+            && !key_event
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            && let KeyCode::Char(name) = key_event.code
+            && Registers::is_register_name(name)
+        {
+            self.use_register(name);
+            return Ok(());
+        }
+
         if self.is_text_input_mode() {
+            let previous_input = self.input.value().to_string();
             self.handle_text_input(key_event);
             self.update_temporary_highlights();
+
+            if self.overlay == Some(Overlay::RegexTester) && self.input.value() != previous_input {
+                self.update_regex_tester();
+            }
+            if self.overlay == Some(Overlay::ListFilter) && self.input.value() != previous_input {
+                let query = self.input.value().to_string();
+                self.set_list_query(&query);
+            }
         }
 
         if let Some(command) = self.keybindings.lookup(&self.view_state, &self.overlay, key_event) {
             debug!("Command: {:?}", command);
             command.execute(self)?;
         }
+        self.update_input_preview();
 
         Ok(())
     }
@@ -786,11 +2443,11 @@ impl App {
     fn handle_text_input(&mut self, key_event: KeyEvent) {
         if self.view_state == ViewState::GotoLineMode {
             match key_event.code {
-                KeyCode::Char(c) if c.is_ascii_digit() => {
+                KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, '%' | '+' | '-' | '$') => {
                     self.input.handle(InputRequest::InsertChar(c));
                 }
                 KeyCode::Char(_) => {
-                    // Ignore non-digit characters
+                    // Ignore characters that can't be part of a line, percentage or offset
                 }
                 _ => {
                     self.input.handle_event(&Key(key_event));
@@ -802,6 +2459,29 @@ impl App {
         self.input.handle_event(&Key(key_event));
     }
 
+    /// Handles editing keys in the notes view. Returns true if the key was consumed.
+    fn handle_notes_input(&mut self, key_event: KeyEvent) -> bool {
+        let modifiers = key_event.modifiers - KeyModifiers::SHIFT;
+        if !modifiers.is_empty() {
+            return false;
+        }
+
+        match key_event.code {
+            KeyCode::Char(c) => self.notes.insert_char(c),
+            KeyCode::Enter => self.notes.insert_newline(),
+            KeyCode::Backspace => self.notes.backspace(),
+            KeyCode::Delete => self.notes.delete(),
+            KeyCode::Left => self.notes.move_left(),
+            KeyCode::Right => self.notes.move_right(),
+            KeyCode::Up => self.notes.move_up(),
+            KeyCode::Down => self.notes.move_down(),
+            KeyCode::Home => self.notes.move_home(),
+            KeyCode::End => self.notes.move_end(),
+            _ => return false,
+        }
+        true
+    }
+
     pub fn confirm(&mut self) {
         if let Some(ref overlay) = self.overlay {
             match overlay {
@@ -833,16 +2513,74 @@ impl App {
                     }
                     return;
                 }
+                Overlay::SaveSession => {
+                    if !self.input.value().is_empty() {
+                        let name = self.input.value().to_string();
+                        self.save_session_as(name);
+                    } else {
+                        self.close_overlay();
+                    }
+                    return;
+                }
+                Overlay::PipeCommand => {
+                    if !self.input.value().is_empty() {
+                        let command = self.input.value().to_string();
+                        self.pipe_lines_through_command(&command);
+                    } else {
+                        self.close_overlay();
+                    }
+                    return;
+                }
+                Overlay::Query => {
+                    if !self.input.value().is_empty() {
+                        let query = self.input.value().to_string();
+                        self.run_query(&query);
+                    } else {
+                        self.close_overlay();
+                    }
+                    return;
+                }
+                Overlay::Correlate => {
+                    if !self.input.value().is_empty() {
+                        let pattern = self.input.value().to_string();
+                        self.correlate(&pattern);
+                    } else {
+                        self.close_overlay();
+                    }
+                    return;
+                }
+                Overlay::ExportFilters => {
+                    if !self.input.value().is_empty() {
+                        let path = self.input.value().to_string();
+                        self.export_filters_to_file(&path);
+                    } else {
+                        self.close_overlay();
+                    }
+                    return;
+                }
+                Overlay::ExportReport => {
+                    if !self.input.value().is_empty() {
+                        let path = self.input.value().to_string();
+                        self.export_report_to_file(&path);
+                    } else {
+                        self.close_overlay();
+                    }
+                    return;
+                }
                 Overlay::MarkName => {
-                    if self.view_state == ViewState::EventsView && self.event_tracker.showing_marks() {
+                    if self.view_state == ViewState::EventsView && self.events_list_shows_marks() {
                         let (events, _) = self.get_events_for_list();
-                        let visible_marks = self.get_visible_marks();
+                        let visible_marks = self.get_listed_marks();
                         let merged_items = EventMarkView::merge(&events, &visible_marks, true);
 
                         if let Some(EventOrMark::Mark(mark)) = merged_items.get(self.events_list_state.selected_index())
                         {
                             self.marking.set_mark_name(mark.line_index, self.input.value());
                         }
+                    } else if self.view_state == ViewState::MarksView && !self.selected_marks.is_empty() {
+                        for &line_index in &self.selected_marks {
+                            self.marking.set_mark_name(line_index, self.input.value());
+                        }
                     } else if self.view_state == ViewState::MarksView
                         && let Some(mark) = self.get_selected_mark()
                     {
@@ -853,24 +2591,14 @@ impl App {
                     return;
                 }
                 Overlay::AddCustomEvent => {
-                    if !self.input.value().is_empty() {
-                        let pattern = self.input.value().to_string();
-                        if self.event_tracker.add_custom_event(&pattern) {
-                            let style = PatternStyle {
-                                fg_color: None,
-                                bg_color: Some(self.config.custom_event_bg_color()),
-                                bold: false,
-                            };
-                            self.highlighter.add_custom_event(&pattern, style);
-
-                            self.event_tracker.scan_all_lines(&self.log_buffer);
-                            self.update_events_view_count();
-                        }
-                    }
-                    self.close_overlay();
+                    self.confirm_custom_event_step();
+                    return;
+                }
+                Overlay::AddFile | Overlay::RegexTester => {
                     return;
                 }
-                Overlay::AddFile => {
+                Overlay::ListFilter => {
+                    self.close_overlay();
                     return;
                 }
                 Overlay::EventsFilter => {
@@ -892,49 +2620,38 @@ impl App {
         match self.view_state {
             ViewState::ActiveSearchMode => {
                 if self.input.value().is_empty() {
-                    self.search.clear_matches();
-                } else {
-                    let all_lines = self.log_buffer.all_lines();
-                    let visible_lines = self.resolver.get_visible_lines(all_lines);
-                    let content_iter = visible_lines.iter().map(|vl| all_lines[vl.log_index].content());
-                    let all_content_iter = all_lines.iter().map(|log_line| log_line.content());
-
-                    let visible_matches = self
-                        .search
-                        .apply_pattern(self.input.value(), content_iter, all_content_iter);
-
-                    if let Some(matches) = visible_matches
-                        && matches == 0
-                    {
-                        let (_, _, total_matches) = self.search.get_match_info();
-                        if total_matches > 0 {
-                            self.show_message(
-                                format!(
-                                    "0 hits for '{}' ({} in filtered lines)",
-                                    self.input.value(),
-                                    total_matches
-                                )
-                                .as_str(),
-                            );
-                        } else {
-                            self.show_message(format!("0 hits for '{}'", self.input.value()).as_str());
-                        }
-                        return;
-                    }
+                    self.clear_search();
+                    self.set_view_state(ViewState::LogView);
+                    return;
+                }
 
-                    if self.options.is_disabled(AppOption::SearchDisableJumping) && !self.viewport.follow_mode {
-                        if let Some(line) = self.search.first_match_from(self.viewport.selected_line) {
-                            self.push_viewport_line_to_history(line);
-                            self.viewport.goto_line(line, false);
-                        }
-                        self.viewport.follow_mode = false;
-                    }
+                let pattern = self.input.value().to_string();
+                self.search.set_pattern(&pattern);
+                self.global_history.add(
+                    QueryKind::Search,
+                    &pattern,
+                    self.search.is_case_sensitive(),
+                    self.search.is_whole_word(),
+                );
+                if self.search.reveals_hidden_matches() {
+                    self.update_view();
+                } else {
+                    self.update_search_matches();
                 }
+
                 self.set_view_state(ViewState::LogView);
+                self.search_result_pending = true;
+                self.report_search_result();
             }
             ViewState::ActiveFilterMode => {
                 if !self.input.value().is_empty() {
                     self.filter.add_filter_from_pattern(self.input.value());
+                    self.global_history.add(
+                        QueryKind::Filter(self.filter.get_mode()),
+                        self.input.value(),
+                        self.filter.is_case_sensitive(),
+                        self.filter.is_whole_word(),
+                    );
                     self.filter_list_state.set_item_count(self.filter.count());
                     self.expansion.clear();
                     self.update_view();
@@ -945,46 +2662,144 @@ impl App {
                 self.goto_selected_event(true);
                 self.set_view_state(ViewState::LogView);
             }
+            ViewState::TimelineView => {
+                self.goto_selected_timeline_bucket(true);
+                self.set_view_state(ViewState::LogView);
+            }
             ViewState::OptionsView => {
                 let selected_index = self.options_list_state.selected_index();
                 self.options.enable_option(selected_index);
                 self.set_view_state(ViewState::LogView);
             }
+            ViewState::LevelsView => {
+                let selected_index = self.levels_list_state.selected_index();
+                self.set_min_level(selected_index.checked_sub(1).map(|i| LogLevel::ALL[i]));
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::ColumnsView => {
+                if !self.column_view.is_enabled() {
+                    self.toggle_column_view();
+                }
+                self.set_view_state(ViewState::LogView);
+            }
             ViewState::MarksView => {
                 self.goto_selected_mark(true);
                 self.set_view_state(ViewState::LogView);
             }
-            ViewState::GotoLineMode => {
-                if let Ok(line_number) = self.input.value().parse::<usize>() {
-                    let viewport_index = line_number.saturating_sub(1);
-                    if line_number > 0 && viewport_index < self.viewport.total_lines {
-                        self.push_viewport_line_to_history(viewport_index);
-                        self.viewport.goto_line(viewport_index, true);
-                    }
-                }
+            ViewState::QueryResultsView => {
+                self.goto_selected_query_row();
                 self.set_view_state(ViewState::LogView);
             }
-            _ => {}
-        }
-    }
-
-    pub fn cancel(&mut self) {
-        // Handle overlays first
-        if let Some(ref overlay) = self.overlay {
-            match overlay {
-                Overlay::EventsFilter => {
-                    self.close_overlay();
-                }
-                Overlay::MarkName => {
-                    self.close_overlay();
-                }
-                Overlay::EditFilter => {
-                    self.set_view_state(ViewState::FilterView);
-                }
-                Overlay::SaveToFile => {
-                    self.set_view_state(ViewState::LogView);
+            ViewState::PatternsView => {
+                self.pattern_to_filter(ActiveFilterMode::Include);
+            }
+            ViewState::NoiseView => {
+                self.exclude_noise_suggestion(self.noise_list_state.selected_index());
+            }
+            ViewState::SessionsView => {
+                self.open_selected_session();
+            }
+            ViewState::HistoryView => {
+                self.use_selected_history_record();
+            }
+            ViewState::AlertsView => {
+                self.goto_selected_alert();
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::CorrelationView => {
+                self.goto_selected_correlated_line();
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::JumpHistoryView => {
+                self.goto_selected_jump();
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::RestartsView => {
+                self.goto_selected_restart();
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::FormatView => {
+                self.set_view_state(ViewState::LogView);
+                self.select_format();
+            }
+            ViewState::StaleFiltersView => {
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::QuickFilterView => {
+                if let Some(filter) = self.quick_filters.get(self.quick_filter_list_state.selected_index()) {
+                    self.apply_quick_filter(filter.kind);
                 }
-                Overlay::AddCustomEvent => {
+            }
+            ViewState::ProfilesView => {
+                self.select_profile();
+            }
+            ViewState::RegistersView => {
+                if let Some(name) = self.registers.name_at(self.registers_list_state.selected_index()) {
+                    self.use_register(name);
+                }
+            }
+            ViewState::EventScopeView => {
+                self.set_event_scope_from_selection();
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::ActionsView => {
+                self.run_selected_action();
+            }
+            ViewState::ScriptsView => {
+                self.run_selected_script();
+            }
+            ViewState::InspectorView => {
+                self.copy_selected_line_detail();
+            }
+            ViewState::CopyFormatView => {
+                self.copy_lines_in_selected_format();
+            }
+            ViewState::GotoLineMode => {
+                if let Some(viewport_index) = self.viewport.resolve_goto_line(self.input.value()) {
+                    self.push_viewport_line_to_history(viewport_index);
+                    self.viewport.goto_line(viewport_index, true);
+                }
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::GotoTimeMode => {
+                let input = self.input.value().to_string();
+                self.set_view_state(ViewState::LogView);
+                if !input.trim().is_empty() {
+                    self.goto_time(&input);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        // Handle overlays first
+        if let Some(ref overlay) = self.overlay {
+            match overlay {
+                Overlay::EventsFilter => {
+                    self.close_overlay();
+                }
+                Overlay::ListFilter => {
+                    self.set_list_query("");
+                    self.close_overlay();
+                }
+                Overlay::MarkName => {
+                    self.close_overlay();
+                }
+                Overlay::EditFilter => {
+                    self.set_view_state(ViewState::FilterView);
+                }
+                Overlay::SaveToFile => {
+                    self.set_view_state(ViewState::LogView);
+                }
+                Overlay::AddCustomEvent
+                | Overlay::SaveSession
+                | Overlay::ExportFilters
+                | Overlay::ExportReport
+                | Overlay::PipeCommand
+                | Overlay::Query
+                | Overlay::Correlate
+                | Overlay::RegexTester => {
                     self.close_overlay();
                 }
                 Overlay::AddFile => {}
@@ -999,10 +2814,10 @@ impl App {
         // Handle view states
         match self.view_state {
             ViewState::ActiveSearchMode => {
-                self.search.clear_matches();
+                self.clear_search();
                 self.set_view_state(ViewState::LogView);
             }
-            ViewState::GotoLineMode | ViewState::ActiveFilterMode => {
+            ViewState::GotoLineMode | ViewState::GotoTimeMode | ViewState::ActiveFilterMode => {
                 self.set_view_state(ViewState::LogView);
             }
             ViewState::SelectionMode => {
@@ -1010,7 +2825,7 @@ impl App {
                 self.set_view_state(ViewState::LogView);
             }
             ViewState::LogView => {
-                self.search.clear_matches();
+                self.clear_search();
                 self.update_temporary_highlights();
 
                 if self.show_marked_lines_only {
@@ -1018,11 +2833,43 @@ impl App {
                     self.update_view();
                 }
             }
+            ViewState::EventsView | ViewState::MarksView if !self.list_query.is_empty() => {
+                self.set_list_query("");
+            }
             ViewState::FilterView
             | ViewState::OptionsView
             | ViewState::EventsView
             | ViewState::MarksView
-            | ViewState::FilesView => {
+            | ViewState::FilesView
+            | ViewState::SessionsView
+            | ViewState::ActionsView
+            | ViewState::ScriptsView
+            | ViewState::PipeOutputView
+            | ViewState::TimelineView
+            | ViewState::NotesView
+            | ViewState::LevelsView
+            | ViewState::ColumnsView
+            | ViewState::QueryResultsView
+            | ViewState::PatternsView
+            | ViewState::NoiseView
+            | ViewState::HistoryView
+            | ViewState::AlertsView
+            | ViewState::CorrelationView
+            | ViewState::RegistersView
+            | ViewState::JumpHistoryView
+            | ViewState::RestartsView
+            | ViewState::FullLineView
+            | ViewState::HexView
+            | ViewState::FormatView
+            | ViewState::StaleFiltersView
+            | ViewState::QuickFilterView
+            | ViewState::ProfilesView
+            | ViewState::EventScopeView
+            | ViewState::InspectorView => {
+                self.set_view_state(ViewState::LogView);
+            }
+            ViewState::CopyFormatView => {
+                self.cancel_selection();
                 self.set_view_state(ViewState::LogView);
             }
         }
@@ -1030,15 +2877,26 @@ impl App {
 
     pub fn move_up(&mut self) {
         // Handle overlay-specific navigation
+        if self.message_text().is_some() {
+            self.scroll_message(-1);
+            return;
+        }
         if let Some(Overlay::EventsFilter) = self.overlay {
             self.event_filter_list_state.move_up_wrap();
             return;
         }
+        if let Some(Overlay::RegexTester) = self.overlay {
+            self.regex_tester_list_state.move_up();
+            return;
+        }
 
         // Handle view-specific navigation
         match self.view_state {
             ViewState::FilterView => self.filter_list_state.move_up_wrap(),
             ViewState::OptionsView => self.options_list_state.move_up_wrap(),
+            ViewState::LevelsView => self.levels_list_state.move_up_wrap(),
+            ViewState::EventScopeView => self.event_scope_list_state.move_up(),
+            ViewState::ColumnsView => self.columns_list_state.move_up_wrap(),
             ViewState::EventsView => {
                 self.events_list_state.move_up();
                 self.viewport.follow_mode = false;
@@ -1049,6 +2907,72 @@ impl App {
             ViewState::FilesView => {
                 self.files_list_state.move_up();
             }
+            ViewState::SessionsView => {
+                self.sessions_list_state.move_up();
+            }
+            ViewState::HistoryView => {
+                self.history_list_state.move_up();
+            }
+            ViewState::AlertsView => {
+                self.alerts_list_state.move_up();
+            }
+            ViewState::CorrelationView => {
+                self.correlation_list_state.move_up();
+            }
+            ViewState::RegistersView => {
+                self.registers_list_state.move_up();
+            }
+            ViewState::JumpHistoryView => {
+                self.jump_history_list_state.move_up();
+            }
+            ViewState::RestartsView => {
+                self.restarts_list_state.move_up();
+            }
+            ViewState::FullLineView => {
+                self.full_line_list_state.move_up();
+            }
+            ViewState::HexView => {
+                self.hex_list_state.move_up();
+            }
+            ViewState::FormatView => {
+                self.format_list_state.move_up();
+            }
+            ViewState::StaleFiltersView => {
+                self.stale_filters_list_state.move_up();
+            }
+            ViewState::QuickFilterView => {
+                self.quick_filter_list_state.move_up();
+            }
+            ViewState::ProfilesView => {
+                self.profiles_list_state.move_up();
+            }
+            ViewState::ActionsView => {
+                self.actions_list_state.move_up();
+            }
+            ViewState::ScriptsView => {
+                self.scripts_list_state.move_up();
+            }
+            ViewState::InspectorView => {
+                self.inspector_list_state.move_up();
+            }
+            ViewState::CopyFormatView => {
+                self.copy_format_list_state.move_up();
+            }
+            ViewState::PipeOutputView => {
+                self.pipe_output_list_state.move_up();
+            }
+            ViewState::QueryResultsView => {
+                self.query_results_list_state.move_up();
+            }
+            ViewState::PatternsView => {
+                self.templates_list_state.move_up();
+            }
+            ViewState::NoiseView => {
+                self.noise_list_state.move_up();
+            }
+            ViewState::TimelineView => {
+                self.timeline_list_state.move_up();
+            }
             ViewState::SelectionMode => {
                 self.viewport.move_up();
                 self.viewport.follow_mode = false;
@@ -1063,15 +2987,26 @@ impl App {
 
     pub fn move_down(&mut self) {
         // Handle overlay-specific navigation
+        if self.message_text().is_some() {
+            self.scroll_message(1);
+            return;
+        }
         if let Some(Overlay::EventsFilter) = self.overlay {
             self.event_filter_list_state.move_down_wrap();
             return;
         }
+        if let Some(Overlay::RegexTester) = self.overlay {
+            self.regex_tester_list_state.move_down();
+            return;
+        }
 
         // Handle view-specific navigation
         match self.view_state {
             ViewState::FilterView => self.filter_list_state.move_down_wrap(),
             ViewState::OptionsView => self.options_list_state.move_down_wrap(),
+            ViewState::LevelsView => self.levels_list_state.move_down_wrap(),
+            ViewState::EventScopeView => self.event_scope_list_state.move_down(),
+            ViewState::ColumnsView => self.columns_list_state.move_down_wrap(),
             ViewState::EventsView => {
                 self.events_list_state.move_down();
             }
@@ -1081,6 +3016,72 @@ impl App {
             ViewState::FilesView => {
                 self.files_list_state.move_down();
             }
+            ViewState::SessionsView => {
+                self.sessions_list_state.move_down();
+            }
+            ViewState::HistoryView => {
+                self.history_list_state.move_down();
+            }
+            ViewState::AlertsView => {
+                self.alerts_list_state.move_down();
+            }
+            ViewState::CorrelationView => {
+                self.correlation_list_state.move_down();
+            }
+            ViewState::RegistersView => {
+                self.registers_list_state.move_down();
+            }
+            ViewState::JumpHistoryView => {
+                self.jump_history_list_state.move_down();
+            }
+            ViewState::RestartsView => {
+                self.restarts_list_state.move_down();
+            }
+            ViewState::FullLineView => {
+                self.full_line_list_state.move_down();
+            }
+            ViewState::HexView => {
+                self.hex_list_state.move_down();
+            }
+            ViewState::FormatView => {
+                self.format_list_state.move_down();
+            }
+            ViewState::StaleFiltersView => {
+                self.stale_filters_list_state.move_down();
+            }
+            ViewState::QuickFilterView => {
+                self.quick_filter_list_state.move_down();
+            }
+            ViewState::ProfilesView => {
+                self.profiles_list_state.move_down();
+            }
+            ViewState::ActionsView => {
+                self.actions_list_state.move_down();
+            }
+            ViewState::ScriptsView => {
+                self.scripts_list_state.move_down();
+            }
+            ViewState::InspectorView => {
+                self.inspector_list_state.move_down();
+            }
+            ViewState::CopyFormatView => {
+                self.copy_format_list_state.move_down();
+            }
+            ViewState::PipeOutputView => {
+                self.pipe_output_list_state.move_down();
+            }
+            ViewState::QueryResultsView => {
+                self.query_results_list_state.move_down();
+            }
+            ViewState::PatternsView => {
+                self.templates_list_state.move_down();
+            }
+            ViewState::NoiseView => {
+                self.noise_list_state.move_down();
+            }
+            ViewState::TimelineView => {
+                self.timeline_list_state.move_down();
+            }
             ViewState::SelectionMode => {
                 self.viewport.move_down();
                 self.viewport.follow_mode = false;
@@ -1093,6 +3094,15 @@ impl App {
     }
 
     pub fn page_up(&mut self) {
+        if self.message_text().is_some() {
+            self.scroll_message(-(MESSAGE_PAGE_LINES as isize));
+            return;
+        }
+        if let Some(Overlay::RegexTester) = self.overlay {
+            self.regex_tester_list_state.page_up();
+            return;
+        }
+
         match self.view_state {
             ViewState::EventsView => {
                 self.events_list_state.page_up();
@@ -1103,6 +3113,72 @@ impl App {
             ViewState::FilesView => {
                 self.files_list_state.page_up();
             }
+            ViewState::SessionsView => {
+                self.sessions_list_state.page_up();
+            }
+            ViewState::HistoryView => {
+                self.history_list_state.page_up();
+            }
+            ViewState::AlertsView => {
+                self.alerts_list_state.page_up();
+            }
+            ViewState::CorrelationView => {
+                self.correlation_list_state.page_up();
+            }
+            ViewState::RegistersView => {
+                self.registers_list_state.page_up();
+            }
+            ViewState::JumpHistoryView => {
+                self.jump_history_list_state.page_up();
+            }
+            ViewState::RestartsView => {
+                self.restarts_list_state.page_up();
+            }
+            ViewState::FullLineView => {
+                self.full_line_list_state.page_up();
+            }
+            ViewState::HexView => {
+                self.hex_list_state.page_up();
+            }
+            ViewState::FormatView => {
+                self.format_list_state.page_up();
+            }
+            ViewState::StaleFiltersView => {
+                self.stale_filters_list_state.page_up();
+            }
+            ViewState::QuickFilterView => {
+                self.quick_filter_list_state.page_up();
+            }
+            ViewState::ProfilesView => {
+                self.profiles_list_state.page_up();
+            }
+            ViewState::EventScopeView => {
+                self.event_scope_list_state.page_up();
+            }
+            ViewState::ActionsView => {
+                self.actions_list_state.page_up();
+            }
+            ViewState::ScriptsView => {
+                self.scripts_list_state.page_up();
+            }
+            ViewState::InspectorView => {
+                self.inspector_list_state.page_up();
+            }
+            ViewState::CopyFormatView => {
+                self.copy_format_list_state.page_up();
+            }
+            ViewState::PipeOutputView => {
+                self.pipe_output_list_state.page_up();
+            }
+            ViewState::QueryResultsView => {
+                self.query_results_list_state.page_up();
+            }
+            ViewState::PatternsView => {
+                self.templates_list_state.page_up();
+            }
+            ViewState::NoiseView => {
+                self.noise_list_state.page_up();
+            }
             ViewState::SelectionMode => {
                 self.viewport.page_up();
                 self.viewport.follow_mode = false;
@@ -1116,6 +3192,15 @@ impl App {
     }
 
     pub fn page_down(&mut self) {
+        if self.message_text().is_some() {
+            self.scroll_message(MESSAGE_PAGE_LINES as isize);
+            return;
+        }
+        if let Some(Overlay::RegexTester) = self.overlay {
+            self.regex_tester_list_state.page_down();
+            return;
+        }
+
         match self.view_state {
             ViewState::EventsView => {
                 self.events_list_state.page_down();
@@ -1126,6 +3211,72 @@ impl App {
             ViewState::FilesView => {
                 self.files_list_state.page_down();
             }
+            ViewState::SessionsView => {
+                self.sessions_list_state.page_down();
+            }
+            ViewState::HistoryView => {
+                self.history_list_state.page_down();
+            }
+            ViewState::AlertsView => {
+                self.alerts_list_state.page_down();
+            }
+            ViewState::CorrelationView => {
+                self.correlation_list_state.page_down();
+            }
+            ViewState::RegistersView => {
+                self.registers_list_state.page_down();
+            }
+            ViewState::JumpHistoryView => {
+                self.jump_history_list_state.page_down();
+            }
+            ViewState::RestartsView => {
+                self.restarts_list_state.page_down();
+            }
+            ViewState::FullLineView => {
+                self.full_line_list_state.page_down();
+            }
+            ViewState::HexView => {
+                self.hex_list_state.page_down();
+            }
+            ViewState::FormatView => {
+                self.format_list_state.page_down();
+            }
+            ViewState::StaleFiltersView => {
+                self.stale_filters_list_state.page_down();
+            }
+            ViewState::QuickFilterView => {
+                self.quick_filter_list_state.page_down();
+            }
+            ViewState::ProfilesView => {
+                self.profiles_list_state.page_down();
+            }
+            ViewState::EventScopeView => {
+                self.event_scope_list_state.page_down();
+            }
+            ViewState::ActionsView => {
+                self.actions_list_state.page_down();
+            }
+            ViewState::ScriptsView => {
+                self.scripts_list_state.page_down();
+            }
+            ViewState::InspectorView => {
+                self.inspector_list_state.page_down();
+            }
+            ViewState::CopyFormatView => {
+                self.copy_format_list_state.page_down();
+            }
+            ViewState::PipeOutputView => {
+                self.pipe_output_list_state.page_down();
+            }
+            ViewState::QueryResultsView => {
+                self.query_results_list_state.page_down();
+            }
+            ViewState::PatternsView => {
+                self.templates_list_state.page_down();
+            }
+            ViewState::NoiseView => {
+                self.noise_list_state.page_down();
+            }
             ViewState::SelectionMode => {
                 self.viewport.page_down();
                 self.viewport.follow_mode = false;
@@ -1137,6 +3288,36 @@ impl App {
         }
     }
 
+    pub fn half_page_up(&mut self) {
+        self.viewport.half_page_up();
+        self.viewport.follow_mode = false;
+        if self.view_state == ViewState::SelectionMode {
+            self.update_selection_end();
+        }
+    }
+
+    pub fn half_page_down(&mut self) {
+        self.viewport.half_page_down();
+        if self.view_state == ViewState::SelectionMode {
+            self.update_selection_end();
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.viewport.scroll_up();
+        self.viewport.follow_mode = false;
+        if self.view_state == ViewState::SelectionMode {
+            self.update_selection_end();
+        }
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.viewport.scroll_down();
+        if self.view_state == ViewState::SelectionMode {
+            self.update_selection_end();
+        }
+    }
+
     pub fn goto_top(&mut self) {
         self.viewport.goto_top();
         self.push_viewport_line_to_history(self.viewport.selected_line);
@@ -1151,9 +3332,12 @@ impl App {
     pub fn activate_search_mode(&mut self) {
         self.input.reset();
         self.search.clear_matches();
+        self.search_task = None;
+        self.search_result_pending = false;
         self.search.reset_case_sensitivity();
         self.search.history.reset();
         self.set_view_state(ViewState::ActiveSearchMode);
+        self.update_completion_history();
     }
 
     pub fn activate_goto_line_mode(&mut self) {
@@ -1162,12 +3346,60 @@ impl App {
         self.viewport.follow_mode = false;
     }
 
+    pub fn activate_goto_time_mode(&mut self) {
+        self.input.reset();
+        self.set_view_state(ViewState::GotoTimeMode);
+        self.viewport.follow_mode = false;
+    }
+
+    /// Jumps to the first visible line at or after the given time.
+    ///
+    /// Times of day and relative times like `-5m` are relative to the timestamp of the selected line.
+    fn goto_time(&mut self, input: &str) {
+        let Some(target) = TimeTarget::parse(input) else {
+            self.show_error(&format!(
+                "Invalid time: {}\nExpected e.g. 12:34:56, 2024-01-15 12:34:56, -5m or +1h30m",
+                input.trim()
+            ));
+            return;
+        };
+
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let timestamp = |viewport_index: usize| all_lines[visible_lines[viewport_index].log_index].timestamp;
+        let reference = (self.viewport.selected_line < visible_lines.len())
+            .then(|| timestamp(self.viewport.selected_line))
+            .flatten()
+            .or_else(|| visible_lines.iter().find_map(|vl| all_lines[vl.log_index].timestamp));
+        let Some(reference) = reference else {
+            self.show_error("No timestamps found in the visible lines");
+            return;
+        };
+
+        // Lines without a timestamp only precede the first timestamp, as later lines inherit it
+        let Some(time) = target.resolve(reference) else {
+            self.show_error(&format!("Time out of range: {}", input.trim()));
+            return;
+        };
+        let viewport_index = visible_lines
+            .partition_point(|vl| {
+                all_lines[vl.log_index]
+                    .timestamp
+                    .is_none_or(|line_time| line_time < time)
+            })
+            .min(visible_lines.len().saturating_sub(1));
+
+        self.push_viewport_line_to_history(viewport_index);
+        self.viewport.goto_line(viewport_index, true);
+    }
+
     pub fn activate_filter_mode(&mut self) {
         self.input.reset();
         self.filter.reset_mode();
         self.filter.reset_case_sensitivity();
         self.filter.history.reset();
         self.set_view_state(ViewState::ActiveFilterMode);
+        self.update_completion_history();
     }
 
     pub fn activate_filter_list_view(&mut self) {
@@ -1186,621 +3418,3112 @@ impl App {
         self.set_view_state(ViewState::OptionsView);
     }
 
-    pub fn toggle_option(&mut self) {
-        let selected_index = self.options_list_state.selected_index();
-        self.options.toggle_option(selected_index);
-        self.highlighter.invalidate_cache();
-        self.update_view();
-    }
-
-    pub fn activate_events_view(&mut self) {
-        // Scan events on first activation (events list is empty)
-        if self.event_tracker.is_empty() {
-            self.event_tracker.scan_all_lines(&self.log_buffer);
-        }
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line) {
-            if let Some(nearest_index) = self.find_nearest_event(line_index) {
-                self.events_list_state.select_index(nearest_index);
-            } else {
-                self.events_list_state.select_index(0);
-            }
-        }
-        self.set_view_state(ViewState::EventsView);
+    /// Opens the levels view with the line counts per level and the current minimum level selected.
+    pub fn activate_levels_view(&mut self) {
+        self.level_counts = count_levels(self.log_buffer.all_lines());
+        let selected_index = self.min_level.map_or(0, |level| level as usize + 1);
+        self.levels_list_state.select_index(selected_index);
+        self.set_view_state(ViewState::LevelsView);
     }
 
-    pub fn activate_event_filter_view(&mut self) {
-        if self.view_state == ViewState::EventsView {
-            self.show_overlay(Overlay::EventsFilter);
+    /// Opens the patterns view with the message patterns of the visible lines.
+    pub fn activate_patterns_view(&mut self) {
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        self.templates = count_templates(visible_lines.iter().map(|vl| all_lines[vl.log_index].content()));
+        if self.templates.is_empty() {
+            self.show_message("No lines to analyze");
+            return;
         }
+        self.templates_list_state = ListViewState::new_with_count(self.templates.len());
+        self.set_view_state(ViewState::PatternsView);
     }
 
-    pub fn activate_marks_view(&mut self) {
-        let visible_mark_count = self.get_visible_marks().len();
-        self.marking_list_state.set_item_count(visible_mark_count);
+    /// Adds a regex filter matching the lines of the selected pattern.
+    pub fn pattern_to_filter(&mut self, mode: ActiveFilterMode) {
+        let Some(template) = self.templates.get(self.templates_list_state.selected_index()) else {
+            return;
+        };
+        let Some(filter) = FilterPattern::new_regex(template_regex(&template.template), mode, true, true) else {
+            return;
+        };
 
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line) {
-            self.select_nearest_mark(line_index);
-        } else {
-            self.marking_list_state.reset();
+        let filter_count = self.filter.count();
+        self.filter.add_filter(&filter);
+        if self.filter.count() == filter_count {
+            self.show_error("Filter already exists");
+            return;
         }
 
-        self.set_view_state(ViewState::MarksView);
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
+        self.set_view_state(ViewState::LogView);
+        let mode_name = match mode {
+            ActiveFilterMode::Include => "include",
+            ActiveFilterMode::Exclude => "exclude",
+        };
+        self.show_message(format!("Added {} filter for pattern", mode_name).as_str());
     }
 
-    pub fn activate_files_view(&mut self) {
-        if !self.log_buffer.streaming {
-            self.set_view_state(ViewState::FilesView);
+    /// Opens the noise view with the most frequent message patterns of the visible lines.
+    pub fn activate_noise_view(&mut self) {
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let templates = count_templates(visible_lines.iter().map(|vl| all_lines[vl.log_index].content()));
+        self.noise_line_count = visible_lines.len();
+        self.noise_suggestions = noise_suggestions(&templates, self.noise_line_count);
+        if self.noise_suggestions.is_empty() {
+            self.show_message("No noisy patterns found");
+            return;
         }
+        self.noise_list_state = ListViewState::new_with_count(self.noise_suggestions.len());
+        self.set_view_state(ViewState::NoiseView);
     }
 
-    pub fn add_file(&mut self, path: String) {
-        let canonical = match std::fs::canonicalize(&path) {
-            Ok(p) => p,
-            Err(_) => {
-                self.show_error(&format!("File not found: {}", path));
-                return;
-            }
+    /// Adds an exclude filter for the noise suggestion at `index` and removes it from the
+    /// suggestions, so the next one can be excluded right away.
+    pub fn exclude_noise_suggestion(&mut self, index: usize) {
+        let Some(suggestion) = self.noise_suggestions.get(index) else {
+            return;
+        };
+        let Some(filter) = FilterPattern::new_regex(
+            template_regex(&suggestion.template),
+            ActiveFilterMode::Exclude,
+            true,
+            true,
+        ) else {
+            return;
         };
 
-        let already_loaded = self
-            .file_manager
-            .iter()
-            .any(|f| std::fs::canonicalize(&f.path).ok().as_deref() == Some(&canonical));
-
-        if already_loaded {
-            self.show_error(&format!("File already loaded: {}", path));
+        let filter_count = self.filter.count();
+        self.filter.add_filter(&filter);
+        if self.filter.count() == filter_count {
+            self.show_error("Filter already exists");
             return;
         }
 
-        let file_id = self.file_manager.add_file(path.clone());
-        self.files_list_state.set_item_count(self.file_manager.count());
+        let suggestion = self.noise_suggestions.remove(index);
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
 
-        if let Err(e) = self.log_buffer.add_file(&path, file_id, self.parse_timestamps) {
-            self.file_manager.remove_last();
-            self.files_list_state.set_item_count(self.file_manager.count());
-            self.show_error(&format!("Failed to load file: {}", e));
-            return;
+        if self.noise_suggestions.is_empty() {
+            self.set_view_state(ViewState::LogView);
+        } else {
+            self.noise_list_state.set_item_count(self.noise_suggestions.len());
         }
+        self.show_message(format!("Excluded {} lines matching: {}", suggestion.count, suggestion.template).as_str());
+    }
 
-        if self.parse_timestamps {
-            self.marking.clear_all();
-            self.marking_list_state.reset();
+    /// Opens the columns view, if a field extractor is configured.
+    pub fn activate_columns_view(&mut self) {
+        if !self.column_view.is_available() {
+            self.show_error("No fields configured. Add a [fields] section to the config to use columns.");
+            return;
         }
+        self.set_view_state(ViewState::ColumnsView);
+    }
 
-        self.highlighter.invalidate_cache();
-        self.event_tracker.scan_all_lines(&self.log_buffer);
-        self.update_events_view_count();
+    /// Switches between showing lines as they are and showing their fields as aligned columns.
+    pub fn toggle_column_view(&mut self) {
+        if !self.column_view.is_available() {
+            self.show_error("No fields configured. Add a [fields] section to the config to use columns.");
+            return;
+        }
+        self.column_view.toggle_enabled();
         self.update_view();
     }
 
-    pub fn toggle_file(&mut self) {
-        let selected_index = self.files_list_state.selected_index();
-        self.file_manager.toggle_enabled(selected_index);
-        self.expansion.clear();
-        self.update_view();
+    /// Shows or hides the selected column of the columns view.
+    pub fn toggle_column(&mut self) {
+        self.column_view.toggle_column(self.columns_list_state.selected_index());
     }
 
-    pub fn activate_mark_name_overlay(&mut self) {
-        // Handle EventsView with merged marks
-        if self.view_state == ViewState::EventsView {
-            if self.event_tracker.showing_marks() {
-                let (events, _) = self.get_events_for_list();
-                let visible_marks = self.get_visible_marks();
-                let merged_items = EventMarkView::merge(&events, &visible_marks, true);
+    /// Cycles sorting by the selected column of the columns view.
+    pub fn cycle_column_sort(&mut self) {
+        self.column_view.cycle_sort(self.columns_list_state.selected_index());
+        self.update_view();
+    }
 
-                if let Some(EventOrMark::Mark(mark)) = merged_items.get(self.events_list_state.selected_index()) {
-                    if let Some(name) = &mark.name {
-                        self.input = Input::new(name.clone());
-                    } else {
-                        self.input.reset();
-                    }
-                    self.show_overlay(Overlay::MarkName);
-                }
-            }
+    /// Hides lines below the given level, or shows all levels if `None`.
+    pub fn set_min_level(&mut self, min_level: Option<LogLevel>) {
+        if self.min_level == min_level {
             return;
         }
+        self.min_level = min_level;
+        self.expansion.clear();
+        self.update_view();
+    }
 
-        // Handle MarksView
-        if self.view_state == ViewState::MarksView
-            && let Some(mark) = self.get_selected_mark()
-        {
-            if let Some(name) = &mark.name {
-                self.input = Input::new(name.clone());
-            } else {
-                self.input.reset();
-            }
-            self.show_overlay(Overlay::MarkName);
+    pub fn toggle_option(&mut self) {
+        let selected_index = self.options_list_state.selected_index();
+        let sticky_header = self.options.is_enabled(AppOption::StickyHeader);
+        let height = self.log_view_height();
+        self.options.toggle_option(selected_index);
+        if self.options.is_enabled(AppOption::StickyHeader) != sticky_header {
+            self.resize_viewports(self.log_view_width(), height);
         }
+        self.highlighter.invalidate_cache();
+        self.update_view();
     }
 
-    pub fn activate_save_to_file_mode(&mut self) {
-        if self.log_buffer.streaming {
-            self.input.reset();
-            self.show_overlay(Overlay::SaveToFile);
+    pub fn activate_events_view(&mut self) {
+        // Scan the lines not scanned for events yet
+        self.event_tracker.update(&self.log_buffer);
+        self.list_query.clear();
+        self.update_events_view_count();
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line) {
+            if let Some(nearest_index) = self.find_nearest_event(line_index) {
+                self.events_list_state.select_index(nearest_index);
+            } else {
+                self.events_list_state.select_index(0);
+            }
         }
+        self.tagged_events.clear();
+        self.set_view_state(ViewState::EventsView);
     }
 
-    pub fn activate_add_custom_event_mode(&mut self) {
-        if self.view_state == ViewState::EventsView {
-            self.input.reset();
-            self.show_overlay(Overlay::AddCustomEvent);
-        }
+    pub fn activate_notes_view(&mut self) {
+        self.set_view_state(ViewState::NotesView);
     }
 
-    pub fn remove_custom_event(&mut self) {
-        let event_name = if self.overlay == Some(Overlay::EventsFilter) {
-            let event_stats = self.event_tracker.get_event_stats();
-            event_stats
-                .get(self.event_filter_list_state.selected_index())
-                .map(|es| es.name.clone())
-        } else if self.view_state == ViewState::EventsView {
-            let (events, _) = self.get_events_for_list();
-            let visible_marks = self.get_visible_marks();
-            let merged = EventMarkView::merge(&events, &visible_marks, self.event_tracker.showing_marks());
-            let selected_idx = self.events_list_state.selected_index();
-            if let Some(EventOrMark::Event(event)) = merged.get(selected_idx) {
-                Some(event.name.clone())
-            } else {
-                None
-            }
-        } else {
-            // Not in EventsFilter or EventsView mode
+    /// Jumps to the mark referenced in the notes at the cursor.
+    pub fn goto_note_reference(&mut self) {
+        let Some(name) = self.notes.reference_at_cursor() else {
+            self.show_error("No mark reference (@name) on this line");
             return;
         };
 
-        if let Some(name) = event_name {
-            if !self.event_tracker.is_custom_event(&name) {
-                return;
+        let line_index = self
+            .marking
+            .get_marks()
+            .iter()
+            .find(|mark| mark.name.as_deref() == Some(name.as_str()))
+            .map(|mark| mark.line_index);
+
+        match line_index {
+            Some(line_index) => {
+                if self
+                    .resolver
+                    .log_to_viewport(line_index, self.log_buffer.all_lines())
+                    .is_none()
+                {
+                    self.show_error(format!("Mark '{}' is hidden by the current filters", name).as_str());
+                    return;
+                }
+                self.viewport.push_history(line_index);
+                self.goto_line(line_index, true);
+                self.viewport.follow_mode = false;
+                self.set_view_state(ViewState::LogView);
             }
-
-            if let Some(pattern) = self.event_tracker.remove_custom_event(&name) {
-                self.highlighter.remove_custom_event(&pattern);
+            None => {
+                self.show_error(format!("No mark named '{}'", name).as_str());
             }
-
-            self.update_events_view_count();
         }
     }
 
-    pub fn toggle_mark(&mut self) {
-        if self.view_state == ViewState::SelectionMode {
-            if let Some((start, end)) = self.get_selection_range() {
-                let log_indices: Vec<usize> = (start..=end)
-                    .filter_map(|viewport_line| self.viewport_to_log_line_index(viewport_line))
-                    .collect();
+    /// Builds a histogram of the visible events over time and shows it.
+    pub fn activate_timeline_view(&mut self) {
+        self.event_tracker.update(&self.log_buffer);
 
-                if log_indices.is_empty() {
-                    return;
-                }
+        let points: Vec<(usize, DateTime<Utc>)> = self
+            .get_visible_events()
+            .iter()
+            .filter_map(|event| {
+                let timestamp = self.log_buffer.get_line(event.line_index)?.timestamp?;
+                Some((event.line_index, timestamp))
+            })
+            .collect();
 
-                // Check if all lines are marked
-                let all_marked = log_indices.iter().all(|&idx| self.marking.is_marked(idx));
+        let Some(timeline) = Timeline::build(&points, TIMELINE_MAX_BUCKETS) else {
+            self.show_message("No events with timestamps found");
+            return;
+        };
 
-                if all_marked {
-                    for &idx in &log_indices {
-                        self.marking.toggle_mark(idx);
-                    }
-                } else {
-                    for &idx in &log_indices {
-                        if !self.marking.is_marked(idx) {
-                            self.marking.toggle_mark(idx);
-                        }
-                    }
-                }
-            }
-        } else if self.view_state == ViewState::EventsView {
-            let (events, _) = self.get_events_for_list();
-            let visible_marks = self.get_visible_marks();
-            let merged = EventMarkView::merge(&events, &visible_marks, self.event_tracker.showing_marks());
-            let selected_idx = self.events_list_state.selected_index();
-            if let Some(line_index) = merged.get(selected_idx).map(|item| item.line_index()) {
-                self.marking.toggle_mark(line_index);
-            }
-        } else if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line) {
-            self.marking.toggle_mark(line_index);
+        self.timeline_list_state = ListViewState::new_with_count(timeline.buckets().len());
+        let current_timestamp = self
+            .viewport_to_log_line_index(self.viewport.selected_line)
+            .and_then(|line_index| self.log_buffer.get_line(line_index))
+            .and_then(|log_line| log_line.timestamp);
+        if let Some(bucket) = current_timestamp.and_then(|ts| timeline.bucket_at(ts)) {
+            self.timeline_list_state.select_index(bucket);
         }
 
-        let new_count = self.marking.count();
-        self.marking_list_state.set_item_count(new_count);
+        self.timeline = Some(timeline);
+        self.set_view_state(ViewState::TimelineView);
+    }
 
-        if self.show_marked_lines_only {
-            self.update_view();
-        } else {
-            let marked_indices = self.marking.get_marked_indices();
-            self.resolver.update_mark_tags(&marked_indices);
+    /// Jumps to the first event in the selected timeline bucket, if it has any events.
+    pub fn goto_selected_timeline_bucket(&mut self, center: bool) {
+        let line_index = self.timeline.as_ref().and_then(|timeline| {
+            timeline
+                .buckets()
+                .get(self.timeline_list_state.selected_index())
+                .and_then(|bucket| bucket.first_line_index)
+        });
+
+        if let Some(line_index) = line_index {
+            self.viewport.push_history(line_index);
+            self.goto_line(line_index, center);
+            self.viewport.follow_mode = false;
         }
     }
 
-    pub fn unmark_selected(&mut self) {
-        if let Some(mark) = self.get_selected_mark() {
-            let line_index = mark.line_index;
-            self.marking.unmark(line_index);
+    /// Starts typing a text to filter the items of the events or marks list by.
+    pub fn activate_list_filter(&mut self) {
+        if matches!(self.view_state, ViewState::EventsView | ViewState::MarksView) {
+            self.input = Input::new(self.list_query.clone());
+            self.show_overlay(Overlay::ListFilter);
+        }
+    }
 
-            let new_count = self.marking.count();
-            self.marking_list_state.set_item_count(new_count);
+    /// Filters the items of the events or marks list by the query, keeping the selection on the item
+    /// nearest to the one selected before.
+    fn set_list_query(&mut self, query: &str) {
+        if self.view_state == ViewState::EventsView {
+            let selected_line = self.selected_events_list_line();
+            self.list_query = query.to_string();
+            self.update_events_view_count();
+            self.select_nearest_events_list_item(selected_line);
+        } else {
+            let selected_line = self.get_selected_mark().map(|mark| mark.line_index);
 
-            if self.show_marked_lines_only {
-                self.update_view();
-            } else {
-                let marked_indices = self.marking.get_marked_indices();
-                self.resolver.update_mark_tags(&marked_indices);
+            self.list_query = query.to_string();
+            self.marking_list_state.set_item_count(self.get_marks_view_list().len());
+            match selected_line {
+                Some(line_index) => self.select_nearest_mark(line_index),
+                None => self.marking_list_state.select_index(0),
             }
         }
     }
 
-    /// Converts viewport index to actual log line index.
-    fn viewport_to_log_line_index(&mut self, viewport_idx: usize) -> Option<usize> {
-        let all_lines = self.log_buffer.all_lines();
-        self.resolver.viewport_to_log(viewport_idx, all_lines)
+    /// Returns the line of the item selected in the events list.
+    fn selected_events_list_line(&self) -> Option<usize> {
+        let (events, _) = self.get_events_for_list();
+        let visible_marks = self.get_listed_marks();
+        let merged = EventMarkView::merge(&events, &visible_marks, self.events_list_shows_marks());
+        merged
+            .get(self.events_list_state.selected_index())
+            .map(|item| item.line_index())
     }
 
-    pub fn toggle_case_sensitive(&mut self) {
-        self.search.toggle_case_sensitivity();
-        self.filter.toggle_case_sensitivity();
+    /// Selects the item of the events list nearest to the line, or the first item.
+    fn select_nearest_events_list_item(&mut self, line_index: Option<usize>) {
+        let (events, _) = self.get_events_for_list();
+        let visible_marks = self.get_listed_marks();
+        let merged = EventMarkView::merge(&events, &visible_marks, self.events_list_shows_marks());
+        let nearest_index = line_index.and_then(|line_index| {
+            (0..merged.len()).min_by_key(|&index| merged[index].line_index().abs_diff(line_index))
+        });
+        self.events_list_state.select_index(nearest_index.unwrap_or(0));
+    }
 
-        if self.view_state == ViewState::ActiveSearchMode {
-            let all_lines = self.log_buffer.all_lines();
-            let visible_lines = self.resolver.get_visible_lines(all_lines);
-            let content_iter = visible_lines.iter().map(|vl| all_lines[vl.log_index].content());
-            let all_content_iter = all_lines.iter().map(|log_line| log_line.content());
-            self.search
-                .update_matches(self.input.value(), content_iter, all_content_iter);
+    /// Returns the tabs of the events view with the number of the given events listed on each: all events
+    /// first, then each event type that has any, in the order of the event filters.
+    pub fn events_tabs(&self, events: &[LogEvent]) -> Vec<(Option<String>, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for event in events {
+            *counts.entry(event.name.as_str()).or_default() += 1;
+        }
+        let mut tabs = vec![(None, events.len())];
+        for event_state in self.event_tracker.get_event_stats() {
+            if let Some(&count) = counts.get(event_state.name.as_str()) {
+                tabs.push((Some(event_state.name), count));
+            }
         }
+        tabs
+    }
 
-        self.update_temporary_highlights();
+    /// Switches the events view to the next or previous tab, keeping the selection near the same line.
+    pub fn cycle_events_tab(&mut self, forward: bool) {
+        let (events, _) = self.get_events_for_all_tabs();
+        let tabs = self.events_tabs(&events);
+        let current = tabs.iter().position(|(name, _)| *name == self.events_tab).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % tabs.len()
+        } else {
+            (current + tabs.len() - 1) % tabs.len()
+        };
+
+        let selected_line = self.selected_events_list_line();
+        self.events_tab = tabs[next].0.clone();
+        self.update_events_view_count();
+        self.select_nearest_events_list_item(selected_line);
     }
 
-    pub fn search_next(&mut self) {
-        if let Some(line) = self.search.next_match(self.viewport.selected_line) {
-            self.push_viewport_line_to_history(line);
-            self.viewport.goto_line(line, false);
-        }
+    /// Returns whether marks are listed among the events: only on the tab with all events.
+    pub fn events_list_shows_marks(&self) -> bool {
+        self.event_tracker.showing_marks() && self.events_tab.is_none()
     }
 
-    pub fn search_previous(&mut self) {
-        if let Some(line) = self.search.previous_match(self.viewport.selected_line) {
-            self.push_viewport_line_to_history(line);
-            self.viewport.goto_line(line, false);
+    /// Checks if a list item with the given name and line matches the list query, ignoring case. Whether a line
+    /// matches is kept until the query or the lines change, so the list isn't searched again on every frame.
+    fn matches_list_query(&self, name: &str, line_index: usize) -> bool {
+        if self.list_query.is_empty() || contains_ignore_case(name, &self.list_query, self.case_folding) {
+            return true;
         }
+        let mut matches = self.list_query_matches.borrow_mut();
+        let (generation, query, line_matches) = &mut *matches;
+        if *generation != self.log_buffer.generation() || *query != self.list_query {
+            *generation = self.log_buffer.generation();
+            query.clone_from(&self.list_query);
+            line_matches.clear();
+        }
+        *line_matches.entry(line_index).or_insert_with(|| {
+            self.log_buffer
+                .get_line(line_index)
+                .is_some_and(|log_line| contains_ignore_case(log_line.content(), &self.list_query, self.case_folding))
+        })
     }
 
-    pub fn mark_next(&mut self) {
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(next_mark_line) = self.get_next_mark(line_index)
-        {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_mark_line, all_lines) {
-                self.viewport.push_history(next_mark_line);
-                self.viewport.goto_line(viewport_idx, false);
-            }
+    pub fn activate_event_filter_view(&mut self) {
+        if self.view_state == ViewState::EventsView {
+            self.show_overlay(Overlay::EventsFilter);
         }
     }
 
-    pub fn mark_previous(&mut self) {
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(prev_mark_line) = self.get_previous_mark(line_index)
-        {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_mark_line, all_lines) {
-                self.viewport.push_history(prev_mark_line);
-                self.viewport.goto_line(viewport_idx, false);
-            }
+    pub fn activate_marks_view(&mut self) {
+        self.list_query.clear();
+        let visible_mark_count = self.get_listed_marks().len();
+        self.marking_list_state.set_item_count(visible_mark_count);
+
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line) {
+            self.select_nearest_mark(line_index);
+        } else {
+            self.marking_list_state.reset();
         }
+
+        self.set_view_state(ViewState::MarksView);
     }
 
-    pub fn filter_on_context(&mut self) {
-        if let Some(capture_value) = self.active_context_capture_value() {
-            self.filter.add_filter_from_pattern(&capture_value);
-            self.filter_list_state.set_item_count(self.filter.count());
-            self.update_view();
+    pub fn activate_files_view(&mut self) {
+        if !self.log_buffer.streaming {
+            self.set_view_state(ViewState::FilesView);
         }
     }
 
-    pub fn context_next(&mut self) {
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(next_line) = self.get_next_context_capture_line(line_index)
-        {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_line, all_lines) {
-                self.viewport.push_history(next_line);
-                self.viewport.goto_line(viewport_idx, false);
-            }
+    pub fn add_file(&mut self, path: String) {
+        let canonical = match std::fs::canonicalize(&path) {
+            Ok(p) => p,
+            Err(_) => {
+                self.show_error(&format!("File not found: {}", path));
+                return;
+            }
+        };
+
+        let already_loaded = self
+            .file_manager
+            .iter()
+            .any(|f| std::fs::canonicalize(&f.path).ok().as_deref() == Some(&canonical));
+
+        if already_loaded {
+            self.show_error(&format!("File already loaded: {}", path));
+            return;
         }
-    }
 
-    pub fn context_previous(&mut self) {
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(prev_line) = self.get_previous_context_capture_line(line_index)
-        {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_line, all_lines) {
-                self.viewport.push_history(prev_line);
-                self.viewport.goto_line(viewport_idx, false);
-            }
+        if self.load_task.is_some() {
+            self.show_error("Files can be added once the opened file is loaded");
+            return;
         }
-    }
 
-    pub fn event_next(&mut self) {
-        let line_index = self.viewport_to_log_line_index(self.viewport.selected_line);
-        let next_line = match line_index {
-            Some(line_idx) if self.overlay == Some(Overlay::EventsFilter) => {
-                self.get_next_event_line_by_filter(line_idx)
-            }
-            Some(line_idx) => self.get_next_event_line(line_idx),
-            None => None,
-        };
-        if let Some(next_event_line) = next_line {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_event_line, all_lines) {
-                self.viewport.push_history(next_event_line);
-                self.viewport.goto_line(viewport_idx, false);
-            }
+        let file_id = self.file_manager.add_file(path.clone());
+        self.files_list_state.set_item_count(self.file_manager.count());
+
+        if let Err(e) = self.log_buffer.add_file(&path, file_id, self.parse_timestamps) {
+            self.file_manager.remove_last();
+            self.files_list_state.set_item_count(self.file_manager.count());
+            self.show_error(&format!("Failed to load file: {}", e));
+            return;
         }
-    }
 
-    pub fn event_previous(&mut self) {
-        let line_index = self.viewport_to_log_line_index(self.viewport.selected_line);
-        let prev_line = match line_index {
-            Some(line_idx) if self.overlay == Some(Overlay::EventsFilter) => {
-                self.get_previous_event_line_by_filter(line_idx)
-            }
-            Some(line_idx) => self.get_previous_event_line(line_idx),
-            None => None,
-        };
-        if let Some(prev_event_line) = prev_line {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_event_line, all_lines) {
-                self.viewport.push_history(prev_event_line);
-                self.viewport.goto_line(viewport_idx, false);
-            }
+        if self.parse_timestamps {
+            self.marking.clear_all();
+            self.marking_list_state.reset();
+            self.registers.clear();
         }
+
+        self.highlighter.invalidate_cache();
+        self.event_tracker.scan_all_lines(&self.log_buffer);
+        self.update_events_view_count();
+        self.update_view();
     }
 
-    pub fn select_to_event_next(&mut self) {
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(next_event_line) = self.get_next_event_line(line_index)
-        {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_event_line, all_lines) {
-                self.viewport.goto_line(viewport_idx, false);
-                self.update_selection_end();
-            }
+    pub fn activate_save_session_mode(&mut self) {
+        if self.log_buffer.streaming || self.file_manager.is_empty() {
+            self.show_error("Sessions are only available when viewing files");
+            return;
         }
+        self.input = Input::new(self.session_name.clone().unwrap_or_default());
+        self.show_overlay(Overlay::SaveSession);
     }
 
-    pub fn select_to_event_previous(&mut self) {
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(prev_event_line) = self.get_previous_event_line(line_index)
-        {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_event_line, all_lines) {
-                self.viewport.goto_line(viewport_idx, false);
-                self.update_selection_end();
+    /// Saves the opened files and the application state as a named session.
+    fn save_session_as(&mut self, name: String) {
+        match save_session(&name, &Session::from_app(self)) {
+            Ok(path) => {
+                self.session_name = Some(name);
+                self.show_message(format!("Session saved to:\n{}", path.display()).as_str());
             }
+            Err(e) => self.show_error(format!("Failed to save session:\n{}", e).as_str()),
         }
     }
 
-    pub fn select_to_mark_next(&mut self) {
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(next_mark_line) = self.get_next_mark(line_index)
+    /// Opens the list of saved sessions.
+    pub fn activate_sessions_view(&mut self) {
+        if self.log_buffer.streaming {
+            self.show_error("Sessions are only available when viewing files");
+            return;
+        }
+        self.sessions = list_sessions();
+        if self.sessions.is_empty() {
+            self.show_message("No saved sessions");
+            return;
+        }
+        self.sessions_list_state = ListViewState::new_with_count(self.sessions.len());
+        if let Some(index) = self
+            .session_name
+            .as_ref()
+            .and_then(|name| self.sessions.iter().position(|session| session == name))
         {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_mark_line, all_lines) {
-                self.viewport.goto_line(viewport_idx, false);
-                self.update_selection_end();
-            }
+            self.sessions_list_state.select_index(index);
         }
+        self.set_view_state(ViewState::SessionsView);
     }
 
-    pub fn select_to_mark_previous(&mut self) {
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(prev_mark_line) = self.get_previous_mark(line_index)
-        {
-            let all_lines = self.log_buffer.all_lines();
-            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_mark_line, all_lines) {
-                self.viewport.goto_line(viewport_idx, false);
-                self.update_selection_end();
-            }
+    /// Opens the list of recent search and filter queries, most recent first.
+    pub fn activate_history_view(&mut self) {
+        let count = self.global_history.records().len();
+        if count == 0 {
+            self.show_message("History is empty");
+            return;
         }
+        self.history_list_state = ListViewState::new_with_count(count);
+        self.set_view_state(ViewState::HistoryView);
     }
 
-    /// Helper to go to a log line by its log line index. If the line is not visible, it does nothing.
-    pub fn goto_line(&mut self, log_index: usize, center: bool) {
-        let all_lines = self.log_buffer.all_lines();
-        if let Some(viewport_idx) = self.resolver.log_to_viewport(log_index, all_lines) {
-            self.viewport.goto_line(viewport_idx, center);
+    /// Opens the list of fired alerts, most recent first.
+    pub fn activate_alerts_view(&mut self) {
+        if self.alerts.is_empty() {
+            self.show_message("No alert rules. Add them to the config with [[alerts]]");
+            return;
+        }
+        let count = self.alerts.firings().len();
+        if count == 0 {
+            self.show_message("No alerts fired");
+            return;
         }
+        self.alert_toast = None;
+        self.alerts_list_state = ListViewState::new_with_count(count);
+        self.set_view_state(ViewState::AlertsView);
     }
 
-    /// Helper to record a viewport line in history by converting from viewport index to log index.
-    fn push_viewport_line_to_history(&mut self, viewport_line: usize) {
-        if let Some(line_index) = self.viewport_to_log_line_index(viewport_line) {
-            self.viewport.push_history(line_index);
-        }
+    /// Returns the fired alerts, most recent first.
+    pub fn alert_firings(&self) -> impl Iterator<Item = &AlertFiring> {
+        self.alerts.firings().iter().rev()
     }
 
-    pub fn scroll_right(&mut self, small_increment: bool) {
-        let (start, end) = self.viewport.visible();
+    /// Opens the list of runs of the application, with the run of the selected line selected.
+    pub fn activate_restarts_view(&mut self) {
+        let count = self.restarts.boundaries().len();
+        if count == 0 {
+            self.show_message("No restarts found. Set restart_pattern in the config to find them");
+            return;
+        }
+        let current_run = self
+            .viewport_to_log_line_index(self.viewport.selected_line)
+            .map(|line_index| {
+                self.restarts
+                    .boundaries()
+                    .partition_point(|&boundary| boundary <= line_index)
+            })
+            .unwrap_or(0);
+        self.restarts_list_state = ListViewState::new_with_count(count);
+        self.restarts_list_state.select_index(current_run.saturating_sub(1));
+        self.set_view_state(ViewState::RestartsView);
+    }
 
-        let all_lines = self.log_buffer.all_lines();
-        let visible_lines = self.resolver.get_visible_lines(all_lines);
+    fn goto_selected_restart(&mut self) {
+        let Some(&line_index) = self
+            .restarts
+            .boundaries()
+            .get(self.restarts_list_state.selected_index())
+        else {
+            return;
+        };
+        self.viewport.follow_mode = false;
+        self.viewport.push_history(line_index);
+        self.goto_line(line_index, true);
+    }
 
-        // Calculate max length in the visible viewport range
-        let max_line_length = if start < visible_lines.len() {
-            let range_end = end.min(visible_lines.len());
-            visible_lines[start..range_end]
-                .iter()
-                .map(|vl| all_lines[vl.log_index].content.len())
-                .max()
-                .unwrap_or(0)
-        } else {
-            0
+    /// Goes to the line that fired the selected alert.
+    fn goto_selected_alert(&mut self) {
+        let Some(line_index) = self
+            .alert_firings()
+            .nth(self.alerts_list_state.selected_index())
+            .map(|firing| firing.line_index)
+        else {
+            return;
         };
+        if self
+            .resolver
+            .log_to_viewport(line_index, self.log_buffer.all_lines())
+            .is_none()
+        {
+            self.show_error("The line of the alert is hidden by the current filters");
+            return;
+        }
+        self.viewport.follow_mode = false;
+        self.viewport.push_history(line_index);
+        self.goto_line(line_index, true);
+    }
 
-        if small_increment {
-            self.viewport.scroll_right_small(max_line_length);
-        } else {
-            self.viewport.scroll_right(max_line_length);
+    pub fn activate_correlate_mode(&mut self) {
+        if self.view_state == ViewState::LogView {
+            self.input = Input::new(self.correlation_pattern.clone());
+            self.show_overlay(Overlay::Correlate);
         }
     }
 
-    pub fn toggle_follow_mode(&mut self) {
-        if self.log_buffer.streaming {
-            self.viewport.follow_mode = !self.viewport.follow_mode;
-            if self.viewport.follow_mode {
-                self.viewport.goto_bottom();
+    /// Lists the lines sharing the id the pattern captures from the selected line.
+    fn correlate(&mut self, pattern: &str) {
+        let regex = match Regex::new(pattern) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.show_error(format!("Invalid pattern:\n{}", e).as_str());
+                return;
             }
+        };
+        self.correlation_pattern = pattern.to_string();
+
+        let Some(log_index) = self.viewport_to_log_line_index(self.viewport.selected_line) else {
+            self.close_overlay();
+            return;
+        };
+        let all_lines = self.log_buffer.all_lines();
+        let Some(correlation) = Correlation::find(&regex, &all_lines[log_index], all_lines) else {
+            self.show_message("The pattern does not capture an id from the selected line");
+            return;
+        };
+
+        self.correlation_list_state = ListViewState::new_with_count(correlation.line_indices.len());
+        if let Ok(position) = correlation.line_indices.binary_search(&log_index) {
+            self.correlation_list_state.select_index(position);
         }
+        self.correlation = Some(correlation);
+        self.close_overlay();
+        self.set_view_state(ViewState::CorrelationView);
     }
 
-    pub fn toggle_pause_mode(&mut self) {
-        if self.log_buffer.streaming {
-            self.streaming_paused = !self.streaming_paused;
+    /// Goes to the selected correlated line, keeping the line correlated from in the history to jump back to.
+    fn goto_selected_correlated_line(&mut self) {
+        let Some(line_index) = self
+            .correlation
+            .as_ref()
+            .and_then(|correlation| {
+                correlation
+                    .line_indices
+                    .get(self.correlation_list_state.selected_index())
+            })
+            .copied()
+        else {
+            return;
+        };
+        if let Some(current_index) = self.viewport_to_log_line_index(self.viewport.selected_line) {
+            self.viewport.push_history(current_index);
         }
+        self.viewport.follow_mode = false;
+        self.viewport.push_history(line_index);
+        self.goto_line(line_index, true);
     }
 
-    pub fn toggle_center_cursor_mode(&mut self) {
-        self.viewport.center_cursor_mode = !self.viewport.center_cursor_mode;
-        if self.viewport.center_cursor_mode {
-            self.viewport.center_selected();
+    /// Adds an include filter for the correlated id, showing the correlated lines in the log view.
+    pub fn correlation_to_filter(&mut self) {
+        let Some(correlation) = self.correlation.clone() else {
+            return;
+        };
+        // A whole word match keeps e.g. id 42 from matching the lines of id 142
+        let filter =
+            FilterPattern::new(correlation.value.clone(), ActiveFilterMode::Include, true, true).with_whole_word(true);
+
+        let filter_count = self.filter.count();
+        self.filter.add_filter(&filter);
+        if self.filter.count() == filter_count {
+            self.show_error("Filter already exists");
+            return;
         }
+
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
+        self.set_view_state(ViewState::LogView);
+        self.show_message(format!("Added include filter for {} {}", correlation.name, correlation.value).as_str());
     }
 
-    pub fn toggle_help(&mut self) {
-        if self.help.is_visible() {
-            self.help.toggle_visibility();
+    /// Shows the alerts fired since `first_alert` as a toast and rings the bell or sends desktop
+    /// notifications for the rules that ask for it, unless the rule did so within the cooldown.
+    fn announce_alerts(&mut self, first_alert: usize) {
+        let firings = &self.alerts.firings()[first_alert..];
+        let Some(last) = firings.last() else {
+            return;
+        };
+
+        let rules = self.alerts.rules();
+        let line = self
+            .log_buffer
+            .get_line(last.line_index)
+            .map(|line| line.content().trim().to_string())
+            .unwrap_or_default();
+        let toast = if firings.len() == 1 {
+            format!("{}: {}", rules[last.rule_index].name, line)
         } else {
-            self.help.show_for_context(&self.view_state, &self.overlay);
+            format!(
+                "{} alerts, last {}: {}",
+                firings.len(),
+                rules[last.rule_index].name,
+                line
+            )
+        };
+
+        // Each rule rings or notifies at most once per cooldown, however many lines fired it
+        let now = Instant::now();
+        let mut bell = false;
+        let mut notified = Vec::new();
+        let mut announced = HashSet::new();
+        for firing in firings {
+            let rule = &rules[firing.rule_index];
+            if (rule.bell || rule.notify)
+                && announced.insert(firing.rule_index)
+                && self
+                    .announce_cooldown
+                    .try_announce(&format!("alert:{}", rule.name), now)
+            {
+                bell |= rule.bell;
+                if rule.notify {
+                    notified.push(rule.name.clone());
+                }
+            }
         }
-    }
 
-    pub fn history_back(&mut self) {
-        if let Some(line_index) = self.viewport.history_back() {
-            self.goto_line(line_index, false);
+        if bell {
+            self.ring_bell();
         }
-        self.viewport.follow_mode = false;
+        for name in notified {
+            self.send_notification(&name, &line);
+        }
+        self.alert_toast = Some((toast, now));
     }
 
-    pub fn history_forward(&mut self) {
-        if let Some(line_index) = self.viewport.history_forward() {
-            self.goto_line(line_index, false);
+    /// Rings the bell or sends desktop notifications for the events on the lines from `first_line` on whose
+    /// pattern asks for it, once per event type and at most once per cooldown, shared with the alerts.
+    fn announce_events(&mut self, first_line: usize) {
+        let now = Instant::now();
+        let mut bell = false;
+        let mut notified = Vec::new();
+        let mut announced = HashSet::new();
+        for (event, pattern) in self.event_tracker.announced_events(first_line) {
+            if announced.insert(&pattern.name)
+                && self
+                    .announce_cooldown
+                    .try_announce(&format!("event:{}", pattern.name), now)
+            {
+                bell |= pattern.bell;
+                if pattern.notify {
+                    let line = self
+                        .log_buffer
+                        .get_line(event.line_index)
+                        .map(|line| line.content().trim().to_string())
+                        .unwrap_or_default();
+                    notified.push((pattern.name.clone(), line));
+                }
+            }
+        }
+
+        if bell {
+            self.ring_bell();
+        }
+        for (name, line) in notified {
+            self.send_notification(&name, &line);
         }
-        self.viewport.follow_mode = false;
     }
 
-    pub fn clear_log_buffer(&mut self) {
-        if self.log_buffer.streaming {
-            self.log_buffer.clear_all();
-            self.marking.clear_all();
-            self.event_tracker.clear_all();
-            self.highlighter.invalidate_cache();
-            self.viewport.reset_view();
-            self.update_view();
+    /// Rings the terminal bell, unless notifications are muted.
+    fn ring_bell(&self) {
+        if self.options.is_disabled(AppOption::MuteNotifications) {
+            let mut stderr = std::io::stderr();
+            let _ = stderr.write_all(b"\x07").and_then(|_| stderr.flush());
         }
     }
 
-    pub fn clear_all_marks(&mut self) {
-        self.marking.clear_all();
+    /// Sends a desktop notification, unless notifications are muted.
+    fn send_notification(&self, name: &str, line: &str) {
+        if self.options.is_enabled(AppOption::MuteNotifications) {
+            return;
+        }
+        let title = format!("lazylog: {}", name);
+        let command = format!("notify-send {} {}", shell_quote(&title), shell_quote(line));
+        if let Err(e) = spawn_command(&command) {
+            debug!("Failed to send desktop notification: {}", e);
+        }
+    }
 
-        if self.show_marked_lines_only {
-            self.update_view();
+    pub fn toggle_mute_notifications(&mut self) {
+        self.options.toggle(AppOption::MuteNotifications);
+        if self.options.is_enabled(AppOption::MuteNotifications) {
+            self.show_message("Muted bells and desktop notifications");
         } else {
-            let marked_indices = self.marking.get_marked_indices();
-            self.resolver.update_mark_tags(&marked_indices);
+            self.show_message("Unmuted bells and desktop notifications");
         }
     }
 
-    pub fn toggle_filter_pattern_active(&mut self) {
-        let selected_index = self.filter_list_state.selected_index();
-        self.filter.toggle_pattern_enabled(selected_index);
-        self.expansion.clear();
-        self.update_view();
+    /// Returns the global history records, most recent first.
+    pub fn history_records(&self) -> impl Iterator<Item = &HistoryRecord> {
+        self.global_history.records().iter().rev()
     }
 
-    pub fn remove_filter_pattern(&mut self) {
-        let selected_index = self.filter_list_state.selected_index();
-        self.filter.remove_pattern(selected_index);
-        self.filter_list_state.set_item_count(self.filter.count());
-        self.expansion.clear();
-        self.update_view();
+    /// Opens the search or filter mode of the selected query with the query as input.
+    fn use_selected_history_record(&mut self) {
+        let Some(record) = self
+            .history_records()
+            .nth(self.history_list_state.selected_index())
+            .cloned()
+        else {
+            return;
+        };
+        match record.kind {
+            QueryKind::Search => {
+                self.activate_search_mode();
+                if self.search.is_case_sensitive() != record.case_sensitive {
+                    self.search.toggle_case_sensitivity();
+                }
+                self.search.set_whole_word(record.whole_word);
+            }
+            QueryKind::Filter(mode) => {
+                self.activate_filter_mode();
+                self.filter.set_mode(mode);
+                self.filter.set_case_sensitivity(record.case_sensitive);
+                self.filter.set_whole_word(record.whole_word);
+            }
+        }
+        self.input = Input::new(record.pattern);
+        self.update_temporary_highlights();
     }
 
-    pub fn toggle_filter_pattern_case_sensitive(&mut self) {
-        let selected_index = self.filter_list_state.selected_index();
-        self.filter.toggle_pattern_case_sensitivity(selected_index);
+    /// Replaces the opened files and the application state with those of the selected session.
+    ///
+    /// The state of the files opened before is saved first, as when quitting.
+    fn open_selected_session(&mut self) {
+        let Some(name) = self.sessions.get(self.sessions_list_state.selected_index()).cloned() else {
+            return;
+        };
+        let session = match load_session(&name) {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                self.show_error(&format!("Session not found: {}", name));
+                return;
+            }
+            Err(e) => {
+                self.show_error(&format!("Failed to load session:\n{}", e));
+                return;
+            }
+        };
+
+        let paths: Vec<&str> = session.files().iter().map(String::as_str).collect();
+        if self.format_choice == FormatChoice::Auto {
+            self.detected_format = paths.first().and_then(|path| LogFormat::detect_in_file(path));
+        }
+        let mut log_buffer = self.new_log_buffer();
+        if let Err(e) = log_buffer.load_files(&paths, self.parse_timestamps) {
+            self.show_error(&format!(
+                "Failed to load file(s) of session: {}\nError: {}",
+                paths.join(", "),
+                e
+            ));
+            return;
+        }
+
+        if self.persist_enabled && !self.file_manager.is_empty() && self.load_task.is_none() {
+            save_state(&self.file_manager.paths(), self);
+        }
+        if let Some(current) = &self.session_name {
+            let _ = save_session(current, &Session::from_app(self));
+        }
+        self.replace_with_session(name, session, log_buffer);
+    }
+
+    /// Replaces the opened files and the application state with those of a session whose files were loaded into
+    /// `log_buffer`. The filters, marks, viewport and the rest of the state of the files opened before are reset
+    /// first, so none of it is mixed into the session.
+    fn replace_with_session(&mut self, name: String, session: Session, log_buffer: LogBuffer) {
+        self.log_buffer = log_buffer;
+        self.file_manager = FileManager::new(session.files());
+        self.files_list_state.set_item_count(self.file_manager.count());
+
+        self.filter = Filter::with_patterns(Vec::new()).with_case_folding(self.case_folding);
+        self.filter_list_state.set_item_count(0);
+        self.marking.clear_all();
+        self.marking_list_state.reset();
+        self.registers.clear();
+        for pattern in self.event_tracker.remove_custom_events() {
+            self.highlighter.remove_custom_event(&pattern);
+        }
+        let enabled_events: Vec<(String, bool)> = self
+            .event_tracker
+            .get_event_stats()
+            .into_iter()
+            .map(|event| (event.name, true))
+            .collect();
+        self.event_tracker.restore_filter_states(&enabled_events);
+        self.clear_search();
+        self.close_split();
+        self.viewport.reset_view();
         self.expansion.clear();
+        self.selection_range = None;
+        self.filter_task = None;
+        self.filter_mask = None;
+        self.load_task = None;
+        self.pipe_task = None;
+
+        self.highlighter.invalidate_cache();
+        self.event_tracker.scan_all_lines(&self.log_buffer);
+        self.update_format_columns();
         self.update_view();
+        self.restore_session(session);
+        self.update_events_view_count();
+        self.update_completion_words();
+
+        self.set_view_state(ViewState::LogView);
+        self.show_message(format!("Opened session {}", name).as_str());
+        self.session_name = Some(name);
     }
 
-    pub fn toggle_filter_pattern_mode(&mut self) {
-        let selected_index = self.filter_list_state.selected_index();
-        self.filter.toggle_pattern_mode(selected_index);
+    /// Re-reads the opened files from disk, keeping filters, search and marks on lines that still exist.
+    pub fn reload_file(&mut self) {
+        if self.log_buffer.streaming || self.file_manager.is_empty() {
+            self.show_error("Reload is only available when viewing files");
+            return;
+        }
+
+        let Some((added, removed, removed_marks)) = self.reload_lines() else {
+            return;
+        };
+        let mut message = format!("Reloaded: {} lines added, {} lines removed", added, removed);
+        if removed_marks > 0 {
+            message.push_str(&format!("\n{} marks were on removed lines", removed_marks));
+        }
+        self.show_message(&message);
+    }
+
+    /// Re-reads the opened files with the current log format, keeping the state on lines that still exist.
+    /// Returns the number of added and removed lines and of the marks on removed lines.
+    fn reload_lines(&mut self) -> Option<(usize, usize, usize)> {
+        let mut log_buffer = self.new_log_buffer();
+        if let Err(e) = log_buffer.load_files(&self.file_manager.paths(), self.parse_timestamps) {
+            self.show_error(&format!("Failed to reload file: {}", e));
+            return None;
+        }
+
+        let selected_log_index = self.viewport_to_log_line_index(self.viewport.selected_line);
+        let old_buffer = std::mem::replace(&mut self.log_buffer, log_buffer);
+        let line_map = match_lines_by_content(old_buffer.all_lines(), self.log_buffer.all_lines());
+        let new_index = |line_index: usize| line_map.get(line_index).copied().flatten();
+
+        let matched = line_map.iter().flatten().count();
+        let added = self.log_buffer.get_total_lines_count() - matched;
+        let removed = old_buffer.get_total_lines_count() - matched;
+
+        self.log_buffer.hide_lines(
+            old_buffer
+                .hidden_lines()
+                .iter()
+                .filter_map(|&line_index| new_index(line_index)),
+        );
+        let mark_count = self.marking.count();
+        self.marking.remap_lines(new_index);
+        self.selected_marks.clear();
+        let removed_marks = mark_count - self.marking.count();
+        self.marking_list_state.set_item_count(self.marking.count());
+        self.registers.remap_lines(new_index);
+        self.viewport.remap_history(new_index);
         self.expansion.clear();
+        self.selection_range = None;
+        self.filter_task = None;
+        self.filter_mask = None;
+        self.load_task = None;
+        self.pipe_task = None;
+
+        self.highlighter.invalidate_cache();
+        self.event_tracker.scan_all_lines(&self.log_buffer);
+        self.update_events_view_count();
+        self.update_completion_words();
+        self.update_format_columns();
         self.update_view();
+
+        // Stay on the same line, or at the same position if it was removed
+        let last_index = self.log_buffer.get_total_lines_count().checked_sub(1);
+        if let (Some(old_index), Some(last_index)) = (selected_log_index, last_index) {
+            let target = new_index(old_index).unwrap_or(old_index.min(last_index));
+            self.goto_line(target, false);
+        }
+
+        Some((added, removed, removed_marks))
     }
 
-    pub fn toggle_all_filter_patterns(&mut self) {
-        self.filter.toggle_all_patterns_enabled();
+    /// Returns the format the lines are parsed with: the chosen one, or the detected one when chosen automatically.
+    pub fn log_format(&self) -> Option<LogFormat> {
+        match self.format_choice {
+            FormatChoice::Auto => self.detected_format,
+            FormatChoice::None => None,
+            FormatChoice::Format(format) => Some(format),
+        }
+    }
+
+    /// Returns the level detector of the lines. Configured levels take precedence over the log format.
+    fn level_detector(&self) -> LevelDetector {
+        match self.log_format() {
+            Some(format) if self.config.levels.is_none() => format.level_detector(),
+            _ => self.config.parse_level_detector(),
+        }
+    }
+
+    /// Returns the parser of the timestamps of the lines. Configured formats take precedence over the log format.
+    fn timestamp_parser(&self) -> TimestampParser {
+        self.config.parse_timestamp_parser(self.log_format())
+    }
+
+    /// Returns an empty buffer parsing lines with the current log format, to replace the current buffer.
+    fn new_log_buffer(&self) -> LogBuffer {
+        let mut log_buffer = LogBuffer::default();
+        log_buffer.continue_generations(&self.log_buffer);
+        log_buffer.set_level_detector(self.level_detector());
+        log_buffer.set_timestamp_parser(self.timestamp_parser());
+        log_buffer.set_ansi_mode(self.ansi_mode);
+        log_buffer
+    }
+
+    /// Shows the fields of the log format as columns, unless fields are configured.
+    fn update_format_columns(&mut self) {
+        if self.config.fields.is_some() {
+            return;
+        }
+        let extractor = self.log_format().and_then(|format| {
+            format.field_extractor(
+                self.log_buffer
+                    .all_lines()
+                    .iter()
+                    .take(DETECTION_LINES)
+                    .map(LogLine::content),
+            )
+        });
+        self.column_view = ColumnView::new(extractor);
+        self.columns_list_state.set_item_count(self.column_view.columns().len());
+        self.update_processor_context();
+    }
+
+    /// Detects the format of the stream from the lines received so far.
+    fn detect_stream_format(&mut self) {
+        self.format_detection_pending = false;
+        self.detected_format = LogFormat::detect(self.log_buffer.all_lines().iter().map(LogLine::content));
+        if self.detected_format.is_some() {
+            self.reparse_stream_lines();
+        }
+    }
+
+    /// Detects the levels and records of the streamed lines again with the current log format.
+    fn reparse_stream_lines(&mut self) {
+        self.log_buffer.set_level_detector(self.level_detector());
+        self.log_buffer.set_timestamp_parser(self.timestamp_parser());
+        self.log_buffer.redetect_stream_lines();
+        self.update_format_columns();
+        self.highlighter.invalidate_cache();
+        self.event_tracker.scan_all_lines(&self.log_buffer);
+        self.update_events_view_count();
+        self.update_view();
+    }
+
+    pub fn activate_format_view(&mut self) {
+        let selected = FormatChoice::all()
+            .iter()
+            .position(|&choice| choice == self.format_choice)
+            .unwrap_or_default();
+        self.format_list_state.select_index(selected);
+        self.set_view_state(ViewState::FormatView);
+    }
+
+    /// Parses the lines with the format selected in the format view.
+    fn select_format(&mut self) {
+        let Some(&choice) = FormatChoice::all().get(self.format_list_state.selected_index()) else {
+            return;
+        };
+        if self.load_task.is_some() {
+            self.show_error("The log format can be changed once the file is loaded");
+            return;
+        }
+        self.format_choice = choice;
+        if self.log_buffer.streaming {
+            if choice == FormatChoice::Auto {
+                self.detect_stream_format();
+            } else {
+                self.format_detection_pending = false;
+                self.reparse_stream_lines();
+            }
+        } else if !self.file_manager.is_empty() {
+            if choice == FormatChoice::Auto {
+                self.detected_format = self
+                    .file_manager
+                    .paths()
+                    .first()
+                    .and_then(|path| LogFormat::detect_in_file(path));
+            }
+            if self.reload_lines().is_none() {
+                return;
+            }
+        }
+        let name = self.log_format().map_or("none", |format| format.name());
+        self.show_message(&format!("Log format: {}", name));
+    }
+
+    pub fn toggle_file(&mut self) {
+        let selected_index = self.files_list_state.selected_index();
+        self.file_manager.toggle_enabled(selected_index);
         self.expansion.clear();
         self.update_view();
     }
 
-    pub fn toggle_show_marked_only(&mut self) {
-        self.show_marked_lines_only = !self.show_marked_lines_only;
-        self.update_view();
+    pub fn activate_mark_name_overlay(&mut self) {
+        // Handle EventsView with merged marks
+        if self.view_state == ViewState::EventsView {
+            if self.events_list_shows_marks() {
+                let (events, _) = self.get_events_for_list();
+                let visible_marks = self.get_listed_marks();
+                let merged_items = EventMarkView::merge(&events, &visible_marks, true);
+
+                if let Some(EventOrMark::Mark(mark)) = merged_items.get(self.events_list_state.selected_index()) {
+                    if let Some(name) = &mark.name {
+                        self.input = Input::new(name.clone());
+                    } else {
+                        self.input.reset();
+                    }
+                    self.show_overlay(Overlay::MarkName);
+                }
+            }
+            return;
+        }
+
+        // Handle MarksView, renaming all selected marks if any are selected
+        if self.view_state == ViewState::MarksView && !self.selected_marks.is_empty() {
+            self.input.reset();
+            self.show_overlay(Overlay::MarkName);
+        } else if self.view_state == ViewState::MarksView
+            && let Some(mark) = self.get_selected_mark()
+        {
+            if let Some(name) = &mark.name {
+                self.input = Input::new(name.clone());
+            } else {
+                self.input.reset();
+            }
+            self.show_overlay(Overlay::MarkName);
+        }
+    }
+
+    pub fn activate_save_to_file_mode(&mut self) {
+        if self.log_buffer.streaming {
+            self.input.reset();
+            self.show_overlay(Overlay::SaveToFile);
+        }
+    }
+
+    pub fn activate_add_custom_event_mode(&mut self) {
+        if self.view_state == ViewState::EventsView {
+            self.input.reset();
+            self.custom_event_step = CustomEventStep::Pattern;
+            self.show_overlay(Overlay::AddCustomEvent);
+        }
+    }
+
+    /// Takes the input as the pattern, name or color of the custom event being added and moves on to
+    /// the next field. The event is added once the color is entered.
+    fn confirm_custom_event_step(&mut self) {
+        let value = self.input.value().trim().to_string();
+        match std::mem::take(&mut self.custom_event_step) {
+            CustomEventStep::Pattern => {
+                if value.is_empty() {
+                    self.close_overlay();
+                    return;
+                }
+                if self.event_tracker.has_plain_pattern(&value) {
+                    self.show_error(&format!("An event with the pattern '{}' already exists", value));
+                    return;
+                }
+                // Suggest the default name, so Enter keeps it
+                self.input = Input::new(LogEventTracker::custom_event_name(&value));
+                self.custom_event_step = CustomEventStep::Name { pattern: value };
+            }
+            CustomEventStep::Name { pattern } => {
+                let name = if value.is_empty() {
+                    LogEventTracker::custom_event_name(&pattern)
+                } else {
+                    value
+                };
+                if self.event_tracker.has_event(&name) {
+                    self.show_error(&format!("An event named '{}' already exists", name));
+                    return;
+                }
+                self.input.reset();
+                self.custom_event_step = CustomEventStep::Color { pattern, name };
+            }
+            CustomEventStep::Color { pattern, name } => {
+                let color = if value.is_empty() {
+                    None
+                } else if let Some(color) = Config::parse_color(&value) {
+                    Some(color)
+                } else {
+                    self.show_error(&format!(
+                        "Invalid color: {}\nExpected a name like red, a palette index like 214 or a hex value like #ff8800",
+                        value
+                    ));
+                    return;
+                };
+                self.close_overlay();
+                self.add_custom_event(&pattern, true, Some(&name), color);
+            }
+        }
+    }
+
+    /// Returns the name of the selected event in the events view or the event filter list.
+    fn selected_event_name(&self) -> Option<String> {
+        if self.overlay == Some(Overlay::EventsFilter) {
+            let event_stats = self.event_tracker.get_event_stats();
+            event_stats
+                .get(self.event_filter_list_state.selected_index())
+                .map(|es| es.name.clone())
+        } else if self.view_state == ViewState::EventsView {
+            let (events, _) = self.get_events_for_list();
+            let visible_marks = self.get_listed_marks();
+            let merged = EventMarkView::merge(&events, &visible_marks, self.events_list_shows_marks());
+            let selected_idx = self.events_list_state.selected_index();
+            if let Some(EventOrMark::Event(event)) = merged.get(selected_idx) {
+                Some(event.name.clone())
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    pub fn remove_custom_event(&mut self) {
+        let Some(name) = self.selected_event_name() else {
+            return;
+        };
+        if !self.event_tracker.is_custom_event(&name) {
+            return;
+        }
+
+        if let Some(pattern) = self.event_tracker.remove_custom_event(&name) {
+            self.highlighter.remove_custom_event(&pattern);
+        }
+        if self.event_scope.as_ref() == Some(&name) {
+            self.event_scope = None;
+        }
+        if self.events_tab.as_ref() == Some(&name) {
+            self.events_tab = None;
+        }
+
+        self.update_events_view_count();
+    }
+
+    /// Appends the selected custom event to the config file, so it is tracked in every file.
+    pub fn save_custom_event_to_config(&mut self) {
+        let Some(name) = self.selected_event_name() else {
+            return;
+        };
+        let custom_events = self.event_tracker.get_custom_events();
+        let Some(event) = custom_events.iter().find(|event| event.name == name) else {
+            self.show_error("Only custom events with a plain pattern can be saved to the config");
+            return;
+        };
+
+        match self.config.save_event(EventConfig::from(event)) {
+            Ok(path) => self.show_message(&format!("Saved event '{}' to {}", name, path.display())),
+            Err(e) => self.show_error(&format!("Failed to save event to config:\n{}", e)),
+        }
+    }
+
+    pub fn toggle_mark(&mut self) {
+        if self.view_state == ViewState::SelectionMode {
+            if let Some((start, end)) = self.get_selection_range() {
+                let log_indices: Vec<usize> = (start..=end)
+                    .filter_map(|viewport_line| self.viewport_to_log_line_index(viewport_line))
+                    .collect();
+
+                if log_indices.is_empty() {
+                    return;
+                }
+
+                // Check if all lines are marked
+                let all_marked = log_indices.iter().all(|&idx| self.marking.is_marked(idx));
+
+                if all_marked {
+                    for &idx in &log_indices {
+                        self.marking.toggle_mark(idx);
+                    }
+                } else {
+                    for &idx in &log_indices {
+                        if !self.marking.is_marked(idx) {
+                            self.marking.toggle_mark(idx);
+                        }
+                    }
+                }
+            }
+        } else if self.view_state == ViewState::EventsView {
+            let (events, _) = self.get_events_for_list();
+            let visible_marks = self.get_listed_marks();
+            let merged = EventMarkView::merge(&events, &visible_marks, self.events_list_shows_marks());
+            let selected_idx = self.events_list_state.selected_index();
+            if let Some(line_index) = merged.get(selected_idx).map(|item| item.line_index()) {
+                self.marking.toggle_mark(line_index);
+            }
+        } else if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line) {
+            self.marking.toggle_mark(line_index);
+        }
+
+        let new_count = self.marking.count();
+        self.marking_list_state.set_item_count(new_count);
+        let marking = &self.marking;
+        self.selected_marks.retain(|&line_index| marking.is_marked(line_index));
+
+        if self.show_marked_lines_only {
+            self.update_view();
+        } else {
+            let marked_indices = self.marking.get_marked_indices();
+            self.resolver.update_mark_tags(&marked_indices);
+        }
+    }
+
+    /// Hides the selected lines in selection mode, otherwise the selected line, until all lines are unhidden.
+    pub fn hide_lines(&mut self) {
+        let log_indices: Vec<usize> = match self.get_selection_range() {
+            Some((start, end)) if self.view_state == ViewState::SelectionMode => (start..=end)
+                .filter_map(|viewport_line| self.viewport_to_log_line_index(viewport_line))
+                .collect(),
+            _ => self
+                .viewport_to_log_line_index(self.viewport.selected_line)
+                .into_iter()
+                .collect(),
+        };
+        self.selection_range = None;
+        self.set_view_state(ViewState::LogView);
+        if log_indices.is_empty() {
+            return;
+        }
+
+        self.log_buffer.hide_lines(log_indices);
+        self.expansion.clear();
+        self.update_view();
+    }
+
+    /// Shows all lines hidden with [`App::hide_lines`] again.
+    pub fn unhide_all_lines(&mut self) {
+        let count = self.log_buffer.unhide_all();
+        if count == 0 {
+            self.show_message("No hidden lines");
+            return;
+        }
+        self.update_view();
+        self.show_message(&format!("Unhid {} line{}", count, if count == 1 { "" } else { "s" }));
+    }
+
+    pub fn unmark_selected(&mut self) {
+        if !self.selected_marks.is_empty() {
+            let count = self.selected_marks.len();
+            for line_index in std::mem::take(&mut self.selected_marks) {
+                self.marking.unmark(line_index);
+            }
+            self.marking_list_state.set_item_count(self.get_marks_view_list().len());
+            if self.show_marked_lines_only {
+                self.update_view();
+            } else {
+                let marked_indices = self.marking.get_marked_indices();
+                self.resolver.update_mark_tags(&marked_indices);
+            }
+            self.show_message(&format!("Deleted {} mark{}", count, if count == 1 { "" } else { "s" }));
+        } else if let Some(mark) = self.get_selected_mark() {
+            let line_index = mark.line_index;
+            self.marking.unmark(line_index);
+            self.selected_marks.remove(&line_index);
+
+            let new_count = self.marking.count();
+            self.marking_list_state.set_item_count(new_count);
+
+            if self.show_marked_lines_only {
+                self.update_view();
+            } else {
+                let marked_indices = self.marking.get_marked_indices();
+                self.resolver.update_mark_tags(&marked_indices);
+            }
+        }
+    }
+
+    /// Converts viewport index to actual log line index.
+    fn viewport_to_log_line_index(&mut self, viewport_idx: usize) -> Option<usize> {
+        let all_lines = self.log_buffer.all_lines();
+        self.resolver.viewport_to_log(viewport_idx, all_lines)
+    }
+
+    /// Toggles fuzzy matching of the search being typed.
+    pub fn toggle_fuzzy_search(&mut self) {
+        self.search.toggle_fuzzy();
+        self.update_temporary_highlights();
+    }
+
+    /// Toggles between searching the filtered lines and all lines, revealing matches hidden by filters.
+    pub fn toggle_search_scope(&mut self) {
+        self.search.toggle_scope();
+        if self.search.get_active_pattern().is_some() {
+            self.update_view();
+            if self.view_state == ViewState::LogView && self.search.scope() == SearchScope::Filtered {
+                self.show_message("Matches hidden by filters are hidden again");
+            }
+        }
+    }
+
+    /// Clears the search and hides matches revealed by it again.
+    fn clear_search(&mut self) {
+        let revealed = self.search.reveals_hidden_matches();
+        self.search.clear_matches();
+        self.search_task = None;
+        self.search_result_pending = false;
+        if revealed {
+            self.update_view();
+        }
+    }
+
+    pub fn toggle_case_sensitive(&mut self) {
+        self.search.toggle_case_sensitivity();
+        self.filter.toggle_case_sensitivity();
+
+        if self.view_state == ViewState::ActiveSearchMode {
+            let all_lines = self.log_buffer.all_lines();
+            let visible_lines = self.resolver.get_visible_lines(all_lines);
+            let content_iter = visible_lines.iter().map(|vl| all_lines[vl.log_index].content());
+            let all_content_iter = all_lines.iter().map(|log_line| log_line.content());
+            self.search
+                .update_matches(self.input.value(), content_iter, all_content_iter);
+        }
+
+        self.update_temporary_highlights();
+    }
+
+    /// Toggles whole word matching of the search or filter being typed.
+    pub fn toggle_whole_word(&mut self) {
+        match self.view_state {
+            ViewState::ActiveSearchMode => self.search.toggle_whole_word(),
+            ViewState::ActiveFilterMode => self.filter.toggle_whole_word(),
+            _ => return,
+        }
+        self.update_temporary_highlights();
+    }
+
+    pub fn search_next(&mut self) {
+        if let Some(line) = self.search.next_match(self.viewport.selected_line) {
+            self.push_viewport_line_to_history(line);
+            self.viewport.goto_line(line, false);
+            self.scroll_to_search_match();
+        }
+    }
+
+    pub fn search_previous(&mut self) {
+        if let Some(line) = self.search.previous_match(self.viewport.selected_line) {
+            self.push_viewport_line_to_history(line);
+            self.viewport.goto_line(line, false);
+            self.scroll_to_search_match();
+        }
+    }
+
+    /// Scrolls horizontally so the first search match on the selected line is centered, if it is not visible.
+    fn scroll_to_search_match(&mut self) {
+        if self.options.is_enabled(AppOption::SearchDisableHorizontalScroll) {
+            return;
+        }
+        let Some(log_index) = self.viewport_to_log_line_index(self.viewport.selected_line) else {
+            return;
+        };
+        let Some(log_line) = self.log_buffer.get_line(log_index) else {
+            return;
+        };
+
+        // Offsets are relative to the displayed line, so match against the transformed content
+        let line = self.options.apply_to_line(log_line.content());
+        if let Some((start, end)) = self.search.find_first_match(line) {
+            self.viewport
+                .center_columns(byte_to_column(line, start), byte_to_column(line, end));
+            self.search_scroll = Some((log_index, self.viewport.horizontal_offset));
+        }
+    }
+
+    /// Keeps following search matches horizontally once the selection leaves a line scrolled to for a match.
+    ///
+    /// Scrolls to the match on the newly selected line, or back to the start if the line has no match.
+    /// Stops following when the user scrolled horizontally in the meantime.
+    fn follow_search_scroll(&mut self) {
+        let Some((log_index, offset)) = self.search_scroll else {
+            return;
+        };
+        if self.viewport.horizontal_offset != offset {
+            self.search_scroll = None;
+            return;
+        }
+        if self.viewport_to_log_line_index(self.viewport.selected_line) == Some(log_index) {
+            return;
+        }
+
+        self.search_scroll = None;
+        self.scroll_to_search_match();
+        if self.search_scroll.is_none() {
+            self.viewport.reset_horizontal();
+        }
+    }
+
+    pub fn mark_next(&mut self) {
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(next_mark_line) = self
+                .cycle_selected_marks(line_index, true)
+                .or(self.get_next_mark(line_index))
+        {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_mark_line, all_lines) {
+                self.viewport.push_history(next_mark_line);
+                self.viewport.goto_line(viewport_idx, false);
+            }
+        }
+    }
+
+    pub fn mark_previous(&mut self) {
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(prev_mark_line) = self
+                .cycle_selected_marks(line_index, false)
+                .or(self.get_previous_mark(line_index))
+        {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_mark_line, all_lines) {
+                self.viewport.push_history(prev_mark_line);
+                self.viewport.goto_line(viewport_idx, false);
+            }
+        }
+    }
+
+    pub fn filter_on_context(&mut self) {
+        if let Some(capture_value) = self.active_context_capture_value() {
+            self.filter.add_filter_from_pattern(&capture_value);
+            self.filter_list_state.set_item_count(self.filter.count());
+            self.update_view();
+        }
+    }
+
+    pub fn context_next(&mut self) {
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(next_line) = self.get_next_context_capture_line(line_index)
+        {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_line, all_lines) {
+                self.viewport.push_history(next_line);
+                self.viewport.goto_line(viewport_idx, false);
+            }
+        }
+    }
+
+    pub fn context_previous(&mut self) {
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(prev_line) = self.get_previous_context_capture_line(line_index)
+        {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_line, all_lines) {
+                self.viewport.push_history(prev_line);
+                self.viewport.goto_line(viewport_idx, false);
+            }
+        }
+    }
+
+    /// Goes to the next visible line with the same template as the selected line.
+    pub fn similar_next(&mut self) {
+        if let Some(viewport_idx) = self.find_similar_line(true) {
+            self.push_viewport_line_to_history(viewport_idx);
+            self.viewport.goto_line(viewport_idx, false);
+        }
+    }
+
+    /// Goes to the previous visible line with the same template as the selected line.
+    pub fn similar_previous(&mut self) {
+        if let Some(viewport_idx) = self.find_similar_line(false) {
+            self.push_viewport_line_to_history(viewport_idx);
+            self.viewport.goto_line(viewport_idx, false);
+        }
+    }
+
+    /// Finds the viewport index of the nearest visible line sharing the selected line's template.
+    fn find_similar_line(&mut self, forward: bool) -> Option<usize> {
+        let selected = self.viewport.selected_line;
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let template = line_template(all_lines.get(visible_lines.get(selected)?.log_index)?.content());
+        let is_similar = |idx: &usize| line_template(all_lines[visible_lines[*idx].log_index].content()) == template;
+
+        if forward {
+            (selected + 1..visible_lines.len()).find(is_similar)
+        } else {
+            (0..selected).rev().find(is_similar)
+        }
+    }
+
+    /// Opens the list of event types to choose the one the event navigation jumps between, with the
+    /// current choice selected.
+    pub fn activate_event_scope_view(&mut self) {
+        let event_stats = self.event_tracker.get_event_stats();
+        if event_stats.is_empty() {
+            self.show_message("No events defined");
+            return;
+        }
+        let selected_index = self
+            .event_scope
+            .as_ref()
+            .and_then(|scope| event_stats.iter().position(|es| &es.name == scope))
+            .map_or(0, |index| index + 1);
+        self.event_scope_list_state = ListViewState::new_with_count(event_stats.len() + 1);
+        self.event_scope_list_state.select_index(selected_index);
+        self.set_view_state(ViewState::EventScopeView);
+    }
+
+    /// Scopes the event navigation to the selected event type, or to any event for the first entry.
+    fn set_event_scope_from_selection(&mut self) {
+        self.event_scope = self
+            .event_scope_list_state
+            .selected_index()
+            .checked_sub(1)
+            .and_then(|index| self.event_tracker.get_event_stats().into_iter().nth(index))
+            .map(|es| es.name);
+    }
+
+    pub fn event_next(&mut self) {
+        let line_index = self.viewport_to_log_line_index(self.viewport.selected_line);
+        let next_line = match line_index {
+            Some(line_idx) if self.overlay == Some(Overlay::EventsFilter) => {
+                self.get_next_event_line_by_filter(line_idx)
+            }
+            Some(line_idx) => self.get_next_event_line(line_idx),
+            None => None,
+        };
+        if let Some(next_event_line) = next_line {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_event_line, all_lines) {
+                self.viewport.push_history(next_event_line);
+                self.viewport.goto_line(viewport_idx, false);
+            }
+        }
+    }
+
+    pub fn event_previous(&mut self) {
+        let line_index = self.viewport_to_log_line_index(self.viewport.selected_line);
+        let prev_line = match line_index {
+            Some(line_idx) if self.overlay == Some(Overlay::EventsFilter) => {
+                self.get_previous_event_line_by_filter(line_idx)
+            }
+            Some(line_idx) => self.get_previous_event_line(line_idx),
+            None => None,
+        };
+        if let Some(prev_event_line) = prev_line {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_event_line, all_lines) {
+                self.viewport.push_history(prev_event_line);
+                self.viewport.goto_line(viewport_idx, false);
+            }
+        }
+    }
+
+    /// Goes to the next event of the given severity that is visible in the log view.
+    pub fn severity_event_next(&mut self, severity: LogLevel) {
+        let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line) else {
+            return;
+        };
+        let all_lines = self.log_buffer.all_lines();
+        let next = self
+            .event_tracker
+            .severity_lines_after(severity, line_index)
+            .find_map(|line| Some((line, self.resolver.log_to_viewport(line, all_lines)?)));
+        if let Some((next_event_line, viewport_idx)) = next {
+            self.viewport.push_history(next_event_line);
+            self.viewport.goto_line(viewport_idx, false);
+        }
+    }
+
+    pub fn select_to_event_next(&mut self) {
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(next_event_line) = self.get_next_event_line(line_index)
+        {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_event_line, all_lines) {
+                self.viewport.goto_line(viewport_idx, false);
+                self.update_selection_end();
+            }
+        }
+    }
+
+    pub fn select_to_event_previous(&mut self) {
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(prev_event_line) = self.get_previous_event_line(line_index)
+        {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_event_line, all_lines) {
+                self.viewport.goto_line(viewport_idx, false);
+                self.update_selection_end();
+            }
+        }
+    }
+
+    pub fn select_to_mark_next(&mut self) {
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(next_mark_line) = self.get_next_mark(line_index)
+        {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(next_mark_line, all_lines) {
+                self.viewport.goto_line(viewport_idx, false);
+                self.update_selection_end();
+            }
+        }
+    }
+
+    pub fn select_to_mark_previous(&mut self) {
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(prev_mark_line) = self.get_previous_mark(line_index)
+        {
+            let all_lines = self.log_buffer.all_lines();
+            if let Some(viewport_idx) = self.resolver.log_to_viewport(prev_mark_line, all_lines) {
+                self.viewport.goto_line(viewport_idx, false);
+                self.update_selection_end();
+            }
+        }
+    }
+
+    /// Processes the lines left of a stream that ended and runs the startup commands waiting for them.
+    fn finish_stream_input(&mut self) {
+        while self.events.has_new_lines() && !self.streaming_paused {
+            self.process_new_lines();
+        }
+        if self.format_detection_pending {
+            self.detect_stream_format();
+        }
+        self.run_startup_commands();
+    }
+
+    /// Runs the commands given on the command line, in the order they were given.
+    fn run_startup_commands(&mut self) {
+        for command in std::mem::take(&mut self.startup_commands) {
+            match command {
+                StartupCommand::GotoEnd => self.goto_bottom(),
+                StartupCommand::GotoLine(line_number) => {
+                    // Like in less, a line number past the end goes to the last line
+                    if let Some(last_line) = self.viewport.total_lines.checked_sub(1) {
+                        let line = (line_number - 1).min(last_line);
+                        self.push_viewport_line_to_history(line);
+                        self.viewport.goto_line(line, true);
+                    }
+                }
+                StartupCommand::Search(pattern) => {
+                    self.search.set_pattern(&pattern);
+                    if self.search.reveals_hidden_matches() {
+                        self.update_view();
+                    } else {
+                        self.update_search_matches();
+                    }
+                    // While following a stream the matches are highlighted as they arrive
+                    if !self.viewport.follow_mode {
+                        self.search_result_pending = true;
+                        self.report_search_result();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Helper to go to a log line by its log line index. If the line is not visible, it does nothing.
+    pub fn goto_line(&mut self, log_index: usize, center: bool) {
+        let all_lines = self.log_buffer.all_lines();
+        if let Some(viewport_idx) = self.resolver.log_to_viewport(log_index, all_lines) {
+            self.viewport.goto_line(viewport_idx, center);
+        }
+    }
+
+    /// Helper to record a viewport line in history by converting from viewport index to log index.
+    fn push_viewport_line_to_history(&mut self, viewport_line: usize) {
+        if let Some(line_index) = self.viewport_to_log_line_index(viewport_line) {
+            self.viewport.push_history(line_index);
+        }
+    }
+
+    pub fn scroll_right(&mut self, small_increment: bool) {
+        let (start, end) = self.viewport.visible();
+
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+
+        // Calculate max length in the visible viewport range
+        let max_line_length = if start < visible_lines.len() {
+            let range_end = end.min(visible_lines.len());
+            visible_lines[start..range_end]
+                .iter()
+                .map(|vl| display_width(&all_lines[vl.log_index].content))
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        if small_increment {
+            self.viewport.scroll_right_small(max_line_length);
+        } else {
+            self.viewport.scroll_right(max_line_length);
+        }
+    }
+
+    /// Returns the display width of the selected line as shown in the log view, as a row of columns in the
+    /// column view.
+    pub fn selected_line_width(&self) -> Option<usize> {
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let selected = self.viewport.selected_line;
+        let log_line = &all_lines[visible_lines.get(selected)?.log_index];
+
+        let (start, end) = self.viewport.visible();
+        if (start..end).contains(&selected) {
+            let rows = self.column_rows(visible_lines[start..end.min(visible_lines.len())].iter());
+            if let Some(Some(row)) = rows.get(selected - start) {
+                return Some(display_width(row));
+            }
+        }
+        Some(display_width(self.options.apply_to_line(log_line.content())))
+    }
+
+    /// Returns the lines as rows of aligned columns if the column view is on, or no rows otherwise. Columns are
+    /// aligned over the given lines, which are those in view. Lines without fields, e.g. stack traces, have no row
+    /// and are shown as is.
+    pub fn column_rows<'a>(&self, lines: impl Iterator<Item = &'a VisibleLine>) -> Vec<Option<String>> {
+        if !self.column_view.is_enabled() {
+            return Vec::new();
+        }
+        let all_lines = self.log_buffer.all_lines();
+        let rows: Vec<_> = lines
+            .map(|vl| self.column_view.visible_values(all_lines[vl.log_index].content()))
+            .collect();
+        align_columns(&rows)
+    }
+
+    /// Scrolls horizontally to show the end of the selected line.
+    pub fn scroll_to_line_end(&mut self) {
+        if let Some(width) = self.selected_line_width() {
+            self.viewport.scroll_to_columns(width, width);
+        }
+    }
+
+    /// Returns the word of the selected line at the left edge of the view, or the first word after it.
+    fn word_under_cursor(&mut self) -> Option<String> {
+        let log_index = self.viewport_to_log_line_index(self.viewport.selected_line)?;
+        let content = self
+            .options
+            .apply_to_line(self.log_buffer.get_line(log_index)?.content());
+        word_at_column(content, self.viewport.horizontal_offset).map(str::to_string)
+    }
+
+    /// Searches for the word under the cursor as a whole word, like vim's `*`.
+    pub fn search_word_under_cursor(&mut self) {
+        let Some(word) = self.word_under_cursor() else {
+            self.show_message("No word under the cursor");
+            return;
+        };
+        self.search.set_whole_word(true);
+        self.search.set_pattern(&word);
+        self.global_history
+            .add(QueryKind::Search, &word, self.search.is_case_sensitive(), true);
+        if self.search.reveals_hidden_matches() {
+            self.update_view();
+        } else {
+            self.update_search_matches();
+        }
+        self.search_result_pending = true;
+        self.report_search_result();
+    }
+
+    /// Adds an include filter for the word under the cursor as a whole word.
+    pub fn filter_word_under_cursor(&mut self) {
+        let Some(word) = self.word_under_cursor() else {
+            self.show_message("No word under the cursor");
+            return;
+        };
+        let filter = FilterPattern::new(word.clone(), ActiveFilterMode::Include, true, true).with_whole_word(true);
+
+        let filter_count = self.filter.count();
+        self.filter.add_filter(&filter);
+        if self.filter.count() == filter_count {
+            self.show_error("Filter already exists");
+            return;
+        }
+
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
+        self.show_message(format!("Added include filter for '{}'", word).as_str());
+    }
+
+    /// Marks all lines containing the word under the cursor, named after the word.
+    pub fn mark_word_under_cursor(&mut self) {
+        let Some(word) = self.word_under_cursor() else {
+            self.show_message("No word under the cursor");
+            return;
+        };
+        let mark_count = self.marking.count();
+        self.marking
+            .create_marks_from_pattern(&word, self.log_buffer.iter(), self.case_folding);
+        let new_marks = self.marking.count() - mark_count;
+        self.marking_list_state.set_item_count(self.marking.count());
+
+        if self.show_marked_lines_only {
+            self.update_view();
+        } else {
+            let marked_indices = self.marking.get_marked_indices();
+            self.resolver.update_mark_tags(&marked_indices);
+        }
+        self.show_message(&format!(
+            "Marked {} line{} containing '{}'",
+            new_marks,
+            if new_marks == 1 { "" } else { "s" },
+            word
+        ));
+    }
+
+    pub fn toggle_follow_mode(&mut self) {
+        if self.log_buffer.streaming {
+            self.viewport.follow_mode = !self.viewport.follow_mode;
+            if self.viewport.follow_mode {
+                self.viewport.goto_bottom();
+            }
+        }
+    }
+
+    /// Resizes the viewports to the log view size, dividing it between the panes in split view.
+    fn resize_viewports(&mut self, width: usize, height: usize) {
+        // The sticky header takes the top row of the log view
+        let height = if self.options.is_enabled(AppOption::StickyHeader) {
+            height.saturating_sub(1)
+        } else {
+            height
+        };
+        let Some(split) = &mut self.split_viewport else {
+            self.viewport.resize(width, height);
+            return;
+        };
+        if self.split_pinned {
+            // The pinned pane has a title row
+            let (left, right) = split_widths(width);
+            self.viewport.resize(left, height);
+            split.resize(right, height.saturating_sub(1));
+            return;
+        }
+        let (top, bottom) = split_heights(height);
+        let (focused, other) = if self.split_focus_top {
+            (top, bottom)
+        } else {
+            (bottom, top)
+        };
+        self.viewport.resize(width, focused);
+        split.resize(width, other);
+    }
+
+    /// Returns the height of the whole log view, including both panes in split view and the sticky header.
+    fn log_view_height(&self) -> usize {
+        let split_height = match &self.split_viewport {
+            Some(_) if self.split_pinned => 0,
+            Some(split) => split.height + 1,
+            None => 0,
+        };
+        self.viewport.height + split_height + usize::from(self.options.is_enabled(AppOption::StickyHeader))
+    }
+
+    /// Returns the width of the whole log view, including the pinned pane and the separator before it.
+    fn log_view_width(&self) -> usize {
+        match &self.split_viewport {
+            Some(split) if self.split_pinned => self.viewport.width + split.width + 2,
+            _ => self.viewport.width,
+        }
+    }
+
+    /// Splits the log view into two panes showing the same lines, each with its own position.
+    pub fn split_view(&mut self) {
+        if self.split_viewport.is_some() {
+            return;
+        }
+        if self.viewport.height < MIN_SPLIT_HEIGHT {
+            self.show_error("The terminal is too small to split the view");
+            return;
+        }
+
+        let height = self.log_view_height();
+        self.split_viewport = Some(self.viewport.clone());
+        self.split_focus_top = true;
+        self.resize_viewports(self.viewport.width, height);
+    }
+
+    /// Pins the current position into a read-only pane to the right, to compare it with the lines navigated to
+    /// in the focused pane, like the state before and after a change in the same file.
+    pub fn pin_split_view(&mut self) {
+        if self.split_viewport.is_some() {
+            self.show_error("Close the split view before pinning a pane");
+            return;
+        }
+        if self.viewport.width < MIN_PIN_WIDTH {
+            self.show_error("The terminal is too narrow to pin a pane");
+            return;
+        }
+
+        let (width, height) = (self.log_view_width(), self.log_view_height());
+        let mut pinned = self.viewport.clone();
+        pinned.follow_mode = false;
+        self.split_viewport = Some(pinned);
+        self.split_focus_top = true;
+        self.split_pinned = true;
+        self.resize_viewports(width, height);
+    }
+
+    pub fn switch_split_focus(&mut self) {
+        if self.split_pinned {
+            self.show_message("The pinned pane keeps its position, close it with Ctrl+x");
+            return;
+        }
+        if let Some(split) = &mut self.split_viewport {
+            std::mem::swap(&mut self.viewport, split);
+            self.split_focus_top = !self.split_focus_top;
+        }
+    }
+
+    /// Closes the split view, keeping the focused pane.
+    pub fn close_split(&mut self) {
+        if self.split_viewport.is_none() {
+            return;
+        }
+        let (width, height) = (self.log_view_width(), self.log_view_height());
+        self.split_viewport = None;
+        self.split_pinned = false;
+        self.resize_viewports(width, height);
+    }
+
+    pub fn toggle_pause_mode(&mut self) {
+        if self.log_buffer.streaming {
+            self.streaming_paused = !self.streaming_paused;
+        }
+    }
+
+    /// Freezes the display while incoming lines keep being buffered. Unfreezing shows the buffered lines
+    /// and selects the first of them, so reading continues where the display was frozen.
+    pub fn toggle_freeze_display(&mut self) {
+        if !self.log_buffer.streaming {
+            return;
+        }
+        match self.display_frozen_at.take() {
+            None => {
+                self.display_frozen_at = Some(self.log_buffer.get_total_lines_count());
+                self.update_view();
+            }
+            Some(first_new_line) => {
+                self.update_view();
+                let visible_lines = self.resolver.get_visible_lines(self.log_buffer.all_lines());
+                let first_visible = visible_lines.iter().position(|vl| vl.log_index >= first_new_line);
+                if let Some(viewport_line) = first_visible {
+                    self.viewport.follow_mode = false;
+                    self.viewport.goto_line(viewport_line, false);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of lines received since the display was frozen.
+    pub fn frozen_new_line_count(&self) -> Option<usize> {
+        self.display_frozen_at
+            .map(|first_new_line| self.log_buffer.get_total_lines_count().saturating_sub(first_new_line))
+    }
+
+    pub fn toggle_center_cursor_mode(&mut self) {
+        self.viewport.center_cursor_mode = !self.viewport.center_cursor_mode;
+        if self.viewport.center_cursor_mode {
+            self.viewport.center_selected();
+        }
+    }
+
+    pub fn toggle_help(&mut self) {
+        if self.help.is_visible() {
+            self.help.toggle_visibility();
+        } else {
+            self.help.show_for_context(&self.view_state, &self.overlay);
+        }
+    }
+
+    pub fn history_back(&mut self) {
+        let back = match self.viewport_to_log_line_index(self.viewport.selected_line) {
+            Some(current_index) => self.viewport.history_back_from(current_index),
+            None => self.viewport.history_back(),
+        };
+        if let Some(line_index) = back {
+            self.goto_line(line_index, false);
+        }
+        self.viewport.follow_mode = false;
+    }
+
+    /// Opens the list of positions in the navigation history, with the current position selected.
+    pub fn activate_jump_history_view(&mut self) {
+        let count = self.viewport.history().len();
+        if count == 0 {
+            self.show_message("No jumps in the history yet");
+            return;
+        }
+        self.jump_history_list_state = ListViewState::new_with_count(count);
+        self.jump_history_list_state
+            .select_index(count - 1 - self.viewport.history_position().min(count - 1));
+        self.set_view_state(ViewState::JumpHistoryView);
+    }
+
+    /// Goes to the position selected in the navigation history view.
+    fn goto_selected_jump(&mut self) {
+        let count = self.viewport.history().len();
+        let Some(position) = count.checked_sub(1 + self.jump_history_list_state.selected_index()) else {
+            return;
+        };
+        if let Some(line_index) = self.viewport.goto_history(position) {
+            self.goto_line(line_index, false);
+        }
+        self.viewport.follow_mode = false;
+    }
+
+    pub fn history_forward(&mut self) {
+        if let Some(line_index) = self.viewport.history_forward() {
+            self.goto_line(line_index, false);
+        }
+        self.viewport.follow_mode = false;
+    }
+
+    /// Starts the spawned command, streaming its output into the log buffer.
+    fn start_child_process(&mut self) {
+        let Some(line_tx) = self.events.line_sender() else {
+            return;
+        };
+        let Some(child_process) = &mut self.child_process else {
+            return;
+        };
+        let on_exit = self.events.app_sender();
+        child_process.start(line_tx, move |generation, status| {
+            on_exit(AppEvent::CommandExited(generation, status))
+        });
+
+        if let ChildStatus::Failed(e) = child_process.status() {
+            let message = format!("Failed to run '{}':\n{}", child_process.command_line(), e);
+            self.show_error(&message);
+        }
+    }
+
+    /// Kills and restarts the spawned command.
+    pub fn restart_child_process(&mut self) {
+        let Some(child_process) = &self.child_process else {
+            self.show_error("No command to restart. Run a command with: lazylog -- <command>");
+            return;
+        };
+        let command_line = child_process.command_line();
+        self.start_child_process();
+        if self.child_process.as_ref().map(ChildProcess::status) == Some(&ChildStatus::Running) {
+            self.show_message(format!("Restarted '{}'", command_line).as_str());
+        }
+    }
+
+    pub fn clear_log_buffer(&mut self) {
+        if self.log_buffer.streaming {
+            self.log_buffer.clear_all();
+            if self.display_frozen_at.is_some() {
+                self.display_frozen_at = Some(0);
+            }
+            self.marking.clear_all();
+            self.selected_marks.clear();
+            self.registers.clear();
+            self.event_tracker.clear_all();
+            self.tagged_events.clear();
+            self.watches.clear_values();
+            self.alerts.clear();
+            // Annotations for the cleared lines that are still on their way are ignored
+            self.script_line_offset += self.log_buffer.get_total_lines_count();
+            self.script_fields.clear();
+            self.highlighter.invalidate_cache();
+            self.viewport.reset_view();
+            if let Some(split) = &mut self.split_viewport {
+                split.reset_view();
+            }
+            self.update_view();
+        }
+    }
+
+    /// Drops the oldest lines when the buffer exceeds the maximum line count, along with a chunk of the lines
+    /// below it, and rebases everything referring to log line indices.
+    fn drop_lines_over_max(&mut self) {
+        let Some(max_lines) = self.max_lines else {
+            return;
+        };
+        let excess = self.log_buffer.get_total_lines_count().saturating_sub(max_lines);
+        if excess == 0 {
+            return;
+        }
+        let count = excess + max_lines / DROP_CHUNK_DIVISOR;
+
+        self.log_buffer.drop_oldest(count);
+        self.stream_stats.record_dropped(count);
+        self.display_frozen_at = self
+            .display_frozen_at
+            .map(|first_new_line| first_new_line.saturating_sub(count));
+        self.marking.drop_lines_before(count);
+        self.registers.drop_lines_before(count);
+        self.event_tracker.drop_lines_before(count);
+        self.alerts.drop_lines_before(count);
+        self.script_line_offset += count;
+        self.script_fields = std::mem::take(&mut self.script_fields)
+            .into_iter()
+            .filter_map(|(line_index, fields)| Some((line_index.checked_sub(count)?, fields)))
+            .collect();
+        self.tagged_events = self
+            .tagged_events
+            .iter()
+            .filter_map(|line_index| line_index.checked_sub(count))
+            .collect();
+        self.selected_marks = self
+            .selected_marks
+            .iter()
+            .filter_map(|line_index| line_index.checked_sub(count))
+            .collect();
+        self.expansion.drop_lines_before(count);
+        self.viewport.drop_history_before(count);
+        self.highlighter.invalidate_cache();
+
+        // Keep the viewport on the same lines
+        let dropped_visible = self.resolver.drop_lines_before(count);
+        self.viewport.shift_up(dropped_visible);
+        if let Some(split) = &mut self.split_viewport {
+            split.shift_up(dropped_visible);
+        }
+        if let Some((start, end)) = self.selection_range {
+            self.selection_range = Some((
+                start.saturating_sub(dropped_visible),
+                end.saturating_sub(dropped_visible),
+            ));
+        }
+    }
+
+    pub fn clear_all_marks(&mut self) {
+        self.marking.clear_all();
+        self.selected_marks.clear();
+
+        if self.show_marked_lines_only {
+            self.update_view();
+        } else {
+            let marked_indices = self.marking.get_marked_indices();
+            self.resolver.update_mark_tags(&marked_indices);
+        }
+    }
+
+    /// Lists the enabled filters matching no line, so filters kept from an earlier investigation don't blank the
+    /// view unnoticed.
+    fn show_stale_filters(&mut self) {
+        let stale_filters = unmatched_patterns(
+            self.filter.get_filter_patterns(),
+            self.log_buffer.all_lines(),
+            &self.file_manager.file_names(),
+            self.column_view.extractor(),
+            self.case_folding,
+        );
+        if stale_filters.is_empty() {
+            return;
+        }
+        self.stale_filters = stale_filters;
+        self.stale_filters_list_state = ListViewState::new_with_count(self.stale_filters.len());
+        self.set_view_state(ViewState::StaleFiltersView);
+    }
+
+    /// Disables the filters listed in the stale filters view.
+    pub fn disable_stale_filters(&mut self) {
+        for index in std::mem::take(&mut self.stale_filters) {
+            if self.filter.get_pattern(index).is_some_and(|filter| filter.enabled) {
+                self.filter.toggle_pattern_enabled(index);
+            }
+        }
+        self.expansion.clear();
+        self.update_view();
+        self.set_view_state(ViewState::LogView);
+    }
+
+    /// Deletes the filters listed in the stale filters view.
+    pub fn remove_stale_filters(&mut self) {
+        let mut stale_filters = std::mem::take(&mut self.stale_filters);
+        stale_filters.sort_unstable();
+        for index in stale_filters.into_iter().rev() {
+            self.filter.remove_pattern(index);
+        }
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
+        self.set_view_state(ViewState::LogView);
+    }
+
+    /// Opens the quick filter view with the filters derived from the selected line.
+    pub fn activate_quick_filter_view(&mut self) {
+        let Some(log_line) = self
+            .viewport_to_log_line_index(self.viewport.selected_line)
+            .and_then(|line_index| self.log_buffer.get_line(line_index))
+        else {
+            return;
+        };
+        let file = log_line
+            .log_file_id
+            .filter(|_| self.file_manager.is_multi_file())
+            .and_then(|id| self.file_manager.get(id))
+            .map(|file| file.get_filename());
+        self.quick_filters = quick_filters(
+            log_line,
+            self.log_buffer.level_detector(),
+            self.column_view.extractor(),
+            file,
+        );
+        self.quick_filter_mode = ActiveFilterMode::Include;
+        self.quick_filter_list_state = ListViewState::new_with_count(self.quick_filters.len());
+        self.set_view_state(ViewState::QuickFilterView);
+    }
+
+    /// Switches the quick filter view between adding include and exclude filters.
+    pub fn toggle_quick_filter_mode(&mut self) {
+        self.quick_filter_mode = match self.quick_filter_mode {
+            ActiveFilterMode::Include => ActiveFilterMode::Exclude,
+            ActiveFilterMode::Exclude => ActiveFilterMode::Include,
+        };
+    }
+
+    /// Adds the filter derived from the given part of the selected line, showing only the lines sharing it or
+    /// hiding them.
+    pub fn apply_quick_filter(&mut self, kind: QuickFilterKind) {
+        let Some(quick_filter) = self.quick_filters.iter().find(|filter| filter.kind == kind) else {
+            return;
+        };
+        let patterns = quick_filter.patterns(self.quick_filter_mode, &self.file_manager.file_names());
+        let description = format!("{} {}", kind.name().to_lowercase(), quick_filter.value);
+
+        let filter_count = self.filter.count();
+        for pattern in &patterns {
+            self.filter.add_filter(pattern);
+        }
+        if self.filter.count() == filter_count {
+            self.show_error("Filter already exists");
+            return;
+        }
+
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
+        self.set_view_state(ViewState::LogView);
+        let action = match self.quick_filter_mode {
+            ActiveFilterMode::Include => "Showing only",
+            ActiveFilterMode::Exclude => "Hiding",
+        };
+        self.show_message(&format!("{} lines with {}", action, description));
+    }
+
+    pub fn toggle_filter_pattern_active(&mut self) {
+        let selected_index = self.filter_list_state.selected_index();
+        self.filter.toggle_pattern_enabled(selected_index);
+        self.expansion.clear();
+        self.update_view();
+    }
+
+    pub fn remove_filter_pattern(&mut self) {
+        let selected_index = self.filter_list_state.selected_index();
+        self.filter.remove_pattern(selected_index);
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
+    }
+
+    pub fn toggle_filter_pattern_case_sensitive(&mut self) {
+        let selected_index = self.filter_list_state.selected_index();
+        self.filter.toggle_pattern_case_sensitivity(selected_index);
+        self.expansion.clear();
+        self.update_view();
+    }
+
+    pub fn toggle_filter_pattern_whole_word(&mut self) {
+        let selected_index = self.filter_list_state.selected_index();
+        self.filter.toggle_pattern_whole_word(selected_index);
+        self.expansion.clear();
+        self.update_view();
+    }
+
+    /// Scopes the selected filter to the next opened file, cycling through all files and back to applying the
+    /// filter to the lines of all files.
+    pub fn cycle_filter_pattern_file(&mut self) {
+        if !self.file_manager.is_multi_file() {
+            self.show_message("Filters can be scoped to a file when multiple files are opened");
+            return;
+        }
+        let selected_index = self.filter_list_state.selected_index();
+        let Some(pattern) = self.filter.get_pattern(selected_index) else {
+            return;
+        };
+
+        let file_names = self.file_manager.file_names();
+        let next = match &pattern.file {
+            None => file_names.first().cloned(),
+            Some(file) => file_names
+                .iter()
+                .position(|name| name == file)
+                .and_then(|position| file_names.get(position + 1))
+                .cloned(),
+        };
+        self.filter.set_pattern_file(selected_index, next);
+        self.expansion.clear();
+        self.update_view();
+    }
+
+    pub fn toggle_filter_pattern_mode(&mut self) {
+        let selected_index = self.filter_list_state.selected_index();
+        self.filter.toggle_pattern_mode(selected_index);
+        self.expansion.clear();
+        self.update_view();
+    }
+
+    /// Returns true if lines would be visible without the filters but the enabled filters hide all of them, so the
+    /// log view explains why it is empty instead of showing a blank screen.
+    pub fn filters_hide_all_lines(&self) -> bool {
+        self.filter_task.is_none() && self.filters_hide_all
+    }
+
+    pub fn toggle_all_filter_patterns(&mut self) {
+        self.filter.toggle_all_patterns_enabled();
+        self.expansion.clear();
+        self.update_view();
+    }
+
+    /// Builds a TOML config snippet from the current filters and custom events.
+    fn build_config_snippet(&self) -> Option<ConfigSnippet> {
+        let custom_events = self.event_tracker.get_custom_events();
+        let snippet = ConfigSnippet::new(self.filter.get_filter_patterns(), &custom_events);
+        (!snippet.is_empty()).then_some(snippet)
+    }
+
+    /// Copies text to the configured clipboard. The terminal clipboard is set through the stream the terminal is
+    /// drawn to, as stdout may be piped in streaming mode.
+    fn copy_to_clipboard(&self, text: &str) -> Result<(), String> {
+        let backend = self.config.clipboard.unwrap_or_default();
+        if self.draws_to_stderr {
+            clipboard::copy(text, backend, &mut std::io::stderr())
+        } else {
+            clipboard::copy(text, backend, &mut std::io::stdout())
+        }
+    }
+
+    /// Copies the current filters and custom events to the clipboard as a TOML config snippet.
+    pub fn copy_filters_to_clipboard(&mut self) {
+        let Some(snippet) = self.build_config_snippet() else {
+            self.show_message("No filters or custom events to copy");
+            return;
+        };
+
+        let content = match snippet.to_toml() {
+            Ok(content) => content,
+            Err(e) => {
+                self.show_error(&e);
+                return;
+            }
+        };
+
+        match self.copy_to_clipboard(&content) {
+            Ok(()) => self.show_message("Copied filters to clipboard as TOML"),
+            Err(e) => self.show_error(&e),
+        }
+    }
+
+    pub fn activate_export_filters_mode(&mut self) {
+        if self.view_state == ViewState::FilterView {
+            self.input.reset();
+            self.show_overlay(Overlay::ExportFilters);
+        }
+    }
+
+    /// Writes the current filters and custom events to a file as a TOML config snippet.
+    fn export_filters_to_file(&mut self, path: &str) {
+        let Some(snippet) = self.build_config_snippet() else {
+            self.show_message("No filters or custom events to export");
+            return;
+        };
+
+        let result = snippet
+            .to_toml()
+            .and_then(|content| std::fs::write(path, content).map_err(|e| e.to_string()));
+
+        match result {
+            Ok(_) => {
+                let abs_path = std::fs::canonicalize(path)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| path.to_string());
+                self.show_message(format!("Filters exported to file:\n{}", abs_path).as_str());
+            }
+            Err(e) => {
+                self.show_error(format!("Failed to export filters:\n{}", e).as_str());
+            }
+        }
+    }
+
+    /// Writes the current filters, with the ones added or edited at runtime and whether they are enabled, back to
+    /// the filters file given with `--filters`.
+    pub fn write_filters_file(&mut self) {
+        let Some(path) = self.filters_path.clone() else {
+            self.show_error("No filters file to write to, start with --filters <file>");
+            return;
+        };
+
+        // Filters from config.toml are loaded from there, so writing them to the file would load them twice
+        let config_filters = self.config.parse_filter_patterns();
+        let filters: Vec<FilterPattern> = self
+            .filter
+            .get_filter_patterns()
+            .iter()
+            .filter(|filter| {
+                !config_filters
+                    .iter()
+                    .any(|config_filter| config_filter.same_filter(filter))
+            })
+            .cloned()
+            .collect();
+        let result = Filters::from_patterns(&filters)
+            .to_toml()
+            .and_then(|content| std::fs::write(&path, content).map_err(|e| e.to_string()));
+
+        match result {
+            Ok(_) => {
+                // Filters from the file are now the ones in the list, so reloading the file doesn't add them again
+                self.show_message(&format!("Wrote {} filters to {}", filters.len(), path));
+                self.configured_filters = config_filters;
+                self.configured_filters.extend(filters);
+            }
+            Err(e) => self.show_error(&format!("Failed to write filters:\n{}", e)),
+        }
+    }
+
+    pub fn activate_export_report_mode(&mut self) {
+        if matches!(self.view_state, ViewState::EventsView | ViewState::MarksView) {
+            self.input.reset();
+            self.show_overlay(Overlay::ExportReport);
+        }
+    }
+
+    /// Writes the events and marks listed in the events or marks view to a file, as JSON if the file name ends in
+    /// `.json` and as CSV otherwise. Only the selected marks are written if marks are selected in the marks view.
+    fn export_report_to_file(&mut self, path: &str) {
+        let (events, _) = self.get_events_for_list();
+        let marks = if self.view_state == ViewState::MarksView {
+            let mut marks = self.get_marks_view_list();
+            if !self.selected_marks.is_empty() {
+                marks.retain(|mark| self.selected_marks.contains(&mark.line_index));
+            }
+            marks
+        } else {
+            self.get_listed_marks()
+        };
+        let items = if self.view_state == ViewState::MarksView {
+            EventMarkView::merge(&[], &marks, true)
+        } else {
+            EventMarkView::merge(&events, &marks, self.events_list_shows_marks())
+        };
+        if items.is_empty() {
+            self.show_message("No events or marks to export");
+            return;
+        }
+
+        let rows: Vec<ReportRow> = items
+            .iter()
+            .filter_map(|item| {
+                let log_line = self.log_buffer.get_line(item.line_index())?;
+                Some(ReportRow::new(item, log_line))
+            })
+            .collect();
+        let result = format_report(&rows, ReportFormat::from_path(path))
+            .and_then(|content| std::fs::write(path, content).map_err(|e| e.to_string()));
+
+        match result {
+            Ok(_) => {
+                let abs_path = std::fs::canonicalize(path)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| path.to_string());
+                self.show_message(format!("Exported {} rows to file:\n{}", rows.len(), abs_path).as_str());
+            }
+            Err(e) => {
+                self.show_error(format!("Failed to export report:\n{}", e).as_str());
+            }
+        }
+    }
+
+    pub fn activate_regex_tester(&mut self) {
+        self.input.reset();
+        self.regex_tester.reset();
+        self.regex_tester_list_state = ListViewState::new();
+        self.show_overlay(Overlay::RegexTester);
+    }
+
+    /// Tests the regex tester pattern against the visible lines.
+    fn update_regex_tester(&mut self) {
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let visible_iter = visible_lines.iter().map(|vl| &all_lines[vl.log_index]);
+        self.regex_tester.update(self.input.value(), visible_iter);
+        self.regex_tester_list_state = ListViewState::new_with_count(self.regex_tester.matches().len());
+    }
+
+    pub fn toggle_regex_tester_case_sensitive(&mut self) {
+        self.regex_tester.toggle_case_sensitivity();
+        self.update_regex_tester();
+    }
+
+    /// Adds the regex tester pattern as an include filter.
+    pub fn regex_tester_add_filter(&mut self) {
+        if self.regex_tester.regex().is_none() {
+            return;
+        }
+
+        let pattern = self.input.value().to_string();
+        if let Some(filter) = FilterPattern::new_regex(
+            pattern,
+            ActiveFilterMode::Include,
+            self.regex_tester.is_case_sensitive(),
+            true,
+        ) {
+            self.filter.add_filter(&filter);
+            self.filter_list_state.set_item_count(self.filter.count());
+            self.expansion.clear();
+            self.update_view();
+            self.show_message("Added regex filter");
+        }
+    }
+
+    /// Adds the regex tester pattern as a custom event.
+    pub fn regex_tester_add_event(&mut self) {
+        let Some(regex) = self.regex_tester.regex() else {
+            return;
+        };
+        // Keep the case-insensitivity in the pattern itself, so it survives persisting and exporting the event
+        let regex = if self.regex_tester.is_case_sensitive() {
+            regex.clone()
+        } else {
+            match Regex::new(&format!("(?i){}", regex.as_str())) {
+                Ok(regex) => regex,
+                Err(_) => return,
+            }
+        };
+
+        if self.add_custom_regex_event(regex) {
+            self.show_message("Added regex event");
+        } else {
+            self.show_error("Event already exists");
+        }
+    }
+
+    /// Style used for highlighting custom events, in the configured custom event color unless a color is given.
+    fn custom_event_style(&self, color: Option<Color>) -> PatternStyle {
+        PatternStyle {
+            fg_color: None,
+            bg_color: Some(color.unwrap_or_else(|| self.config.custom_event_bg_color())),
+            bold: false,
+        }
+    }
+
+    /// Adds a plain custom event and scans the log for it. Returns false if the event already exists.
+    fn add_custom_event(
+        &mut self,
+        pattern: &str,
+        case_sensitive: bool,
+        name: Option<&str>,
+        color: Option<Color>,
+    ) -> bool {
+        if !self
+            .event_tracker
+            .add_custom_event(pattern, case_sensitive, name, color)
+        {
+            return false;
+        }
+        self.highlighter
+            .add_custom_event(pattern, case_sensitive, self.custom_event_style(color));
+        self.event_tracker.scan_added_event(&self.log_buffer);
+        self.update_events_view_count();
+        true
+    }
+
+    /// Adds a regex custom event and scans the log for it. Returns false if the event already exists.
+    fn add_custom_regex_event(&mut self, regex: Regex) -> bool {
+        if !self.event_tracker.add_custom_regex_event(regex.clone(), None, None) {
+            return false;
+        }
+        self.highlighter
+            .add_custom_regex_event(regex, self.custom_event_style(None));
+        self.event_tracker.scan_added_event(&self.log_buffer);
+        self.update_events_view_count();
+        true
+    }
+
+    /// Adds the pattern of the selected event in the event filter list as an include filter.
+    pub fn event_to_filter(&mut self) {
+        let Some(name) = self.selected_filter_event_name() else {
+            return;
+        };
+        let filter = match self.event_tracker.get_event_matcher(&name) {
+            Some(PatternMatcher::Plain(plain)) => Some(FilterPattern::new_text(
+                plain.pattern.clone(),
+                ActiveFilterMode::Include,
+                plain.case_sensitive,
+                true,
+            )),
+            Some(PatternMatcher::Regex(regex)) => {
+                // Case-insensitive regex events carry a leading `(?i)` flag
+                let (pattern, case_sensitive) = match regex.as_str().strip_prefix("(?i)") {
+                    Some(pattern) => (pattern, false),
+                    None => (regex.as_str(), true),
+                };
+                FilterPattern::new_regex(pattern.to_string(), ActiveFilterMode::Include, case_sensitive, true)
+            }
+            Some(PatternMatcher::Fuzzy(_)) | None => None,
+        };
+        let Some(filter) = filter else {
+            return;
+        };
+
+        let filter_count = self.filter.count();
+        self.filter.add_filter(&filter);
+        if self.filter.count() == filter_count {
+            self.show_error("Filter already exists");
+            return;
+        }
+
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
+        self.update_events_view_count();
+        self.show_message(format!("Added include filter for event '{}'", name).as_str());
+    }
+
+    /// Adds the active search pattern as an include filter, keeping its case sensitivity and whole word setting.
+    pub fn search_to_filter(&mut self) {
+        let Some(pattern) = self.search.get_active_pattern().map(str::to_string) else {
+            self.show_message("No active search");
+            return;
+        };
+        if self.search.is_fuzzy() {
+            self.show_error("Fuzzy searches can't be added as filters");
+            return;
+        }
+        let filter = FilterPattern::new_text(
+            pattern.clone(),
+            ActiveFilterMode::Include,
+            self.search.is_case_sensitive(),
+            true,
+        )
+        .with_whole_word(self.search.is_whole_word());
+
+        let filter_count = self.filter.count();
+        self.filter.add_filter(&filter);
+        if self.filter.count() == filter_count {
+            self.show_error("Filter already exists");
+            return;
+        }
+
+        self.filter_list_state.set_item_count(self.filter.count());
+        self.expansion.clear();
+        self.update_view();
+        self.show_message(format!("Added include filter for '{}'", pattern).as_str());
+    }
+
+    /// Starts a search seeded with the selected filter's pattern, keeping its case sensitivity and whole word
+    /// setting.
+    pub fn filter_to_search(&mut self) {
+        let Some(filter) = self
+            .filter
+            .get_pattern(self.filter_list_state.selected_index())
+            .cloned()
+        else {
+            return;
+        };
+        if filter.is_regex() {
+            self.show_error("Regex filters can't be searched");
+            return;
+        }
+        if filter.field.is_some() {
+            self.show_error("Field filters can't be searched");
+            return;
+        }
+
+        self.activate_search_mode();
+        if self.search.is_case_sensitive() != filter.case_sensitive {
+            self.search.toggle_case_sensitivity();
+        }
+        if self.search.is_fuzzy() {
+            self.search.toggle_fuzzy();
+        }
+        self.search.set_whole_word(filter.whole_word);
+        self.input = Input::new(filter.pattern);
+        self.update_temporary_highlights();
+    }
+
+    /// Adds the selected filter pattern as a custom event.
+    pub fn filter_to_event(&mut self) {
+        let Some(filter) = self
+            .filter
+            .get_pattern(self.filter_list_state.selected_index())
+            .cloned()
+        else {
+            return;
+        };
+
+        let added = if !filter.is_regex() {
+            self.add_custom_event(&filter.pattern, filter.case_sensitive, None, None)
+        } else {
+            // Keep the case-insensitivity in the pattern, where it is read back when promoting the event to a filter
+            let pattern = if filter.case_sensitive {
+                filter.pattern.clone()
+            } else {
+                format!("(?i){}", filter.pattern)
+            };
+            Regex::new(&pattern).is_ok_and(|regex| self.add_custom_regex_event(regex))
+        };
+
+        if added {
+            self.show_message(format!("Added event for filter '{}'", filter.pattern).as_str());
+        } else {
+            self.show_error("Event already exists");
+        }
+    }
+
+    /// Adds the regex tester pattern as a watch expression, seeded with the values of the current lines.
+    pub fn regex_tester_add_watch(&mut self) {
+        let Some(regex) = self.regex_tester.regex().cloned() else {
+            return;
+        };
+
+        let name = regex
+            .capture_names()
+            .flatten()
+            .next()
+            .unwrap_or(regex.as_str())
+            .to_string();
+        let Some(mut watch) = WatchExpression::new(&name, regex) else {
+            self.show_error("A watch expression needs a capture group for the value, e.g. depth=(\\d+)");
+            return;
+        };
+        watch.seed(self.log_buffer.all_lines().iter().map(LogLine::content));
+
+        if self.watches.add(watch) {
+            self.show_watches = true;
+            self.show_message(&format!("Added watch '{}'", name));
+        } else {
+            self.show_error("Watch already exists");
+        }
+    }
+
+    pub fn toggle_watches(&mut self) {
+        if self.watches.is_empty() {
+            self.show_error("No watch expressions. Add one in the regex tester (Alt+w) or in the config");
+            return;
+        }
+        self.show_watches = !self.show_watches;
+        if self.show_watches {
+            self.watches
+                .seed_empty(self.log_buffer.all_lines().iter().map(LogLine::content));
+        }
+    }
+
+    pub fn toggle_debug_overlay(&mut self) {
+        if self.debug_mode {
+            self.show_debug_overlay = !self.show_debug_overlay;
+        }
+    }
+
+    /// Adds the regex tester pattern as a highlight.
+    pub fn regex_tester_add_highlight(&mut self) {
+        let Some(regex) = self.regex_tester.regex().cloned() else {
+            return;
+        };
+
+        let style = PatternStyle::new(Some(Config::hash_to_color(regex.as_str())), None, false);
+        self.highlighter.add_highlight(HighlightPattern {
+            matcher: PatternMatcher::Regex(regex),
+            style,
+            group_styles: Vec::new(),
+            file: None,
+        });
+        self.show_message("Added regex highlight");
+    }
+
+    pub fn toggle_show_marked_only(&mut self) {
+        self.show_marked_lines_only = !self.show_marked_lines_only;
+        self.update_view();
+    }
+
+    pub fn toggle_event_filter(&mut self) {
+        let selected_index = self.event_filter_list_state.selected_index();
+        let event_stats = self.event_tracker.get_event_stats();
+
+        if let Some(event_stat) = event_stats.get(selected_index) {
+            self.event_tracker.toggle_event_enabled(&event_stat.name);
+            self.update_events_view_count();
+
+            if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+                && let Some(nearest_index) = self.find_nearest_event(line_index)
+            {
+                self.events_list_state.select_index(nearest_index);
+            }
+        }
+    }
+
+    pub fn toggle_all_event_filters(&mut self) {
+        self.event_tracker.toggle_all_filters();
+        self.update_events_view_count();
+
+        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+            && let Some(nearest_index) = self.find_nearest_event(line_index)
+        {
+            self.events_list_state.select_index(nearest_index);
+        }
+    }
+
+    pub fn solo_event_filter(&mut self) {
+        let selected_index = self.event_filter_list_state.selected_index();
+        let event_stats = self.event_tracker.get_event_stats();
+
+        if let Some(event_stat) = event_stats.get(selected_index) {
+            self.event_tracker.solo_event_filter(&event_stat.name);
+            self.update_events_view_count();
+
+            if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
+                && let Some(nearest_index) = self.find_nearest_event(line_index)
+            {
+                self.events_list_state.select_index(nearest_index);
+            }
+        }
+    }
+
+    /// Tags or untags the selected event and moves to the next item.
+    pub fn toggle_event_tag(&mut self) {
+        let (events, _) = self.get_events_for_list();
+        let visible_marks = self.get_listed_marks();
+        let merged = EventMarkView::merge(&events, &visible_marks, self.events_list_shows_marks());
+        let selected_idx = self.events_list_state.selected_index();
+
+        if let Some(EventOrMark::Event(event)) = merged.get(selected_idx) {
+            if !self.tagged_events.remove(&event.line_index) {
+                self.tagged_events.insert(event.line_index);
+            }
+            self.events_list_state.move_down();
+        }
+    }
+
+    /// Tags all events in the events list, or untags them if all are already tagged.
+    pub fn toggle_all_event_tags(&mut self) {
+        let (events, _) = self.get_events_for_list();
+        let line_indices: HashSet<usize> = events.iter().map(|event| event.line_index).collect();
+
+        if line_indices.is_subset(&self.tagged_events) {
+            self.tagged_events.clear();
+        } else {
+            self.tagged_events = line_indices;
+        }
+    }
+
+    /// Creates a mark for each tagged event, named after the event.
+    pub fn mark_tagged_events(&mut self) {
+        if self.tagged_events.is_empty() {
+            self.show_message("No events tagged. Press v to tag events");
+            return;
+        }
+
+        let (events, _) = self.get_events_for_list();
+        let mut marked = HashSet::new();
+        for event in events
+            .iter()
+            .filter(|event| self.tagged_events.contains(&event.line_index))
+        {
+            // Lines with several events are named after the first one
+            if marked.insert(event.line_index) {
+                self.marking.add_named_mark(event.line_index, &event.name);
+            }
+        }
+        self.tagged_events.clear();
+
+        self.marking_list_state.set_item_count(self.marking.count());
+        if self.show_marked_lines_only {
+            self.update_view();
+        } else {
+            let marked_indices = self.marking.get_marked_indices();
+            self.resolver.update_mark_tags(&marked_indices);
+        }
+        self.update_events_view_count();
+        self.show_message(&format!("Created {} marks from events", marked.len()));
+    }
+
+    pub fn toggle_events_show_marks(&mut self) {
+        self.event_tracker.toggle_show_marks();
+        self.update_events_view_count();
     }
 
-    pub fn toggle_event_filter(&mut self) {
-        let selected_index = self.event_filter_list_state.selected_index();
-        let event_stats = self.event_tracker.get_event_stats();
-
-        if let Some(event_stat) = event_stats.get(selected_index) {
-            self.event_tracker.toggle_event_enabled(&event_stat.name);
-            self.update_events_view_count();
+    pub fn toggle_events_preview(&mut self) {
+        self.options.toggle(AppOption::EventsPreview);
+    }
 
-            if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-                && let Some(nearest_index) = self.find_nearest_event(line_index)
+    /// Reloads the config and filters file, e.g. after one of them changed or another profile was selected,
+    /// replacing the configured highlights, events and filters while keeping the ones added at runtime. The heading
+    /// is shown above the changes, and the changes alone are shown without one. Returns false if the config
+    /// couldn't be loaded and was kept as it was.
+    fn reload_config(&mut self, heading: Option<&str>) -> bool {
+        let mut config = match Config::load(&self.config_path) {
+            Ok(config) => config,
+            Err(err) => {
+                self.show_error(&format!("Failed to reload the config:\n{}", err));
+                return false;
+            }
+        };
+        if let Some(Err(err)) = self.profile.as_deref().map(|name| config.apply_profile(name)) {
+            self.show_error(&format!("Failed to reload the config:\n{}", err));
+            return false;
+        }
+        let mut issues = config.validate();
+        let mut configured_filters = config.parse_filter_patterns();
+        if let Some(path) = self.filters_path.as_deref().filter(|path| Path::new(path).exists()) {
+            let Some(filters_file) = Filters::load(&self.filters_path) else {
+                self.show_error(&format!("Failed to reload the filters file '{}'", path));
+                return false;
+            };
+            issues.extend(filters_file.validate());
+            configured_filters.extend(filters_file.parse_filter_patterns());
+        }
+        debug!("Reloaded config {:?}", config.get_path());
+
+        let filter_key = |filter: &FilterPattern| {
+            (
+                filter.pattern.clone(),
+                filter.mode,
+                filter.case_sensitive,
+                filter.whole_word,
+                filter.is_regex(),
+                filter.file.clone(),
+            )
+        };
+        let old_filters: Vec<_> = self.configured_filters.iter().map(filter_key).collect();
+        let new_filters: Vec<_> = configured_filters.iter().map(filter_key).collect();
+        let filter_changes = SectionChanges::new("filters", &old_filters, &new_filters);
+        if !filter_changes.is_empty() {
+            for key in old_filters.iter().filter(|key| !new_filters.contains(key)) {
+                let position = self
+                    .filter
+                    .get_filter_patterns()
+                    .iter()
+                    .position(|filter| filter_key(filter) == *key);
+                if let Some(index) = position {
+                    self.filter.remove_pattern(index);
+                }
+            }
+            for filter in configured_filters
+                .iter()
+                .filter(|filter| !old_filters.contains(&filter_key(filter)))
             {
-                self.events_list_state.select_index(nearest_index);
+                self.filter.add_filter(filter);
             }
+            self.filter_list_state.set_item_count(self.filter.count());
+            self.expansion.clear();
+            self.update_view();
         }
-    }
 
-    pub fn toggle_all_event_filters(&mut self) {
-        self.event_tracker.toggle_all_filters();
-        self.update_events_view_count();
+        let highlight_changes = SectionChanges::new("highlights", &self.config.highlights, &config.highlights);
+        let event_changes = SectionChanges::new("events", &self.config.events, &config.events);
+        if !highlight_changes.is_empty() || !event_changes.is_empty() {
+            self.highlighter.replace_configured(
+                config.parse_highlight_patterns(),
+                config.parse_highlight_event_patterns(),
+            );
+        }
+        if !event_changes.is_empty() {
+            self.event_tracker
+                .replace_configured_patterns(config.parse_log_event_patterns());
+            self.event_tracker.scan_all_lines(&self.log_buffer);
+            self.update_events_view_count();
+        }
 
-        if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-            && let Some(nearest_index) = self.find_nearest_event(line_index)
-        {
-            self.events_list_state.select_index(nearest_index);
+        self.config = config;
+        self.configured_filters = configured_filters;
+
+        let summary = describe_changes(&[highlight_changes, event_changes, filter_changes]);
+        let heading = heading
+            .map(str::to_string)
+            .or_else(|| summary.is_some().then(|| "Reloaded the config:".to_string()));
+        let warning = (!issues.is_empty()).then(|| {
+            format!(
+                "Warning: Some configuration entries were ignored:\n\n{}",
+                issues.join("\n")
+            )
+        });
+        let message: Vec<String> = heading.into_iter().chain(summary).chain(warning).collect();
+        if !message.is_empty() {
+            self.show_message(&message.join("\n\n"));
         }
+        true
     }
 
-    pub fn solo_event_filter(&mut self) {
-        let selected_index = self.event_filter_list_state.selected_index();
-        let event_stats = self.event_tracker.get_event_stats();
-
-        if let Some(event_stat) = event_stats.get(selected_index) {
-            self.event_tracker.solo_event_filter(&event_stat.name);
-            self.update_events_view_count();
-
-            if let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line)
-                && let Some(nearest_index) = self.find_nearest_event(line_index)
-            {
-                self.events_list_state.select_index(nearest_index);
-            }
+    /// Opens the profiles view listing the profiles of the config, after an entry for using no profile.
+    pub fn activate_profiles_view(&mut self) {
+        let profiles = self.config.profile_names();
+        if profiles.is_empty() {
+            self.show_message("The config has no profiles, add them as [profile.<name>] sections");
+            return;
         }
+        let selected = self
+            .profile
+            .as_ref()
+            .and_then(|profile| profiles.iter().position(|name| name == profile))
+            .map_or(0, |index| index + 1);
+        self.profiles_list_state = ListViewState::new_with_count(profiles.len() + 1);
+        self.profiles_list_state.select_index(selected);
+        self.set_view_state(ViewState::ProfilesView);
     }
 
-    pub fn toggle_events_show_marks(&mut self) {
-        self.event_tracker.toggle_show_marks();
-        self.update_events_view_count();
+    /// Switches to the profile selected in the profiles view, replacing the highlights, events and filters of the
+    /// previous profile with its own.
+    fn select_profile(&mut self) {
+        let index = self.profiles_list_state.selected_index();
+        let profile = index
+            .checked_sub(1)
+            .and_then(|index| self.config.profile_names().get(index).cloned());
+        self.set_view_state(ViewState::LogView);
+        if profile == self.profile {
+            return;
+        }
+        let heading = match &profile {
+            Some(profile) => format!("Switched to profile {}", profile),
+            None => "Switched to no profile".to_string(),
+        };
+        let previous = std::mem::replace(&mut self.profile, profile);
+        if !self.reload_config(Some(&heading)) {
+            self.profile = previous;
+        }
     }
 
     fn update_events_view_count(&mut self) {
+        self.event_pairs.update(
+            self.event_tracker.get_events(),
+            self.log_buffer.all_lines(),
+            self.log_buffer.generation(),
+        );
         let (events, _) = self.get_events_for_list();
-        let visible_marks = self.get_visible_marks();
-        let merged_items = EventMarkView::merge(&events, &visible_marks, self.event_tracker.showing_marks());
+        let visible_marks = self.get_listed_marks();
+        let merged_items = EventMarkView::merge(&events, &visible_marks, self.events_list_shows_marks());
         self.events_list_state.set_item_count(merged_items.len());
 
         let filter_count = self.event_tracker.filter_count();
@@ -1841,10 +6564,18 @@ impl App {
             None
         };
 
-        let hidden_indices: Vec<usize> = if let Some(next_index) = next_log_index {
-            ((current_log_index + 1)..next_index).collect()
+        // A folded record only expands its own continuation lines
+        let record_end = if self.options.is_enabled(AppOption::FoldMultiLineRecords) {
+            Some(self.log_buffer.record_range(current_log_index).end).filter(|&end| end > current_log_index + 1)
         } else {
-            Vec::new()
+            None
+        };
+
+        let hidden_indices: Vec<usize> = match (next_log_index, record_end) {
+            (Some(next_index), Some(end)) => ((current_log_index + 1)..next_index.min(end)).collect(),
+            (None, Some(end)) => ((current_log_index + 1)..end).collect(),
+            (Some(next_index), None) => ((current_log_index + 1)..next_index).collect(),
+            (None, None) => Vec::new(),
         };
 
         if hidden_indices.is_empty() {
@@ -1855,6 +6586,25 @@ impl App {
         self.update_view();
     }
 
+    /// Returns the number of continuation lines folded below a line, or 0 if its record is not folded.
+    pub fn folded_line_count(&self, log_line: &LogLine) -> usize {
+        if self.options.is_disabled(AppOption::FoldMultiLineRecords)
+            || log_line.continuation
+            || self.expansion.is_expanded(log_line.index)
+        {
+            return 0;
+        }
+        self.log_buffer.record_range(log_line.index).len() - 1
+    }
+
+    /// Returns the number of repeats collapsed into the line, or 0 if it is expanded or nothing is collapsed.
+    pub fn collapsed_repeat_count(&self, log_line: &LogLine) -> usize {
+        if self.repeat_match().is_none() || self.expansion.is_expanded(log_line.index) {
+            return 0;
+        }
+        self.repeated_lines.repeat_count(log_line.index)
+    }
+
     pub fn collapse_all_expansions(&mut self) {
         self.expansion.clear();
         self.update_view();
@@ -1882,6 +6632,7 @@ impl App {
             self.input = Input::new(history_entry.pattern);
             self.filter.set_mode(history_entry.mode);
             self.filter.set_case_sensitivity(history_entry.case_sensitive);
+            self.filter.set_whole_word(history_entry.whole_word);
             self.update_temporary_highlights();
         }
     }
@@ -1891,6 +6642,7 @@ impl App {
             self.input = Input::new(history_entry.pattern);
             self.filter.set_mode(history_entry.mode);
             self.filter.set_case_sensitivity(history_entry.case_sensitive);
+            self.filter.set_whole_word(history_entry.whole_word);
             self.update_temporary_highlights();
         } else {
             self.input.reset();
@@ -1900,43 +6652,605 @@ impl App {
         }
     }
 
-    pub fn goto_selected_event(&mut self, center: bool) {
-        let (events, filtered_indices) = self.get_events_for_list();
-        let visible_marks = self.get_visible_marks();
-        let merged = EventMarkView::merge(&events, &visible_marks, self.event_tracker.showing_marks());
-        let selected_idx = self.events_list_state.selected_index();
-        let line_index = merged.get(selected_idx).map(|item| item.line_index());
+    pub fn goto_selected_event(&mut self, center: bool) {
+        let (events, filtered_indices) = self.get_events_for_list();
+        let visible_marks = self.get_listed_marks();
+        let merged = EventMarkView::merge(&events, &visible_marks, self.events_list_shows_marks());
+        let selected_idx = self.events_list_state.selected_index();
+        let line_index = merged.get(selected_idx).map(|item| item.line_index());
+
+        if let Some(line_index) = line_index {
+            if filtered_indices.contains(&line_index) {
+                self.filter.disable_all_patterns();
+                self.update_view();
+            }
+            self.viewport.push_history(line_index);
+            self.goto_line(line_index, center);
+        }
+    }
+
+    /// Selects or deselects the mark under the cursor in the marks view and moves to the next one.
+    pub fn toggle_mark_selection(&mut self) {
+        if let Some(mark) = self.get_selected_mark() {
+            if !self.selected_marks.remove(&mark.line_index) {
+                self.selected_marks.insert(mark.line_index);
+            }
+            self.marking_list_state.move_down();
+        }
+    }
+
+    /// Selects all marks in the marks view, or deselects them if all are already selected.
+    pub fn toggle_all_mark_selections(&mut self) {
+        let line_indices: HashSet<usize> = self.get_marks_view_list().iter().map(|mark| mark.line_index).collect();
+
+        if line_indices.is_subset(&self.selected_marks) {
+            self.selected_marks.clear();
+        } else {
+            self.selected_marks = line_indices;
+        }
+    }
+
+    /// Toggles grouping the marks view by mark name, keeping the cursor on the same mark.
+    pub fn toggle_marks_grouping(&mut self) {
+        let selected_line = self.get_selected_mark().map(|mark| mark.line_index);
+        self.marks_grouped = !self.marks_grouped;
+        if let Some(line_index) = selected_line {
+            self.select_nearest_mark(line_index);
+        }
+    }
+
+    pub fn goto_selected_mark(&mut self, center: bool) {
+        if let Some(mark) = self.get_selected_mark() {
+            let line_index = mark.line_index;
+            self.viewport.push_history(line_index);
+            self.goto_line(line_index, center);
+        }
+    }
+
+    /// Opens the registers popup to save the position under the next letter typed, or to jump to it.
+    pub fn activate_registers_view(&mut self, action: RegisterAction) {
+        if action == RegisterAction::Jump && self.registers.is_empty() {
+            self.show_message("No registers set. Press M and a letter to save the position");
+            return;
+        }
+        self.register_action = action;
+        self.registers_list_state = ListViewState::new_with_count(self.registers.len());
+        self.set_view_state(ViewState::RegistersView);
+    }
+
+    /// Saves the position in the register, or jumps to the position saved in it.
+    fn use_register(&mut self, name: char) {
+        self.set_view_state(ViewState::LogView);
+        let Some(line_index) = self.viewport_to_log_line_index(self.viewport.selected_line) else {
+            return;
+        };
+        match self.register_action {
+            RegisterAction::Set => {
+                let position = RegisterPosition {
+                    line_index,
+                    horizontal_offset: self.viewport.horizontal_offset,
+                };
+                self.registers.set(name, position);
+                self.show_message(&format!("Saved line {} in register '{}'", line_index + 1, name));
+            }
+            RegisterAction::Jump => {
+                let Some(position) = self.registers.get(name) else {
+                    self.show_message(&format!("Register '{}' is not set", name));
+                    return;
+                };
+                self.viewport.follow_mode = false;
+                self.viewport.push_history(line_index);
+                self.viewport.push_history(position.line_index);
+                self.goto_line(position.line_index, true);
+                self.viewport.horizontal_offset = position.horizontal_offset;
+            }
+        }
+    }
+
+    /// Removes the register selected in the registers popup.
+    pub fn remove_selected_register(&mut self) {
+        if let Some(name) = self.registers.name_at(self.registers_list_state.selected_index()) {
+            self.registers.remove(name);
+            self.registers_list_state.set_item_count(self.registers.len());
+        }
+    }
+
+    /// Enters selection mode and sets the start of the selection range.
+    pub fn start_selection(&mut self) {
+        let current_line = self.viewport.selected_line;
+        self.selection_range = Some((current_line, current_line));
+        self.selection_columns = None;
+        self.set_view_state(ViewState::SelectionMode);
+    }
+
+    /// Enters selection mode for a block of columns, starting at the left edge of the view.
+    pub fn start_block_selection(&mut self) {
+        if self.column_view.is_enabled() {
+            self.show_message("Block selection is not available in the column view");
+            return;
+        }
+        let current_line = self.viewport.selected_line;
+        let column = self.viewport.horizontal_offset;
+        self.selection_range = Some((current_line, current_line));
+        self.selection_columns = Some((column, column));
+        self.set_view_state(ViewState::SelectionMode);
+    }
+
+    /// Moves the cursor column of a block selection, or scrolls horizontally when whole lines are selected.
+    pub fn move_selection_column(&mut self, right: bool) {
+        let Some((anchor, cursor)) = self.selection_columns else {
+            if right {
+                self.scroll_right(false);
+            } else {
+                self.viewport.scroll_left();
+            }
+            return;
+        };
+        let cursor = if right {
+            (cursor + 1).min(self.selected_line_width().unwrap_or(0).max(cursor))
+        } else {
+            cursor.saturating_sub(1)
+        };
+        self.selection_columns = Some((anchor, cursor));
+        self.viewport.scroll_to_columns(cursor, cursor + 1);
+    }
+
+    /// Updates the end of the selection range as the cursor moves.
+    pub fn update_selection_end(&mut self) {
+        if let Some((start, _)) = self.selection_range {
+            self.selection_range = Some((start, self.viewport.selected_line));
+        }
+    }
+
+    pub fn activate_pipe_command_mode(&mut self) {
+        if matches!(self.view_state, ViewState::LogView | ViewState::SelectionMode) {
+            self.input.reset();
+            self.show_overlay(Overlay::PipeCommand);
+        }
+    }
+
+    /// Returns the content of the lines to pipe: the selected lines in selection mode, otherwise all visible lines.
+    fn get_lines_to_pipe(&mut self) -> Vec<String> {
+        let all_lines = self.log_buffer.all_lines();
+        if self.view_state == ViewState::SelectionMode
+            && let Some((start, end)) = self.get_selection_range()
+        {
+            (start..=end)
+                .filter_map(|viewport_line| self.resolver.viewport_to_log(viewport_line, all_lines))
+                .filter_map(|log_index| self.log_buffer.get_line(log_index))
+                .map(|log_line| log_line.content.clone())
+                .collect()
+        } else {
+            self.resolver
+                .get_visible_lines(all_lines)
+                .iter()
+                .map(|vl| all_lines[vl.log_index].content.clone())
+                .collect()
+        }
+    }
+
+    /// Pipes the selected or visible lines through a shell command in the background. The output is shown once
+    /// the command exits.
+    fn pipe_lines_through_command(&mut self, command: &str) {
+        let mut input = self.get_lines_to_pipe().join("\n");
+        input.push('\n');
+
+        self.close_overlay();
+        self.spawn_pipe_task(command.to_string(), input, PipeTarget::OutputView);
+    }
+
+    /// Starts piping the input through a command in the background, replacing a command still running.
+    fn spawn_pipe_task(&mut self, command: String, input: String, target: PipeTarget) {
+        let on_complete = self.events.app_sender();
+        self.pipe_task = Some(PipeTask::spawn(command, input, target, move |output| {
+            on_complete(AppEvent::PipeComplete(output))
+        }));
+    }
+
+    /// Shows the output of a command the lines were piped through, or applies it as the annotations of a script.
+    fn finish_pipe_command(&mut self, task: PipeTask, output: Result<String, String>) {
+        match (task.target(), output) {
+            (PipeTarget::OutputView, Ok(output)) if output.is_empty() => {
+                self.show_message("Command produced no output");
+            }
+            (PipeTarget::OutputView, Ok(output)) => {
+                self.pipe_command = task.command().to_string();
+                self.pipe_output = output.lines().map(|line| line.to_string()).collect();
+                self.pipe_output_list_state = ListViewState::new_with_count(self.pipe_output.len());
+                self.selection_range = None;
+                self.set_view_state(ViewState::PipeOutputView);
+            }
+            (PipeTarget::OutputView, Err(e)) => {
+                self.show_error(format!("Command failed:\n{}", e).as_str());
+            }
+            (PipeTarget::Script(name), Ok(output)) => {
+                let (annotations, invalid) = parse_annotations(&output);
+                let (marks, highlights, fields) = self.apply_script_annotations(annotations, 0);
+                let mut message = format!(
+                    "Script '{}': {} marks, {} highlights, {} fields",
+                    name, marks, highlights, fields
+                );
+                if invalid > 0 {
+                    message.push_str(&format!("\n{} output lines were not valid annotations", invalid));
+                }
+                self.show_message(&message);
+            }
+            (PipeTarget::Script(name), Err(e)) => {
+                self.show_error(&format!("Script '{}' failed:\n{}", name, e));
+            }
+        }
+    }
+
+    pub fn activate_query_mode(&mut self) {
+        if self.view_state == ViewState::LogView {
+            self.input = Input::new(self.query.clone());
+            self.show_overlay(Overlay::Query);
+        }
+    }
+
+    /// Runs a query over the fields of the visible lines and shows the result table.
+    fn run_query(&mut self, query: &str) {
+        let parsed = match Query::parse(query) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.show_error(format!("Invalid query:\n{}", e).as_str());
+                return;
+            }
+        };
+
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let result = parsed.run(
+            visible_lines.iter().map(|vl| &all_lines[vl.log_index]),
+            self.column_view.extractor(),
+            self.case_folding,
+        );
+
+        self.query = query.to_string();
+        if result.rows.is_empty() {
+            self.show_message("No lines match the query");
+            return;
+        }
+        self.query_results_list_state = ListViewState::new_with_count(result.rows.len());
+        self.query_table = result.table();
+        self.query_result = result;
+        self.set_view_state(ViewState::QueryResultsView);
+    }
+
+    /// Goes to the first source line of the selected query result row.
+    fn goto_selected_query_row(&mut self) {
+        let selected = self.query_results_list_state.selected_index();
+        if let Some(&line_index) = self
+            .query_result
+            .rows
+            .get(selected)
+            .and_then(|row| row.line_indices.first())
+        {
+            self.viewport.push_history(line_index);
+            self.goto_line(line_index, true);
+        }
+    }
+
+    /// Marks all source lines of the selected query result row, named after the row's first cell.
+    pub fn mark_query_row(&mut self) {
+        let selected = self.query_results_list_state.selected_index();
+        let Some(row) = self.query_result.rows.get(selected) else {
+            return;
+        };
+
+        let name = row.cells.first().cloned().unwrap_or_default();
+        for &line_index in &row.line_indices {
+            self.marking.add_named_mark(line_index, &name);
+        }
+        let count = row.line_indices.len();
+
+        self.marking_list_state.set_item_count(self.marking.count());
+        if self.show_marked_lines_only {
+            self.update_view();
+        } else {
+            let marked_indices = self.marking.get_marked_indices();
+            self.resolver.update_mark_tags(&marked_indices);
+        }
+        self.show_message(format!("Marked {} line{}", count, if count == 1 { "" } else { "s" }).as_str());
+    }
+
+    /// Opens the actions popup with the configured actions that apply to the selected line.
+    pub fn activate_actions_view(&mut self) {
+        if self.actions.is_empty() {
+            self.show_error("No actions configured. Add [[actions]] to the config to use actions.");
+            return;
+        }
+        let Some(log_index) = self.viewport_to_log_line_index(self.viewport.selected_line) else {
+            return;
+        };
+        let Some(log_line) = self.log_buffer.get_line(log_index) else {
+            return;
+        };
+
+        self.action_choices = self
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, action)| Some((index, action.expand(log_line.content())?)))
+            .collect();
+        if self.action_choices.is_empty() {
+            self.show_message("No actions apply to the selected line");
+            return;
+        }
+        self.actions_list_state = ListViewState::new_with_count(self.action_choices.len());
+        self.set_view_state(ViewState::ActionsView);
+    }
+
+    /// Runs the selected action: starts its command or copies its text to the clipboard.
+    fn run_selected_action(&mut self) {
+        let Some((index, text)) = self
+            .action_choices
+            .get(self.actions_list_state.selected_index())
+            .cloned()
+        else {
+            return;
+        };
+        self.set_view_state(ViewState::LogView);
+
+        match self.actions[index].kind() {
+            ActionKind::Command(_) => match spawn_command(&text) {
+                Ok(()) => self.show_message(format!("Started:\n{}", text).as_str()),
+                Err(e) => self.show_error(&e),
+            },
+            ActionKind::Copy(_) => match self.copy_to_clipboard(&text) {
+                Ok(()) => self.show_message(format!("Copied to clipboard:\n{}", text).as_str()),
+                Err(e) => self.show_error(&e),
+            },
+        }
+    }
+
+    /// Opens the list of scripts to run over the log lines.
+    pub fn activate_scripts_view(&mut self) {
+        if self.scripts.is_empty() {
+            self.show_error("No scripts configured. Add [[scripts]] to the config to use scripts.");
+            return;
+        }
+        self.scripts_list_state = ListViewState::new_with_count(self.scripts.len());
+        self.set_view_state(ViewState::ScriptsView);
+    }
+
+    /// Runs the selected script over all log lines in the background. The annotations it returns are applied once
+    /// it exits.
+    fn run_selected_script(&mut self) {
+        let Some(script) = self.scripts.get(self.scripts_list_state.selected_index()).cloned() else {
+            return;
+        };
+        self.set_view_state(ViewState::LogView);
+
+        let input = encode_lines(
+            self.log_buffer
+                .all_lines()
+                .iter()
+                .map(|line| (line.index, line.content())),
+        );
+        self.spawn_pipe_task(script.command, input, PipeTarget::Script(script.name));
+    }
+
+    /// Starts the scripts that run alongside the stream.
+    fn start_streaming_scripts(&mut self) {
+        let mut errors = Vec::new();
+        for script in self.scripts.iter().filter(|script| script.stream) {
+            let on_annotations = self.events.app_sender();
+            match StreamingScript::spawn(script, move || on_annotations(AppEvent::ScriptAnnotations)) {
+                Ok(streaming) => self.streaming_scripts.push(streaming),
+                Err(e) => errors.push(e),
+            }
+        }
+        if !errors.is_empty() {
+            self.show_error(&errors.join("\n"));
+        }
+    }
+
+    /// Applies annotations from a script. `offset` is subtracted from the line indices they refer to.
+    /// Returns the number of marks, highlights and fields added.
+    fn apply_script_annotations(&mut self, annotations: Vec<ScriptAnnotation>, offset: usize) -> (usize, usize, usize) {
+        let line_count = self.log_buffer.get_total_lines_count();
+        let line_index = |index: usize| index.checked_sub(offset).filter(|&line_index| line_index < line_count);
+        let (mut marks, mut highlights, mut fields) = (0, 0, 0);
+
+        for annotation in annotations {
+            match annotation {
+                ScriptAnnotation::Mark { index, name } => {
+                    let Some(line_index) = line_index(index) else {
+                        continue;
+                    };
+                    match name {
+                        Some(name) => self.marking.add_named_mark(line_index, &name),
+                        None if !self.marking.is_marked(line_index) => self.marking.toggle_mark(line_index),
+                        None => {}
+                    }
+                    marks += 1;
+                }
+                ScriptAnnotation::Highlight { pattern, fg, bg } => {
+                    if self.script_highlights.contains(&pattern) {
+                        continue;
+                    }
+                    let Ok(regex) = Regex::new(&pattern) else {
+                        debug!("Invalid script highlight pattern '{}'", pattern);
+                        continue;
+                    };
+                    let fg_color = fg.as_deref().and_then(Config::parse_color);
+                    let bg_color = bg.as_deref().and_then(Config::parse_color);
+                    let style = if fg_color.is_none() && bg_color.is_none() {
+                        PatternStyle::new(Some(Config::hash_to_color(&pattern)), None, false)
+                    } else {
+                        PatternStyle::new(fg_color, bg_color, false)
+                    };
+                    self.highlighter.add_highlight(HighlightPattern {
+                        matcher: PatternMatcher::Regex(regex),
+                        style,
+                        group_styles: Vec::new(),
+                        file: None,
+                    });
+                    self.script_highlights.insert(pattern);
+                    highlights += 1;
+                }
+                ScriptAnnotation::Field { index, name, value } => {
+                    let Some(line_index) = line_index(index) else {
+                        continue;
+                    };
+                    let line_fields = self.script_fields.entry(line_index).or_default();
+                    match line_fields.iter_mut().find(|(field, _)| *field == name) {
+                        Some((_, existing)) => *existing = value,
+                        None => line_fields.push((name, value)),
+                    }
+                    fields += 1;
+                }
+            }
+        }
 
-        if let Some(line_index) = line_index {
-            if filtered_indices.contains(&line_index) {
-                self.filter.disable_all_patterns();
+        if marks > 0 {
+            self.marking_list_state.set_item_count(self.marking.count());
+            if self.show_marked_lines_only {
                 self.update_view();
+            } else {
+                let marked_indices = self.marking.get_marked_indices();
+                self.resolver.update_mark_tags(&marked_indices);
             }
-            self.viewport.push_history(line_index);
-            self.goto_line(line_index, center);
         }
+        (marks, highlights, fields)
     }
 
-    pub fn goto_selected_mark(&mut self, center: bool) {
-        if let Some(mark) = self.get_selected_mark() {
-            let line_index = mark.line_index;
-            self.viewport.push_history(line_index);
-            self.goto_line(line_index, center);
+    /// Shows the selected line in full, wrapped to the width of the popup. Unlike the log view, long lines
+    /// are not cut off.
+    pub fn activate_full_line_view(&mut self) {
+        let Some(log_line) = self
+            .viewport_to_log_line_index(self.viewport.selected_line)
+            .and_then(|line_index| self.log_buffer.get_line(line_index))
+        else {
+            return;
+        };
+        let width = (self.viewport.width.saturating_sub(4)).min(118).saturating_sub(2);
+        self.full_line_rows = wrap_columns(log_line.content(), width)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        self.full_line_list_state = ListViewState::new_with_count(self.full_line_rows.len());
+        self.set_view_state(ViewState::FullLineView);
+    }
+
+    /// Shows the bytes of the selected line in hex and ASCII. Lines of files are read from the file again, as
+    /// invalid UTF-8 and control characters are replaced or removed when loading them.
+    pub fn activate_hex_view(&mut self) {
+        let Some(log_line) = self
+            .viewport_to_log_line_index(self.viewport.selected_line)
+            .and_then(|line_index| self.log_buffer.get_line(line_index))
+        else {
+            return;
+        };
+        let shown = log_line.content().as_bytes().to_vec();
+        let source_index = log_line.source_index;
+        let path = log_line
+            .log_file_id
+            .and_then(|id| self.file_manager.get(id))
+            .map(|file| file.path.clone());
+        let raw_line = match path {
+            Some(path) => match read_raw_line(&path, source_index) {
+                Ok(raw_line) => raw_line,
+                Err(e) => {
+                    self.show_error(&format!("Failed to read {}:\n{}", path, e));
+                    return;
+                }
+            },
+            None => None,
+        };
+        self.hex_dump_raw = raw_line.is_some();
+        self.hex_dump = HexDump::new(raw_line.unwrap_or(shown));
+        self.hex_list_state = ListViewState::new_with_count(self.hex_dump.row_count());
+        self.set_view_state(ViewState::HexView);
+    }
+
+    /// Opens the line inspector with the details of the selected line.
+    pub fn activate_line_inspector(&mut self) {
+        let Some(log_index) = self.viewport_to_log_line_index(self.viewport.selected_line) else {
+            return;
+        };
+        let Some(log_line) = self.log_buffer.get_line(log_index) else {
+            return;
+        };
+        let content = log_line.content();
+
+        let file_name = log_line
+            .log_file_id
+            .filter(|_| self.file_manager.is_multi_file())
+            .and_then(|id| self.file_manager.iter().find(|file| file.file_id == id))
+            .map(|file| file.get_filename());
+        let event_names: Vec<String> = self
+            .event_tracker
+            .get_events()
+            .iter()
+            .filter(|event| event.line_index == log_index)
+            .map(|event| event.name.clone())
+            .collect();
+        let filters: Vec<String> = self
+            .filter
+            .get_filter_patterns()
+            .iter()
+            .filter(|pattern| {
+                pattern.enabled && pattern.is_match(content, self.column_view.extractor(), self.case_folding)
+            })
+            .map(|pattern| match pattern.mode {
+                ActiveFilterMode::Include => format!("+{}", pattern.pattern),
+                ActiveFilterMode::Exclude => format!("-{}", pattern.pattern),
+            })
+            .collect();
+        let mut fields = match self.column_view.extractor() {
+            Some(extractor) => extractor.all_fields(content).unwrap_or_default(),
+            None => detect_fields(content),
+        };
+        if let Some(script_fields) = self.script_fields.get(&log_index) {
+            fields.extend(script_fields.iter().cloned());
         }
+
+        self.line_inspector = LineInspector::new(log_line, file_name, &event_names, &filters, fields);
+        self.inspector_list_state = ListViewState::new_with_count(self.line_inspector.details.len());
+        self.set_view_state(ViewState::InspectorView);
     }
 
-    /// Enters selection mode and sets the start of the selection range.
-    pub fn start_selection(&mut self) {
-        let current_line = self.viewport.selected_line;
-        self.selection_range = Some((current_line, current_line));
-        self.set_view_state(ViewState::SelectionMode);
+    /// Copies the value of the selected line inspector row to the clipboard.
+    pub fn copy_selected_line_detail(&mut self) {
+        let Some(detail) = self
+            .line_inspector
+            .details
+            .get(self.inspector_list_state.selected_index())
+        else {
+            return;
+        };
+        match self.copy_to_clipboard(&detail.value) {
+            Ok(()) => self.show_message(format!("Copied {} to clipboard", detail.label.to_lowercase()).as_str()),
+            Err(e) => self.show_error(&e),
+        }
     }
 
-    /// Updates the end of the selection range as the cursor moves.
-    pub fn update_selection_end(&mut self) {
-        if let Some((start, _)) = self.selection_range {
-            self.selection_range = Some((start, self.viewport.selected_line));
+    /// Opens the source file of the selected line in the user's editor at that line.
+    pub fn open_in_editor(&mut self) {
+        if self.log_buffer.streaming {
+            self.show_error("Opening in editor is not available in streaming mode");
+            return;
         }
+
+        let Some(log_index) = self.viewport_to_log_line_index(self.viewport.selected_line) else {
+            return;
+        };
+        let Some(log_line) = self.log_buffer.get_line(log_index) else {
+            return;
+        };
+        let Some(file) = log_line.log_file_id.and_then(|id| self.file_manager.get(id)) else {
+            self.show_error("No file associated with the selected line");
+            return;
+        };
+
+        self.pending_editor = Some((file.path.clone(), log_line.source_index + 1));
+    }
+
+    /// Suspends lazylog to the shell on the next loop iteration.
+    pub fn suspend(&mut self) {
+        self.pending_suspend = true;
     }
 
     /// Cancels the current selection.
@@ -1950,74 +7264,164 @@ impl App {
             .map(|(start, end)| if start <= end { (start, end) } else { (end, start) })
     }
 
-    /// Copies the selected lines to the clipboard.
+    /// Gets the first and last column of a block selection, or None if whole lines are selected.
+    pub fn get_block_columns(&self) -> Option<(usize, usize)> {
+        self.selection_range?;
+        self.selection_columns
+            .map(|(anchor, cursor)| (anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// Copies the selected lines to the clipboard as plain text.
     pub fn copy_selection_to_clipboard(&mut self) {
-        if let Some((start, end)) = self.get_selection_range() {
-            let all_lines = self.log_buffer.all_lines();
-            let lines: Vec<String> = (start..=end)
-                .filter_map(|viewport_line| {
-                    self.resolver
-                        .viewport_to_log(viewport_line, all_lines)
-                        .and_then(|log_index| self.log_buffer.get_line(log_index))
-                })
-                .map(|log_line| {
-                    if self.file_manager.is_multi_file() {
-                        if let Some(file_id) = log_line.log_file_id
-                            && self.options.is_disabled(AppOption::HideFileIds)
-                        {
-                            format!("[{}] {}", file_id + 1, log_line.content)
-                        } else {
-                            log_line.content.clone()
-                        }
-                    } else {
-                        log_line.content.clone()
-                    }
-                })
-                .collect();
-
-            if !lines.is_empty() {
-                let content = lines.join("\n");
-                match arboard::Clipboard::new() {
-                    Ok(mut clipboard) => match clipboard.set_text(content) {
-                        Ok(_) => {
-                            let num_lines = lines.len();
-                            self.selection_range = None;
-                            self.set_view_state(ViewState::LogView);
-                            self.show_message(
-                                format!(
-                                    "Copied {} line{} to clipboard",
-                                    num_lines,
-                                    if num_lines == 1 { "" } else { "s" }
-                                )
-                                .as_str(),
-                            );
-                        }
-                        Err(e) => {
-                            self.selection_range = None;
-                            self.set_view_state(ViewState::LogView);
-                            self.show_error(format!("Failed to copy to clipboard: {}", e).as_str());
-                        }
-                    },
-                    Err(e) => {
-                        self.selection_range = None;
-                        self.set_view_state(ViewState::LogView);
-                        self.show_error(format!("Failed to access clipboard: {}", e).as_str());
-                    }
-                }
+        self.copy_source = CopySource::Selection;
+        self.copy_lines(CopyFormat::Plain);
+    }
+
+    /// Opens the copy format popup for the selected lines, the filtered view or the marked lines.
+    pub fn activate_copy_format_view(&mut self, source: CopySource) {
+        if source == CopySource::Marks && self.marking.get_marks().is_empty() {
+            self.show_message("No marked lines to copy");
+            return;
+        }
+        self.copy_source = source;
+        self.copy_format_list_state = ListViewState::new_with_count(CopyFormat::ALL.len());
+        self.set_view_state(ViewState::CopyFormatView);
+    }
+
+    /// Copies the lines of the copy source as the format selected in the copy format popup.
+    fn copy_lines_in_selected_format(&mut self) {
+        let format = CopyFormat::ALL[self.copy_format_list_state.selected_index()];
+        self.copy_lines(format);
+    }
+
+    /// Returns the lines of the copy source with their line numbers.
+    fn lines_to_copy(&self) -> Vec<(usize, String)> {
+        let all_lines = self.log_buffer.all_lines();
+        let block_columns = self
+            .get_block_columns()
+            .filter(|_| self.copy_source == CopySource::Selection);
+        let log_indices: Vec<usize> = match self.copy_source {
+            CopySource::Selection => {
+                let Some((start, end)) = self.get_selection_range() else {
+                    return Vec::new();
+                };
+                let visible_lines = self.resolver.get_visible_lines(all_lines);
+                (start..=end)
+                    .filter_map(|viewport_line| visible_lines.get(viewport_line).map(|vl| vl.log_index))
+                    .collect()
+            }
+            CopySource::View => self
+                .resolver
+                .get_visible_lines(all_lines)
+                .iter()
+                .map(|vl| vl.log_index)
+                .collect(),
+            CopySource::Marks => {
+                let mut indices: Vec<usize> = self.marking.get_marks().iter().map(|mark| mark.line_index).collect();
+                indices.sort_unstable();
+                indices
+            }
+        };
+
+        log_indices
+            .into_iter()
+            .filter_map(|log_index| self.log_buffer.get_line(log_index))
+            .map(|log_line| {
+                let content = if let Some((first, last)) = block_columns {
+                    column_slice(self.options.apply_to_line(log_line.content()), first, last + 1).to_string()
+                } else if self.file_manager.is_multi_file()
+                    && let Some(file_id) = log_line.log_file_id
+                    && self.options.is_disabled(AppOption::HideFileIds)
+                {
+                    format!("[{}] {}", file_id + 1, log_line.content)
+                } else {
+                    log_line.content.clone()
+                };
+                (log_line.source_index + 1, content)
+            })
+            .collect()
+    }
+
+    /// Copies the lines of the copy source to the clipboard in the given format.
+    fn copy_lines(&mut self, format: CopyFormat) {
+        let lines = self.lines_to_copy();
+        self.selection_range = None;
+        self.set_view_state(ViewState::LogView);
+        if lines.is_empty() {
+            return;
+        }
+
+        match self.copy_to_clipboard(&format.format(&lines)) {
+            Ok(()) => {
+                let num_lines = lines.len();
+                let as_format = match format {
+                    CopyFormat::Plain => String::new(),
+                    format => format!(" as {}", format.name().to_lowercase()),
+                };
+                self.show_message(
+                    format!(
+                        "Copied {} line{} to clipboard{}",
+                        num_lines,
+                        if num_lines == 1 { "" } else { "s" },
+                        as_format
+                    )
+                    .as_str(),
+                );
             }
+            Err(e) => self.show_error(&e),
         }
     }
 
+    /// Measures the time, lines and events between the ends of the selection, or between the selected
+    /// mark and the next one in the marks view, and shows the result in a message.
+    pub fn measure_interval(&mut self) {
+        let endpoints = match self.view_state {
+            ViewState::SelectionMode => self.get_selection_range().and_then(|(start, end)| {
+                Some((
+                    self.viewport_to_log_line_index(start)?,
+                    self.viewport_to_log_line_index(end)?,
+                ))
+            }),
+            ViewState::MarksView => {
+                let marks = self.get_marks_view_list();
+                let selected = self.marking_list_state.selected_index();
+                let other = if selected + 1 < marks.len() {
+                    selected + 1
+                } else {
+                    selected.saturating_sub(1)
+                };
+                match (marks.get(selected), marks.get(other)) {
+                    (Some(first), Some(second)) if selected != other => Some((first.line_index, second.line_index)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let Some((first, second)) = endpoints else {
+            self.show_error("Select a range of lines, or at least two marks");
+            return;
+        };
+
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let interval = Interval::measure(
+            all_lines,
+            first,
+            second,
+            visible_lines.iter().map(|vl| vl.log_index),
+            self.event_tracker.get_enabled_events(),
+        );
+        self.show_message(&interval.report());
+    }
+
     /// Returns marks that are currently visible based on active filters.
     pub fn get_visible_marks(&self) -> Vec<Mark> {
         let lines = self.log_buffer.all_lines();
-        let visible_lines = self.resolver.get_visible_lines(lines);
-        let visible_indices: HashSet<usize> = visible_lines.iter().map(|vl| vl.log_index).collect();
 
         self.marking
             .get_marks()
             .iter()
-            .filter(|mark| visible_indices.contains(&mark.line_index))
+            .filter(|mark| self.resolver.is_visible(lines, mark.line_index))
             .cloned()
             .collect()
     }
@@ -2025,27 +7429,121 @@ impl App {
     /// Returns events that are currently visible based on active filters and enabled.
     pub fn get_visible_events(&self) -> Vec<LogEvent> {
         let lines = self.log_buffer.all_lines();
-        let visible_lines = self.resolver.get_visible_lines(lines);
-        let visible_indices: HashSet<usize> = visible_lines.iter().map(|vl| vl.log_index).collect();
 
         self.event_tracker
             .get_enabled_events()
             .into_iter()
-            .filter(|event| visible_indices.contains(&event.line_index))
+            .filter(|event| self.resolver.is_visible(lines, event.line_index))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the visible events the event navigation jumps between: those of the chosen event type,
+    /// even if it is disabled in the event filter, or else all enabled events.
+    fn get_navigation_events(&self) -> Vec<LogEvent> {
+        let Some(scope) = &self.event_scope else {
+            return self.get_visible_events();
+        };
+        let all_lines = self.log_buffer.all_lines();
+
+        self.event_tracker
+            .get_events_by_name(scope)
+            .into_iter()
+            .filter(|event| self.resolver.is_visible(all_lines, event.line_index))
             .cloned()
             .collect()
     }
 
+    /// Builds the minimap of a viewport with the given number of rows from the visible enabled events,
+    /// marks and search matches.
+    pub fn build_minimap(&self, viewport: &Viewport, height: usize) -> Minimap {
+        let mut hasher = DefaultHasher::new();
+        self.event_tracker.hash_enabled_events(&mut hasher);
+        for mark in self.marking.get_marks() {
+            mark.line_index.hash(&mut hasher);
+        }
+        if self.search.get_active_pattern().is_some() {
+            self.search.get_match_indices().hash(&mut hasher);
+        }
+        let key = MinimapKey {
+            generation: self.log_buffer.generation(),
+            height,
+            total_lines: viewport.total_lines,
+            ticks_hash: hasher.finish(),
+            visible_lines: self.resolver.get_visible_lines(self.log_buffer.all_lines()),
+        };
+        self.minimap_cache
+            .get_or_build(key, || self.build_uncached_minimap(viewport, height))
+    }
+
+    /// Builds the minimap of a viewport from scratch.
+    fn build_uncached_minimap(&self, viewport: &Viewport, height: usize) -> Minimap {
+        let mut line_ticks: HashMap<usize, MinimapTick> = HashMap::new();
+        for event in self.event_tracker.get_enabled_events() {
+            line_ticks.insert(event.line_index, MinimapTick::Event);
+        }
+        for line_index in self.event_tracker.get_critical_event_indices() {
+            line_ticks.insert(line_index, MinimapTick::CriticalEvent);
+        }
+        for mark in self.marking.get_marks() {
+            let tick = line_ticks.entry(mark.line_index).or_insert(MinimapTick::Mark);
+            *tick = (*tick).max(MinimapTick::Mark);
+        }
+
+        let mut ticks: Vec<(usize, MinimapTick)> = Vec::new();
+        if !line_ticks.is_empty() {
+            let visible_lines = self.resolver.get_visible_lines(self.log_buffer.all_lines());
+            ticks.extend(
+                visible_lines
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, vl)| line_ticks.get(&vl.log_index).map(|&tick| (index, tick))),
+            );
+        }
+        if self.search.get_active_pattern().is_some() {
+            ticks.extend(
+                self.search
+                    .get_match_indices()
+                    .iter()
+                    .map(|&index| (index, MinimapTick::SearchMatch)),
+            );
+        }
+        Minimap::new(viewport.total_lines, height, ticks)
+    }
+
+    /// Jumps to the first event, mark or search match in the next minimap row that has one.
+    pub fn minimap_next(&mut self) {
+        let minimap = self.build_minimap(&self.viewport, self.minimap_height());
+        if let Some(line) = minimap.next_tick_line(self.viewport.selected_line) {
+            self.push_viewport_line_to_history(line);
+            self.viewport.goto_line(line, false);
+        }
+    }
+
+    /// Jumps to the first event, mark or search match in the previous minimap row that has one.
+    pub fn minimap_previous(&mut self) {
+        let minimap = self.build_minimap(&self.viewport, self.minimap_height());
+        if let Some(line) = minimap.previous_tick_line(self.viewport.selected_line) {
+            self.push_viewport_line_to_history(line);
+            self.viewport.goto_line(line, false);
+        }
+    }
+
+    /// Returns the number of rows of the minimap, or the height it would have if it isn't shown.
+    fn minimap_height(&self) -> usize {
+        self.minimap_area
+            .get()
+            .map_or(self.viewport.height, |area| area.height as usize)
+    }
+
     /// Returns enabled events whose lines are NOT visible (filtered out by text filters).
     fn get_filtered_events(&self) -> Vec<LogEvent> {
         let lines = self.log_buffer.all_lines();
-        let visible_lines = self.resolver.get_visible_lines(lines);
-        let visible_indices: HashSet<usize> = visible_lines.iter().map(|vl| vl.log_index).collect();
 
         self.event_tracker
             .get_enabled_events()
             .into_iter()
-            .filter(|event| !visible_indices.contains(&event.line_index))
+            .filter(|event| !self.resolver.is_visible(lines, event.line_index))
             .cloned()
             .collect()
     }
@@ -2053,8 +7551,22 @@ impl App {
     /// Returns all events for the events list plus a set of filtered-out line indices.
     /// When event filtering is active, includes both visible and filtered-out events.
     pub fn get_events_for_list(&self) -> (Vec<LogEvent>, HashSet<usize>) {
+        let (events, filtered_indices) = self.get_events_for_all_tabs();
+        (self.events_of_tab(events), filtered_indices)
+    }
+
+    /// Keeps the events of the selected events view tab.
+    pub fn events_of_tab(&self, mut events: Vec<LogEvent>) -> Vec<LogEvent> {
+        if let Some(tab) = &self.events_tab {
+            events.retain(|event| &event.name == tab);
+        }
+        events
+    }
+
+    /// Returns the events of every type for the events list, before picking those of the selected tab.
+    pub fn get_events_for_all_tabs(&self) -> (Vec<LogEvent>, HashSet<usize>) {
         let visible = self.get_visible_events();
-        if self.event_tracker.has_event_filtering() {
+        let (mut events, filtered_indices) = if self.event_tracker.has_event_filtering() {
             let filtered = self.get_filtered_events();
             let filtered_indices: HashSet<usize> = filtered.iter().map(|e| e.line_index).collect();
             let mut all = visible;
@@ -2063,15 +7575,58 @@ impl App {
             (all, filtered_indices)
         } else {
             (visible, HashSet::new())
+        };
+        events.retain(|event| self.matches_list_query(&event.name, event.line_index));
+        (events, filtered_indices)
+    }
+
+    /// Returns the visible marks matching the list query, as listed in the marks and events views.
+    pub fn get_listed_marks(&self) -> Vec<Mark> {
+        let mut marks = self.get_visible_marks();
+        marks.retain(|mark| self.matches_list_query(mark.name.as_deref().unwrap_or("MARK"), mark.line_index));
+        marks
+    }
+
+    /// Returns the marks as listed in the marks view, grouped by name if grouping is on.
+    pub fn get_marks_view_list(&self) -> Vec<Mark> {
+        let mut marks = self.get_listed_marks();
+        if self.marks_grouped {
+            group_marks_by_name(&mut marks);
         }
+        marks
     }
 
     /// Gets the currently selected mark based on marking_list_state selection.
     fn get_selected_mark(&self) -> Option<Mark> {
-        let marks = self.get_visible_marks();
+        let marks = self.get_marks_view_list();
         marks.get(self.marking_list_state.selected_index()).cloned()
     }
 
+    /// Gets the next selected mark after the given line index, or before it, wrapping around at the ends.
+    /// Returns `None` if no visible mark is selected in the marks view.
+    fn cycle_selected_marks(&self, current_line_index: usize, forward: bool) -> Option<usize> {
+        let selected: Vec<usize> = self
+            .get_visible_marks()
+            .iter()
+            .map(|mark| mark.line_index)
+            .filter(|line_index| self.selected_marks.contains(line_index))
+            .collect();
+        if forward {
+            selected
+                .iter()
+                .find(|&&line_index| line_index > current_line_index)
+                .or(selected.first())
+                .copied()
+        } else {
+            selected
+                .iter()
+                .rev()
+                .find(|&&line_index| line_index < current_line_index)
+                .or(selected.last())
+                .copied()
+        }
+    }
+
     /// Gets the next mark after the given line index.
     fn get_next_mark(&self, current_line_index: usize) -> Option<usize> {
         let visible_marks = self.get_visible_marks();
@@ -2093,10 +7648,13 @@ impl App {
 
     /// Finds the index in the marks list that is nearest to the given line index.
     fn find_nearest_mark(&self, line_index: usize) -> Option<usize> {
-        let marks = self.get_visible_marks();
+        let marks = self.get_marks_view_list();
         if marks.is_empty() {
             return None;
         }
+        if self.marks_grouped {
+            return (0..marks.len()).min_by_key(|&index| marks[index].line_index.abs_diff(line_index));
+        }
 
         match marks.binary_search_by_key(&line_index, |m| m.line_index) {
             Ok(idx) => Some(idx),
@@ -2175,8 +7733,7 @@ impl App {
     }
 
     fn get_next_event_line(&self, line_index: usize) -> Option<usize> {
-        let enabled_events = self.get_visible_events();
-        enabled_events
+        self.get_navigation_events()
             .iter()
             .find(|event| event.line_index > line_index)
             .map(|event| event.line_index)
@@ -2184,8 +7741,7 @@ impl App {
 
     /// Returns the line index of the previous event before the given line index.
     fn get_previous_event_line(&self, line_index: usize) -> Option<usize> {
-        let enabled_events = self.get_visible_events();
-        enabled_events
+        self.get_navigation_events()
             .iter()
             .rev()
             .find(|event| event.line_index < line_index)
@@ -2218,3 +7774,513 @@ impl App {
             .map(|e| e.line_index)
     }
 }
+
+/// Keeps the viewport on the given log line, or the closest visible line, after the visible lines
+/// changed. Follows the end of the log instead when streaming in follow mode.
+fn restore_selection(
+    resolver: &mut ViewportResolver,
+    all_lines: &[LogLine],
+    viewport: &mut Viewport,
+    log_line_index: Option<usize>,
+    streaming: bool,
+) {
+    let num_lines = viewport.total_lines;
+    if num_lines == 0 {
+        viewport.selected_line = 0;
+        return;
+    }
+
+    if streaming && viewport.follow_mode {
+        viewport.goto_bottom();
+        return;
+    }
+
+    let new_selected_line = if let Some(target_log_line_index) = log_line_index {
+        // Find closest visible line to the target
+        resolver
+            .log_to_viewport(target_log_line_index, all_lines)
+            .unwrap_or_else(|| {
+                // Find closest visible line
+                let visible = resolver.get_visible_lines(all_lines);
+                visible
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, v)| v.log_index.abs_diff(target_log_line_index))
+                    .map(|(idx, _)| idx)
+                    .unwrap_or(viewport.selected_line.min(num_lines - 1))
+            })
+    } else {
+        viewport.selected_line.min(num_lines - 1)
+    };
+
+    viewport.goto_line(new_selected_line, false);
+}
+
+#[cfg(test)]
+impl App {
+    /// Constructs an app showing `lines` with `config` as its config file, without saved state, sized to
+    /// `width` x `height` columns and rows of log view. Must be called within a tokio runtime.
+    pub(crate) fn with_lines(lines: &[&str], config: &str, width: usize, height: usize) -> Self {
+        use clap::Parser;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let id = format!("{}-{}", std::process::id(), NEXT_ID.fetch_add(1, Ordering::Relaxed));
+        let log_path = std::env::temp_dir().join(format!("lazylog-app-{}.log", id));
+        let config_path = std::env::temp_dir().join(format!("lazylog-app-{}.toml", id));
+        std::fs::write(&log_path, lines.join("\n")).unwrap();
+        std::fs::write(&config_path, config).unwrap();
+
+        let args = Cli::parse_from([
+            "lazylog",
+            "--no-persist",
+            "--config",
+            config_path.to_str().unwrap(),
+            log_path.to_str().unwrap(),
+        ]);
+        let mut app = App::new(args);
+        app.resize_viewports(width, height);
+        std::fs::remove_file(&log_path).unwrap();
+        std::fs::remove_file(&config_path).unwrap();
+        app
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{buffer::Buffer, widgets::Widget};
+
+    fn mouse_event(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_events_changing_the_screen_need_a_redraw() {
+        let mut app = App::with_lines(&["one", "two", "three"], "", 40, 8);
+        let ignored = [
+            crossterm::event::Event::FocusGained,
+            crossterm::event::Event::Mouse(mouse_event(MouseEventKind::Moved, 3, 3)),
+            Key(KeyEvent::new_with_kind(
+                KeyCode::Char('j'),
+                KeyModifiers::NONE,
+                KeyEventKind::Release,
+            )),
+        ];
+        for event in ignored {
+            assert!(!app.handle_crossterm_event(event.clone()).unwrap(), "{:?}", event);
+        }
+        assert_eq!(app.viewport.selected_line, 0);
+
+        let key = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert!(app.handle_crossterm_event(Key(key)).unwrap());
+        assert_eq!(app.viewport.selected_line, 1);
+        assert!(
+            app.handle_crossterm_event(crossterm::event::Event::Resize(50, 10))
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mouse_clicks_on_the_minimap_and_badges() {
+        let lines: Vec<String> = (0..100).map(|i| format!("line {}", i)).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let mut app = App::with_lines(&lines, "", 40, 10);
+        let click = MouseEventKind::Down(MouseButton::Left);
+
+        // Nothing is clicked before the minimap is rendered
+        assert!(!app.handle_mouse_event(mouse_event(click, 40, 9)));
+
+        app.minimap_area.set(Some(Rect::new(40, 1, 1, 10)));
+        assert!(!app.handle_mouse_event(mouse_event(MouseEventKind::Moved, 40, 10)));
+        assert!(!app.handle_mouse_event(mouse_event(MouseEventKind::Down(MouseButton::Right), 40, 10)));
+        assert!(!app.handle_mouse_event(mouse_event(click, 20, 10)));
+        assert_eq!(app.viewport.selected_line, 0);
+
+        assert!(app.handle_mouse_event(mouse_event(click, 40, 10)));
+        assert!(app.viewport.selected_line >= 90, "{}", app.viewport.selected_line);
+
+        app.severity_badge_areas
+            .borrow_mut()
+            .push((Rect::new(0, 11, 5, 1), LogLevel::Error));
+        assert!(app.handle_mouse_event(mouse_event(click, 2, 11)));
+
+        // Clicks only act in the log view without an overlay
+        app.overlay = Some(Overlay::AddFile);
+        assert!(!app.handle_mouse_event(mouse_event(click, 40, 10)));
+    }
+
+    #[tokio::test]
+    async fn test_long_messages_scroll_and_stay_open() {
+        let mut app = App::with_lines(&["one"], "", 40, 10);
+        app.show_overlay(Overlay::Message("short".to_string()));
+        assert!(app.message_timestamp.is_some());
+
+        let long: Vec<String> = (0..40).map(|i| format!("at frame {}", i)).collect();
+        app.show_overlay(Overlay::Message(long.join("\n")));
+        assert!(app.message_timestamp.is_none());
+
+        let area = Rect::new(0, 0, 41, 12);
+        (&app).render(area, &mut Buffer::empty(area));
+        let max_scroll = app.message_max_scroll.get();
+        assert!(max_scroll > 0);
+
+        app.move_down();
+        assert_eq!(app.message_scroll, 1);
+        app.move_up();
+        app.move_up();
+        assert_eq!(app.message_scroll, 0);
+        app.page_down();
+        app.page_down();
+        app.page_down();
+        app.page_down();
+        assert_eq!(app.message_scroll, max_scroll);
+        assert_eq!(app.viewport.selected_line, 0);
+
+        // A new message starts at the top
+        app.show_overlay(Overlay::Message("short".to_string()));
+        assert_eq!(app.message_scroll, 0);
+    }
+
+    #[tokio::test]
+    async fn test_tagged_events_become_named_marks() {
+        let config = "events = [{ name = \"Boom\", pattern = \"boom\" }]\n";
+        let mut app = App::with_lines(&["start", "boom 1", "ok", "boom 2"], config, 40, 8);
+        let (events, _) = app.get_events_for_list();
+        let event_lines: HashSet<usize> = events.iter().map(|event| event.line_index).collect();
+        assert!(
+            event_lines.contains(&1) && event_lines.contains(&3),
+            "{:?}",
+            event_lines
+        );
+
+        app.mark_tagged_events();
+        assert_eq!(app.marking.count(), 0);
+
+        // Tagging moves on to the next event, tagging again untags
+        app.toggle_event_tag();
+        assert_eq!(app.tagged_events.len(), 1);
+        assert_eq!(app.events_list_state.selected_index(), 1);
+        app.events_list_state.move_up();
+        app.toggle_event_tag();
+        assert!(app.tagged_events.is_empty());
+
+        app.toggle_all_event_tags();
+        assert_eq!(app.tagged_events, event_lines);
+        app.toggle_all_event_tags();
+        assert!(app.tagged_events.is_empty());
+        app.toggle_all_event_tags();
+
+        app.mark_tagged_events();
+        assert!(app.tagged_events.is_empty());
+        assert_eq!(app.marking.count(), event_lines.len());
+        for mark in app
+            .marking
+            .get_marks()
+            .iter()
+            .filter(|mark| [1, 3].contains(&mark.line_index))
+        {
+            assert_eq!(mark.name.as_deref(), Some("Boom"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idle_app_schedules_no_timer() {
+        let mut app = App::with_lines(&["one", "two"], "", 40, 8);
+        assert_eq!(app.next_timer(), None);
+
+        let before = Instant::now();
+        app.show_message("saved");
+        let deadline = app.next_timer().unwrap();
+        assert!(deadline > before && deadline <= Instant::now() + MESSAGE_TIMEOUT);
+
+        // The tick at the deadline hides the message, after which nothing is scheduled
+        app.message_timestamp = Some(before - MESSAGE_TIMEOUT);
+        app.tick();
+        assert!(app.overlay.is_none());
+        assert_eq!(app.next_timer(), None);
+
+        let autosave = Instant::now() + AUTOSAVE_DELAY;
+        app.autosave_due = Some(autosave);
+        assert_eq!(app.next_timer(), Some(autosave));
+    }
+
+    #[tokio::test]
+    async fn test_frozen_display_keeps_buffering_lines() {
+        let mut app = App::with_lines(&["one", "two"], "", 40, 8);
+        // Only streams can be frozen
+        app.toggle_freeze_display();
+        assert_eq!(app.frozen_new_line_count(), None);
+
+        app.log_buffer.streaming = true;
+        app.toggle_freeze_display();
+        for content in ["three", "four"] {
+            app.log_buffer.append_stream_line(content.to_string(), None);
+        }
+        app.update_view();
+        assert_eq!(app.viewport.total_lines, 2);
+        assert_eq!(app.frozen_new_line_count(), Some(2));
+
+        // Resuming shows the buffered lines, starting at the first one received while frozen
+        app.toggle_freeze_display();
+        assert_eq!(app.frozen_new_line_count(), None);
+        assert_eq!(app.viewport.total_lines, 4);
+        assert_eq!(app.viewport.selected_line, 2);
+        assert!(!app.viewport.follow_mode);
+    }
+
+    #[tokio::test]
+    async fn test_pinned_pane_divides_the_log_view_width() {
+        let mut app = App::with_lines(&["one", "two"], "", 81, 20);
+        app.pin_split_view();
+        let split = app.split_viewport.as_ref().unwrap();
+        assert_eq!((app.viewport.width, app.viewport.height), (39, 20));
+        // The pinned pane has a title row
+        assert_eq!((split.width, split.height), (40, 19));
+        assert_eq!((app.log_view_width(), app.log_view_height()), (81, 20));
+
+        app.resize_viewports(101, 30);
+        let split = app.split_viewport.as_ref().unwrap();
+        assert_eq!((app.viewport.width, split.width, split.height), (49, 50, 29));
+        assert_eq!(app.log_view_width(), 101);
+
+        app.pin_split_view();
+        assert!(matches!(app.overlay, Some(Overlay::Error(_))));
+
+        app.close_split();
+        assert_eq!((app.viewport.width, app.viewport.height), (101, 30));
+
+        // Too narrow to pin
+        app.resize_viewports(MIN_PIN_WIDTH - 1, 30);
+        app.pin_split_view();
+        assert!(app.split_viewport.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_filters_hide_all_lines() {
+        let mut app = App::with_lines(&["INFO started", "INFO ready"], "", 80, 10);
+        assert!(!app.filters_hide_all_lines());
+
+        app.filter.add_filter(&FilterPattern::new(
+            "timeout".to_string(),
+            ActiveFilterMode::Include,
+            false,
+            true,
+        ));
+        app.update_view();
+        assert!(app.filters_hide_all_lines());
+
+        // Esc leaves the filters alone
+        app.handle_key_events(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(app.filter.enabled_count(), 1);
+
+        // Lines hidden by the level are not blamed on the filters
+        app.set_min_level(Some(LogLevel::Error));
+        assert!(!app.filters_hide_all_lines());
+    }
+
+    #[tokio::test]
+    async fn test_search_to_filter_matches_the_searched_text() {
+        let lines = [
+            r#"{"error":"refused","msg":"retry after error:timeout"}"#,
+            r#"{"error":"timeout"}"#,
+            "WARN error:timeout",
+        ];
+        let mut app = App::with_lines(&lines, "", 80, 10);
+        app.search.set_pattern("error:timeout");
+        app.search_to_filter();
+
+        let filter = &app.filter.get_filter_patterns()[0];
+        assert!(filter.field.is_none());
+        assert!(!filter.case_sensitive);
+        assert_eq!(app.viewport.total_lines, 2);
+
+        // Searching the filter again gives the same pattern
+        app.filter_to_search();
+        assert_eq!(app.view_state, ViewState::ActiveSearchMode);
+        assert_eq!(app.input.value(), "error:timeout");
+    }
+
+    #[tokio::test]
+    async fn test_field_filters_are_not_searched() {
+        let mut app = App::with_lines(&[r#"{"level":"ERROR"}"#], "", 80, 10);
+        app.filter.add_filter_from_pattern("@level:ERROR");
+        app.filter_to_search();
+        assert_ne!(app.view_state, ViewState::ActiveSearchMode);
+
+        // A search for the text of a field filter becomes a plain text filter
+        let mut app = App::with_lines(&[r#"{"level":"ERROR"}"#], "", 80, 10);
+        app.search.set_pattern("@level:ERROR");
+        app.search_to_filter();
+        assert!(app.filter.get_filter_patterns()[0].field.is_none());
+        assert_eq!(app.viewport.total_lines, 0);
+    }
+
+    #[tokio::test]
+    async fn test_block_selection_copies_the_columns() {
+        let mut app = App::with_lines(&["id=1234 ok", "id=5678 failed", "id=90"], "", 80, 10);
+        app.viewport.horizontal_offset = 3;
+        app.start_block_selection();
+        for _ in 0..3 {
+            app.move_selection_column(true);
+        }
+        app.viewport.selected_line = 2;
+        app.update_selection_end();
+        app.copy_source = CopySource::Selection;
+
+        let copied: Vec<(usize, String)> = app.lines_to_copy();
+        assert_eq!(
+            copied,
+            vec![(1, "1234".to_string()), (2, "5678".to_string()), (3, "90".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_input_preview_counted_once_typing_pauses() {
+        let mut app = App::with_lines(&["INFO started", "ERROR failed", "ERROR retry failed"], "", 80, 10);
+        app.activate_filter_mode();
+        for c in "error".chars() {
+            app.handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .unwrap();
+        }
+        assert_eq!(app.preview_count(), None);
+
+        app.preview_due = Some(Instant::now());
+        app.tick();
+        assert_eq!(app.preview_count(), Some(2));
+        assert_eq!(app.preview_due, None);
+    }
+
+    #[tokio::test]
+    async fn test_oldest_lines_dropped_in_chunks() {
+        let lines: Vec<String> = (0..60).map(|index| format!("line {}", index)).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let mut app = App::with_lines(&lines, "", 80, 10);
+        app.max_lines = Some(50);
+        app.marking.toggle_mark(59);
+
+        app.drop_lines_over_max();
+        assert_eq!(app.log_buffer.get_total_lines_count(), 45);
+        assert_eq!(app.log_buffer.get_line(0).unwrap().content(), "line 15");
+        assert_eq!(app.marking.get_marks()[0].line_index, 44);
+
+        // Nothing is dropped again until the buffer is over the maximum
+        app.drop_lines_over_max();
+        assert_eq!(app.log_buffer.get_total_lines_count(), 45);
+    }
+
+    #[tokio::test]
+    async fn test_opened_session_replaces_filters_marks_and_viewport() {
+        let lines = ["INFO start", "ERROR timeout db", "ERROR timeout cache", "WARN retry db"];
+        let mut session_app = App::with_lines(&lines, "", 80, 10);
+        let session = Session::from_app(&session_app);
+        let session_lines = std::mem::take(&mut session_app.log_buffer);
+
+        let mut app = App::with_lines(&lines, "", 80, 10);
+        app.filter.add_filter(&FilterPattern::new(
+            "db".to_string(),
+            ActiveFilterMode::Exclude,
+            false,
+            true,
+        ));
+        app.marking.toggle_mark(2);
+        app.viewport.selected_line = 1;
+        app.viewport.horizontal_offset = 5;
+        app.update_view();
+
+        app.replace_with_session("triage".to_string(), session, session_lines);
+        assert_eq!(app.filter.count(), 0);
+        assert!(app.marking.get_marks().is_empty());
+        assert_eq!(app.viewport.selected_line, 0);
+        assert_eq!(app.viewport.horizontal_offset, 0);
+        assert_eq!(app.viewport.total_lines, 4);
+        assert_eq!(app.session_name.as_deref(), Some("triage"));
+    }
+
+    const TIMEOUT_RETRY_EVENTS: &str = r#"
+        [[events]]
+        name = "Timeout"
+        pattern = "timeout"
+
+        [[events]]
+        name = "Retry"
+        pattern = "retry"
+    "#;
+
+    fn listed_event_lines(app: &App) -> Vec<usize> {
+        app.get_events_for_list()
+            .0
+            .iter()
+            .map(|event| event.line_index)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_events_list_filtered_by_typed_query() {
+        let lines = ["INFO start", "ERROR timeout db", "ERROR timeout cache", "WARN retry db"];
+        let mut app = App::with_lines(&lines, TIMEOUT_RETRY_EVENTS, 80, 10);
+        app.activate_events_view();
+        assert_eq!(listed_event_lines(&app), vec![1, 2, 3]);
+
+        app.handle_key_events(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE))
+            .unwrap();
+        for c in "DB".chars() {
+            app.handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .unwrap();
+        }
+        assert_eq!(app.list_query, "DB");
+        assert_eq!(listed_event_lines(&app), vec![1, 3]);
+        assert_eq!(app.events_list_state.item_count(), 2);
+
+        // Enter keeps the query, which can be changed later
+        app.handle_key_events(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(app.list_query, "DB");
+        app.set_list_query("cache");
+        assert_eq!(listed_event_lines(&app), vec![2]);
+
+        // Esc in the list clears the query
+        app.handle_key_events(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+            .unwrap();
+        assert!(app.list_query.is_empty());
+        assert_eq!(listed_event_lines(&app), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_events_tabs() {
+        let lines = ["INFO start", "ERROR timeout db", "ERROR timeout cache", "WARN retry db"];
+        let mut app = App::with_lines(&lines, TIMEOUT_RETRY_EVENTS, 80, 10);
+        app.activate_events_view();
+
+        let (events, _) = app.get_events_for_all_tabs();
+        let tabs = app.events_tabs(&events);
+        assert_eq!(
+            tabs,
+            vec![
+                (None, 3),
+                (Some("Timeout".to_string()), 2),
+                (Some("Retry".to_string()), 1)
+            ]
+        );
+
+        app.handle_key_events(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(app.events_tab.as_deref(), Some("Timeout"));
+        assert_eq!(listed_event_lines(&app), vec![1, 2]);
+
+        // Shift+Tab, reported with or without the shift modifier
+        app.handle_key_events(KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(app.events_tab, None);
+        app.handle_key_events(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT))
+            .unwrap();
+        assert_eq!(app.events_tab.as_deref(), Some("Retry"));
+        assert_eq!(listed_event_lines(&app), vec![3]);
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+use std::process::Stdio;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::live_processor::InputLine;
+use crate::log::OutputStream;
+
+/// State of a spawned command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChildStatus {
+    Running,
+    /// The command exited with the given code, or `None` if it was terminated by a signal.
+    Exited(Option<i32>),
+    /// The command could not be started or waited for.
+    Failed(String),
+}
+
+impl fmt::Display for ChildStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChildStatus::Running => write!(f, "running"),
+            ChildStatus::Exited(Some(code)) => write!(f, "exited ({})", code),
+            ChildStatus::Exited(None) => write!(f, "terminated"),
+            ChildStatus::Failed(error) => write!(f, "failed: {}", error),
+        }
+    }
+}
+
+/// A command spawned by lazylog whose stdout and stderr are streamed into the log buffer.
+#[derive(Debug)]
+pub struct ChildProcess {
+    /// Program and arguments.
+    command: Vec<String>,
+    /// Incremented on every start, so exit events of a previous run can be told apart.
+    generation: usize,
+    status: ChildStatus,
+    /// Sender used to kill the running command.
+    kill_tx: Option<oneshot::Sender<()>>,
+}
+
+impl ChildProcess {
+    pub fn new(command: Vec<String>) -> Self {
+        Self {
+            command,
+            generation: 0,
+            status: ChildStatus::Exited(None),
+            kill_tx: None,
+        }
+    }
+
+    /// Returns the command line as a single string.
+    pub fn command_line(&self) -> String {
+        self.command.join(" ")
+    }
+
+    pub fn status(&self) -> &ChildStatus {
+        &self.status
+    }
+
+    /// Starts the command, killing it first if it is already running.
+    ///
+    /// Output lines are sent to `line_tx`. `on_exit` is called with the generation and exit status
+    /// when the command exits by itself.
+    pub fn start<F>(&mut self, line_tx: mpsc::UnboundedSender<InputLine>, on_exit: F)
+    where
+        F: FnOnce(usize, ChildStatus) + Send + 'static,
+    {
+        self.kill();
+        self.generation += 1;
+
+        let Some((program, args)) = self.command.split_first() else {
+            self.status = ChildStatus::Failed("no command given".to_string());
+            return;
+        };
+
+        let spawned = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                self.status = ChildStatus::Failed(e.to_string());
+                return;
+            }
+        };
+
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward_lines(stdout, OutputStream::Stdout, line_tx.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_lines(stderr, OutputStream::Stderr, line_tx));
+        }
+
+        let (kill_tx, kill_rx) = oneshot::channel();
+        let generation = self.generation;
+        tokio::spawn(async move {
+            tokio::select! {
+                result = child.wait() => {
+                    let status = match result {
+                        Ok(status) => ChildStatus::Exited(status.code()),
+                        Err(e) => ChildStatus::Failed(e.to_string()),
+                    };
+                    on_exit(generation, status);
+                }
+                _ = kill_rx => {
+                    let _ = child.kill().await;
+                }
+            }
+        });
+
+        self.kill_tx = Some(kill_tx);
+        self.status = ChildStatus::Running;
+    }
+
+    /// Kills the command if it is running.
+    pub fn kill(&mut self) {
+        if let Some(kill_tx) = self.kill_tx.take() {
+            let _ = kill_tx.send(());
+        }
+    }
+
+    /// Records the exit status of the command. Ignored if it belongs to a previous run.
+    pub fn handle_exit(&mut self, generation: usize, status: ChildStatus) {
+        if generation == self.generation {
+            self.kill_tx = None;
+            self.status = status;
+        }
+    }
+}
+
+/// Reads lines from a command output stream and forwards them, tagged with the stream.
+async fn forward_lines<R>(reader: R, stream: OutputStream, line_tx: mpsc::UnboundedSender<InputLine>)
+where
+    R: AsyncRead + Unpin,
+{
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                while matches!(buf.last(), Some(b'\n' | b'\r')) {
+                    buf.pop();
+                }
+                // Commands may write non-UTF-8 output, so don't fail on it
+                let line = InputLine {
+                    content: String::from_utf8_lossy(&buf).into_owned(),
+                    stream: Some(stream),
+                };
+                if line_tx.send(line).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_start_captures_both_streams() {
+        let (line_tx, mut line_rx) = mpsc::unbounded_channel();
+        let (exit_tx, exit_rx) = oneshot::channel();
+        let mut child = ChildProcess::new(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo out; echo err >&2; exit 3".to_string(),
+        ]);
+
+        child.start(line_tx, move |generation, status| {
+            let _ = exit_tx.send((generation, status));
+        });
+        assert_eq!(child.status(), &ChildStatus::Running);
+
+        let (generation, status) = exit_rx.await.unwrap();
+        child.handle_exit(generation, status);
+        assert_eq!(child.status(), &ChildStatus::Exited(Some(3)));
+
+        let mut lines = Vec::new();
+        while let Some(line) = line_rx.recv().await {
+            lines.push((line.content, line.stream));
+        }
+        lines.sort_by_key(|(content, _)| content.clone());
+        assert_eq!(
+            lines,
+            vec![
+                ("err".to_string(), Some(OutputStream::Stderr)),
+                ("out".to_string(), Some(OutputStream::Stdout)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_handle_exit_ignores_previous_runs() {
+        let mut child = ChildProcess::new(vec!["true".to_string()]);
+        child.generation = 2;
+        child.status = ChildStatus::Running;
+
+        child.handle_exit(1, ChildStatus::Exited(Some(0)));
+        assert_eq!(child.status(), &ChildStatus::Running);
+
+        child.handle_exit(2, ChildStatus::Exited(Some(0)));
+        assert_eq!(child.status().to_string(), "exited (0)");
+    }
+}
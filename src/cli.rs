@@ -1,10 +1,16 @@
 use clap::Parser;
 use std::io::IsTerminal;
+use std::num::NonZeroUsize;
+
+use crate::filter::{ActiveFilterMode, FilterPattern, parse_filter_arg};
+use crate::level::LogLevel;
+use crate::pager::{StartupCommand, split_startup_commands};
 
 #[derive(Parser, Debug)]
 #[command(version, long_version = crate::version::long_version())]
 pub struct Cli {
-    /// Log file path(s). If not provided, reads from stdin.
+    /// Log file path(s). If not provided, reads from stdin. Commands like `+G`, `+/pattern` and `+N` run
+    /// once the lines are loaded, like in `less`.
     pub files: Vec<String>,
 
     /// Path to config file
@@ -15,25 +21,213 @@ pub struct Cli {
     #[arg(short, long, value_name = "FILE")]
     pub filters: Option<String>,
 
+    /// Profile of the config whose highlights, events and filters are used on top of the shared ones
+    #[arg(short, long, value_name = "NAME")]
+    pub profile: Option<String>,
+
     /// Clear all persisted state files
     #[arg(long)]
     pub clear_state: bool,
 
+    /// Print what migrating the persisted state files to the current format would change, then exit
+    #[arg(long)]
+    pub migrate_dry_run: bool,
+
+    /// Print the lines passing the filters to stdout instead of opening the viewer. Exits with 1 if no line passes.
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Filter added to the configured filters. Prefix with ! to exclude matching lines. Can be given several times.
+    #[arg(long = "filter", value_name = "PATTERN")]
+    pub filter_args: Vec<String>,
+
+    /// Exclude filter added to the configured filters. Can be given several times.
+    #[arg(long = "exclude", value_name = "PATTERN")]
+    pub exclude_args: Vec<String>,
+
+    /// Hide lines below the level: trace, debug, info, warn or error. Lines without a level are shown.
+    #[arg(long, value_name = "LEVEL")]
+    pub level: Option<LogLevel>,
+
+    /// Search for the pattern once the lines are loaded and go to the first match
+    #[arg(long, value_name = "PATTERN")]
+    pub search: Option<String>,
+
+    /// Go to line N once the lines are loaded
+    #[arg(long, value_name = "N")]
+    pub goto_line: Option<NonZeroUsize>,
+
+    /// Start at the last line, and keep following new lines of stdin or a command in pager mode
+    #[arg(long)]
+    pub follow: bool,
+
+    /// Only print the lines of the named event in headless mode. Can be given several times.
+    #[arg(long = "event", value_name = "NAME", requires = "headless")]
+    pub headless_events: Vec<String>,
+
+    /// Color the lines printed in headless mode with the styles of their events and highlights
+    #[arg(long, requires = "headless")]
+    pub color: bool,
+
+    /// Behave like a pager when reading stdin: start at the first line instead of following new lines
+    #[arg(long)]
+    pub pager: bool,
+
+    /// Print the input and exit if it fits on one screen. Implies --pager.
+    #[arg(short = 'F', long)]
+    pub quit_if_one_screen: bool,
+
+    /// Commands given as `+` arguments or startup flags, run once the lines are loaded
+    #[arg(skip)]
+    pub startup_commands: Vec<StartupCommand>,
+
+    /// Lines already read from stdin, shown before the lines read by the viewer
+    #[arg(skip)]
+    pub stdin_lines: Vec<String>,
+
+    /// Open the named session, or create it from the given files. The session is saved on quit. Not available
+    /// when streaming.
+    #[arg(long, value_name = "NAME")]
+    pub session: Option<String>,
+
     /// Disable persistence
     #[arg(long)]
     pub no_persist: bool,
 
+    /// Maximum number of lines to keep when streaming from stdin. The oldest lines are dropped, with a tenth of
+    /// the maximum more at once so they aren't dropped on every new line.
+    #[arg(long, value_name = "N")]
+    pub max_lines: Option<usize>,
+
     /// Skip timestamp parsing. Multi-file logs will not be sorted chronologically.
     #[arg(long)]
     pub no_timestamps: bool,
 
+    /// Print the timestamps parsed from the first lines of the files, or of stdin, then exit
+    #[arg(long)]
+    pub test_timestamps: bool,
+
+    /// Remove ANSI escape sequences instead of showing their colors
+    #[arg(long)]
+    pub strip_ansi: bool,
+
     /// Enable debug logging to file. Use RUST_LOG= to set log level
     #[arg(long, value_name = "FILE")]
     pub debug: Option<String>,
+
+    /// Command to run and follow the output of, e.g. `lazylog -- journalctl -f`
+    #[arg(last = true, value_name = "COMMAND")]
+    pub command: Vec<String>,
 }
 
 impl Cli {
     pub fn should_use_stdin(&self) -> bool {
-        self.files.is_empty() && !std::io::stdin().is_terminal()
+        self.files.is_empty() && self.command.is_empty() && !std::io::stdin().is_terminal()
+    }
+
+    pub fn should_run_command(&self) -> bool {
+        !self.command.is_empty()
+    }
+
+    /// Moves the `+` startup commands given among the files to [`Cli::startup_commands`], followed by the
+    /// commands of the startup flags. Startup commands turn on pager mode, so a stream is read before they run.
+    pub fn take_startup_commands(&mut self) {
+        let (files, mut commands) = split_startup_commands(std::mem::take(&mut self.files));
+        self.files = files;
+        commands.extend(self.goto_line.map(|line| StartupCommand::GotoLine(line.get())));
+        commands.extend(self.search.clone().map(StartupCommand::Search));
+        self.pager |= !commands.is_empty();
+
+        // Streams are followed instead
+        if self.follow && !self.files.is_empty() {
+            commands.insert(0, StartupCommand::GotoEnd);
+        }
+        self.startup_commands = commands;
+    }
+
+    /// Returns true if stdin is shown like in a pager, starting at the top once it has been read.
+    pub fn is_pager(&self) -> bool {
+        self.pager || self.quit_if_one_screen
+    }
+
+    /// Returns the filters given with `--filter` and `--exclude`, and the issues of the ignored arguments.
+    pub fn parse_filter_args(&self) -> (Vec<FilterPattern>, Vec<String>) {
+        let mut patterns = Vec::new();
+        let mut issues = Vec::new();
+        for arg in &self.filter_args {
+            match parse_filter_arg(arg) {
+                Some(pattern) => patterns.push(pattern),
+                None => issues.push(format!("Filter '{}': pattern is empty", arg)),
+            }
+        }
+        for arg in &self.exclude_args {
+            if arg.is_empty() {
+                issues.push("Exclude filter: pattern is empty".to_string());
+            } else {
+                patterns.push(FilterPattern::new(arg.clone(), ActiveFilterMode::Exclude, false, true));
+            }
+        }
+        (patterns, issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        let mut cli = Cli::try_parse_from(std::iter::once("lazylog").chain(args.iter().copied())).unwrap();
+        cli.take_startup_commands();
+        cli
+    }
+
+    #[test]
+    fn test_startup_commands() {
+        let cli = parse(&["--search", "timeout", "+G", "app.log", "--goto-line", "20"]);
+        assert_eq!(cli.files, vec!["app.log"]);
+        assert!(cli.is_pager());
+        assert_eq!(
+            cli.startup_commands,
+            vec![
+                StartupCommand::GotoEnd,
+                StartupCommand::GotoLine(20),
+                StartupCommand::Search("timeout".to_string())
+            ]
+        );
+
+        let cli = parse(&["--follow", "app.log"]);
+        assert!(!cli.is_pager());
+        assert_eq!(cli.startup_commands, vec![StartupCommand::GotoEnd]);
+        let cli = parse(&["--goto-line", "20"]);
+        assert!(cli.is_pager());
+        let cli = parse(&["--search", "timeout", "--", "make"]);
+        assert!(cli.is_pager());
+        assert!(Cli::try_parse_from(["lazylog", "--goto-line", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_args() {
+        let cli = parse(&[
+            "--filter",
+            "api",
+            "--filter",
+            "!health",
+            "--exclude",
+            "DEBUG",
+            "--level",
+            "warn",
+        ]);
+        let (patterns, issues) = cli.parse_filter_args();
+        let modes: Vec<_> = patterns.iter().map(|p| (p.pattern.as_str(), p.mode)).collect();
+        assert_eq!(
+            modes,
+            vec![
+                ("api", ActiveFilterMode::Include),
+                ("health", ActiveFilterMode::Exclude),
+                ("DEBUG", ActiveFilterMode::Exclude)
+            ]
+        );
+        assert!(issues.is_empty());
+        assert_eq!(cli.level, Some(LogLevel::Warn));
     }
 }
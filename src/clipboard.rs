@@ -0,0 +1,224 @@
+use std::io::Write;
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use serde::Deserialize;
+
+/// Lines to copy, chosen before picking a format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopySource {
+    /// The lines of the visual selection.
+    #[default]
+    Selection,
+    /// All lines in the filtered view.
+    View,
+    /// All marked lines.
+    Marks,
+}
+
+/// Text format of copied lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    Plain,
+    Numbered,
+    Markdown,
+    Json,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 4] = [
+        CopyFormat::Plain,
+        CopyFormat::Numbered,
+        CopyFormat::Markdown,
+        CopyFormat::Json,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CopyFormat::Plain => "Plain text",
+            CopyFormat::Numbered => "With line numbers",
+            CopyFormat::Markdown => "Markdown code block",
+            CopyFormat::Json => "JSON array",
+        }
+    }
+
+    /// Formats lines given as (line number, content).
+    pub fn format(&self, lines: &[(usize, String)]) -> String {
+        let contents = lines.iter().map(|(_, content)| content.as_str());
+        match self {
+            CopyFormat::Plain => contents.collect::<Vec<_>>().join("\n"),
+            CopyFormat::Numbered => {
+                let width = lines
+                    .iter()
+                    .map(|(number, _)| number.to_string().len())
+                    .max()
+                    .unwrap_or(0);
+                lines
+                    .iter()
+                    .map(|(number, content)| format!("{:>width$}  {}", number, content))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            CopyFormat::Markdown => {
+                // The fence must be longer than any backtick run in the lines
+                let longest_run = lines
+                    .iter()
+                    .flat_map(|(_, content)| content.split(|c| c != '`').map(str::len))
+                    .max()
+                    .unwrap_or(0);
+                let fence = "`".repeat(longest_run.max(2) + 1);
+                format!("{}\n{}\n{}", fence, contents.collect::<Vec<_>>().join("\n"), fence)
+            }
+            CopyFormat::Json => serde_json::to_string_pretty(&contents.collect::<Vec<_>>()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Clipboard used for copying.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardBackend {
+    /// The system clipboard, or the terminal clipboard if there is no system clipboard.
+    #[default]
+    Auto,
+    /// Only the system clipboard.
+    System,
+    /// Only the terminal clipboard, set with OSC 52 escape sequences, e.g. when the system clipboard
+    /// is on another machine than the terminal.
+    Osc52,
+}
+
+/// Largest text that is sent to the terminal clipboard. Terminals ignore or cut longer sequences.
+const OSC52_MAX_BYTES: usize = 1024 * 1024;
+
+/// Size of the pieces an escape sequence is split into for GNU screen, which limits the length of
+/// the sequences it passes through.
+const SCREEN_CHUNK_BYTES: usize = 768;
+
+/// Terminal multiplexer the escape sequence must pass through to reach the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    fn detect() -> Option<Self> {
+        if std::env::var_os("TMUX").is_some() {
+            Some(Multiplexer::Tmux)
+        } else if std::env::var_os("STY").is_some() {
+            Some(Multiplexer::Screen)
+        } else {
+            None
+        }
+    }
+}
+
+/// Copies text to the clipboard of the given backend. The terminal clipboard is set by writing to `terminal`, which
+/// must be the stream the terminal is drawn to.
+pub fn copy(text: &str, backend: ClipboardBackend, terminal: &mut impl Write) -> Result<(), String> {
+    let error = match backend {
+        ClipboardBackend::Osc52 => return copy_osc52(text, terminal),
+        ClipboardBackend::System | ClipboardBackend::Auto => match arboard::Clipboard::new() {
+            Ok(mut clipboard) => match clipboard.set_text(text) {
+                Ok(()) => return Ok(()),
+                Err(e) => format!("Failed to copy to clipboard: {}", e),
+            },
+            Err(e) => format!("Failed to access clipboard: {}", e),
+        },
+    };
+    if backend == ClipboardBackend::System {
+        return Err(error);
+    }
+    // Without a system clipboard, e.g. over SSH, ask the terminal to set its clipboard
+    copy_osc52(text, terminal).map_err(|osc52_error| format!("{}\n{}", error, osc52_error))
+}
+
+fn copy_osc52(text: &str, terminal: &mut impl Write) -> Result<(), String> {
+    if text.len() > OSC52_MAX_BYTES {
+        return Err(format!(
+            "Text is too large for the terminal clipboard ({} KB, at most {} KB)",
+            text.len() / 1024,
+            OSC52_MAX_BYTES / 1024
+        ));
+    }
+    terminal
+        .write_all(osc52_sequence(text, Multiplexer::detect()).as_bytes())
+        .and_then(|_| terminal.flush())
+        .map_err(|e| format!("Failed to write to the terminal clipboard: {}", e))
+}
+
+/// Returns the OSC 52 escape sequence setting the clipboard to the text, wrapped to pass through
+/// a terminal multiplexer.
+fn osc52_sequence(text: &str, multiplexer: Option<Multiplexer>) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", BASE64_STANDARD.encode(text));
+    match multiplexer {
+        None => sequence,
+        // tmux passes on DCS sequences with escapes doubled, if `allow-passthrough` is on
+        Some(Multiplexer::Tmux) => format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b")),
+        // The sequence is ASCII, so it can be split at any byte
+        Some(Multiplexer::Screen) => sequence
+            .as_bytes()
+            .chunks(SCREEN_CHUNK_BYTES)
+            .map(|chunk| format!("\x1bP{}\x1b\\", String::from_utf8_lossy(chunk)))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[(usize, &str)]) -> Vec<(usize, String)> {
+        lines
+            .iter()
+            .map(|(number, content)| (*number, content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_copy_formats() {
+        let lines = lines(&[(9, "start"), (10, "say \"hi\"")]);
+
+        assert_eq!(CopyFormat::Plain.format(&lines), "start\nsay \"hi\"");
+        assert_eq!(CopyFormat::Numbered.format(&lines), " 9  start\n10  say \"hi\"");
+        assert_eq!(CopyFormat::Markdown.format(&lines), "```\nstart\nsay \"hi\"\n```");
+        assert_eq!(
+            CopyFormat::Json.format(&lines),
+            "[\n  \"start\",\n  \"say \\\"hi\\\"\"\n]"
+        );
+    }
+
+    #[test]
+    fn test_markdown_fence_longer_than_backticks() {
+        let lines = lines(&[(1, "```rust")]);
+        assert_eq!(CopyFormat::Markdown.format(&lines), "````\n```rust\n````");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hello", None), "\x1b]52;c;aGVsbG8=\x07");
+        assert_eq!(
+            osc52_sequence("hello", Some(Multiplexer::Tmux)),
+            "\x1bPtmux;\x1b\x1b]52;c;aGVsbG8=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_osc52_sequence_chunked_for_screen() {
+        let text = "x".repeat(1000);
+        let sequence = osc52_sequence(&text, Some(Multiplexer::Screen));
+
+        let chunks: Vec<&str> = sequence.split("\x1b\\").filter(|chunk| !chunk.is_empty()).collect();
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|chunk| chunk.starts_with("\x1bP")));
+        let unwrapped: String = chunks.iter().map(|chunk| &chunk[2..]).collect();
+        assert_eq!(unwrapped, osc52_sequence(&text, None));
+    }
+
+    #[test]
+    fn test_osc52_copy_writes_to_terminal() {
+        let mut terminal = Vec::new();
+        copy("hello", ClipboardBackend::Osc52, &mut terminal).unwrap();
+        assert_eq!(terminal, osc52_sequence("hello", Multiplexer::detect()).into_bytes());
+    }
+}
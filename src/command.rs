@@ -1,4 +1,9 @@
 use crate::app::App;
+use crate::clipboard::CopySource;
+use crate::filter::ActiveFilterMode;
+use crate::level::LogLevel;
+use crate::quick_filter::QuickFilterKind;
+use crate::registers::RegisterAction;
 use color_eyre::Result;
 
 /// Represents actions that can be performed in the application.
@@ -9,6 +14,10 @@ pub enum Command {
     MoveDown,
     PageUp,
     PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    ScrollUp,
+    ScrollDown,
     GotoTop,
     GotoBottom,
     CenterSelected,
@@ -17,74 +26,167 @@ pub enum Command {
     ScrollLeftSmall,
     ScrollRightSmall,
     ResetHorizontal,
+    ScrollToLineEnd,
     HistoryBack,
+    ActivateJumpHistoryView,
+    ActivateRestartsView,
+    ActivateFullLineView,
+    ActivateHexView,
+    ActivateFormatView,
+    ActivateProfilesView,
     HistoryForward,
 
     // Application Control
     Quit,
+    Suspend,
     ToggleHelp,
+    ToggleDebugOverlay,
     ClearLogBuffer,
     Cancel,
     Confirm,
+    CopyMessage,
 
     // Search
     ActivateActiveSearchMode,
     SearchNext,
     SearchPrevious,
+    SearchWordUnderCursor,
     ToggleCaseSearch,
+    ToggleSearchScope,
+    ToggleFuzzySearch,
     SearchHistoryPrevious,
     SearchHistoryNext,
+    ActivateHistoryView,
     TabCompletion,
 
     // Filter
     ActivateActiveFilterMode,
     ActivateFilterView,
+    FilterWordUnderCursor,
+    ActivateQuickFilterView,
+    QuickFilterLevel,
+    QuickFilterField,
+    QuickFilterTemplate,
+    QuickFilterFile,
+    ToggleQuickFilterMode,
     ActivateEditActiveFilterMode,
     ToggleFilterPattern,
     RemoveFilterPattern,
+    DisableStaleFilters,
+    RemoveStaleFilters,
+    ToggleEventsPreview,
     ToggleAllFilterPatterns,
     ToggleFilterPatternCaseSensitive,
+    ToggleFilterPatternWholeWord,
+    CycleFilterPatternFile,
+    ToggleWholeWord,
     ToggleFilterPatternMode,
     ToggleCaseFilter,
     ToggleActiveFilterModeInOut,
     FilterHistoryPrevious,
     FilterHistoryNext,
+    CopyFiltersToClipboard,
+    ActivateExportFiltersMode,
+    WriteFiltersFile,
+    ActivateExportReportMode,
+    FilterToEvent,
+    SearchToFilter,
+    FilterToSearch,
 
     // Goto Line
     ActivateGotoLineMode,
+    ActivateGotoTimeMode,
 
     // Display Options
     ActivateOptionsView,
     ToggleOption,
 
+    // Log levels
+    ActivateLevelsView,
+
+    // Columns
+    ActivateColumnsView,
+    ToggleColumnView,
+    ToggleColumn,
+    CycleColumnSort,
+
+    // Query
+    ActivateQueryMode,
+    MarkQueryRow,
+
+    // Patterns
+    ActivatePatternsView,
+    PatternToIncludeFilter,
+    PatternToExcludeFilter,
+    ActivateNoiseView,
+    ExcludeNoiseSuggestion,
+
     // Events
     ActivateEventsView,
     ActivateEventFilterView,
+    ActivateListFilter,
+    NextEventsTab,
+    PreviousEventsTab,
     ActivateAddCustomEventMode,
     RemoveCustomEvent,
+    SaveCustomEventToConfig,
     GotoSelectedEvent,
     ToggleEventFilter,
     ToggleAllEventFilters,
     SoloEventFilter,
+    EventToFilter,
     ToggleEventsShowMarks,
+    ToggleEventTag,
+    ToggleAllEventTags,
+    MarkTaggedEvents,
     EventNext,
     EventPrevious,
+    ErrorEventNext,
+    WarningEventNext,
+    InfoEventNext,
+    DebugEventNext,
+    ActivateEventScopeView,
+    MinimapNext,
+    MinimapPrevious,
+    ActivateTimelineView,
+    GotoSelectedTimelineBucket,
 
     // Marks
     ToggleMark,
     ActivateMarksView,
     GotoSelectedMark,
+    ToggleMarkSelection,
+    ToggleAllMarkSelections,
+    ToggleMarksGrouping,
     ActivateMarkNameMode,
     UnmarkSelected,
     ClearAllMarks,
     MarkNext,
     MarkPrevious,
     ToggleShowMarkedOnly,
+    MeasureInterval,
+    ActivateSetRegister,
+    ActivateJumpToRegister,
+    RemoveRegister,
+    MarkWordUnderCursor,
+    HideLines,
+    UnhideAllLines,
 
     // Files
     ActivateFilesView,
     ToggleFile,
     ActivateAddFileMode,
+    ReloadFile,
+
+    // Actions
+    ActivateActionsView,
+    ActivateScriptsView,
+    ActivateLineInspector,
+    CopyLineDetail,
+
+    // Sessions
+    ActivateSaveSessionMode,
+    ActivateSessionsView,
 
     // Expansion
     ToggleExpansion,
@@ -93,21 +195,60 @@ pub enum Command {
     // Streaming
     ToggleFollowMode,
     TogglePauseMode,
+    ToggleFreezeDisplay,
     ToggleCenterCursorMode,
     ActivateSaveToFileMode,
+    ToggleWatches,
+    ActivateAlertsView,
+    ToggleMuteNotifications,
+    ActivateCorrelateMode,
+    CorrelationToFilter,
+
+    // Split view
+    SplitView,
+    PinSplitView,
+    SwitchSplitFocus,
+    CloseSplit,
 
     // Selection
     StartSelection,
+    StartBlockSelection,
+    SelectionColumnLeft,
+    SelectionColumnRight,
     CopySelection,
+    CopySelectionAs,
+    CopyViewAs,
+    CopyMarkedLinesAs,
     SelectToEventNext,
     SelectToEventPrevious,
     SelectToMarkNext,
     SelectToMarkPrevious,
 
+    // Shell
+    ActivatePipeCommandMode,
+    OpenInEditor,
+    RestartCommand,
+
+    // Notes
+    ActivateNotesView,
+    GotoNoteReference,
+
+    // Regex tester
+    ActivateRegexTester,
+    ToggleRegexTesterCase,
+    RegexTesterAddFilter,
+    RegexTesterAddEvent,
+    RegexTesterAddHighlight,
+    RegexTesterAddWatch,
+
     // Log line context capture navigation
     ContextNext,
     ContextPrevious,
     ContextFilter,
+
+    // Same template navigation
+    SimilarNext,
+    SimilarPrevious,
 }
 
 impl Command {
@@ -119,6 +260,10 @@ impl Command {
             Command::MoveDown => "Move down",
             Command::PageUp => "Page up",
             Command::PageDown => "Page down",
+            Command::HalfPageUp => "Half page up",
+            Command::HalfPageDown => "Half page down",
+            Command::ScrollUp => "Scroll view up",
+            Command::ScrollDown => "Scroll view down",
             Command::GotoTop => "Go to start",
             Command::GotoBottom => "Go to end",
             Command::CenterSelected => "Center selected line",
@@ -127,74 +272,167 @@ impl Command {
             Command::ScrollLeftSmall => "Scroll left (small)",
             Command::ScrollRightSmall => "Scroll right (small)",
             Command::ResetHorizontal => "Reset horizontal scroll",
+            Command::ScrollToLineEnd => "Scroll to end of selected line",
             Command::HistoryBack => "Go back in history",
+            Command::ActivateJumpHistoryView => "Show navigation history",
+            Command::ActivateRestartsView => "Show application restarts",
+            Command::ActivateFullLineView => "Show the selected line in full",
+            Command::ActivateHexView => "Show the bytes of the selected line in hex",
+            Command::ActivateFormatView => "Choose the log format",
+            Command::ActivateProfilesView => "Switch the profile of the config",
             Command::HistoryForward => "Go forward in history",
 
             // Application Control
             Command::Quit => "Quit",
+            Command::Suspend => "Suspend to the shell (resume with fg)",
             Command::ToggleHelp => "Toggle help",
+            Command::ToggleDebugOverlay => "Toggle debug overlay (with --debug)",
             Command::ClearLogBuffer => "Clear buffer (stdin)",
             Command::Cancel => "Cancel/Exit mode",
             Command::Confirm => "Confirm",
+            Command::CopyMessage => "Copy message to clipboard",
 
             // Search
             Command::ActivateActiveSearchMode => "Start search",
             Command::SearchNext => "Next match",
             Command::SearchPrevious => "Previous match",
+            Command::SearchWordUnderCursor => "Search for word under cursor",
             Command::ToggleCaseSearch => "Toggle case sensitivity",
+            Command::ToggleSearchScope => "Toggle searching filtered lines / all lines",
+            Command::ToggleFuzzySearch => "Toggle fuzzy matching",
             Command::SearchHistoryPrevious => "Previous search from history",
             Command::SearchHistoryNext => "Next search from history",
+            Command::ActivateHistoryView => "Pick a query from the history",
             Command::TabCompletion => "Tab completion",
 
             // Filter
             Command::ActivateActiveFilterMode => "Start filter",
             Command::ActivateFilterView => "View filter list",
+            Command::FilterWordUnderCursor => "Filter on word under cursor",
+            Command::ActivateQuickFilterView => "Filter by the level, module, template or file of the line",
+            Command::QuickFilterLevel => "Filter by level",
+            Command::QuickFilterField => "Filter by module or tag field",
+            Command::QuickFilterTemplate => "Filter by template",
+            Command::QuickFilterFile => "Filter by file",
+            Command::ToggleQuickFilterMode => "Switch between showing only and hiding",
             Command::ActivateEditActiveFilterMode => "Edit selected filter",
             Command::ToggleFilterPattern => "Toggle filter on/off",
             Command::RemoveFilterPattern => "Remove selected filter",
+            Command::DisableStaleFilters => "Disable the filters matching no line",
+            Command::RemoveStaleFilters => "Delete the filters matching no line",
+            Command::ToggleEventsPreview => "Toggle preview of the lines around the event",
             Command::ToggleAllFilterPatterns => "Toggle all filters",
             Command::ToggleFilterPatternCaseSensitive => "Toggle case sensitive",
+            Command::ToggleFilterPatternWholeWord => "Toggle whole word matching",
+            Command::CycleFilterPatternFile => "Cycle file the filter applies to",
+            Command::ToggleWholeWord => "Toggle whole word matching",
             Command::ToggleFilterPatternMode => "Toggle include/exclude",
             Command::ToggleCaseFilter => "Toggle case sensitivity",
             Command::ToggleActiveFilterModeInOut => "Toggle include/exclude",
             Command::FilterHistoryPrevious => "Previous filter from history",
             Command::FilterHistoryNext => "Next filter from history",
+            Command::CopyFiltersToClipboard => "Copy filters as TOML",
+            Command::ActivateExportFiltersMode => "Export filters to TOML file",
+            Command::WriteFiltersFile => "Write filters back to the --filters file",
+            Command::ActivateExportReportMode => "Export listed events and marks to CSV/JSON file",
+            Command::FilterToEvent => "Add filter as custom event",
+            Command::SearchToFilter => "Add search as include filter",
+            Command::FilterToSearch => "Search for filter pattern",
 
             // Goto Line
             Command::ActivateGotoLineMode => "Go to line",
+            Command::ActivateGotoTimeMode => "Go to time",
 
             // Display Options
             Command::ActivateOptionsView => "Display options",
             Command::ToggleOption => "Toggle option on/off",
 
+            // Log levels
+            Command::ActivateLevelsView => "Filter by log level",
+
+            // Columns
+            Command::ActivateColumnsView => "Configure columns",
+            Command::ToggleColumnView => "Toggle column view",
+            Command::ToggleColumn => "Show/hide column",
+            Command::CycleColumnSort => "Sort by column (ascending/descending/off)",
+
+            // Query
+            Command::ActivateQueryMode => "Query fields of visible lines",
+            Command::MarkQueryRow => "Mark source lines of result",
+
+            // Patterns
+            Command::ActivatePatternsView => "Show message patterns",
+            Command::PatternToIncludeFilter => "Add include filter for pattern",
+            Command::PatternToExcludeFilter => "Add exclude filter for pattern",
+            Command::ActivateNoiseView => "Analyze noise and suggest exclude filters",
+            Command::ExcludeNoiseSuggestion => "Exclude pattern (or 1-9)",
+
             // Events
             Command::ActivateEventsView => "View log events",
             Command::ActivateEventFilterView => "Filter events",
+            Command::ActivateListFilter => "Filter the list by text",
+            Command::NextEventsTab => "Next event type tab",
+            Command::PreviousEventsTab => "Previous event type tab",
             Command::ActivateAddCustomEventMode => "Add custom event",
             Command::RemoveCustomEvent => "Remove custom event",
+            Command::SaveCustomEventToConfig => "Save custom event to config",
             Command::GotoSelectedEvent => "Go to selected event",
             Command::ToggleEventFilter => "Toggle event filter",
             Command::ToggleAllEventFilters => "Toggle all event filters",
             Command::SoloEventFilter => "Solo event filter",
+            Command::EventToFilter => "Add event as include filter",
             Command::ToggleEventsShowMarks => "Toggle showing marks in events view",
+            Command::ToggleEventTag => "Tag event",
+            Command::ToggleAllEventTags => "Tag all events",
+            Command::MarkTaggedEvents => "Create marks from tagged events",
             Command::EventNext => "Go to next event",
             Command::EventPrevious => "Go to previous event",
+            Command::ErrorEventNext => "Go to next error severity event",
+            Command::WarningEventNext => "Go to next warning severity event",
+            Command::InfoEventNext => "Go to next info severity event",
+            Command::DebugEventNext => "Go to next debug severity event",
+            Command::ActivateEventScopeView => "Choose event type to jump between",
+            Command::MinimapNext => "Go to next minimap tick",
+            Command::MinimapPrevious => "Go to previous minimap tick",
+            Command::ActivateTimelineView => "View event timeline",
+            Command::GotoSelectedTimelineBucket => "Go to first event in time slot",
 
             // Marks
             Command::ToggleMark => "Toggle mark on line",
             Command::ActivateMarksView => "View marked lines",
             Command::GotoSelectedMark => "Go to selected mark",
-            Command::ActivateMarkNameMode => "Name the mark",
-            Command::UnmarkSelected => "Remove selected mark",
+            Command::ToggleMarkSelection => "Select mark",
+            Command::ToggleAllMarkSelections => "Select all marks",
+            Command::ToggleMarksGrouping => "Group marks by name",
+            Command::ActivateMarkNameMode => "Name the mark or the selected marks",
+            Command::UnmarkSelected => "Remove the mark or the selected marks",
             Command::ClearAllMarks => "Clear all marks",
             Command::MarkNext => "Go to next mark",
             Command::MarkPrevious => "Go to previous mark",
             Command::ToggleShowMarkedOnly => "Show marked lines only on/off",
+            Command::MeasureInterval => "Measure time, lines and events between lines",
+            Command::ActivateSetRegister => "Save position in register",
+            Command::ActivateJumpToRegister => "Jump to position in register",
+            Command::RemoveRegister => "Remove selected register",
+            Command::MarkWordUnderCursor => "Mark all lines containing word under cursor",
+            Command::HideLines => "Hide line or selected lines",
+            Command::UnhideAllLines => "Unhide all hidden lines",
 
             // Files
             Command::ActivateFilesView => "View files list",
             Command::ToggleFile => "Toggle file visibility",
             Command::ActivateAddFileMode => "Add a file",
+            Command::ReloadFile => "Reload file from disk",
+
+            // Actions
+            Command::ActivateActionsView => "Run an action on the line",
+            Command::ActivateScriptsView => "Run a script over the lines",
+            Command::ActivateLineInspector => "Inspect the selected line",
+            Command::CopyLineDetail => "Copy the selected value",
+
+            // Sessions
+            Command::ActivateSaveSessionMode => "Save session",
+            Command::ActivateSessionsView => "Open a saved session",
 
             // Expansion
             Command::ToggleExpansion => "Expand/collapse hidden lines",
@@ -202,22 +440,61 @@ impl Command {
 
             // Streaming
             Command::ToggleFollowMode => "Toggle follow mode (stdin)",
-            Command::TogglePauseMode => "Toggle pause mode (stdin)",
+            Command::TogglePauseMode => "Toggle pause mode, dropping new lines (stdin)",
+            Command::ToggleFreezeDisplay => "Toggle frozen display, keeping new lines (stdin)",
             Command::ToggleCenterCursorMode => "Toggle center cursor mode",
             Command::ActivateSaveToFileMode => "Save to file (stdin)",
+            Command::ToggleWatches => "Toggle watch expression sparklines",
+            Command::ActivateAlertsView => "Show fired alerts",
+            Command::ToggleMuteNotifications => "Mute bells and desktop notifications of events and alerts",
+            Command::ActivateCorrelateMode => "Show lines sharing an id with line",
+            Command::CorrelationToFilter => "Filter on correlated id",
+
+            // Split view
+            Command::SplitView => "Split view horizontally",
+            Command::PinSplitView => "Pin the current position in a pane to the right to compare with",
+            Command::SwitchSplitFocus => "Switch focus between split panes",
+            Command::CloseSplit => "Close split view",
 
             // Selection
             Command::StartSelection => "Start visual selection",
+            Command::StartBlockSelection => "Start visual block selection",
+            Command::SelectionColumnLeft => "Move block selection column left",
+            Command::SelectionColumnRight => "Move block selection column right",
             Command::CopySelection => "Copy selection to clipboard",
+            Command::CopySelectionAs => "Copy selection in a chosen format",
+            Command::CopyViewAs => "Copy all lines in view",
+            Command::CopyMarkedLinesAs => "Copy all marked lines",
             Command::SelectToEventNext => "Select to next event",
             Command::SelectToEventPrevious => "Select to previous event",
             Command::SelectToMarkNext => "Select to next mark",
             Command::SelectToMarkPrevious => "Select to previous mark",
 
+            // Shell
+            Command::ActivatePipeCommandMode => "Pipe lines through shell command",
+            Command::OpenInEditor => "Open line in $EDITOR",
+            Command::RestartCommand => "Restart command (lazylog -- cmd)",
+
+            // Notes
+            Command::ActivateNotesView => "Open session notes",
+            Command::GotoNoteReference => "Go to mark referenced by @name",
+
+            // Regex tester
+            Command::ActivateRegexTester => "Open regex tester",
+            Command::ToggleRegexTesterCase => "Toggle case sensitivity",
+            Command::RegexTesterAddFilter => "Add regex as filter",
+            Command::RegexTesterAddEvent => "Add regex as event",
+            Command::RegexTesterAddHighlight => "Add regex as highlight",
+            Command::RegexTesterAddWatch => "Add regex as watch expression",
+
             // Context capture navigation
             Command::ContextNext => "Go to next line with same capture",
             Command::ContextPrevious => "Go to previous line with same capture",
             Command::ContextFilter => "Add capture value as filter",
+
+            // Same template navigation
+            Command::SimilarNext => "Go to next line with same template",
+            Command::SimilarPrevious => "Go to previous line with same template",
         }
     }
 
@@ -226,6 +503,7 @@ impl Command {
         if app.help.is_visible() {
             match self {
                 Command::Quit => app.quit(),
+                Command::Suspend => app.suspend(),
                 Command::ToggleHelp | Command::Cancel => app.help.toggle_visibility(),
                 Command::MoveUp => app.help.move_up(),
                 Command::MoveDown => app.help.move_down(),
@@ -240,6 +518,10 @@ impl Command {
             Command::MoveDown => app.move_down(),
             Command::PageUp => app.page_up(),
             Command::PageDown => app.page_down(),
+            Command::HalfPageUp => app.half_page_up(),
+            Command::HalfPageDown => app.half_page_down(),
+            Command::ScrollUp => app.scroll_up(),
+            Command::ScrollDown => app.scroll_down(),
             Command::GotoTop => app.goto_top(),
             Command::GotoBottom => app.goto_bottom(),
             Command::CenterSelected => app.viewport.center_selected(),
@@ -248,74 +530,167 @@ impl Command {
             Command::ScrollLeftSmall => app.viewport.scroll_left_small(),
             Command::ScrollRightSmall => app.scroll_right(true),
             Command::ResetHorizontal => app.viewport.reset_horizontal(),
+            Command::ScrollToLineEnd => app.scroll_to_line_end(),
             Command::HistoryBack => app.history_back(),
+            Command::ActivateJumpHistoryView => app.activate_jump_history_view(),
+            Command::ActivateRestartsView => app.activate_restarts_view(),
+            Command::ActivateFullLineView => app.activate_full_line_view(),
+            Command::ActivateHexView => app.activate_hex_view(),
+            Command::ActivateFormatView => app.activate_format_view(),
+            Command::ActivateProfilesView => app.activate_profiles_view(),
             Command::HistoryForward => app.history_forward(),
 
             // Application Control
             Command::Quit => app.quit(),
+            Command::Suspend => app.suspend(),
             Command::ToggleHelp => app.toggle_help(),
+            Command::ToggleDebugOverlay => app.toggle_debug_overlay(),
             Command::ClearLogBuffer => app.clear_log_buffer(),
             Command::Cancel => app.cancel(),
             Command::Confirm => app.confirm(),
+            Command::CopyMessage => app.copy_message_to_clipboard(),
 
             // Search
             Command::ActivateActiveSearchMode => app.activate_search_mode(),
             Command::SearchNext => app.search_next(),
             Command::SearchPrevious => app.search_previous(),
+            Command::SearchWordUnderCursor => app.search_word_under_cursor(),
             Command::ToggleCaseSearch => app.toggle_case_sensitive(),
+            Command::ToggleSearchScope => app.toggle_search_scope(),
+            Command::ToggleFuzzySearch => app.toggle_fuzzy_search(),
             Command::SearchHistoryPrevious => app.search_history_previous(),
             Command::SearchHistoryNext => app.search_history_next(),
+            Command::ActivateHistoryView => app.activate_history_view(),
             Command::TabCompletion => app.apply_tab_completion(),
 
             // Filter
             Command::ActivateActiveFilterMode => app.activate_filter_mode(),
             Command::ActivateFilterView => app.activate_filter_list_view(),
+            Command::FilterWordUnderCursor => app.filter_word_under_cursor(),
+            Command::ActivateQuickFilterView => app.activate_quick_filter_view(),
+            Command::QuickFilterLevel => app.apply_quick_filter(QuickFilterKind::Level),
+            Command::QuickFilterField => app.apply_quick_filter(QuickFilterKind::Field),
+            Command::QuickFilterTemplate => app.apply_quick_filter(QuickFilterKind::Template),
+            Command::QuickFilterFile => app.apply_quick_filter(QuickFilterKind::File),
+            Command::ToggleQuickFilterMode => app.toggle_quick_filter_mode(),
             Command::ActivateEditActiveFilterMode => app.activate_edit_filter_mode(),
             Command::ToggleFilterPattern => app.toggle_filter_pattern_active(),
             Command::RemoveFilterPattern => app.remove_filter_pattern(),
+            Command::DisableStaleFilters => app.disable_stale_filters(),
+            Command::RemoveStaleFilters => app.remove_stale_filters(),
+            Command::ToggleEventsPreview => app.toggle_events_preview(),
             Command::ToggleAllFilterPatterns => app.toggle_all_filter_patterns(),
             Command::ToggleFilterPatternCaseSensitive => app.toggle_filter_pattern_case_sensitive(),
+            Command::ToggleFilterPatternWholeWord => app.toggle_filter_pattern_whole_word(),
+            Command::CycleFilterPatternFile => app.cycle_filter_pattern_file(),
+            Command::ToggleWholeWord => app.toggle_whole_word(),
             Command::ToggleFilterPatternMode => app.toggle_filter_pattern_mode(),
             Command::ToggleCaseFilter => app.toggle_case_sensitive(),
             Command::ToggleActiveFilterModeInOut => app.filter.toggle_mode(),
             Command::FilterHistoryPrevious => app.filter_history_previous(),
             Command::FilterHistoryNext => app.filter_history_next(),
+            Command::CopyFiltersToClipboard => app.copy_filters_to_clipboard(),
+            Command::ActivateExportFiltersMode => app.activate_export_filters_mode(),
+            Command::WriteFiltersFile => app.write_filters_file(),
+            Command::ActivateExportReportMode => app.activate_export_report_mode(),
+            Command::FilterToEvent => app.filter_to_event(),
+            Command::SearchToFilter => app.search_to_filter(),
+            Command::FilterToSearch => app.filter_to_search(),
 
             // Goto Line
             Command::ActivateGotoLineMode => app.activate_goto_line_mode(),
+            Command::ActivateGotoTimeMode => app.activate_goto_time_mode(),
 
             // Display Options
             Command::ActivateOptionsView => app.activate_options_view(),
             Command::ToggleOption => app.toggle_option(),
 
+            // Log levels
+            Command::ActivateLevelsView => app.activate_levels_view(),
+
+            // Columns
+            Command::ActivateColumnsView => app.activate_columns_view(),
+            Command::ToggleColumnView => app.toggle_column_view(),
+            Command::ToggleColumn => app.toggle_column(),
+            Command::CycleColumnSort => app.cycle_column_sort(),
+
+            // Query
+            Command::ActivateQueryMode => app.activate_query_mode(),
+            Command::MarkQueryRow => app.mark_query_row(),
+
+            // Patterns
+            Command::ActivatePatternsView => app.activate_patterns_view(),
+            Command::PatternToIncludeFilter => app.pattern_to_filter(ActiveFilterMode::Include),
+            Command::PatternToExcludeFilter => app.pattern_to_filter(ActiveFilterMode::Exclude),
+            Command::ActivateNoiseView => app.activate_noise_view(),
+            Command::ExcludeNoiseSuggestion => app.exclude_noise_suggestion(app.noise_list_state.selected_index()),
+
             // Events
             Command::ActivateEventsView => app.activate_events_view(),
             Command::ActivateEventFilterView => app.activate_event_filter_view(),
+            Command::ActivateListFilter => app.activate_list_filter(),
+            Command::NextEventsTab => app.cycle_events_tab(true),
+            Command::PreviousEventsTab => app.cycle_events_tab(false),
             Command::ActivateAddCustomEventMode => app.activate_add_custom_event_mode(),
             Command::RemoveCustomEvent => app.remove_custom_event(),
+            Command::SaveCustomEventToConfig => app.save_custom_event_to_config(),
             Command::GotoSelectedEvent => app.goto_selected_event(false),
             Command::ToggleEventFilter => app.toggle_event_filter(),
             Command::ToggleAllEventFilters => app.toggle_all_event_filters(),
             Command::SoloEventFilter => app.solo_event_filter(),
+            Command::EventToFilter => app.event_to_filter(),
             Command::ToggleEventsShowMarks => app.toggle_events_show_marks(),
+            Command::ToggleEventTag => app.toggle_event_tag(),
+            Command::ToggleAllEventTags => app.toggle_all_event_tags(),
+            Command::MarkTaggedEvents => app.mark_tagged_events(),
             Command::EventNext => app.event_next(),
             Command::EventPrevious => app.event_previous(),
+            Command::ErrorEventNext => app.severity_event_next(LogLevel::Error),
+            Command::WarningEventNext => app.severity_event_next(LogLevel::Warn),
+            Command::InfoEventNext => app.severity_event_next(LogLevel::Info),
+            Command::DebugEventNext => app.severity_event_next(LogLevel::Debug),
+            Command::ActivateEventScopeView => app.activate_event_scope_view(),
+            Command::MinimapNext => app.minimap_next(),
+            Command::MinimapPrevious => app.minimap_previous(),
+            Command::ActivateTimelineView => app.activate_timeline_view(),
+            Command::GotoSelectedTimelineBucket => app.goto_selected_timeline_bucket(true),
 
             // Marks
             Command::ToggleMark => app.toggle_mark(),
             Command::ActivateMarksView => app.activate_marks_view(),
             Command::GotoSelectedMark => app.goto_selected_mark(false),
+            Command::ToggleMarkSelection => app.toggle_mark_selection(),
+            Command::ToggleAllMarkSelections => app.toggle_all_mark_selections(),
+            Command::ToggleMarksGrouping => app.toggle_marks_grouping(),
             Command::ActivateMarkNameMode => app.activate_mark_name_overlay(),
             Command::UnmarkSelected => app.unmark_selected(),
             Command::ClearAllMarks => app.clear_all_marks(),
             Command::MarkNext => app.mark_next(),
             Command::MarkPrevious => app.mark_previous(),
             Command::ToggleShowMarkedOnly => app.toggle_show_marked_only(),
+            Command::MeasureInterval => app.measure_interval(),
+            Command::ActivateSetRegister => app.activate_registers_view(RegisterAction::Set),
+            Command::ActivateJumpToRegister => app.activate_registers_view(RegisterAction::Jump),
+            Command::RemoveRegister => app.remove_selected_register(),
+            Command::MarkWordUnderCursor => app.mark_word_under_cursor(),
+            Command::HideLines => app.hide_lines(),
+            Command::UnhideAllLines => app.unhide_all_lines(),
 
             // Files
             Command::ActivateFilesView => app.activate_files_view(),
             Command::ToggleFile => app.toggle_file(),
             Command::ActivateAddFileMode => app.activate_add_file_overlay(),
+            Command::ReloadFile => app.reload_file(),
+
+            // Actions
+            Command::ActivateActionsView => app.activate_actions_view(),
+            Command::ActivateScriptsView => app.activate_scripts_view(),
+            Command::ActivateLineInspector => app.activate_line_inspector(),
+            Command::CopyLineDetail => app.copy_selected_line_detail(),
+
+            // Sessions
+            Command::ActivateSaveSessionMode => app.activate_save_session_mode(),
+            Command::ActivateSessionsView => app.activate_sessions_view(),
 
             // Expansion
             Command::ToggleExpansion => app.toggle_expansion(),
@@ -324,21 +699,60 @@ impl Command {
             // Streaming
             Command::ToggleFollowMode => app.toggle_follow_mode(),
             Command::TogglePauseMode => app.toggle_pause_mode(),
+            Command::ToggleFreezeDisplay => app.toggle_freeze_display(),
             Command::ToggleCenterCursorMode => app.toggle_center_cursor_mode(),
             Command::ActivateSaveToFileMode => app.activate_save_to_file_mode(),
+            Command::ToggleWatches => app.toggle_watches(),
+            Command::ActivateAlertsView => app.activate_alerts_view(),
+            Command::ToggleMuteNotifications => app.toggle_mute_notifications(),
+            Command::ActivateCorrelateMode => app.activate_correlate_mode(),
+            Command::CorrelationToFilter => app.correlation_to_filter(),
+
+            // Split view
+            Command::SplitView => app.split_view(),
+            Command::PinSplitView => app.pin_split_view(),
+            Command::SwitchSplitFocus => app.switch_split_focus(),
+            Command::CloseSplit => app.close_split(),
 
             // Selection
             Command::StartSelection => app.start_selection(),
+            Command::StartBlockSelection => app.start_block_selection(),
+            Command::SelectionColumnLeft => app.move_selection_column(false),
+            Command::SelectionColumnRight => app.move_selection_column(true),
             Command::CopySelection => app.copy_selection_to_clipboard(),
+            Command::CopySelectionAs => app.activate_copy_format_view(CopySource::Selection),
+            Command::CopyViewAs => app.activate_copy_format_view(CopySource::View),
+            Command::CopyMarkedLinesAs => app.activate_copy_format_view(CopySource::Marks),
             Command::SelectToEventNext => app.select_to_event_next(),
             Command::SelectToEventPrevious => app.select_to_event_previous(),
             Command::SelectToMarkNext => app.select_to_mark_next(),
             Command::SelectToMarkPrevious => app.select_to_mark_previous(),
 
+            // Shell
+            Command::ActivatePipeCommandMode => app.activate_pipe_command_mode(),
+            Command::OpenInEditor => app.open_in_editor(),
+            Command::RestartCommand => app.restart_child_process(),
+
+            // Notes
+            Command::ActivateNotesView => app.activate_notes_view(),
+            Command::GotoNoteReference => app.goto_note_reference(),
+
+            // Regex tester
+            Command::ActivateRegexTester => app.activate_regex_tester(),
+            Command::ToggleRegexTesterCase => app.toggle_regex_tester_case_sensitive(),
+            Command::RegexTesterAddFilter => app.regex_tester_add_filter(),
+            Command::RegexTesterAddEvent => app.regex_tester_add_event(),
+            Command::RegexTesterAddHighlight => app.regex_tester_add_highlight(),
+            Command::RegexTesterAddWatch => app.regex_tester_add_watch(),
+
             // Context capture navigation
             Command::ContextNext => app.context_next(),
             Command::ContextPrevious => app.context_previous(),
             Command::ContextFilter => app.filter_on_context(),
+
+            // Same template navigation
+            Command::SimilarNext => app.similar_next(),
+            Command::SimilarPrevious => app.similar_previous(),
         }
         Ok(())
     }
@@ -1,84 +1,233 @@
 use std::collections::HashSet;
 
+use crate::fuzzy::{fuzzy_contains, fuzzy_match};
 use crate::log::LogLine;
 
+/// Maximum number of candidates offered for cycling.
+const MAX_CANDIDATES: usize = 100;
+
 /// Manages tab completion.
 #[derive(Debug)]
 pub struct CompletionEngine {
     words: HashSet<String>,
+    /// Previous search and filter patterns, most relevant first.
+    history: Vec<String>,
+    /// Candidates being cycled through with repeated Tab.
+    cycle: Option<CompletionCycle>,
+}
+
+/// State of cycling through completion candidates.
+#[derive(Debug)]
+struct CompletionCycle {
+    candidates: Vec<String>,
+    /// Index of the candidate in the input, or `None` if only the common prefix was completed.
+    index: Option<usize>,
+    /// Input after the last completion. Cycling stops when the input is edited.
+    current: String,
 }
 
 impl CompletionEngine {
     pub fn new() -> Self {
-        Self { words: HashSet::new() }
+        Self {
+            words: HashSet::new(),
+            history: Vec::new(),
+            cycle: None,
+        }
     }
 
     /// Extracts all unique words from the provided log lines.
     ///
     /// Words are split on whitespace.
     pub fn update<'a>(&mut self, lines: impl Iterator<Item = &'a LogLine>) {
-        let log_line_content = lines.map(|log_line| log_line.content());
+        for line in lines {
+            self.insert_words(line.content());
+        }
+    }
 
-        for line in log_line_content {
-            for word in line.split_whitespace() {
+    /// Appends words from a single log line.
+    pub fn append_line(&mut self, log_line: &LogLine) {
+        self.insert_words(log_line.content());
+    }
+
+    /// Inserts the words of a line, and the values of `key=value` and JSON `"key": "value"` fields.
+    fn insert_words(&mut self, line: &str) {
+        for word in line.split_whitespace() {
+            if !self.words.contains(word) {
                 self.words.insert(word.to_string());
             }
+
+            let trimmed = trim_value(word);
+            if !trimmed.is_empty() && trimmed != word && !self.words.contains(trimmed) {
+                self.words.insert(trimmed.to_string());
+            }
+
+            if let Some(value) = field_value(word)
+                && !self.words.contains(value)
+            {
+                self.words.insert(value.to_string());
+            }
         }
     }
 
-    /// Appends words from a single log line.
-    pub fn append_line(&mut self, log_line: &LogLine) {
-        for word in log_line.content().split_whitespace() {
-            self.words.insert(word.to_string());
+    /// Sets the history entries offered as completions, most relevant first.
+    pub fn set_history<'a>(&mut self, entries: impl Iterator<Item = &'a str>) {
+        self.history.clear();
+        for entry in entries {
+            if !self.history.iter().any(|e| e == entry) {
+                self.history.push(entry.to_string());
+            }
         }
+        self.cycle = None;
     }
 
-    /// Finds the longest common prefix completion for the given prefix.
+    /// Returns the completions of the input: history entries matching the input, followed by words
+    /// and field values matching the last word of the input.
+    ///
+    /// Candidates match fuzzily, with the characters of the input in order, and are ranked by their
+    /// fuzzy score so prefix and word start matches come first. They match case-sensitively, so a
+    /// completion never changes the case of what was typed and the case folding setting doesn't apply.
+    pub fn candidates(&self, input: &str) -> Vec<String> {
+        let mut candidates: Vec<String> = rank_by_score(self.history.iter().filter(|entry| *entry != input), input)
+            .into_iter()
+            .cloned()
+            .collect();
+
+        let word_start = input.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let (head, last_word) = input.split_at(word_start);
+        if !last_word.is_empty() {
+            let mut words: Vec<&String> = self.words.iter().filter(|word| *word != last_word).collect();
+            words.sort();
+
+            for word in rank_by_score(words.into_iter(), last_word) {
+                let candidate = format!("{}{}", head, word);
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+
+        candidates.truncate(MAX_CANDIDATES);
+        candidates
+    }
+
+    /// Finds the longest common prefix completion of the candidates starting with the given prefix.
     pub fn find_completion(&self, prefix: &str) -> Option<String> {
         if prefix.is_empty() {
             return None;
         }
 
-        let mut matches: Vec<&String> = self.words.iter().filter(|word| word.starts_with(prefix)).collect();
+        let candidates = self.candidates(prefix);
+        if !candidates.iter().any(|candidate| candidate.starts_with(prefix)) {
+            return None;
+        }
+        let common = common_prefix_completion(&candidates, prefix);
+        Some(common[prefix.len()..].to_string())
+    }
 
-        matches.sort();
+    /// Completes the input and returns the new input.
+    ///
+    /// The first Tab completes the common prefix of the candidates starting with the input. Repeated
+    /// Tabs without editing the input cycle through all candidates, best match first.
+    pub fn complete(&mut self, input: &str) -> Option<String> {
+        if let Some(cycle) = &mut self.cycle
+            && cycle.current == input
+        {
+            let next = cycle.index.map_or(0, |i| (i + 1) % cycle.candidates.len());
+            cycle.index = Some(next);
+            cycle.current = cycle.candidates[next].clone();
+            return Some(cycle.current.clone());
+        }
+        self.cycle = None;
 
-        match matches.len() {
+        let candidates = self.candidates(input);
+        match candidates.len() {
             0 => None,
-            1 => {
-                let word = matches[0];
-                (word.len() > prefix.len()).then(|| word[prefix.len()..].to_string())
-            }
+            1 => candidates.into_iter().next(),
             _ => {
-                let common = self.find_common_prefix(&matches);
-                Some(common[prefix.len()..].to_string())
+                let common = common_prefix_completion(&candidates, input);
+                let (current, index) = if common.len() > input.len() {
+                    (common, None)
+                } else {
+                    (candidates[0].clone(), Some(0))
+                };
+                self.cycle = Some(CompletionCycle {
+                    candidates,
+                    index,
+                    current: current.clone(),
+                });
+                Some(current)
             }
         }
     }
 
-    fn find_common_prefix(&self, words: &[&String]) -> String {
-        if words.is_empty() {
-            return String::new();
-        }
+    /// Returns a short hint about the candidates being cycled, if the input is still a completion.
+    pub fn hint(&self, input: &str) -> Option<String> {
+        let cycle = self.cycle.as_ref().filter(|cycle| cycle.current == input)?;
+        let count = cycle.candidates.len();
+        let next = cycle.index.map_or(0, |i| (i + 1) % count);
+        let position = cycle.index.map_or("-".to_string(), |i| (i + 1).to_string());
+        Some(format!("{}/{} Tab: {}", position, count, cycle.candidates[next]))
+    }
 
-        let first = words[0];
-        let mut prefix_len = first.chars().count();
+    /// Stops cycling through candidates.
+    pub fn reset_cycle(&mut self) {
+        self.cycle = None;
+    }
+}
 
-        for word in words.iter().skip(1) {
-            prefix_len = first
-                .chars()
-                .zip(word.chars())
-                .take(prefix_len)
-                .take_while(|(c1, c2)| c1 == c2)
-                .count();
+/// Strips quotes and trailing punctuation from a word, e.g. `"info",` becomes `info`.
+fn trim_value(word: &str) -> &str {
+    word.trim_matches(|c: char| matches!(c, '"' | '\'' | ',' | ';' | '{' | '}' | '[' | ']'))
+}
 
-            if prefix_len == 0 {
-                return String::new();
-            }
-        }
+/// Returns the value of a `key=value` or JSON `"key":"value"` word.
+fn field_value(word: &str) -> Option<&str> {
+    let (_, value) = word.split_once('=').or_else(|| word.split_once("\":"))?;
+    let value = trim_value(value);
+    (!value.is_empty()).then_some(value)
+}
+
+/// Returns the items fuzzily matching the pattern, best score first. Items with the same score keep
+/// their order.
+fn rank_by_score<'a>(items: impl Iterator<Item = &'a String>, pattern: &str) -> Vec<&'a String> {
+    let mut scored: Vec<(i64, &String)> = items
+        .filter(|item| fuzzy_contains(item, pattern, true))
+        .filter_map(|item| Some((fuzzy_match(item, pattern, true)?.score, item)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Returns the longest common prefix of the candidates starting with the input.
+fn common_prefix_completion(candidates: &[String], input: &str) -> String {
+    let completions: Vec<String> = candidates
+        .iter()
+        .filter(|candidate| candidate.starts_with(input))
+        .cloned()
+        .collect();
+    find_common_prefix(&completions)
+}
+
+fn find_common_prefix(words: &[String]) -> String {
+    let Some(first) = words.first() else {
+        return String::new();
+    };
+    let mut prefix_len = first.chars().count();
 
-        first.chars().take(prefix_len).collect()
+    for word in words.iter().skip(1) {
+        prefix_len = first
+            .chars()
+            .zip(word.chars())
+            .take(prefix_len)
+            .take_while(|(c1, c2)| c1 == c2)
+            .count();
+
+        if prefix_len == 0 {
+            return String::new();
+        }
     }
+
+    first.chars().take(prefix_len).collect()
 }
 
 impl Default for CompletionEngine {
@@ -126,4 +275,76 @@ mod tests {
         // No match for lowercase
         assert_eq!(engine.find_completion("err"), Some("or".to_string()));
     }
+
+    #[test]
+    fn test_field_values_are_completed() {
+        let mut engine = CompletionEngine::new();
+        let line1 = LogLine::new("request user=alice status=200", 0);
+        let line2 = LogLine::new(r#"{"level": "warning", "service":"billing"}"#, 1);
+        engine.update([&line1, &line2].into_iter());
+
+        assert_eq!(engine.find_completion("ali"), Some("ce".to_string()));
+        assert_eq!(engine.find_completion("user=a"), Some("lice".to_string()));
+        assert_eq!(engine.find_completion("warn"), Some("ing".to_string()));
+        assert_eq!(engine.find_completion("bil"), Some("ling".to_string()));
+    }
+
+    #[test]
+    fn test_completes_last_word_and_history() {
+        let mut engine = CompletionEngine::new();
+        let line = LogLine::new("connection timeout", 0);
+        engine.update([&line].into_iter());
+        engine.set_history(["connection refused", "timeout"].into_iter());
+
+        assert_eq!(
+            engine.candidates("conn"),
+            vec!["connection refused".to_string(), "connection".to_string()]
+        );
+        assert_eq!(
+            engine.candidates("failed with time"),
+            vec!["failed with timeout".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_repeated_tab_cycles_candidates() {
+        let mut engine = CompletionEngine::new();
+        let line = LogLine::new("Processing Program Profile", 0);
+        engine.update([&line].into_iter());
+
+        // Common prefix "Pro" is already typed, so the first Tab selects the first candidate
+        let first = engine.complete("Pro").unwrap();
+        assert_eq!(first, "Processing");
+        assert_eq!(engine.hint(&first), Some("1/3 Tab: Profile".to_string()));
+
+        let second = engine.complete(&first).unwrap();
+        assert_eq!(second, "Profile");
+        let third = engine.complete(&second).unwrap();
+        assert_eq!(third, "Program");
+        assert_eq!(engine.complete(&third), Some("Processing".to_string()));
+
+        // Editing the input stops cycling
+        assert_eq!(engine.hint("Pr"), None);
+        assert_eq!(engine.complete("Prog"), Some("Program".to_string()));
+    }
+
+    #[test]
+    fn test_fuzzy_candidates_ranked_by_score() {
+        let mut engine = CompletionEngine::new();
+        let line = LogLine::new("request_timeout retransmit user_request_id reqtimeout", 0);
+        engine.update([&line].into_iter());
+
+        assert_eq!(
+            engine.candidates("rqt"),
+            vec![
+                "reqtimeout".to_string(),
+                "request_timeout".to_string(),
+                "user_request_id".to_string()
+            ]
+        );
+        assert_eq!(engine.candidates("reqid"), vec!["user_request_id".to_string()]);
+        // Fuzzy matches are cycled, but only completions of the input extend it as a common prefix
+        assert_eq!(engine.find_completion("rqt"), None);
+        assert_eq!(engine.complete("reqid"), Some("user_request_id".to_string()));
+    }
 }
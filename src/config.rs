@@ -1,11 +1,26 @@
+use crate::action::{ActionKind, LineAction};
+use crate::alert::{AlertRule, AlertThreshold};
+use crate::ansi::AnsiMode;
+use crate::clipboard::ClipboardBackend;
+use crate::event_pairs::EventPairPattern;
+use crate::fields::{FieldExtractor, FieldFormat};
 use crate::filter::{ActiveFilterMode, FilterPattern};
-use crate::highlighter::{HighlightPattern, PatternStyle};
-use crate::log_event::EventPattern;
+use crate::format::{FormatChoice, LogFormat};
+use crate::highlighter::{HighlightPattern, PatternStyle, capture_group_index};
+use crate::level::{LevelDetector, LogLevel};
+use crate::log_event::{CustomEvent, EventPattern};
 use crate::matcher::{PatternMatchType, PatternMatcher, PlainMatch};
+use crate::script::Script;
+use crate::theme::{THEME_NAMES, Theme};
+use crate::timestamp::{TimestampFormat, TimestampParser};
 use crate::ui::colors::EVENT_NAME_CUSTOM_DEFAULT_FG;
+use crate::utils::CaseFolding;
+use crate::watch::WatchExpression;
+use chrono::TimeDelta;
 use ratatui::style::Color;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use tracing::debug;
 
@@ -18,6 +33,9 @@ pub struct Config {
     /// Event patterns for coloring and tracking.
     #[serde(default)]
     pub events: Vec<EventConfig>,
+    /// Pairs of events, like the start and end of a request, whose durations are measured.
+    #[serde(default)]
+    pub event_pairs: Vec<EventPairConfig>,
     /// Predefined filters.
     #[serde(default)]
     pub filters: Vec<FilterConfig>,
@@ -25,7 +43,107 @@ pub struct Config {
     pub default_event_bg_color_index: Option<u8>,
     pub default_custom_event_bg_color_index: Option<u8>,
     pub context_capture: Option<ContextCaptureConfig>,
+    /// Regex matching the first line of a section, shown in the sticky header while scrolling through the section.
+    pub section_pattern: Option<String>,
+    /// Regex matching the first line of a run of the application, e.g. "Starting application". The matching
+    /// lines are marked with a separator and listed in the restarts view.
+    pub restart_pattern: Option<String>,
     pub disable_timestamp_parsing: Option<bool>,
+    /// Log format preset: `auto` to detect it from the first lines, `none`, or a preset name like `nginx`.
+    /// `levels` and `fields` take precedence over the preset. Defaults to `auto`, or to `none` if `levels` or
+    /// `fields` are configured, so a detected format doesn't change the parsing of an existing config.
+    pub format: Option<String>,
+    /// Whether case-insensitive matching folds the case of non-ASCII letters too. Defaults to true.
+    pub unicode_case_folding: Option<bool>,
+    /// How ANSI escape sequences in log lines are handled.
+    pub ansi: Option<AnsiMode>,
+    /// Maximum number of lines to keep when streaming from stdin.
+    pub max_lines: Option<usize>,
+    /// Maximum number of search and filter queries kept in the global history.
+    pub max_history: Option<usize>,
+    /// Number of lines of the previous page kept on screen when paging up or down.
+    pub page_overlap: Option<usize>,
+    /// Minimum interval in milliseconds between redraws while lines are streamed in.
+    pub tick_rate_ms: Option<u64>,
+    /// Number of lines shown before and after the selected event in the preview of the events view.
+    pub event_preview_lines: Option<usize>,
+    /// Number of bytes of a line shown in the log view. Longer lines, e.g. a base64 blob, are cut off.
+    pub max_line_length: Option<usize>,
+    /// Clipboard used for copying.
+    pub clipboard: Option<ClipboardBackend>,
+    /// Numeric values to extract from streamed lines and plot.
+    #[serde(default)]
+    pub watches: Vec<WatchConfig>,
+    /// Patterns raising an alert when they match streamed lines.
+    #[serde(default)]
+    pub alerts: Vec<AlertConfig>,
+    /// Patterns overriding the default log level detection.
+    pub levels: Option<LevelsConfig>,
+    /// Color theme of the UI.
+    pub theme: Option<ThemeConfig>,
+    /// Extraction of fields shown in the column view.
+    pub fields: Option<FieldsConfig>,
+    /// Custom timestamp formats, tried before those of the log format and the common formats.
+    pub timestamps: Option<TimestampsConfig>,
+    /// Commands to run on, or text to copy from, the selected line.
+    #[serde(default)]
+    pub actions: Vec<ActionConfig>,
+    /// Scripts analysing the log lines and sending annotations back.
+    #[serde(default)]
+    pub scripts: Vec<ScriptConfig>,
+    /// Named profiles, e.g. `[profile.k8s]`, whose highlights, events and filters are used on top of the shared
+    /// ones when the profile is selected.
+    #[serde(default)]
+    pub profile: BTreeMap<String, ProfileConfig>,
+}
+
+/// Highlights, events and filters for one type of log, like Kubernetes pods or an embedded device.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct ProfileConfig {
+    #[serde(default)]
+    pub highlights: Vec<HighlightConfig>,
+    #[serde(default)]
+    pub events: Vec<EventConfig>,
+    #[serde(default)]
+    pub filters: Vec<FilterConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FieldsConfig {
+    /// How fields are extracted.
+    pub format: FieldFormat,
+    /// Regex with named capture groups, required for the regex format.
+    pub pattern: Option<String>,
+    /// Fields shown as columns, in order. Optional for the regex format, where all named groups are used.
+    #[serde(default)]
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct TimestampsConfig {
+    /// Formats tried in order on each line.
+    #[serde(default)]
+    pub formats: Vec<TimestampFormatConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TimestampFormatConfig {
+    /// strftime-style format of the timestamp, e.g. `%d.%m.%Y %H:%M:%S`.
+    pub format: String,
+    /// Regex finding the timestamp, in its first capture group or as the whole match.
+    pub pattern: Option<String>,
+    /// Column the timestamp starts at, counted from 1 like the column in the footer. Used when no pattern is given,
+    /// defaults to the start of the line.
+    pub column: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct ThemeConfig {
+    /// Name of the built-in theme to start from.
+    pub name: Option<String>,
+    /// Colors overriding single UI elements of the theme, e.g. `footer_bg = "#303030"`.
+    #[serde(flatten)]
+    pub overrides: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -34,27 +152,128 @@ pub struct ContextCaptureConfig {
     pub pattern: String,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct EventPairConfig {
+    /// Name of the pair, e.g. `request`.
+    pub name: String,
+    /// Name of the event starting a pair.
+    pub start: String,
+    /// Name of the event ending a pair.
+    pub end: String,
+    /// Regex with a capture group correlating a start with its end, like `request_id=(\w+)`. Without it, an end is
+    /// paired with the oldest unfinished start.
+    pub key: Option<String>,
+    /// Pairs taking longer than this many milliseconds are flagged as slow.
+    pub threshold_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WatchConfig {
+    /// Name shown next to the plotted values.
+    pub name: String,
+    /// Regex with a capture group for the numeric value.
+    pub pattern: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AlertConfig {
+    /// Name shown in the alert notification and the alerts list.
+    pub name: String,
+    /// Regex the streamed lines are matched against.
+    pub pattern: String,
+    /// Only fire when more than this many lines match within `window_secs`.
+    pub threshold: Option<usize>,
+    /// Length of the threshold window in seconds.
+    pub window_secs: Option<u64>,
+    /// Ring the terminal bell when the alert fires.
+    #[serde(default)]
+    pub bell: bool,
+    /// Send a desktop notification when the alert fires.
+    #[serde(default)]
+    pub notify: bool,
+}
+
+impl AlertConfig {
+    /// Returns the threshold if both the count and the window are given.
+    fn threshold(&self) -> Option<AlertThreshold> {
+        Some(AlertThreshold {
+            count: self.threshold?,
+            window: std::time::Duration::from_secs(self.window_secs?),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ActionConfig {
+    /// Name shown in the actions popup.
+    pub name: String,
+    /// Regex the line must match, whose capture groups can be used in the template.
+    pub pattern: Option<String>,
+    /// Shell command to run.
+    pub command: Option<String>,
+    /// Text to copy to the clipboard.
+    pub copy: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScriptConfig {
+    /// Name shown in the scripts popup.
+    pub name: String,
+    /// Shell command starting the script.
+    pub command: String,
+    /// Run the script for the whole stream, sending it each new line.
+    #[serde(default)]
+    pub stream: bool,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct LevelsConfig {
+    /// Regex detecting TRACE lines.
+    pub trace: Option<String>,
+    /// Regex detecting DEBUG lines.
+    pub debug: Option<String>,
+    /// Regex detecting INFO lines.
+    pub info: Option<String>,
+    /// Regex detecting WARN lines.
+    pub warn: Option<String>,
+    /// Regex detecting ERROR lines.
+    pub error: Option<String>,
+}
+
+impl LevelsConfig {
+    /// Returns the patterns in the order of [`LogLevel::ALL`].
+    fn patterns(&self) -> [Option<&str>; 5] {
+        [
+            self.trace.as_deref(),
+            self.debug.as_deref(),
+            self.info.as_deref(),
+            self.warn.as_deref(),
+            self.error.as_deref(),
+        ]
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
 pub struct Filters {
     /// Predefined filters.
     #[serde(default)]
     pub filters: Vec<FilterConfig>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct StyleConfig {
     /// Foreground color.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fg: Option<String>,
     /// Background color.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bg: Option<String>,
     /// Bold text.
     #[serde(default)]
     pub bold: bool,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct HighlightConfig {
     /// Match pattern. Can be a substring or regex.
     pub pattern: String,
@@ -64,12 +283,18 @@ pub struct HighlightConfig {
     /// Whether the pattern matching is case-sensitive.
     #[serde(default)]
     pub case_sensitive: bool,
-    /// Style to use for highlighting. If None, a style will be generated.
+    /// Style to use for highlighting. If None, a style will be generated, unless groups are styled.
     #[serde(default)]
     pub style: Option<StyleConfig>,
+    /// Styles of capture groups of a regex pattern, by group number or name.
+    #[serde(default)]
+    pub groups: BTreeMap<String, StyleConfig>,
+    /// Name of the file whose lines are highlighted when multiple files are opened, e.g. `api.log`.
+    #[serde(default)]
+    pub file: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct EventConfig {
     /// Name of the event.
     pub name: String,
@@ -79,14 +304,48 @@ pub struct EventConfig {
     #[serde(default)]
     pub regex: bool,
     /// Style to use for the whole line. If None, default style is applied.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub style: Option<StyleConfig>,
     /// Whether this event should be highlighted as critical (shown in scrollbar with red marker).
     #[serde(default)]
     pub critical: bool,
+    /// Severity counted in the footer badges, e.g. `error` or `warn`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<LogLevel>,
+    /// Ring the terminal bell when the event occurs in a streamed line.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bell: bool,
+    /// Send a desktop notification when the event occurs in a streamed line.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub notify: bool,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl From<&CustomEvent<'_>> for EventConfig {
+    fn from(event: &CustomEvent) -> Self {
+        // Configured plain events are case-sensitive, so a case-insensitive one is exported as a regex
+        let (pattern, regex) = if event.regex || event.case_sensitive {
+            (event.pattern.to_string(), event.regex)
+        } else {
+            (format!("(?i){}", regex::escape(event.pattern)), true)
+        };
+        Self {
+            name: event.name.to_string(),
+            pattern,
+            regex,
+            style: event.color.map(|color| StyleConfig {
+                fg: None,
+                bg: Some(color.to_string()),
+                bold: false,
+            }),
+            critical: false,
+            severity: None,
+            bell: false,
+            notify: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct FilterConfig {
     /// Match pattern.
     pub pattern: String,
@@ -99,13 +358,124 @@ pub struct FilterConfig {
     /// Whether this filter is enabled by default.
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// Whether the pattern is a regex or a simple substring.
+    #[serde(default)]
+    pub regex: bool,
+    /// Whether only occurrences of the pattern that are whole words match.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub whole_word: bool,
+    /// Name of the file the filter applies to when multiple files are opened, e.g. `api.log`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+impl FilterConfig {
+    /// Converts to a FilterPattern. Returns None if the pattern is an invalid regex.
+    fn to_filter_pattern(&self) -> Option<FilterPattern> {
+        let mode = match self.mode.to_lowercase().as_str() {
+            "exclude" => ActiveFilterMode::Exclude,
+            _ => ActiveFilterMode::Include, // Default to Include
+        };
+
+        let filter = if self.regex {
+            FilterPattern::new_regex(self.pattern.clone(), mode, self.case_sensitive, self.enabled)?
+        } else {
+            FilterPattern::new(self.pattern.clone(), mode, self.case_sensitive, self.enabled)
+        };
+        Some(filter.with_whole_word(self.whole_word).with_file(self.file.clone()))
+    }
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Returns a description of each filter that is dropped when parsing.
+fn filter_config_issues(filters: &[FilterConfig]) -> Vec<String> {
+    filters
+        .iter()
+        .filter(|f| f.to_filter_pattern().is_none())
+        .map(|f| format!("Filter '{}': invalid regex", f.pattern))
+        .collect()
+}
+
+/// Returns a description of each color in the style that cannot be parsed.
+fn style_config_issues(style: &Option<StyleConfig>, entry: &str) -> Vec<String> {
+    let Some(style) = style else {
+        return Vec::new();
+    };
+    [("fg", &style.fg), ("bg", &style.bg)]
+        .into_iter()
+        .filter_map(|(name, color)| {
+            let color = color.as_ref()?;
+            Config::parse_color(color)
+                .is_none()
+                .then(|| format!("{}: unknown {} color '{}'", entry, name, color))
+        })
+        .collect()
+}
+
+impl From<&FilterPattern> for FilterConfig {
+    fn from(filter: &FilterPattern) -> Self {
+        let mode = match filter.mode {
+            ActiveFilterMode::Include => "include",
+            ActiveFilterMode::Exclude => "exclude",
+        };
+
+        Self {
+            pattern: filter.pattern.clone(),
+            mode: mode.to_string(),
+            case_sensitive: filter.case_sensitive,
+            enabled: filter.enabled,
+            regex: filter.is_regex(),
+            whole_word: filter.whole_word,
+            file: filter.file.clone(),
+        }
+    }
+}
+
+/// Filters and custom events set up at runtime, serializable as a TOML config snippet.
+#[derive(Debug, Serialize, Default)]
+pub struct ConfigSnippet {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<FilterConfig>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<EventConfig>,
+}
+
+impl ConfigSnippet {
+    /// Creates a snippet from filter patterns and custom events.
+    pub fn new(filters: &[FilterPattern], custom_events: &[CustomEvent]) -> Self {
+        Self {
+            filters: filters.iter().map(FilterConfig::from).collect(),
+            events: custom_events.iter().map(EventConfig::from).collect(),
+        }
+    }
+
+    /// Returns true if there is nothing to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty() && self.events.is_empty()
+    }
+
+    /// Serializes the snippet to TOML, in the same format as the config and filters files.
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string(self).map_err(|e| format!("Failed to serialize config snippet: {}", e))
+    }
+}
+
 impl Filters {
+    /// Creates a filters file from filter patterns, e.g. to write the filters set up at runtime back to it.
+    pub fn from_patterns(filters: &[FilterPattern]) -> Self {
+        Self {
+            filters: filters.iter().map(FilterConfig::from).collect(),
+        }
+    }
+
+    /// Serializes the filters to TOML, in the format of the filters file.
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string(self).map_err(|e| format!("Failed to serialize filters: {}", e))
+    }
+
     /// Load filters from a specified file path.
     pub fn load(path: &Option<String>) -> Option<Self> {
         path.as_ref().and_then(|p| {
@@ -125,21 +495,14 @@ impl Filters {
     pub fn parse_filter_patterns(&self) -> Vec<FilterPattern> {
         self.filters
             .iter()
-            .map(|filter_config| {
-                let mode = match filter_config.mode.to_lowercase().as_str() {
-                    "exclude" => ActiveFilterMode::Exclude,
-                    _ => ActiveFilterMode::Include,
-                };
-
-                FilterPattern {
-                    pattern: filter_config.pattern.clone(),
-                    mode,
-                    case_sensitive: filter_config.case_sensitive,
-                    enabled: filter_config.enabled,
-                }
-            })
+            .filter_map(FilterConfig::to_filter_pattern)
             .collect()
     }
+
+    /// Returns a description of each entry that is discarded when parsing.
+    pub fn validate(&self) -> Vec<String> {
+        filter_config_issues(&self.filters)
+    }
 }
 
 impl Config {
@@ -180,6 +543,31 @@ impl Config {
         }
     }
 
+    /// Returns the names of the profiles, in alphabetical order.
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profile.keys().cloned().collect()
+    }
+
+    /// Adds the highlights, events and filters of the named profile to the shared ones. Events of the profile are
+    /// evaluated after the shared events.
+    pub fn apply_profile(&mut self, name: &str) -> Result<(), String> {
+        let Some(profile) = self.profile.get(name).cloned() else {
+            return Err(if self.profile.is_empty() {
+                format!("Unknown profile '{}', the config has no profiles", name)
+            } else {
+                format!(
+                    "Unknown profile '{}', the config has: {}",
+                    name,
+                    self.profile_names().join(", ")
+                )
+            });
+        };
+        self.highlights.extend(profile.highlights);
+        self.events.extend(profile.events);
+        self.filters.extend(profile.filters);
+        Ok(())
+    }
+
     /// Get the path of the configuration file if it was loaded from a file.
     pub fn get_path(&self) -> Option<&String> {
         self.path.as_ref()
@@ -192,6 +580,44 @@ impl Config {
             .unwrap_or(EVENT_NAME_CUSTOM_DEFAULT_FG)
     }
 
+    /// Appends an event to the config file, creating it in the default config dir if no config was loaded.
+    /// Returns the path of the config file.
+    pub fn save_event(&self, event: EventConfig) -> Result<PathBuf, String> {
+        let path = match &self.path {
+            Some(path) => PathBuf::from(path),
+            None => dirs::config_dir()
+                .map(|dir| dir.join("lazylog").join("config.toml"))
+                .ok_or("No config directory found")?,
+        };
+
+        let content = if path.exists() {
+            std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        } else {
+            String::new()
+        };
+        let snippet = ConfigSnippet {
+            filters: Vec::new(),
+            events: vec![event],
+        };
+        let mut updated = content.trim_end().to_string();
+        if !updated.is_empty() {
+            updated.push_str("\n\n");
+        }
+        updated.push_str(&snippet.to_toml()?);
+
+        // Don't write a file that would no longer load, e.g. when events are given as an inline array
+        toml::from_str::<Config>(&updated)
+            .map_err(|e| format!("Can't add the event to {}: {}", path.display(), e.message()))?;
+
+        if let Some(dir) = path.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+
     fn default_config_dir() -> PathBuf {
         if let Some(config_dir) = dirs::config_dir() {
             let config_path = config_dir.join("lazylog").join("config.toml");
@@ -203,23 +629,202 @@ impl Config {
         PathBuf::from("config.toml")
     }
 
+    /// Returns a description of each entry that is discarded or only partially applied when parsing.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        for hl_config in &self.highlights {
+            let entry = format!("Highlight '{}'", hl_config.pattern);
+            if hl_config.regex && Regex::new(&hl_config.pattern).is_err() {
+                issues.push(format!("{}: invalid regex", entry));
+            }
+            issues.extend(style_config_issues(&hl_config.style, &entry));
+            if !hl_config.groups.is_empty() && !hl_config.regex {
+                issues.push(format!("{}: groups require a regex pattern", entry));
+            }
+            for (group, style_config) in &hl_config.groups {
+                let group_entry = format!("{} group '{}'", entry, group);
+                if hl_config.regex
+                    && let Ok(regex) = Regex::new(&hl_config.pattern)
+                    && capture_group_index(&regex, group).is_none()
+                {
+                    issues.push(format!("{}: no such capture group", group_entry));
+                }
+                issues.extend(style_config_issues(&Some(style_config.clone()), &group_entry));
+            }
+        }
+
+        for ev_config in &self.events {
+            let entry = format!("Event '{}'", ev_config.name);
+            if ev_config.regex && Regex::new(&ev_config.pattern).is_err() {
+                issues.push(format!("{}: invalid regex '{}'", entry, ev_config.pattern));
+            }
+            issues.extend(style_config_issues(&ev_config.style, &entry));
+        }
+
+        for pair_config in &self.event_pairs {
+            let entry = format!("Event pair '{}'", pair_config.name);
+            for event_name in [&pair_config.start, &pair_config.end] {
+                if !self.events.iter().any(|event| &event.name == event_name) {
+                    issues.push(format!("{}: unknown event '{}'", entry, event_name));
+                }
+            }
+            match pair_config.key.as_deref().map(Regex::new) {
+                Some(Ok(regex)) if regex.captures_len() < 2 => {
+                    issues.push(format!("{}: key has no capture group", entry));
+                }
+                Some(Err(_)) => issues.push(format!(
+                    "{}: invalid regex '{}'",
+                    entry,
+                    pair_config.key.as_deref().unwrap_or_default()
+                )),
+                _ => {}
+            }
+        }
+
+        issues.extend(filter_config_issues(&self.filters));
+
+        if let Some(context_capture) = &self.context_capture
+            && Regex::new(&context_capture.pattern).is_err()
+        {
+            issues.push(format!("Context capture '{}': invalid regex", context_capture.pattern));
+        }
+
+        if let Some(section_pattern) = &self.section_pattern
+            && Regex::new(section_pattern).is_err()
+        {
+            issues.push(format!("Section pattern '{}': invalid regex", section_pattern));
+        }
+
+        if let Some(restart_pattern) = &self.restart_pattern
+            && Regex::new(restart_pattern).is_err()
+        {
+            issues.push(format!("Restart pattern '{}': invalid regex", restart_pattern));
+        }
+
+        if let Some(Err(e)) = self.format.as_deref().map(str::parse::<FormatChoice>) {
+            issues.push(format!("Format: {}", e));
+        }
+
+        for timestamp in self.timestamps.iter().flat_map(|timestamps| &timestamps.formats) {
+            if !TimestampFormat::is_valid_format(&timestamp.format) {
+                issues.push(format!("Timestamp format '{}': invalid format", timestamp.format));
+            } else if timestamp.pattern.is_some() && timestamp.column.is_some() {
+                issues.push(format!(
+                    "Timestamp format '{}': pattern and column can't be given together",
+                    timestamp.format
+                ));
+            } else if timestamp.column == Some(0) {
+                issues.push(format!("Timestamp format '{}': column starts at 1", timestamp.format));
+            } else if let Some(pattern) = &timestamp.pattern
+                && Regex::new(pattern).is_err()
+            {
+                issues.push(format!(
+                    "Timestamp format '{}': invalid regex '{}'",
+                    timestamp.format, pattern
+                ));
+            }
+        }
+
+        if let Some(levels) = &self.levels {
+            for (level, pattern) in LogLevel::ALL.iter().zip(levels.patterns()) {
+                if let Some(pattern) = pattern
+                    && Regex::new(pattern).is_err()
+                {
+                    issues.push(format!("Level {}: invalid regex '{}'", level, pattern));
+                }
+            }
+        }
+
+        if let Some(theme) = &self.theme {
+            if let Some(name) = &theme.name
+                && Theme::by_name(name).is_none()
+            {
+                issues.push(format!(
+                    "Theme: unknown theme '{}', expected one of {}",
+                    name,
+                    THEME_NAMES.join(", ")
+                ));
+            }
+            let mut default_theme = Theme::default();
+            for (element, color) in &theme.overrides {
+                match Self::parse_color(color) {
+                    Some(color) if default_theme.set(element, color) => {}
+                    Some(_) => issues.push(format!("Theme: unknown element '{}'", element)),
+                    None => issues.push(format!("Theme: unknown {} color '{}'", element, color)),
+                }
+            }
+        }
+
+        if let Some(fields) = &self.fields {
+            match (fields.format, &fields.pattern) {
+                (FieldFormat::Regex, None) => issues.push("Fields: regex format needs a pattern".to_string()),
+                (FieldFormat::Regex, Some(pattern)) => match Regex::new(pattern) {
+                    Ok(regex) => {
+                        let group_names: Vec<&str> = regex.capture_names().flatten().collect();
+                        if group_names.is_empty() {
+                            issues.push("Fields: pattern has no named capture group".to_string());
+                        }
+                        for column in fields.columns.iter().filter(|c| !group_names.contains(&c.as_str())) {
+                            issues.push(format!("Fields: pattern has no group named '{}'", column));
+                        }
+                    }
+                    Err(_) => issues.push(format!("Fields: invalid regex '{}'", pattern)),
+                },
+                (_, _) if fields.columns.is_empty() => issues.push("Fields: no columns given".to_string()),
+                _ => {}
+            }
+        }
+
+        for watch_config in &self.watches {
+            match Regex::new(&watch_config.pattern) {
+                Ok(regex) if regex.captures_len() < 2 => {
+                    issues.push(format!("Watch '{}': pattern has no capture group", watch_config.name));
+                }
+                Ok(_) => {}
+                Err(_) => issues.push(format!(
+                    "Watch '{}': invalid regex '{}'",
+                    watch_config.name, watch_config.pattern
+                )),
+            }
+        }
+
+        for alert_config in &self.alerts {
+            let entry = format!("Alert '{}'", alert_config.name);
+            if Regex::new(&alert_config.pattern).is_err() {
+                issues.push(format!("{}: invalid regex '{}'", entry, alert_config.pattern));
+            }
+            if alert_config.threshold.is_some() != alert_config.window_secs.is_some() {
+                issues.push(format!("{}: threshold and window_secs must be given together", entry));
+            }
+        }
+
+        for action_config in &self.actions {
+            let entry = format!("Action '{}'", action_config.name);
+            if action_config.command.is_some() == action_config.copy.is_some() {
+                issues.push(format!("{}: needs either a command or a copy template", entry));
+            }
+            if let Some(pattern) = &action_config.pattern
+                && Regex::new(pattern).is_err()
+            {
+                issues.push(format!("{}: invalid regex '{}'", entry, pattern));
+            }
+        }
+
+        for script_config in &self.scripts {
+            if script_config.command.trim().is_empty() {
+                issues.push(format!("Script '{}': command is empty", script_config.name));
+            }
+        }
+
+        issues
+    }
+
     /// Parses filter configurations and returns a list of FilterPatterns.
     pub fn parse_filter_patterns(&self) -> Vec<FilterPattern> {
         self.filters
             .iter()
-            .map(|filter_config| {
-                let mode = match filter_config.mode.to_lowercase().as_str() {
-                    "exclude" => ActiveFilterMode::Exclude,
-                    _ => ActiveFilterMode::Include, // Default to Include
-                };
-
-                FilterPattern {
-                    pattern: filter_config.pattern.clone(),
-                    mode,
-                    case_sensitive: filter_config.case_sensitive,
-                    enabled: filter_config.enabled,
-                }
-            })
+            .filter_map(FilterConfig::to_filter_pattern)
             .collect()
     }
 
@@ -230,6 +835,9 @@ impl Config {
             .filter_map(|hl_config| {
                 let style = if let Some(style_config) = &hl_config.style {
                     Self::parse_style_config(style_config)
+                } else if !hl_config.groups.is_empty() {
+                    // Only the groups are styled
+                    PatternStyle::default()
                 } else {
                     PatternStyle {
                         fg_color: Some(Self::hash_to_color(&hl_config.pattern)),
@@ -244,7 +852,15 @@ impl Config {
                     PatternMatchType::Plain(hl_config.case_sensitive)
                 };
 
-                HighlightPattern::new(&hl_config.pattern, match_type, style)
+                let group_styles: Vec<(String, PatternStyle)> = hl_config
+                    .groups
+                    .iter()
+                    .map(|(group, style_config)| (group.clone(), Self::parse_style_config(style_config)))
+                    .collect();
+
+                HighlightPattern::new(&hl_config.pattern, match_type, style)?
+                    .with_group_styles(&group_styles)
+                    .map(|pattern| pattern.with_file(hl_config.file.clone()))
             })
             .collect()
     }
@@ -266,7 +882,8 @@ impl Config {
                                 bold: false,
                             }
                         } else {
-                            PatternStyle::default_colors()
+                            let theme = self.parse_theme();
+                            PatternStyle::new(Some(theme.event_fg), Some(theme.event_bg), false)
                         }
                     });
 
@@ -281,11 +898,167 @@ impl Config {
             .collect()
     }
 
+    /// Builds the configured theme. Unknown themes, elements and colors are ignored.
+    pub fn parse_theme(&self) -> Theme {
+        let Some(theme_config) = &self.theme else {
+            return Theme::default();
+        };
+        let mut theme = theme_config
+            .name
+            .as_deref()
+            .and_then(Theme::by_name)
+            .unwrap_or_default();
+        for (element, color) in &theme_config.overrides {
+            if let Some(color) = Self::parse_color(color) {
+                theme.set(element, color);
+            }
+        }
+        theme
+    }
+
     /// Parses the context capture regex, if configured.
     pub fn parse_context_capture(&self) -> Option<Regex> {
         self.context_capture.as_ref().and_then(|c| Regex::new(&c.pattern).ok())
     }
 
+    /// Parses the section pattern of the sticky header, if configured.
+    pub fn parse_section_pattern(&self) -> Option<Regex> {
+        self.section_pattern.as_ref().and_then(|p| Regex::new(p).ok())
+    }
+
+    /// Parses the pattern of the lines starting a run of the application, if configured.
+    pub fn parse_restart_pattern(&self) -> Option<Regex> {
+        self.restart_pattern.as_ref().and_then(|p| Regex::new(p).ok())
+    }
+
+    /// Parses how the log format is chosen. Without a known format, it's detected unless `levels` or `fields` are
+    /// configured.
+    pub fn parse_format_choice(&self) -> FormatChoice {
+        self.format.as_deref().and_then(|format| format.parse().ok()).unwrap_or(
+            if self.levels.is_none() && self.fields.is_none() {
+                FormatChoice::Auto
+            } else {
+                FormatChoice::None
+            },
+        )
+    }
+
+    /// Builds the timestamp parser from the configured formats, followed by the format of the log. Invalid formats are
+    /// skipped.
+    pub fn parse_timestamp_parser(&self, log_format: Option<LogFormat>) -> TimestampParser {
+        let configured = self
+            .timestamps
+            .iter()
+            .flat_map(|timestamps| &timestamps.formats)
+            .filter(|timestamp| TimestampFormat::is_valid_format(&timestamp.format))
+            .filter_map(|timestamp| match (&timestamp.pattern, timestamp.column) {
+                (Some(pattern), None) => TimestampFormat::new(pattern, &timestamp.format).ok(),
+                (None, column) if column != Some(0) => {
+                    Some(TimestampFormat::at_column(column.unwrap_or(1), &timestamp.format))
+                }
+                _ => None,
+            });
+        let formats = configured
+            .chain(log_format.map(|format| format.timestamp_format()))
+            .collect();
+        TimestampParser::new(formats)
+    }
+
+    /// Builds the log level detector from the configured patterns. Invalid patterns are replaced by
+    /// the default pattern of their level.
+    pub fn parse_level_detector(&self) -> LevelDetector {
+        let Some(levels) = &self.levels else {
+            return LevelDetector::default();
+        };
+        let patterns = levels
+            .patterns()
+            .map(|pattern| pattern.filter(|p| Regex::new(p).is_ok()));
+        LevelDetector::new(patterns).unwrap_or_default()
+    }
+
+    /// Returns how case-insensitive matching folds the case of characters, of all letters by default.
+    pub fn case_folding(&self) -> CaseFolding {
+        CaseFolding::new(self.unicode_case_folding.unwrap_or(true))
+    }
+
+    /// Parses the field extractor of the column view, if configured and valid.
+    pub fn parse_field_extractor(&self) -> Option<FieldExtractor> {
+        let fields = self.fields.as_ref()?;
+        match fields.format {
+            FieldFormat::Regex => FieldExtractor::regex(fields.pattern.as_deref()?, &fields.columns),
+            FieldFormat::Logfmt if !fields.columns.is_empty() => Some(FieldExtractor::logfmt(&fields.columns)),
+            FieldFormat::Json if !fields.columns.is_empty() => Some(FieldExtractor::json(&fields.columns)),
+            _ => None,
+        }
+    }
+
+    /// Parses the event pairs, skipping those with an invalid key.
+    pub fn parse_event_pairs(&self) -> Vec<EventPairPattern> {
+        self.event_pairs
+            .iter()
+            .filter_map(|pair| {
+                let key = match &pair.key {
+                    Some(key) => Some(Regex::new(key).ok()?),
+                    None => None,
+                };
+                let threshold = pair.threshold_ms.map(|millis| TimeDelta::milliseconds(millis as i64));
+                EventPairPattern::new(&pair.name, &pair.start, &pair.end, key, threshold)
+            })
+            .collect()
+    }
+
+    /// Parses the watch expressions, skipping invalid ones.
+    pub fn parse_watches(&self) -> Vec<WatchExpression> {
+        self.watches
+            .iter()
+            .filter_map(|w| WatchExpression::new(&w.name, Regex::new(&w.pattern).ok()?))
+            .collect()
+    }
+
+    /// Parses the alert rules, skipping invalid ones.
+    pub fn parse_alerts(&self) -> Vec<AlertRule> {
+        self.alerts
+            .iter()
+            .filter(|a| a.threshold.is_some() == a.window_secs.is_some())
+            .filter_map(|a| {
+                let rule = AlertRule::new(&a.name, Regex::new(&a.pattern).ok()?, a.threshold());
+                Some(rule.with_bell(a.bell).with_notify(a.notify))
+            })
+            .collect()
+    }
+
+    /// Parses the line actions, skipping invalid ones.
+    pub fn parse_actions(&self) -> Vec<LineAction> {
+        self.actions
+            .iter()
+            .filter_map(|a| {
+                let regex = match &a.pattern {
+                    Some(pattern) => Some(Regex::new(pattern).ok()?),
+                    None => None,
+                };
+                let kind = match (&a.command, &a.copy) {
+                    (Some(command), None) => ActionKind::Command(command.clone()),
+                    (None, Some(copy)) => ActionKind::Copy(copy.clone()),
+                    _ => return None,
+                };
+                Some(LineAction::new(&a.name, regex, kind))
+            })
+            .collect()
+    }
+
+    /// Parses the scripts, skipping those without a command.
+    pub fn parse_scripts(&self) -> Vec<Script> {
+        self.scripts
+            .iter()
+            .filter(|s| !s.command.trim().is_empty())
+            .map(|s| Script {
+                name: s.name.clone(),
+                command: s.command.clone(),
+                stream: s.stream,
+            })
+            .collect()
+    }
+
     /// Parses event patterns to the log event tracker
     pub fn parse_log_event_patterns(&self) -> Vec<EventPattern> {
         self.events
@@ -297,6 +1070,8 @@ impl Config {
                     Some(PatternMatcher::Plain(PlainMatch {
                         pattern: ev_config.pattern.clone(),
                         case_sensitive: true,
+                        whole_word: false,
+                        case_folding: CaseFolding::default(),
                     }))
                 };
 
@@ -306,7 +1081,11 @@ impl Config {
                     enabled: true,
                     count: 0,
                     critical: ev_config.critical,
+                    severity: ev_config.severity,
                     is_custom: false,
+                    color: None,
+                    bell: ev_config.bell,
+                    notify: ev_config.notify,
                 })
             })
             .collect()
@@ -321,6 +1100,18 @@ impl Config {
     }
 
     pub fn parse_color(color_str: &str) -> Option<Color> {
+        // Truecolor hex value, e.g. "#ff8800"
+        if let Some(hex) = color_str.strip_prefix('#') {
+            if hex.len() != 6 {
+                return None;
+            }
+            let rgb = u32::from_str_radix(hex, 16).ok()?;
+            return Some(Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8));
+        }
+        // Index in the 256-color palette
+        if let Ok(index) = color_str.parse::<u8>() {
+            return Some(Color::Indexed(index));
+        }
         match color_str.to_lowercase().as_str() {
             "red" => Some(Color::Red),
             "green" => Some(Color::Green),
@@ -355,3 +1146,475 @@ impl Config {
         Color::Indexed(color_index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlighter::{Highlighter, StyledRange};
+
+    #[test]
+    fn test_apply_profile() {
+        let toml_str = r#"
+            [[events]]
+            name = "Error"
+            pattern = "ERROR"
+
+            [profile.k8s]
+            events = [{ name = "OOMKilled", pattern = "OOMKilled" }]
+            filters = [{ pattern = "kube-probe", mode = "exclude" }]
+
+            [profile.embedded]
+            highlights = [{ pattern = "0x[0-9a-f]+" }]
+        "#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.profile_names(), vec!["embedded", "k8s"]);
+
+        config.apply_profile("k8s").unwrap();
+        let events: Vec<&str> = config.events.iter().map(|event| event.name.as_str()).collect();
+        assert_eq!(events, vec!["Error", "OOMKilled"]);
+        assert_eq!(config.filters.len(), 1);
+        assert!(config.highlights.is_empty());
+
+        assert_eq!(
+            config.apply_profile("docker"),
+            Err("Unknown profile 'docker', the config has: embedded, k8s".to_string())
+        );
+        assert!(Config::default().apply_profile("k8s").is_err());
+    }
+
+    #[test]
+    fn test_filters_file_round_trip() {
+        let filters = vec![
+            FilterPattern::new("healthcheck".to_string(), ActiveFilterMode::Exclude, false, false)
+                .with_file(Some("api.log".to_string())),
+            FilterPattern::new_regex(r"user=\d+".to_string(), ActiveFilterMode::Include, true, true).unwrap(),
+        ];
+        let toml_str = Filters::from_patterns(&filters).to_toml().unwrap();
+
+        let patterns = toml::from_str::<Filters>(&toml_str).unwrap().parse_filter_patterns();
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].mode, ActiveFilterMode::Exclude);
+        assert!(!patterns[0].enabled);
+        assert_eq!(patterns[0].file.as_deref(), Some("api.log"));
+        assert!(patterns[1].is_regex());
+        assert_eq!(patterns[1].pattern, r"user=\d+");
+
+        let empty = Filters::from_patterns(&[]).to_toml().unwrap();
+        assert!(toml::from_str::<Filters>(&empty).unwrap().filters.is_empty());
+    }
+
+    #[test]
+    fn test_config_snippet_round_trip() {
+        let filters = vec![
+            FilterPattern::new("ERROR".to_string(), ActiveFilterMode::Include, true, true),
+            FilterPattern::new("heartbeat".to_string(), ActiveFilterMode::Exclude, false, false).with_whole_word(true),
+        ];
+        let event = CustomEvent {
+            name: "lost",
+            pattern: "connection lost",
+            regex: false,
+            case_sensitive: true,
+            color: Some(Color::Indexed(214)),
+        };
+        let snippet = ConfigSnippet::new(&filters, &[event]);
+        let toml_str = snippet.to_toml().unwrap();
+
+        let parsed: Filters = toml::from_str(&toml_str).unwrap();
+        let patterns = parsed.parse_filter_patterns();
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].pattern, "ERROR");
+        assert_eq!(patterns[0].mode, ActiveFilterMode::Include);
+        assert!(patterns[0].case_sensitive);
+        assert_eq!(patterns[1].mode, ActiveFilterMode::Exclude);
+        assert!(!patterns[1].enabled);
+        assert!(!patterns[0].whole_word);
+        assert!(patterns[1].whole_word);
+
+        let config: Config = toml::from_str(&toml_str).unwrap();
+        let events = config.parse_log_event_patterns();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "lost");
+        let highlights = config.parse_highlight_event_patterns();
+        assert_eq!(highlights[0].style.bg_color, Some(Color::Indexed(214)));
+    }
+
+    #[test]
+    fn test_case_insensitive_custom_event_exported_as_regex() {
+        let event = CustomEvent {
+            name: "lost",
+            pattern: "conn.lost",
+            regex: false,
+            case_sensitive: false,
+            color: None,
+        };
+        let config = EventConfig::from(&event);
+        assert!(config.regex);
+        assert_eq!(config.pattern, r"(?i)conn\.lost");
+    }
+
+    #[test]
+    fn test_validate_reports_discarded_entries() {
+        let config: Config = toml::from_str(
+            r#"
+            section_pattern = "=== ("
+
+            [[highlights]]
+            pattern = "ok"
+            style = { fg = "purpel", bg = "blue" }
+
+            [[events]]
+            name = "broken"
+            pattern = "("
+            regex = true
+
+            [[filters]]
+            pattern = "[a-"
+            regex = true
+
+            [[filters]]
+            pattern = "fine"
+
+            [[watches]]
+            name = "queue"
+            pattern = "queue=\\d+"
+
+            [[alerts]]
+            name = "errors"
+            pattern = "ERROR"
+            threshold = 5
+            "#,
+        )
+        .unwrap();
+
+        let issues = config.validate();
+        assert_eq!(
+            issues,
+            vec![
+                "Highlight 'ok': unknown fg color 'purpel'",
+                "Event 'broken': invalid regex '('",
+                "Filter '[a-': invalid regex",
+                "Section pattern '=== (': invalid regex",
+                "Watch 'queue': pattern has no capture group",
+                "Alert 'errors': threshold and window_secs must be given together",
+            ]
+        );
+        assert!(Config::default().validate().is_empty());
+    }
+
+    #[test]
+    fn test_format_choice_defaults_to_none_with_levels_or_fields() {
+        assert_eq!(Config::default().parse_format_choice(), FormatChoice::Auto);
+
+        let config: Config = toml::from_str("[levels]\nerror = 'FAIL'").unwrap();
+        assert_eq!(config.parse_format_choice(), FormatChoice::None);
+
+        let config: Config = toml::from_str("format = 'auto'\n[levels]\nerror = 'FAIL'").unwrap();
+        assert_eq!(config.parse_format_choice(), FormatChoice::Auto);
+    }
+
+    #[test]
+    fn test_timestamp_formats() {
+        let config: Config = toml::from_str(
+            r#"
+            [[timestamps.formats]]
+            format = "%d.%m.%Y %H:%M:%S"
+            column = 7
+
+            [[timestamps.formats]]
+            format = "%s"
+            pattern = 'epoch=(\d+)'
+
+            [[timestamps.formats]]
+            format = "%Y-%Q"
+
+            [[timestamps.formats]]
+            format = "%H:%M"
+            pattern = "("
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.validate(),
+            vec![
+                "Timestamp format '%Y-%Q': invalid format",
+                "Timestamp format '%H:%M': invalid regex '('",
+            ]
+        );
+
+        let parser = config.parse_timestamp_parser(None);
+        let expected = crate::timestamp::parse_timestamp("2024-01-15 10:30:45");
+        assert_eq!(parser.parse("[app] 15.01.2024 10:30:45 started"), expected);
+        assert_eq!(parser.parse("request epoch=1705314645 done"), expected);
+        assert_eq!(parser.parse("2024-01-15T10:30:45Z fallback"), expected);
+    }
+
+    #[test]
+    fn test_highlight_capture_group_styles() {
+        let config: Config = toml::from_str(
+            r#"
+            [[highlights]]
+            pattern = '(\w+)=(?P<value>\d+)'
+            regex = true
+            groups = { 1 = { fg = "yellow" }, value = { fg = "cyan", bold = true } }
+
+            [[highlights]]
+            pattern = 'id=(\d+)'
+            regex = true
+            groups = { name = { fg = "red" } }
+            "#,
+        )
+        .unwrap();
+
+        // The pattern with an unknown group is discarded
+        let patterns = config.parse_highlight_patterns();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(
+            config.validate(),
+            vec![r"Highlight 'id=(\d+)' group 'name': no such capture group"]
+        );
+
+        let highlighter = Highlighter::new(patterns, Vec::new());
+        let segments = highlighter.highlight_text("took ms=12", None, &[]).segments;
+        let yellow = PatternStyle::new(Some(Color::Yellow), None, false);
+        let cyan = PatternStyle::new(Some(Color::Cyan), None, true);
+        assert_eq!(
+            segments,
+            vec![
+                StyledRange {
+                    start: 5,
+                    end: 7,
+                    style: yellow
+                },
+                StyledRange {
+                    start: 8,
+                    end: 10,
+                    style: cyan
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlight_patterns_are_drawn_in_order() {
+        let config: Config = toml::from_str(
+            r#"
+            [[highlights]]
+            pattern = "error"
+            style = { fg = "red" }
+
+            [[highlights]]
+            pattern = 'E\w+'
+            regex = true
+            style = { fg = "blue" }
+
+            [[highlights]]
+            pattern = "Or"
+            case_sensitive = true
+            style = { fg = "green" }
+            "#,
+        )
+        .unwrap();
+
+        let highlighter = Highlighter::new(config.parse_highlight_patterns(), Vec::new());
+        let segments: Vec<(usize, usize, Option<Color>)> = highlighter
+            .highlight_text("ERROR x error ErrOr", None, &[])
+            .segments
+            .iter()
+            .map(|segment| (segment.start, segment.end, segment.style.fg_color))
+            .collect();
+        assert_eq!(
+            segments,
+            vec![
+                (0, 5, Some(Color::Blue)),
+                (8, 13, Some(Color::Red)),
+                (14, 17, Some(Color::Blue)),
+                (17, 19, Some(Color::Green)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_actions() {
+        let config: Config = toml::from_str(
+            r#"
+            [[actions]]
+            name = "Open trace"
+            pattern = 'trace=(?P<trace>\w+)'
+            command = "xdg-open https://trace.example.com/{trace}"
+
+            [[actions]]
+            name = "Copy line"
+            copy = "{line}"
+
+            [[actions]]
+            name = "Nothing"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.validate(),
+            vec!["Action 'Nothing': needs either a command or a copy template"]
+        );
+
+        let actions = config.parse_actions();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(
+            actions[0].expand("x trace=abc"),
+            Some("xdg-open https://trace.example.com/'abc'".to_string())
+        );
+        assert_eq!(actions[1].kind(), &ActionKind::Copy("{line}".to_string()));
+    }
+
+    #[test]
+    fn test_parse_event_severity() {
+        let config: Config = toml::from_str(
+            r#"
+            events = [
+                { name = "Error", pattern = " ERROR ", severity = "error" },
+                { name = "Warning", pattern = " WARN", severity = "warning" },
+                { name = "Started", pattern = "started" },
+            ]
+            "#,
+        )
+        .unwrap();
+        let severities: Vec<_> = config.parse_log_event_patterns().iter().map(|p| p.severity).collect();
+        assert_eq!(severities, vec![Some(LogLevel::Error), Some(LogLevel::Warn), None]);
+        assert!(toml::from_str::<Config>(r#"events = [{ name = "x", pattern = "x", severity = "fatal" }]"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_alerts() {
+        let config: Config = toml::from_str(
+            r#"
+            [[alerts]]
+            name = "Panic"
+            pattern = "panicked at"
+            bell = true
+
+            [[alerts]]
+            name = "Timeouts"
+            pattern = 'timeout|timed out'
+            threshold = 5
+            window_secs = 10
+            notify = true
+
+            [[alerts]]
+            name = "Broken"
+            pattern = "("
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.validate(), vec!["Alert 'Broken': invalid regex '('"]);
+
+        let alerts = config.parse_alerts();
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts[0].bell && !alerts[0].notify);
+        assert_eq!(alerts[0].threshold(), None);
+        assert_eq!(
+            alerts[1].threshold(),
+            Some(AlertThreshold {
+                count: 5,
+                window: std::time::Duration::from_secs(10)
+            })
+        );
+        assert!(alerts[1].notify);
+    }
+
+    #[test]
+    fn test_parse_scripts() {
+        let config: Config = toml::from_str(
+            r#"
+            [[scripts]]
+            name = "Latency"
+            command = "python3 latency.py"
+            stream = true
+
+            [[scripts]]
+            name = "Empty"
+            command = " "
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.validate(), vec!["Script 'Empty': command is empty"]);
+        assert_eq!(
+            config.parse_scripts(),
+            vec![Script {
+                name: "Latency".to_string(),
+                command: "python3 latency.py".to_string(),
+                stream: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_field_extractor() {
+        let config: Config = toml::from_str(
+            r#"
+            [fields]
+            format = "regex"
+            pattern = '^(?P<time>\S+) (?P<level>\w+) (?P<msg>.*)$'
+            columns = ["level", "msg", "thread"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.validate(), vec!["Fields: pattern has no group named 'thread'"]);
+
+        let config: Config = toml::from_str(
+            r#"
+            [fields]
+            format = "json"
+            columns = ["level", "msg"]
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_empty());
+        let extractor = config.parse_field_extractor().unwrap();
+        assert_eq!(extractor.columns(), ["level", "msg"]);
+
+        let config: Config = toml::from_str("[fields]\nformat = \"logfmt\"").unwrap();
+        assert_eq!(config.validate(), vec!["Fields: no columns given"]);
+        assert!(config.parse_field_extractor().is_none());
+    }
+
+    #[test]
+    fn test_parse_color_formats() {
+        assert_eq!(Config::parse_color("LightBlue"), Some(Color::LightBlue));
+        assert_eq!(Config::parse_color("#ff8000"), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(Config::parse_color("237"), Some(Color::Indexed(237)));
+        assert_eq!(Config::parse_color("#ff80"), None);
+        assert_eq!(Config::parse_color("256"), None);
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let config: Config = toml::from_str(
+            r##"
+            [theme]
+            name = "light"
+            footer_bg = "#102030"
+            selection_bg = "nope"
+            unknown = "red"
+            "##,
+        )
+        .unwrap();
+
+        let theme = config.parse_theme();
+        assert_eq!(theme.footer_bg, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(theme.selection_bg, Theme::light().selection_bg);
+        assert_eq!(
+            config.validate(),
+            vec![
+                "Theme: unknown selection_bg color 'nope'",
+                "Theme: unknown element 'unknown'",
+            ]
+        );
+        assert_eq!(Config::default().parse_theme(), Theme::dark());
+    }
+
+    #[test]
+    fn test_config_snippet_empty() {
+        let snippet = ConfigSnippet::new(&[], &[]);
+        assert!(snippet.is_empty());
+        assert_eq!(snippet.to_toml().unwrap(), "");
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::event::AppEvent;
+
+/// Interval between checks of the config and filters files for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Returns the modification time of each file, or `None` if it doesn't exist.
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
+
+/// Polls the files for changes and sends [`AppEvent::ConfigChanged`] when one of them is written, created or
+/// removed.
+pub fn spawn_config_watcher(paths: Vec<PathBuf>, send: impl Fn(AppEvent) + Send + 'static) {
+    if paths.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut modified = modified_times(&paths);
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let current = modified_times(&paths);
+            if current != modified {
+                modified = current;
+                send(AppEvent::ConfigChanged);
+            }
+        }
+    });
+}
+
+/// Numbers of entries added to and removed from a section of the config when it was reloaded. An edited entry
+/// counts as removed and added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionChanges {
+    pub section: &'static str,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl SectionChanges {
+    /// Compares the entries of a section before and after reloading.
+    pub fn new<T: PartialEq>(section: &'static str, old: &[T], new: &[T]) -> Self {
+        Self {
+            section,
+            added: new.iter().filter(|entry| !old.contains(entry)).count(),
+            removed: old.iter().filter(|entry| !new.contains(entry)).count(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+}
+
+/// Describes the changes of the sections, one per line like `events: 2 added, 1 removed`. Returns `None` if no
+/// section changed.
+pub fn describe_changes(changes: &[SectionChanges]) -> Option<String> {
+    let lines: Vec<String> = changes
+        .iter()
+        .filter(|changes| !changes.is_empty())
+        .map(|changes| {
+            let mut parts = Vec::new();
+            if changes.added > 0 {
+                parts.push(format!("{} added", changes.added));
+            }
+            if changes.removed > 0 {
+                parts.push(format!("{} removed", changes.removed));
+            }
+            format!("{}: {}", changes.section, parts.join(", "))
+        })
+        .collect();
+    if lines.is_empty() { None } else { Some(lines.join("\n")) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_config_changes() {
+        let events = SectionChanges::new("events", &["error", "warn"], &["error", "timeout", "retry"]);
+        assert_eq!(
+            events,
+            SectionChanges {
+                section: "events",
+                added: 2,
+                removed: 1
+            }
+        );
+        let highlights = SectionChanges::new("highlights", &["id=\\d+"], &["id=\\d+"]);
+        assert!(highlights.is_empty());
+        let filters = SectionChanges::new("filters", &[], &["healthcheck"]);
+
+        assert_eq!(
+            describe_changes(&[highlights.clone(), events, filters]),
+            Some("events: 2 added, 1 removed\nfilters: 1 added".to_string())
+        );
+        assert_eq!(describe_changes(&[highlights]), None);
+    }
+}
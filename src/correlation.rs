@@ -0,0 +1,88 @@
+use rayon::prelude::*;
+use regex::Regex;
+
+use crate::log::LogLine;
+
+/// Lines sharing an id with a line, e.g. all lines of a request or trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correlation {
+    /// Name of the capture group the id was taken from, or the pattern if it has no named group.
+    pub name: String,
+    /// The shared id.
+    pub value: String,
+    /// Log indices of the lines with the id, in log order.
+    pub line_indices: Vec<usize>,
+}
+
+impl Correlation {
+    /// Finds the lines with the id the pattern captures from the given line.
+    ///
+    /// The id is the first named group of the pattern, otherwise its first group or the whole match, so a
+    /// pattern like `req-\d+` correlates on the id in the line. Returns None if the pattern does not match the line.
+    pub fn find(regex: &Regex, line: &LogLine, lines: &[LogLine]) -> Option<Self> {
+        let (group, name) = match regex.capture_names().enumerate().find_map(|(i, name)| Some((i, name?))) {
+            Some((group, name)) => (group, name.to_string()),
+            None => (usize::from(regex.captures_len() > 1), regex.as_str().to_string()),
+        };
+        let value = regex.captures(line.content())?.get(group)?.as_str().to_string();
+        if value.is_empty() {
+            return None;
+        }
+
+        let line_indices = lines
+            .par_iter()
+            .filter(|line| {
+                line.content().contains(&value)
+                    && regex
+                        .captures_iter(line.content())
+                        .any(|captures| captures.get(group).is_some_and(|m| m.as_str() == value))
+            })
+            .map(|line| line.index)
+            .collect();
+
+        Some(Self {
+            name,
+            value,
+            line_indices,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(contents: &[&str]) -> Vec<LogLine> {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(index, content)| LogLine::new(content, index))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_correlated_lines() {
+        let lines = lines(&[
+            "request_id=a1 start",
+            "request_id=b2 start",
+            "request_id=a1 query took 12ms",
+            "request_id=a12 start",
+            "request_id=b2 done, caused by request_id=a1",
+        ]);
+
+        let regex = Regex::new(r"request_id=(?P<request_id>\w+)").unwrap();
+        let correlation = Correlation::find(&regex, &lines[2], &lines).unwrap();
+        assert_eq!(correlation.name, "request_id");
+        assert_eq!(correlation.value, "a1");
+        assert_eq!(correlation.line_indices, vec![0, 2, 4]);
+
+        // Without groups the whole match is the id
+        let regex = Regex::new(r"request_id=\w+").unwrap();
+        let correlation = Correlation::find(&regex, &lines[1], &lines).unwrap();
+        assert_eq!(correlation.name, r"request_id=\w+");
+        assert_eq!(correlation.line_indices, vec![1, 4]);
+
+        let regex = Regex::new(r"trace=(\w+)").unwrap();
+        assert_eq!(Correlation::find(&regex, &lines[0], &lines), None);
+    }
+}
@@ -0,0 +1,126 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of frames whose render times are kept.
+const FRAME_HISTORY: usize = 60;
+
+/// Timings shown in the debug overlay, which is available when started with `--debug`.
+#[derive(Debug, Default)]
+pub struct DebugStats {
+    /// Render times of the most recent frames, oldest first.
+    frame_times: VecDeque<Duration>,
+    /// Time spent applying the filters the last time the visible lines were computed.
+    pub filter_time: Option<Duration>,
+    /// Memory usage of the log buffer, with the buffer generation it was measured at.
+    memory_usage: Cell<Option<(u64, usize)>>,
+}
+
+impl DebugStats {
+    /// Records the render time of a frame.
+    pub fn record_frame(&mut self, time: Duration) {
+        if self.frame_times.len() == FRAME_HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(time);
+    }
+
+    /// Returns the render time of the last frame.
+    pub fn last_frame_time(&self) -> Option<Duration> {
+        self.frame_times.back().copied()
+    }
+
+    /// Returns the average and the maximum render time of the recent frames.
+    pub fn frame_time_stats(&self) -> Option<(Duration, Duration)> {
+        let max = self.frame_times.iter().max().copied()?;
+        let average = self.frame_times.iter().sum::<Duration>() / self.frame_times.len() as u32;
+        Some((average, max))
+    }
+
+    /// Returns the memory usage of the log buffer at `generation`, measuring it with `measure` only when the
+    /// generation changed since the last call.
+    pub fn memory_usage(&self, generation: u64, measure: impl FnOnce() -> usize) -> usize {
+        match self.memory_usage.get() {
+            Some((cached_generation, bytes)) if cached_generation == generation => bytes,
+            _ => {
+                let bytes = measure();
+                self.memory_usage.set(Some((generation, bytes)));
+                bytes
+            }
+        }
+    }
+}
+
+/// Formats a duration in milliseconds with enough precision for fast operations, e.g. `0.42 ms`.
+pub fn format_millis(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_time_stats() {
+        let mut stats = DebugStats::default();
+        assert_eq!(stats.frame_time_stats(), None);
+
+        for millis in [4, 2, 6] {
+            stats.record_frame(Duration::from_millis(millis));
+        }
+        assert_eq!(stats.last_frame_time(), Some(Duration::from_millis(6)));
+        assert_eq!(
+            stats.frame_time_stats(),
+            Some((Duration::from_millis(4), Duration::from_millis(6)))
+        );
+
+        for _ in 0..FRAME_HISTORY {
+            stats.record_frame(Duration::from_millis(1));
+        }
+        assert_eq!(
+            stats.frame_time_stats(),
+            Some((Duration::from_millis(1), Duration::from_millis(1)))
+        );
+    }
+
+    #[test]
+    fn test_memory_usage_measured_once_per_generation() {
+        let stats = DebugStats::default();
+        let measured = Cell::new(0);
+        let measure = |bytes| {
+            measured.set(measured.get() + 1);
+            bytes
+        };
+
+        assert_eq!(stats.memory_usage(1, || measure(100)), 100);
+        assert_eq!(stats.memory_usage(1, || measure(200)), 100);
+        assert_eq!(measured.get(), 1);
+
+        assert_eq!(stats.memory_usage(2, || measure(200)), 200);
+        assert_eq!(measured.get(), 2);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(format_millis(Duration::from_micros(420)), "0.42 ms");
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::log::LogLine;
+use crate::resolver::VisibilityRule;
+use crate::template::line_template;
+
+/// How lines are compared to find repeated lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatMatch {
+    /// Lines repeat if their content is identical.
+    Identical,
+    /// Lines repeat if their templates, with numbers and ids masked, are identical.
+    Template,
+}
+
+/// Lines the repeated lines were found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ScannedLines {
+    base_generation: u64,
+    count: usize,
+    repeat_match: RepeatMatch,
+}
+
+/// Runs of consecutive repeated lines, collapsed into the first line of each run like syslog's
+/// "last message repeated N times".
+#[derive(Debug, Default)]
+pub struct RepeatedLines {
+    /// Hash of what each scanned line is compared by, by log index.
+    keys: Vec<u64>,
+    /// Number of repeats following the first line of each run, by the log index of the first line.
+    repeat_counts: HashMap<usize, usize>,
+    /// Log indices of the repeats, which are hidden.
+    repeats: Arc<HashSet<usize>>,
+    scanned: Option<ScannedLines>,
+}
+
+impl RepeatedLines {
+    /// Finds the runs of repeated lines among the lines shown by the other visibility rules, given by their log
+    /// indices in log order, so a repeat is only hidden while the first line of its run is shown. Appended lines
+    /// are hashed on their own.
+    pub fn update(
+        &mut self,
+        lines: &[LogLine],
+        base_generation: u64,
+        repeat_match: RepeatMatch,
+        visible: impl IntoIterator<Item = usize>,
+    ) {
+        let start = match self.scanned {
+            Some(scanned)
+                if scanned.base_generation == base_generation
+                    && scanned.repeat_match == repeat_match
+                    && scanned.count <= lines.len() =>
+            {
+                scanned.count
+            }
+            _ => {
+                self.clear();
+                0
+            }
+        };
+        self.scanned = Some(ScannedLines {
+            base_generation,
+            count: lines.len(),
+            repeat_match,
+        });
+        self.keys.extend(lines[start..].iter().map(|line| {
+            let mut hasher = DefaultHasher::new();
+            match repeat_match {
+                RepeatMatch::Identical => line.content().hash(&mut hasher),
+                RepeatMatch::Template => line_template(line.content()).hash(&mut hasher),
+            }
+            hasher.finish()
+        }));
+
+        self.repeat_counts.clear();
+        let mut repeats = HashSet::new();
+        let mut run_start: Option<usize> = None;
+        for index in visible {
+            match run_start {
+                Some(start) if self.keys.get(start) == self.keys.get(index) => {
+                    repeats.insert(index);
+                    *self.repeat_counts.entry(start).or_default() += 1;
+                }
+                _ => run_start = Some(index),
+            }
+        }
+        self.repeats = Arc::new(repeats);
+    }
+
+    /// Returns the number of repeats collapsed into the line at the given log index.
+    pub fn repeat_count(&self, log_index: usize) -> usize {
+        self.repeat_counts.get(&log_index).copied().unwrap_or(0)
+    }
+
+    /// Returns the log indices of the repeats.
+    pub fn repeats(&self) -> Arc<HashSet<usize>> {
+        Arc::clone(&self.repeats)
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.repeat_counts.clear();
+        self.repeats = Arc::default();
+        self.scanned = None;
+    }
+}
+
+/// Rule that hides repeated lines, leaving the first line of each run.
+pub struct RepeatedLinesRule {
+    repeats: Arc<HashSet<usize>>,
+}
+
+impl RepeatedLinesRule {
+    pub fn new(repeats: Arc<HashSet<usize>>) -> Self {
+        Self { repeats }
+    }
+}
+
+impl VisibilityRule for RepeatedLinesRule {
+    fn is_visible(&self, line: &LogLine) -> bool {
+        !self.repeats.contains(&line.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::LogBuffer;
+
+    fn buffer(contents: &[&str]) -> LogBuffer {
+        let mut buffer = LogBuffer::default();
+        buffer.init_stdin_mode();
+        for content in contents {
+            buffer.append_line(content.to_string());
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_identical_repeats() {
+        let buffer = buffer(&["connect", "retry", "retry", "retry", "connected", "retry"]);
+        let mut repeated = RepeatedLines::default();
+        repeated.update(
+            buffer.all_lines(),
+            buffer.base_generation(),
+            RepeatMatch::Identical,
+            0..buffer.get_total_lines_count(),
+        );
+
+        assert_eq!(repeated.repeat_count(1), 2);
+        assert_eq!(repeated.repeat_count(5), 0);
+        let rule = RepeatedLinesRule::new(repeated.repeats());
+        let visible: Vec<bool> = buffer.iter().map(|line| rule.is_visible(line)).collect();
+        assert_eq!(visible, vec![true, true, false, false, true, true]);
+    }
+
+    #[test]
+    fn test_template_repeats_continue_with_appended_lines() {
+        let mut buffer = buffer(&["took 12ms", "took 15ms", "done"]);
+        let mut repeated = RepeatedLines::default();
+        repeated.update(
+            buffer.all_lines(),
+            buffer.base_generation(),
+            RepeatMatch::Identical,
+            0..buffer.get_total_lines_count(),
+        );
+        assert_eq!(repeated.repeat_count(0), 0);
+
+        repeated.update(
+            buffer.all_lines(),
+            buffer.base_generation(),
+            RepeatMatch::Template,
+            0..buffer.get_total_lines_count(),
+        );
+        assert_eq!(repeated.repeat_count(0), 1);
+
+        buffer.append_line("done".to_string());
+        buffer.append_line("done".to_string());
+        repeated.update(
+            buffer.all_lines(),
+            buffer.base_generation(),
+            RepeatMatch::Template,
+            0..buffer.get_total_lines_count(),
+        );
+        assert_eq!(repeated.repeat_count(2), 2);
+        assert_eq!(repeated.repeats().len(), 3);
+
+        // Dropped lines are scanned again
+        buffer.drop_oldest(3);
+        repeated.update(
+            buffer.all_lines(),
+            buffer.base_generation(),
+            RepeatMatch::Template,
+            0..buffer.get_total_lines_count(),
+        );
+        assert_eq!(repeated.repeat_count(0), 1);
+        assert_eq!(repeated.repeats().len(), 1);
+    }
+
+    #[test]
+    fn test_repeats_among_visible_lines() {
+        let buffer = buffer(&["connect", "retry", "retry", "retry", "debug", "retry"]);
+        let mut repeated = RepeatedLines::default();
+
+        // The first line of the run is hidden, so the next one starts it
+        repeated.update(
+            buffer.all_lines(),
+            buffer.base_generation(),
+            RepeatMatch::Identical,
+            [0, 2, 3],
+        );
+        assert_eq!(repeated.repeat_count(1), 0);
+        assert_eq!(repeated.repeat_count(2), 1);
+        assert_eq!(*repeated.repeats(), HashSet::from([3]));
+
+        // A hidden line in between doesn't end the run
+        repeated.update(
+            buffer.all_lines(),
+            buffer.base_generation(),
+            RepeatMatch::Identical,
+            [0, 1, 5],
+        );
+        assert_eq!(repeated.repeat_count(1), 1);
+        assert_eq!(*repeated.repeats(), HashSet::from([5]));
+    }
+}
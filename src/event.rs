@@ -2,22 +2,25 @@ use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::event::Event as CrosstermEvent;
 use std::io::{BufRead, BufReader};
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::{mpsc, watch};
 
-use crate::live_processor::{LiveProcessorHandle, ProcessedLine};
-
-/// The frequency at which tick events are emitted.
-const TICK_FPS: f64 = 5.0;
+use crate::child_process::ChildStatus;
+use crate::filter_task::FilterMask;
+use crate::live_processor::{InputLine, LiveProcessorHandle, ProcessedLine};
+use crate::load_task::LoadedLines;
+use crate::preview_task::PreviewCount;
+use crate::search_task::SearchProgress;
 
 /// Representation of all possible events.
 #[derive(Clone, Debug)]
 pub enum Event {
-    /// An event that is emitted on a regular schedule.
+    /// An event that is emitted when a timer scheduled by the application is due.
     ///
     /// Use this event to run any code which has to run outside of being a direct response to a user
-    /// event. e.g. polling exernal systems, updating animations, or rendering the UI based on a
-    /// fixed frame rate.
+    /// event, e.g. hiding a message after a timeout. Nothing is emitted while no timer is scheduled,
+    /// so an idle application doesn't wake up.
     Tick,
     /// Crossterm events.
     ///
@@ -34,8 +37,44 @@ pub enum Event {
 /// Keep events minimal - only for async operations.
 #[derive(Clone, Debug)]
 pub enum AppEvent {
-    /// New line(s) received from stdin and processed.
-    NewLines(Vec<ProcessedLine>),
+    /// New line(s) received from stdin and processed, taken with [`EventHandler::take_new_lines`].
+    ///
+    /// No further event is sent until the lines are taken, so lines arriving in the meantime are coalesced.
+    NewLines,
+    /// Background filtering finished.
+    FilterComplete(FilterMask),
+    /// A command lines were piped through in the background exited. Contains its output or error.
+    PipeComplete(Result<String, String>),
+    /// Background loading of a large file finished.
+    LoadComplete(LoadedLines),
+    /// Background search found matches or finished.
+    SearchProgress(SearchProgress),
+    /// Background count of the lines a filter or search being typed would give finished.
+    PreviewCount(PreviewCount),
+    /// A spawned command exited. Contains the generation of the command and its exit status.
+    CommandExited(usize, ChildStatus),
+    /// Stdin ended and all of its lines were processed, sent after the [`AppEvent::NewLines`] of its last lines.
+    StdinEnded,
+    /// Annotations from a streaming script arrived, taken with [`StreamingScript::take_annotations`].
+    ///
+    /// [`StreamingScript::take_annotations`]: crate::script::StreamingScript::take_annotations
+    ScriptAnnotations,
+    /// The process received SIGTERM or SIGHUP, e.g. because its terminal was closed, and should save its state
+    /// and quit.
+    Terminate,
+    /// The config or filters file changed on disk and should be reloaded.
+    ConfigChanged,
+}
+
+/// Source of streamed log lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamSource {
+    /// Nothing is streamed, lines are loaded from files.
+    None,
+    /// Lines are read from stdin.
+    Stdin,
+    /// Lines are read from the output of a spawned command.
+    Command,
 }
 
 /// Terminal event handler.
@@ -47,48 +86,91 @@ pub struct EventHandler {
     receiver: mpsc::UnboundedReceiver<Event>,
     /// Log processor handle for streaming mode.
     pub processor: Option<LiveProcessorHandle>,
+    /// Processed lines not yet taken by the application.
+    new_lines: Arc<Mutex<Vec<ProcessedLine>>>,
+    /// Whether reading terminal events is suspended, e.g. while an external program uses the terminal.
+    suspended: watch::Sender<bool>,
 }
 
 impl EventHandler {
     /// Constructs a new instance of [`EventHandler`] and spawns a new thread to handle events.
-    pub fn new(use_stdin: bool) -> Self {
-        if use_stdin {
-            let (sender, receiver) = mpsc::unbounded_channel();
-            let actor = EventTask::new(sender.clone());
+    ///
+    /// When reading stdin, `stdin_lines` are lines already read from it, processed before the rest.
+    pub fn new(source: StreamSource, stdin_lines: Vec<String>) -> Self {
+        let (suspended, suspended_rx) = watch::channel(false);
+        let new_lines: Arc<Mutex<Vec<ProcessedLine>>> = Arc::default();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        spawn_termination_handler(sender.clone());
+
+        if source != StreamSource::None {
+            let actor = EventTask::new(sender.clone(), suspended_rx);
             tokio::spawn(async { actor.run().await });
 
             let (output_tx, mut output_rx) = mpsc::unbounded_channel();
             let processor = LiveProcessorHandle::spawn(output_tx);
 
             let event_sender = sender.clone();
-            let proc_input = processor.input_tx.clone();
-
-            // Spawn a blocking thread to read stdin lines
-            std::thread::spawn({
-                move || {
-                    let stdin = std::io::stdin();
-                    let reader = BufReader::new(stdin);
-
-                    for line in reader.lines() {
-                        match line {
-                            Ok(log_line) => {
-                                if proc_input.send(log_line).is_err() {
-                                    break;
+
+            // Number of lines read from stdin, set once it ended
+            let (stdin_total_tx, mut stdin_total_rx) = watch::channel(None);
+            if source == StreamSource::Stdin {
+                let proc_input = processor.input_tx.clone();
+
+                // Spawn a blocking thread to read stdin lines
+                std::thread::spawn({
+                    move || {
+                        let stdin = std::io::stdin();
+                        let reader = BufReader::new(stdin);
+                        let mut count = 0;
+
+                        for line in stdin_lines.into_iter().map(Ok).chain(reader.lines()) {
+                            match line {
+                                Ok(log_line) => {
+                                    if proc_input.send(log_line.into()).is_err() {
+                                        return;
+                                    }
+                                    count += 1;
                                 }
+                                Err(_) => break,
                             }
-                            Err(_) => break,
                         }
+                        stdin_total_tx.send_replace(Some(count));
                     }
-                }
-            });
+                });
+            }
 
+            let pending_lines = new_lines.clone();
             tokio::spawn(async move {
-                while let Some(processed_lines) = output_rx.recv().await {
-                    if event_sender
-                        .send(Event::App(AppEvent::NewLines(processed_lines)))
-                        .is_err()
-                    {
-                        break;
+                let mut received = 0;
+                let mut stdin_total = None;
+                let mut stdin_ended = false;
+                loop {
+                    tokio::select! {
+                        processed_lines = output_rx.recv() => {
+                            let Some(processed_lines) = processed_lines else {
+                                break;
+                            };
+                            received += processed_lines.len();
+                            let was_empty = {
+                                let mut pending = pending_lines.lock().unwrap();
+                                let was_empty = pending.is_empty();
+                                pending.extend(processed_lines);
+                                was_empty
+                            };
+                            // An event is already queued for lines that haven't been taken yet
+                            if was_empty && event_sender.send(Event::App(AppEvent::NewLines)).is_err() {
+                                break;
+                            }
+                        }
+                        Ok(()) = stdin_total_rx.changed(), if stdin_total.is_none() => {
+                            stdin_total = *stdin_total_rx.borrow();
+                        }
+                    }
+                    if !stdin_ended && stdin_total.is_some_and(|total| received >= total) {
+                        stdin_ended = true;
+                        if event_sender.send(Event::App(AppEvent::StdinEnded)).is_err() {
+                            break;
+                        }
                     }
                 }
             });
@@ -97,31 +179,41 @@ impl EventHandler {
                 sender,
                 receiver,
                 processor: Some(processor),
+                new_lines,
+                suspended,
             }
         } else {
-            let (sender, receiver) = mpsc::unbounded_channel();
-            let actor = EventTask::new(sender.clone());
+            let actor = EventTask::new(sender.clone(), suspended_rx);
             tokio::spawn(async { actor.run().await });
 
             Self {
                 sender,
                 receiver,
                 processor: None,
+                new_lines,
+                suspended,
             }
         }
     }
 
     /// Receives an event from the sender.
     ///
-    /// This function blocks until an event is received.
+    /// This function blocks until an event is received, or returns [`Event::Tick`] when the given
+    /// deadline is reached first.
     ///
     /// # Errors
     ///
     /// This function returns an error if the sender channel is disconnected. This can happen if an
     /// error occurs in the event thread. In practice, this should not happen unless there is a
     /// problem with the underlying terminal.
-    pub async fn next(&mut self) -> color_eyre::Result<Event> {
-        self.receiver.recv().await.ok_or_eyre("Failed to receive event")
+    pub async fn next(&mut self, deadline: Option<Instant>) -> color_eyre::Result<Event> {
+        let Some(deadline) = deadline else {
+            return self.receiver.recv().await.ok_or_eyre("Failed to receive event");
+        };
+        tokio::select! {
+            event = self.receiver.recv() => event.ok_or_eyre("Failed to receive event"),
+            _ = tokio::time::sleep_until(deadline.into()) => Ok(Event::Tick),
+        }
     }
 
     /// Queue an app event to be sent to the event receiver.
@@ -130,49 +222,155 @@ impl EventHandler {
         // reference to it
         let _ = self.sender.send(Event::App(app_event));
     }
+
+    /// Takes up to `max` of the processed lines received since the last call, oldest first.
+    pub fn take_new_lines(&self, max: usize) -> Vec<ProcessedLine> {
+        let mut new_lines = self.new_lines.lock().unwrap();
+        if new_lines.len() <= max {
+            return std::mem::take(&mut *new_lines);
+        }
+        new_lines.drain(..max).collect()
+    }
+
+    /// Returns true if processed lines are waiting to be taken.
+    pub fn has_new_lines(&self) -> bool {
+        !self.new_lines.lock().unwrap().is_empty()
+    }
+
+    /// Returns a sender for feeding lines to the log processor, if streaming.
+    pub fn line_sender(&self) -> Option<mpsc::UnboundedSender<InputLine>> {
+        self.processor.as_ref().map(|processor| processor.input_tx.clone())
+    }
+
+    /// Returns a sender that can be moved to background tasks to queue app events.
+    pub fn app_sender(&self) -> impl Fn(AppEvent) + Send + 'static {
+        let sender = self.sender.clone();
+        move |app_event| {
+            let _ = sender.send(Event::App(app_event));
+        }
+    }
+
+    /// Stops reading terminal events so another program can take over the terminal.
+    pub fn suspend(&self) {
+        self.suspended.send_replace(true);
+    }
+
+    /// Resumes reading terminal events after [`EventHandler::suspend`].
+    pub fn resume(&self) {
+        self.suspended.send_replace(false);
+    }
+}
+
+/// Sends [`AppEvent::Terminate`] when the process receives SIGTERM or SIGHUP, so the state is saved before exiting.
+#[cfg(unix)]
+fn spawn_termination_handler(sender: mpsc::UnboundedSender<Event>) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let (Ok(mut terminate), Ok(mut hangup)) = (signal(SignalKind::terminate()), signal(SignalKind::hangup())) else {
+        return;
+    };
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = hangup.recv() => {}
+            }
+            if sender.send(Event::App(AppEvent::Terminate)).is_err() {
+                return;
+            }
+        }
+    });
 }
 
-/// A thread that handles reading crossterm events and emitting tick events on a regular schedule.
+#[cfg(not(unix))]
+fn spawn_termination_handler(_sender: mpsc::UnboundedSender<Event>) {}
+
+/// A thread that handles reading crossterm events.
 struct EventTask {
     /// Event sender channel.
     sender: mpsc::UnboundedSender<Event>,
+    /// Whether reading terminal events is suspended.
+    suspended: watch::Receiver<bool>,
 }
 
 impl EventTask {
     /// Constructs a new instance of [`EventThread`].
-    fn new(sender: mpsc::UnboundedSender<Event>) -> Self {
-        Self { sender }
+    fn new(sender: mpsc::UnboundedSender<Event>, suspended: watch::Receiver<bool>) -> Self {
+        Self { sender, suspended }
     }
 
     /// Runs the event thread.
     ///
-    /// This function emits tick events at a fixed rate and polls for crossterm events in between.
-    async fn run(self) -> color_eyre::Result<()> {
-        let tick_rate = Duration::from_secs_f64(1.0 / TICK_FPS);
-        let mut reader = crossterm::event::EventStream::new();
-        let mut tick = tokio::time::interval(tick_rate);
+    /// This function waits for crossterm events without polling, so it only wakes up on input.
+    /// While suspended, the terminal event reader is dropped so it doesn't consume input meant for another program.
+    async fn run(mut self) -> color_eyre::Result<()> {
         loop {
-            let tick_delay = tick.tick();
-            let crossterm_event = reader.next().fuse();
-            tokio::select! {
-              _ = self.sender.closed() => {
-                break;
-              }
-              _ = tick_delay => {
-                self.send(Event::Tick);
-              }
-              Some(Ok(evt)) = crossterm_event => {
-                self.send(Event::Crossterm(evt));
-              }
-            };
+            while *self.suspended.borrow_and_update() {
+                if self.suspended.changed().await.is_err() {
+                    return Ok(());
+                }
+            }
+
+            let mut reader = crossterm::event::EventStream::new();
+            // Send results are ignored because shutting down the app drops the receiver, which causes the send
+            // operation to fail. This is expected behavior and should not panic.
+            loop {
+                let crossterm_event = reader.next().fuse();
+                tokio::select! {
+                  _ = self.sender.closed() => {
+                    return Ok(());
+                  }
+                  changed = self.suspended.changed() => {
+                    if changed.is_err() {
+                        return Ok(());
+                    }
+                    break;
+                  }
+                  Some(Ok(evt)) = crossterm_event => {
+                    let _ = self.sender.send(Event::Crossterm(evt));
+                  }
+                };
+            }
         }
-        Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processed(content: &str) -> ProcessedLine {
+        ProcessedLine {
+            line_content: content.to_string(),
+            passes_filter: true,
+            stream: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_take_new_lines() {
+        let events = EventHandler::new(StreamSource::None, Vec::new());
+        assert!(!events.has_new_lines());
+
+        events
+            .new_lines
+            .lock()
+            .unwrap()
+            .extend(["one", "two", "three"].map(processed));
+        let taken: Vec<String> = events
+            .take_new_lines(2)
+            .into_iter()
+            .map(|line| line.line_content)
+            .collect();
+        assert_eq!(taken, ["one", "two"]);
+        assert!(events.has_new_lines());
 
-    /// Sends an event to the receiver.
-    fn send(&self, event: Event) {
-        // Ignores the result because shutting down the app drops the receiver, which causes the send
-        // operation to fail. This is expected behavior and should not panic.
-        let _ = self.sender.send(event);
+        let taken: Vec<String> = events
+            .take_new_lines(2)
+            .into_iter()
+            .map(|line| line.line_content)
+            .collect();
+        assert_eq!(taken, ["three"]);
+        assert!(!events.has_new_lines());
     }
 }
@@ -0,0 +1,209 @@
+use std::collections::{HashMap, VecDeque};
+
+use chrono::TimeDelta;
+use regex::Regex;
+
+use crate::log::LogLine;
+use crate::log_event::LogEvent;
+
+/// Two events whose occurrences are paired to measure the time between them, like the start and end of a request.
+#[derive(Debug, Clone)]
+pub struct EventPairPattern {
+    /// Name of the pair, e.g. `request`.
+    pub name: String,
+    /// Name of the event starting a pair.
+    pub start: String,
+    /// Name of the event ending a pair.
+    pub end: String,
+    /// Regex with a capture group correlating a start with its end, like `request_id=(\w+)`.
+    key: Option<Regex>,
+    /// Pairs taking longer than this are slow.
+    pub threshold: Option<TimeDelta>,
+}
+
+impl EventPairPattern {
+    /// Creates a pair pattern. Returns `None` if the key regex has no capture group.
+    pub fn new(name: &str, start: &str, end: &str, key: Option<Regex>, threshold: Option<TimeDelta>) -> Option<Self> {
+        if key.as_ref().is_some_and(|key| key.captures_len() < 2) {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            start: start.to_string(),
+            end: end.to_string(),
+            key,
+            threshold,
+        })
+    }
+
+    /// Returns the key correlating the line with the other event of its pair, from the first participating
+    /// capture group.
+    fn key_of(&self, line: &str) -> Option<String> {
+        let captures = self.key.as_ref()?.captures(line)?;
+        captures
+            .iter()
+            .skip(1)
+            .flatten()
+            .next()
+            .map(|key| key.as_str().to_string())
+    }
+}
+
+/// A start event paired with its end event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventPair {
+    /// Name of the pair pattern.
+    pub name: String,
+    /// Log index of the line of the start event.
+    pub start: usize,
+    /// Log index of the line of the end event.
+    pub end: usize,
+    /// Time between the start and the end, if both lines have a timestamp.
+    pub duration: Option<TimeDelta>,
+    /// Whether the pair took longer than the threshold of its pattern.
+    pub slow: bool,
+}
+
+/// Lines the pairs were computed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PairedEvents {
+    generation: u64,
+    event_count: usize,
+}
+
+/// Durations between paired events, e.g. of requests from their start to their end line.
+///
+/// An end event is paired with the oldest unfinished start event with the same key.
+#[derive(Debug, Default)]
+pub struct EventPairs {
+    patterns: Vec<EventPairPattern>,
+    /// Pairs ordered by the line of their end event.
+    pairs: Vec<EventPair>,
+    paired: Option<PairedEvents>,
+}
+
+impl EventPairs {
+    pub fn new(patterns: Vec<EventPairPattern>) -> Self {
+        Self {
+            patterns,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Pairs the events again if the events or the lines changed since the last update.
+    pub fn update(&mut self, events: &[LogEvent], lines: &[LogLine], generation: u64) {
+        if self.patterns.is_empty() {
+            return;
+        }
+        let paired = PairedEvents {
+            generation,
+            event_count: events.len(),
+        };
+        if self.paired == Some(paired) {
+            return;
+        }
+        self.paired = Some(paired);
+
+        self.pairs.clear();
+        for pattern in &self.patterns {
+            let mut open: HashMap<Option<String>, VecDeque<usize>> = HashMap::new();
+            for event in events {
+                let is_start = event.name == pattern.start;
+                let is_end = event.name == pattern.end;
+                if !is_start && !is_end {
+                    continue;
+                }
+                let Some(line) = lines.get(event.line_index) else {
+                    continue;
+                };
+                let key = pattern.key_of(line.content());
+                if is_end && let Some(start) = open.get_mut(&key).and_then(VecDeque::pop_front) {
+                    let duration = lines
+                        .get(start)
+                        .and_then(|start_line| start_line.timestamp)
+                        .zip(line.timestamp)
+                        .map(|(start_time, end_time)| end_time - start_time);
+                    let slow = pattern
+                        .threshold
+                        .zip(duration)
+                        .is_some_and(|(threshold, duration)| duration > threshold);
+                    self.pairs.push(EventPair {
+                        name: pattern.name.clone(),
+                        start,
+                        end: event.line_index,
+                        duration,
+                        slow,
+                    });
+                } else if is_start {
+                    open.entry(key).or_default().push_back(event.line_index);
+                }
+            }
+        }
+        self.pairs.sort_by_key(|pair| pair.end);
+    }
+
+    /// Returns the pairs ordered by the line of their end event.
+    pub fn pairs(&self) -> &[EventPair] {
+        &self.pairs
+    }
+
+    /// Returns the pairs ended by the event at the given log index.
+    pub fn ending_at(&self, line_index: usize) -> impl Iterator<Item = &EventPair> {
+        let start = self.pairs.partition_point(|pair| pair.end < line_index);
+        self.pairs[start..]
+            .iter()
+            .take_while(move |pair| pair.end == line_index)
+    }
+
+    /// Returns the number of pairs taking longer than their threshold.
+    pub fn slow_count(&self) -> usize {
+        self.pairs.iter().filter(|pair| pair.slow).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timestamp::parse_timestamp;
+
+    #[test]
+    fn test_pairs_by_key_with_durations() {
+        let contents = [
+            "2024-01-15 10:00:00.000 start request_id=a",
+            "2024-01-15 10:00:00.100 start request_id=b",
+            "2024-01-15 10:00:00.200 end request_id=b",
+            "2024-01-15 10:00:02.000 end request_id=a",
+            "2024-01-15 10:00:03.000 end request_id=c",
+            "2024-01-15 10:00:04.000 start request_id=d",
+        ];
+        let mut lines = Vec::new();
+        let mut events = Vec::new();
+        for (index, content) in contents.into_iter().enumerate() {
+            let mut line = LogLine::new(content, index);
+            line.timestamp = parse_timestamp(content);
+            lines.push(line);
+            events.push(LogEvent {
+                name: content.split(' ').nth(2).unwrap().to_string(),
+                line_index: index,
+            });
+        }
+
+        let key = Regex::new(r"request_id=(\w+)").unwrap();
+        let pattern = EventPairPattern::new("request", "start", "end", Some(key), Some(TimeDelta::seconds(1))).unwrap();
+        let mut pairs = EventPairs::new(vec![pattern]);
+        pairs.update(&events, &lines, 1);
+
+        let found: Vec<(usize, usize, bool)> = pairs.pairs().iter().map(|p| (p.start, p.end, p.slow)).collect();
+        assert_eq!(found, vec![(1, 2, false), (0, 3, true)]);
+        assert_eq!(pairs.pairs()[0].duration, Some(TimeDelta::milliseconds(100)));
+        assert_eq!(pairs.ending_at(3).count(), 1);
+        assert_eq!(pairs.ending_at(4).count(), 0);
+        assert_eq!(pairs.slow_count(), 1);
+
+        assert!(EventPairPattern::new("request", "start", "end", Some(Regex::new("id").unwrap()), None).is_none());
+    }
+}
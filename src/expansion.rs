@@ -1,6 +1,9 @@
 use std::collections::{HashMap, hash_map::Entry};
 use std::sync::Arc;
 
+use crate::log::LogLine;
+use crate::resolver::VisibilityRule;
+
 /// Manages expansion expansion for filtered log lines.
 /// Tracks which LOG LINES (not viewport positions) have been expanded.
 #[derive(Debug)]
@@ -63,6 +66,23 @@ impl Expansions {
             .map(|(parent, _)| *parent)
     }
 
+    /// Removes expansions of the first `count` lines and shifts the rest after those lines were dropped.
+    pub fn drop_lines_before(&mut self, count: usize) {
+        if self.expanded.is_empty() {
+            return;
+        }
+        self.expanded = Arc::new(
+            self.expanded
+                .iter()
+                .filter(|(parent, _)| **parent >= count)
+                .map(|(parent, children)| {
+                    let children = children.iter().filter(|&&c| c >= count).map(|c| c - count).collect();
+                    (parent - count, children)
+                })
+                .collect(),
+        );
+    }
+
     /// Clears all expansions.
     pub fn clear(&mut self) {
         self.expanded = Arc::new(HashMap::new());
@@ -74,9 +94,38 @@ impl Expansions {
     }
 }
 
+/// Rule that folds multi-line records by hiding their continuation lines.
+pub struct FoldRecordsRule;
+
+impl VisibilityRule for FoldRecordsRule {
+    fn is_visible(&self, line: &LogLine) -> bool {
+        !line.continuation
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::log::LogBuffer;
+
+    #[test]
+    fn test_fold_records() {
+        let mut log_buffer = LogBuffer::default();
+        log_buffer.init_stdin_mode();
+        log_buffer.append_line("plain line without timestamp".to_string());
+        log_buffer.append_line("2024-01-01 12:00:00 ERROR request failed".to_string());
+        log_buffer.append_line("java.lang.IllegalStateException: boom".to_string());
+        log_buffer.append_line("    at com.example.Main.main(Main.java:12)".to_string());
+        log_buffer.append_line("2024-01-01 12:00:01 INFO recovered".to_string());
+
+        assert_eq!(log_buffer.record_range(0), 0..1);
+        assert_eq!(log_buffer.record_range(1), 1..4);
+        assert_eq!(log_buffer.record_range(3), 1..4);
+        assert_eq!(log_buffer.record_range(4), 4..5);
+
+        let visible: Vec<bool> = log_buffer.iter().map(|line| FoldRecordsRule.is_visible(line)).collect();
+        assert_eq!(visible, vec![true, true, false, false, true]);
+    }
 
     #[test]
     fn test_toggle_expansion() {
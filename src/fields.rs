@@ -0,0 +1,560 @@
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::log::LogLine;
+use crate::resolver::{SortKey, SortRule};
+use crate::utils::display_width;
+
+/// Maximum width of a column in the column view. Longer values are truncated, except in the last column.
+pub const MAX_COLUMN_WIDTH: usize = 40;
+
+/// How fields are extracted from log lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldFormat {
+    /// Named capture groups of a regex.
+    Regex,
+    /// `key=value` pairs.
+    Logfmt,
+    /// Keys of a JSON object.
+    Json,
+}
+
+#[derive(Debug, Clone)]
+enum Extractor {
+    Regex(Regex),
+    Logfmt,
+    Json,
+}
+
+/// Parses log lines into named fields.
+#[derive(Debug, Clone)]
+pub struct FieldExtractor {
+    extractor: Extractor,
+    /// Names of the extracted fields, in column order.
+    columns: Vec<String>,
+}
+
+impl FieldExtractor {
+    /// Creates an extractor using the named capture groups of the regex as columns.
+    ///
+    /// If `columns` is not empty, only those groups are extracted, in the given order.
+    /// Returns `None` if the regex is invalid or has no named groups.
+    pub fn regex(pattern: &str, columns: &[String]) -> Option<Self> {
+        let regex = Regex::new(pattern).ok()?;
+        let columns: Vec<String> = if columns.is_empty() {
+            regex.capture_names().flatten().map(str::to_string).collect()
+        } else {
+            columns.to_vec()
+        };
+        if columns.is_empty() {
+            return None;
+        }
+        Some(Self {
+            extractor: Extractor::Regex(regex),
+            columns,
+        })
+    }
+
+    /// Creates an extractor selecting the given keys of logfmt lines.
+    pub fn logfmt(columns: &[String]) -> Self {
+        Self {
+            extractor: Extractor::Logfmt,
+            columns: columns.to_vec(),
+        }
+    }
+
+    /// Creates an extractor selecting the given keys of JSON lines. Nested keys are separated by dots.
+    pub fn json(columns: &[String]) -> Self {
+        Self {
+            extractor: Extractor::Json,
+            columns: columns.to_vec(),
+        }
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Extracts the value of every column from the line. Missing fields are empty.
+    ///
+    /// Returns `None` if the line does not have the format at all, e.g. a stack trace line.
+    pub fn extract(&self, line: &str) -> Option<Vec<String>> {
+        match &self.extractor {
+            Extractor::Regex(regex) => {
+                let captures = regex.captures(line)?;
+                Some(
+                    self.columns
+                        .iter()
+                        .map(|name| captures.name(name).map_or("", |m| m.as_str()).to_string())
+                        .collect(),
+                )
+            }
+            Extractor::Logfmt => {
+                let pairs = parse_logfmt(line);
+                if pairs.is_empty() {
+                    return None;
+                }
+                Some(
+                    self.columns
+                        .iter()
+                        .map(|name| {
+                            pairs
+                                .iter()
+                                .find(|(key, _)| key == name)
+                                .map(|(_, value)| value.clone())
+                                .unwrap_or_default()
+                        })
+                        .collect(),
+                )
+            }
+            Extractor::Json => {
+                let object: Value = serde_json::from_str(&line[line.find('{')?..]).ok()?;
+                Some(
+                    self.columns
+                        .iter()
+                        .map(|name| {
+                            let pointer = format!("/{}", name.replace('.', "/"));
+                            match object.pointer(&pointer) {
+                                Some(Value::String(s)) => s.clone(),
+                                Some(Value::Null) | None => String::new(),
+                                Some(value) => value.to_string(),
+                            }
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+impl FieldExtractor {
+    /// Extracts every field of the line with its name: all named groups of a regex, or all keys of a
+    /// logfmt or JSON line rather than only the configured columns.
+    pub fn all_fields(&self, line: &str) -> Option<Vec<(String, String)>> {
+        match &self.extractor {
+            Extractor::Regex(_) => Some(self.columns.iter().cloned().zip(self.extract(line)?).collect()),
+            Extractor::Logfmt => Some(parse_logfmt(line)).filter(|pairs| !pairs.is_empty()),
+            Extractor::Json => parse_json_fields(line),
+        }
+    }
+}
+
+/// Detects the fields of a line without a configured format: the keys of a JSON object, or else
+/// the `key=value` pairs of a logfmt line.
+pub fn detect_fields(line: &str) -> Vec<(String, String)> {
+    parse_json_fields(line).unwrap_or_else(|| parse_logfmt(line))
+}
+
+/// Returns the value of a field of the line, parsed with the configured extractor or else detected
+/// from a JSON or logfmt line. Returns `None` if the line has no such field.
+pub fn field_value(line: &str, name: &str, extractor: Option<&FieldExtractor>) -> Option<String> {
+    let fields = match extractor {
+        Some(extractor) => extractor.all_fields(line)?,
+        None => detect_fields(line),
+    };
+    fields.into_iter().find(|(key, _)| key == name).map(|(_, value)| value)
+}
+
+/// Parses the JSON object in the line into its keys and values. Nested keys are separated by dots.
+fn parse_json_fields(line: &str) -> Option<Vec<(String, String)>> {
+    let object: Value = serde_json::from_str(&line[line.find('{')?..]).ok()?;
+    let mut fields = Vec::new();
+    flatten_json("", &object, &mut fields);
+    Some(fields)
+}
+
+fn flatten_json(prefix: &str, value: &Value, fields: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_json(&key, value, fields);
+            }
+        }
+        Value::String(s) => fields.push((prefix.to_string(), s.clone())),
+        Value::Null => fields.push((prefix.to_string(), String::new())),
+        value => fields.push((prefix.to_string(), value.to_string())),
+    }
+}
+
+/// Returns the keys of logfmt lines in the order they first appear, e.g. to show them as columns.
+pub fn logfmt_keys<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for (key, _) in lines.into_iter().flat_map(parse_logfmt) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// Parses the `key=value` pairs of a logfmt line. Values may be quoted with escaped quotes inside.
+fn parse_logfmt(line: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut rest = line.trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        rest = &rest[key_end..];
+
+        let mut value = String::new();
+        if let Some(after_eq) = rest.strip_prefix('=') {
+            if let Some(quoted) = after_eq.strip_prefix('"') {
+                let mut chars = quoted.char_indices();
+                let mut end = quoted.len();
+                while let Some((i, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            if let Some((_, escaped)) = chars.next() {
+                                value.push(escaped);
+                            }
+                        }
+                        '"' => {
+                            end = i + 1;
+                            break;
+                        }
+                        c => value.push(c),
+                    }
+                }
+                rest = &quoted[end..];
+            } else {
+                let value_end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                value.push_str(&after_eq[..value_end]);
+                rest = &after_eq[value_end..];
+            }
+            if !key.is_empty() {
+                pairs.push((key.to_string(), value));
+            }
+        }
+        rest = rest.trim_start();
+    }
+
+    pairs
+}
+
+/// A column of the column view.
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub name: String,
+    pub visible: bool,
+}
+
+/// Sorting of the log view by a column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnSort {
+    /// Index of the column.
+    pub column: usize,
+    pub descending: bool,
+}
+
+/// Tabular display of extracted fields, with columns that can be hidden and sorted.
+#[derive(Debug, Default)]
+pub struct ColumnView {
+    extractor: Option<FieldExtractor>,
+    columns: Vec<Column>,
+    /// Whether lines are shown as columns.
+    enabled: bool,
+    sort: Option<ColumnSort>,
+}
+
+impl ColumnView {
+    pub fn new(extractor: Option<FieldExtractor>) -> Self {
+        let columns = extractor
+            .as_ref()
+            .map(|extractor| {
+                extractor
+                    .columns()
+                    .iter()
+                    .map(|name| Column {
+                        name: name.clone(),
+                        visible: true,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            extractor,
+            columns,
+            enabled: false,
+            sort: None,
+        }
+    }
+
+    /// Returns true if a field extractor is configured.
+    pub fn is_available(&self) -> bool {
+        self.extractor.is_some()
+    }
+
+    pub fn extractor(&self) -> Option<&FieldExtractor> {
+        self.extractor.as_ref()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && self.extractor.is_some()
+    }
+
+    pub fn toggle_enabled(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn sort(&self) -> Option<ColumnSort> {
+        self.sort
+    }
+
+    /// Shows or hides a column.
+    pub fn toggle_column(&mut self, index: usize) {
+        if let Some(column) = self.columns.get_mut(index) {
+            column.visible = !column.visible;
+        }
+    }
+
+    /// Cycles sorting by a column through ascending, descending and unsorted.
+    pub fn cycle_sort(&mut self, index: usize) {
+        if index >= self.columns.len() {
+            return;
+        }
+        self.sort = match self.sort {
+            Some(sort) if sort.column == index && !sort.descending => Some(ColumnSort {
+                column: index,
+                descending: true,
+            }),
+            Some(sort) if sort.column == index => None,
+            _ => Some(ColumnSort {
+                column: index,
+                descending: false,
+            }),
+        };
+    }
+
+    /// Returns the rule sorting the visible lines, if the column view is enabled and sorted.
+    pub fn sort_rule(&self) -> Option<ColumnSortRule> {
+        let sort = self.sort.filter(|_| self.is_enabled())?;
+        Some(ColumnSortRule {
+            extractor: self.extractor.clone()?,
+            sort,
+        })
+    }
+
+    /// Extracts the values of the visible columns from the line.
+    pub fn visible_values(&self, line: &str) -> Option<Vec<String>> {
+        let values = self.extractor.as_ref()?.extract(line)?;
+        Some(
+            values
+                .into_iter()
+                .zip(&self.columns)
+                .filter(|(_, column)| column.visible)
+                .map(|(value, _)| value)
+                .collect(),
+        )
+    }
+}
+
+/// Aligns rows of column values into lines. Columns are as wide as their widest value, up to
+/// `MAX_COLUMN_WIDTH`, except the last column which is never truncated.
+pub fn align_columns(rows: &[Option<Vec<String>>]) -> Vec<Option<String>> {
+    let mut widths: Vec<usize> = Vec::new();
+    for values in rows.iter().flatten() {
+        if widths.len() < values.len() {
+            widths.resize(values.len(), 0);
+        }
+        for (width, value) in widths.iter_mut().zip(values) {
+            *width = (*width).max(display_width(value)).min(MAX_COLUMN_WIDTH);
+        }
+    }
+
+    rows.iter()
+        .map(|values| {
+            let values = values.as_ref()?;
+            let last = values.len().saturating_sub(1);
+            let cells: Vec<String> = values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    let value_width = display_width(value);
+                    if i == last {
+                        value.clone()
+                    } else if value_width > widths[i] {
+                        truncate(value, widths[i])
+                    } else {
+                        format!("{}{}", value, " ".repeat(widths[i] - value_width))
+                    }
+                })
+                .collect();
+            Some(cells.join(" │ "))
+        })
+        .collect()
+}
+
+/// Truncates the value to the given number of columns, ending it with an ellipsis.
+fn truncate(value: &str, width: usize) -> String {
+    let mut truncated = String::new();
+    let mut truncated_width = 0;
+    for ch in value.chars() {
+        let ch_width = display_width(ch.encode_utf8(&mut [0; 4]));
+        if truncated_width + ch_width + 1 > width {
+            break;
+        }
+        truncated.push(ch);
+        truncated_width += ch_width;
+    }
+    truncated.push('…');
+    truncated.push_str(&" ".repeat(width - truncated_width - 1));
+    truncated
+}
+
+/// Sorts visible lines by the value of a column.
+pub struct ColumnSortRule {
+    extractor: FieldExtractor,
+    sort: ColumnSort,
+}
+
+impl SortRule for ColumnSortRule {
+    fn sort_key(&self, line: &LogLine) -> SortKey {
+        let Some(mut values) = self.extractor.extract(line.content()) else {
+            return SortKey::Missing;
+        };
+        let value = values.swap_remove(self.sort.column);
+        if value.is_empty() {
+            SortKey::Missing
+        } else if let Ok(number) = value.parse::<f64>() {
+            SortKey::Number(number)
+        } else {
+            SortKey::Text(value)
+        }
+    }
+
+    fn is_descending(&self) -> bool {
+        self.sort.descending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_regex_extractor() {
+        let extractor = FieldExtractor::regex(r"^(?P<time>\S+) (?P<level>\w+) (?P<msg>.*)$", &[]).unwrap();
+        assert_eq!(extractor.columns(), names(&["time", "level", "msg"]));
+        assert_eq!(
+            extractor.extract("12:00:01 INFO server started"),
+            Some(names(&["12:00:01", "INFO", "server started"]))
+        );
+        assert_eq!(extractor.extract("  at main.rs:12"), None);
+        assert!(FieldExtractor::regex(r"(\d+)", &[]).is_none());
+    }
+
+    #[test]
+    fn test_logfmt_extractor() {
+        let extractor = FieldExtractor::logfmt(&names(&["level", "msg", "user"]));
+        assert_eq!(
+            extractor.extract(r#"ts=1 level=warn msg="disk \"sda\" full" flag"#),
+            Some(names(&["warn", r#"disk "sda" full"#, ""]))
+        );
+        assert_eq!(extractor.extract(""), None);
+    }
+
+    #[test]
+    fn test_json_extractor() {
+        let extractor = FieldExtractor::json(&names(&["level", "req.status", "msg"]));
+        assert_eq!(
+            extractor.extract(r#"prefix {"level":"info","req":{"status":404},"msg":null}"#),
+            Some(names(&["info", "404", ""]))
+        );
+        assert_eq!(extractor.extract("not json"), None);
+    }
+
+    #[test]
+    fn test_align_columns() {
+        let rows = vec![
+            Some(names(&["INFO", "a", "first"])),
+            None,
+            Some(names(&["ERROR", &"x".repeat(50), "second"])),
+        ];
+        let aligned = align_columns(&rows);
+        assert_eq!(
+            aligned[0].as_deref(),
+            Some(format!("INFO  │ a{} │ first", " ".repeat(39)).as_str())
+        );
+        assert_eq!(aligned[1], None);
+        assert_eq!(
+            aligned[2].as_deref(),
+            Some(format!("ERROR │ {}… │ second", "x".repeat(39)).as_str())
+        );
+    }
+
+    #[test]
+    fn test_cycle_sort_and_sort_key() {
+        let extractor = FieldExtractor::logfmt(&names(&["n"]));
+        let mut view = ColumnView::new(Some(extractor));
+        view.toggle_enabled();
+        view.cycle_sort(0);
+        assert_eq!(
+            view.sort(),
+            Some(ColumnSort {
+                column: 0,
+                descending: false
+            })
+        );
+        view.cycle_sort(0);
+        assert!(view.sort_rule().unwrap().is_descending());
+        view.cycle_sort(0);
+        assert_eq!(view.sort(), None);
+
+        view.cycle_sort(0);
+        let rule = view.sort_rule().unwrap();
+        assert_eq!(rule.sort_key(&LogLine::new("n=10", 0)), SortKey::Number(10.0));
+        assert_eq!(
+            rule.sort_key(&LogLine::new("n=abc", 0)),
+            SortKey::Text("abc".to_string())
+        );
+        assert_eq!(rule.sort_key(&LogLine::new("other=1", 0)), SortKey::Missing);
+    }
+
+    #[test]
+    fn test_detect_fields() {
+        assert_eq!(
+            detect_fields(r#"12:00:01 {"level":"info","req":{"id":7,"path":"/a"},"user":null}"#),
+            vec![
+                ("level".to_string(), "info".to_string()),
+                ("req.id".to_string(), "7".to_string()),
+                ("req.path".to_string(), "/a".to_string()),
+                ("user".to_string(), String::new()),
+            ]
+        );
+        assert_eq!(
+            detect_fields(r#"level=warn msg="disk full" free=0"#),
+            vec![
+                ("level".to_string(), "warn".to_string()),
+                ("msg".to_string(), "disk full".to_string()),
+                ("free".to_string(), "0".to_string()),
+            ]
+        );
+        assert!(detect_fields("plain text line").is_empty());
+
+        let extractor = FieldExtractor::regex(r"^(?<time>\S+) (?<level>\S+)", &[]).unwrap();
+        assert_eq!(
+            extractor.all_fields("12:00 INFO started"),
+            Some(vec![
+                ("time".to_string(), "12:00".to_string()),
+                ("level".to_string(), "INFO".to_string()),
+            ])
+        );
+        assert_eq!(extractor.all_fields("?"), None);
+    }
+}
@@ -68,6 +68,11 @@ impl FileManager {
         self.files.iter().map(|f| f.path.as_str()).collect()
     }
 
+    /// Returns the file names by file id, e.g. to match the files filters are scoped to.
+    pub fn file_names(&self) -> Vec<String> {
+        self.files.iter().map(|f| f.get_filename().to_string()).collect()
+    }
+
     /// Returns the first file path (typically used in single-file mode).
     pub fn first_path(&self) -> Option<&str> {
         self.files.first().map(|f| f.path.as_str())
@@ -1,13 +1,19 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use crate::fields::{FieldExtractor, field_value};
 use crate::log::LogLine;
-use crate::utils::contains_ignore_case;
+use crate::utils::{CaseFolding, contains_ignore_case, contains_whole_word, is_whole_word};
 use crate::{history::History, resolver::VisibilityRule};
+use rayon::prelude::*;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 
+/// Prefix of field filters, e.g. `@level:ERROR`. Without it `error:timeout` is matched as plain text.
+pub const FIELD_FILTER_PREFIX: char = '@';
+
 /// Filter mode - include or exclude matching lines.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum ActiveFilterMode {
     /// Include only lines matching the pattern.
     #[default]
@@ -22,6 +28,8 @@ pub struct FilterHistoryEntry {
     pub pattern: String,
     pub mode: ActiveFilterMode,
     pub case_sensitive: bool,
+    #[serde(default)]
+    pub whole_word: bool,
 }
 
 /// A single filter pattern.
@@ -35,27 +43,191 @@ pub struct FilterPattern {
     pub case_sensitive: bool,
     /// Whether this pattern is currently active.
     pub enabled: bool,
+    /// Whether only occurrences of the pattern that are whole words match.
+    pub whole_word: bool,
+    /// Compiled regex if this is a regex filter, otherwise the pattern is matched as plain text.
+    pub regex: Option<Regex>,
+    /// Field and value of an `@field:value` filter, matched against the parsed fields of a line.
+    pub field: Option<FieldPattern>,
+    /// Name of the file the filter applies to, e.g. `api.log`. Without a file it applies to the lines of all files.
+    pub file: Option<String>,
 }
 
 impl FilterPattern {
-    /// Creates a new filter pattern.
+    /// Creates a new filter pattern, matching a field for an `@field:value` pattern.
     pub fn new(pattern: String, mode: ActiveFilterMode, case_sensitive: bool, enabled: bool) -> Self {
         Self {
+            field: FieldPattern::parse(&pattern),
+            ..Self::new_text(pattern, mode, case_sensitive, enabled)
+        }
+    }
+
+    /// Creates a new filter pattern matched as plain text, even if it looks like `@field:value`.
+    pub fn new_text(pattern: String, mode: ActiveFilterMode, case_sensitive: bool, enabled: bool) -> Self {
+        Self {
+            mode,
+            case_sensitive,
+            enabled,
+            whole_word: false,
+            field: None,
+            regex: None,
+            file: None,
+            pattern,
+        }
+    }
+
+    /// Creates a new regex filter pattern. Returns None if the regex is invalid.
+    pub fn new_regex(pattern: String, mode: ActiveFilterMode, case_sensitive: bool, enabled: bool) -> Option<Self> {
+        let regex = compile_regex(&pattern, case_sensitive)?;
+        Some(Self {
             pattern,
             mode,
             case_sensitive,
             enabled,
+            whole_word: false,
+            regex: Some(regex),
+            field: None,
+            file: None,
+        })
+    }
+
+    /// Returns the pattern matching only whole words, or anywhere.
+    pub fn with_whole_word(mut self, whole_word: bool) -> Self {
+        self.whole_word = whole_word;
+        self
+    }
+
+    /// Scopes the filter to the lines of the file with the given name.
+    pub fn with_file(mut self, file: Option<String>) -> Self {
+        self.file = file;
+        self
+    }
+
+    /// Returns whether the filter applies to the lines of the file with the given name, or of no file.
+    pub fn applies_to(&self, file: Option<&str>) -> bool {
+        self.file.as_deref().is_none_or(|scope| file == Some(scope))
+    }
+
+    /// Returns whether this is a regex filter.
+    pub fn is_regex(&self) -> bool {
+        self.regex.is_some()
+    }
+
+    /// Returns whether both patterns match the same lines, whether or not they are enabled.
+    pub fn same_filter(&self, other: &FilterPattern) -> bool {
+        self.pattern == other.pattern
+            && self.mode == other.mode
+            && self.case_sensitive == other.case_sensitive
+            && self.whole_word == other.whole_word
+            && self.is_regex() == other.is_regex()
+            && self.file == other.file
+    }
+
+    /// Checks if the content matches this pattern. An `@field:value` pattern is matched against the
+    /// field parsed with the extractor, or as the plain text `field:value` if the line has no such field.
+    pub fn is_match(&self, content: &str, extractor: Option<&FieldExtractor>, folding: CaseFolding) -> bool {
+        let mut text = self.pattern.as_str();
+        if let Some(field) = &self.field {
+            if let Some(value) = field_value(content, &field.name, extractor) {
+                return field.matches(&value, self.case_sensitive, folding);
+            }
+            text = text.strip_prefix(FIELD_FILTER_PREFIX).unwrap_or(text);
+        }
+
+        if let Some(regex) = &self.regex {
+            if self.whole_word {
+                regex
+                    .find_iter(content)
+                    .any(|m| is_whole_word(content, m.start(), m.end()))
+            } else {
+                regex.is_match(content)
+            }
+        } else if self.whole_word {
+            contains_whole_word(content, text, self.case_sensitive, folding)
+        } else if self.case_sensitive {
+            content.contains(text)
+        } else {
+            contains_ignore_case(content, text, folding)
         }
     }
 }
 
+/// The field and value of an `@field:value` filter, e.g. `@level:ERROR` or `@module:auth*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPattern {
+    pub name: String,
+    /// Expected value, where `*` matches any number of characters.
+    pub value: String,
+}
+
+impl FieldPattern {
+    /// Parses an `@field:value` pattern. The field name starts with a letter or `_` and consists of
+    /// letters, digits, `_`, `-` and `.` for nested JSON keys, which keeps times like `12:30` plain.
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let (name, value) = pattern.strip_prefix(FIELD_FILTER_PREFIX)?.split_once(':')?;
+        let mut chars = name.chars();
+        let valid_name = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        if !valid_name || value.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Checks if a field value matches the expected value, with `*` as wildcard.
+    pub fn matches(&self, value: &str, case_sensitive: bool, folding: CaseFolding) -> bool {
+        if case_sensitive {
+            wildcard_match(value, &self.value)
+        } else if folding == CaseFolding::Ascii {
+            wildcard_match(&value.to_ascii_lowercase(), &self.value.to_ascii_lowercase())
+        } else {
+            wildcard_match(&value.to_lowercase(), &self.value.to_lowercase())
+        }
+    }
+}
+
+/// Matches the whole text against a pattern where `*` matches any number of characters.
+fn wildcard_match(text: &str, pattern: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Compiles a filter regex with the given case sensitivity.
+fn compile_regex(pattern: &str, case_sensitive: bool) -> Option<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .ok()
+}
+
 /// Manages filter patterns.
 #[derive(Debug, Default)]
 pub struct Filter {
     patterns: Vec<FilterPattern>,
     filter_mode: ActiveFilterMode,
     case_sensitive: bool,
+    /// Whether new filters only match whole words. Kept between filters.
+    whole_word: bool,
     pub history: History<FilterHistoryEntry>,
+    /// How the case of characters is folded by filters matching case-insensitively.
+    case_folding: CaseFolding,
 }
 
 const DEFAULT_CASE_SENSITIVITY: bool = false;
@@ -67,7 +239,9 @@ impl Filter {
             patterns,
             filter_mode: ActiveFilterMode::default(),
             case_sensitive: DEFAULT_CASE_SENSITIVITY,
+            whole_word: false,
             history: History::new(),
+            case_folding: CaseFolding::default(),
         }
     }
 }
@@ -116,29 +290,47 @@ impl Filter {
         self.case_sensitive = DEFAULT_CASE_SENSITIVITY;
     }
 
+    /// Returns whether new filters only match whole words.
+    pub fn is_whole_word(&self) -> bool {
+        self.whole_word
+    }
+
+    /// Toggles whether new filters only match whole words.
+    pub fn toggle_whole_word(&mut self) {
+        self.whole_word = !self.whole_word;
+    }
+
+    /// Sets whether new filters only match whole words.
+    pub fn set_whole_word(&mut self, whole_word: bool) {
+        self.whole_word = whole_word;
+    }
+
     /// Adds a new filter pattern if it doesn't already exist.
     pub fn add_filter_from_pattern(&mut self, pattern: &str) {
-        if !pattern.is_empty() && !self.pattern_exists(pattern, self.filter_mode) {
-            let new_filter = FilterPattern::new(pattern.to_string(), self.filter_mode, self.case_sensitive, true);
+        if !pattern.is_empty() && !self.pattern_exists(pattern, self.filter_mode, self.whole_word, None) {
+            let new_filter = FilterPattern::new(pattern.to_string(), self.filter_mode, self.case_sensitive, true)
+                .with_whole_word(self.whole_word);
             self.patterns.push(new_filter);
 
             self.history.add(FilterHistoryEntry {
                 pattern: pattern.to_string(),
                 mode: self.filter_mode,
                 case_sensitive: self.case_sensitive,
+                whole_word: self.whole_word,
             });
         }
     }
 
     /// Add a FilterPattern
     pub fn add_filter(&mut self, filter: &FilterPattern) {
-        if !self.pattern_exists(&filter.pattern, filter.mode) {
+        if !self.pattern_exists(&filter.pattern, filter.mode, filter.whole_word, filter.file.as_deref()) {
             self.patterns.push(filter.clone());
 
             self.history.add(FilterHistoryEntry {
                 pattern: filter.pattern.clone(),
                 mode: filter.mode,
                 case_sensitive: filter.case_sensitive,
+                whole_word: filter.whole_word,
             });
         }
     }
@@ -153,6 +345,11 @@ impl Filter {
         self.patterns.len()
     }
 
+    /// Returns the number of enabled filter patterns.
+    pub fn enabled_count(&self) -> usize {
+        self.patterns.iter().filter(|pattern| pattern.enabled).count()
+    }
+
     /// Returns the pattern at the given index, if any.
     pub fn get_pattern(&self, index: usize) -> Option<&FilterPattern> {
         self.patterns.get(index)
@@ -195,6 +392,16 @@ impl Filter {
     pub fn toggle_pattern_case_sensitivity(&mut self, index: usize) {
         if let Some(pattern) = self.patterns.get_mut(index) {
             pattern.case_sensitive = !pattern.case_sensitive;
+            if pattern.is_regex() {
+                pattern.regex = compile_regex(&pattern.pattern, pattern.case_sensitive);
+            }
+        }
+    }
+
+    /// Toggles whole word matching for the pattern at the given index.
+    pub fn toggle_pattern_whole_word(&mut self, index: usize) {
+        if let Some(pattern) = self.patterns.get_mut(index) {
+            pattern.whole_word = !pattern.whole_word;
         }
     }
 
@@ -208,39 +415,64 @@ impl Filter {
         }
     }
 
+    /// Scopes the pattern at the given index to the lines of a file, or to all files if None.
+    pub fn set_pattern_file(&mut self, index: usize, file: Option<String>) {
+        if let Some(pattern) = self.patterns.get_mut(index) {
+            pattern.file = file;
+        }
+    }
+
     /// Updates the pattern text at the given index.
+    /// Returns false if the pattern would be a duplicate or is an invalid regex for a regex filter.
     pub fn update_pattern(&mut self, index: usize, new_pattern: &str) -> bool {
         if let Some(pattern) = self.patterns.get(index) {
-            let selected_mode = pattern.mode;
-            let duplicate_exists = self
-                .patterns
-                .iter()
-                .enumerate()
-                .any(|(idx, fp)| idx != index && fp.pattern == new_pattern && fp.mode == selected_mode);
-
-            if !duplicate_exists {
-                if let Some(pattern) = self.patterns.get_mut(index) {
-                    pattern.pattern = new_pattern.to_string();
+            let duplicate_exists = self.patterns.iter().enumerate().any(|(idx, fp)| {
+                idx != index && fp.pattern == new_pattern && fp.mode == pattern.mode && fp.file == pattern.file
+            });
+
+            if !duplicate_exists && let Some(pattern) = self.patterns.get_mut(index) {
+                if pattern.is_regex() {
+                    let Some(regex) = compile_regex(new_pattern, pattern.case_sensitive) else {
+                        return false;
+                    };
+                    pattern.regex = Some(regex);
+                } else {
+                    pattern.field = FieldPattern::parse(new_pattern);
                 }
+                pattern.pattern = new_pattern.to_string();
                 return true;
             }
         }
         false
     }
 
-    /// Checks if a pattern exists with the given mode.
-    fn pattern_exists(&self, pattern: &str, mode: ActiveFilterMode) -> bool {
-        self.patterns.iter().any(|fp| fp.pattern == pattern && fp.mode == mode)
+    /// Checks if a pattern exists with the given mode, whole word matching and file.
+    fn pattern_exists(&self, pattern: &str, mode: ActiveFilterMode, whole_word: bool, file: Option<&str>) -> bool {
+        self.patterns.iter().any(|fp| {
+            fp.pattern == pattern && fp.mode == mode && fp.whole_word == whole_word && fp.file.as_deref() == file
+        })
     }
 
-    /// Checks if content passes the filter patterns.
+    /// Checks if content passes the filter patterns, detecting the fields of `@field:value` filters.
     pub fn apply_filters(&self, content: &str) -> bool {
-        apply_filters(content, &self.patterns)
+        apply_filters(content, None, &self.patterns, None, self.case_folding)
+    }
+
+    /// Returns the filter folding the case of characters this way when matching case-insensitively.
+    pub fn with_case_folding(mut self, case_folding: CaseFolding) -> Self {
+        self.case_folding = case_folding;
+        self
     }
 }
 
-/// Checks if content passes the given filter patterns.
-pub fn apply_filters(content: &str, filter_patterns: &[FilterPattern]) -> bool {
+/// Checks if content from the file with the given name passes the filter patterns applying to that file.
+pub fn apply_filters(
+    content: &str,
+    file: Option<&str>,
+    filter_patterns: &[FilterPattern],
+    extractor: Option<&FieldExtractor>,
+    folding: CaseFolding,
+) -> bool {
     if filter_patterns.is_empty() {
         return true;
     }
@@ -248,12 +480,8 @@ pub fn apply_filters(content: &str, filter_patterns: &[FilterPattern]) -> bool {
     let mut has_include_filters = false;
     let mut include_matched = false;
 
-    for filter in filter_patterns.iter().filter(|f| f.enabled) {
-        let matches = if filter.case_sensitive {
-            content.contains(&filter.pattern)
-        } else {
-            contains_ignore_case(content, &filter.pattern)
-        };
+    for filter in filter_patterns.iter().filter(|f| f.enabled && f.applies_to(file)) {
+        let matches = filter.is_match(content, extractor, folding);
 
         match filter.mode {
             ActiveFilterMode::Exclude => {
@@ -273,10 +501,51 @@ pub fn apply_filters(content: &str, filter_patterns: &[FilterPattern]) -> bool {
     if has_include_filters { include_matched } else { true }
 }
 
+/// Returns the indices of the enabled filter patterns matching none of the lines they apply to, like filters kept
+/// from investigating another file. `file_names` are the names of the opened files by file id.
+pub fn unmatched_patterns(
+    filter_patterns: &[FilterPattern],
+    lines: &[LogLine],
+    file_names: &[String],
+    extractor: Option<&FieldExtractor>,
+    folding: CaseFolding,
+) -> Vec<usize> {
+    filter_patterns
+        .iter()
+        .enumerate()
+        .filter(|(_, filter)| filter.enabled)
+        .filter(|(_, filter)| {
+            !lines.par_iter().any(|line| {
+                let file = line.log_file_id.and_then(|id| file_names.get(id)).map(String::as_str);
+                filter.applies_to(file) && filter.is_match(line.content(), extractor, folding)
+            })
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Parses a `--filter` command line argument. A leading `!` makes it an exclude filter.
+///
+/// Like filters added in the viewer, the pattern is matched case-insensitively as plain text or as `@field:value`.
+pub fn parse_filter_arg(arg: &str) -> Option<FilterPattern> {
+    let (pattern, mode) = match arg.strip_prefix('!') {
+        Some(pattern) => (pattern, ActiveFilterMode::Exclude),
+        None => (arg, ActiveFilterMode::Include),
+    };
+    if pattern.is_empty() {
+        return None;
+    }
+    Some(FilterPattern::new(pattern.to_string(), mode, false, true))
+}
+
 /// Rule that applies text filtering
 pub struct FilterRule {
     patterns: Arc<Vec<FilterPattern>>,
     always_visible: Arc<HashSet<usize>>,
+    extractor: Option<FieldExtractor>,
+    /// Names of the opened files by file id, matched against the files filters are scoped to.
+    file_names: Vec<String>,
+    case_folding: CaseFolding,
 }
 
 impl FilterRule {
@@ -284,8 +553,30 @@ impl FilterRule {
         Self {
             patterns,
             always_visible,
+            extractor: None,
+            file_names: Vec::new(),
+            case_folding: CaseFolding::default(),
         }
     }
+
+    /// Returns the rule matching `@field:value` filters against the fields parsed by the extractor.
+    pub fn with_extractor(mut self, extractor: Option<&FieldExtractor>) -> Self {
+        self.extractor = extractor.cloned();
+        self
+    }
+
+    /// Returns the rule applying filters scoped to a file only to the lines of the file, given the names of the
+    /// opened files by file id.
+    pub fn with_file_names(mut self, file_names: Vec<String>) -> Self {
+        self.file_names = file_names;
+        self
+    }
+
+    /// Returns the rule folding the case of characters this way when matching case-insensitively.
+    pub fn with_case_folding(mut self, case_folding: CaseFolding) -> Self {
+        self.case_folding = case_folding;
+        self
+    }
 }
 
 impl VisibilityRule for FilterRule {
@@ -296,7 +587,17 @@ impl VisibilityRule for FilterRule {
         if self.patterns.is_empty() {
             true
         } else {
-            apply_filters(line.content(), &self.patterns)
+            let file = line
+                .log_file_id
+                .and_then(|id| self.file_names.get(id))
+                .map(String::as_str);
+            apply_filters(
+                line.content(),
+                file,
+                &self.patterns,
+                self.extractor.as_ref(),
+                self.case_folding,
+            )
         }
     }
 }
@@ -382,4 +683,194 @@ mod tests {
         assert_eq!(filter.get_filter_patterns()[1].pattern, "ERROR");
         assert_eq!(filter.get_filter_patterns()[1].mode, ActiveFilterMode::Exclude);
     }
+
+    #[test]
+    fn test_regex_filter() {
+        let patterns =
+            vec![FilterPattern::new_regex(r"id=\d+".to_string(), ActiveFilterMode::Include, false, true).unwrap()];
+        assert!(apply_filters(
+            "request ID=42 done",
+            None,
+            &patterns,
+            None,
+            CaseFolding::default()
+        ));
+        assert!(!apply_filters(
+            "request id=abc done",
+            None,
+            &patterns,
+            None,
+            CaseFolding::default()
+        ));
+    }
+
+    #[test]
+    fn test_file_scoped_filter() {
+        let exclude = FilterPattern::new("healthcheck".to_string(), ActiveFilterMode::Exclude, false, true)
+            .with_file(Some("api.log".to_string()));
+        let rule = FilterRule::new(Arc::new(vec![exclude.clone()]), Arc::new(HashSet::new()))
+            .with_file_names(vec!["api.log".to_string(), "web.log".to_string()]);
+        let mut line = LogLine::new("GET /healthcheck 200", 0);
+        line.log_file_id = Some(0);
+        assert!(!rule.is_visible(&line));
+        line.log_file_id = Some(1);
+        assert!(rule.is_visible(&line));
+
+        // The same pattern can be added once per file
+        let mut filter = Filter::default();
+        filter.add_filter(&exclude);
+        filter.add_filter(&exclude.clone().with_file(Some("web.log".to_string())));
+        filter.add_filter(&exclude);
+        assert_eq!(filter.count(), 2);
+    }
+
+    #[test]
+    fn test_whole_word_filter() {
+        let mut filter = Filter::default();
+        filter.toggle_whole_word();
+        filter.add_filter_from_pattern("err");
+        assert!(filter.get_filter_patterns()[0].whole_word);
+        assert!(filter.history.get_history()[0].whole_word);
+        assert!(filter.apply_filters("ERR: disk full"));
+        assert!(!filter.apply_filters("transferred 3 files"));
+
+        filter.toggle_pattern_whole_word(0);
+        assert!(filter.apply_filters("transferred 3 files"));
+        // The same pattern matching whole words only is another filter
+        filter.add_filter_from_pattern("err");
+        assert_eq!(filter.count(), 2);
+        filter.add_filter_from_pattern("err");
+        assert_eq!(filter.count(), 2);
+
+        let regex = FilterPattern::new_regex(r"err\w*".to_string(), ActiveFilterMode::Include, true, true)
+            .unwrap()
+            .with_whole_word(true);
+        assert!(regex.is_match("an error", None, CaseFolding::default()));
+        assert!(!regex.is_match("transferred", None, CaseFolding::default()));
+    }
+
+    #[test]
+    fn test_update_regex_pattern_rejects_invalid_regex() {
+        let mut filter = Filter::default();
+        filter.add_filter(
+            &FilterPattern::new_regex("err(or)?".to_string(), ActiveFilterMode::Include, false, true).unwrap(),
+        );
+        assert!(!filter.update_pattern(0, "err(or"));
+        assert_eq!(filter.get_filter_patterns()[0].pattern, "err(or)?");
+        assert!(filter.update_pattern(0, "warn(ing)?"));
+        assert!(filter.get_filter_patterns()[0].is_match("WARNING", None, CaseFolding::default()));
+    }
+
+    #[test]
+    fn test_same_filter() {
+        let filter = FilterPattern::new("error".to_string(), ActiveFilterMode::Include, false, true);
+        assert!(filter.same_filter(&FilterPattern::new(
+            "error".to_string(),
+            ActiveFilterMode::Include,
+            false,
+            false
+        )));
+        assert!(!filter.same_filter(&FilterPattern::new(
+            "error".to_string(),
+            ActiveFilterMode::Include,
+            true,
+            true
+        )));
+        assert!(!filter.same_filter(&filter.clone().with_whole_word(true)));
+        assert!(!filter.same_filter(
+            &FilterPattern::new_regex("error".to_string(), ActiveFilterMode::Include, false, true).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_field_filter() {
+        assert_eq!(
+            FieldPattern::parse("@module:auth*"),
+            Some(FieldPattern {
+                name: "module".to_string(),
+                value: "auth*".to_string(),
+            })
+        );
+        assert_eq!(FieldPattern::parse("module:auth*"), None);
+        assert_eq!(FieldPattern::parse("@12:30"), None);
+        assert_eq!(FieldPattern::parse("@level:"), None);
+
+        let mut filter = Filter::default();
+        filter.add_filter_from_pattern("@level:ERROR");
+        assert!(filter.apply_filters("ts=1 level=error msg=failed"));
+        assert!(!filter.apply_filters("ts=1 level=info msg=ERROR"));
+        assert!(filter.apply_filters(r#"{"level":"ERROR","msg":"failed"}"#));
+        // Lines without the field are matched as plain text
+        assert!(filter.apply_filters("plain line with level:ERROR"));
+        assert!(!filter.apply_filters("ERROR plain line"));
+
+        let module = FilterPattern::new("@module:auth*".to_string(), ActiveFilterMode::Include, false, true);
+        assert!(module.is_match("module=authz msg=ok", None, CaseFolding::default()));
+        assert!(!module.is_match("module=oauth msg=ok", None, CaseFolding::default()));
+
+        let extractor = FieldExtractor::regex(r"^(?P<level>\w+) (?P<module>\w+):", &[]).unwrap();
+        assert!(module.is_match("INFO auth: login", Some(&extractor), CaseFolding::default()));
+        assert!(!module.is_match("INFO db: auth query", Some(&extractor), CaseFolding::default()));
+
+        let wildcard = FieldPattern::parse("@msg:*time*out").unwrap();
+        assert!(wildcard.matches("connection timed out", false, CaseFolding::default()));
+        assert!(wildcard.matches("Timeout", false, CaseFolding::default()));
+        assert!(!wildcard.matches("time spent", false, CaseFolding::default()));
+        assert!(wildcard.matches("a TIME, then out", false, CaseFolding::default()));
+        assert!(!wildcard.matches("a TIME, then out", true, CaseFolding::default()));
+    }
+
+    #[test]
+    fn test_word_colon_filter_is_plain_text() {
+        let filter = FilterPattern::new("error:timeout".to_string(), ActiveFilterMode::Include, false, true);
+        assert!(filter.field.is_none());
+        // The line's `error` field doesn't hide the text matching the pattern
+        let line = r#"{"error":"refused","msg":"retry after error:timeout"}"#;
+        assert!(filter.is_match(line, None, CaseFolding::default()));
+        assert!(!filter.is_match(r#"{"error":"timeout"}"#, None, CaseFolding::default()));
+
+        let text = FilterPattern::new_text("@level:ERROR".to_string(), ActiveFilterMode::Include, false, true);
+        assert!(text.field.is_none());
+        assert!(!text.is_match(r#"{"level":"ERROR"}"#, None, CaseFolding::default()));
+    }
+
+    #[test]
+    fn test_parse_filter_arg() {
+        let include = parse_filter_arg("ERROR").unwrap();
+        assert_eq!(include.pattern, "ERROR");
+        assert_eq!(include.mode, ActiveFilterMode::Include);
+        assert!(!include.case_sensitive);
+
+        let exclude = parse_filter_arg("!healthcheck").unwrap();
+        assert_eq!(exclude.pattern, "healthcheck");
+        assert_eq!(exclude.mode, ActiveFilterMode::Exclude);
+
+        assert!(parse_filter_arg("!").is_none());
+    }
+
+    #[test]
+    fn test_unmatched_patterns() {
+        let patterns = vec![
+            FilterPattern::new("ERROR".to_string(), ActiveFilterMode::Include, false, true),
+            FilterPattern::new("order_id=42".to_string(), ActiveFilterMode::Include, false, true),
+            FilterPattern::new("healthcheck".to_string(), ActiveFilterMode::Exclude, false, true),
+            FilterPattern::new("timeout".to_string(), ActiveFilterMode::Include, false, false),
+            FilterPattern::new("ERROR".to_string(), ActiveFilterMode::Include, false, true)
+                .with_file(Some("web.log".to_string())),
+        ];
+        let mut lines = vec![
+            LogLine::new("ERROR connection refused", 0),
+            LogLine::new("GET /healthcheck 200", 1),
+        ];
+        for line in &mut lines {
+            line.log_file_id = Some(0);
+        }
+        let file_names = vec!["api.log".to_string(), "web.log".to_string()];
+
+        // Disabled filters aren't reported, and filters scoped to a file only match its lines
+        assert_eq!(
+            unmatched_patterns(&patterns, &lines, &file_names, None, CaseFolding::default()),
+            vec![1, 4]
+        );
+    }
 }
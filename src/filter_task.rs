@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::filter::{FilterPattern, FilterRule};
+use crate::log::LogLine;
+use crate::resolver::VisibilityRule;
+use crate::task::TaskProgress;
+use rayon::prelude::*;
+
+/// Buffers with at least this many lines are filtered in the background.
+pub const BACKGROUND_FILTER_MIN_LINES: usize = 500_000;
+
+/// Number of lines filtered between progress updates and cancellation checks.
+const CHUNK_SIZE: usize = 65_536;
+
+/// Visibility of every log line, computed by a [`FilterTask`].
+#[derive(Debug, Clone)]
+pub struct FilterMask {
+    /// Key of the filter inputs the mask was computed for.
+    pub key: u64,
+    /// Whether each log line passes the filters, indexed by log line index.
+    pub visible: Arc<Vec<bool>>,
+    /// How long computing the mask took.
+    pub elapsed: Duration,
+}
+
+/// Filter running on a background thread.
+///
+/// The task is cancelled when dropped, so replacing it with a new task cancels the old one.
+#[derive(Debug)]
+pub struct FilterTask {
+    key: u64,
+    progress: TaskProgress,
+}
+
+impl FilterTask {
+    /// Starts filtering the given lines. `on_complete` is called from the background thread
+    /// with the result, unless the task is cancelled first.
+    pub fn spawn<F>(key: u64, lines: Arc<Vec<LogLine>>, rule: FilterRule, on_complete: F) -> Self
+    where
+        F: FnOnce(FilterMask) + Send + 'static,
+    {
+        let progress = TaskProgress::spawn(lines.len(), move |cancelled, progress| {
+            let start = Instant::now();
+            if let Some(visible) = compute_filter_mask(&lines, &rule, cancelled, progress) {
+                on_complete(FilterMask {
+                    key,
+                    visible: Arc::new(visible),
+                    elapsed: start.elapsed(),
+                });
+            }
+        });
+
+        Self { key, progress }
+    }
+
+    /// Returns the key of the filter inputs this task is computing.
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    pub fn progress(&self) -> &TaskProgress {
+        &self.progress
+    }
+}
+
+/// Applies the filter rule to all lines in chunks. Returns `None` if cancelled.
+pub fn compute_filter_mask(
+    lines: &[LogLine],
+    rule: &FilterRule,
+    cancelled: &AtomicBool,
+    progress: &AtomicUsize,
+) -> Option<Vec<bool>> {
+    let mut visible = vec![false; lines.len()];
+
+    for (chunk, chunk_visible) in lines.chunks(CHUNK_SIZE).zip(visible.chunks_mut(CHUNK_SIZE)) {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        chunk
+            .par_iter()
+            .zip(chunk_visible.par_iter_mut())
+            .for_each(|(line, is_visible)| *is_visible = rule.is_visible(line));
+        progress.fetch_add(chunk.len(), Ordering::Relaxed);
+    }
+
+    Some(visible)
+}
+
+/// Computes a key identifying the inputs of a filter run, used to detect stale results.
+pub fn filter_key(patterns: &[FilterPattern], always_visible: &HashSet<usize>, line_count: usize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for pattern in patterns {
+        pattern.pattern.hash(&mut hasher);
+        pattern.mode.hash(&mut hasher);
+        pattern.case_sensitive.hash(&mut hasher);
+        pattern.whole_word.hash(&mut hasher);
+        pattern.enabled.hash(&mut hasher);
+        pattern.regex.is_some().hash(&mut hasher);
+        pattern.file.hash(&mut hasher);
+    }
+    let mut always_visible: Vec<usize> = always_visible.iter().copied().collect();
+    always_visible.sort_unstable();
+    always_visible.hash(&mut hasher);
+    line_count.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Rule that uses a precomputed filter mask.
+pub struct FilterMaskRule {
+    visible: Arc<Vec<bool>>,
+}
+
+impl FilterMaskRule {
+    pub fn new(visible: Arc<Vec<bool>>) -> Self {
+        Self { visible }
+    }
+}
+
+impl VisibilityRule for FilterMaskRule {
+    fn is_visible(&self, line: &LogLine) -> bool {
+        self.visible.get(line.index).copied().unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::ActiveFilterMode;
+
+    fn lines(contents: &[&str]) -> Vec<LogLine> {
+        contents.iter().enumerate().map(|(i, c)| LogLine::new(c, i)).collect()
+    }
+
+    #[test]
+    fn test_compute_filter_mask() {
+        let lines = lines(&["ERROR one", "INFO two", "ERROR three", "DEBUG four"]);
+        let patterns = vec![FilterPattern::new(
+            "ERROR".to_string(),
+            ActiveFilterMode::Include,
+            true,
+            true,
+        )];
+        let rule = FilterRule::new(Arc::new(patterns), Arc::new(HashSet::from([3])));
+        let progress = AtomicUsize::new(0);
+
+        let mask = compute_filter_mask(&lines, &rule, &AtomicBool::new(false), &progress).unwrap();
+        assert_eq!(mask, vec![true, false, true, true]);
+        assert_eq!(progress.load(Ordering::Relaxed), 4);
+
+        assert!(compute_filter_mask(&lines, &rule, &AtomicBool::new(true), &progress).is_none());
+    }
+
+    #[test]
+    fn test_filter_key_changes_with_inputs() {
+        let patterns = vec![FilterPattern::new(
+            "ERROR".to_string(),
+            ActiveFilterMode::Include,
+            true,
+            true,
+        )];
+        let key = filter_key(&patterns, &HashSet::new(), 10);
+
+        assert_eq!(key, filter_key(&patterns, &HashSet::new(), 10));
+        assert_ne!(key, filter_key(&patterns, &HashSet::new(), 11));
+        assert_ne!(key, filter_key(&patterns, &HashSet::from([1]), 10));
+
+        let mut disabled = patterns.clone();
+        disabled[0].enabled = false;
+        assert_ne!(key, filter_key(&disabled, &HashSet::new(), 10));
+    }
+}
@@ -0,0 +1,380 @@
+use std::io::Read;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::fields::{FieldExtractor, logfmt_keys};
+use crate::level::LevelDetector;
+use crate::timestamp::{TimestampFormat, TimestampParser};
+
+/// Number of lines at the start of a log its format is detected from.
+pub const DETECTION_LINES: usize = 50;
+
+/// Bytes read from the start of a file to detect its format.
+const DETECTION_BYTES: u64 = 64 * 1024;
+
+/// Maximum number of logfmt keys shown as columns.
+const MAX_LOGFMT_COLUMNS: usize = 8;
+
+/// A well-known log layout providing timestamp parsing, level detection and fields without any config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// RFC 5424 syslog messages, e.g. `<34>1 2024-01-15T10:30:45.003Z host app 1234 ID47 - started`.
+    Syslog,
+    /// journalctl's default short output, also the layout of `/var/log/syslog`, e.g.
+    /// `Jan 15 10:30:45 host sshd[42]: accepted`.
+    Journald,
+    /// nginx access log in the combined format.
+    NginxAccess,
+    /// Apache access log in the common format.
+    Apache,
+    /// Lines of Docker's json-file logging driver, e.g. `{"log":"started\n","stream":"stdout","time":"..."}`.
+    DockerJson,
+    /// `key=value` pairs, e.g. `ts=2024-01-15T10:30:45Z level=info msg=started`.
+    Logfmt,
+    /// Rust's env_logger, e.g. `[2024-01-15T10:30:45Z INFO  my_crate] started`.
+    EnvLogger,
+    /// Python's logging with the `%(asctime)s - %(name)s - %(levelname)s - %(message)s` format.
+    PythonLogging,
+}
+
+/// Matches the `key=value` pairs of a logfmt line.
+const LOGFMT_PAIR: &str = r#"[\w.-]+=(?:"(?:[^"\\]|\\.)*"|\S*)"#;
+
+/// Matches the timestamp of access logs, e.g. `[15/Jan/2024:10:30:45 +0200]`.
+const ACCESS_LOG_TIMESTAMP: &str = r"\[(\d{2}/[A-Z][a-z]{2}/\d{4}:\d{2}:\d{2}:\d{2} [+-]\d{4})\]";
+
+static LINE_REGEXES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    LogFormat::ALL
+        .iter()
+        .map(|format| Regex::new(&format.line_pattern()).expect("format patterns are valid"))
+        .collect()
+});
+
+impl LogFormat {
+    /// All formats, in the order ties are broken when detecting.
+    pub const ALL: [LogFormat; 8] = [
+        LogFormat::Syslog,
+        LogFormat::Journald,
+        LogFormat::NginxAccess,
+        LogFormat::Apache,
+        LogFormat::DockerJson,
+        LogFormat::Logfmt,
+        LogFormat::EnvLogger,
+        LogFormat::PythonLogging,
+    ];
+
+    /// Name of the format, as used in the config.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogFormat::Syslog => "syslog",
+            LogFormat::Journald => "journald",
+            LogFormat::NginxAccess => "nginx",
+            LogFormat::Apache => "apache",
+            LogFormat::DockerJson => "docker",
+            LogFormat::Logfmt => "logfmt",
+            LogFormat::EnvLogger => "env_logger",
+            LogFormat::PythonLogging => "python",
+        }
+    }
+
+    /// Short description of the layout, shown in the format view.
+    pub fn description(&self) -> &'static str {
+        match self {
+            LogFormat::Syslog => "RFC 5424 syslog",
+            LogFormat::Journald => "journald short output, /var/log/syslog",
+            LogFormat::NginxAccess => "nginx access log (combined)",
+            LogFormat::Apache => "Apache access log (common)",
+            LogFormat::DockerJson => "Docker JSON log lines",
+            LogFormat::Logfmt => "key=value pairs",
+            LogFormat::EnvLogger => "Rust env_logger",
+            LogFormat::PythonLogging => "Python logging",
+        }
+    }
+
+    /// Regex matching a line of the format, with named groups for its fields.
+    fn line_pattern(&self) -> String {
+        match self {
+            LogFormat::Syslog => {
+                r"^<(?P<pri>\d{1,3})>1 (?P<timestamp>\S+) (?P<host>\S+) (?P<app>\S+) (?P<pid>\S+) (?P<msgid>\S+) (?P<message>.*)$"
+                    .to_string()
+            }
+            LogFormat::Journald => {
+                r"^(?P<timestamp>[A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}) (?P<host>\S+) (?P<unit>[^\s\[:]+)(?:\[(?P<pid>\d+)\])?: (?P<message>.*)$"
+                    .to_string()
+            }
+            LogFormat::NginxAccess => {
+                r#"^(?P<remote_addr>\S+) - (?P<remote_user>\S+) \[(?P<time>[^\]]+)\] "(?P<request>[^"]*)" (?P<status>\d{3}) (?P<bytes>\d+|-) "(?P<referer>[^"]*)" "(?P<user_agent>[^"]*)""#
+                    .to_string()
+            }
+            LogFormat::Apache => {
+                r#"^(?P<host>\S+) (?P<ident>\S+) (?P<user>\S+) \[(?P<time>[^\]]+)\] "(?P<request>[^"]*)" (?P<status>\d{3}) (?P<bytes>\d+|-)$"#
+                    .to_string()
+            }
+            LogFormat::DockerJson => r#"^\{"log":".*","stream":"(?:stdout|stderr)","time":"[^"]+"\}$"#.to_string(),
+            LogFormat::Logfmt => format!(r"^{pair}(?:\s+{pair})+\s*$", pair = LOGFMT_PAIR),
+            LogFormat::EnvLogger => {
+                r"^\[(?:(?P<timestamp>\d{4}-\d{2}-\d{2}T[^\s\]]+) )?(?P<level>TRACE|DEBUG|INFO|WARN|ERROR) +(?P<target>[^\]]+)\] (?P<message>.*)$"
+                    .to_string()
+            }
+            LogFormat::PythonLogging => {
+                r"^(?P<asctime>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2},\d{3}) - (?P<name>\S+) - (?P<levelname>[A-Z]+) - (?P<message>.*)$"
+                    .to_string()
+            }
+        }
+    }
+
+    /// Returns whether the line has the layout of the format.
+    pub fn matches(&self, line: &str) -> bool {
+        LINE_REGEXES[*self as usize].is_match(line)
+    }
+
+    /// Detects the format of the first lines of a log: the format most of them have, if at least half do.
+    pub fn detect<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let sample: Vec<&str> = lines
+            .into_iter()
+            .filter(|line| !line.trim().is_empty())
+            .take(DETECTION_LINES)
+            .collect();
+        if sample.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<(Self, usize)> = None;
+        for format in Self::ALL {
+            let count = sample.iter().filter(|line| format.matches(line)).count();
+            if best.is_none_or(|(_, best_count)| count > best_count) {
+                best = Some((format, count));
+            }
+        }
+        best.filter(|(_, count)| count * 2 >= sample.len())
+            .map(|(format, _)| format)
+    }
+
+    /// Detects the format from the start of a file.
+    pub fn detect_in_file(path: &str) -> Option<Self> {
+        let mut bytes = Vec::new();
+        std::fs::File::open(path)
+            .ok()?
+            .take(DETECTION_BYTES)
+            .read_to_end(&mut bytes)
+            .ok()?;
+        Self::detect(String::from_utf8_lossy(&bytes).lines())
+    }
+
+    /// Returns the parser of the timestamps of the format.
+    pub fn timestamp_parser(&self) -> TimestampParser {
+        TimestampParser::new(vec![self.timestamp_format()])
+    }
+
+    /// Returns where the timestamps of the format are and how to parse them.
+    pub fn timestamp_format(&self) -> TimestampFormat {
+        let (pattern, format) = match self {
+            LogFormat::Syslog => (r"^<\d{1,3}>1 (\S+)", "%+"),
+            LogFormat::Journald => (r"^([A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2})", "%b %e %H:%M:%S"),
+            LogFormat::NginxAccess | LogFormat::Apache => (ACCESS_LOG_TIMESTAMP, "%d/%b/%Y:%H:%M:%S %z"),
+            LogFormat::DockerJson => (r#""time":"([^"]+)""#, "%+"),
+            LogFormat::Logfmt => (r#"\b(?:ts|time|timestamp)="?([^"\s]+)"#, "%+"),
+            LogFormat::EnvLogger => (r"^\[(\d{4}-\d{2}-\d{2}T[^\s\]]+)", "%+"),
+            LogFormat::PythonLogging => (r"^(\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2},\d{3})", "%Y-%m-%d %H:%M:%S,%3f"),
+        };
+        TimestampFormat::new(pattern, format).expect("format timestamp patterns are valid")
+    }
+
+    /// Returns the level detector of the format. Levels the format has no pattern for use the default pattern.
+    pub fn level_detector(&self) -> LevelDetector {
+        let patterns: [Option<&str>; 5] = match self {
+            LogFormat::Syslog | LogFormat::Journald | LogFormat::DockerJson => [None; 5],
+            // Access logs have no levels, server errors are errors and client errors warnings
+            LogFormat::NginxAccess | LogFormat::Apache => [
+                None,
+                None,
+                Some(r#"" [1-3]\d\d "#),
+                Some(r#"" 4\d\d "#),
+                Some(r#"" 5\d\d "#),
+            ],
+            LogFormat::Logfmt => [
+                Some(r#"\b(?:level|lvl)="?(?i:trace)\b"#),
+                Some(r#"\b(?:level|lvl)="?(?i:debug|dbg)\b"#),
+                Some(r#"\b(?:level|lvl)="?(?i:info)\b"#),
+                Some(r#"\b(?:level|lvl)="?(?i:warn|warning)\b"#),
+                Some(r#"\b(?:level|lvl)="?(?i:error|err|fatal|crit|critical|panic)\b"#),
+            ],
+            LogFormat::EnvLogger => [
+                Some(r"^\[(?:\S+ )?TRACE "),
+                Some(r"^\[(?:\S+ )?DEBUG "),
+                Some(r"^\[(?:\S+ )?INFO "),
+                Some(r"^\[(?:\S+ )?WARN "),
+                Some(r"^\[(?:\S+ )?ERROR "),
+            ],
+            LogFormat::PythonLogging => [
+                None,
+                Some(r" - DEBUG - "),
+                Some(r" - INFO - "),
+                Some(r" - WARNING - "),
+                Some(r" - (?:ERROR|CRITICAL) - "),
+            ],
+        };
+        LevelDetector::new(patterns).expect("format level patterns are valid")
+    }
+
+    /// Returns the extractor of the fields of the format. The columns of logfmt lines are the keys of the given
+    /// lines.
+    pub fn field_extractor<'a>(&self, lines: impl IntoIterator<Item = &'a str>) -> Option<FieldExtractor> {
+        match self {
+            LogFormat::DockerJson => Some(FieldExtractor::json(&[
+                "time".to_string(),
+                "stream".to_string(),
+                "log".to_string(),
+            ])),
+            LogFormat::Logfmt => {
+                let keys = logfmt_keys(lines.into_iter().take(DETECTION_LINES));
+                let columns: Vec<String> = keys.into_iter().take(MAX_LOGFMT_COLUMNS).collect();
+                (!columns.is_empty()).then(|| FieldExtractor::logfmt(&columns))
+            }
+            _ => FieldExtractor::regex(&self.line_pattern(), &[]),
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|format| format.name() == s).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|format| format.name()).collect();
+            format!("unknown format '{}', expected auto, none or {}", s, names.join(", "))
+        })
+    }
+}
+
+/// How the log format is chosen, in the config or the format view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatChoice {
+    /// The format is detected from the first lines.
+    Auto,
+    /// No format, lines are parsed with the common timestamp formats and level names only.
+    None,
+    /// The given format, regardless of the lines.
+    Format(LogFormat),
+}
+
+impl FormatChoice {
+    /// Returns the format of the file: the chosen one, or the one detected from its start.
+    pub fn format_of_file(&self, path: &str) -> Option<LogFormat> {
+        match self {
+            FormatChoice::Auto => LogFormat::detect_in_file(path),
+            FormatChoice::None => None,
+            FormatChoice::Format(format) => Some(*format),
+        }
+    }
+
+    /// Returns the format of the given first lines of a log: the chosen one, or the one detected from them.
+    pub fn format_of_lines<'a>(&self, lines: impl IntoIterator<Item = &'a str>) -> Option<LogFormat> {
+        match self {
+            FormatChoice::Auto => LogFormat::detect(lines),
+            FormatChoice::None => None,
+            FormatChoice::Format(format) => Some(*format),
+        }
+    }
+
+    /// All choices, in the order they are listed in the format view.
+    pub fn all() -> Vec<FormatChoice> {
+        [FormatChoice::Auto, FormatChoice::None]
+            .into_iter()
+            .chain(LogFormat::ALL.into_iter().map(FormatChoice::Format))
+            .collect()
+    }
+}
+
+impl FromStr for FormatChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(FormatChoice::Auto),
+            "none" => Ok(FormatChoice::None),
+            _ => s.parse().map(FormatChoice::Format),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::level::LogLevel;
+    use crate::timestamp::parse_timestamp;
+
+    #[test]
+    fn test_detect_formats() {
+        let samples = [
+            (
+                LogFormat::Syslog,
+                "<34>1 2024-01-15T10:30:45.003Z host app 1234 ID47 - started",
+            ),
+            (LogFormat::Journald, "Jan 15 10:30:45 host sshd[42]: Accepted publickey"),
+            (
+                LogFormat::NginxAccess,
+                r#"10.0.0.1 - - [15/Jan/2024:10:30:45 +0000] "GET / HTTP/1.1" 200 612 "-" "curl/8.0""#,
+            ),
+            (
+                LogFormat::Apache,
+                r#"10.0.0.1 - frank [15/Jan/2024:10:30:45 +0000] "GET / HTTP/1.1" 404 0"#,
+            ),
+            (
+                LogFormat::DockerJson,
+                r#"{"log":"started\n","stream":"stdout","time":"2024-01-15T10:30:45.123456789Z"}"#,
+            ),
+            (
+                LogFormat::Logfmt,
+                r#"ts=2024-01-15T10:30:45Z level=info msg="server started" port=80"#,
+            ),
+            (
+                LogFormat::EnvLogger,
+                "[2024-01-15T10:30:45Z INFO  my_crate::server] started",
+            ),
+            (
+                LogFormat::PythonLogging,
+                "2024-01-15 10:30:45,123 - app.db - WARNING - slow query",
+            ),
+        ];
+        for (format, line) in samples {
+            assert_eq!(LogFormat::detect([line, line, "  continued"]), Some(format), "{}", line);
+            assert!(format.timestamp_parser().parse(line).is_some(), "{}", line);
+        }
+
+        assert_eq!(LogFormat::detect(["plain", "text", "lines"]), None);
+        assert_eq!("nginx".parse(), Ok(FormatChoice::Format(LogFormat::NginxAccess)));
+        assert!("xml".parse::<FormatChoice>().is_err());
+    }
+
+    #[test]
+    fn test_format_levels_timestamps_and_fields() {
+        let line = r#"10.0.0.1 - - [15/Jan/2024:12:30:45 +0200] "GET /a HTTP/1.1" 503 0 "-" "curl/8.0""#;
+        let format = LogFormat::NginxAccess;
+        assert_eq!(format.level_detector().detect(line), Some(LogLevel::Error));
+        assert_eq!(
+            format.timestamp_parser().parse(line),
+            parse_timestamp("2024-01-15 10:30:45")
+        );
+        let extractor = format.field_extractor([line]).unwrap();
+        let fields = extractor.extract(line).unwrap();
+        assert_eq!(extractor.columns()[4], "status");
+        assert_eq!(fields[4], "503");
+
+        let line = "ts=2024-01-15T10:30:45Z level=warn msg=slow";
+        let format = LogFormat::Logfmt;
+        assert_eq!(format.level_detector().detect(line), Some(LogLevel::Warn));
+        assert_eq!(
+            format.field_extractor([line]).unwrap().columns(),
+            &["ts", "level", "msg"]
+        );
+
+        let line = "2024-01-15 10:30:45,123 - app - ERROR - failed";
+        assert_eq!(
+            LogFormat::PythonLogging.level_detector().detect(line),
+            Some(LogLevel::Error)
+        );
+    }
+}
@@ -0,0 +1,165 @@
+/// Score of each matched character.
+const SCORE_MATCH: i64 = 16;
+/// Penalty for the first skipped character of a gap between matched characters.
+const PENALTY_GAP_START: i64 = 3;
+/// Penalty for each further skipped character of a gap.
+const PENALTY_GAP_EXTENSION: i64 = 1;
+/// Bonus for a match at the start of a word, e.g. after a space, `_` or `/`.
+const BONUS_BOUNDARY: i64 = 8;
+/// Bonus for a match at a camelCase or letter-digit transition.
+const BONUS_CAMEL: i64 = 7;
+/// Bonus for a match directly following the previous matched character.
+const BONUS_CONSECUTIVE: i64 = 4;
+/// Multiplier of the bonus of the first pattern character.
+const FIRST_CHAR_BONUS_MULTIPLIER: i64 = 2;
+
+/// Result of matching a pattern as a subsequence of a text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// Higher scores are better matches: consecutive characters and word starts score higher, gaps lower.
+    pub score: i64,
+    /// Byte positions of the matched characters in the text.
+    pub positions: Vec<usize>,
+}
+
+/// Matches the characters of the pattern in order anywhere in the text, like fzf.
+///
+/// The leftmost match is tightened by scanning back from where it ends, so `abc` matches the
+/// `abc` in `a_xabc` rather than spreading over the whole text. Returns `None` if the text doesn't
+/// contain all characters of the pattern in order.
+pub fn fuzzy_match(text: &str, pattern: &str, case_sensitive: bool) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let eq = |a: char, b: char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a == b || a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+
+    // Find where the leftmost occurrence of the pattern ends
+    let mut pattern_index = 0;
+    let mut end = None;
+    for (index, (_, c)) in chars.iter().enumerate() {
+        if eq(*c, pattern[pattern_index]) {
+            pattern_index += 1;
+            if pattern_index == pattern.len() {
+                end = Some(index);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    // Scan back for the latest start of the pattern ending there
+    let mut matched = vec![0; pattern.len()];
+    let mut pattern_index = pattern.len();
+    for index in (0..=end).rev() {
+        if eq(chars[index].1, pattern[pattern_index - 1]) {
+            pattern_index -= 1;
+            matched[pattern_index] = index;
+            if pattern_index == 0 {
+                break;
+            }
+        }
+    }
+
+    let score = score(&chars, &matched);
+    Some(FuzzyMatch {
+        score,
+        positions: matched.iter().map(|&index| chars[index].0).collect(),
+    })
+}
+
+/// Returns whether the text contains the characters of the pattern in order, without computing
+/// the positions and score of the match.
+pub fn fuzzy_contains(text: &str, pattern: &str, case_sensitive: bool) -> bool {
+    let mut pattern_chars = pattern.chars().peekable();
+    for c in text.chars() {
+        let Some(&next) = pattern_chars.peek() else {
+            break;
+        };
+        if c == next || (!case_sensitive && c.to_lowercase().eq(next.to_lowercase())) {
+            pattern_chars.next();
+        }
+    }
+    pattern_chars.peek().is_none()
+}
+
+/// Scores the matched character indices.
+fn score(chars: &[(usize, char)], matched: &[usize]) -> i64 {
+    let mut score = 0;
+    for (i, &index) in matched.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|p| chars[p].1);
+        let mut bonus = char_bonus(previous, chars[index].1);
+        if i == 0 {
+            bonus *= FIRST_CHAR_BONUS_MULTIPLIER;
+        } else {
+            let gap = index - matched[i - 1] - 1;
+            if gap == 0 {
+                bonus = bonus.max(BONUS_CONSECUTIVE);
+            } else {
+                score -= PENALTY_GAP_START + (gap as i64 - 1) * PENALTY_GAP_EXTENSION;
+            }
+        }
+        score += SCORE_MATCH + bonus;
+    }
+    score
+}
+
+/// Returns the bonus for matching a character following the given character.
+fn char_bonus(previous: Option<char>, current: char) -> i64 {
+    match previous {
+        None => BONUS_BOUNDARY,
+        Some(previous) if !previous.is_alphanumeric() && current.is_alphanumeric() => BONUS_BOUNDARY,
+        Some(previous)
+            if (previous.is_lowercase() && current.is_uppercase())
+                || (!previous.is_numeric() && current.is_numeric()) =>
+        {
+            BONUS_CAMEL
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match_positions() {
+        let result = fuzzy_match("connection timeout", "ctmo", false).unwrap();
+        assert_eq!(result.positions, vec![5, 11, 13, 15]);
+
+        // The match is tightened to the end of the leftmost occurrence
+        assert_eq!(fuzzy_match("a_xabc", "abc", true).unwrap().positions, vec![3, 4, 5]);
+        assert_eq!(fuzzy_match("Ünïcode", "ün", false).unwrap().positions, vec![0, 2]);
+
+        assert!(fuzzy_match("timeout", "tx", false).is_none());
+        assert!(fuzzy_match("ERROR", "err", true).is_none());
+        assert!(fuzzy_match("ERROR", "err", false).is_some());
+
+        assert!(fuzzy_contains("connection timeout", "ctmo", false));
+        assert!(!fuzzy_contains("ERROR", "err", true));
+        assert!(fuzzy_contains("anything", "", true));
+    }
+
+    #[test]
+    fn test_fuzzy_match_scores() {
+        let score = |text, pattern| fuzzy_match(text, pattern, false).unwrap().score;
+
+        // Consecutive beats spread out
+        assert!(score("timeout", "time") > score("txixmxe", "time"));
+        // Word starts beat the middle of words
+        assert!(score("user_id", "id") > score("valid", "id"));
+        assert!(score("getUserId", "ui") > score("quiet", "ui"));
+        assert!(score("request", "req") > score("prerequisite", "req"));
+    }
+}
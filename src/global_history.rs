@@ -0,0 +1,230 @@
+use crate::filter::{ActiveFilterMode, FilterHistoryEntry};
+use crate::persistence::state_dir;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Default maximum number of queries kept in the global history.
+pub const DEFAULT_MAX_HISTORY: usize = 500;
+
+/// Kind of query in the global history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueryKind {
+    Search,
+    Filter(ActiveFilterMode),
+}
+
+/// Search or filter query in the global history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub kind: QueryKind,
+    pub pattern: String,
+    pub case_sensitive: bool,
+    /// Whether the query only matched whole words.
+    #[serde(default)]
+    pub whole_word: bool,
+    /// Unix time in seconds the query was last used.
+    pub timestamp: i64,
+}
+
+impl HistoryRecord {
+    /// Returns whether the records are the same query, regardless of when they were used.
+    fn same_query(&self, other: &HistoryRecord) -> bool {
+        self.kind == other.kind
+            && self.pattern == other.pattern
+            && self.case_sensitive == other.case_sensitive
+            && self.whole_word == other.whole_word
+    }
+}
+
+/// Search and filter queries shared by all files and stdin sessions, stored in ~/.lazylog/history.json.
+///
+/// Records are ordered from oldest to most recently used. Using a query again moves it to the end.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GlobalHistory {
+    records: Vec<HistoryRecord>,
+    #[serde(skip)]
+    max_size: usize,
+}
+
+impl GlobalHistory {
+    /// Loads the history from disk. A missing or unreadable history file gives an empty history.
+    pub fn load(max_size: usize) -> Self {
+        let mut history = history_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| match serde_json::from_str::<GlobalHistory>(&json) {
+                Ok(history) => Some(history),
+                Err(e) => {
+                    info!("Failed to parse global history: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        history.max_size = max_size;
+        history.truncate();
+        history
+    }
+
+    /// Returns the records, oldest first.
+    pub fn records(&self) -> &[HistoryRecord] {
+        &self.records
+    }
+
+    /// Returns the search patterns, oldest first.
+    pub fn search_history(&self) -> Vec<String> {
+        self.records
+            .iter()
+            .filter(|record| record.kind == QueryKind::Search)
+            .map(|record| record.pattern.clone())
+            .collect()
+    }
+
+    /// Returns the filter queries, oldest first.
+    pub fn filter_history(&self) -> Vec<FilterHistoryEntry> {
+        self.records
+            .iter()
+            .filter_map(|record| match record.kind {
+                QueryKind::Filter(mode) => Some(FilterHistoryEntry {
+                    pattern: record.pattern.clone(),
+                    mode,
+                    case_sensitive: record.case_sensitive,
+                    whole_word: record.whole_word,
+                }),
+                QueryKind::Search => None,
+            })
+            .collect()
+    }
+
+    /// Records a query as the most recently used and saves the history.
+    ///
+    /// Queries saved by other instances in the meantime are kept, so concurrent sessions share their history.
+    /// Does nothing for a history with a maximum size of 0, e.g. when persistence is disabled.
+    pub fn add(&mut self, kind: QueryKind, pattern: &str, case_sensitive: bool, whole_word: bool) {
+        if pattern.is_empty() || self.max_size == 0 {
+            return;
+        }
+        let saved = Self::load(self.max_size);
+        self.records = saved.records;
+        self.push(HistoryRecord {
+            kind,
+            pattern: pattern.to_string(),
+            case_sensitive,
+            whole_word,
+            timestamp: chrono::Local::now().timestamp(),
+        });
+        self.save();
+    }
+
+    /// Appends a record, removing an earlier record of the same query and the oldest records above the maximum size.
+    fn push(&mut self, record: HistoryRecord) {
+        self.records.retain(|existing| !existing.same_query(&record));
+        self.records.push(record);
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        let excess = self.records.len().saturating_sub(self.max_size);
+        self.records.drain(..excess);
+    }
+
+    fn save(&self) {
+        let Some(path) = history_path() else {
+            return;
+        };
+        if let Some(dir) = path.parent()
+            && fs::create_dir_all(dir).is_err()
+        {
+            return;
+        }
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => {
+                if let Err(e) = fs::write(&path, json) {
+                    info!("Failed to save global history to {:?}: {}", path, e);
+                }
+            }
+            Err(e) => info!("Failed to serialize global history: {}", e),
+        }
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    Some(state_dir()?.join("history.json"))
+}
+
+/// Merges per-file history with the global history. Entries only in the per-file history come first,
+/// as they are older than anything recorded globally.
+pub fn merge_history<T: Clone + PartialEq>(local: &[T], global: Vec<T>) -> Vec<T> {
+    let mut merged: Vec<T> = local.iter().filter(|entry| !global.contains(entry)).cloned().collect();
+    merged.extend(global);
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(kind: QueryKind, pattern: &str, timestamp: i64) -> HistoryRecord {
+        HistoryRecord {
+            kind,
+            pattern: pattern.to_string(),
+            case_sensitive: false,
+            whole_word: false,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_push_deduplicates_and_truncates() {
+        let mut history = GlobalHistory {
+            records: Vec::new(),
+            max_size: 3,
+        };
+        history.push(record(QueryKind::Search, "timeout", 1));
+        history.push(record(QueryKind::Filter(ActiveFilterMode::Include), "ERROR", 2));
+        history.push(record(QueryKind::Filter(ActiveFilterMode::Exclude), "ERROR", 3));
+        history.push(record(QueryKind::Search, "timeout", 4));
+
+        let patterns: Vec<(QueryKind, i64)> = history.records().iter().map(|r| (r.kind, r.timestamp)).collect();
+        assert_eq!(
+            patterns,
+            vec![
+                (QueryKind::Filter(ActiveFilterMode::Include), 2),
+                (QueryKind::Filter(ActiveFilterMode::Exclude), 3),
+                (QueryKind::Search, 4),
+            ]
+        );
+        assert_eq!(history.search_history(), vec!["timeout".to_string()]);
+        assert_eq!(history.filter_history()[1].mode, ActiveFilterMode::Exclude);
+
+        history.push(record(QueryKind::Search, "panic", 5));
+        assert_eq!(history.records().len(), 3);
+        assert_eq!(history.records()[0].kind, QueryKind::Filter(ActiveFilterMode::Exclude));
+    }
+
+    #[test]
+    fn test_whole_word_queries() {
+        let mut history = GlobalHistory {
+            records: Vec::new(),
+            max_size: 10,
+        };
+        let filter = QueryKind::Filter(ActiveFilterMode::Include);
+        history.push(record(filter, "err", 1));
+        history.push(HistoryRecord {
+            whole_word: true,
+            ..record(filter, "err", 2)
+        });
+
+        let entries = history.filter_history();
+        assert_eq!(entries.len(), 2);
+        assert!(!entries[0].whole_word);
+        assert!(entries[1].whole_word);
+    }
+
+    #[test]
+    fn test_merge_history() {
+        let local = ["a".to_string(), "b".to_string()];
+        let global = vec!["c".to_string(), "a".to_string()];
+        assert_eq!(merge_history(&local, global), ["b", "c", "a"]);
+    }
+}
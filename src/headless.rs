@@ -0,0 +1,350 @@
+use std::io::{BufRead, BufWriter, ErrorKind, Write};
+
+use color_eyre::eyre::eyre;
+
+use crate::ansi::{self, AnsiMode};
+use crate::cli::Cli;
+use crate::config::{Config, Filters};
+use crate::fields::FieldExtractor;
+use crate::file_manager::FileManager;
+use crate::filter::{FilterPattern, apply_filters};
+use crate::format::DETECTION_LINES;
+use crate::highlighter::{HighlightedLine, Highlighter, StyledRange};
+use crate::level::{LevelDetector, LogLevel};
+use crate::log::{LogBuffer, sanitize_line_owned};
+use crate::log_event::EventPattern;
+use crate::utils::CaseFolding;
+
+/// Number of lines whose timestamps are printed by [`test_timestamps`].
+const TIMESTAMP_TEST_LINES: usize = 10;
+
+/// Decides which lines are printed in headless mode and how they look.
+pub struct HeadlessPrinter {
+    filter_patterns: Vec<FilterPattern>,
+    extractor: Option<FieldExtractor>,
+    case_folding: CaseFolding,
+    event_patterns: Vec<EventPattern>,
+    /// Names of the events whose lines are printed. All lines passing the filters are printed if empty.
+    event_names: Vec<String>,
+    /// Lines below this level are not printed. Lines without a level are always printed.
+    min_level: Option<LogLevel>,
+    level_detector: LevelDetector,
+    /// Highlighter for the styles of events and highlights, if the lines are colored.
+    highlighter: Option<Highlighter>,
+}
+
+impl HeadlessPrinter {
+    pub fn new(
+        config: &Config,
+        filter_patterns: Vec<FilterPattern>,
+        event_names: Vec<String>,
+        min_level: Option<LogLevel>,
+        color: bool,
+    ) -> Self {
+        let highlighter = color.then(|| {
+            Highlighter::new(
+                config.parse_highlight_patterns(),
+                config.parse_highlight_event_patterns(),
+            )
+        });
+        Self {
+            filter_patterns,
+            extractor: config.parse_field_extractor(),
+            case_folding: config.case_folding(),
+            event_patterns: config.parse_log_event_patterns(),
+            event_names,
+            min_level,
+            level_detector: config.parse_level_detector(),
+            highlighter,
+        }
+    }
+
+    /// Returns the line from the file with the given name as it is printed, or None if it does not pass the
+    /// filters and events.
+    pub fn render(&self, content: &str, file: Option<&str>, ansi_styles: &[StyledRange]) -> Option<String> {
+        if !apply_filters(
+            content,
+            file,
+            &self.filter_patterns,
+            self.extractor.as_ref(),
+            self.case_folding,
+        ) {
+            return None;
+        }
+        if let Some(min_level) = self.min_level
+            && self
+                .level_detector
+                .detect(content)
+                .is_some_and(|level| level < min_level)
+        {
+            return None;
+        }
+        if !self.event_names.is_empty() {
+            // Like in the events view, a line belongs to the first event pattern it matches
+            let event = self.event_patterns.iter().find(|p| p.matcher.matches(content))?;
+            if !self.event_names.contains(&event.name) {
+                return None;
+            }
+        }
+
+        match &self.highlighter {
+            Some(highlighter) => Some(to_ansi(
+                content,
+                &highlighter.highlight_text(content, file, ansi_styles),
+            )),
+            None => Some(content.to_string()),
+        }
+    }
+}
+
+/// Writes a highlighted line with ANSI color sequences.
+pub fn to_ansi(content: &str, highlighted: &HighlightedLine) -> String {
+    let mut result = String::with_capacity(content.len() + highlighted.segments.len() * 16);
+    let mut pos = 0;
+    for segment in &highlighted.segments {
+        if segment.start < pos || segment.end > content.len() {
+            continue;
+        }
+        result.push_str(&content[pos..segment.start]);
+        let params = ansi::sgr_params(&segment.style);
+        if params.is_empty() {
+            result.push_str(&content[segment.start..segment.end]);
+        } else {
+            result.push_str(&format!(
+                "\x1b[{}m{}\x1b[0m",
+                params,
+                &content[segment.start..segment.end]
+            ));
+        }
+        pos = segment.end;
+    }
+    result.push_str(&content[pos..]);
+    result
+}
+
+/// Prints the lines of the given files, or of stdin, that pass the configured and given filters.
+///
+/// Stdin is read line by line, so lines are printed as they arrive. Returns the number of lines printed.
+pub fn run(args: &Cli) -> color_eyre::Result<usize> {
+    if args.should_run_command() {
+        return Err(eyre!(
+            "Headless mode reads files or stdin, pipe the output of the command instead"
+        ));
+    }
+
+    let mut config = Config::load(&args.config).map_err(|e| eyre!(e))?;
+    if let Some(profile) = &args.profile {
+        config.apply_profile(profile).map_err(|e| eyre!(e))?;
+    }
+    let mut issues = config.validate();
+    let mut filter_patterns = config.parse_filter_patterns();
+    if let Some(filters_file) = Filters::load(&args.filters) {
+        issues.extend(filters_file.validate());
+        filter_patterns.extend(filters_file.parse_filter_patterns());
+    }
+    let (arg_patterns, arg_issues) = args.parse_filter_args();
+    filter_patterns.extend(arg_patterns);
+    issues.extend(arg_issues);
+    if !issues.is_empty() {
+        eprintln!(
+            "Warning: Some configuration entries were ignored:\n{}",
+            issues.join("\n")
+        );
+    }
+
+    let ansi_mode = if args.strip_ansi {
+        AnsiMode::Strip
+    } else {
+        config.ansi.unwrap_or_default()
+    };
+    let printer = HeadlessPrinter::new(
+        &config,
+        filter_patterns,
+        args.headless_events.clone(),
+        args.level,
+        args.color,
+    );
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    let mut printed = 0;
+
+    let result = if args.files.is_empty() {
+        print_stdin(&printer, ansi_mode, &mut out, &mut printed)
+    } else {
+        let parse_timestamps = !args.no_timestamps && !config.disable_timestamp_parsing.unwrap_or(false);
+        let log_format = args
+            .files
+            .first()
+            .and_then(|path| config.parse_format_choice().format_of_file(path));
+        let mut log_buffer = LogBuffer::default();
+        log_buffer.set_level_detector(config.parse_level_detector());
+        log_buffer.set_timestamp_parser(config.parse_timestamp_parser(log_format));
+        log_buffer.set_ansi_mode(ansi_mode);
+        let paths: Vec<&str> = args.files.iter().map(String::as_str).collect();
+        log_buffer.load_files(&paths, parse_timestamps)?;
+        let file_names = FileManager::new(&args.files).file_names();
+
+        log_buffer.iter().try_for_each(|line| {
+            let file = line.log_file_id.and_then(|id| file_names.get(id)).map(String::as_str);
+            print_line(&printer, &line.content, file, &line.ansi_styles, &mut out, &mut printed)
+        })
+    };
+
+    match result.and_then(|_| out.flush()) {
+        // The reader went away, e.g. `| head`
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(printed),
+        Err(e) => Err(e.into()),
+        Ok(()) => Ok(printed),
+    }
+}
+
+/// Prints the timestamps parsed from the first lines of the given files, or of stdin, next to the lines, to check
+/// the configured timestamp formats.
+pub fn test_timestamps(args: &Cli) -> color_eyre::Result<()> {
+    let config = Config::load(&args.config).map_err(|e| eyre!(e))?;
+    let issues = config.validate();
+    if !issues.is_empty() {
+        eprintln!(
+            "Warning: Some configuration entries were ignored:\n{}",
+            issues.join("\n")
+        );
+    }
+
+    let mut sources: Vec<(String, Vec<String>)> = Vec::new();
+    if args.files.is_empty() {
+        let lines = std::io::stdin()
+            .lock()
+            .lines()
+            .take(DETECTION_LINES)
+            .collect::<Result<_, _>>()?;
+        sources.push(("stdin".to_string(), lines));
+    }
+    for path in &args.files {
+        let file = std::fs::File::open(path).map_err(|e| eyre!("Failed to open {}: {}", path, e))?;
+        let lines = std::io::BufReader::new(file)
+            .lines()
+            .take(DETECTION_LINES)
+            .collect::<Result<_, _>>()?;
+        sources.push((path.clone(), lines));
+    }
+
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    for (name, lines) in sources {
+        let log_format = config
+            .parse_format_choice()
+            .format_of_lines(lines.iter().map(String::as_str));
+        let parser = config.parse_timestamp_parser(log_format);
+        let format_name = log_format.map_or("none", |format| format.name());
+        writeln!(out, "==> {} (format: {}) <==", name, format_name)?;
+        for line in lines.iter().take(TIMESTAMP_TEST_LINES) {
+            let timestamp = parser
+                .parse(line)
+                .map(|timestamp| timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+                .unwrap_or_else(|| "-".to_string());
+            writeln!(out, "{:<23}  {}", timestamp, line)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn print_stdin(
+    printer: &HeadlessPrinter,
+    ansi_mode: AnsiMode,
+    out: &mut impl Write,
+    printed: &mut usize,
+) -> std::io::Result<()> {
+    let mut stdin = std::io::stdin().lock();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if stdin.read_until(b'\n', &mut buf)? == 0 {
+            return Ok(());
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim_end_matches(['\n', '\r']).to_string();
+        let (content, ansi_styles) = sanitize_line_owned(line, ansi_mode);
+        let printed_before = *printed;
+        print_line(printer, &content, None, &ansi_styles, out, printed)?;
+        // Keep up with followed input, e.g. `tail -f app.log | lazylog --headless`
+        if *printed > printed_before {
+            out.flush()?;
+        }
+    }
+}
+
+fn print_line(
+    printer: &HeadlessPrinter,
+    content: &str,
+    file: Option<&str>,
+    ansi_styles: &[StyledRange],
+    out: &mut impl Write,
+    printed: &mut usize,
+) -> std::io::Result<()> {
+    if let Some(rendered) = printer.render(content, file, ansi_styles) {
+        writeln!(out, "{}", rendered)?;
+        *printed += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::parse_filter_arg;
+
+    fn config(toml: &str) -> Config {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_render_applies_filters_and_events() {
+        let config = config(
+            r#"
+events = [
+    { name = "Error", pattern = "ERROR" },
+    { name = "Slow", pattern = "slow" },
+]
+"#,
+        );
+        let filters = vec![
+            parse_filter_arg("api").unwrap(),
+            parse_filter_arg("!healthcheck").unwrap(),
+        ];
+        let printer = HeadlessPrinter::new(&config, filters.clone(), Vec::new(), None, false);
+        assert_eq!(
+            printer.render("ERROR api down", None, &[]),
+            Some("ERROR api down".to_string())
+        );
+        assert_eq!(printer.render("INFO api healthcheck", None, &[]), None);
+        assert_eq!(printer.render("INFO db up", None, &[]), None);
+
+        let printer = HeadlessPrinter::new(&config, filters, vec!["Slow".to_string()], None, false);
+        assert_eq!(printer.render("ERROR api slow", None, &[]), None);
+        assert!(printer.render("WARN api slow", None, &[]).is_some());
+        assert_eq!(printer.render("INFO api ok", None, &[]), None);
+    }
+
+    #[test]
+    fn test_render_colors_events() {
+        let config = config(
+            r#"
+events = [{ name = "Error", pattern = "ERROR", style = { fg = "red", bold = true } }]
+"#,
+        );
+        let printer = HeadlessPrinter::new(&config, Vec::new(), Vec::new(), None, true);
+        assert_eq!(
+            printer.render("ERROR x", None, &[]),
+            Some("\x1b[1;31mERROR x\x1b[0m".to_string())
+        );
+        assert_eq!(printer.render("INFO x", None, &[]), Some("INFO x".to_string()));
+    }
+
+    #[test]
+    fn test_render_hides_lines_below_level() {
+        let printer = HeadlessPrinter::new(&Config::default(), Vec::new(), Vec::new(), Some(LogLevel::Warn), false);
+        assert_eq!(printer.render("INFO started", None, &[]), None);
+        assert!(printer.render("WARN slow", None, &[]).is_some());
+        assert!(printer.render("ERROR failed", None, &[]).is_some());
+        assert!(printer.render("  at main.rs:1", None, &[]).is_some());
+    }
+}
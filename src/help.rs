@@ -1,6 +1,6 @@
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Line;
 use ratatui::widgets::{
     Block, Borders, Clear, List, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget,
@@ -10,7 +10,7 @@ use std::cell::Cell;
 use crate::app::{Overlay, ViewState};
 use crate::command::Command;
 use crate::keybindings::{KeybindingContext, KeybindingRegistry};
-use crate::ui::colors::{HELP_BG, HELP_HEADER_FG, HELP_HIGHLIGHT_FG};
+use crate::ui::colors::{HELP_HEADER_FG, HELP_HIGHLIGHT_FG};
 
 /// Manages the help popup display with keybindings and navigation.
 #[derive(Debug, Default)]
@@ -103,6 +103,7 @@ impl Help {
             // Global bindings section
             HelpItem::new_header("Global", None),
             HelpItem::new("Ctrl+c/q", "Quit", HelpItemType::Keybind),
+            HelpItem::new("Ctrl+z", "Suspend (resume with fg)", HelpItemType::Keybind),
             HelpItem::new("Esc", "Cancel/Exit mode", HelpItemType::Keybind),
             HelpItem::new("Enter", "Confirm", HelpItemType::Keybind),
             HelpItem::new("Ctrl+l", "Clear buffer (stdin)", HelpItemType::Keybind),
@@ -192,6 +193,66 @@ impl Help {
             &KeybindingContext::View(ViewState::OptionsView),
         );
 
+        // Timeline View section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Event Timeline",
+            Some(KeybindingContext::View(ViewState::TimelineView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::TimelineView),
+        );
+
+        // Levels View section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Log Levels",
+            Some(KeybindingContext::View(ViewState::LevelsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::LevelsView),
+        );
+
+        // Event Scope View section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Event Navigation",
+            Some(KeybindingContext::View(ViewState::EventScopeView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::EventScopeView),
+        );
+
+        // Copy format section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Copy As",
+            Some(KeybindingContext::View(ViewState::CopyFormatView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::CopyFormatView),
+        );
+
+        // Columns View section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Columns",
+            Some(KeybindingContext::View(ViewState::ColumnsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::ColumnsView),
+        );
+
         // Marks View section
         help_items.push(HelpItem::new_empty());
         help_items.push(HelpItem::new_header(
@@ -216,6 +277,266 @@ impl Help {
             &KeybindingContext::View(ViewState::FilesView),
         );
 
+        // Sessions list
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Sessions",
+            Some(KeybindingContext::View(ViewState::SessionsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::SessionsView),
+        );
+
+        // History list
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "History",
+            Some(KeybindingContext::View(ViewState::HistoryView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::HistoryView),
+        );
+
+        // Alerts list
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Alerts",
+            Some(KeybindingContext::View(ViewState::AlertsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::AlertsView),
+        );
+
+        // Correlated lines
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Correlated lines",
+            Some(KeybindingContext::View(ViewState::CorrelationView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::CorrelationView),
+        );
+
+        // Registers
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Registers",
+            Some(KeybindingContext::View(ViewState::RegistersView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::RegistersView),
+        );
+
+        // Navigation history
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Navigation history",
+            Some(KeybindingContext::View(ViewState::JumpHistoryView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::JumpHistoryView),
+        );
+
+        // Restarts list
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Restarts",
+            Some(KeybindingContext::View(ViewState::RestartsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::RestartsView),
+        );
+
+        // Full line
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Full line",
+            Some(KeybindingContext::View(ViewState::FullLineView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::FullLineView),
+        );
+
+        // Hex view
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Hex view",
+            Some(KeybindingContext::View(ViewState::HexView)),
+        ));
+        self.add_context_bindings(&mut help_items, registry, &KeybindingContext::View(ViewState::HexView));
+
+        // Log format
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Log format",
+            Some(KeybindingContext::View(ViewState::FormatView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::FormatView),
+        );
+
+        // Profiles
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Profiles",
+            Some(KeybindingContext::View(ViewState::ProfilesView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::ProfilesView),
+        );
+
+        // Stale filters
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Stale filters",
+            Some(KeybindingContext::View(ViewState::StaleFiltersView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::StaleFiltersView),
+        );
+
+        // Quick filter
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Filter by",
+            Some(KeybindingContext::View(ViewState::QuickFilterView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::QuickFilterView),
+        );
+
+        // Actions list
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Actions",
+            Some(KeybindingContext::View(ViewState::ActionsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::ActionsView),
+        );
+
+        // Scripts list
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Scripts",
+            Some(KeybindingContext::View(ViewState::ScriptsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::ScriptsView),
+        );
+
+        // Line inspector
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Line Inspector",
+            Some(KeybindingContext::View(ViewState::InspectorView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::InspectorView),
+        );
+
+        // Regex Tester section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Regex Tester",
+            Some(KeybindingContext::Overlay(Overlay::RegexTester)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::Overlay(Overlay::RegexTester),
+        );
+
+        // Notes section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Notes",
+            Some(KeybindingContext::View(ViewState::NotesView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::NotesView),
+        );
+
+        // Pipe Output section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Command Output",
+            Some(KeybindingContext::View(ViewState::PipeOutputView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::PipeOutputView),
+        );
+
+        // Query Results section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Query Results",
+            Some(KeybindingContext::View(ViewState::QueryResultsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::QueryResultsView),
+        );
+
+        // Patterns section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Patterns",
+            Some(KeybindingContext::View(ViewState::PatternsView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::PatternsView),
+        );
+
+        // Noise section
+        help_items.push(HelpItem::new_empty());
+        help_items.push(HelpItem::new_header(
+            "Noise",
+            Some(KeybindingContext::View(ViewState::NoiseView)),
+        ));
+        self.add_context_bindings(
+            &mut help_items,
+            registry,
+            &KeybindingContext::View(ViewState::NoiseView),
+        );
+
         self.help_items = help_items;
         self.reset();
     }
@@ -233,9 +554,11 @@ impl Help {
             if matches!(
                 command,
                 Command::Quit
+                    | Command::Suspend
                     | Command::Confirm
                     | Command::Cancel
                     | Command::ToggleHelp
+                    | Command::ToggleDebugOverlay
                     | Command::PageUp
                     | Command::PageDown
                     | Command::MoveUp
@@ -267,9 +590,17 @@ impl Help {
             let target_context = match overlay_value {
                 Overlay::EditFilter => KeybindingContext::Overlay(Overlay::EditFilter),
                 Overlay::EventsFilter => KeybindingContext::Overlay(Overlay::EventsFilter),
+                Overlay::ListFilter => KeybindingContext::View(view_state.clone()),
                 Overlay::MarkName => KeybindingContext::Overlay(Overlay::MarkName),
                 Overlay::SaveToFile => KeybindingContext::Overlay(Overlay::SaveToFile),
+                Overlay::SaveSession => KeybindingContext::Overlay(Overlay::SaveSession),
                 Overlay::AddCustomEvent => KeybindingContext::Overlay(Overlay::AddCustomEvent),
+                Overlay::ExportFilters => KeybindingContext::Overlay(Overlay::ExportFilters),
+                Overlay::ExportReport => KeybindingContext::Overlay(Overlay::ExportReport),
+                Overlay::PipeCommand => KeybindingContext::Overlay(Overlay::PipeCommand),
+                Overlay::Query => KeybindingContext::Overlay(Overlay::Query),
+                Overlay::Correlate => KeybindingContext::Overlay(Overlay::Correlate),
+                Overlay::RegexTester => KeybindingContext::Overlay(Overlay::RegexTester),
                 Overlay::AddFile => KeybindingContext::Overlay(Overlay::AddFile),
                 Overlay::Message(_) => KeybindingContext::Overlay(Overlay::Message(String::new())),
                 Overlay::Error(_) => KeybindingContext::Overlay(Overlay::Error(String::new())),
@@ -408,14 +739,14 @@ impl Help {
     }
 
     /// Renders the help popup to the buffer.
-    pub fn render(&self, popup_area: Rect, buf: &mut Buffer) {
+    pub fn render(&self, popup_area: Rect, bg: Color, buf: &mut Buffer) {
         Clear.render(popup_area, buf);
 
         let block = Block::default()
             .title(" Help ")
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL)
-            .style(Style::default().bg(HELP_BG));
+            .style(Style::default().bg(bg));
 
         let inner_area = block.inner(popup_area);
 
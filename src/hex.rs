@@ -0,0 +1,115 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Number of bytes shown per row of the hex view.
+pub const BYTES_PER_ROW: usize = 16;
+
+/// Bytes of a line shown in the hex view, e.g. to examine a protocol dump embedded in a log line.
+#[derive(Debug, Default)]
+pub struct HexDump {
+    bytes: Vec<u8>,
+    /// Whether each byte is binary: a control character other than a tab, or not part of valid UTF-8.
+    binary: Vec<bool>,
+}
+
+impl HexDump {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        let mut binary = Vec::with_capacity(bytes.len());
+        for chunk in bytes.utf8_chunks() {
+            for ch in chunk.valid().chars() {
+                let is_binary = ch.is_control() && ch != '\t';
+                binary.extend(std::iter::repeat_n(is_binary, ch.len_utf8()));
+            }
+            binary.extend(std::iter::repeat_n(true, chunk.invalid().len()));
+        }
+        Self { bytes, binary }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.bytes.len().div_ceil(BYTES_PER_ROW)
+    }
+
+    /// Returns the number of binary bytes, which the log view doesn't show as they are.
+    pub fn binary_count(&self) -> usize {
+        self.binary.iter().filter(|&&binary| binary).count()
+    }
+
+    /// Returns the bytes of the row and whether each of them is binary.
+    pub fn row(&self, row: usize) -> (&[u8], &[bool]) {
+        let start = (row * BYTES_PER_ROW).min(self.bytes.len());
+        let end = (start + BYTES_PER_ROW).min(self.bytes.len());
+        (&self.bytes[start..end], &self.binary[start..end])
+    }
+}
+
+/// Returns the character shown for the byte in the ASCII column, a dot for anything but printable ASCII.
+pub fn ascii_char(byte: u8) -> char {
+    if (0x20..0x7f).contains(&byte) {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+/// Reads a line of a file as it is stored, before invalid UTF-8 is replaced and control characters are removed
+/// for the log view. Returns `None` if the file has fewer lines.
+///
+/// The file is read from the start up to the line each time, so opening the hex view of a line near the end of a
+/// large file takes a moment.
+pub fn read_raw_line(path: &str, line_index: usize) -> std::io::Result<Option<Vec<u8>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut line = Vec::new();
+    for _ in 0..=line_index {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+    Ok(Some(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_dump_marks_binary_bytes() {
+        let mut bytes = b"id=7\tpayload=".to_vec();
+        bytes.extend([0x00, 0x1b, 0xff, 0xfe]);
+        bytes.extend("é".as_bytes());
+        bytes.extend(b" end");
+        let dump = HexDump::new(bytes);
+
+        assert_eq!(dump.len(), 23);
+        assert_eq!(dump.row_count(), 2);
+        assert_eq!(dump.binary_count(), 4);
+        let (row, binary) = dump.row(1);
+        assert_eq!(row, &[0xfe, 0xc3, 0xa9, b' ', b'e', b'n', b'd']);
+        assert_eq!(binary, &[true, false, false, false, false, false, false]);
+        assert_eq!(ascii_char(0xc3), '.');
+        assert_eq!(ascii_char(b'~'), '~');
+
+        let path = std::env::temp_dir().join(format!("lazylog-hex-{}.log", std::process::id()));
+        std::fs::write(&path, b"first\r\nsecond \x00\x01\nthird").unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(read_raw_line(path, 1).unwrap(), Some(b"second \x00\x01".to_vec()));
+        assert_eq!(read_raw_line(path, 0).unwrap(), Some(b"first".to_vec()));
+        assert_eq!(read_raw_line(path, 2).unwrap(), Some(b"third".to_vec()));
+        assert_eq!(read_raw_line(path, 3).unwrap(), None);
+        std::fs::remove_file(path).unwrap();
+    }
+}
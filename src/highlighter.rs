@@ -1,15 +1,14 @@
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
 use ratatui::style::{Color, Modifier, Style};
 use regex::Regex;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 
-use crate::{
-    matcher::{PatternMatchType, PatternMatcher, PlainMatch},
-    ui::colors::{DEFAULT_EVENT_BG, DEFAULT_EVENT_FG},
-};
+use crate::matcher::{FuzzyPattern, PatternMatchType, PatternMatcher, PlainMatch};
+use crate::utils::CaseFolding;
 
 /// Style configuration for text rendering.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct PatternStyle {
     /// Foreground color.
     pub fg_color: Option<Color>,
@@ -29,15 +28,6 @@ impl PatternStyle {
         }
     }
 
-    /// Creates a PatternStyle with a default color profile.
-    pub fn default_colors() -> Self {
-        Self {
-            fg_color: Some(DEFAULT_EVENT_FG),
-            bg_color: Some(DEFAULT_EVENT_BG),
-            bold: false,
-        }
-    }
-
     /// Convert to ratatui Style.
     pub fn to_ratatui(&self) -> Style {
         let mut ratatui_style = Style::default();
@@ -61,6 +51,10 @@ pub struct HighlightPattern {
     pub matcher: PatternMatcher,
     /// Style to apply to matched text.
     pub style: PatternStyle,
+    /// Styles of capture groups of a regex pattern as (group index, style), drawn on top of the match.
+    pub group_styles: Vec<(usize, PatternStyle)>,
+    /// Name of the file whose lines are highlighted, e.g. `api.log`. Without a file the lines of all files are.
+    pub file: Option<String>,
 }
 
 impl HighlightPattern {
@@ -70,16 +64,97 @@ impl HighlightPattern {
             PatternMatchType::Plain(case_sensitive) => PatternMatcher::Plain(PlainMatch {
                 pattern: pattern.to_string(),
                 case_sensitive,
+                whole_word: false,
+                case_folding: CaseFolding::default(),
             }),
             PatternMatchType::Regex => PatternMatcher::Regex(Regex::new(pattern).ok()?),
         };
 
-        Some(Self { matcher, style })
+        Some(Self {
+            matcher,
+            style,
+            group_styles: Vec::new(),
+            file: None,
+        })
+    }
+
+    /// Scopes the pattern to the lines of the file with the given name.
+    pub fn with_file(mut self, file: Option<String>) -> Self {
+        self.file = file;
+        self
+    }
+
+    /// Returns whether the pattern highlights the lines of the file with the given name, or of no file.
+    fn applies_to(&self, file: Option<&str>) -> bool {
+        self.file.as_deref().is_none_or(|scope| file == Some(scope))
+    }
+
+    /// Styles capture groups of a regex pattern, given by number or name, separately from the match.
+    ///
+    /// Returns None if a group does not exist or the pattern is not a regex.
+    pub fn with_group_styles(mut self, groups: &[(String, PatternStyle)]) -> Option<Self> {
+        if groups.is_empty() {
+            return Some(self);
+        }
+        let PatternMatcher::Regex(regex) = &self.matcher else {
+            return None;
+        };
+        self.group_styles = groups
+            .iter()
+            .map(|(group, style)| Some((capture_group_index(regex, group)?, *style)))
+            .collect::<Option<_>>()?;
+        Some(self)
+    }
+
+    /// Adds the ranges of the line styled by this pattern.
+    ///
+    /// With group styles, the whole match is only styled if the pattern has a style of its own.
+    fn push_ranges(&self, line: &str, ranges: &mut Vec<StyledRange>) {
+        match &self.matcher {
+            PatternMatcher::Regex(regex) if !self.group_styles.is_empty() => {
+                for captures in regex.captures_iter(line) {
+                    if self.style != PatternStyle::default() {
+                        let whole = captures.get_match();
+                        ranges.push(StyledRange {
+                            start: whole.start(),
+                            end: whole.end(),
+                            style: self.style,
+                        });
+                    }
+                    for (group, style) in &self.group_styles {
+                        if let Some(group_match) = captures.get(*group).filter(|m| !m.is_empty()) {
+                            ranges.push(StyledRange {
+                                start: group_match.start(),
+                                end: group_match.end(),
+                                style: *style,
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {
+                for (start, end) in self.matcher.find_all(line) {
+                    ranges.push(StyledRange {
+                        start,
+                        end,
+                        style: self.style,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns the index of a capture group given by number or name, if the regex has it.
+pub fn capture_group_index(regex: &Regex, group: &str) -> Option<usize> {
+    match group.parse::<usize>() {
+        Ok(index) => (index < regex.captures_len()).then_some(index),
+        Err(_) => regex.capture_names().position(|name| name == Some(group)),
     }
 }
 
 /// Styled range for rendering.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StyledRange {
     /// Start position in text.
     pub start: usize,
@@ -96,18 +171,110 @@ pub struct HighlightedLine {
     pub segments: Vec<StyledRange>,
 }
 
+/// The plain highlight patterns compiled into multi-pattern matchers, so a line is scanned once for
+/// all of them instead of once per pattern.
+#[derive(Debug, Default)]
+struct PlainPatternSet {
+    /// Matcher for the case-sensitive patterns, with the index of each of its patterns in the highlight patterns.
+    case_sensitive: Option<(AhoCorasick, Vec<usize>)>,
+    /// Matcher for the case-insensitive patterns, with the index of each of its patterns in the highlight patterns.
+    case_insensitive: Option<(AhoCorasick, Vec<usize>)>,
+}
+
+impl PlainPatternSet {
+    fn new(patterns: &[HighlightPattern]) -> Self {
+        Self {
+            case_sensitive: Self::build(patterns, true),
+            case_insensitive: Self::build(patterns, false),
+        }
+    }
+
+    fn build(patterns: &[HighlightPattern], case_sensitive: bool) -> Option<(AhoCorasick, Vec<usize>)> {
+        let (indices, plain_patterns): (Vec<usize>, Vec<&str>) = patterns
+            .iter()
+            .enumerate()
+            .filter_map(|(index, pattern)| match Self::plain_pattern(pattern) {
+                Some(plain) if plain.case_sensitive == case_sensitive => Some((index, plain.pattern.as_str())),
+                _ => None,
+            })
+            .unzip();
+        if plain_patterns.is_empty() {
+            return None;
+        }
+        let matcher = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(!case_sensitive)
+            .build(plain_patterns)
+            .ok()?;
+        Some((matcher, indices))
+    }
+
+    /// Returns the plain pattern of a highlight pattern that is matched by the set.
+    ///
+    /// The set only folds ASCII case, so case-insensitive patterns with other characters are matched on their own.
+    fn plain_pattern(pattern: &HighlightPattern) -> Option<&PlainMatch> {
+        match &pattern.matcher {
+            PatternMatcher::Plain(plain)
+                if !plain.pattern.is_empty() && (plain.case_sensitive || plain.pattern.is_ascii()) =>
+            {
+                Some(plain)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether the highlight pattern is matched by the set.
+    fn contains(&self, pattern: &HighlightPattern) -> bool {
+        let Some(plain) = Self::plain_pattern(pattern) else {
+            return false;
+        };
+        if plain.case_sensitive {
+            self.case_sensitive.is_some()
+        } else {
+            self.case_insensitive.is_some()
+        }
+    }
+
+    /// Returns all matches in the line as (pattern index, start, end), ordered by pattern index and
+    /// then by position.
+    fn find_all(&self, line: &str) -> Vec<(usize, usize, usize)> {
+        let mut matches: Vec<(usize, usize, usize)> = [&self.case_sensitive, &self.case_insensitive]
+            .into_iter()
+            .flatten()
+            .flat_map(|(matcher, indices)| {
+                matcher
+                    .find_overlapping_iter(line)
+                    .map(|m| (indices[m.pattern().as_usize()], m.start(), m.end()))
+            })
+            .collect();
+        matches.sort_by_key(|(index, start, _)| (*index, *start));
+        matches
+    }
+}
+
 /// Manages text highlighting and line coloring based on configured patterns.
 pub struct Highlighter {
     /// Patterns for text highlighting.
     patterns: Vec<HighlightPattern>,
+    /// Plain highlight patterns compiled for matching them all at once.
+    plain_patterns: PlainPatternSet,
     /// Event patterns for line coloring and tracking.
     events: Vec<HighlightPattern>,
+    /// Number of highlight and event patterns from the config at the start of `patterns` and `events`, followed
+    /// by the ones added at runtime.
+    configured: (usize, usize),
     /// Temporary highlights.
     temporary_highlights: Vec<HighlightPattern>,
-    /// Cache of highlighted lines to avoid re-computation.
-    cache: RefCell<HashMap<usize, HighlightedLine>>,
+    /// Cache of highlighted lines by line index, with the cache version they were highlighted in.
+    cache: RefCell<HashMap<usize, (u64, HighlightedLine)>>,
+    /// Version of the patterns. Cached lines of older versions are stale.
+    cache_version: u64,
     /// Maximum cache size to prevent unbounded growth.
     max_cache_size: usize,
+    /// Number of lines served from and missing in the cache, for the debug overlay.
+    cache_hits: Cell<usize>,
+    cache_misses: Cell<usize>,
+    /// How the case of characters is folded by the plain patterns added at runtime.
+    case_folding: CaseFolding,
 }
 
 impl std::fmt::Debug for Highlighter {
@@ -126,14 +293,31 @@ impl Highlighter {
     /// Creates a new highlighter with the given patterns.
     pub fn new(patterns: Vec<HighlightPattern>, events: Vec<HighlightPattern>) -> Self {
         Self {
+            plain_patterns: PlainPatternSet::new(&patterns),
+            configured: (patterns.len(), events.len()),
             patterns,
             events,
             temporary_highlights: Vec::new(),
             cache: RefCell::new(HashMap::new()),
+            cache_version: 0,
             max_cache_size: 500,
+            cache_hits: Cell::new(0),
+            cache_misses: Cell::new(0),
+            case_folding: CaseFolding::default(),
         }
     }
 
+    /// Returns the highlighter folding the case of characters this way in the plain patterns added at runtime.
+    pub fn with_case_folding(mut self, case_folding: CaseFolding) -> Self {
+        self.case_folding = case_folding;
+        self
+    }
+
+    /// Returns the number of lines served from the cache and the number of lines highlighted anew.
+    pub fn cache_stats(&self) -> (usize, usize) {
+        (self.cache_hits.get(), self.cache_misses.get())
+    }
+
     /// Returns the style for the whole line if it matches any event pattern.
     ///
     /// Returns the first matching event's style, or `None` if no pattern matches.
@@ -146,19 +330,43 @@ impl Highlighter {
         None
     }
 
-    /// Invalidates the highlight cache by clearing all entries.
+    /// Invalidates the highlight cache. Cached lines are replaced as they are highlighted again.
     pub fn invalidate_cache(&mut self) {
-        self.cache.borrow_mut().clear();
+        self.cache_version += 1;
     }
 
     /// Adds a temporary highlight pattern to be applied on top of any other highlighting.
-    pub fn add_temporary_highlight(&mut self, pattern: &str, style: PatternStyle, case_sensitive: bool) {
+    pub fn add_temporary_highlight(
+        &mut self,
+        pattern: &str,
+        style: PatternStyle,
+        case_sensitive: bool,
+        whole_word: bool,
+    ) {
         self.temporary_highlights.push(HighlightPattern {
             matcher: PatternMatcher::Plain(PlainMatch {
                 pattern: pattern.to_string(),
                 case_sensitive,
+                whole_word,
+                case_folding: self.case_folding,
             }),
             style,
+            group_styles: Vec::new(),
+            file: None,
+        });
+        self.invalidate_cache();
+    }
+
+    /// Adds a temporary highlight of the characters matched by a fuzzy pattern.
+    pub fn add_temporary_fuzzy_highlight(&mut self, pattern: &str, style: PatternStyle, case_sensitive: bool) {
+        self.temporary_highlights.push(HighlightPattern {
+            matcher: PatternMatcher::Fuzzy(FuzzyPattern {
+                pattern: pattern.to_string(),
+                case_sensitive,
+            }),
+            style,
+            group_styles: Vec::new(),
+            file: None,
         });
         self.invalidate_cache();
     }
@@ -170,41 +378,121 @@ impl Highlighter {
     }
 
     /// Adds a custom event highlight pattern.
-    pub fn add_custom_event(&mut self, pattern: &str, style: PatternStyle) {
+    pub fn add_custom_event(&mut self, pattern: &str, case_sensitive: bool, style: PatternStyle) {
         self.events.push(HighlightPattern {
             matcher: PatternMatcher::Plain(PlainMatch {
                 pattern: pattern.to_string(),
-                case_sensitive: true,
+                case_sensitive,
+                whole_word: false,
+                case_folding: self.case_folding,
             }),
             style,
+            group_styles: Vec::new(),
+            file: None,
+        });
+        self.invalidate_cache();
+    }
+
+    /// Adds a custom event highlight pattern matching a regex.
+    pub fn add_custom_regex_event(&mut self, regex: Regex, style: PatternStyle) {
+        self.events.push(HighlightPattern {
+            matcher: PatternMatcher::Regex(regex),
+            style,
+            group_styles: Vec::new(),
+            file: None,
         });
         self.invalidate_cache();
     }
 
     /// Removes a custom event highlight pattern by its pattern string.
     pub fn remove_custom_event(&mut self, pattern: &str) {
-        self.events.retain(|event| {
-            if let PatternMatcher::Plain(plain) = &event.matcher {
-                plain.pattern != pattern
-            } else {
-                true
-            }
-        });
+        self.events.retain(|event| event.matcher.pattern() != pattern);
+        self.invalidate_cache();
+    }
+
+    /// Replaces the highlight and event patterns from the config, e.g. after it was edited, keeping the ones added
+    /// at runtime. Custom events now in the config are dropped, as the configured event takes their place.
+    pub fn replace_configured(&mut self, patterns: Vec<HighlightPattern>, events: Vec<HighlightPattern>) {
+        let (configured_patterns, configured_events) = self.configured;
+        self.configured = (patterns.len(), events.len());
+
+        let runtime_patterns = self.patterns.split_off(configured_patterns.min(self.patterns.len()));
+        self.patterns = patterns;
+        self.patterns.extend(runtime_patterns);
+        self.plain_patterns = PlainPatternSet::new(&self.patterns);
+
+        let runtime_events = self.events.split_off(configured_events.min(self.events.len()));
+        self.events = events;
+        let runtime_events: Vec<HighlightPattern> = runtime_events
+            .into_iter()
+            .filter(|custom| {
+                self.events
+                    .iter()
+                    .all(|event| event.matcher.pattern() != custom.matcher.pattern())
+            })
+            .collect();
+        self.events.extend(runtime_events);
+        self.invalidate_cache();
+    }
+
+    /// Adds a highlight pattern.
+    pub fn add_highlight(&mut self, pattern: HighlightPattern) {
+        self.patterns.push(pattern);
+        self.plain_patterns = PlainPatternSet::new(&self.patterns);
         self.invalidate_cache();
     }
 
     /// Returns a HighlightedLine with all styling information.
     pub fn highlight_line(&self, log_index: usize, line: &str) -> HighlightedLine {
+        self.highlight_styled_line(log_index, line, None, &[])
+    }
+
+    /// Returns a HighlightedLine for a line from the file with the given name with its own styles, e.g. from
+    /// ANSI sequences.
+    ///
+    /// Events and highlight patterns are drawn on top of the line's own styles.
+    pub fn highlight_styled_line(
+        &self,
+        log_index: usize,
+        line: &str,
+        file: Option<&str>,
+        line_styles: &[StyledRange],
+    ) -> HighlightedLine {
         // Check cache first
         {
             let cache = self.cache.borrow();
-            if let Some(cached) = cache.get(&log_index) {
+            if let Some((version, cached)) = cache.get(&log_index)
+                && *version == self.cache_version
+            {
+                self.cache_hits.set(self.cache_hits.get() + 1);
                 return cached.clone();
             }
         } // Ref goes out of scope here
 
         // Cache miss
-        let mut ranges = Vec::with_capacity(10);
+        self.cache_misses.set(self.cache_misses.get() + 1);
+        let result = self.highlight_text(line, file, line_styles);
+
+        {
+            let mut cache = self.cache.borrow_mut();
+            if cache.len() >= self.max_cache_size && !cache.contains_key(&log_index) {
+                // Drop stale lines first, and start over if the lines in view keep changing
+                cache.retain(|_, (version, _)| *version == self.cache_version);
+                if cache.len() >= self.max_cache_size {
+                    cache.clear();
+                }
+            }
+            cache.insert(log_index, (self.cache_version, result.clone()));
+        } // Ref goes out of scope here
+
+        result
+    }
+
+    /// Returns a HighlightedLine for text from the file with the given name that is not cached, e.g. because it
+    /// depends on the layout.
+    pub fn highlight_text(&self, line: &str, file: Option<&str>, line_styles: &[StyledRange]) -> HighlightedLine {
+        let mut ranges = Vec::with_capacity(10 + line_styles.len());
+        ranges.extend_from_slice(line_styles);
 
         // Check for event line styling
         if let Some(line_style) = self.is_event(line) {
@@ -215,9 +503,20 @@ impl Highlighter {
             });
         }
 
-        // Apply configured highlight patterns
-        for pattern in &self.patterns {
-            for (start, end) in pattern.matcher.find_all(line) {
+        // Apply configured highlight patterns, in order so later patterns are drawn on top
+        let mut plain_matches = self.plain_patterns.find_all(line).into_iter().peekable();
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            let applies = pattern.applies_to(file);
+            if !self.plain_patterns.contains(pattern) {
+                if applies {
+                    pattern.push_ranges(line, &mut ranges);
+                }
+                continue;
+            }
+            while let Some((_, start, end)) = plain_matches.next_if(|(pattern_index, _, _)| *pattern_index == index) {
+                if !applies {
+                    continue;
+                }
                 ranges.push(StyledRange {
                     start,
                     end,
@@ -239,16 +538,7 @@ impl Highlighter {
 
         let segments = self.split_into_segments(ranges);
 
-        let result = HighlightedLine { segments };
-
-        {
-            let mut cache = self.cache.borrow_mut();
-            if cache.len() < self.max_cache_size {
-                cache.insert(log_index, result.clone());
-            }
-        } // Ref goes out of scope here
-
-        result
+        HighlightedLine { segments }
     }
 
     /// Adjusts a HighlightedLine for horizontal scrolling offset.
@@ -0,0 +1,128 @@
+use crate::log::LogLine;
+
+/// A labelled value of the inspected line that can be copied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineDetail {
+    pub label: String,
+    pub value: String,
+}
+
+impl LineDetail {
+    fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Everything known about a single log line, shown in the line inspector.
+#[derive(Debug, Clone, Default)]
+pub struct LineInspector {
+    /// Full content of the line.
+    pub content: String,
+    /// Details of the line, starting with the raw line itself.
+    pub details: Vec<LineDetail>,
+}
+
+impl LineInspector {
+    /// Collects the details of a line. `fields` are the fields parsed from the line, `filters` the
+    /// enabled filter patterns matching it.
+    pub fn new(
+        line: &LogLine,
+        file_name: Option<&str>,
+        event_names: &[String],
+        filters: &[String],
+        fields: Vec<(String, String)>,
+    ) -> Self {
+        let content = line.content();
+        let mut details = vec![
+            LineDetail::new("Raw line", content),
+            LineDetail::new(
+                "Line number",
+                match file_name {
+                    Some(name) => format!("{} in {}", line.source_index + 1, name),
+                    None => (line.source_index + 1).to_string(),
+                },
+            ),
+            LineDetail::new(
+                "Length",
+                format!("{} bytes, {} chars", content.len(), content.chars().count()),
+            ),
+        ];
+        if let Some(timestamp) = line.timestamp {
+            details.push(LineDetail::new(
+                "Timestamp",
+                timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            ));
+        }
+        if let Some(level) = line.level {
+            details.push(LineDetail::new("Level", level.name()));
+        }
+        if !event_names.is_empty() {
+            details.push(LineDetail::new("Events", event_names.join(", ")));
+        }
+        details.push(LineDetail::new(
+            "Filters",
+            if filters.is_empty() {
+                "none".to_string()
+            } else {
+                filters.join(", ")
+            },
+        ));
+        details.extend(fields.into_iter().map(|(key, value)| LineDetail::new(key, value)));
+
+        Self {
+            content: content.to_string(),
+            details,
+        }
+    }
+
+    /// Returns the width of the widest label, for aligning the values.
+    pub fn label_width(&self) -> usize {
+        self.details
+            .iter()
+            .map(|detail| detail.label.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::level::LogLevel;
+
+    #[test]
+    fn test_line_details() {
+        let mut line = LogLine::new("ERROR naïve user=bob", 41);
+        line.level = Some(LogLevel::Error);
+
+        let inspector = LineInspector::new(
+            &line,
+            Some("app.log"),
+            &["error".to_string()],
+            &[],
+            vec![("user".to_string(), "bob".to_string())],
+        );
+
+        let details: Vec<(&str, &str)> = inspector
+            .details
+            .iter()
+            .map(|detail| (detail.label.as_str(), detail.value.as_str()))
+            .collect();
+        assert_eq!(
+            details,
+            vec![
+                ("Raw line", "ERROR naïve user=bob"),
+                ("Line number", "42 in app.log"),
+                ("Length", "21 bytes, 20 chars"),
+                ("Level", "ERROR"),
+                ("Events", "error"),
+                ("Filters", "none"),
+                ("user", "bob"),
+            ]
+        );
+        assert_eq!(inspector.label_width(), 11);
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::TimeDelta;
+
+use crate::log::LogLine;
+use crate::log_event::LogEvent;
+
+/// Measurements of the lines between two log lines, both included.
+#[derive(Debug, PartialEq)]
+pub struct Interval {
+    /// Log index of the first line.
+    pub start: usize,
+    /// Log index of the last line.
+    pub end: usize,
+    /// Time between the first and the last line, if both have a timestamp.
+    pub elapsed: Option<TimeDelta>,
+    /// Number of lines in the interval.
+    pub line_count: usize,
+    /// Number of lines in the interval that are not hidden by filters.
+    pub visible_count: usize,
+    /// Number of events per event name, most frequent first.
+    pub event_counts: Vec<(String, usize)>,
+}
+
+impl Interval {
+    /// Measures the interval between two log lines, given in any order.
+    pub fn measure<'a>(
+        lines: &[LogLine],
+        first: usize,
+        second: usize,
+        visible_indices: impl Iterator<Item = usize>,
+        events: impl IntoIterator<Item = &'a LogEvent>,
+    ) -> Self {
+        let (start, end) = if first <= second {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        let elapsed = match (
+            lines.get(start).and_then(|line| line.timestamp),
+            lines.get(end).and_then(|line| line.timestamp),
+        ) {
+            (Some(start_time), Some(end_time)) => Some(end_time - start_time),
+            _ => None,
+        };
+
+        let mut event_counts: Vec<(String, usize)> = Vec::new();
+        for event in events.into_iter().filter(|e| (start..=end).contains(&e.line_index)) {
+            match event_counts.iter_mut().find(|(name, _)| *name == event.name) {
+                Some((_, count)) => *count += 1,
+                None => event_counts.push((event.name.clone(), 1)),
+            }
+        }
+        event_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Self {
+            start,
+            end,
+            elapsed,
+            line_count: end - start + 1,
+            visible_count: visible_indices.filter(|index| (start..=end).contains(index)).count(),
+            event_counts,
+        }
+    }
+
+    /// Formats the measurements as a multi-line report.
+    pub fn report(&self) -> String {
+        let mut lines = vec![
+            format!("From line {} to line {}", self.start + 1, self.end + 1),
+            format!(
+                "Elapsed: {}",
+                self.elapsed
+                    .map_or_else(|| "unknown (no timestamps)".to_string(), format_elapsed)
+            ),
+            format!("Lines: {} ({} visible)", self.line_count, self.visible_count),
+        ];
+
+        if self.event_counts.is_empty() {
+            lines.push("Events: none".to_string());
+        } else {
+            lines.push("Events:".to_string());
+            let name_width = self.event_counts.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+            for (name, count) in &self.event_counts {
+                lines.push(format!("  {:<width$}  {}", name, count, width = name_width));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Formats an elapsed time with the largest units needed, e.g. "1h 02m 03.250s" or "0.015s".
+pub fn format_elapsed(elapsed: TimeDelta) -> String {
+    let sign = if elapsed < TimeDelta::zero() { "-" } else { "" };
+    let millis = elapsed.num_milliseconds().unsigned_abs();
+    let (days, hours) = (millis / 86_400_000, millis / 3_600_000 % 24);
+    let (minutes, seconds, millis) = (millis / 60_000 % 60, millis / 1000 % 60, millis % 1000);
+
+    if days > 0 {
+        format!(
+            "{}{}d {:02}h {:02}m {:02}.{:03}s",
+            sign, days, hours, minutes, seconds, millis
+        )
+    } else if hours > 0 {
+        format!("{}{}h {:02}m {:02}.{:03}s", sign, hours, minutes, seconds, millis)
+    } else if minutes > 0 {
+        format!("{}{}m {:02}.{:03}s", sign, minutes, seconds, millis)
+    } else {
+        format!("{}{}.{:03}s", sign, seconds, millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timestamp::parse_timestamp;
+
+    fn line(content: &str, index: usize) -> LogLine {
+        let mut line = LogLine::new(content, index);
+        line.timestamp = parse_timestamp(content);
+        line
+    }
+
+    fn event(name: &str, line_index: usize) -> LogEvent {
+        LogEvent {
+            name: name.to_string(),
+            line_index,
+        }
+    }
+
+    #[test]
+    fn test_measure_interval() {
+        let lines = vec![
+            line("2024-01-15 10:00:00.000 start", 0),
+            line("2024-01-15 10:00:01.500 ERROR a", 1),
+            line("2024-01-15 10:01:02.000 WARN b", 2),
+            line("2024-01-15 11:02:03.250 ERROR c", 3),
+            line("2024-01-15 11:02:04.000 ERROR d", 4),
+        ];
+        let events = [
+            event("Error", 1),
+            event("Warning", 2),
+            event("Error", 3),
+            event("Error", 4),
+        ];
+
+        let interval = Interval::measure(&lines, 3, 0, [0, 1, 3, 4].into_iter(), &events);
+
+        assert_eq!((interval.start, interval.end), (0, 3));
+        assert_eq!(interval.elapsed.map(format_elapsed).as_deref(), Some("1h 02m 03.250s"));
+        assert_eq!((interval.line_count, interval.visible_count), (4, 3));
+        assert_eq!(
+            interval.event_counts,
+            vec![("Error".to_string(), 2), ("Warning".to_string(), 1)]
+        );
+        assert_eq!(
+            interval.report(),
+            "From line 1 to line 4\nElapsed: 1h 02m 03.250s\nLines: 4 (3 visible)\nEvents:\n  Error    2\n  Warning  1"
+        );
+
+        let untimed = Interval::measure(&[LogLine::new("a", 0), LogLine::new("b", 1)], 0, 1, 0..2, []);
+        assert_eq!(untimed.elapsed, None);
+        assert!(untimed.report().contains("Elapsed: unknown"));
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(TimeDelta::milliseconds(15)), "0.015s");
+        assert_eq!(format_elapsed(TimeDelta::seconds(125)), "2m 05.000s");
+        assert_eq!(format_elapsed(TimeDelta::seconds(90_061)), "1d 01h 01m 01.000s");
+        assert_eq!(format_elapsed(TimeDelta::seconds(-3)), "-3.000s");
+    }
+}
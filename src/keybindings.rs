@@ -30,8 +30,36 @@ impl KeybindingRegistry {
         registry.register_options_view_bindings();
         registry.register_events_view_bindings();
         registry.register_event_filter_view_bindings();
+        registry.register_list_filter_bindings();
         registry.register_marks_view_bindings();
         registry.register_files_view_bindings();
+        registry.register_sessions_view_bindings();
+        registry.register_history_view_bindings();
+        registry.register_alerts_view_bindings();
+        registry.register_correlation_view_bindings();
+        registry.register_registers_view_bindings();
+        registry.register_jump_history_view_bindings();
+        registry.register_restarts_view_bindings();
+        registry.register_full_line_view_bindings();
+        registry.register_hex_view_bindings();
+        registry.register_format_view_bindings();
+        registry.register_profiles_view_bindings();
+        registry.register_stale_filters_view_bindings();
+        registry.register_quick_filter_view_bindings();
+        registry.register_actions_view_bindings();
+        registry.register_scripts_view_bindings();
+        registry.register_inspector_view_bindings();
+        registry.register_pipe_output_view_bindings();
+        registry.register_query_results_view_bindings();
+        registry.register_patterns_view_bindings();
+        registry.register_noise_view_bindings();
+        registry.register_timeline_view_bindings();
+        registry.register_levels_view_bindings();
+        registry.register_event_scope_view_bindings();
+        registry.register_copy_format_view_bindings();
+        registry.register_columns_view_bindings();
+        registry.register_notes_view_bindings();
+        registry.register_regex_tester_bindings();
         registry.register_message_state_bindings();
         registry.register_error_state_bindings();
         registry.register_fatal_state_bindings();
@@ -46,14 +74,49 @@ impl KeybindingRegistry {
         registry.register_global_bindings(KeybindingContext::View(ViewState::EventsView));
         registry.register_global_bindings(KeybindingContext::View(ViewState::MarksView));
         registry.register_global_bindings(KeybindingContext::View(ViewState::FilesView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::SessionsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::HistoryView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::AlertsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::CorrelationView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::RegistersView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::JumpHistoryView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::RestartsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::FullLineView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::HexView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::FormatView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::ProfilesView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::StaleFiltersView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::QuickFilterView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::ActionsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::ScriptsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::InspectorView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::PipeOutputView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::QueryResultsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::PatternsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::NoiseView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::TimelineView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::LevelsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::EventScopeView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::CopyFormatView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::ColumnsView));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::NotesView));
         registry.register_global_bindings(KeybindingContext::View(ViewState::GotoLineMode));
+        registry.register_global_bindings(KeybindingContext::View(ViewState::GotoTimeMode));
 
         // Register global bindings for all overlay types
         registry.register_global_bindings(KeybindingContext::Overlay(Overlay::EditFilter));
         registry.register_global_bindings(KeybindingContext::Overlay(Overlay::EventsFilter));
+        registry.register_global_bindings(KeybindingContext::Overlay(Overlay::ListFilter));
         registry.register_global_bindings(KeybindingContext::Overlay(Overlay::MarkName));
         registry.register_global_bindings(KeybindingContext::Overlay(Overlay::SaveToFile));
+        registry.register_global_bindings(KeybindingContext::Overlay(Overlay::SaveSession));
         registry.register_global_bindings(KeybindingContext::Overlay(Overlay::AddCustomEvent));
+        registry.register_global_bindings(KeybindingContext::Overlay(Overlay::ExportFilters));
+        registry.register_global_bindings(KeybindingContext::Overlay(Overlay::ExportReport));
+        registry.register_global_bindings(KeybindingContext::Overlay(Overlay::PipeCommand));
+        registry.register_global_bindings(KeybindingContext::Overlay(Overlay::Query));
+        registry.register_global_bindings(KeybindingContext::Overlay(Overlay::Correlate));
+        registry.register_global_bindings(KeybindingContext::Overlay(Overlay::RegexTester));
         registry.register_global_bindings(KeybindingContext::Overlay(Overlay::Message(String::new())));
         registry.register_global_bindings(KeybindingContext::Overlay(Overlay::Error(String::new())));
         registry.register_global_bindings(KeybindingContext::Overlay(Overlay::Fatal(String::new())));
@@ -98,18 +161,27 @@ impl KeybindingRegistry {
         }
     }
 
-    /// Returns all keybindings for a specific context, grouped and sorted.
+    /// Returns all keybindings for a specific context, grouped and sorted. Keys bound twice for terminals that
+    /// report them differently, like Shift+Tab, are listed once.
     pub fn get_keybindings_for_context(&self, target_context: &KeybindingContext) -> Vec<(String, Command)> {
-        let bindings: Vec<(String, Command)> = self
+        let mut bindings: Vec<(String, Command)> = Vec::new();
+        for ((_, keycode, modifiers), cmd) in self
             .bindings
             .iter()
             .filter(|((context, _, _), _)| context == target_context)
-            .map(|((_, keycode, modifiers), cmd)| (Self::format_key(*keycode, *modifiers), *cmd))
-            .collect();
+        {
+            let binding = (Self::format_key(*keycode, *modifiers), *cmd);
+            if !bindings.contains(&binding) {
+                bindings.push(binding);
+            }
+        }
         bindings
     }
 
     fn format_key(keycode: KeyCode, modifiers: KeyModifiers) -> String {
+        if keycode == KeyCode::BackTab {
+            return "Shift+Tab".to_string();
+        }
         let key_str = match keycode {
             KeyCode::Char(' ') => "Space".to_string(),
             KeyCode::Char(c) if c.is_uppercase() => c.to_string(),
@@ -150,6 +222,13 @@ impl KeybindingRegistry {
         self.bindings.push(((context, keycode, modifiers), command));
     }
 
+    /// Returns true if the key is already bound in the context.
+    fn is_bound(&self, context: &KeybindingContext, keycode: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.bindings
+            .iter()
+            .any(|((ctx, kcode, kmod), _)| ctx == context && *kcode == keycode && *kmod == modifiers)
+    }
+
     /// Helper to register a keybinding without modifiers.
     fn bind_simple(&mut self, context: KeybindingContext, keycode: KeyCode, command: Command) {
         self.bind(context, keycode, KeyModifiers::empty(), command);
@@ -168,9 +247,19 @@ impl KeybindingRegistry {
             KeyModifiers::CONTROL,
             Command::Quit,
         );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('z'),
+            KeyModifiers::CONTROL,
+            Command::Suspend,
+        );
         self.bind_simple(context.clone(), KeyCode::Esc, Command::Cancel);
-        self.bind_simple(context.clone(), KeyCode::Enter, Command::Confirm);
+        // The log view opens the line inspector with Enter instead
+        if !self.is_bound(&context, KeyCode::Enter, KeyModifiers::empty()) {
+            self.bind_simple(context.clone(), KeyCode::Enter, Command::Confirm);
+        }
         self.bind_simple(context.clone(), KeyCode::F(1), Command::ToggleHelp);
+        self.bind_simple(context.clone(), KeyCode::F(12), Command::ToggleDebugOverlay);
     }
 
     fn register_log_view_bindings(&mut self) {
@@ -185,6 +274,30 @@ impl KeybindingRegistry {
         self.bind_simple(context.clone(), KeyCode::Char('u'), Command::PageUp);
         self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
         self.bind_simple(context.clone(), KeyCode::Char('d'), Command::PageDown);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL,
+            Command::HalfPageUp,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('d'),
+            KeyModifiers::CONTROL,
+            Command::HalfPageDown,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('y'),
+            KeyModifiers::CONTROL,
+            Command::ScrollUp,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('e'),
+            KeyModifiers::CONTROL,
+            Command::ScrollDown,
+        );
         self.bind_simple(context.clone(), KeyCode::Char('g'), Command::GotoTop);
         self.bind_shift(context.clone(), 'G', Command::GotoBottom);
         self.bind_simple(context.clone(), KeyCode::Char('z'), Command::CenterSelected);
@@ -205,6 +318,7 @@ impl KeybindingRegistry {
         self.bind_simple(context.clone(), KeyCode::Char('h'), Command::ScrollLeft);
         self.bind_simple(context.clone(), KeyCode::Char('l'), Command::ScrollRight);
         self.bind_simple(context.clone(), KeyCode::Char('0'), Command::ResetHorizontal);
+        self.bind_simple(context.clone(), KeyCode::Char('$'), Command::ScrollToLineEnd);
         self.bind_simple(context.clone(), KeyCode::Char('/'), Command::ActivateActiveSearchMode);
         self.bind(
             context.clone(),
@@ -214,20 +328,115 @@ impl KeybindingRegistry {
         );
         self.bind_simple(context.clone(), KeyCode::Char('n'), Command::SearchNext);
         self.bind_shift(context.clone(), 'N', Command::SearchPrevious);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('n'),
+            KeyModifiers::ALT,
+            Command::ToggleSearchScope,
+        );
         self.bind_simple(context.clone(), KeyCode::Char('f'), Command::ActivateActiveFilterMode);
         self.bind_shift(context.clone(), 'F', Command::ActivateFilterView);
+        self.bind_simple(context.clone(), KeyCode::Char('*'), Command::SearchWordUnderCursor);
+        self.bind_simple(context.clone(), KeyCode::Char('#'), Command::FilterWordUnderCursor);
+        self.bind_simple(context.clone(), KeyCode::Char('b'), Command::ActivateQuickFilterView);
+        self.bind_shift(context.clone(), 'I', Command::SearchToFilter);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('m'),
+            KeyModifiers::ALT,
+            Command::MarkWordUnderCursor,
+        );
         self.bind_simple(context.clone(), KeyCode::Char(':'), Command::ActivateGotoLineMode);
+        self.bind_simple(context.clone(), KeyCode::Char('@'), Command::ActivateGotoTimeMode);
         self.bind_simple(context.clone(), KeyCode::Char('o'), Command::ActivateOptionsView);
         self.bind_simple(context.clone(), KeyCode::Char('e'), Command::ActivateEventsView);
+        self.bind_shift(context.clone(), 'T', Command::ActivateTimelineView);
+        self.bind_shift(context.clone(), 'L', Command::ActivateLevelsView);
+        self.bind_shift(context.clone(), 'C', Command::ActivateColumnsView);
+        self.bind_shift(context.clone(), 'Q', Command::ActivateQueryMode);
+        self.bind_shift(context.clone(), 'P', Command::ActivatePatternsView);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('x'),
+            KeyModifiers::ALT,
+            Command::ActivateNoiseView,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('c'),
+            KeyModifiers::ALT,
+            Command::ToggleColumnView,
+        );
         self.bind_simple(context.clone(), KeyCode::Char(' '), Command::ToggleMark);
         self.bind_simple(context.clone(), KeyCode::Char('m'), Command::ActivateMarksView);
+        self.bind_shift(context.clone(), 'M', Command::ActivateSetRegister);
+        self.bind_simple(context.clone(), KeyCode::Char('`'), Command::ActivateJumpToRegister);
+        self.bind_simple(context.clone(), KeyCode::Delete, Command::HideLines);
+        self.bind_shift(context.clone(), 'U', Command::UnhideAllLines);
         self.bind_simple(context.clone(), KeyCode::Char('i'), Command::ActivateFilesView);
+        self.bind_shift(context.clone(), 'R', Command::ReloadFile);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('s'),
+            KeyModifiers::ALT,
+            Command::ActivateSaveSessionMode,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('o'),
+            KeyModifiers::ALT,
+            Command::ActivateSessionsView,
+        );
         self.bind_simple(context.clone(), KeyCode::Char(']'), Command::MarkNext);
         self.bind_simple(context.clone(), KeyCode::Char('['), Command::MarkPrevious);
         self.bind_simple(context.clone(), KeyCode::Char('}'), Command::EventNext);
         self.bind_simple(context.clone(), KeyCode::Char('{'), Command::EventPrevious);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('g'),
+            KeyModifiers::ALT,
+            Command::ActivateEventScopeView,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('e'),
+            KeyModifiers::ALT,
+            Command::ErrorEventNext,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('w'),
+            KeyModifiers::ALT,
+            Command::WarningEventNext,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('i'),
+            KeyModifiers::ALT,
+            Command::InfoEventNext,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('d'),
+            KeyModifiers::ALT,
+            Command::DebugEventNext,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('j'),
+            KeyModifiers::ALT,
+            Command::MinimapNext,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('k'),
+            KeyModifiers::ALT,
+            Command::MinimapPrevious,
+        );
         self.bind_simple(context.clone(), KeyCode::Char(')'), Command::ContextNext);
         self.bind_simple(context.clone(), KeyCode::Char('('), Command::ContextPrevious);
+        self.bind_simple(context.clone(), KeyCode::Char('>'), Command::SimilarNext);
+        self.bind_simple(context.clone(), KeyCode::Char('<'), Command::SimilarPrevious);
         self.bind(
             context.clone(),
             KeyCode::Char('f'),
@@ -239,6 +448,26 @@ impl KeybindingRegistry {
         self.bind_simple(context.clone(), KeyCode::Char('c'), Command::ToggleCenterCursorMode);
         self.bind_simple(context.clone(), KeyCode::Char('t'), Command::ToggleFollowMode);
         self.bind_simple(context.clone(), KeyCode::Char('p'), Command::TogglePauseMode);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('p'),
+            KeyModifiers::ALT,
+            Command::ToggleFreezeDisplay,
+        );
+        self.bind_shift(context.clone(), 'W', Command::ToggleWatches);
+        self.bind_shift(context.clone(), 'A', Command::ActivateAlertsView);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('b'),
+            KeyModifiers::ALT,
+            Command::ToggleMuteNotifications,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('t'),
+            KeyModifiers::ALT,
+            Command::ActivateCorrelateMode,
+        );
         self.bind(
             context.clone(),
             KeyCode::Char('l'),
@@ -258,13 +487,83 @@ impl KeybindingRegistry {
             Command::HistoryBack,
         );
         self.bind_simple(context.clone(), KeyCode::Tab, Command::HistoryForward);
+        self.bind_shift(context.clone(), 'H', Command::ActivateJumpHistoryView);
+        self.bind_shift(context.clone(), 'B', Command::ActivateRestartsView);
         self.bind_shift(context.clone(), 'V', Command::StartSelection);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('v'),
+            KeyModifiers::CONTROL,
+            Command::StartBlockSelection,
+        );
+        self.bind_shift(context.clone(), 'S', Command::SplitView);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('v'),
+            KeyModifiers::ALT,
+            Command::PinSplitView,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('w'),
+            KeyModifiers::CONTROL,
+            Command::SwitchSplitFocus,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('x'),
+            KeyModifiers::CONTROL,
+            Command::CloseSplit,
+        );
         self.bind(
             context.clone(),
             KeyCode::Char('a'),
             KeyModifiers::CONTROL,
             Command::ToggleAllFilterPatterns,
         );
+        self.bind_simple(context.clone(), KeyCode::Char('|'), Command::ActivatePipeCommandMode);
+        self.bind_simple(context.clone(), KeyCode::Char('r'), Command::ActivateRegexTester);
+        self.bind_shift(context.clone(), 'E', Command::OpenInEditor);
+        self.bind_simple(context.clone(), KeyCode::Char('a'), Command::ActivateActionsView);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('a'),
+            KeyModifiers::ALT,
+            Command::ActivateScriptsView,
+        );
+        self.bind_simple(context.clone(), KeyCode::Enter, Command::ActivateLineInspector);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('l'),
+            KeyModifiers::ALT,
+            Command::ActivateFullLineView,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('h'),
+            KeyModifiers::ALT,
+            Command::ActivateHexView,
+        );
+        self.bind_shift(context.clone(), 'Y', Command::CopyViewAs);
+        self.bind_shift(context.clone(), 'D', Command::ActivateFormatView);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('p'),
+            KeyModifiers::CONTROL,
+            Command::ActivateProfilesView,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('r'),
+            KeyModifiers::CONTROL,
+            Command::RestartCommand,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('n'),
+            KeyModifiers::CONTROL,
+            Command::ActivateNotesView,
+        );
     }
 
     fn register_selection_mode_bindings(&mut self) {
@@ -277,14 +576,46 @@ impl KeybindingRegistry {
         self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
         self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
         self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL,
+            Command::HalfPageUp,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('d'),
+            KeyModifiers::CONTROL,
+            Command::HalfPageDown,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('y'),
+            KeyModifiers::CONTROL,
+            Command::ScrollUp,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('e'),
+            KeyModifiers::CONTROL,
+            Command::ScrollDown,
+        );
         self.bind_simple(context.clone(), KeyCode::Char('g'), Command::GotoTop);
         self.bind_shift(context.clone(), 'G', Command::GotoBottom);
         self.bind_simple(context.clone(), KeyCode::Char('y'), Command::CopySelection);
+        self.bind_shift(context.clone(), 'Y', Command::CopySelectionAs);
+        self.bind_simple(context.clone(), KeyCode::Char('i'), Command::MeasureInterval);
         self.bind_simple(context.clone(), KeyCode::Char(' '), Command::ToggleMark);
+        self.bind_simple(context.clone(), KeyCode::Delete, Command::HideLines);
+        self.bind_simple(context.clone(), KeyCode::Left, Command::SelectionColumnLeft);
+        self.bind_simple(context.clone(), KeyCode::Right, Command::SelectionColumnRight);
+        self.bind_simple(context.clone(), KeyCode::Char('h'), Command::SelectionColumnLeft);
+        self.bind_simple(context.clone(), KeyCode::Char('l'), Command::SelectionColumnRight);
         self.bind_simple(context.clone(), KeyCode::Char(']'), Command::SelectToMarkNext);
         self.bind_simple(context.clone(), KeyCode::Char('['), Command::SelectToMarkPrevious);
         self.bind_simple(context.clone(), KeyCode::Char('}'), Command::SelectToEventNext);
         self.bind_simple(context.clone(), KeyCode::Char('{'), Command::SelectToEventPrevious);
+        self.bind_simple(context.clone(), KeyCode::Char('|'), Command::ActivatePipeCommandMode);
     }
 
     fn register_search_mode_bindings(&mut self) {
@@ -297,8 +628,32 @@ impl KeybindingRegistry {
             KeyModifiers::ALT,
             Command::ToggleCaseSearch,
         );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('f'),
+            KeyModifiers::ALT,
+            Command::ToggleSearchScope,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('z'),
+            KeyModifiers::ALT,
+            Command::ToggleFuzzySearch,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('w'),
+            KeyModifiers::ALT,
+            Command::ToggleWholeWord,
+        );
         self.bind_simple(context.clone(), KeyCode::Up, Command::SearchHistoryPrevious);
         self.bind_simple(context.clone(), KeyCode::Down, Command::SearchHistoryNext);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('r'),
+            KeyModifiers::CONTROL,
+            Command::ActivateHistoryView,
+        );
     }
 
     fn register_filter_mode_bindings(&mut self) {
@@ -317,8 +672,20 @@ impl KeybindingRegistry {
             KeyModifiers::ALT,
             Command::ToggleActiveFilterModeInOut,
         );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('w'),
+            KeyModifiers::ALT,
+            Command::ToggleWholeWord,
+        );
         self.bind_simple(context.clone(), KeyCode::Up, Command::FilterHistoryPrevious);
         self.bind_simple(context.clone(), KeyCode::Down, Command::FilterHistoryNext);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('r'),
+            KeyModifiers::CONTROL,
+            Command::ActivateHistoryView,
+        );
     }
 
     fn register_filter_list_bindings(&mut self) {
@@ -345,12 +712,29 @@ impl KeybindingRegistry {
             KeyModifiers::ALT,
             Command::ToggleFilterPatternCaseSensitive,
         );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('w'),
+            KeyModifiers::ALT,
+            Command::ToggleFilterPatternWholeWord,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('f'),
+            KeyModifiers::ALT,
+            Command::CycleFilterPatternFile,
+        );
         self.bind(
             context.clone(),
             KeyCode::Char('e'),
             KeyModifiers::ALT,
             Command::ToggleFilterPatternMode,
         );
+        self.bind_simple(context.clone(), KeyCode::Char('y'), Command::CopyFiltersToClipboard);
+        self.bind_simple(context.clone(), KeyCode::Char('w'), Command::ActivateExportFiltersMode);
+        self.bind_shift(context.clone(), 'W', Command::WriteFiltersFile);
+        self.bind_shift(context.clone(), 'E', Command::FilterToEvent);
+        self.bind_simple(context.clone(), KeyCode::Char('s'), Command::FilterToSearch);
     }
 
     fn register_options_view_bindings(&mut self) {
@@ -369,7 +753,18 @@ impl KeybindingRegistry {
 
         self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
         self.bind_shift(context.clone(), 'F', Command::ActivateEventFilterView);
+        self.bind_simple(context.clone(), KeyCode::Char('/'), Command::ActivateListFilter);
+        self.bind_simple(context.clone(), KeyCode::Tab, Command::NextEventsTab);
+        self.bind(
+            context.clone(),
+            KeyCode::BackTab,
+            KeyModifiers::SHIFT,
+            Command::PreviousEventsTab,
+        );
+        // Some terminals report Shift+Tab without the shift modifier
+        self.bind_simple(context.clone(), KeyCode::BackTab, Command::PreviousEventsTab);
         self.bind_shift(context.clone(), 'M', Command::ToggleEventsShowMarks);
+        self.bind_simple(context.clone(), KeyCode::Char('p'), Command::ToggleEventsPreview);
         self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
         self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
         self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
@@ -379,10 +774,15 @@ impl KeybindingRegistry {
         self.bind_simple(context.clone(), KeyCode::Char(' '), Command::GotoSelectedEvent);
         self.bind_simple(context.clone(), KeyCode::Char('e'), Command::ActivateMarkNameMode);
         self.bind_simple(context.clone(), KeyCode::Char('m'), Command::ToggleMark);
+        self.bind_simple(context.clone(), KeyCode::Char('v'), Command::ToggleEventTag);
+        self.bind_shift(context.clone(), 'V', Command::ToggleAllEventTags);
+        self.bind_simple(context.clone(), KeyCode::Char('c'), Command::MarkTaggedEvents);
         self.bind_simple(context.clone(), KeyCode::Char('t'), Command::ToggleFollowMode);
         self.bind_simple(context.clone(), KeyCode::Char('a'), Command::ActivateAddCustomEventMode);
         self.bind_simple(context.clone(), KeyCode::Char('d'), Command::RemoveCustomEvent);
         self.bind_simple(context.clone(), KeyCode::Delete, Command::RemoveCustomEvent);
+        self.bind_simple(context.clone(), KeyCode::Char('w'), Command::SaveCustomEventToConfig);
+        self.bind_shift(context.clone(), 'X', Command::ActivateExportReportMode);
         self.bind(
             context.clone(),
             KeyCode::Char('l'),
@@ -404,12 +804,23 @@ impl KeybindingRegistry {
         self.bind_simple(context.clone(), KeyCode::Char(' '), Command::ToggleEventFilter);
         self.bind_simple(context.clone(), KeyCode::Char('a'), Command::ToggleAllEventFilters);
         self.bind_simple(context.clone(), KeyCode::Char('s'), Command::SoloEventFilter);
+        self.bind_simple(context.clone(), KeyCode::Char('f'), Command::EventToFilter);
         self.bind_simple(context.clone(), KeyCode::Char('d'), Command::RemoveCustomEvent);
         self.bind_simple(context.clone(), KeyCode::Delete, Command::RemoveCustomEvent);
+        self.bind_simple(context.clone(), KeyCode::Char('w'), Command::SaveCustomEventToConfig);
         self.bind_simple(context.clone(), KeyCode::Char('}'), Command::EventNext);
         self.bind_simple(context.clone(), KeyCode::Char('{'), Command::EventPrevious);
     }
 
+    fn register_list_filter_bindings(&mut self) {
+        let context = KeybindingContext::Overlay(Overlay::ListFilter);
+
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
     fn register_marks_view_bindings(&mut self) {
         let context = KeybindingContext::View(ViewState::MarksView);
 
@@ -420,11 +831,18 @@ impl KeybindingRegistry {
         self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
         self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
         self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
-        self.bind_simple(context.clone(), KeyCode::Char(' '), Command::GotoSelectedMark);
+        self.bind_simple(context.clone(), KeyCode::Char(' '), Command::ToggleMarkSelection);
+        self.bind_shift(context.clone(), 'V', Command::ToggleAllMarkSelections);
+        self.bind_simple(context.clone(), KeyCode::Char('o'), Command::GotoSelectedMark);
+        self.bind_simple(context.clone(), KeyCode::Char('g'), Command::ToggleMarksGrouping);
+        self.bind_simple(context.clone(), KeyCode::Char('/'), Command::ActivateListFilter);
         self.bind_simple(context.clone(), KeyCode::Delete, Command::UnmarkSelected);
         self.bind_simple(context.clone(), KeyCode::Char('d'), Command::UnmarkSelected);
         self.bind_simple(context.clone(), KeyCode::Char('e'), Command::ActivateMarkNameMode);
         self.bind_simple(context.clone(), KeyCode::Char('c'), Command::ClearAllMarks);
+        self.bind_simple(context.clone(), KeyCode::Char('i'), Command::MeasureInterval);
+        self.bind_simple(context.clone(), KeyCode::Char('y'), Command::CopyMarkedLinesAs);
+        self.bind_shift(context.clone(), 'X', Command::ActivateExportReportMode);
         self.bind_shift(context.clone(), 'F', Command::ToggleShowMarkedOnly)
     }
 
@@ -442,21 +860,436 @@ impl KeybindingRegistry {
         self.bind_simple(context.clone(), KeyCode::Char('a'), Command::ActivateAddFileMode);
     }
 
+    fn register_sessions_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::SessionsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_history_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::HistoryView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_alerts_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::AlertsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_correlation_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::CorrelationView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind_simple(context.clone(), KeyCode::Char('f'), Command::CorrelationToFilter);
+    }
+
+    /// Letters pick a register in the registers view, so the list is only navigated with the arrow keys.
+    fn register_registers_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::RegistersView);
+
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind_simple(context.clone(), KeyCode::Delete, Command::RemoveRegister);
+    }
+
+    fn register_jump_history_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::JumpHistoryView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_restarts_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::RestartsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_full_line_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::FullLineView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_hex_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::HexView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_format_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::FormatView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+    }
+
+    fn register_profiles_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::ProfilesView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+    }
+
+    fn register_stale_filters_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::StaleFiltersView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char(' '), Command::DisableStaleFilters);
+        self.bind_simple(context.clone(), KeyCode::Delete, Command::RemoveStaleFilters);
+        self.bind_simple(context.clone(), KeyCode::Char('d'), Command::RemoveStaleFilters);
+    }
+
+    fn register_quick_filter_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::QuickFilterView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('l'), Command::QuickFilterLevel);
+        self.bind_simple(context.clone(), KeyCode::Char('m'), Command::QuickFilterField);
+        self.bind_simple(context.clone(), KeyCode::Char('t'), Command::QuickFilterTemplate);
+        self.bind_simple(context.clone(), KeyCode::Char('f'), Command::QuickFilterFile);
+        self.bind_simple(context.clone(), KeyCode::Tab, Command::ToggleQuickFilterMode);
+    }
+
+    fn register_actions_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::ActionsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_scripts_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::ScriptsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_inspector_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::InspectorView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind_simple(context.clone(), KeyCode::Char('y'), Command::CopyLineDetail);
+    }
+
+    fn register_pipe_output_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::PipeOutputView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_query_results_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::QueryResultsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind_simple(context.clone(), KeyCode::Char('m'), Command::MarkQueryRow);
+    }
+
+    fn register_patterns_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::PatternsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind_simple(context.clone(), KeyCode::Char('i'), Command::PatternToIncludeFilter);
+        self.bind_simple(context.clone(), KeyCode::Char('x'), Command::PatternToExcludeFilter);
+    }
+
+    fn register_noise_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::NoiseView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind_simple(context.clone(), KeyCode::Char('x'), Command::ExcludeNoiseSuggestion);
+    }
+
+    fn register_timeline_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::TimelineView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Left, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Right, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('h'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('l'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char(' '), Command::GotoSelectedTimelineBucket);
+    }
+
+    fn register_levels_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::LevelsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+    }
+
+    fn register_event_scope_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::EventScopeView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_copy_format_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::CopyFormatView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+    }
+
+    fn register_columns_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::ColumnsView);
+
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char(' '), Command::ToggleColumn);
+        self.bind_simple(context.clone(), KeyCode::Char('s'), Command::CycleColumnSort);
+        self.bind_simple(context.clone(), KeyCode::Char('c'), Command::ToggleColumnView);
+    }
+
+    fn register_notes_view_bindings(&mut self) {
+        let context = KeybindingContext::View(ViewState::NotesView);
+
+        self.bind(
+            context.clone(),
+            KeyCode::Char('g'),
+            KeyModifiers::CONTROL,
+            Command::GotoNoteReference,
+        );
+    }
+
+    fn register_regex_tester_bindings(&mut self) {
+        let context = KeybindingContext::Overlay(Overlay::RegexTester);
+
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind(
+            context.clone(),
+            KeyCode::Char('a'),
+            KeyModifiers::ALT,
+            Command::ToggleRegexTesterCase,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('f'),
+            KeyModifiers::ALT,
+            Command::RegexTesterAddFilter,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('e'),
+            KeyModifiers::ALT,
+            Command::RegexTesterAddEvent,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('h'),
+            KeyModifiers::ALT,
+            Command::RegexTesterAddHighlight,
+        );
+        self.bind(
+            context.clone(),
+            KeyCode::Char('w'),
+            KeyModifiers::ALT,
+            Command::RegexTesterAddWatch,
+        );
+    }
+
     fn register_message_state_bindings(&mut self) {
         let context = KeybindingContext::Overlay(Overlay::Message(String::new()));
 
-        self.bind_simple(context, KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.register_message_scroll_bindings(context);
     }
 
     fn register_error_state_bindings(&mut self) {
         let context = KeybindingContext::Overlay(Overlay::Error(String::new()));
 
-        self.bind_simple(context, KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.register_message_scroll_bindings(context);
     }
 
     fn register_fatal_state_bindings(&mut self) {
         let context = KeybindingContext::Overlay(Overlay::Fatal(String::new()));
 
-        self.bind_simple(context, KeyCode::Char('q'), Command::Quit);
+        self.bind_simple(context.clone(), KeyCode::Char('q'), Command::Quit);
+        self.register_message_scroll_bindings(context);
+    }
+
+    fn register_message_scroll_bindings(&mut self, context: KeybindingContext) {
+        self.bind_simple(context.clone(), KeyCode::Up, Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Down, Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::Char('k'), Command::MoveUp);
+        self.bind_simple(context.clone(), KeyCode::Char('j'), Command::MoveDown);
+        self.bind_simple(context.clone(), KeyCode::PageUp, Command::PageUp);
+        self.bind_simple(context.clone(), KeyCode::PageDown, Command::PageDown);
+        self.bind_simple(context, KeyCode::Char('y'), Command::CopyMessage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_no_duplicate_bindings_in_a_context() {
+        let registry = KeybindingRegistry::new();
+        let mut seen = HashSet::new();
+        let duplicates: Vec<String> = registry
+            .bindings
+            .iter()
+            .filter(|(key, _)| !seen.insert(key.clone()))
+            .map(|((context, keycode, modifiers), cmd)| {
+                format!(
+                    "{:?}: {} -> {:?}",
+                    context,
+                    KeybindingRegistry::format_key(*keycode, *modifiers),
+                    cmd
+                )
+            })
+            .collect();
+        assert!(duplicates.is_empty(), "Duplicate bindings:\n{}", duplicates.join("\n"));
+    }
+
+    #[test]
+    fn test_back_tab_with_or_without_shift() {
+        let registry = KeybindingRegistry::new();
+        for modifiers in [KeyModifiers::SHIFT, KeyModifiers::NONE] {
+            assert_eq!(
+                registry.lookup(
+                    &ViewState::EventsView,
+                    &None,
+                    KeyEvent::new(KeyCode::BackTab, modifiers)
+                ),
+                Some(Command::PreviousEventsTab)
+            );
+        }
+
+        let previous_tab_keys: Vec<String> = registry
+            .get_keybindings_for_context(&KeybindingContext::View(ViewState::EventsView))
+            .into_iter()
+            .filter(|(_, cmd)| *cmd == Command::PreviousEventsTab)
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(previous_tab_keys, vec!["Shift+Tab"]);
     }
 }
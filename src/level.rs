@@ -0,0 +1,212 @@
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::log::LogLine;
+use crate::resolver::VisibilityRule;
+
+/// Severity of a log line, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    #[serde(alias = "warning")]
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// All levels from least to most severe.
+    pub const ALL: [LogLevel; 5] = [
+        LogLevel::Trace,
+        LogLevel::Debug,
+        LogLevel::Info,
+        LogLevel::Warn,
+        LogLevel::Error,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    /// Default pattern used to detect the level.
+    fn default_pattern(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => r"\bTRACE\b",
+            LogLevel::Debug => r"\bDEBUG\b",
+            LogLevel::Info => r"\bINFO\b",
+            LogLevel::Warn => r"\bWARN(?:ING)?\b",
+            LogLevel::Error => r"\b(?:ERROR|FATAL|CRITICAL)\b",
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    /// Parses a level name, ignoring case, e.g. `warn` or `WARNING`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            _ => Err(format!(
+                "unknown level '{}', expected trace, debug, info, warn or error",
+                s
+            )),
+        }
+    }
+}
+
+/// Detects the log level of lines.
+///
+/// All level patterns are combined into one regex, so the leftmost level token of a line wins.
+#[derive(Debug, Clone)]
+pub struct LevelDetector {
+    regex: Regex,
+    /// Pattern of each level, in the order of [`LogLevel::ALL`].
+    patterns: Vec<String>,
+}
+
+impl LevelDetector {
+    /// Creates a detector from one pattern per level, in the order of [`LogLevel::ALL`].
+    /// Levels without a pattern use the default pattern.
+    pub fn new(patterns: [Option<&str>; 5]) -> Result<Self, regex::Error> {
+        let patterns: Vec<String> = LogLevel::ALL
+            .iter()
+            .zip(patterns)
+            .map(|(level, pattern)| pattern.unwrap_or(level.default_pattern()).to_string())
+            .collect();
+        let groups: Vec<String> = LogLevel::ALL
+            .iter()
+            .zip(&patterns)
+            .map(|(level, pattern)| format!("(?P<{}>{})", level.name(), pattern))
+            .collect();
+        Ok(Self {
+            regex: Regex::new(&groups.join("|"))?,
+            patterns,
+        })
+    }
+
+    /// Returns the pattern detecting the level.
+    pub fn pattern(&self, level: LogLevel) -> &str {
+        let index = LogLevel::ALL.iter().position(|&l| l == level).unwrap_or_default();
+        &self.patterns[index]
+    }
+
+    /// Returns the level of the line, if it contains one.
+    pub fn detect(&self, line: &str) -> Option<LogLevel> {
+        let caps = self.regex.captures(line)?;
+        LogLevel::ALL
+            .iter()
+            .copied()
+            .find(|level| caps.name(level.name()).is_some())
+    }
+}
+
+impl Default for LevelDetector {
+    fn default() -> Self {
+        Self::new([None; 5]).expect("default level patterns are valid")
+    }
+}
+
+/// Counts lines per level, in the order of [`LogLevel::ALL`], followed by lines without a level.
+pub fn count_levels(lines: &[LogLine]) -> [usize; 6] {
+    let mut counts = [0; 6];
+    for line in lines {
+        let slot = line.level.map_or(LogLevel::ALL.len(), |level| level as usize);
+        counts[slot] += 1;
+    }
+    counts
+}
+
+/// Rule that hides lines below a minimum level. Lines without a level are always shown.
+pub struct LevelVisibilityRule {
+    min_level: LogLevel,
+}
+
+impl LevelVisibilityRule {
+    pub fn new(min_level: LogLevel) -> Self {
+        Self { min_level }
+    }
+}
+
+impl VisibilityRule for LevelVisibilityRule {
+    fn is_visible(&self, line: &LogLine) -> bool {
+        line.level.is_none_or(|level| level >= self.min_level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_default_levels() {
+        let detector = LevelDetector::default();
+        assert_eq!(
+            detector.detect("2024-01-01 12:00:00 INFO started"),
+            Some(LogLevel::Info)
+        );
+        assert_eq!(detector.detect("[WARNING] disk almost full"), Some(LogLevel::Warn));
+        assert_eq!(detector.detect("FATAL out of memory"), Some(LogLevel::Error));
+        assert_eq!(detector.detect("no level here, INFORMATION only"), None);
+    }
+
+    #[test]
+    fn test_leftmost_level_wins() {
+        let detector = LevelDetector::default();
+        assert_eq!(detector.detect("DEBUG retrying after ERROR"), Some(LogLevel::Debug));
+    }
+
+    #[test]
+    fn test_custom_patterns() {
+        let detector = LevelDetector::new([None, None, Some(r"\bI\b"), Some(r"\bW\b"), Some(r"\bE\b")]).unwrap();
+        assert_eq!(detector.detect("12:00:00 E main: crashed"), Some(LogLevel::Error));
+        assert_eq!(detector.detect("12:00:00 I main: started"), Some(LogLevel::Info));
+        assert_eq!(
+            detector.detect("12:00:00 DEBUG main: still default"),
+            Some(LogLevel::Debug)
+        );
+        assert!(LevelDetector::new([Some("("), None, None, None, None]).is_err());
+    }
+
+    #[test]
+    fn test_level_visibility_rule() {
+        let rule = LevelVisibilityRule::new(LogLevel::Warn);
+        let mut line = LogLine::new("message", 0);
+        assert!(rule.is_visible(&line));
+
+        line.level = Some(LogLevel::Info);
+        assert!(!rule.is_visible(&line));
+
+        line.level = Some(LogLevel::Error);
+        assert!(rule.is_visible(&line));
+    }
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!("warn".parse(), Ok(LogLevel::Warn));
+        assert_eq!("WARNING".parse(), Ok(LogLevel::Warn));
+        assert_eq!("Error".parse(), Ok(LogLevel::Error));
+        assert!("fatal".parse::<LogLevel>().is_err());
+    }
+}
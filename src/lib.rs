@@ -1,32 +1,76 @@
 shadow_rs::shadow!(build);
 
+pub mod action;
+pub mod alert;
+pub mod ansi;
 pub mod app;
+pub mod child_process;
 pub mod cli;
+pub mod clipboard;
 pub mod command;
 pub mod completion;
 pub mod config;
+pub mod config_watch;
+pub mod correlation;
 pub mod debug_log;
+pub mod debug_stats;
+pub mod dedup;
 pub mod event;
 pub mod event_mark_view;
+pub mod event_pairs;
 pub mod expansion;
+pub mod fields;
 pub mod file_manager;
 pub mod filter;
+pub mod filter_task;
+pub mod format;
+pub mod fuzzy;
+pub mod global_history;
+pub mod headless;
 pub mod help;
+pub mod hex;
 pub mod highlighter;
 pub mod history;
+pub mod inspector;
+pub mod interval;
 pub mod keybindings;
+pub mod level;
 pub mod list_view_state;
 pub mod live_processor;
+pub mod load_task;
 pub mod log;
 pub mod log_event;
 pub mod marking;
 pub mod matcher;
+pub mod migration;
+pub mod minimap;
+pub mod notes;
 pub mod options;
+pub mod pager;
 pub mod persistence;
+pub mod pipe_task;
+pub mod preview_task;
+pub mod query;
+pub mod quick_filter;
+pub mod regex_tester;
+pub mod registers;
+pub mod report;
 pub mod resolver;
+pub mod restarts;
+pub mod script;
 pub mod search;
+pub mod search_task;
+pub mod session;
+pub mod shell;
+pub mod sticky_header;
+pub mod stream_stats;
+pub mod task;
+pub mod template;
+pub mod theme;
+pub mod timeline;
 pub mod timestamp;
 pub mod ui;
 pub mod utils;
 pub mod version;
 pub mod viewport;
+pub mod watch;
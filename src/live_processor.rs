@@ -1,26 +1,48 @@
+use crate::fields::FieldExtractor;
 use crate::filter::{FilterPattern, apply_filters};
+use crate::log::OutputStream;
+use crate::utils::CaseFolding;
 use rayon::prelude::*;
-use std::{sync::Arc, time::Duration};
-use tokio::{
-    sync::mpsc,
-    time::{MissedTickBehavior, interval},
-};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Maximum number of lines processed as one batch.
+const MAX_BATCH_LINES: usize = 10_000;
+
+/// A raw line received from stdin or a spawned command.
+#[derive(Debug, Clone)]
+pub struct InputLine {
+    pub content: String,
+    /// Output stream of the spawned command, or `None` for stdin.
+    pub stream: Option<OutputStream>,
+}
+
+impl From<String> for InputLine {
+    fn from(content: String) -> Self {
+        Self { content, stream: None }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ProcessedLine {
     pub line_content: String,
     pub passes_filter: bool,
+    pub stream: Option<OutputStream>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct ProcessingContext {
     pub filter_patterns: Vec<FilterPattern>,
+    /// Extractor for the fields of `@field:value` filters, if configured.
+    pub field_extractor: Option<FieldExtractor>,
+    /// How filters matching case-insensitively fold the case of characters.
+    pub case_folding: CaseFolding,
     pub search_pattern: Option<String>,
     pub search_case_sensitive: bool,
 }
 
 pub struct LiveProcessor {
-    input_rx: mpsc::UnboundedReceiver<String>,
+    input_rx: mpsc::UnboundedReceiver<InputLine>,
     output_tx: mpsc::UnboundedSender<Vec<ProcessedLine>>,
     context_rx: mpsc::UnboundedReceiver<ProcessingContext>,
     current_context: ProcessingContext,
@@ -28,7 +50,7 @@ pub struct LiveProcessor {
 
 impl LiveProcessor {
     pub fn new(
-        input_rx: mpsc::UnboundedReceiver<String>,
+        input_rx: mpsc::UnboundedReceiver<InputLine>,
         output_tx: mpsc::UnboundedSender<Vec<ProcessedLine>>,
         context_rx: mpsc::UnboundedReceiver<ProcessingContext>,
     ) -> Self {
@@ -40,13 +62,10 @@ impl LiveProcessor {
         }
     }
 
+    /// Processes lines as they arrive. Lines that queued up while a batch was processed are
+    /// processed together, so batches grow with the throughput instead of sending one per line.
     pub async fn run(mut self) {
-        const BATCH_SIZE: usize = 5;
-        const BATCH_TIMEOUT_MS: u64 = 100;
-
-        let mut batched_lines = Vec::with_capacity(BATCH_SIZE);
-        let mut interval = interval(Duration::from_millis(BATCH_TIMEOUT_MS));
-        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut batched_lines = Vec::new();
 
         loop {
             tokio::select! {
@@ -60,39 +79,28 @@ impl LiveProcessor {
                     self.current_context = new_context;
                 }
 
-                _ = interval.tick() => {
-                    if !batched_lines.is_empty()
-                        && let Some(processed) = self.process(&mut batched_lines)
-                            && self.output_tx.send(processed).is_err() {
-                                break;
-                            }
-                }
-
                 result = self.input_rx.recv() => {
-                    match result {
-                        Some(line) => {
-                            batched_lines.push(line);
-
-                            if batched_lines.len() >= BATCH_SIZE
-                                && let Some(processed) = self.process(&mut batched_lines)
-                                    && self.output_tx.send(processed).is_err() {
-                                        break;
-                                    }
-                        }
-                        None => { // processor is being shut down, process remaining lines
-                            if !batched_lines.is_empty()
-                                && let Some(processed) = self.process(&mut batched_lines) {
-                                    let _ = self.output_tx.send(processed);
-                                }
-                            break;
-                        }
+                    let Some(line) = result else {
+                        break;
+                    };
+                    batched_lines.push(line);
+                    while batched_lines.len() < MAX_BATCH_LINES
+                        && let Ok(line) = self.input_rx.try_recv()
+                    {
+                        batched_lines.push(line);
+                    }
+
+                    if let Some(processed) = self.process(&mut batched_lines)
+                        && self.output_tx.send(processed).is_err()
+                    {
+                        break;
                     }
                 }
             }
         }
     }
 
-    fn process(&self, batch: &mut Vec<String>) -> Option<Vec<ProcessedLine>> {
+    fn process(&self, batch: &mut Vec<InputLine>) -> Option<Vec<ProcessedLine>> {
         if batch.is_empty() {
             return None;
         }
@@ -102,12 +110,19 @@ impl LiveProcessor {
 
         let processed: Vec<ProcessedLine> = batch
             .par_drain(..)
-            .map(|line_content| {
-                let passes_filter = apply_filters(&line_content, &filter_patterns);
+            .map(|line| {
+                let passes_filter = apply_filters(
+                    &line.content,
+                    None,
+                    &filter_patterns,
+                    context.field_extractor.as_ref(),
+                    context.case_folding,
+                );
 
                 ProcessedLine {
-                    line_content,
+                    line_content: line.content,
                     passes_filter,
+                    stream: line.stream,
                 }
             })
             .collect();
@@ -118,7 +133,7 @@ impl LiveProcessor {
 
 #[derive(Debug)]
 pub struct LiveProcessorHandle {
-    pub input_tx: mpsc::UnboundedSender<String>,
+    pub input_tx: mpsc::UnboundedSender<InputLine>,
     pub context_tx: mpsc::UnboundedSender<ProcessingContext>,
 }
 
@@ -141,6 +156,31 @@ impl LiveProcessorHandle {
     }
 
     pub fn send_line(&self, line: String) {
-        let _ = self.input_tx.send(line);
+        let _ = self.input_tx.send(line.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_queued_lines_are_processed_in_batches() {
+        let (output_tx, mut output_rx) = mpsc::unbounded_channel();
+        let processor = LiveProcessorHandle::spawn(output_tx);
+        let line_count = 2 * MAX_BATCH_LINES + 5;
+        for i in 0..line_count {
+            processor.send_line(format!("line {}", i));
+        }
+
+        // The lines queued up before the processor ran, so they are processed in as few batches as possible
+        let mut batches = Vec::new();
+        while batches.iter().map(Vec::len).sum::<usize>() < line_count {
+            batches.push(output_rx.recv().await.unwrap());
+        }
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [MAX_BATCH_LINES, MAX_BATCH_LINES, 5]);
+        assert_eq!(batches[2][4].line_content, format!("line {}", line_count - 1));
+        assert!(batches.iter().flatten().all(|line| line.passes_filter));
     }
 }
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use crate::log::{LogBuffer, LogLine};
+use crate::task::TaskProgress;
+
+/// Files at least this large are loaded in the background, showing the start of the file right away.
+pub const BACKGROUND_LOAD_MIN_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Size of the start of a file loaded in the background that is shown right away.
+pub const FIRST_PAINT_BYTES: usize = 256 * 1024;
+
+/// All lines of a file loaded by a [`LoadTask`].
+#[derive(Debug, Clone)]
+pub struct LoadedLines {
+    /// The lines, or the error reading the file.
+    pub lines: Result<Arc<Vec<LogLine>>, String>,
+    /// How long loading the file took.
+    pub elapsed: Duration,
+}
+
+/// Loading of a large file running on a background thread, while the start of the file is already shown.
+///
+/// The task is cancelled when dropped, so the result of a load replaced in the meantime, e.g. by reloading the
+/// file, is never delivered.
+#[derive(Debug)]
+pub struct LoadTask {
+    progress: TaskProgress,
+}
+
+impl LoadTask {
+    /// Starts loading the file into the given buffer, which is configured like the shown buffer.
+    /// `on_complete` is called from the background thread with the lines, unless the task is cancelled first.
+    pub fn spawn<F>(path: String, mut buffer: LogBuffer, parse_timestamps: bool, on_complete: F) -> Self
+    where
+        F: FnOnce(LoadedLines) + Send + 'static,
+    {
+        let total = std::fs::metadata(&path).map_or(0, |metadata| metadata.len() as usize);
+        let progress = TaskProgress::spawn(total, move |cancelled, progress| {
+            let start = Instant::now();
+            let lines = buffer
+                .load_file_with_progress(&path, parse_timestamps, progress, cancelled)
+                .map(|()| buffer.shared_lines())
+                .map_err(|e| e.to_string());
+            if !cancelled.load(Ordering::Relaxed) {
+                on_complete(LoadedLines {
+                    lines,
+                    elapsed: start.elapsed(),
+                });
+            }
+        });
+
+        Self { progress }
+    }
+
+    pub fn progress(&self) -> &TaskProgress {
+        &self.progress
+    }
+}
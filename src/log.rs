@@ -1,5 +1,13 @@
-use crate::timestamp::parse_timestamp;
+use crate::ansi::{self, AnsiMode};
+use crate::highlighter::{PatternStyle, StyledRange};
+use crate::level::{LevelDetector, LogLevel};
+use crate::resolver::VisibilityRule;
+use crate::timestamp::TimestampParser;
 use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 fn needs_sanitization(line: &str) -> bool {
     line.bytes().any(|b| b == b'\t' || b == b'\r' || b < 0x20)
@@ -9,27 +17,68 @@ fn sanitize_line(line: &str) -> String {
     if !needs_sanitization(line) {
         return line.to_string();
     }
-    do_sanitize(line)
+    do_sanitize(line, AnsiMode::Strip).0
 }
 
-fn sanitize_line_owned(line: String) -> String {
+/// Sanitizes a line and collects the styles of its ANSI color sequences if `ansi_mode` asks for them.
+fn sanitize_line_styled(line: &str, ansi_mode: AnsiMode) -> (String, Box<[StyledRange]>) {
+    if !needs_sanitization(line) {
+        return (line.to_string(), Box::default());
+    }
+    do_sanitize(line, ansi_mode)
+}
+
+/// Sanitizes a line like `sanitize_line_styled`, without copying it if nothing needs to be sanitized.
+pub fn sanitize_line_owned(line: String, ansi_mode: AnsiMode) -> (String, Box<[StyledRange]>) {
     if !needs_sanitization(&line) {
-        return line;
+        return (line, Box::default());
     }
-    do_sanitize(&line)
+    do_sanitize(&line, ansi_mode)
 }
 
-fn do_sanitize(line: &str) -> String {
+fn do_sanitize(line: &str, ansi_mode: AnsiMode) -> (String, Box<[StyledRange]>) {
     let mut result = String::with_capacity(line.len());
-    for ch in line.chars() {
+    let mut styles = Vec::new();
+    let mut style = PatternStyle::default();
+    let mut style_start = 0;
+    let mut rest = line;
+
+    while let Some(ch) = rest.chars().next() {
+        if ch == '\x1b' {
+            let (len, sgr_params) = ansi::escape_sequence(rest);
+            if let Some(params) = sgr_params
+                && ansi_mode == AnsiMode::Colors
+            {
+                let mut new_style = style;
+                ansi::apply_sgr(&mut new_style, params);
+                if new_style != style {
+                    push_style(&mut styles, style, style_start, result.len());
+                    style = new_style;
+                    style_start = result.len();
+                }
+            }
+            rest = &rest[len..];
+            continue;
+        }
+
         match ch {
             '\t' => result.push_str("    "),
             '\r' => {}
             c if c.is_control() => {}
             c => result.push(c),
         }
+        rest = &rest[ch.len_utf8()..];
+    }
+    push_style(&mut styles, style, style_start, result.len());
+
+    (result, styles.into_boxed_slice())
+}
+
+/// Adds the range styled by an ANSI sequence, unless it is empty or unstyled.
+fn push_style(styles: &mut Vec<StyledRange>, style: PatternStyle, start: usize, end: usize) {
+    if end > start && style != PatternStyle::default() {
+        styles.push(StyledRange { start, end, style });
     }
-    result
 }
 
 /// A single log line with its content and original index.
@@ -39,19 +88,69 @@ pub struct LogLine {
     pub content: String,
     /// The original index of the line in the source.
     pub index: usize,
+    /// Index of the line in its source file. Unlike `index`, this is kept when lines from multiple files are merged.
+    pub source_index: usize,
     /// Parsed timestamp (if applicable).
     pub timestamp: Option<DateTime<Utc>>,
     /// File id
     pub log_file_id: Option<usize>,
+    /// Output stream of the spawned command the line came from.
+    pub stream: Option<OutputStream>,
+    /// Detected log level. Lines without a level inherit the level of the line above.
+    pub level: Option<LogLevel>,
+    /// Whether the line continues the multi-line record of the line above, e.g. a stack trace line.
+    pub continuation: bool,
+    /// Styles of the ANSI color sequences removed from the content.
+    pub ansi_styles: Box<[StyledRange]>,
+}
+
+/// Output stream of a spawned command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
 }
 
 /// Buffer for storing and managing log lines with filtering support.
 #[derive(Debug, Default)]
 pub struct LogBuffer {
-    /// All log lines (unfiltered).
-    lines: Vec<LogLine>,
+    /// All log lines (unfiltered). Shared so background tasks can work on a snapshot.
+    lines: Arc<Vec<LogLine>>,
     /// Whether the buffer is in streaming mode (reading from stdin).
     pub streaming: bool,
+    /// Detects the log level of loaded and appended lines.
+    level_detector: LevelDetector,
+    /// Parses the timestamps of loaded and appended lines.
+    timestamp_parser: TimestampParser,
+    /// Whether the last appended line belongs to a record started by a line with a timestamp.
+    record_open: bool,
+    /// How ANSI escape sequences in loaded and appended lines are handled.
+    ansi_mode: AnsiMode,
+    /// Identifies the current version of the lines. Increases whenever lines are added, dropped or replaced.
+    generation: u64,
+    /// Identifies the current version of the existing lines. Unlike `generation` it stays the same when lines are
+    /// appended, so work on the lines can continue with the new lines.
+    base_generation: u64,
+    /// Log indices of the lines hidden by hand. Shared with the visibility rule hiding them.
+    hidden: Arc<HashSet<usize>>,
+}
+
+/// Number of lines parsed between progress updates when loading a file in the background.
+const PROGRESS_INTERVAL_LINES: usize = 65_536;
+
+/// Matches lines of a reloaded buffer to the lines before the reload by content.
+///
+/// Returns the new index of every old line, or `None` if the line no longer exists. Repeated lines
+/// are matched in order, so the n-th occurrence of a line maps to the n-th occurrence after reload.
+pub fn match_lines_by_content(old: &[LogLine], new: &[LogLine]) -> Vec<Option<usize>> {
+    let mut occurrences: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (index, line) in new.iter().enumerate() {
+        occurrences.entry(line.content()).or_default().push_back(index);
+    }
+
+    old.iter()
+        .map(|line| occurrences.get_mut(line.content()).and_then(VecDeque::pop_front))
+        .collect()
 }
 
 impl LogLine {
@@ -60,8 +159,13 @@ impl LogLine {
         Self {
             content: sanitize_line(content),
             index,
+            source_index: index,
             timestamp: None,
             log_file_id: None,
+            stream: None,
+            level: None,
+            continuation: false,
+            ansi_styles: Box::default(),
         }
     }
 
@@ -71,7 +175,87 @@ impl LogLine {
     }
 }
 
+/// Rule hiding the lines received after the display was frozen, while they keep being buffered.
+pub struct FrozenDisplayRule {
+    /// Log index of the first line received after freezing.
+    first_new_line: usize,
+}
+
+impl FrozenDisplayRule {
+    pub fn new(first_new_line: usize) -> Self {
+        Self { first_new_line }
+    }
+}
+
+impl VisibilityRule for FrozenDisplayRule {
+    fn is_visible(&self, line: &LogLine) -> bool {
+        line.index < self.first_new_line
+    }
+}
+
+/// Rule that hides the lines hidden by hand.
+pub struct HiddenLinesRule {
+    hidden: Arc<HashSet<usize>>,
+}
+
+impl HiddenLinesRule {
+    pub fn new(hidden: Arc<HashSet<usize>>) -> Self {
+        Self { hidden }
+    }
+}
+
+impl VisibilityRule for HiddenLinesRule {
+    fn is_visible(&self, line: &LogLine) -> bool {
+        !self.hidden.contains(&line.index)
+    }
+}
+
+/// Lets lines without a level, e.g. stack traces, inherit the level of the line above.
+fn inherit_levels(lines: &mut [LogLine]) {
+    let mut last_level = None;
+    for line in lines {
+        if line.level.is_some() {
+            last_level = line.level;
+        } else {
+            line.level = last_level;
+        }
+    }
+}
+
+/// Marks lines without a timestamp following a line with one as continuations of that record.
+/// Must be called before timestamps are inherited from the line above.
+fn mark_continuations(lines: &mut [LogLine]) {
+    let mut record_open = false;
+    for line in lines {
+        if line.timestamp.is_some() {
+            record_open = true;
+        } else {
+            line.continuation = record_open;
+        }
+    }
+}
+
 impl LogBuffer {
+    /// Returns the detector used for the log level of lines.
+    pub fn level_detector(&self) -> &LevelDetector {
+        &self.level_detector
+    }
+
+    /// Sets the detector used for the log level of lines loaded or appended from now on.
+    pub fn set_level_detector(&mut self, level_detector: LevelDetector) {
+        self.level_detector = level_detector;
+    }
+
+    /// Sets the parser used for the timestamps of lines loaded or appended from now on.
+    pub fn set_timestamp_parser(&mut self, timestamp_parser: TimestampParser) {
+        self.timestamp_parser = timestamp_parser;
+    }
+
+    /// Sets how ANSI escape sequences of lines loaded or appended from now on are handled.
+    pub fn set_ansi_mode(&mut self, ansi_mode: AnsiMode) {
+        self.ansi_mode = ansi_mode;
+    }
+
     /// Loads log lines from one or more files and parse timestamps if not disabled.
     pub fn load_files(&mut self, paths: &[&str], parse_timestamps: bool) -> color_eyre::Result<usize> {
         if paths.is_empty() {
@@ -80,44 +264,25 @@ impl LogBuffer {
 
         self.streaming = false;
         let multi_file = paths.len() > 1;
+        self.generation += 1;
+        self.base_generation = self.generation;
+        let mut lines = Vec::new();
         let mut timestamp_parsing_errors = 0;
 
         for (file_id, path) in paths.iter().enumerate() {
             let bytes = std::fs::read(path)?;
-            let content = String::from_utf8_lossy(&bytes);
-            let mut file_lines: Vec<LogLine> = content
-                .lines()
-                .enumerate()
-                .map(|(index, line)| LogLine {
-                    content: sanitize_line(line),
-                    index,
-                    timestamp: if parse_timestamps { parse_timestamp(line) } else { None },
-                    log_file_id: Some(file_id),
-                })
-                .collect();
+            let mut file_lines = self.parse_file(&String::from_utf8_lossy(&bytes), file_id, parse_timestamps, |_| true);
 
-            if parse_timestamps {
-                // Lines without a timestamp inherit from the line above.
-                let mut last_timestamp: Option<DateTime<Utc>> = None;
-                for line in file_lines.iter_mut() {
-                    if line.timestamp.is_some() {
-                        last_timestamp = line.timestamp;
-                    } else {
-                        line.timestamp = last_timestamp;
-                    }
-                }
-
-                if multi_file {
-                    timestamp_parsing_errors += file_lines.iter().filter(|l| l.timestamp.is_none()).count();
-                }
+            if parse_timestamps && multi_file {
+                timestamp_parsing_errors += file_lines.iter().filter(|l| l.timestamp.is_none()).count();
             }
 
-            self.lines.append(&mut file_lines);
+            lines.append(&mut file_lines);
         }
 
         if multi_file {
             if parse_timestamps {
-                self.lines.sort_by(|a, b| match (&a.timestamp, &b.timestamp) {
+                lines.sort_by(|a, b| match (&a.timestamp, &b.timestamp) {
                     (Some(ts_a), Some(ts_b)) => ts_a.cmp(ts_b),
                     (Some(_), None) => std::cmp::Ordering::Less,
                     (None, Some(_)) => std::cmp::Ordering::Greater,
@@ -125,34 +290,114 @@ impl LogBuffer {
                 });
             }
 
-            for (new_index, line) in self.lines.iter_mut().enumerate() {
+            for (new_index, line) in lines.iter_mut().enumerate() {
                 line.index = new_index;
             }
         }
 
+        *Arc::make_mut(&mut self.lines) = lines;
         Ok(timestamp_parsing_errors)
     }
 
-    /// Adds a new file to an existing buffer.
+    /// Loads the lines of the first `max_bytes` of a file, e.g. to show a large file while the rest is loaded.
     ///
-    /// Sorts all lines by timestamp if `parse_timestamps` is true.
-    pub fn add_file(&mut self, path: &str, file_id: usize, parse_timestamps: bool) -> color_eyre::Result<()> {
+    /// A line cut off at `max_bytes` is left out, so the lines are the same as the first lines of the whole file.
+    pub fn load_file_prefix(&mut self, path: &str, max_bytes: usize, parse_timestamps: bool) -> color_eyre::Result<()> {
+        use std::io::Read;
+        let mut bytes = Vec::with_capacity(max_bytes);
+        std::fs::File::open(path)?
+            .take(max_bytes as u64 + 1)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() > max_bytes {
+            let end = bytes.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+            bytes.truncate(end);
+        }
+
+        self.streaming = false;
+        self.generation += 1;
+        self.base_generation = self.generation;
+        self.lines = Arc::new(self.parse_file(&String::from_utf8_lossy(&bytes), 0, parse_timestamps, |_| true));
+        Ok(())
+    }
+
+    /// Loads the lines of a single file like [`LogBuffer::load_files`], storing the number of bytes parsed so far
+    /// in `progress`. Parsing stops early once `cancelled` is set, leaving only the lines parsed so far.
+    pub fn load_file_with_progress(
+        &mut self,
+        path: &str,
+        parse_timestamps: bool,
+        progress: &AtomicUsize,
+        cancelled: &AtomicBool,
+    ) -> color_eyre::Result<()> {
         let bytes = std::fs::read(path)?;
-        let content = String::from_utf8_lossy(&bytes);
-        let mut last_timestamp: Option<DateTime<Utc>> = None;
+        self.streaming = false;
+        self.generation += 1;
+        self.base_generation = self.generation;
+        let on_progress = |offset| {
+            progress.store(offset, Ordering::Relaxed);
+            !cancelled.load(Ordering::Relaxed)
+        };
+        self.lines = Arc::new(self.parse_file(&String::from_utf8_lossy(&bytes), 0, parse_timestamps, on_progress));
+        Ok(())
+    }
 
+    /// Replaces the lines loaded from the start of a file with all lines of the file, loaded in the background.
+    ///
+    /// The lines start with the lines already loaded, so work on them continues as if the rest was appended.
+    pub fn finish_loading(&mut self, lines: Arc<Vec<LogLine>>) {
+        self.generation += 1;
+        if lines.len() < self.lines.len() {
+            self.base_generation = self.generation;
+        }
+        self.lines = lines;
+    }
+
+    /// Parses the content of a file into log lines.
+    ///
+    /// Lines without a level inherit the level of the line above and, if timestamps are parsed, lines without a
+    /// timestamp continue the record of the line above and inherit its timestamp. `on_progress` is called with the
+    /// byte offset of the line being parsed now and then, and parsing stops early when it returns false.
+    fn parse_file(
+        &self,
+        content: &str,
+        file_id: usize,
+        parse_timestamps: bool,
+        on_progress: impl Fn(usize) -> bool,
+    ) -> Vec<LogLine> {
         let mut file_lines: Vec<LogLine> = content
             .lines()
             .enumerate()
-            .map(|(index, line)| LogLine {
-                content: sanitize_line(line),
-                index,
-                timestamp: if parse_timestamps { parse_timestamp(line) } else { None },
-                log_file_id: Some(file_id),
+            .map_while(|(index, line)| {
+                if index % PROGRESS_INTERVAL_LINES == 0
+                    && !on_progress(line.as_ptr() as usize - content.as_ptr() as usize)
+                {
+                    return None;
+                }
+                let (content, ansi_styles) = sanitize_line_styled(line, self.ansi_mode);
+                Some(LogLine {
+                    timestamp: if parse_timestamps {
+                        self.timestamp_parser.parse(&content)
+                    } else {
+                        None
+                    },
+                    level: self.level_detector.detect(&content),
+                    content,
+                    index,
+                    source_index: index,
+                    log_file_id: Some(file_id),
+                    stream: None,
+                    continuation: false,
+                    ansi_styles,
+                })
             })
             .collect();
+        inherit_levels(&mut file_lines);
 
         if parse_timestamps {
+            mark_continuations(&mut file_lines);
+
+            // Lines without a timestamp inherit from the line above.
+            let mut last_timestamp: Option<DateTime<Utc>> = None;
             for line in file_lines.iter_mut() {
                 if line.timestamp.is_some() {
                     last_timestamp = line.timestamp;
@@ -161,11 +406,25 @@ impl LogBuffer {
                 }
             }
         }
+        on_progress(content.len());
 
-        self.lines.append(&mut file_lines);
+        file_lines
+    }
+
+    /// Adds a new file to an existing buffer.
+    ///
+    /// Sorts all lines by timestamp if `parse_timestamps` is true.
+    pub fn add_file(&mut self, path: &str, file_id: usize, parse_timestamps: bool) -> color_eyre::Result<()> {
+        let bytes = std::fs::read(path)?;
+        let mut file_lines = self.parse_file(&String::from_utf8_lossy(&bytes), file_id, parse_timestamps, |_| true);
+
+        self.generation += 1;
+        self.base_generation = self.generation;
+        let lines = Arc::make_mut(&mut self.lines);
+        lines.append(&mut file_lines);
 
         if parse_timestamps {
-            self.lines.sort_by(|a, b| match (&a.timestamp, &b.timestamp) {
+            lines.sort_by(|a, b| match (&a.timestamp, &b.timestamp) {
                 (Some(ts_a), Some(ts_b)) => ts_a.cmp(ts_b),
                 (Some(_), None) => std::cmp::Ordering::Less,
                 (None, Some(_)) => std::cmp::Ordering::Greater,
@@ -173,7 +432,18 @@ impl LogBuffer {
             });
         }
 
-        for (new_index, line) in self.lines.iter_mut().enumerate() {
+        // Old lines keep their index until re-indexed, the added lines are never hidden
+        if !self.hidden.is_empty() {
+            self.hidden = Arc::new(
+                lines
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, line)| line.log_file_id != Some(file_id) && self.hidden.contains(&line.index))
+                    .map(|(new_index, _)| new_index)
+                    .collect(),
+            );
+        }
+        for (new_index, line) in lines.iter_mut().enumerate() {
             line.index = new_index;
         }
 
@@ -183,7 +453,9 @@ impl LogBuffer {
     /// Initializes the buffer for stdin streaming mode.
     pub fn init_stdin_mode(&mut self) {
         self.streaming = true;
-        self.lines.clear();
+        self.generation += 1;
+        self.base_generation = self.generation;
+        Arc::make_mut(&mut self.lines).clear();
     }
 
     /// Appends a new line to the buffer (streaming mode).
@@ -191,21 +463,114 @@ impl LogBuffer {
     /// Takes ownership of the content to avoid allocation when no sanitization is needed.
     /// Returns the index of the newly created LogLine.
     pub fn append_line(&mut self, content: String) -> usize {
+        self.append_stream_line(content, None)
+    }
+
+    /// Appends a new line from the given output stream of a spawned command to the buffer.
+    pub fn append_stream_line(&mut self, content: String, stream: Option<OutputStream>) -> usize {
         let index = self.lines.len();
+        let (content, ansi_styles) = sanitize_line_owned(content, self.ansi_mode);
+        let level = self
+            .level_detector
+            .detect(&content)
+            .or_else(|| self.lines.last().and_then(|line| line.level));
+        let starts_record = self.timestamp_parser.parse(&content).is_some();
+        let continuation = !starts_record && self.record_open;
+        self.record_open = starts_record || continuation;
         let log_line = LogLine {
-            content: sanitize_line_owned(content),
+            content,
             index,
+            source_index: index,
             timestamp: None,
             log_file_id: None,
+            stream,
+            level,
+            continuation,
+            ansi_styles,
         };
-        self.lines.push(log_line);
+        self.generation += 1;
+        Arc::make_mut(&mut self.lines).push(log_line);
         index
     }
 
+    /// Detects the level and the records of the lines of a stream again, e.g. after the log format changed.
+    pub fn redetect_stream_lines(&mut self) {
+        let mut level = None;
+        let mut record_open = false;
+        for line in Arc::make_mut(&mut self.lines).iter_mut() {
+            level = self.level_detector.detect(&line.content).or(level);
+            line.level = level;
+            let starts_record = self.timestamp_parser.parse(&line.content).is_some();
+            line.continuation = !starts_record && record_open;
+            record_open = starts_record || record_open;
+        }
+        self.record_open = record_open;
+        self.generation += 1;
+        self.base_generation = self.generation;
+    }
+
+    /// Returns the generation of the lines, which changes whenever lines are added, dropped or replaced.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Continues the generations of the buffer this buffer replaces, so a generation is never reused by a reloaded
+    /// buffer and caches keyed by it don't mistake these lines for the replaced ones.
+    pub fn continue_generations(&mut self, replaced: &LogBuffer) {
+        self.generation = self.generation.max(replaced.generation) + 1;
+        self.base_generation = self.generation;
+    }
+
+    /// Returns the generation of the existing lines, which changes whenever lines are dropped or replaced but not
+    /// when lines are appended.
+    pub fn base_generation(&self) -> u64 {
+        self.base_generation
+    }
+
+    /// Returns an estimate of the memory used by the lines, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| {
+                std::mem::size_of::<LogLine>()
+                    + line.content.capacity()
+                    + std::mem::size_of_val::<[StyledRange]>(&line.ansi_styles)
+            })
+            .sum()
+    }
+
+    /// Drops the oldest `count` lines and re-indexes the remaining lines.
+    pub fn drop_oldest(&mut self, count: usize) {
+        self.generation += 1;
+        self.base_generation = self.generation;
+        let lines = Arc::make_mut(&mut self.lines);
+        lines.drain(..count.min(lines.len()));
+        for (new_index, line) in lines.iter_mut().enumerate() {
+            line.index = new_index;
+            line.source_index = new_index;
+        }
+        // The start of the first record may have been dropped
+        for line in lines.iter_mut().take_while(|line| line.continuation) {
+            line.continuation = false;
+        }
+        if !self.hidden.is_empty() {
+            self.hidden = Arc::new(
+                self.hidden
+                    .iter()
+                    .filter_map(|&line_index| line_index.checked_sub(count))
+                    .collect(),
+            );
+        }
+    }
+
     /// Remove all lines and filters from the buffer. (Only in streaming mode.)
     pub fn clear_all(&mut self) {
         if self.streaming {
-            self.lines.clear();
+            self.generation += 1;
+            self.base_generation = self.generation;
+            Arc::make_mut(&mut self.lines).clear();
+            self.record_open = false;
+            self.hidden = Arc::default();
         }
     }
 
@@ -213,7 +578,7 @@ impl LogBuffer {
     pub fn save_to_file(&self, path: &str) -> color_eyre::Result<()> {
         use std::io::Write;
         let mut file = std::fs::File::create(path)?;
-        for line in &self.lines {
+        for line in self.lines.iter() {
             writeln!(file, "{}", line.content)?;
         }
         Ok(())
@@ -227,6 +592,45 @@ impl LogBuffer {
         Some(&self.lines[line_index])
     }
 
+    /// Returns the range of line indices of the multi-line record containing the given line.
+    ///
+    /// A record is a line followed by its continuation lines, e.g. a log message and its stack trace.
+    pub fn record_range(&self, line_index: usize) -> Range<usize> {
+        if line_index >= self.lines.len() {
+            return line_index..line_index;
+        }
+        let start = self.lines[..=line_index]
+            .iter()
+            .rposition(|line| !line.continuation)
+            .unwrap_or(0);
+        let end = self.lines[line_index + 1..]
+            .iter()
+            .position(|line| !line.continuation)
+            .map_or(self.lines.len(), |offset| line_index + 1 + offset);
+        start..end
+    }
+
+    /// Hides the lines with the given log indices from view until unhidden.
+    pub fn hide_lines(&mut self, line_indices: impl IntoIterator<Item = usize>) {
+        let line_count = self.lines.len();
+        Arc::make_mut(&mut self.hidden).extend(line_indices.into_iter().filter(|&index| index < line_count));
+    }
+
+    /// Unhides all hidden lines and returns how many were hidden.
+    pub fn unhide_all(&mut self) -> usize {
+        std::mem::take(&mut self.hidden).len()
+    }
+
+    /// Returns the log indices of the hidden lines.
+    pub fn hidden_lines(&self) -> Arc<HashSet<usize>> {
+        Arc::clone(&self.hidden)
+    }
+
+    /// Returns the number of hidden lines.
+    pub fn hidden_count(&self) -> usize {
+        self.hidden.len()
+    }
+
     /// Returns the total count of log lines.
     pub fn get_total_lines_count(&self) -> usize {
         self.lines.len()
@@ -241,4 +645,100 @@ impl LogBuffer {
     pub fn all_lines(&self) -> &[LogLine] {
         &self.lines
     }
+
+    /// Returns a shared snapshot of all log lines, e.g. for processing in a background task.
+    pub fn shared_lines(&self) -> Arc<Vec<LogLine>> {
+        Arc::clone(&self.lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hidden_lines_follow_dropped_lines() {
+        let mut buffer = LogBuffer::default();
+        buffer.init_stdin_mode();
+        for content in ["one", "two", "three", "four"] {
+            buffer.append_line(content.to_string());
+        }
+        buffer.hide_lines([0, 2, 3, 10]);
+        assert_eq!(buffer.hidden_count(), 3);
+
+        buffer.drop_oldest(1);
+        let rule = HiddenLinesRule::new(buffer.hidden_lines());
+        let visible: Vec<&str> = buffer
+            .iter()
+            .filter(|line| rule.is_visible(line))
+            .map(|line| line.content())
+            .collect();
+        assert_eq!(visible, vec!["two"]);
+
+        assert_eq!(buffer.unhide_all(), 2);
+        assert_eq!(buffer.hidden_count(), 0);
+    }
+
+    #[test]
+    fn test_cancelled_load_stops_parsing() {
+        let path = std::env::temp_dir().join(format!("lazylog-cancelled-{}.log", std::process::id()));
+        std::fs::write(&path, "line\n".repeat(2 * PROGRESS_INTERVAL_LINES)).unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut buffer = LogBuffer::default();
+        buffer
+            .load_file_with_progress(path, false, &AtomicUsize::new(0), &AtomicBool::new(true))
+            .unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(buffer.get_total_lines_count(), 0);
+    }
+
+    #[test]
+    fn test_prefix_is_continued_by_whole_file() {
+        let path = std::env::temp_dir().join(format!("lazylog-prefix-{}.log", std::process::id()));
+        std::fs::write(
+            &path,
+            "2024-01-01 10:00:00 ERROR first\n  at main.rs:1\n2024-01-01 10:00:01 second\n",
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut buffer = LogBuffer::default();
+        buffer.load_file_prefix(path, 50, true).unwrap();
+        assert_eq!(buffer.get_total_lines_count(), 2);
+        assert!(buffer.all_lines()[1].continuation);
+        let base_generation = buffer.base_generation();
+
+        let mut whole = LogBuffer::default();
+        let progress = AtomicUsize::new(0);
+        whole
+            .load_file_with_progress(path, true, &progress, &AtomicBool::new(false))
+            .unwrap();
+        assert_eq!(
+            progress.load(Ordering::Relaxed),
+            std::fs::metadata(path).unwrap().len() as usize
+        );
+        buffer.finish_loading(whole.shared_lines());
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(buffer.get_total_lines_count(), 3);
+        assert_eq!(buffer.base_generation(), base_generation);
+    }
+
+    #[test]
+    fn test_generations_continue_in_replacing_buffer() {
+        let mut buffer = LogBuffer::default();
+        buffer.init_stdin_mode();
+        buffer.append_line("first".to_string());
+        let base_generation = buffer.base_generation();
+        buffer.append_line("second".to_string());
+        assert!(buffer.generation() > base_generation);
+        assert_eq!(buffer.base_generation(), base_generation);
+
+        let mut replacing = LogBuffer::default();
+        replacing.continue_generations(&buffer);
+        assert!(replacing.generation() > buffer.generation());
+        assert_eq!(replacing.base_generation(), replacing.generation());
+    }
 }
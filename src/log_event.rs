@@ -1,10 +1,15 @@
+use crate::level::LogLevel;
 use crate::log::{LogBuffer, LogLine};
 use crate::matcher::{PatternMatcher, PlainMatch};
+use crate::utils::CaseFolding;
+use ratatui::style::Color;
+use regex::Regex;
 
 use rayon::prelude::*;
 use std::collections::HashSet;
-use std::iter::once;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// A log event occurrence.
 #[derive(Debug, Clone, PartialEq)]
@@ -24,8 +29,28 @@ pub struct EventPattern {
     pub count: usize,
     /// Whether this event is critical (shown with special indicators).
     pub critical: bool,
+    /// Severity counted in the footer badges.
+    pub severity: Option<LogLevel>,
     /// Whether this is a custom event.
     pub is_custom: bool,
+    /// Color of a custom event, overriding the configured custom event color.
+    pub color: Option<Color>,
+    /// Whether to ring the terminal bell when the event occurs in a streamed line.
+    pub bell: bool,
+    /// Whether to send a desktop notification when the event occurs in a streamed line.
+    pub notify: bool,
+}
+
+/// Custom event, as persisted and exported to config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomEvent<'a> {
+    pub name: &'a str,
+    pub pattern: &'a str,
+    /// Whether the pattern is a regex rather than a plain pattern.
+    pub regex: bool,
+    /// Whether a plain pattern is matched case-sensitively. A regex carries its own `(?i)` flag.
+    pub case_sensitive: bool,
+    pub color: Option<Color>,
 }
 
 #[derive(Debug)]
@@ -44,6 +69,13 @@ pub struct LogEventTracker {
     events: Vec<LogEvent>,
     /// Whether to show marks in the events view
     pub show_marks: bool,
+    /// How long the last scan for events took.
+    last_scan_time: Option<Duration>,
+    /// Number of lines at the start of the buffer scanned for events. Lines appended after them are scanned
+    /// on the next update.
+    scanned_lines: usize,
+    /// How the case of characters is folded by custom events matching case-insensitively.
+    case_folding: CaseFolding,
 }
 
 impl LogEventTracker {
@@ -53,28 +85,97 @@ impl LogEventTracker {
             patterns,
             events: Vec::new(),
             show_marks: false,
+            last_scan_time: None,
+            scanned_lines: 0,
+            case_folding: CaseFolding::default(),
         }
     }
 
+    /// Returns the tracker folding the case of characters this way in custom events.
+    pub fn with_case_folding(mut self, case_folding: CaseFolding) -> Self {
+        self.case_folding = case_folding;
+        self
+    }
+
+    /// Returns how long the last scan of all lines or of new lines took.
+    pub fn last_scan_time(&self) -> Option<Duration> {
+        self.last_scan_time
+    }
+
     /// Scans all log lines for event matches.
     pub fn scan_all_lines(&mut self, log_buffer: &LogBuffer) {
         self.events.clear();
         self.reset_event_counts();
 
+        let scan_start = Instant::now();
         self.events = self.scan_lines(log_buffer.iter());
+        self.last_scan_time = Some(scan_start.elapsed());
 
         for event in &self.events {
             if let Some(pattern) = self.patterns.iter_mut().find(|p| p.name == event.name) {
                 pattern.count += 1;
             }
         }
+        self.scanned_lines = log_buffer.get_total_lines_count();
+    }
+
+    /// Scans the lines appended to the buffer since the last scan. The lines scanned before are not scanned again.
+    ///
+    /// Returns true if an event was added and should be selected in the events list
+    pub fn update(&mut self, log_buffer: &LogBuffer) -> bool {
+        let lines = log_buffer.all_lines();
+        if self.scanned_lines >= lines.len() {
+            return false;
+        }
+        self.scan_new_lines(&lines[self.scanned_lines..])
+    }
+
+    /// Scans the lines for the last added event only, instead of scanning for all events again.
+    ///
+    /// Lines with an event are skipped, as the first matching event of a line wins and the added event comes last.
+    pub fn scan_added_event(&mut self, log_buffer: &LogBuffer) {
+        let lines = &log_buffer.all_lines()[..self.scanned_lines.min(log_buffer.get_total_lines_count())];
+        let Some(pattern) = self.patterns.last() else {
+            return;
+        };
+
+        let scan_start = Instant::now();
+        let event_lines = self.get_event_indices();
+        let new_events: Vec<LogEvent> = lines
+            .par_iter()
+            .filter(|log_line| !event_lines.contains(&log_line.index) && pattern.matcher.matches(log_line.content()))
+            .map(|log_line| LogEvent {
+                name: pattern.name.clone(),
+                line_index: log_line.index,
+            })
+            .collect();
+        self.last_scan_time = Some(scan_start.elapsed());
+
+        if let Some(pattern) = self.patterns.last_mut() {
+            pattern.count += new_events.len();
+        }
+        self.events.extend(new_events);
+        self.events.sort_by_key(|e| e.line_index);
+
+        // Appended lines are scanned for all events
+        self.update(log_buffer);
     }
 
     /// Checks a single line for event matches and adds it if it matches.
     ///
     /// Returns true if an event was added and should be selected in the events list
     pub fn scan_single_line(&mut self, log_line: &LogLine) -> bool {
-        let new_events = self.scan_lines(once(log_line));
+        self.scan_new_lines(std::slice::from_ref(log_line))
+    }
+
+    /// Checks newly appended lines for event matches and adds the events found.
+    ///
+    /// Returns true if an event was added and should be selected in the events list
+    pub fn scan_new_lines(&mut self, log_lines: &[LogLine]) -> bool {
+        let scan_start = Instant::now();
+        let new_events = self.scan_lines(log_lines.iter());
+        self.last_scan_time = Some(scan_start.elapsed());
+        self.scanned_lines += log_lines.len();
 
         if new_events.is_empty() {
             return false;
@@ -149,6 +250,16 @@ impl LogEventTracker {
             .collect()
     }
 
+    /// Hashes the enabled and critical event patterns and the events found, to tell when the events shown as
+    /// ticks changed.
+    pub fn hash_enabled_events(&self, state: &mut impl Hasher) {
+        for pattern in &self.patterns {
+            (&pattern.name, pattern.enabled, pattern.critical).hash(state);
+        }
+        self.events.len().hash(state);
+        self.events.last().map(|event| event.line_index).hash(state);
+    }
+
     /// Returns enabled events matching a specific event name.
     pub fn get_events_by_name(&self, name: &str) -> Vec<&LogEvent> {
         self.events.iter().filter(|e| e.name == name).collect()
@@ -191,6 +302,19 @@ impl LogEventTracker {
             .collect()
     }
 
+    /// Returns the events on the lines from `first_line` on whose enabled pattern rings the bell or sends a desktop
+    /// notification, with their pattern, e.g. to announce the events of newly streamed lines.
+    pub fn announced_events(&self, first_line: usize) -> Vec<(&LogEvent, &EventPattern)> {
+        let start = self.events.partition_point(|event| event.line_index < first_line);
+        self.events[start..]
+            .iter()
+            .filter_map(|event| {
+                let pattern = self.patterns.iter().find(|pattern| pattern.name == event.name)?;
+                (pattern.enabled && (pattern.bell || pattern.notify)).then_some((event, pattern))
+            })
+            .collect()
+    }
+
     /// Returns true if an event with the given name is marked as critical.
     pub fn is_critical_event(&self, event_name: &str) -> bool {
         self.patterns.iter().any(|p| p.name == event_name && p.critical)
@@ -201,8 +325,23 @@ impl LogEventTracker {
         self.patterns.iter().any(|p| p.name == event_name && p.is_custom)
     }
 
+    /// Removes events on the first `count` lines and shifts the rest after those lines were dropped.
+    pub fn drop_lines_before(&mut self, count: usize) {
+        let dropped = self.events.partition_point(|e| e.line_index < count);
+        for event in self.events.drain(..dropped) {
+            if let Some(pattern) = self.patterns.iter_mut().find(|p| p.name == event.name) {
+                pattern.count = pattern.count.saturating_sub(1);
+            }
+        }
+        for event in &mut self.events {
+            event.line_index -= count;
+        }
+        self.scanned_lines = self.scanned_lines.saturating_sub(count);
+    }
+
     pub fn clear_all(&mut self) {
         self.events.clear();
+        self.scanned_lines = 0;
         for pattern in &mut self.patterns {
             pattern.count = 0;
         }
@@ -265,6 +404,42 @@ impl LogEventTracker {
             .unwrap_or(0)
     }
 
+    /// Returns the number of events per severity, most severe first. Only severities of event patterns are included.
+    pub fn severity_counts(&self) -> Vec<(LogLevel, usize)> {
+        let mut counts: Vec<(LogLevel, usize)> = Vec::new();
+        for pattern in &self.patterns {
+            let Some(severity) = pattern.severity else {
+                continue;
+            };
+            match counts.iter_mut().find(|(level, _)| *level == severity) {
+                Some((_, count)) => *count += pattern.count,
+                None => counts.push((severity, pattern.count)),
+            }
+        }
+        counts.sort_by_key(|(severity, _)| std::cmp::Reverse(*severity));
+        counts
+    }
+
+    /// Returns true if any event pattern has a severity, so its badge is shown in the footer.
+    pub fn has_severities(&self) -> bool {
+        self.patterns.iter().any(|pattern| pattern.severity.is_some())
+    }
+
+    /// Returns the line indices of the events with the given severity after the given line index, in order.
+    pub fn severity_lines_after(&self, severity: LogLevel, line_index: usize) -> impl Iterator<Item = usize> + '_ {
+        let names: HashSet<&str> = self
+            .patterns
+            .iter()
+            .filter(|p| p.severity == Some(severity))
+            .map(|p| p.name.as_str())
+            .collect();
+        let start = self.events.partition_point(|e| e.line_index <= line_index);
+        self.events[start..]
+            .iter()
+            .filter(move |e| names.contains(e.name.as_str()))
+            .map(|e| e.line_index)
+    }
+
     /// Gets the total number of filters.
     pub fn filter_count(&self) -> usize {
         self.patterns.len()
@@ -302,76 +477,176 @@ impl LogEventTracker {
         }
     }
 
-    /// Adds a custom event pattern. Returns false if the pattern already exists.
-    pub fn add_custom_event(&mut self, pattern: &str) -> bool {
+    /// Replaces the event patterns from the config, e.g. after it was edited, keeping custom events and whether
+    /// events that are still configured are enabled. Custom events with the name or pattern of a configured event
+    /// are dropped, as the configured event takes their place. The lines have to be scanned again afterwards.
+    pub fn replace_configured_patterns(&mut self, patterns: Vec<EventPattern>) {
+        let previous = std::mem::take(&mut self.patterns);
+        self.patterns = patterns;
+        for pattern in &mut self.patterns {
+            if let Some(old) = previous.iter().find(|old| !old.is_custom && old.name == pattern.name) {
+                pattern.enabled = old.enabled;
+            }
+        }
+        let custom: Vec<EventPattern> = previous
+            .into_iter()
+            .filter(|old| old.is_custom)
+            .filter(|old| {
+                self.patterns
+                    .iter()
+                    .all(|p| p.name != old.name && p.matcher.pattern() != old.matcher.pattern())
+            })
+            .collect();
+        self.patterns.extend(custom);
+    }
+
+    /// Adds a custom event pattern, named after the pattern unless a name is given.
+    /// Returns false if the pattern or name already exists.
+    pub fn add_custom_event(
+        &mut self,
+        pattern: &str,
+        case_sensitive: bool,
+        name: Option<&str>,
+        color: Option<Color>,
+    ) -> bool {
         if pattern.is_empty() {
             return false;
         }
 
-        // Check if pattern already exists (either as custom or config event)
+        let name = name.map_or_else(|| Self::custom_event_name(pattern), str::to_string);
+        if self.has_plain_pattern(pattern) || self.has_event(&name) {
+            return false;
+        }
+
+        let event_pattern = EventPattern {
+            name,
+            matcher: PatternMatcher::Plain(PlainMatch {
+                pattern: pattern.to_string(),
+                case_sensitive,
+                whole_word: false,
+                case_folding: self.case_folding,
+            }),
+            enabled: true,
+            count: 0,
+            critical: false,
+            severity: None,
+            is_custom: true,
+            color,
+            bell: false,
+            notify: false,
+        };
+
+        self.patterns.push(event_pattern);
+        true
+    }
+
+    /// Adds a custom event matching a regex, named after the regex unless a name is given.
+    /// Returns false if the regex or name already exists.
+    pub fn add_custom_regex_event(&mut self, regex: Regex, name: Option<&str>, color: Option<Color>) -> bool {
         let pattern_exists = self.patterns.iter().any(|p| {
-            if let PatternMatcher::Plain(plain) = &p.matcher {
-                plain.pattern == pattern
+            if let PatternMatcher::Regex(existing) = &p.matcher {
+                existing.as_str() == regex.as_str()
             } else {
                 false
             }
         });
 
-        if pattern_exists {
+        let name = name.map_or_else(|| Self::custom_event_name(regex.as_str()), str::to_string);
+        if pattern_exists || self.has_event(&name) {
             return false;
         }
 
-        // Create name from pattern, capped at 16 characters
-        let name = if pattern.len() > 16 {
-            format!("{}...", &pattern[..13])
-        } else {
-            pattern.to_string()
-        };
-
         let event_pattern = EventPattern {
             name,
-            matcher: PatternMatcher::Plain(PlainMatch {
-                pattern: pattern.to_string(),
-                case_sensitive: true,
-            }),
+            matcher: PatternMatcher::Regex(regex),
             enabled: true,
             count: 0,
             critical: false,
+            severity: None,
             is_custom: true,
+            color,
+            bell: false,
+            notify: false,
         };
 
         self.patterns.push(event_pattern);
         true
     }
 
-    /// Returns the patterns of all custom events (for persistence).
-    pub fn get_custom_event_patterns(&self) -> Vec<&str> {
+    /// Returns true if an event (either custom or from config) matches exactly the given plain pattern.
+    pub fn has_plain_pattern(&self, pattern: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|p| matches!(&p.matcher, PatternMatcher::Plain(plain) if plain.pattern == pattern))
+    }
+
+    /// Returns true if an event with the given name exists.
+    pub fn has_event(&self, name: &str) -> bool {
+        self.patterns.iter().any(|p| p.name == name)
+    }
+
+    /// Returns the color of the custom event with the given name, if it has one.
+    pub fn custom_event_color(&self, name: &str) -> Option<Color> {
+        self.patterns
+            .iter()
+            .find(|p| p.is_custom && p.name == name)
+            .and_then(|p| p.color)
+    }
+
+    /// Creates a custom event name from a pattern, capped at 16 characters.
+    pub fn custom_event_name(pattern: &str) -> String {
+        if pattern.chars().count() > 16 {
+            format!("{}...", pattern.chars().take(13).collect::<String>())
+        } else {
+            pattern.to_string()
+        }
+    }
+
+    /// Returns the matcher of the event with the given name.
+    pub fn get_event_matcher(&self, name: &str) -> Option<&PatternMatcher> {
+        self.patterns.iter().find(|p| p.name == name).map(|p| &p.matcher)
+    }
+
+    /// Returns all custom events (for persistence).
+    pub fn get_custom_events(&self) -> Vec<CustomEvent<'_>> {
         self.patterns
             .iter()
             .filter(|p| p.is_custom)
             .filter_map(|p| {
-                if let PatternMatcher::Plain(plain) = &p.matcher {
-                    Some(plain.pattern.as_str())
-                } else {
-                    None
-                }
+                let (pattern, regex, case_sensitive) = match &p.matcher {
+                    PatternMatcher::Plain(plain) => (plain.pattern.as_str(), false, plain.case_sensitive),
+                    PatternMatcher::Regex(regex) => (regex.as_str(), true, true),
+                    PatternMatcher::Fuzzy(_) => return None,
+                };
+                Some(CustomEvent {
+                    name: &p.name,
+                    pattern,
+                    regex,
+                    case_sensitive,
+                    color: p.color,
+                })
             })
             .collect()
     }
 
+    /// Removes all custom events. Returns their pattern strings.
+    pub fn remove_custom_events(&mut self) -> Vec<String> {
+        let names: Vec<String> = self
+            .patterns
+            .iter()
+            .filter(|p| p.is_custom)
+            .map(|p| p.name.clone())
+            .collect();
+        names.iter().filter_map(|name| self.remove_custom_event(name)).collect()
+    }
+
     /// Removes a custom event by name. Returns the pattern string if found.
     pub fn remove_custom_event(&mut self, name: &str) -> Option<String> {
         let pattern_str = self
             .patterns
             .iter()
             .find(|p| p.is_custom && p.name == name)
-            .and_then(|p| {
-                if let PatternMatcher::Plain(plain) = &p.matcher {
-                    Some(plain.pattern.clone())
-                } else {
-                    None
-                }
-            });
+            .map(|p| p.matcher.pattern().to_string());
 
         self.patterns.retain(|p| !(p.is_custom && p.name == name));
         self.events.retain(|e| e.name != name);
@@ -392,33 +667,51 @@ mod tests {
                 matcher: PatternMatcher::Plain(PlainMatch {
                     pattern: "ERROR".to_string(),
                     case_sensitive: true,
+                    whole_word: false,
+                    case_folding: CaseFolding::default(),
                 }),
                 enabled: true,
                 count: 0,
                 critical: false,
+                severity: Some(LogLevel::Error),
                 is_custom: false,
+                color: None,
+                bell: false,
+                notify: false,
             },
             EventPattern {
                 name: "warning".to_string(),
                 matcher: PatternMatcher::Plain(PlainMatch {
                     pattern: "WARN".to_string(),
                     case_sensitive: true,
+                    whole_word: false,
+                    case_folding: CaseFolding::default(),
                 }),
                 enabled: true,
                 count: 0,
                 critical: false,
+                severity: Some(LogLevel::Warn),
                 is_custom: false,
+                color: None,
+                bell: false,
+                notify: false,
             },
             EventPattern {
                 name: "info".to_string(),
                 matcher: PatternMatcher::Plain(PlainMatch {
                     pattern: "INFO".to_string(),
                     case_sensitive: true,
+                    whole_word: false,
+                    case_folding: CaseFolding::default(),
                 }),
                 enabled: true,
                 count: 0,
                 critical: false,
+                severity: None,
                 is_custom: false,
+                color: None,
+                bell: false,
+                notify: false,
             },
         ]
     }
@@ -466,6 +759,44 @@ mod tests {
         assert_eq!(tracker.get_event_count("info"), 2);
     }
 
+    #[test]
+    fn test_announced_events() {
+        let mut patterns = create_test_patterns();
+        patterns[0].bell = true;
+        patterns[1].notify = true;
+        patterns[1].enabled = false;
+        let mut tracker = LogEventTracker::new(patterns);
+        tracker.scan_all_lines(&create_test_log_buffer());
+
+        let announced: Vec<(usize, &str)> = tracker
+            .announced_events(2)
+            .into_iter()
+            .map(|(event, pattern)| (event.line_index, pattern.name.as_str()))
+            .collect();
+        assert_eq!(announced, vec![(4, "error")]);
+        assert_eq!(tracker.announced_events(0).len(), 2);
+    }
+
+    #[test]
+    fn test_severity_counts_and_lines() {
+        let mut tracker = LogEventTracker::new(create_test_patterns());
+        tracker.scan_all_lines(&create_test_log_buffer());
+
+        assert_eq!(
+            tracker.severity_counts(),
+            vec![(LogLevel::Error, 2), (LogLevel::Warn, 1)]
+        );
+        assert_eq!(
+            tracker.severity_lines_after(LogLevel::Error, 1).collect::<Vec<_>>(),
+            vec![4]
+        );
+        assert_eq!(
+            tracker.severity_lines_after(LogLevel::Warn, 0).collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(tracker.severity_lines_after(LogLevel::Info, 0).count(), 0);
+    }
+
     #[test]
     fn test_get_event_stats_sorted_by_count() {
         let patterns = create_test_patterns();
@@ -636,4 +967,168 @@ mod tests {
 
         assert_eq!(tracker.get_event_count("error"), initial_error_count + 1);
     }
+
+    #[test]
+    fn test_get_event_matcher() {
+        let mut tracker = LogEventTracker::new(create_test_patterns());
+        tracker.add_custom_regex_event(Regex::new(r"took \d+ms").unwrap(), None, None);
+
+        match tracker.get_event_matcher("error") {
+            Some(PatternMatcher::Plain(plain)) => assert_eq!(plain.pattern, "ERROR"),
+            other => panic!("unexpected matcher: {:?}", other),
+        }
+        match tracker.get_event_matcher(r"took \d+ms") {
+            Some(PatternMatcher::Regex(regex)) => assert_eq!(regex.as_str(), r"took \d+ms"),
+            other => panic!("unexpected matcher: {:?}", other),
+        }
+        assert!(tracker.get_event_matcher("missing").is_none());
+    }
+
+    #[test]
+    fn test_custom_regex_events() {
+        let mut tracker = LogEventTracker::new(create_test_patterns());
+        assert!(tracker.add_custom_event("Timeout", false, None, None));
+        assert!(tracker.add_custom_regex_event(Regex::new(r"took \d+ms").unwrap(), None, None));
+        // Regexes and names are unique
+        assert!(!tracker.add_custom_regex_event(Regex::new(r"took \d+ms").unwrap(), Some("slow"), None));
+        assert!(!tracker.add_custom_regex_event(Regex::new(r"ERR\w+").unwrap(), Some("error"), None));
+
+        assert_eq!(
+            tracker.get_custom_events(),
+            vec![
+                CustomEvent {
+                    name: "Timeout",
+                    pattern: "Timeout",
+                    regex: false,
+                    case_sensitive: false,
+                    color: None,
+                },
+                CustomEvent {
+                    name: r"took \d+ms",
+                    pattern: r"took \d+ms",
+                    regex: true,
+                    case_sensitive: true,
+                    color: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drop_lines_before_rebases_events() {
+        let mut tracker = LogEventTracker::new(create_test_patterns());
+        let mut buffer = create_test_log_buffer();
+        tracker.scan_all_lines(&buffer);
+
+        buffer.drop_oldest(2);
+        tracker.drop_lines_before(2);
+
+        let indices: Vec<usize> = tracker.get_events().iter().map(|e| e.line_index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+        assert_eq!(tracker.get_event_count("error"), 1);
+        assert_eq!(tracker.get_event_count("info"), 1);
+        assert_eq!(buffer.get_line(0).unwrap().content(), "WARN: Retrying connection");
+    }
+
+    #[test]
+    fn test_update_scans_appended_lines_only() {
+        let mut tracker = LogEventTracker::new(create_test_patterns());
+        let mut buffer = create_test_log_buffer();
+        tracker.scan_all_lines(&buffer);
+        assert!(!tracker.update(&buffer));
+
+        buffer.append_line("WARN: Disk almost full".to_string());
+        assert!(tracker.update(&buffer));
+        assert_eq!(tracker.get_event_count("warning"), 2);
+        assert_eq!(tracker.count(), 6);
+
+        // Only lines without an event are scanned for an added event
+        assert!(tracker.add_custom_event("Disk", true, None, None));
+        buffer.append_line("Disk check done".to_string());
+        tracker.scan_added_event(&buffer);
+        assert_eq!(tracker.get_event_count("Disk"), 1);
+        assert_eq!(tracker.get_events().last().unwrap().line_index, 6);
+        assert_eq!(tracker.count(), 7);
+    }
+
+    #[test]
+    fn test_add_named_custom_event() {
+        let mut tracker = LogEventTracker::new(create_test_patterns());
+        let mut buffer = create_test_log_buffer();
+        buffer.append_line("Timeout while reading".to_string());
+
+        assert!(tracker.add_custom_event("Timeout", true, Some("timeouts"), Some(Color::Indexed(214))));
+        // Patterns and names are unique
+        assert!(!tracker.add_custom_event("Timeout", true, Some("other"), None));
+        assert!(!tracker.add_custom_event("Retrying", true, Some("error"), None));
+        assert!(tracker.add_custom_event("a very long custom pattern", true, None, None));
+        tracker.scan_all_lines(&buffer);
+
+        assert_eq!(tracker.get_event_count("timeouts"), 1);
+        assert_eq!(tracker.custom_event_color("timeouts"), Some(Color::Indexed(214)));
+        assert_eq!(tracker.custom_event_color("error"), None);
+        assert_eq!(
+            tracker.get_custom_events(),
+            vec![
+                CustomEvent {
+                    name: "timeouts",
+                    pattern: "Timeout",
+                    regex: false,
+                    case_sensitive: true,
+                    color: Some(Color::Indexed(214)),
+                },
+                CustomEvent {
+                    name: "a very long c...",
+                    pattern: "a very long custom pattern",
+                    regex: false,
+                    case_sensitive: true,
+                    color: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_configured_patterns_keeps_custom_events() {
+        let mut tracker = LogEventTracker::new(create_test_patterns());
+        tracker.toggle_event_enabled("warning");
+        assert!(tracker.add_custom_event("Timeout", true, Some("timeouts"), None));
+        assert!(tracker.add_custom_event("Retrying", true, None, None));
+
+        let mut patterns = create_test_patterns();
+        patterns.remove(0);
+        patterns[0].matcher = PatternMatcher::Plain(PlainMatch {
+            pattern: "WARNING".to_string(),
+            case_sensitive: true,
+            whole_word: false,
+            case_folding: CaseFolding::default(),
+        });
+        patterns.push(EventPattern {
+            name: "retries".to_string(),
+            matcher: PatternMatcher::Plain(PlainMatch {
+                pattern: "Retrying".to_string(),
+                case_sensitive: true,
+                whole_word: false,
+                case_folding: CaseFolding::default(),
+            }),
+            ..patterns[0].clone()
+        });
+        tracker.replace_configured_patterns(patterns);
+
+        let stats: Vec<(String, bool)> = tracker
+            .get_event_stats()
+            .into_iter()
+            .map(|state| (state.name, state.enabled))
+            .collect();
+        assert_eq!(
+            stats,
+            vec![
+                ("warning".to_string(), false),
+                ("info".to_string(), true),
+                ("retries".to_string(), true),
+                ("timeouts".to_string(), true),
+            ]
+        );
+        assert_eq!(tracker.get_event_matcher("warning").unwrap().pattern(), "WARNING");
+    }
 }
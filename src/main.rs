@@ -1,18 +1,27 @@
 use clap::Parser;
+use color_eyre::eyre::{OptionExt, eyre};
 use crossterm::{
+    event::DisableMouseCapture,
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use lazylog::{
+    app::App,
+    cli::Cli,
+    debug_log, headless, migration,
+    pager::{self, ScreenRead},
+    persistence,
 };
-use lazylog::{app::App, cli::Cli, debug_log};
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::io::{LineWriter, stderr, stdout};
+use std::io::{LineWriter, Write, stderr, stdout};
 use tracing::{debug, info};
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
-    let args = Cli::parse();
+    let mut args = Cli::parse();
+    args.take_startup_commands();
 
     if let Some(ref debug_path) = args.debug {
         debug_log::init(debug_path)?;
@@ -20,7 +29,46 @@ async fn main() -> color_eyre::Result<()> {
 
     info!("Starting lazylog with args: {:?}", args);
 
-    if args.should_use_stdin() {
+    if args.migrate_dry_run {
+        let state_dir = persistence::state_dir().ok_or_eyre("Could not find home directory")?;
+        let report = migration::dry_run_report(&state_dir).map_err(|e| eyre!(e))?;
+        println!("{}", report);
+        return Ok(());
+    }
+
+    if args.test_timestamps {
+        headless::test_timestamps(&args)?;
+        return Ok(());
+    }
+
+    if args.headless {
+        let printed = headless::run(&args)?;
+        if printed == 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let streaming = args.should_use_stdin() || args.should_run_command();
+    if streaming && args.session.is_some() {
+        return Err(eyre!("--session can't be used when streaming from stdin or a command"));
+    }
+
+    if args.quit_if_one_screen && args.should_use_stdin() {
+        let (width, height) = terminal::size()?;
+        match pager::read_screen(&mut std::io::stdin().lock(), width as usize, height as usize)? {
+            ScreenRead::Complete(lines) => {
+                let mut out = stdout().lock();
+                for line in lines {
+                    writeln!(out, "{}", line)?;
+                }
+                return Ok(());
+            }
+            ScreenRead::Partial(lines) => args.stdin_lines = lines,
+        }
+    }
+
+    if streaming {
         run_streaming_mode(args).await
     } else {
         run_file_mode(args).await
@@ -72,7 +120,7 @@ fn set_panic_hook_stderr() {
     let hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
-        let _ = execute!(stderr(), LeaveAlternateScreen);
+        let _ = execute!(stderr(), DisableMouseCapture, LeaveAlternateScreen);
         hook(panic_info);
     }));
 }
@@ -81,7 +129,7 @@ fn set_panic_hook_stdout() {
     let hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
-        let _ = execute!(stdout(), LeaveAlternateScreen);
+        let _ = execute!(stdout(), DisableMouseCapture, LeaveAlternateScreen);
         hook(panic_info);
     }));
 }
@@ -1,6 +1,6 @@
 use crate::log::LogLine;
 use crate::resolver::{Tag, TagRule, VisibilityRule};
-use crate::utils::contains_ignore_case;
+use crate::utils::{CaseFolding, contains_ignore_case};
 use rayon::prelude::*;
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -56,8 +56,8 @@ impl Marking {
     /// Add a new named mark or update existing mark name
     pub fn add_named_mark(&mut self, line_index: usize, name: &str) {
         match self.marks.binary_search_by_key(&line_index, |mark| mark.line_index) {
-            Ok(pos) => {
-                self.set_mark_name(pos, name);
+            Ok(_) => {
+                self.set_mark_name(line_index, name);
             }
             Err(pos) => {
                 self.marks.insert(pos, Mark::new_with_name(line_index, name));
@@ -80,7 +80,12 @@ impl Marking {
     }
 
     /// Creates marks for all lines matching the given pattern (case-insensitive).
-    pub fn create_marks_from_pattern<'a>(&mut self, pattern: &str, lines: impl Iterator<Item = &'a LogLine>) {
+    pub fn create_marks_from_pattern<'a>(
+        &mut self,
+        pattern: &str,
+        lines: impl Iterator<Item = &'a LogLine>,
+        folding: CaseFolding,
+    ) {
         if pattern.is_empty() {
             return;
         }
@@ -93,7 +98,9 @@ impl Marking {
         let new_marks: Vec<Mark> = lines_vec
             .par_iter()
             .filter_map(|log_line| {
-                if contains_ignore_case(log_line.content(), &pattern_str) && !marked_set.contains(&log_line.index) {
+                if contains_ignore_case(log_line.content(), &pattern_str, folding)
+                    && !marked_set.contains(&log_line.index)
+                {
                     Some(Mark::new_with_name(log_line.index, &pattern_str))
                 } else {
                     None
@@ -132,12 +139,39 @@ impl Marking {
         &self.marks
     }
 
+    /// Removes marks on the first `count` lines and shifts the rest after those lines were dropped.
+    pub fn drop_lines_before(&mut self, count: usize) {
+        self.marks.retain(|mark| mark.line_index >= count);
+        for mark in &mut self.marks {
+            mark.line_index -= count;
+        }
+    }
+
+    /// Moves marks to new line indices, e.g. after the file was reloaded. Marks on lines without a
+    /// new index are removed.
+    pub fn remap_lines(&mut self, new_index: impl Fn(usize) -> Option<usize>) {
+        self.marks.retain_mut(|mark| match new_index(mark.line_index) {
+            Some(line_index) => {
+                mark.line_index = line_index;
+                true
+            }
+            None => false,
+        });
+        self.marks.sort_by_key(|mark| mark.line_index);
+        self.marks.dedup_by_key(|mark| mark.line_index);
+    }
+
     /// Clears all marks.
     pub fn clear_all(&mut self) {
         self.marks.clear();
     }
 }
 
+/// Orders marks by name, keeping them in line order within each name and putting unnamed marks last.
+pub fn group_marks_by_name(marks: &mut [Mark]) {
+    marks.sort_by(|a, b| (a.name.is_none(), &a.name).cmp(&(b.name.is_none(), &b.name)));
+}
+
 /// Tag rule that marks lines as marked
 pub struct MarkTagRule {
     marked_indices: Arc<HashSet<usize>>,
@@ -194,6 +228,20 @@ mod tests {
         assert_eq!(mark.name, Some("important".to_string()));
     }
 
+    #[test]
+    fn test_group_marks_by_name() {
+        let mut marks = vec![
+            Mark::new_with_name(1, "timeout"),
+            Mark::new(2),
+            Mark::new_with_name(3, "retry"),
+            Mark::new_with_name(4, "timeout"),
+            Mark::new_with_name(5, "retry"),
+        ];
+        group_marks_by_name(&mut marks);
+        let order: Vec<usize> = marks.iter().map(|mark| mark.line_index).collect();
+        assert_eq!(order, vec![3, 5, 1, 4, 2]);
+    }
+
     #[test]
     fn test_toggle_mark_adds_mark() {
         let mut marking = Marking::default();
@@ -226,6 +274,20 @@ mod tests {
         assert_eq!(marking.count(), 3);
     }
 
+    #[test]
+    fn test_add_named_mark_renames_existing_mark() {
+        let mut marking = Marking::default();
+        marking.toggle_mark(3);
+        marking.toggle_mark(10);
+        marking.add_named_mark(10, "slow request");
+
+        let marks = marking.get_marks();
+        assert_eq!(marks.len(), 2);
+        assert_eq!(marks[0].name, None);
+        assert_eq!(marks[1].line_index, 10);
+        assert_eq!(marks[1].name, Some("slow request".to_string()));
+    }
+
     #[test]
     fn test_create_marks_from_pattern_case_insensitive() {
         let log_lines = [
@@ -236,7 +298,7 @@ mod tests {
         ];
 
         let mut marking = Marking::default();
-        marking.create_marks_from_pattern("ErRoR", log_lines.iter());
+        marking.create_marks_from_pattern("ErRoR", log_lines.iter(), CaseFolding::default());
 
         assert_eq!(marking.count(), 3);
         let marks = marking.get_marks();
@@ -256,7 +318,7 @@ mod tests {
         ];
 
         let mut marking = Marking::default();
-        marking.create_marks_from_pattern("error", log_lines.iter());
+        marking.create_marks_from_pattern("error", log_lines.iter(), CaseFolding::default());
 
         assert_eq!(marking.count(), 2);
         let marks = marking.get_marks();
@@ -265,4 +327,44 @@ mod tests {
         assert_eq!(marks[0].name, Some("error".to_string()));
         assert_eq!(marks[1].name, Some("error".to_string()));
     }
+
+    #[test]
+    fn test_drop_lines_before_rebases_marks() {
+        let mut marking = Marking::default();
+        marking.toggle_mark(2);
+        marking.add_named_mark(5, "restart");
+        marking.toggle_mark(9);
+
+        marking.drop_lines_before(4);
+
+        let marks = marking.get_marks();
+        assert_eq!(marks.len(), 2);
+        assert_eq!(marks[0].line_index, 1);
+        assert_eq!(marks[0].name, Some("restart".to_string()));
+        assert_eq!(marks[1].line_index, 5);
+    }
+
+    #[test]
+    fn test_remap_lines_follows_content_after_reload() {
+        use crate::log::match_lines_by_content;
+
+        let lines = |contents: &[&str]| -> Vec<LogLine> {
+            contents.iter().enumerate().map(|(i, c)| LogLine::new(c, i)).collect()
+        };
+        let old = lines(&["start", "a", "b", "a", "end"]);
+        let new = lines(&["header", "start", "a", "a", "end", "more"]);
+
+        let mut marking = Marking::default();
+        marking.add_named_mark(2, "removed");
+        marking.toggle_mark(3);
+        marking.toggle_mark(4);
+
+        let line_map = match_lines_by_content(&old, &new);
+        assert_eq!(line_map, vec![Some(1), Some(2), None, Some(3), Some(4)]);
+
+        marking.remap_lines(|line_index| line_map[line_index]);
+
+        let indices: Vec<usize> = marking.get_marks().iter().map(|mark| mark.line_index).collect();
+        assert_eq!(indices, vec![3, 4]);
+    }
 }
@@ -1,4 +1,5 @@
-use crate::utils::contains_ignore_case;
+use crate::fuzzy::{fuzzy_contains, fuzzy_match};
+use crate::utils::{CaseFolding, contains_ignore_case, contains_whole_word, find_ignore_case, find_whole_words};
 use regex::Regex;
 
 /// Type of pattern matching to use.
@@ -15,46 +16,70 @@ pub struct PlainMatch {
     pub pattern: String,
     /// Whether matching should be case-sensitive
     pub case_sensitive: bool,
+    /// Whether only occurrences that are whole words match
+    pub whole_word: bool,
+    /// How the case of characters is folded when matching case-insensitively
+    pub case_folding: CaseFolding,
 }
 
 impl PlainMatch {
     /// Returns true if there is a match for the plain match pattern anywhere in the haystack given.
     pub fn is_match(&self, haystack: &str) -> bool {
-        if self.case_sensitive {
+        if self.whole_word {
+            contains_whole_word(haystack, &self.pattern, self.case_sensitive, self.case_folding)
+        } else if self.case_sensitive {
             haystack.contains(&self.pattern)
         } else {
-            contains_ignore_case(haystack, &self.pattern)
+            contains_ignore_case(haystack, &self.pattern, self.case_folding)
         }
     }
 
     /// Find all occurrences of a substring in the haystack
     pub fn find(&self, haystack: &str) -> Vec<(usize, usize)> {
-        if self.case_sensitive {
+        if self.whole_word {
+            find_whole_words(haystack, &self.pattern, self.case_sensitive, self.case_folding).collect()
+        } else if self.case_sensitive {
             haystack
                 .match_indices(&self.pattern)
                 .map(|(start, matched)| (start, start + matched.len()))
                 .collect()
         } else {
-            self.find_all_ignore_case(haystack)
+            find_ignore_case(haystack, &self.pattern, self.case_folding)
         }
     }
+}
+
+/// Fuzzy pattern matcher, matching the characters of the pattern in order with anything in between.
+#[derive(Debug, Clone)]
+pub struct FuzzyPattern {
+    pub pattern: String,
+    pub case_sensitive: bool,
+}
 
-    /// Finds all case-insensitive occurrences of a substring in text.
-    fn find_all_ignore_case(&self, text: &str) -> Vec<(usize, usize)> {
-        let pattern_bytes = self.pattern.as_bytes();
-        let text_bytes = text.as_bytes();
-        let pattern_len = pattern_bytes.len();
+impl FuzzyPattern {
+    /// Returns true if the haystack contains the characters of the pattern in order.
+    pub fn is_match(&self, haystack: &str) -> bool {
+        fuzzy_contains(haystack, &self.pattern, self.case_sensitive)
+    }
 
-        if pattern_len == 0 || pattern_len > text_bytes.len() {
+    /// Finds the matched characters, joining consecutive characters into one range.
+    pub fn find(&self, haystack: &str) -> Vec<(usize, usize)> {
+        if self.pattern.is_empty() {
             return Vec::new();
         }
+        let Some(result) = fuzzy_match(haystack, &self.pattern, self.case_sensitive) else {
+            return Vec::new();
+        };
 
-        text_bytes
-            .windows(pattern_len)
-            .enumerate()
-            .filter(|(_, window)| window.eq_ignore_ascii_case(pattern_bytes))
-            .map(|(idx, _)| (idx, idx + pattern_len))
-            .collect()
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for position in result.positions {
+            let end = position + haystack[position..].chars().next().map_or(0, char::len_utf8);
+            match ranges.last_mut() {
+                Some(last) if last.1 == position => last.1 = end,
+                _ => ranges.push((position, end)),
+            }
+        }
+        ranges
     }
 }
 
@@ -65,6 +90,8 @@ pub enum PatternMatcher {
     Plain(PlainMatch),
     /// Regular expression matching (case sensitivity determined at compile time)
     Regex(Regex),
+    /// Fuzzy matching of the pattern characters in order
+    Fuzzy(FuzzyPattern),
 }
 
 impl PatternMatcher {
@@ -73,6 +100,16 @@ impl PatternMatcher {
         match self {
             PatternMatcher::Plain(s) => s.is_match(text),
             PatternMatcher::Regex(r) => r.is_match(text),
+            PatternMatcher::Fuzzy(f) => f.is_match(text),
+        }
+    }
+
+    /// Returns the pattern as written, the regex source for a regex.
+    pub fn pattern(&self) -> &str {
+        match self {
+            PatternMatcher::Plain(plain) => &plain.pattern,
+            PatternMatcher::Regex(regex) => regex.as_str(),
+            PatternMatcher::Fuzzy(fuzzy) => &fuzzy.pattern,
         }
     }
 
@@ -83,6 +120,7 @@ impl PatternMatcher {
         match self {
             PatternMatcher::Plain(plain_match) => plain_match.find(text),
             PatternMatcher::Regex(r) => r.find_iter(text).map(|m| (m.start(), m.end())).collect(),
+            PatternMatcher::Fuzzy(f) => f.find(text),
         }
     }
 }
@@ -0,0 +1,245 @@
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version of the state file format written by this version of lazylog.
+pub const STATE_VERSION: u64 = 3;
+
+/// Upgrades a state file from one version to the next.
+struct Migration {
+    /// Version the migration upgrades from. The result has version `from + 1`.
+    from: u64,
+    /// What the migration changes, shown by `--migrate-dry-run`.
+    description: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// All migrations, ordered by the version they upgrade from.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 1,
+        description: "add fields missing in older version 1 files (custom events, notes, regex flag of filters)",
+        apply: add_missing_v1_fields,
+    },
+    Migration {
+        from: 2,
+        description: "add content identities of the log files, the state is also saved under them on next save",
+        apply: add_file_ids,
+    },
+];
+
+/// Result of migrating a state file.
+#[derive(Debug, PartialEq)]
+pub enum MigrationOutcome {
+    /// The state file already has the current version.
+    UpToDate,
+    /// The state file was upgraded from the given version. Contains the applied changes.
+    Migrated(u64, Vec<&'static str>),
+    /// The state file was written by a newer version of lazylog and is left untouched.
+    TooNew(u64),
+}
+
+/// Upgrades state file JSON to the current version in place.
+pub fn migrate(state: &mut Value) -> Result<MigrationOutcome, String> {
+    let object = state.as_object_mut().ok_or("State file is not a JSON object")?;
+    let from_version = object
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or("State file has no version")?;
+
+    if from_version > STATE_VERSION {
+        return Ok(MigrationOutcome::TooNew(from_version));
+    }
+
+    let mut changes = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= from_version) {
+        (migration.apply)(object);
+        object.insert("version".to_string(), Value::from(migration.from + 1));
+        changes.push(migration.description);
+    }
+
+    if changes.is_empty() {
+        Ok(MigrationOutcome::UpToDate)
+    } else {
+        Ok(MigrationOutcome::Migrated(from_version, changes))
+    }
+}
+
+/// Reads a state file and migrates it to the current version.
+///
+/// If the file was migrated, a backup of the previous file is written next to it before the migrated
+/// file replaces it. Returns the migrated JSON, or `None` if the file was written by a newer version.
+pub fn migrate_file(path: &Path) -> Result<Option<Value>, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let mut state: Value = serde_json::from_str(&json).map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+
+    match migrate(&mut state)? {
+        MigrationOutcome::UpToDate => Ok(Some(state)),
+        MigrationOutcome::TooNew(_) => Ok(None),
+        MigrationOutcome::Migrated(from_version, _) => {
+            let backup_path = backup_path(path, from_version);
+            fs::write(&backup_path, &json).map_err(|e| format!("Failed to back up {:?}: {}", path, e))?;
+            let migrated = serde_json::to_string_pretty(&state).map_err(|e| e.to_string())?;
+            // Written next to the state file and renamed over it, so an interrupted write can't corrupt it
+            let temp_path = path.with_extension("json.tmp");
+            if let Err(e) = fs::write(&temp_path, migrated).and_then(|_| fs::rename(&temp_path, path)) {
+                let _ = fs::remove_file(&temp_path);
+                return Err(format!("Failed to write {:?}: {}", path, e));
+            }
+            Ok(Some(state))
+        }
+    }
+}
+
+/// Returns the path of the backup of a state file before migrating it from the given version.
+fn backup_path(path: &Path, version: u64) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".v{}.bak", version));
+    path.with_file_name(file_name)
+}
+
+/// Describes what migrating the state files in the given directory would change, without writing anything.
+pub fn dry_run_report(state_dir: &Path) -> Result<String, String> {
+    if !state_dir.exists() {
+        return Ok(format!("No state directory found at {:?}.", state_dir));
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(state_dir)
+        .map_err(|e| format!("Failed to read state directory: {}", e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+
+    let mut report = Vec::new();
+    let mut pending = 0;
+    for path in &paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let outcome = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str::<Value>(&json).map_err(|e| e.to_string()))
+            .and_then(|mut state| migrate(&mut state));
+
+        match outcome {
+            Ok(MigrationOutcome::UpToDate) => report.push(format!("{}: up to date", name)),
+            Ok(MigrationOutcome::TooNew(version)) => report.push(format!(
+                "{}: version {} is newer than {}, left untouched",
+                name, version, STATE_VERSION
+            )),
+            Ok(MigrationOutcome::Migrated(version, changes)) => {
+                pending += 1;
+                report.push(format!(
+                    "{}: version {} -> {}, backup {}",
+                    name,
+                    version,
+                    STATE_VERSION,
+                    backup_path(Path::new(name.as_ref()), version).display()
+                ));
+                report.extend(changes.iter().map(|change| format!("  - {}", change)));
+            }
+            Err(e) => report.push(format!("{}: unreadable, ignored ({})", name, e)),
+        }
+    }
+
+    report.push(format!(
+        "{} of {} state file(s) in {:?} would be migrated. Nothing was changed.",
+        pending,
+        paths.len(),
+        state_dir
+    ));
+    Ok(report.join("\n"))
+}
+
+/// Version 1 files written before custom events, notes and regex filters existed lack those fields.
+fn add_missing_v1_fields(state: &mut Map<String, Value>) {
+    state.entry("custom_events").or_insert_with(|| Value::Array(Vec::new()));
+    state.entry("notes").or_insert_with(|| Value::String(String::new()));
+    if let Some(Value::Array(filters)) = state.get_mut("filters") {
+        for filter in filters.iter_mut().filter_map(Value::as_object_mut) {
+            filter.entry("regex").or_insert(Value::Bool(false));
+        }
+    }
+}
+
+/// Version 2 files are keyed by path only. The identities of the files are filled in on next save.
+fn add_file_ids(state: &mut Map<String, Value>) {
+    state.entry("file_ids").or_insert_with(|| Value::Array(Vec::new()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_migrate_v1_adds_missing_fields() {
+        let mut state = json!({
+            "version": 1,
+            "filters": [{ "pattern": "ERROR", "mode": "Include", "case_sensitive": true, "enabled": true }],
+            "marks": [{ "line_index": 3, "name": "start" }],
+        });
+
+        let outcome = migrate(&mut state).unwrap();
+
+        assert!(matches!(outcome, MigrationOutcome::Migrated(1, ref changes) if changes.len() == 2));
+        assert_eq!(state["version"], json!(STATE_VERSION));
+        assert_eq!(state["custom_events"], json!([]));
+        assert_eq!(state["notes"], json!(""));
+        assert_eq!(state["filters"][0]["regex"], json!(false));
+        assert_eq!(state["marks"], json!([{ "line_index": 3, "name": "start" }]));
+        assert_eq!(state["file_ids"], json!([]));
+    }
+
+    #[test]
+    fn test_migrate_v2_adds_file_ids() {
+        let mut state = json!({ "version": 2, "log_file_paths": ["/var/log/app.log"] });
+
+        let outcome = migrate(&mut state).unwrap();
+
+        assert!(matches!(outcome, MigrationOutcome::Migrated(2, ref changes) if changes.len() == 1));
+        assert_eq!(state["version"], json!(STATE_VERSION));
+        assert_eq!(state["file_ids"], json!([]));
+        assert_eq!(state["log_file_paths"], json!(["/var/log/app.log"]));
+    }
+
+    #[test]
+    fn test_migrate_keeps_current_and_newer_versions() {
+        let mut current = json!({ "version": STATE_VERSION, "notes": "keep" });
+        assert_eq!(migrate(&mut current).unwrap(), MigrationOutcome::UpToDate);
+        assert_eq!(current["notes"], json!("keep"));
+
+        let mut newer = json!({ "version": STATE_VERSION + 1 });
+        assert_eq!(
+            migrate(&mut newer).unwrap(),
+            MigrationOutcome::TooNew(STATE_VERSION + 1)
+        );
+
+        assert!(migrate(&mut json!({ "notes": "" })).is_err());
+    }
+
+    #[test]
+    fn test_migrate_file_replaces_the_state_file() {
+        let dir = std::env::temp_dir().join(format!("lazylog-migrate-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+        let old_json = json!({ "version": 2, "log_file_paths": ["/var/log/app.log"] }).to_string();
+        fs::write(&path, &old_json).unwrap();
+
+        let state = migrate_file(&path).unwrap().unwrap();
+
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, state);
+        assert_eq!(written["version"], json!(STATE_VERSION));
+        assert_eq!(fs::read_to_string(backup_path(&path, 2)).unwrap(), old_json);
+        assert!(!path.with_extension("json.tmp").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_backup_path() {
+        assert_eq!(
+            backup_path(Path::new("/home/user/.lazylog/abc.json"), 1),
+            PathBuf::from("/home/user/.lazylog/abc.json.v1.bak")
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::resolver::VisibleLine;
+
+/// Number of minimaps kept by [`MinimapCache`], one per pane of a split view.
+const CACHED_MINIMAPS: usize = 2;
+
+/// Kind of line shown as a tick on the minimap, ordered from least to most important.
+///
+/// A minimap row covering lines of several kinds shows the most important one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MinimapTick {
+    Event,
+    SearchMatch,
+    Mark,
+    CriticalEvent,
+}
+
+/// Overview of where events, marks and search matches are in the visible lines, scaled to a
+/// number of rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Minimap {
+    total_lines: usize,
+    /// Most important tick of each row.
+    ticks: Vec<Option<MinimapTick>>,
+    /// First viewport line with a tick in each row.
+    first_lines: Vec<Option<usize>>,
+}
+
+impl Minimap {
+    /// Builds a minimap of `height` rows for `total_lines` lines from the viewport lines with a tick.
+    pub fn new(total_lines: usize, height: usize, ticks: impl IntoIterator<Item = (usize, MinimapTick)>) -> Self {
+        let mut minimap = Self {
+            total_lines,
+            ticks: vec![None; height],
+            first_lines: vec![None; height],
+        };
+        for (line, tick) in ticks {
+            let Some(row) = minimap.line_to_row(line) else {
+                continue;
+            };
+            minimap.ticks[row] = minimap.ticks[row].max(Some(tick));
+            minimap.first_lines[row] = Some(minimap.first_lines[row].map_or(line, |first| first.min(line)));
+        }
+        minimap
+    }
+
+    /// Returns the most important tick of each row.
+    pub fn ticks(&self) -> &[Option<MinimapTick>] {
+        &self.ticks
+    }
+
+    /// Returns the row showing the given viewport line.
+    pub fn line_to_row(&self, line: usize) -> Option<usize> {
+        if line >= self.total_lines || self.ticks.is_empty() {
+            return None;
+        }
+        Some(line * self.ticks.len() / self.total_lines)
+    }
+
+    /// Returns the first viewport line shown in the given row.
+    pub fn row_to_line(&self, row: usize) -> Option<usize> {
+        if row >= self.ticks.len() || self.total_lines == 0 {
+            return None;
+        }
+        // Smallest line mapped to the row, the inverse of `line_to_row`
+        let line = (row * self.total_lines).div_ceil(self.ticks.len());
+        (line < self.total_lines).then_some(line)
+    }
+
+    /// Returns the rows covering the lines from `start` up to, but not including, `end`.
+    pub fn rows_between(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        let first = self.line_to_row(start)?;
+        let last = self.line_to_row(end.saturating_sub(1).clamp(start, self.total_lines - 1))?;
+        Some((first, last))
+    }
+
+    /// Returns the first line with a tick in the next row with a tick after the row of the given line.
+    pub fn next_tick_line(&self, line: usize) -> Option<usize> {
+        let row = self.line_to_row(line)?;
+        self.first_lines[row + 1..].iter().flatten().next().copied()
+    }
+
+    /// Returns the first line with a tick in the previous row with a tick before the row of the given line.
+    pub fn previous_tick_line(&self, line: usize) -> Option<usize> {
+        let row = self.line_to_row(line)?;
+        self.first_lines[..row].iter().rev().flatten().next().copied()
+    }
+}
+
+/// What a minimap was built from. A minimap is only rebuilt when any of it changed.
+#[derive(Debug, Clone)]
+pub struct MinimapKey {
+    /// Generation of the log buffer.
+    pub generation: u64,
+    /// Number of rows of the minimap.
+    pub height: usize,
+    /// Number of lines of the viewport.
+    pub total_lines: usize,
+    /// Hash of the events, marks and search matches shown as ticks.
+    pub ticks_hash: u64,
+    /// Visible lines the ticks were mapped to. Holding them keeps their address from being reused.
+    pub visible_lines: Rc<Vec<VisibleLine>>,
+}
+
+impl PartialEq for MinimapKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.generation == other.generation
+            && self.height == other.height
+            && self.total_lines == other.total_lines
+            && self.ticks_hash == other.ticks_hash
+            && Rc::ptr_eq(&self.visible_lines, &other.visible_lines)
+    }
+}
+
+/// The most recently built minimaps, so they are not rebuilt on every frame.
+#[derive(Debug, Default)]
+pub struct MinimapCache {
+    entries: RefCell<Vec<(MinimapKey, Minimap)>>,
+}
+
+impl MinimapCache {
+    /// Returns the minimap built for `key`, building it with `build` if it isn't cached.
+    pub fn get_or_build(&self, key: MinimapKey, build: impl FnOnce() -> Minimap) -> Minimap {
+        let mut entries = self.entries.borrow_mut();
+        if let Some(position) = entries.iter().position(|(cached_key, _)| *cached_key == key) {
+            let entry = entries.remove(position);
+            let minimap = entry.1.clone();
+            entries.push(entry);
+            return minimap;
+        }
+        let minimap = build();
+        if entries.len() == CACHED_MINIMAPS {
+            entries.remove(0);
+        }
+        entries.push((key, minimap.clone()));
+        minimap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimap_rows() {
+        let minimap = Minimap::new(
+            100,
+            10,
+            [
+                (5, MinimapTick::Event),
+                (7, MinimapTick::Mark),
+                (3, MinimapTick::SearchMatch),
+                (55, MinimapTick::CriticalEvent),
+                (99, MinimapTick::Event),
+                (100, MinimapTick::Mark),
+            ],
+        );
+
+        let mut expected = vec![None; 10];
+        expected[0] = Some(MinimapTick::Mark);
+        expected[5] = Some(MinimapTick::CriticalEvent);
+        expected[9] = Some(MinimapTick::Event);
+        assert_eq!(minimap.ticks(), expected);
+
+        assert_eq!(minimap.next_tick_line(0), Some(55));
+        assert_eq!(minimap.next_tick_line(55), Some(99));
+        assert_eq!(minimap.next_tick_line(99), None);
+        assert_eq!(minimap.previous_tick_line(99), Some(55));
+        assert_eq!(minimap.previous_tick_line(50), Some(3));
+        assert_eq!(minimap.previous_tick_line(9), None);
+    }
+
+    #[test]
+    fn test_minimap_row_to_line() {
+        // More rows than lines
+        let minimap = Minimap::new(3, 10, []);
+        assert_eq!(minimap.line_to_row(1), Some(3));
+        assert_eq!(minimap.row_to_line(3), Some(1));
+        assert_eq!(minimap.row_to_line(9), None);
+
+        let minimap = Minimap::new(1000, 7, []);
+        for row in 0..7 {
+            let line = minimap.row_to_line(row).unwrap();
+            assert_eq!(minimap.line_to_row(line), Some(row));
+            assert_eq!(minimap.line_to_row(line.saturating_sub(1)), Some(row.saturating_sub(1)));
+        }
+        assert_eq!(minimap.rows_between(0, 150), Some((0, 1)));
+        assert_eq!(Minimap::new(0, 7, []).rows_between(0, 0), None);
+    }
+
+    #[test]
+    fn test_minimap_cache() {
+        let cache = MinimapCache::default();
+        let visible_lines = Rc::new(Vec::new());
+        let key = |height| MinimapKey {
+            generation: 1,
+            height,
+            total_lines: 100,
+            ticks_hash: 0,
+            visible_lines: Rc::clone(&visible_lines),
+        };
+        let builds = std::cell::Cell::new(0);
+        let build = |height| {
+            builds.set(builds.get() + 1);
+            Minimap::new(100, height, [(5, MinimapTick::Event)])
+        };
+
+        // Both panes of a split view stay cached
+        assert_eq!(cache.get_or_build(key(10), || build(10)).ticks().len(), 10);
+        assert_eq!(cache.get_or_build(key(4), || build(4)).ticks().len(), 4);
+        assert_eq!(cache.get_or_build(key(10), || build(10)).ticks().len(), 10);
+        assert_eq!(builds.get(), 2);
+
+        // Other visible lines, e.g. after the filters changed
+        let other_lines = MinimapKey {
+            visible_lines: Rc::new(Vec::new()),
+            ..key(10)
+        };
+        cache.get_or_build(other_lines, || build(10));
+        assert_eq!(builds.get(), 3);
+
+        // The least recently used minimap was dropped
+        cache.get_or_build(key(4), || build(4));
+        assert_eq!(builds.get(), 4);
+    }
+}
@@ -0,0 +1,302 @@
+use std::cell::Cell;
+
+/// Reference to a mark inside the notes, written as `@name` or `@{name with spaces}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkReference {
+    /// Character index where the reference starts (at the `@`).
+    pub start: usize,
+    /// Character index just past the end of the reference.
+    pub end: usize,
+    /// Name of the referenced mark.
+    pub name: String,
+}
+
+/// Free-form notes buffer with a cursor for editing.
+#[derive(Debug)]
+pub struct Notes {
+    /// Lines of text. Always contains at least one line.
+    lines: Vec<String>,
+    /// Line the cursor is on.
+    cursor_row: usize,
+    /// Character position of the cursor within the line.
+    cursor_col: usize,
+    /// First visible line (updated when rendering).
+    top_row: Cell<usize>,
+    /// First visible column (updated when rendering).
+    left_col: Cell<usize>,
+}
+
+impl Default for Notes {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            cursor_row: 0,
+            cursor_col: 0,
+            top_row: Cell::new(0),
+            left_col: Cell::new(0),
+        }
+    }
+}
+
+impl Notes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the notes with the given text and moves the cursor to the end.
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = text.lines().map(|line| line.to_string()).collect();
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.cursor_row = self.lines.len() - 1;
+        self.cursor_col = self.line_len(self.cursor_row);
+    }
+
+    /// Returns the notes as a single string.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Returns whether the notes are empty.
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.is_empty())
+    }
+
+    /// Returns the lines of the notes.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Returns the cursor position as (row, column).
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.cursor_row, self.cursor_col)
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// Converts a character position in a line to a byte offset.
+    fn byte_offset(line: &str, col: usize) -> usize {
+        line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len())
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        let line = &mut self.lines[self.cursor_row];
+        let offset = Self::byte_offset(line, self.cursor_col);
+        line.insert(offset, c);
+        self.cursor_col += 1;
+    }
+
+    pub fn insert_newline(&mut self) {
+        let line = &mut self.lines[self.cursor_row];
+        let offset = Self::byte_offset(line, self.cursor_col);
+        let rest = line.split_off(offset);
+        self.cursor_row += 1;
+        self.cursor_col = 0;
+        self.lines.insert(self.cursor_row, rest);
+    }
+
+    /// Deletes the character before the cursor, joining lines at the start of a line.
+    pub fn backspace(&mut self) {
+        if self.cursor_col > 0 {
+            let line = &mut self.lines[self.cursor_row];
+            let offset = Self::byte_offset(line, self.cursor_col - 1);
+            line.remove(offset);
+            self.cursor_col -= 1;
+        } else if self.cursor_row > 0 {
+            let line = self.lines.remove(self.cursor_row);
+            self.cursor_row -= 1;
+            self.cursor_col = self.line_len(self.cursor_row);
+            self.lines[self.cursor_row].push_str(&line);
+        }
+    }
+
+    /// Deletes the character under the cursor, joining lines at the end of a line.
+    pub fn delete(&mut self) {
+        if self.cursor_col < self.line_len(self.cursor_row) {
+            let line = &mut self.lines[self.cursor_row];
+            let offset = Self::byte_offset(line, self.cursor_col);
+            line.remove(offset);
+        } else if self.cursor_row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.cursor_row + 1);
+            self.lines[self.cursor_row].push_str(&next);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if self.cursor_col > 0 {
+            self.cursor_col -= 1;
+        } else if self.cursor_row > 0 {
+            self.cursor_row -= 1;
+            self.cursor_col = self.line_len(self.cursor_row);
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.cursor_col < self.line_len(self.cursor_row) {
+            self.cursor_col += 1;
+        } else if self.cursor_row + 1 < self.lines.len() {
+            self.cursor_row += 1;
+            self.cursor_col = 0;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.cursor_row > 0 {
+            self.cursor_row -= 1;
+            self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_row));
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor_row + 1 < self.lines.len() {
+            self.cursor_row += 1;
+            self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_row));
+        }
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor_col = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor_col = self.line_len(self.cursor_row);
+    }
+
+    /// Scrolls the visible area so the cursor is inside it and returns the (top row, left column).
+    pub fn scroll_to_cursor(&self, height: usize, width: usize) -> (usize, usize) {
+        let top = self.top_row.get();
+        if self.cursor_row < top {
+            self.top_row.set(self.cursor_row);
+        } else if height > 0 && self.cursor_row >= top + height {
+            self.top_row.set(self.cursor_row + 1 - height);
+        }
+
+        let left = self.left_col.get();
+        if self.cursor_col < left {
+            self.left_col.set(self.cursor_col);
+        } else if width > 0 && self.cursor_col >= left + width {
+            self.left_col.set(self.cursor_col + 1 - width);
+        }
+
+        (self.top_row.get(), self.left_col.get())
+    }
+
+    /// Returns the current (top row, left column) of the visible area.
+    pub fn scroll_offset(&self) -> (usize, usize) {
+        (self.top_row.get(), self.left_col.get())
+    }
+
+    /// Returns the mark reference under the cursor, or the first one on the cursor line.
+    pub fn reference_at_cursor(&self) -> Option<String> {
+        let references = find_mark_references(&self.lines[self.cursor_row]);
+        references
+            .iter()
+            .find(|r| r.start <= self.cursor_col && self.cursor_col <= r.end)
+            .or_else(|| references.first())
+            .map(|r| r.name.clone())
+    }
+}
+
+/// Finds all mark references (`@name` or `@{name with spaces}`) in a line.
+pub fn find_mark_references(line: &str) -> Vec<MarkReference> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut references = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        // Only treat '@' as a reference at the start of a word, so e.g. email addresses are ignored
+        if chars[i] != '@' || (i > 0 && !chars[i - 1].is_whitespace() && chars[i - 1] != '(') {
+            i += 1;
+            continue;
+        }
+
+        if chars.get(i + 1) == Some(&'{') {
+            if let Some(close) = chars[i + 2..].iter().position(|&c| c == '}') {
+                let name: String = chars[i + 2..i + 2 + close].iter().collect();
+                let end = i + 3 + close;
+                if !name.trim().is_empty() {
+                    references.push(MarkReference {
+                        start: i,
+                        end,
+                        name: name.trim().to_string(),
+                    });
+                }
+                i = end;
+                continue;
+            }
+        } else {
+            let mut end = i + 1;
+            while end < chars.len() && !chars[end].is_whitespace() {
+                end += 1;
+            }
+            // Trailing punctuation is not part of the name
+            while end > i + 1 && matches!(chars[end - 1], '.' | ',' | ';' | ':' | ')' | '!' | '?') {
+                end -= 1;
+            }
+            if end > i + 1 {
+                references.push(MarkReference {
+                    start: i,
+                    end,
+                    name: chars[i + 1..end].iter().collect(),
+                });
+            }
+            i = end;
+            continue;
+        }
+        i += 1;
+    }
+
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editing() {
+        let mut notes = Notes::new();
+        for c in "hello".chars() {
+            notes.insert_char(c);
+        }
+        notes.insert_newline();
+        notes.insert_char('w');
+        assert_eq!(notes.text(), "hello\nw");
+
+        notes.move_home();
+        notes.backspace();
+        assert_eq!(notes.text(), "hellow");
+        assert_eq!(notes.cursor(), (0, 5));
+
+        notes.delete();
+        assert_eq!(notes.text(), "hello");
+
+        notes.set_text("a\nbc");
+        assert_eq!(notes.cursor(), (1, 2));
+        notes.move_up();
+        assert_eq!(notes.cursor(), (0, 1));
+    }
+
+    #[test]
+    fn test_find_mark_references() {
+        let refs = find_mark_references("crash after @restart, see @{first error} (mail me@host)");
+        let names: Vec<&str> = refs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["restart", "first error"]);
+        assert_eq!((refs[0].start, refs[0].end), (12, 20));
+    }
+
+    #[test]
+    fn test_reference_at_cursor() {
+        let mut notes = Notes::new();
+        notes.set_text("@one then @two");
+        assert_eq!(notes.reference_at_cursor(), Some("two".to_string()));
+        notes.move_home();
+        assert_eq!(notes.reference_at_cursor(), Some("one".to_string()));
+
+        notes.set_text("no references");
+        assert_eq!(notes.reference_at_cursor(), None);
+    }
+}
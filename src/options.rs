@@ -7,9 +7,17 @@ pub enum AppOption {
     DisableColors,
     HideFileIds,
     SearchDisableJumping,
+    SearchDisableHorizontalScroll,
     AlwaysShowMarkedLines,
     AlwaysShowCriticalEvents,
     AlwaysShowCustomEvents,
+    FoldMultiLineRecords,
+    ShowMinimap,
+    StickyHeader,
+    CollapseRepeatedLines,
+    CollapseSimilarRepeatedLines,
+    EventsPreview,
+    MuteNotifications,
 }
 
 #[derive(Debug, Clone)]
@@ -67,9 +75,23 @@ impl Default for AppOptions {
                 AppOptionDef::new_toggle(AppOption::DisableColors, "Disable Colors"),
                 AppOptionDef::new_toggle(AppOption::HideFileIds, "Hide File Indicator"),
                 AppOptionDef::new_toggle(AppOption::SearchDisableJumping, "Search: Disable jumping to match"),
+                AppOptionDef::new_toggle(AppOption::SearchDisableHorizontalScroll, "Search: Disable horizontal scroll"),
                 AppOptionDef::new_toggle(AppOption::AlwaysShowMarkedLines, "Always show marked lines"),
                 AppOptionDef::new_toggle(AppOption::AlwaysShowCriticalEvents, "Always show critical events"),
                 AppOptionDef::new_toggle(AppOption::AlwaysShowCustomEvents, "Always show custom events"),
+                AppOptionDef::new_toggle(AppOption::FoldMultiLineRecords, "Fold multi-line records"),
+                AppOptionDef::new_toggle(AppOption::ShowMinimap, "Show minimap"),
+                AppOptionDef::new_toggle(AppOption::StickyHeader, "Show sticky header"),
+                AppOptionDef::new_toggle(AppOption::CollapseRepeatedLines, "Collapse repeated lines"),
+                AppOptionDef::new_toggle(
+                    AppOption::CollapseSimilarRepeatedLines,
+                    "Collapse repeated lines differing in numbers and ids",
+                ),
+                AppOptionDef::new_toggle(AppOption::EventsPreview, "Events: Show lines around the selected event"),
+                AppOptionDef::new_toggle(
+                    AppOption::MuteNotifications,
+                    "Mute bells and desktop notifications of events and alerts",
+                ),
             ],
         }
     }
@@ -107,6 +129,12 @@ impl AppOptions {
         }
     }
 
+    pub fn toggle(&mut self, option: AppOption) {
+        if let Some(opt) = self.options.iter_mut().find(|opt| opt.option == option) {
+            opt.enabled = !opt.enabled;
+        }
+    }
+
     /// Applies all enabled line transform options to a line.
     pub fn apply_to_line<'a>(&self, line: &'a str) -> &'a str {
         for opt in &self.options {
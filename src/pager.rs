@@ -0,0 +1,128 @@
+use std::io::BufRead;
+
+use crate::ansi::AnsiMode;
+use crate::log::sanitize_line_owned;
+use crate::utils::display_width;
+
+/// A command given on the command line like to `less`, run once the input is loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupCommand {
+    /// `+G`: go to the last line.
+    GotoEnd,
+    /// `+N`: go to line N, counted from 1.
+    GotoLine(usize),
+    /// `+/pattern`: search for the pattern and go to the first match.
+    Search(String),
+}
+
+impl StartupCommand {
+    /// Parses a `+` argument, e.g. `+G`, `+/ERROR` or `+120`. Returns None if the argument is not a command.
+    pub fn parse(arg: &str) -> Option<Self> {
+        let command = arg.strip_prefix('+')?;
+        if command == "G" {
+            Some(Self::GotoEnd)
+        } else if let Some(pattern) = command.strip_prefix('/') {
+            (!pattern.is_empty()).then(|| Self::Search(pattern.to_string()))
+        } else {
+            command.parse().ok().filter(|&line| line > 0).map(Self::GotoLine)
+        }
+    }
+}
+
+/// Splits the startup commands from the file arguments, keeping arguments that are no command as files.
+pub fn split_startup_commands(args: Vec<String>) -> (Vec<String>, Vec<StartupCommand>) {
+    let mut files = Vec::new();
+    let mut commands = Vec::new();
+    for arg in args {
+        match StartupCommand::parse(&arg) {
+            Some(command) => commands.push(command),
+            None => files.push(arg),
+        }
+    }
+    (files, commands)
+}
+
+/// The beginning of the input, read to check if it fits on one screen.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScreenRead {
+    /// The whole input was read and fits on the screen.
+    Complete(Vec<String>),
+    /// The input continues beyond the lines read, or they are too long for the screen.
+    Partial(Vec<String>),
+}
+
+/// Reads lines until the input ends or more rows than `height` are needed to show them in `width` columns.
+pub fn read_screen(reader: &mut impl BufRead, width: usize, height: usize) -> std::io::Result<ScreenRead> {
+    let mut lines = Vec::new();
+    let mut rows = 0;
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            return Ok(ScreenRead::Complete(lines));
+        }
+        let line = String::from_utf8_lossy(&buf).trim_end_matches(['\n', '\r']).to_string();
+        rows += rows_needed(&line, width);
+        lines.push(line);
+        if rows > height {
+            return Ok(ScreenRead::Partial(lines));
+        }
+    }
+}
+
+/// Returns the number of rows a line takes when wrapped at `width` columns, ignoring ANSI escape sequences.
+fn rows_needed(line: &str, width: usize) -> usize {
+    let (content, _) = sanitize_line_owned(line.to_string(), AnsiMode::Strip);
+    display_width(&content).div_ceil(width.max(1)).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_startup_command() {
+        assert_eq!(StartupCommand::parse("+G"), Some(StartupCommand::GotoEnd));
+        assert_eq!(StartupCommand::parse("+120"), Some(StartupCommand::GotoLine(120)));
+        assert_eq!(
+            StartupCommand::parse("+/ERROR 5\\d\\d"),
+            Some(StartupCommand::Search("ERROR 5\\d\\d".to_string()))
+        );
+        assert_eq!(StartupCommand::parse("+0"), None);
+        assert_eq!(StartupCommand::parse("+/"), None);
+        assert_eq!(StartupCommand::parse("app.log"), None);
+
+        let args = vec!["+G".to_string(), "app.log".to_string(), "+x".to_string()];
+        let (files, commands) = split_startup_commands(args);
+        assert_eq!(files, vec!["app.log", "+x"]);
+        assert_eq!(commands, vec![StartupCommand::GotoEnd]);
+    }
+
+    #[test]
+    fn test_read_screen() {
+        let mut input = "one\ntwo\n".as_bytes();
+        assert_eq!(
+            read_screen(&mut input, 80, 2).unwrap(),
+            ScreenRead::Complete(vec!["one".to_string(), "two".to_string()])
+        );
+
+        let mut input = "one\ntwo\nthree\nfour\n".as_bytes();
+        assert_eq!(
+            read_screen(&mut input, 80, 2).unwrap(),
+            ScreenRead::Partial(vec!["one".to_string(), "two".to_string(), "three".to_string()])
+        );
+        assert_eq!(input, b"four\n");
+
+        // Long lines wrap, escape sequences take no space
+        let mut input = "\x1b[33mcommit abc\x1b[0m\n0123456789012\n".as_bytes();
+        assert!(matches!(
+            read_screen(&mut input, 10, 3).unwrap(),
+            ScreenRead::Complete(_)
+        ));
+        let mut input = "0123456789012\n0123456789012\n".as_bytes();
+        assert!(matches!(
+            read_screen(&mut input, 10, 3).unwrap(),
+            ScreenRead::Partial(_)
+        ));
+    }
+}
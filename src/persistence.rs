@@ -1,26 +1,48 @@
 use crate::app::App;
+use crate::config::Config;
 use crate::filter::{ActiveFilterMode, FilterHistoryEntry};
+use crate::log_event::LogEventTracker;
+use crate::migration::{STATE_VERSION, migrate_file};
 use crate::options::AppOption;
+use crate::registers::RegisterPosition;
+use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::fs;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
+/// Number of bytes at the start of a log file that are hashed to identify it by content.
+const CONTENT_ID_BYTES: u64 = 64 * 1024;
+
 #[derive(Serialize, Deserialize)]
 pub struct PersistedState {
-    version: u8,
+    version: u64,
     log_file_paths: Vec<String>,
+    /// Content identities of the log files, see [`file_ids`].
+    #[serde(default)]
+    file_ids: Vec<String>,
     viewport: ViewportState,
     search_history: Vec<String>,
+    /// Whether searches only match whole words.
+    #[serde(default)]
+    search_whole_word: bool,
     filter_history: Vec<FilterHistoryEntry>,
     filters: Vec<FilterPatternState>,
     marks: Vec<MarkState>,
+    #[serde(default)]
+    registers: Vec<RegisterState>,
     event_filters: Vec<EventFilterState>,
     #[serde(default)]
     custom_events: Vec<CustomEventState>,
     options: Vec<OptionState>,
+    #[serde(default)]
+    notes: String,
+    /// Whether the state was autosaved while lazylog was running, rather than saved when quitting.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    autosaved: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -43,6 +65,12 @@ pub struct FilterPatternState {
     mode: ActiveFilterMode,
     case_sensitive: bool,
     enabled: bool,
+    #[serde(default)]
+    regex: bool,
+    #[serde(default)]
+    whole_word: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -51,6 +79,13 @@ pub struct MarkState {
     name: Option<String>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct RegisterState {
+    name: char,
+    line_index: usize,
+    horizontal_offset: usize,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct EventFilterState {
     name: String,
@@ -60,13 +95,28 @@ pub struct EventFilterState {
 #[derive(Serialize, Deserialize)]
 pub struct CustomEventState {
     pattern: String,
+    /// Name given when adding the event, if it differs from the default name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// Whether the pattern is a regex rather than a plain pattern.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    regex: bool,
+    #[serde(default = "default_true")]
+    case_sensitive: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl PersistedState {
     pub fn from_app(file_paths: &[&str], app: &App) -> Self {
         Self {
-            version: 1,
+            version: STATE_VERSION,
             log_file_paths: file_paths.iter().map(|s| s.to_string()).collect(),
+            file_ids: file_ids(file_paths),
             viewport: ViewportState {
                 selected_line: app.viewport.selected_line,
                 top_line: app.viewport.top_line,
@@ -74,6 +124,7 @@ impl PersistedState {
                 center_cursor_mode: app.viewport.center_cursor_mode,
             },
             search_history: app.search.history.get_history().to_vec(),
+            search_whole_word: app.search.is_whole_word(),
             filter_history: app.filter.history.get_history().to_vec(),
             filters: app
                 .filter
@@ -84,6 +135,9 @@ impl PersistedState {
                     mode: fp.mode,
                     case_sensitive: fp.case_sensitive,
                     enabled: fp.enabled,
+                    regex: fp.is_regex(),
+                    whole_word: fp.whole_word,
+                    file: fp.file.clone(),
                 })
                 .collect(),
             marks: app
@@ -95,6 +149,15 @@ impl PersistedState {
                     name: m.name.clone(),
                 })
                 .collect(),
+            registers: app
+                .registers
+                .iter()
+                .map(|(name, position)| RegisterState {
+                    name,
+                    line_index: position.line_index,
+                    horizontal_offset: position.horizontal_offset,
+                })
+                .collect(),
             event_filters: app
                 .event_tracker
                 .get_event_stats()
@@ -106,10 +169,15 @@ impl PersistedState {
                 .collect(),
             custom_events: app
                 .event_tracker
-                .get_custom_event_patterns()
+                .get_custom_events()
                 .iter()
-                .map(|pattern| CustomEventState {
-                    pattern: pattern.to_string(),
+                .map(|event| CustomEventState {
+                    pattern: event.pattern.to_string(),
+                    name: (event.name != LogEventTracker::custom_event_name(event.pattern))
+                        .then(|| event.name.to_string()),
+                    regex: event.regex,
+                    case_sensitive: event.case_sensitive,
+                    color: event.color.map(|color| color.to_string()),
                 })
                 .collect(),
             options: app
@@ -120,61 +188,165 @@ impl PersistedState {
                     enabled: opt.enabled,
                 })
                 .collect(),
+            notes: app.notes.text(),
+            autosaved: false,
         }
     }
 }
 
 /// Saves the current application state to disk.
+///
+/// The state is saved both under the content identity of the files, so it follows renamed and
+/// copied files, and under their paths, so it is found again after a file has grown.
 pub fn save_state(file_paths: &[&str], app: &App) {
     if !ensure_state_dir() {
         return;
     }
 
-    let state_file_path = match get_state_file_path(file_paths) {
-        Some(path) => path,
-        None => return,
-    };
-
     let state = PersistedState::from_app(file_paths, app);
-    let json = match serde_json::to_string_pretty(&state) {
-        Ok(j) => j,
-        Err(_) => return,
-    };
+    if let Ok(json) = serde_json::to_string_pretty(&state) {
+        write_state(file_paths, &state, &json);
+    }
+}
 
-    let _ = fs::write(state_file_path, json);
+/// Saves the current application state while lazylog runs, so it can be recovered after a crash.
+///
+/// Nothing is written if the state is the same as `previous`, the state last autosaved. Returns the saved state.
+pub fn autosave_state(file_paths: &[&str], app: &App, previous: Option<&str>) -> Option<String> {
+    if !ensure_state_dir() {
+        return None;
+    }
+
+    let mut state = PersistedState::from_app(file_paths, app);
+    state.autosaved = true;
+    let json = serde_json::to_string_pretty(&state).ok()?;
+    if previous == Some(json.as_str()) {
+        return None;
+    }
+    write_state(file_paths, &state, &json);
+    Some(json)
+}
+
+/// Writes the serialized state under the content identity and the paths of the files.
+///
+/// Each file is written to a temporary file first and then renamed, so a crash while writing never leaves a
+/// truncated state file behind.
+fn write_state(file_paths: &[&str], state: &PersistedState, json: &str) {
+    for state_file_path in [
+        get_content_state_file_path(&state.file_ids),
+        get_state_file_path(file_paths),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let temp_path = state_file_path.with_extension("json.tmp");
+        if fs::write(&temp_path, json).is_err() || fs::rename(&temp_path, &state_file_path).is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+    }
 }
 
 /// Loads the application state from disk if it exists.
+///
+/// State saved for files with the same content is preferred, falling back to state saved for the same paths.
 pub fn load_state(file_paths: &[&str]) -> Option<PersistedState> {
-    let state_path = get_state_file_path(file_paths)?;
+    let ids = file_ids(file_paths);
+    if let Some(state) = get_content_state_file_path(&ids)
+        .and_then(|path| read_state_file(&path))
+        .filter(|state| ids_match(&state.file_ids, &ids))
+    {
+        return Some(state);
+    }
+
+    get_state_file_path(file_paths)
+        .and_then(|path| read_state_file(&path))
+        .filter(|state| paths_match(&state.log_file_paths, file_paths))
+}
 
+/// Reads and migrates a state file, returning `None` if it does not exist or can't be used.
+fn read_state_file(state_path: &Path) -> Option<PersistedState> {
     if !state_path.exists() {
         return None;
     }
 
-    match fs::read_to_string(&state_path) {
-        Ok(json) => match serde_json::from_str::<PersistedState>(&json) {
-            Ok(state) => {
-                if paths_match(&state.log_file_paths, file_paths) {
-                    Some(state)
-                } else {
-                    None
-                }
-            }
+    match migrate_file(state_path) {
+        Ok(Some(json)) => match serde_json::from_value::<PersistedState>(json) {
+            Ok(state) => Some(state),
             Err(e) => {
                 info!("Failed to deserialize state file {:?}: {}", state_path, e);
                 // Corrupted state file, ignore it
                 None
             }
         },
+        Ok(None) => {
+            info!(
+                "State file {:?} was written by a newer version, ignoring it",
+                state_path
+            );
+            None
+        }
         Err(e) => {
-            info!("Failed to read state file {:?}: {}", state_path, e);
+            info!("Failed to load state file {:?}: {}", state_path, e);
             // Can't read file, ignore it
             None
         }
     }
 }
 
+/// Returns the ~/.lazylog directory where state files are stored.
+pub fn state_dir() -> Option<PathBuf> {
+    Some(dirs::home_dir()?.join(".lazylog"))
+}
+
+/// Returns the sorted content identities of the given files.
+///
+/// A file is identified by a hash of its first [`CONTENT_ID_BYTES`] bytes and its size. Files that
+/// are empty or can't be read are identified by their absolute path instead.
+fn file_ids(file_paths: &[&str]) -> Vec<String> {
+    let mut ids: Vec<String> = file_paths
+        .iter()
+        .map(|path| {
+            file_content_id(path).unwrap_or_else(|| {
+                let absolute_path = std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+                format!("path:{}", absolute_path.to_string_lossy())
+            })
+        })
+        .collect();
+    ids.sort();
+    ids
+}
+
+/// Returns the content identity of a file, or `None` if it is empty or can't be read.
+fn file_content_id(path: &str) -> Option<String> {
+    let file = fs::File::open(path).ok()?;
+    let size = file.metadata().ok()?.len();
+    if size == 0 {
+        return None;
+    }
+    content_id(file, size)
+}
+
+/// Hashes the first [`CONTENT_ID_BYTES`] bytes of `reader` and combines them with the total size.
+fn content_id(reader: impl Read, size: u64) -> Option<String> {
+    let mut head = Vec::new();
+    reader.take(CONTENT_ID_BYTES).read_to_end(&mut head).ok()?;
+    Some(format!("{:016x}-{}", fnv1a(&head), size))
+}
+
+/// 64-bit FNV-1a hash, which unlike `DefaultHasher` is the same across Rust versions and machines.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Checks if the stored content identities match the given sorted identities.
+fn ids_match(stored: &[String], ids: &[String]) -> bool {
+    let mut stored = stored.to_vec();
+    stored.sort();
+    stored == ids
+}
+
 /// Checks if two file path lists contain the same files, regardless of order.
 fn paths_match(paths1: &[String], paths2: &[&str]) -> bool {
     if paths1.len() != paths2.len() {
@@ -217,6 +389,12 @@ fn get_state_file_path(file_paths: &[&str]) -> Option<PathBuf> {
     Some(state_dir.join(format!("{:x}.json", hash)))
 }
 
+/// Calculates the state file path based on the content identities of the log files.
+fn get_content_state_file_path(ids: &[String]) -> Option<PathBuf> {
+    let hash = fnv1a(ids.join("\n").as_bytes());
+    Some(state_dir()?.join(format!("c{:016x}.json", hash)))
+}
+
 /// Ensures the ~/.lazylog directory exists.
 fn ensure_state_dir() -> bool {
     let home = match dirs::home_dir() {
@@ -246,7 +424,8 @@ pub fn clear_all_state() -> Result<String, String> {
     for entry in fs::read_dir(&state_dir).map_err(|e| format!("Failed to read state directory: {}", e))? {
         let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
         let path = entry.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
+        // Includes backups made before migrating state files and files left over from interrupted writes
+        if path.is_file() && matches!(path.extension().and_then(|s| s.to_str()), Some("json" | "bak" | "tmp")) {
             fs::remove_file(&path).map_err(|e| format!("Failed to remove file {:?}: {}", path, e))?;
             count += 1;
         }
@@ -280,6 +459,10 @@ impl PersistedState {
         &self.search_history
     }
 
+    pub fn search_whole_word(&self) -> bool {
+        self.search_whole_word
+    }
+
     pub fn filter_history(&self) -> &[FilterHistoryEntry] {
         &self.filter_history
     }
@@ -292,6 +475,10 @@ impl PersistedState {
         &self.marks
     }
 
+    pub fn registers(&self) -> &[RegisterState] {
+        &self.registers
+    }
+
     pub fn event_filters(&self) -> &[EventFilterState] {
         &self.event_filters
     }
@@ -300,6 +487,16 @@ impl PersistedState {
         &self.custom_events
     }
 
+    pub fn notes(&self) -> &str {
+        &self.notes
+    }
+
+    /// Returns true if the state was autosaved and lazylog exited without saving it again, e.g. because it
+    /// crashed.
+    pub fn autosaved(&self) -> bool {
+        self.autosaved
+    }
+
     pub fn options(&self) -> Vec<(AppOption, bool)> {
         self.options
             .iter()
@@ -324,6 +521,18 @@ impl FilterPatternState {
     pub fn enabled(&self) -> bool {
         self.enabled
     }
+
+    pub fn regex(&self) -> bool {
+        self.regex
+    }
+
+    pub fn whole_word(&self) -> bool {
+        self.whole_word
+    }
+
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
 }
 
 impl MarkState {
@@ -336,6 +545,19 @@ impl MarkState {
     }
 }
 
+impl RegisterState {
+    pub fn name(&self) -> char {
+        self.name
+    }
+
+    pub fn position(&self) -> RegisterPosition {
+        RegisterPosition {
+            line_index: self.line_index,
+            horizontal_offset: self.horizontal_offset,
+        }
+    }
+}
+
 impl EventFilterState {
     pub fn name(&self) -> &str {
         &self.name
@@ -350,4 +572,79 @@ impl CustomEventState {
     pub fn pattern(&self) -> &str {
         &self.pattern
     }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn is_regex(&self) -> bool {
+        self.regex
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    pub fn color(&self) -> Option<Color> {
+        self.color.as_deref().and_then(Config::parse_color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_id() {
+        let content = "2024-01-01 INFO started\n".repeat(10_000);
+        let id = content_id(content.as_bytes(), content.len() as u64).unwrap();
+
+        // Only the head and the size identify the file
+        let mut changed_tail = content.clone();
+        changed_tail.replace_range(content.len() - 5.., "ended");
+        assert_eq!(
+            content_id(changed_tail.as_bytes(), changed_tail.len() as u64).unwrap(),
+            id
+        );
+        assert_ne!(content_id(content.as_bytes(), content.len() as u64 + 1).unwrap(), id);
+        assert_ne!(content_id(&b"2024-01-02"[..], content.len() as u64).unwrap(), id);
+    }
+
+    #[test]
+    fn test_file_ids_fall_back_to_path() {
+        let ids = file_ids(&["/nonexistent/b.log", "/nonexistent/a.log"]);
+        assert_eq!(ids, ["path:/nonexistent/a.log", "path:/nonexistent/b.log"]);
+        assert!(ids_match(
+            &["path:/nonexistent/b.log".into(), "path:/nonexistent/a.log".into()],
+            &ids
+        ));
+        assert!(!ids_match(&["path:/nonexistent/a.log".into()], &ids));
+    }
+
+    #[tokio::test]
+    async fn test_autosave_round_trip() {
+        let path = std::env::temp_dir().join(format!("lazylog-autosave-{}.log", std::process::id()));
+        std::fs::write(&path, format!("autosave test {}\n", std::process::id())).unwrap();
+        let path = path.to_str().unwrap();
+        let app = App::with_lines(&["one"], "", 40, 8);
+
+        let saved = autosave_state(&[path], &app, None);
+        assert!(saved.is_some());
+        // An unchanged state isn't written again
+        assert_eq!(autosave_state(&[path], &app, saved.as_deref()), None);
+        let loaded = load_state(&[path]);
+
+        for state_path in [
+            get_content_state_file_path(&file_ids(&[path])),
+            get_state_file_path(&[path]),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let _ = fs::remove_file(state_path);
+        }
+        std::fs::remove_file(path).unwrap();
+
+        assert!(loaded.is_some_and(|state| state.autosaved()));
+    }
 }
@@ -0,0 +1,68 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::shell::pipe_through_command;
+use crate::task::TaskProgress;
+
+/// Time a command lines are piped through may run before it is killed.
+pub const PIPE_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What the output of a piped command is used for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipeTarget {
+    /// Shown in the pipe output view.
+    OutputView,
+    /// Applied as the annotations of the script with the given name.
+    Script(String),
+}
+
+/// Shell command running on a background thread with log lines piped to it.
+///
+/// The task is cancelled when dropped, which kills the command, so the output of a command replaced in the meantime
+/// is never delivered.
+#[derive(Debug)]
+pub struct PipeTask {
+    command: String,
+    target: PipeTarget,
+    progress: TaskProgress,
+}
+
+impl PipeTask {
+    /// Starts piping the input through the command. `on_complete` is called from the background thread with the
+    /// output of the command, unless the task is cancelled first.
+    pub fn spawn<F>(command: String, input: String, target: PipeTarget, on_complete: F) -> Self
+    where
+        F: FnOnce(Result<String, String>) + Send + 'static,
+    {
+        // The progress of a command is unknown, so only its spinner is shown
+        let progress = TaskProgress::spawn(0, {
+            let command = command.clone();
+            move |cancelled, _| {
+                let output = pipe_through_command(&command, &input, PIPE_COMMAND_TIMEOUT, cancelled);
+                if !cancelled.load(Ordering::Relaxed) {
+                    on_complete(output);
+                }
+            }
+        });
+
+        Self {
+            command,
+            target,
+            progress,
+        }
+    }
+
+    /// Returns the command the lines are piped through.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Returns what the output of the command is used for.
+    pub fn target(&self) -> &PipeTarget {
+        &self.target
+    }
+
+    pub fn progress(&self) -> &TaskProgress {
+        &self.progress
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::filter::ActiveFilterMode;
+use crate::log::LogLine;
+use crate::matcher::PatternMatcher;
+use crate::resolver::VisibilityRule;
+use crate::search::SearchScope;
+use crate::task::TaskProgress;
+use rayon::prelude::*;
+
+/// Buffers with at least this many lines are counted in the background while typing.
+pub const BACKGROUND_PREVIEW_MIN_LINES: usize = 100_000;
+
+/// Number of lines counted between progress updates and cancellation checks.
+const CHUNK_SIZE: usize = 65_536;
+
+/// Visibility rules that can be evaluated on a background thread.
+pub type PreviewRules = Vec<Box<dyn VisibilityRule + Send + Sync>>;
+
+/// Filter or search being typed that a preview count is computed for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewInput {
+    Search {
+        pattern: String,
+        case_sensitive: bool,
+        fuzzy: bool,
+        whole_word: bool,
+        scope: SearchScope,
+    },
+    Filter {
+        pattern: String,
+        mode: ActiveFilterMode,
+        case_sensitive: bool,
+        whole_word: bool,
+    },
+}
+
+/// Number of lines counted by a [`PreviewTask`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewCount {
+    /// Generation of the task that counted the lines.
+    pub generation: usize,
+    pub count: usize,
+}
+
+/// Rule that only passes lines matching a search pattern.
+pub struct SearchMatchRule {
+    matcher: PatternMatcher,
+}
+
+impl SearchMatchRule {
+    pub fn new(matcher: PatternMatcher) -> Self {
+        Self { matcher }
+    }
+}
+
+impl VisibilityRule for SearchMatchRule {
+    fn is_visible(&self, line: &LogLine) -> bool {
+        self.matcher.matches(line.content())
+    }
+}
+
+/// Count of the lines passing a set of rules, running on a background thread.
+///
+/// Used to preview how many lines a filter or search being typed would give. The task is cancelled
+/// when dropped, so replacing it on the next key press cancels the old count.
+#[derive(Debug)]
+pub struct PreviewTask {
+    generation: usize,
+    progress: TaskProgress,
+}
+
+impl PreviewTask {
+    /// Starts counting the lines passing all rules. `on_complete` is called from the background thread
+    /// with the count, unless the task is cancelled first.
+    pub fn spawn<F>(generation: usize, lines: Arc<Vec<LogLine>>, rules: PreviewRules, on_complete: F) -> Self
+    where
+        F: FnOnce(PreviewCount) + Send + 'static,
+    {
+        let progress = TaskProgress::spawn(lines.len(), move |cancelled, progress| {
+            if let Some(count) = count_lines(&lines, &rules, cancelled, progress) {
+                on_complete(PreviewCount { generation, count });
+            }
+        });
+
+        Self { generation, progress }
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn progress(&self) -> &TaskProgress {
+        &self.progress
+    }
+}
+
+/// Counts the lines passing all rules in chunks. Returns `None` if cancelled.
+pub fn count_lines(
+    lines: &[LogLine],
+    rules: &PreviewRules,
+    cancelled: &AtomicBool,
+    progress: &AtomicUsize,
+) -> Option<usize> {
+    let mut count = 0;
+    for chunk in lines.chunks(CHUNK_SIZE) {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        count += chunk
+            .par_iter()
+            .filter(|line| rules.iter().all(|rule| rule.is_visible(line)))
+            .count();
+        progress.fetch_add(chunk.len(), Ordering::Relaxed);
+    }
+    Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::{FilterPattern, FilterRule};
+    use crate::matcher::PlainMatch;
+    use crate::utils::CaseFolding;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_count_lines() {
+        let lines: Vec<LogLine> = (0..CHUNK_SIZE + 10)
+            .map(|i| LogLine::new(if i % 100 == 0 { "ERROR db timeout" } else { "INFO ok" }, i))
+            .collect();
+        let exclude_db = FilterPattern::new("db".to_string(), ActiveFilterMode::Exclude, true, true);
+        let rules: PreviewRules = vec![Box::new(FilterRule::new(
+            Arc::new(vec![exclude_db]),
+            Arc::new(HashSet::from([0])),
+        ))];
+        let progress = AtomicUsize::new(0);
+
+        let count = count_lines(&lines, &rules, &AtomicBool::new(false), &progress);
+        assert_eq!(count, Some(lines.len() - 656 + 1));
+        assert_eq!(progress.load(Ordering::Relaxed), lines.len());
+
+        let search: PreviewRules = vec![Box::new(SearchMatchRule::new(PatternMatcher::Plain(PlainMatch {
+            pattern: "timeout".to_string(),
+            case_sensitive: false,
+            whole_word: false,
+            case_folding: CaseFolding::default(),
+        })))];
+        assert_eq!(
+            count_lines(&lines, &search, &AtomicBool::new(false), &progress),
+            Some(656)
+        );
+        assert_eq!(count_lines(&lines, &search, &AtomicBool::new(true), &progress), None);
+    }
+}
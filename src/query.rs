@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+
+use crate::fields::{FieldExtractor, align_columns};
+use crate::log::LogLine;
+use crate::utils::{CaseFolding, contains_ignore_case, eq_ignore_case};
+
+/// Field holding the whole line content.
+const LINE_FIELD: &str = "line";
+/// Field holding the detected log level.
+const LEVEL_FIELD: &str = "level";
+
+/// Comparison of a field with a value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    NotEq,
+    Contains,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Op {
+    fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "=" | "==" => Some(Op::Eq),
+            "!=" => Some(Op::NotEq),
+            "~" => Some(Op::Contains),
+            ">" => Some(Op::Gt),
+            ">=" => Some(Op::Ge),
+            "<" => Some(Op::Lt),
+            "<=" => Some(Op::Le),
+            _ => None,
+        }
+    }
+
+    /// Compares ignoring case, or numerically for the ordering operators.
+    fn matches(&self, field_value: &str, value: &str, folding: CaseFolding) -> bool {
+        match self {
+            Op::Eq => eq_ignore_case(field_value, value, folding),
+            Op::NotEq => !eq_ignore_case(field_value, value, folding),
+            Op::Contains => contains_ignore_case(field_value, value, folding),
+            Op::Gt | Op::Ge | Op::Lt | Op::Le => {
+                let (Ok(field_number), Ok(number)) = (field_value.parse::<f64>(), value.parse::<f64>()) else {
+                    return false;
+                };
+                match self {
+                    Op::Gt => field_number > number,
+                    Op::Ge => field_number >= number,
+                    Op::Lt => field_number < number,
+                    _ => field_number <= number,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    field: String,
+    op: Op,
+    value: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Op(Op),
+    Pipe,
+}
+
+/// Splits a query into words, operators and pipes. Quoted words may contain spaces and operator characters.
+fn tokenize(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '|' {
+            chars.next();
+            tokens.push(Token::Pipe);
+        } else if "=!<>~".contains(c) {
+            let mut symbol = String::new();
+            while let Some(&c) = chars.peek().filter(|c| "=!<>~".contains(**c)) {
+                symbol.push(c);
+                chars.next();
+            }
+            let op = Op::from_symbol(&symbol).ok_or(format!("Unknown operator '{}'", symbol))?;
+            tokens.push(Token::Op(op));
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let word: String = chars.by_ref().take_while(|&next| next != c).collect();
+            tokens.push(Token::Word(word));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars
+                .peek()
+                .filter(|c| !c.is_whitespace() && !"|=!<>~\"'".contains(**c))
+            {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A query selecting lines by their fields, optionally counting them per value of a field.
+///
+/// Syntax: `level=ERROR AND module=auth | count by module`. `AND` binds tighter than `OR`.
+/// Besides the extracted fields, `line` is the whole line and `level` the detected log level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    /// Lines match if all conditions of any group match.
+    any_of: Vec<Vec<Condition>>,
+    aggregation: Option<Aggregation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Aggregation {
+    Count,
+    CountBy(String),
+}
+
+impl Query {
+    /// Parses a query, returning a description of the problem if it is invalid.
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let tokens = tokenize(query)?;
+        let (condition_tokens, aggregation_tokens) = match tokens.iter().position(|t| *t == Token::Pipe) {
+            Some(pipe) => (&tokens[..pipe], Some(&tokens[pipe + 1..])),
+            None => (&tokens[..], None),
+        };
+
+        let mut any_of = Vec::new();
+        let mut all_of = Vec::new();
+        let mut rest = condition_tokens;
+        while !rest.is_empty() {
+            match rest {
+                [Token::Word(field), Token::Op(op), Token::Word(value), tail @ ..] => {
+                    all_of.push(Condition {
+                        field: field.clone(),
+                        op: *op,
+                        value: value.clone(),
+                    });
+                    rest = tail;
+                }
+                _ => return Err("Expected a condition like field=value".to_string()),
+            }
+            match rest {
+                [] => {}
+                [Token::Word(word), tail @ ..] if word.eq_ignore_ascii_case("and") && !tail.is_empty() => rest = tail,
+                [Token::Word(word), tail @ ..] if word.eq_ignore_ascii_case("or") && !tail.is_empty() => {
+                    any_of.push(std::mem::take(&mut all_of));
+                    rest = tail;
+                }
+                _ => return Err("Expected AND or OR between conditions".to_string()),
+            }
+        }
+        if !all_of.is_empty() {
+            any_of.push(all_of);
+        }
+
+        let aggregation = match aggregation_tokens {
+            None => None,
+            Some([Token::Word(count)]) if count.eq_ignore_ascii_case("count") => Some(Aggregation::Count),
+            Some([Token::Word(count), Token::Word(by), Token::Word(field)])
+                if count.eq_ignore_ascii_case("count") && by.eq_ignore_ascii_case("by") =>
+            {
+                Some(Aggregation::CountBy(field.clone()))
+            }
+            Some(_) => return Err("Expected 'count' or 'count by <field>' after |".to_string()),
+        };
+
+        Ok(Self { any_of, aggregation })
+    }
+
+    /// Runs the query over the given lines, comparing values with the given case folding.
+    pub fn run<'a>(
+        &self,
+        lines: impl Iterator<Item = &'a LogLine>,
+        extractor: Option<&FieldExtractor>,
+        folding: CaseFolding,
+    ) -> QueryResult {
+        let columns = extractor.map(FieldExtractor::columns).unwrap_or_default();
+        let mut matching: Vec<(&LogLine, Option<Vec<String>>)> = Vec::new();
+
+        for line in lines {
+            let values = extractor.and_then(|extractor| extractor.extract(line.content()));
+            let field = |name: &str| field_value(line, columns, values.as_deref(), name);
+            let is_match = self.any_of.is_empty()
+                || self.any_of.iter().any(|all_of| {
+                    all_of.iter().all(|condition| {
+                        field(&condition.field).is_some_and(|v| condition.op.matches(&v, &condition.value, folding))
+                    })
+                });
+            if is_match {
+                matching.push((line, values));
+            }
+        }
+
+        match &self.aggregation {
+            None if columns.is_empty() => QueryResult {
+                headers: vec!["#".to_string(), LINE_FIELD.to_string()],
+                rows: matching
+                    .into_iter()
+                    .map(|(line, _)| QueryRow {
+                        cells: vec![(line.index + 1).to_string(), line.content().to_string()],
+                        line_indices: vec![line.index],
+                    })
+                    .collect(),
+            },
+            None => QueryResult {
+                headers: std::iter::once("#".to_string())
+                    .chain(columns.iter().cloned())
+                    .collect(),
+                rows: matching
+                    .into_iter()
+                    .map(|(line, values)| QueryRow {
+                        cells: std::iter::once((line.index + 1).to_string())
+                            .chain(values.unwrap_or_else(|| vec![String::new(); columns.len()]))
+                            .collect(),
+                        line_indices: vec![line.index],
+                    })
+                    .collect(),
+            },
+            Some(Aggregation::Count) => QueryResult {
+                headers: vec!["count".to_string()],
+                rows: vec![QueryRow {
+                    cells: vec![matching.len().to_string()],
+                    line_indices: matching.iter().map(|(line, _)| line.index).collect(),
+                }],
+            },
+            Some(Aggregation::CountBy(group_field)) => {
+                let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+                for (line, values) in &matching {
+                    let value = field_value(line, columns, values.as_deref(), group_field).unwrap_or_default();
+                    groups.entry(value).or_default().push(line.index);
+                }
+                let mut rows: Vec<QueryRow> = groups
+                    .into_iter()
+                    .map(|(value, line_indices)| QueryRow {
+                        cells: vec![value, line_indices.len().to_string()],
+                        line_indices,
+                    })
+                    .collect();
+                rows.sort_by(|a, b| {
+                    b.line_indices
+                        .len()
+                        .cmp(&a.line_indices.len())
+                        .then_with(|| a.cells[0].cmp(&b.cells[0]))
+                });
+                QueryResult {
+                    headers: vec![group_field.clone(), "count".to_string()],
+                    rows,
+                }
+            }
+        }
+    }
+}
+
+/// Returns the value of a field of the line: an extracted field, or one of the built-in fields.
+fn field_value(line: &LogLine, columns: &[String], values: Option<&[String]>, name: &str) -> Option<String> {
+    if let Some(values) = values
+        && let Some(position) = columns.iter().position(|column| column == name)
+    {
+        return Some(values[position].clone());
+    }
+    match name {
+        LINE_FIELD => Some(line.content().to_string()),
+        LEVEL_FIELD => line.level.map(|level| level.name().to_string()),
+        _ => None,
+    }
+}
+
+/// A row of a query result with the lines it was computed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryRow {
+    pub cells: Vec<String>,
+    /// Indices of the source lines, in log order.
+    pub line_indices: Vec<usize>,
+}
+
+/// Table of query results.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryResult {
+    pub headers: Vec<String>,
+    pub rows: Vec<QueryRow>,
+}
+
+impl QueryResult {
+    /// Formats the headers followed by the rows as aligned lines.
+    pub fn table(&self) -> Vec<String> {
+        let rows: Vec<Option<Vec<String>>> = std::iter::once(Some(self.headers.clone()))
+            .chain(self.rows.iter().map(|row| Some(row.cells.clone())))
+            .collect();
+        align_columns(&rows).into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::level::LevelDetector;
+
+    fn lines(contents: &[&str]) -> Vec<LogLine> {
+        let detector = LevelDetector::default();
+        contents
+            .iter()
+            .enumerate()
+            .map(|(index, content)| {
+                let mut line = LogLine::new(content, index);
+                line.level = detector.detect(content);
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Query::parse("level=ERROR AND module=auth | count by module").is_ok());
+        assert!(Query::parse("| count").is_ok());
+        assert_eq!(
+            Query::parse("level ERROR"),
+            Err("Expected a condition like field=value".to_string())
+        );
+        assert_eq!(
+            Query::parse("a=1 b=2"),
+            Err("Expected AND or OR between conditions".to_string())
+        );
+        assert_eq!(
+            Query::parse("a=1 AND"),
+            Err("Expected AND or OR between conditions".to_string())
+        );
+        assert_eq!(Query::parse("a=>1"), Err("Unknown operator '=>'".to_string()));
+        assert!(Query::parse("a=1 | sum").is_err());
+    }
+
+    #[test]
+    fn test_count_by_field() {
+        let lines = lines(&[
+            "level=error module=auth msg=\"bad password\"",
+            "level=info module=auth msg=ok",
+            "level=error module=db msg=timeout",
+            "level=error module=auth msg=locked",
+            "  continuation",
+        ]);
+        let columns = ["level", "module", "msg"].map(String::from);
+        let extractor = FieldExtractor::logfmt(&columns);
+
+        let query = Query::parse("level=ERROR | count by module").unwrap();
+        let result = query.run(lines.iter(), Some(&extractor), CaseFolding::default());
+        assert_eq!(result.headers, ["module", "count"]);
+        assert_eq!(result.rows[0].cells, ["auth", "2"]);
+        assert_eq!(result.rows[0].line_indices, [0, 3]);
+        assert_eq!(result.rows[1].cells, ["db", "1"]);
+        assert_eq!(result.table(), ["module │ count", "auth   │ 2", "db     │ 1"]);
+
+        let query = Query::parse("module=db OR msg~PASS").unwrap();
+        let result = query.run(lines.iter(), Some(&extractor), CaseFolding::default());
+        assert_eq!(result.headers, ["#", "level", "module", "msg"]);
+        assert_eq!(
+            result.rows.iter().map(|row| row.cells[0].as_str()).collect::<Vec<_>>(),
+            ["1", "3"]
+        );
+    }
+
+    #[test]
+    fn test_builtin_fields_without_extractor() {
+        let lines = lines(&["12:00 ERROR disk full", "12:01 INFO ok", "12:02 ERROR disk full again"]);
+
+        let query = Query::parse("level=error AND line~\"disk full\" | count").unwrap();
+        let result = query.run(lines.iter(), None, CaseFolding::default());
+        assert_eq!(result.rows[0].cells, ["2"]);
+
+        let result = Query::parse("level!=error")
+            .unwrap()
+            .run(lines.iter(), None, CaseFolding::default());
+        assert_eq!(result.headers, ["#", "line"]);
+        assert_eq!(result.rows[0].cells, ["2", "12:01 INFO ok"]);
+    }
+
+    #[test]
+    fn test_numeric_comparison() {
+        let lines = lines(&["dur=5", "dur=50", "dur=abc"]);
+        let extractor = FieldExtractor::logfmt(&["dur".to_string()]);
+        let result = Query::parse("dur>=10")
+            .unwrap()
+            .run(lines.iter(), Some(&extractor), CaseFolding::default());
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0].line_indices, [1]);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::fields::{FieldExtractor, field_value};
+use crate::filter::{ActiveFilterMode, FIELD_FILTER_PREFIX, FilterPattern};
+use crate::level::LevelDetector;
+use crate::log::LogLine;
+use crate::template::{line_template, template_regex};
+
+/// Fields naming the source of a line, like the module or logger it was written by, in the order they are looked
+/// for.
+const SOURCE_FIELDS: [&str; 8] = ["module", "tag", "logger", "component", "target", "unit", "app", "name"];
+
+/// Part of a line a quick filter is derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickFilterKind {
+    Level,
+    Field,
+    Template,
+    File,
+}
+
+impl QuickFilterKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            QuickFilterKind::Level => "Level",
+            QuickFilterKind::Field => "Field",
+            QuickFilterKind::Template => "Template",
+            QuickFilterKind::File => "File",
+        }
+    }
+
+    /// Key adding the filter in the quick filter view.
+    pub fn key(&self) -> char {
+        match self {
+            QuickFilterKind::Level => 'l',
+            QuickFilterKind::Field => 'm',
+            QuickFilterKind::Template => 't',
+            QuickFilterKind::File => 'f',
+        }
+    }
+}
+
+/// A filter derived from a part of the selected line, like its level or the module it was written by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickFilter {
+    pub kind: QuickFilterKind,
+    /// The part of the line as shown in the quick filter view, e.g. `module:auth`.
+    pub value: String,
+    /// Regex or plain pattern of the filter. Unused for file filters, which match all lines of a file.
+    pattern: String,
+}
+
+impl QuickFilter {
+    /// Returns the filters showing only the lines sharing this part of the line, or hiding them. `file_names` are
+    /// the names of the opened files: showing only the lines of a file hides the lines of each other file.
+    pub fn patterns(&self, mode: ActiveFilterMode, file_names: &[String]) -> Vec<FilterPattern> {
+        let all_lines = || FilterPattern::new_regex("^".to_string(), ActiveFilterMode::Exclude, true, true);
+        match self.kind {
+            QuickFilterKind::Level | QuickFilterKind::Template => {
+                FilterPattern::new_regex(self.pattern.clone(), mode, true, true)
+                    .into_iter()
+                    .collect()
+            }
+            QuickFilterKind::Field => vec![FilterPattern::new(self.pattern.clone(), mode, false, true)],
+            QuickFilterKind::File => match mode {
+                ActiveFilterMode::Exclude => all_lines()
+                    .map(|filter| filter.with_file(Some(self.value.clone())))
+                    .into_iter()
+                    .collect(),
+                ActiveFilterMode::Include => file_names
+                    .iter()
+                    .filter(|&file| *file != self.value)
+                    .filter_map(|file| all_lines().map(|filter| filter.with_file(Some(file.clone()))))
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Returns the filters that can be derived from the line: its detected level, the field naming its source, its
+/// template and, with several files open, the file it comes from.
+pub fn quick_filters(
+    line: &LogLine,
+    level_detector: &LevelDetector,
+    extractor: Option<&FieldExtractor>,
+    file: Option<&str>,
+) -> Vec<QuickFilter> {
+    let mut filters = Vec::new();
+
+    if let Some(level) = line.level {
+        filters.push(QuickFilter {
+            kind: QuickFilterKind::Level,
+            value: level.name().to_string(),
+            pattern: level_detector.pattern(level).to_string(),
+        });
+    }
+
+    let source = SOURCE_FIELDS.iter().find_map(|&name| {
+        field_value(line.content(), name, extractor)
+            .filter(|value| !value.is_empty())
+            .map(|value| format!("{}:{}", name, value))
+    });
+    if let Some(source) = source {
+        filters.push(QuickFilter {
+            kind: QuickFilterKind::Field,
+            pattern: format!("{}{}", FIELD_FILTER_PREFIX, source),
+            value: source,
+        });
+    }
+
+    let template = line_template(line.content());
+    filters.push(QuickFilter {
+        kind: QuickFilterKind::Template,
+        pattern: template_regex(&template),
+        value: template,
+    });
+
+    if let Some(file) = file {
+        filters.push(QuickFilter {
+            kind: QuickFilterKind::File,
+            value: file.to_string(),
+            pattern: String::new(),
+        });
+    }
+
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::apply_filters;
+    use crate::utils::CaseFolding;
+
+    #[test]
+    fn test_quick_filters_from_line() {
+        let detector = LevelDetector::default();
+        let mut line = LogLine::new("ERROR module=auth took 12ms", 0);
+        line.level = detector.detect(line.content());
+        let filters = quick_filters(&line, &detector, None, Some("api.log"));
+
+        let kinds: Vec<QuickFilterKind> = filters.iter().map(|filter| filter.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                QuickFilterKind::Level,
+                QuickFilterKind::Field,
+                QuickFilterKind::Template,
+                QuickFilterKind::File
+            ]
+        );
+        assert_eq!(filters[1].value, "module:auth");
+        assert_eq!(filters[1].pattern, "@module:auth");
+        assert_eq!(filters[2].value, "ERROR module=auth took #");
+
+        let level = filters[0].patterns(ActiveFilterMode::Include, &[]);
+        assert!(apply_filters(
+            "ERROR: disk full",
+            None,
+            &level,
+            None,
+            CaseFolding::default()
+        ));
+        assert!(!apply_filters(
+            "INFO started",
+            None,
+            &level,
+            None,
+            CaseFolding::default()
+        ));
+        let template = filters[2].patterns(ActiveFilterMode::Exclude, &[]);
+        assert!(!apply_filters(
+            "ERROR module=auth took 250ms",
+            None,
+            &template,
+            None,
+            CaseFolding::default()
+        ));
+
+        let file_names = vec!["api.log".to_string(), "db.log".to_string(), "web.log".to_string()];
+        let only_file = filters[3].patterns(ActiveFilterMode::Include, &file_names);
+        assert_eq!(only_file.len(), 2);
+        assert!(apply_filters(
+            "any",
+            Some("api.log"),
+            &only_file,
+            None,
+            CaseFolding::default()
+        ));
+        assert!(!apply_filters(
+            "any",
+            Some("db.log"),
+            &only_file,
+            None,
+            CaseFolding::default()
+        ));
+        let hide_file = filters[3].patterns(ActiveFilterMode::Exclude, &file_names);
+        assert!(!apply_filters(
+            "any",
+            Some("api.log"),
+            &hide_file,
+            None,
+            CaseFolding::default()
+        ));
+        assert!(apply_filters(
+            "any",
+            Some("web.log"),
+            &hide_file,
+            None,
+            CaseFolding::default()
+        ));
+
+        let plain = LogLine::new("started", 1);
+        assert_eq!(quick_filters(&plain, &detector, None, None).len(), 1);
+    }
+}
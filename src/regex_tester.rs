@@ -0,0 +1,199 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::log::LogLine;
+
+/// Maximum number of matching lines kept for display.
+const MAX_RESULTS: usize = 500;
+
+/// A single line matching the tested regex.
+#[derive(Debug, Clone)]
+pub struct RegexTestMatch {
+    /// Index of the matching log line.
+    pub log_index: usize,
+    /// Content of the matching log line.
+    pub content: String,
+    /// Byte ranges of all matches in the line.
+    pub match_ranges: Vec<(usize, usize)>,
+    /// Capture groups of the first match as (group name or number, captured value).
+    pub captures: Vec<(String, String)>,
+}
+
+/// Interactive regex sandbox for testing a pattern against log lines.
+#[derive(Debug, Default)]
+pub struct RegexTester {
+    /// Compiled regex, if the pattern is valid and not empty.
+    regex: Option<Regex>,
+    /// Error message if the pattern failed to compile.
+    error: Option<String>,
+    /// Whether the pattern matching is case-sensitive.
+    case_sensitive: bool,
+    /// Matching lines, capped at MAX_RESULTS.
+    matches: Vec<RegexTestMatch>,
+    /// Total number of matching lines.
+    match_count: usize,
+    /// Number of lines tested.
+    line_count: usize,
+}
+
+impl RegexTester {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clears the pattern and all results.
+    pub fn reset(&mut self) {
+        *self = Self {
+            case_sensitive: self.case_sensitive,
+            ..Self::default()
+        };
+    }
+
+    /// Returns whether the pattern matching is case-sensitive.
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Toggles case sensitivity.
+    pub fn toggle_case_sensitivity(&mut self) {
+        self.case_sensitive = !self.case_sensitive;
+    }
+
+    /// Compiles the pattern and tests it against the given lines.
+    pub fn update<'a>(&mut self, pattern: &str, lines: impl Iterator<Item = &'a LogLine>) {
+        self.matches.clear();
+        self.match_count = 0;
+        self.line_count = 0;
+        self.regex = None;
+        self.error = None;
+
+        if pattern.is_empty() {
+            return;
+        }
+
+        let regex = match RegexBuilder::new(pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+        {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        };
+
+        let group_names: Vec<String> = regex
+            .capture_names()
+            .enumerate()
+            .map(|(i, name)| name.map(|n| n.to_string()).unwrap_or_else(|| i.to_string()))
+            .collect();
+
+        for line in lines {
+            self.line_count += 1;
+
+            let content = line.content();
+            let Some(caps) = regex.captures(content) else {
+                continue;
+            };
+            self.match_count += 1;
+
+            if self.matches.len() >= MAX_RESULTS {
+                continue;
+            }
+
+            let captures = caps
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, group)| {
+                    let value = group.map(|m| m.as_str().to_string()).unwrap_or_default();
+                    (group_names[i].clone(), value)
+                })
+                .collect();
+
+            self.matches.push(RegexTestMatch {
+                log_index: line.index,
+                content: content.to_string(),
+                match_ranges: regex.find_iter(content).map(|m| (m.start(), m.end())).collect(),
+                captures,
+            });
+        }
+
+        self.regex = Some(regex);
+    }
+
+    /// Returns the compiled regex, if valid.
+    pub fn regex(&self) -> Option<&Regex> {
+        self.regex.as_ref()
+    }
+
+    /// Returns the compile error, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the matching lines (capped).
+    pub fn matches(&self) -> &[RegexTestMatch] {
+        &self.matches
+    }
+
+    /// Returns the total number of matching lines.
+    pub fn match_count(&self) -> usize {
+        self.match_count
+    }
+
+    /// Returns the number of lines tested.
+    pub fn line_count(&self) -> usize {
+        self.line_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captures() {
+        let lines = [
+            LogLine::new("user=alice action=login", 0),
+            LogLine::new("heartbeat", 1),
+            LogLine::new("user=bob action=logout", 2),
+        ];
+        let mut tester = RegexTester::new();
+        tester.update(r"user=(?<user>\w+) action=(\w+)", lines.iter());
+
+        assert_eq!(tester.line_count(), 3);
+        assert_eq!(tester.match_count(), 2);
+        let first = &tester.matches()[0];
+        assert_eq!(first.log_index, 0);
+        assert_eq!(
+            first.captures,
+            vec![
+                ("user".to_string(), "alice".to_string()),
+                ("2".to_string(), "login".to_string())
+            ]
+        );
+        assert_eq!(tester.matches()[1].log_index, 2);
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let lines = [LogLine::new("line", 0)];
+        let mut tester = RegexTester::new();
+        tester.update("(unclosed", lines.iter());
+        assert!(tester.error().is_some());
+        assert!(tester.regex().is_none());
+        assert_eq!(tester.match_count(), 0);
+    }
+
+    #[test]
+    fn test_case_sensitivity() {
+        let lines = [LogLine::new("ERROR occurred", 0)];
+        let mut tester = RegexTester::new();
+        tester.update("error", lines.iter());
+        assert_eq!(tester.match_count(), 1);
+
+        tester.toggle_case_sensitivity();
+        tester.update("error", lines.iter());
+        assert_eq!(tester.match_count(), 0);
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+/// Whether picking a register in the registers popup saves the position or jumps to it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RegisterAction {
+    Set,
+    #[default]
+    Jump,
+}
+
+/// A view position saved in a register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterPosition {
+    /// Log index of the selected line.
+    pub line_index: usize,
+    pub horizontal_offset: usize,
+}
+
+/// Positions saved under a letter, like vim's `ma` and `` `a ``.
+///
+/// Unlike marks, registers are not shown on the lines and only remember where the view was.
+#[derive(Debug, Default)]
+pub struct Registers {
+    positions: BTreeMap<char, RegisterPosition>,
+}
+
+impl Registers {
+    /// Returns whether the character names a register.
+    pub fn is_register_name(name: char) -> bool {
+        name.is_ascii_alphabetic()
+    }
+
+    pub fn set(&mut self, name: char, position: RegisterPosition) {
+        self.positions.insert(name, position);
+    }
+
+    pub fn get(&self, name: char) -> Option<RegisterPosition> {
+        self.positions.get(&name).copied()
+    }
+
+    pub fn remove(&mut self, name: char) {
+        self.positions.remove(&name);
+    }
+
+    /// Returns the name of the register at the index when ordered by name.
+    pub fn name_at(&self, index: usize) -> Option<char> {
+        self.positions.keys().nth(index).copied()
+    }
+
+    /// Returns the registers ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (char, RegisterPosition)> + '_ {
+        self.positions.iter().map(|(&name, &position)| (name, position))
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Removes the registers on the first `count` lines and shifts the rest after those lines were dropped.
+    pub fn drop_lines_before(&mut self, count: usize) {
+        self.remap_lines(|line_index| line_index.checked_sub(count));
+    }
+
+    /// Moves the registers to new line indices, e.g. after the file was reloaded. Registers on lines
+    /// without a new index are removed.
+    pub fn remap_lines(&mut self, new_index: impl Fn(usize) -> Option<usize>) {
+        self.positions
+            .retain(|_, position| match new_index(position.line_index) {
+                Some(line_index) => {
+                    position.line_index = line_index;
+                    true
+                }
+                None => false,
+            });
+    }
+
+    pub fn clear(&mut self) {
+        self.positions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(line_index: usize) -> RegisterPosition {
+        RegisterPosition {
+            line_index,
+            horizontal_offset: 0,
+        }
+    }
+
+    #[test]
+    fn test_registers_follow_dropped_lines() {
+        let mut registers = Registers::default();
+        registers.set('b', position(40));
+        registers.set('a', position(5));
+        registers.set('a', position(20));
+        assert_eq!(registers.get('a'), Some(position(20)));
+        assert_eq!(
+            registers.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!['a', 'b']
+        );
+
+        registers.drop_lines_before(30);
+        assert_eq!(registers.get('a'), None);
+        assert_eq!(registers.get('b'), Some(position(10)));
+        assert!(!Registers::is_register_name('1'));
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::event_mark_view::EventOrMark;
+use crate::log::LogLine;
+
+/// File format of an exported report of events and marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+impl ReportFormat {
+    /// Picks the format from the extension of the path: JSON for `.json`, CSV otherwise.
+    pub fn from_path(path: &str) -> Self {
+        match Path::new(path).extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => ReportFormat::Json,
+            _ => ReportFormat::Csv,
+        }
+    }
+}
+
+/// An event or mark occurrence in an exported report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReportRow {
+    /// `event` or `mark`.
+    pub kind: &'static str,
+    pub name: String,
+    /// Line number, counted from 1.
+    pub line: usize,
+    /// Timestamp of the line in RFC 3339, if it has one.
+    pub timestamp: Option<String>,
+    pub content: String,
+}
+
+impl ReportRow {
+    pub fn new(item: &EventOrMark, log_line: &LogLine) -> Self {
+        Self {
+            kind: if item.is_mark() { "mark" } else { "event" },
+            name: item.name().to_string(),
+            line: item.line_index() + 1,
+            timestamp: log_line.timestamp.map(format_timestamp),
+            content: log_line.content().to_string(),
+        }
+    }
+}
+
+fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Formats the rows as a report in the given format.
+pub fn format_report(rows: &[ReportRow], format: ReportFormat) -> Result<String, String> {
+    match format {
+        ReportFormat::Json => serde_json::to_string_pretty(rows).map_err(|e| e.to_string()),
+        ReportFormat::Csv => {
+            let mut csv = String::from("kind,name,line,timestamp,content\n");
+            for row in rows {
+                let fields = [
+                    row.kind.to_string(),
+                    row.name.clone(),
+                    row.line.to_string(),
+                    row.timestamp.clone().unwrap_or_default(),
+                    row.content.clone(),
+                ];
+                let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+            Ok(csv)
+        }
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break, doubling the quotes inside.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_event::LogEvent;
+    use crate::marking::Mark;
+    use crate::timestamp::parse_timestamp;
+
+    #[test]
+    fn test_format_report() {
+        let mut line = LogLine::new(r#"2024-01-15 10:30:45.250 ERROR failed, code="7""#, 4);
+        line.timestamp = parse_timestamp(line.content());
+        let event = LogEvent {
+            name: "Error".to_string(),
+            line_index: 4,
+        };
+        let mark = Mark::new_with_name(4, "first failure");
+        let rows = vec![
+            ReportRow::new(&EventOrMark::Event(&event), &line),
+            ReportRow::new(&EventOrMark::Mark(&mark), &line),
+        ];
+
+        let csv = format_report(&rows, ReportFormat::Csv).unwrap();
+        assert_eq!(
+            csv.lines().nth(1),
+            Some(r#"event,Error,5,2024-01-15T10:30:45.250Z,"2024-01-15 10:30:45.250 ERROR failed, code=""7""""#)
+        );
+        assert!(csv.lines().nth(2).unwrap().starts_with("mark,first failure,5,"));
+
+        let json: serde_json::Value = serde_json::from_str(&format_report(&rows, ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[1]["kind"], "mark");
+        assert_eq!(json[0]["line"], 5);
+
+        assert_eq!(ReportFormat::from_path("out/report.JSON"), ReportFormat::Json);
+        assert_eq!(ReportFormat::from_path("report.csv"), ReportFormat::Csv);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::log::LogLine;
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::rc::Rc;
@@ -34,6 +35,38 @@ pub trait TagRule {
     fn get_tags(&self, line: &LogLine) -> Option<Tag>;
 }
 
+/// Value lines are sorted by. Lines without a value are sorted last.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum SortKey {
+    Number(f64),
+    Text(String),
+    Missing,
+}
+
+/// Trait for rules that order visible lines by a key instead of by log index.
+pub trait SortRule {
+    /// Returns the key the line is sorted by.
+    fn sort_key(&self, line: &LogLine) -> SortKey;
+
+    /// Returns true if lines are sorted by descending key.
+    fn is_descending(&self) -> bool {
+        false
+    }
+}
+
+/// Compares sort keys, keeping lines without a value last regardless of the direction.
+fn compare_sort_keys(a: &SortKey, b: &SortKey, descending: bool) -> Ordering {
+    match (a, b) {
+        (SortKey::Missing, SortKey::Missing) => Ordering::Equal,
+        (SortKey::Missing, _) => Ordering::Greater,
+        (_, SortKey::Missing) => Ordering::Less,
+        _ => {
+            let ordering = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+            if descending { ordering.reverse() } else { ordering }
+        }
+    }
+}
+
 /// A visible line with metadata for rendering
 #[derive(Debug, Clone)]
 pub struct VisibleLine {
@@ -72,8 +105,12 @@ pub struct ViewportResolver {
     visibility_rules: Vec<Box<dyn VisibilityRule>>,
     /// Tag rules (marks, events) - add metadata to visible lines
     tag_rules: Vec<Box<dyn TagRule>>,
+    /// Sort rule (column sorting) - orders visible lines, which are in log order without it
+    sort_rule: Option<Box<dyn SortRule>>,
     /// Cached visible lines.
     visible_cache: RefCell<Option<Rc<Vec<VisibleLine>>>>,
+    /// Whether each log line is visible, derived from the cached visible lines on first use.
+    visible_mask: RefCell<Option<Rc<Vec<bool>>>>,
     /// Expanded lines: log index -> Vec<log_index>
     expanded_lines: Arc<HashMap<usize, Vec<usize>>>,
 }
@@ -83,6 +120,7 @@ impl Debug for ViewportResolver {
         f.debug_struct("ViewportResolver")
             .field("visibility_rules_count", &self.visibility_rules.len())
             .field("tag_rules_count", &self.tag_rules.len())
+            .field("sorted", &self.sort_rule.is_some())
             .field("has_cache", &self.visible_cache.borrow().is_some())
             .finish()
     }
@@ -100,7 +138,9 @@ impl ViewportResolver {
         Self {
             visibility_rules: Vec::new(),
             tag_rules: Vec::new(),
+            sort_rule: None,
             visible_cache: RefCell::new(None),
+            visible_mask: RefCell::new(None),
             expanded_lines: Arc::new(HashMap::new()),
         }
     }
@@ -121,10 +161,17 @@ impl ViewportResolver {
     pub fn clear_rules(&mut self) {
         self.visibility_rules.clear();
         self.tag_rules.clear();
+        self.sort_rule = None;
         self.expanded_lines = Arc::new(HashMap::new());
         self.invalidate_cache();
     }
 
+    /// Set the sort rule
+    pub fn set_sort_rule(&mut self, rule: Box<dyn SortRule>) {
+        self.sort_rule = Some(rule);
+        self.invalidate_cache();
+    }
+
     /// Set expanded line.
     pub fn set_expanded_lines(&mut self, expanded_lines: Arc<HashMap<usize, Vec<usize>>>) {
         self.expanded_lines = expanded_lines;
@@ -134,6 +181,7 @@ impl ViewportResolver {
     /// Invalidate the cache, forcing recomputation on next access
     pub fn invalidate_cache(&mut self) {
         *self.visible_cache.borrow_mut() = None;
+        *self.visible_mask.borrow_mut() = None;
     }
 
     /// Get the visible lines (cached or compute)
@@ -184,6 +232,16 @@ impl ViewportResolver {
             }
         }
 
+        if let Some(sort_rule) = &self.sort_rule {
+            let descending = sort_rule.is_descending();
+            let mut keyed: Vec<(SortKey, VisibleLine)> = results
+                .into_iter()
+                .map(|visible_line| (sort_rule.sort_key(&lines[visible_line.log_index]), visible_line))
+                .collect();
+            keyed.sort_by(|(a, _), (b, _)| compare_sort_keys(a, b, descending));
+            results = keyed.into_iter().map(|(_, visible_line)| visible_line).collect();
+        }
+
         results
     }
 
@@ -196,6 +254,24 @@ impl ViewportResolver {
         }
     }
 
+    /// Updates the cached visible lines after the first `count` log lines were dropped.
+    ///
+    /// Returns the number of dropped lines that were visible.
+    pub fn drop_lines_before(&mut self, count: usize) -> usize {
+        *self.visible_mask.borrow_mut() = None;
+        let mut cache = self.visible_cache.borrow_mut();
+        let Some(rc_visible) = cache.as_mut() else {
+            return 0;
+        };
+        let visible_lines = Rc::make_mut(rc_visible);
+        let previous_len = visible_lines.len();
+        visible_lines.retain(|v| v.log_index >= count);
+        for visible_line in visible_lines.iter_mut() {
+            visible_line.log_index -= count;
+        }
+        previous_len - visible_lines.len()
+    }
+
     /// Convert viewport index to log index
     pub fn viewport_to_log(&mut self, viewport_idx: usize, lines: &[LogLine]) -> Option<usize> {
         let visible = self.get_visible_lines(lines);
@@ -208,6 +284,24 @@ impl ViewportResolver {
         visible.iter().position(|v| v.log_index == log_idx)
     }
 
+    /// Returns whether the log line is visible. Takes constant time once the visibility of all lines was derived
+    /// from the visible lines, e.g. to check many events or marks.
+    pub fn is_visible(&self, lines: &[LogLine], log_index: usize) -> bool {
+        let cached = self.visible_mask.borrow().clone();
+        let mask = cached.unwrap_or_else(|| {
+            let mut mask = vec![false; lines.len()];
+            for visible_line in self.get_visible_lines(lines).iter() {
+                if let Some(visible) = mask.get_mut(visible_line.log_index) {
+                    *visible = true;
+                }
+            }
+            let mask = Rc::new(mask);
+            *self.visible_mask.borrow_mut() = Some(Rc::clone(&mask));
+            mask
+        });
+        mask.get(log_index).copied().unwrap_or(false)
+    }
+
     /// Get the total count of visible lines
     pub fn visible_count(&self, lines: &[LogLine]) -> usize {
         self.get_visible_lines(lines).len()
@@ -0,0 +1,106 @@
+use regex::Regex;
+
+use crate::log::LogLine;
+
+/// Lines the restart boundaries were found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ScannedLines {
+    base_generation: u64,
+    count: usize,
+}
+
+/// Lines where the application writing the log was restarted, e.g. "Starting application", splitting a
+/// long-running log into runs.
+#[derive(Debug, Default)]
+pub struct Restarts {
+    /// Regex matching the first line of a run.
+    pattern: Option<Regex>,
+    /// Log indices of the lines matching the pattern, in log order.
+    boundaries: Vec<usize>,
+    scanned: Option<ScannedLines>,
+}
+
+impl Restarts {
+    pub fn new(pattern: Option<Regex>) -> Self {
+        Self {
+            pattern,
+            ..Self::default()
+        }
+    }
+
+    /// Finds the restart boundaries. Appended lines are scanned on their own.
+    pub fn update(&mut self, lines: &[LogLine], base_generation: u64) {
+        let Some(pattern) = &self.pattern else {
+            return;
+        };
+        let start = match self.scanned {
+            Some(scanned) if scanned.base_generation == base_generation && scanned.count <= lines.len() => {
+                scanned.count
+            }
+            _ => {
+                self.boundaries.clear();
+                0
+            }
+        };
+        self.scanned = Some(ScannedLines {
+            base_generation,
+            count: lines.len(),
+        });
+
+        self.boundaries.extend(
+            lines[start..]
+                .iter()
+                .filter(|line| pattern.is_match(line.content()))
+                .map(|line| line.index),
+        );
+    }
+
+    /// Returns the log indices of the restart boundaries, in log order.
+    pub fn boundaries(&self) -> &[usize] {
+        &self.boundaries
+    }
+
+    /// Returns the number of the run starting at the line at the given log index, counted from 1.
+    pub fn run_starting_at(&self, log_index: usize) -> Option<usize> {
+        self.boundaries.binary_search(&log_index).ok().map(|i| i + 1)
+    }
+
+    /// Returns the number of lines of the run starting at the given boundary, up to the next boundary or the
+    /// end of the log.
+    pub fn run_length(&self, boundary: usize, total_lines: usize) -> usize {
+        let start = self.boundaries[boundary];
+        let end = self.boundaries.get(boundary + 1).copied().unwrap_or(total_lines);
+        end.saturating_sub(start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::LogBuffer;
+
+    #[test]
+    fn test_boundaries_follow_appended_and_dropped_lines() {
+        let mut buffer = LogBuffer::default();
+        buffer.init_stdin_mode();
+        for content in ["boot", "Starting application", "a", "b", "Starting application", "c"] {
+            buffer.append_line(content.to_string());
+        }
+        let mut restarts = Restarts::new(Some(Regex::new("^Starting application").unwrap()));
+        restarts.update(buffer.all_lines(), buffer.base_generation());
+        assert_eq!(restarts.boundaries(), &[1, 4]);
+        assert_eq!(restarts.run_starting_at(4), Some(2));
+        assert_eq!(restarts.run_starting_at(2), None);
+        assert_eq!(restarts.run_length(0, buffer.get_total_lines_count()), 3);
+        assert_eq!(restarts.run_length(1, buffer.get_total_lines_count()), 2);
+
+        buffer.append_line("Starting application".to_string());
+        restarts.update(buffer.all_lines(), buffer.base_generation());
+        assert_eq!(restarts.boundaries(), &[1, 4, 6]);
+
+        // Dropped lines are scanned again
+        buffer.drop_oldest(2);
+        restarts.update(buffer.all_lines(), buffer.base_generation());
+        assert_eq!(restarts.boundaries(), &[2, 4]);
+    }
+}
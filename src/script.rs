@@ -0,0 +1,228 @@
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// A user script that analyses log lines and sends annotations back.
+///
+/// Lines are written to the script's stdin as one JSON object per line, `{"index": 3, "line": "..."}`, and
+/// the script writes [`ScriptAnnotation`]s to its stdout, also one JSON object per line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Script {
+    pub name: String,
+    /// Shell command starting the script.
+    pub command: String,
+    /// Whether the script runs for the whole stream and receives each new line.
+    pub stream: bool,
+}
+
+/// A log line as sent to a script.
+#[derive(Debug, Serialize)]
+struct ScriptInput<'a> {
+    index: usize,
+    line: &'a str,
+}
+
+/// Annotation sent back by a script. Lines are referred to by the index they were sent with.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScriptAnnotation {
+    /// Marks a line, optionally with a name.
+    Mark { index: usize, name: Option<String> },
+    /// Highlights the matches of a regex.
+    Highlight {
+        pattern: String,
+        fg: Option<String>,
+        bg: Option<String>,
+    },
+    /// Adds a computed field to a line, shown in the line inspector.
+    Field { index: usize, name: String, value: String },
+}
+
+/// Encodes a line as the JSON object sent to a script, without the trailing newline.
+pub fn encode_line(index: usize, line: &str) -> String {
+    serde_json::to_string(&ScriptInput { index, line }).unwrap_or_default()
+}
+
+/// Encodes lines as the input of a script run on demand.
+pub fn encode_lines<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> String {
+    let mut input = String::new();
+    for (index, line) in lines {
+        input.push_str(&encode_line(index, line));
+        input.push('\n');
+    }
+    input
+}
+
+/// Parses the output of a script. Returns the annotations and the number of lines that are not valid annotations.
+pub fn parse_annotations(output: &str) -> (Vec<ScriptAnnotation>, usize) {
+    let mut annotations = Vec::new();
+    let mut invalid = 0;
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(annotation) => annotations.push(annotation),
+            Err(e) => {
+                debug!("Invalid script output '{}': {}", line, e);
+                invalid += 1;
+            }
+        }
+    }
+    (annotations, invalid)
+}
+
+/// A script running alongside the stream, receiving each new line.
+#[derive(Debug)]
+pub struct StreamingScript {
+    pub name: String,
+    line_tx: mpsc::UnboundedSender<String>,
+    /// Annotations received but not yet taken by the application.
+    annotations: Arc<Mutex<Vec<ScriptAnnotation>>>,
+}
+
+impl StreamingScript {
+    /// Starts the script. `on_annotations` is called when annotations arrive while none are waiting to be taken.
+    pub fn spawn<F>(script: &Script, on_annotations: F) -> Result<Self, String>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let (shell, shell_arg) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+        let mut child = Command::new(shell)
+            .arg(shell_arg)
+            .arg(&script.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to run script '{}': {}", script.name, e))?;
+
+        let annotations: Arc<Mutex<Vec<ScriptAnnotation>>> = Arc::default();
+        if let Some(stdout) = child.stdout.take() {
+            let pending = annotations.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let (parsed, _) = parse_annotations(&line);
+                    if parsed.is_empty() {
+                        continue;
+                    }
+                    let was_empty = {
+                        let mut pending = pending.lock().unwrap();
+                        let was_empty = pending.is_empty();
+                        pending.extend(parsed);
+                        was_empty
+                    };
+                    if was_empty {
+                        on_annotations();
+                    }
+                }
+            });
+        }
+
+        // The script's stdin is closed when the script is dropped, which lets it finish
+        let (line_tx, mut line_rx) = mpsc::unbounded_channel::<String>();
+        let mut stdin = child.stdin.take();
+        tokio::spawn(async move {
+            while let Some(line) = line_rx.recv().await {
+                let Some(writer) = stdin.as_mut() else {
+                    break;
+                };
+                if writer.write_all(line.as_bytes()).await.is_err() || writer.flush().await.is_err() {
+                    break;
+                }
+            }
+            drop(stdin);
+            let _ = child.wait().await;
+        });
+
+        Ok(Self {
+            name: script.name.clone(),
+            line_tx,
+            annotations,
+        })
+    }
+
+    /// Sends a new line to the script.
+    pub fn send_line(&self, index: usize, line: &str) {
+        let _ = self.line_tx.send(encode_line(index, line) + "\n");
+    }
+
+    /// Takes the annotations received since the last call.
+    pub fn take_annotations(&self) -> Vec<ScriptAnnotation> {
+        std::mem::take(&mut *self.annotations.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_lines() {
+        let input = encode_lines([(0, "first"), (1, "say \"hi\"")].into_iter());
+        assert_eq!(
+            input,
+            "{\"index\":0,\"line\":\"first\"}\n{\"index\":1,\"line\":\"say \\\"hi\\\"\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_annotations() {
+        let output = r#"
+{"type": "mark", "index": 3, "name": "slow request"}
+{"type": "mark", "index": 4}
+{"type": "highlight", "pattern": "req-\\d+", "fg": "yellow"}
+{"type": "field", "index": 3, "name": "latency_ms", "value": "1200"}
+not json
+{"type": "unknown"}
+"#;
+        let (annotations, invalid) = parse_annotations(output);
+        assert_eq!(invalid, 2);
+        assert_eq!(
+            annotations,
+            vec![
+                ScriptAnnotation::Mark {
+                    index: 3,
+                    name: Some("slow request".to_string())
+                },
+                ScriptAnnotation::Mark { index: 4, name: None },
+                ScriptAnnotation::Highlight {
+                    pattern: r"req-\d+".to_string(),
+                    fg: Some("yellow".to_string()),
+                    bg: None
+                },
+                ScriptAnnotation::Field {
+                    index: 3,
+                    name: "latency_ms".to_string(),
+                    value: "1200".to_string()
+                },
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_streaming_script_returns_annotations() {
+        let script = Script {
+            name: "marker".to_string(),
+            command: r#"while read -r line; do echo '{"type": "mark", "index": 7}'; done"#.to_string(),
+            stream: true,
+        };
+        let (notify_tx, mut notify_rx) = mpsc::unbounded_channel();
+        let streaming = StreamingScript::spawn(&script, move || {
+            let _ = notify_tx.send(());
+        })
+        .unwrap();
+
+        streaming.send_line(7, "ERROR");
+        notify_rx.recv().await.unwrap();
+        assert_eq!(
+            streaming.take_annotations(),
+            vec![ScriptAnnotation::Mark { index: 7, name: None }]
+        );
+    }
+}
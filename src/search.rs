@@ -1,7 +1,31 @@
 use crate::history::History;
-use crate::utils::contains_ignore_case;
+use crate::log::LogLine;
+use crate::matcher::{FuzzyPattern, PatternMatcher, PlainMatch};
+use crate::utils::CaseFolding;
 use rayon::prelude::*;
 
+/// Lines a search navigates through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchScope {
+    /// Only lines passing the filters.
+    #[default]
+    Filtered,
+    /// All lines. Matching lines hidden by filters are revealed.
+    All,
+}
+
+/// Log lines the matches were found in, so lines appended to them while streaming are searched on their own.
+#[derive(Debug, Clone, PartialEq)]
+struct ScannedLines {
+    /// Pattern and matching settings the lines were searched with.
+    matcher_key: (String, bool, bool, bool),
+    /// Generation of the first line, changed when lines are dropped.
+    base_generation: u64,
+    line_count: usize,
+    /// Log indices of the visible lines.
+    visible: Vec<usize>,
+}
+
 /// Manages search pattern matching and navigation through search results.
 #[derive(Debug, Default)]
 pub struct Search {
@@ -15,17 +39,41 @@ pub struct Search {
     match_indices: Vec<usize>,
     /// Total number of matches including filtered-out lines.
     total_match_count: usize,
+    /// Lines the search navigates through.
+    scope: SearchScope,
+    /// Whether the pattern is matched fuzzily, as its characters in order with anything in between.
+    fuzzy: bool,
+    /// Whether only occurrences of the pattern that are whole words match.
+    whole_word: bool,
     /// Search query history.
     pub history: History<String>,
+    /// Log lines the current matches were found in, if found with [`Search::update_log_line_matches`].
+    scanned: Option<ScannedLines>,
+    /// How the case of characters is folded when searching case-insensitively.
+    case_folding: CaseFolding,
 }
 
 impl Search {
-    /// Returns whether a line matches the given pattern based on case sensitivity setting.
-    fn matches_pattern(&self, line: &str, pattern: &str) -> bool {
-        if self.case_sensitive {
-            line.contains(pattern)
+    /// Returns the search folding the case of characters this way when searching case-insensitively.
+    pub fn with_case_folding(mut self, case_folding: CaseFolding) -> Self {
+        self.case_folding = case_folding;
+        self
+    }
+
+    /// Returns the matcher for a pattern based on the case sensitivity, fuzzy and whole word settings.
+    pub fn matcher(&self, pattern: &str) -> PatternMatcher {
+        if self.fuzzy {
+            PatternMatcher::Fuzzy(FuzzyPattern {
+                pattern: pattern.to_string(),
+                case_sensitive: self.case_sensitive,
+            })
         } else {
-            contains_ignore_case(line, pattern)
+            PatternMatcher::Plain(PlainMatch {
+                pattern: pattern.to_string(),
+                case_sensitive: self.case_sensitive,
+                whole_word: self.whole_word,
+                case_folding: self.case_folding,
+            })
         }
     }
 
@@ -40,18 +88,100 @@ impl Search {
         if pattern.is_empty() {
             return None;
         }
-        self.active_pattern = Some(pattern.to_string());
-        self.history.add(pattern.to_string());
+        self.set_pattern(pattern);
         self.update_matches(pattern, visible_lines, all_lines);
         Some(self.match_indices.len())
     }
 
-    /// Clears all matches and active pattern.
+    /// Sets the active pattern and adds it to the history, without matches until they are updated.
+    pub fn set_pattern(&mut self, pattern: &str) {
+        self.active_pattern = Some(pattern.to_string());
+        self.history.add(pattern.to_string());
+        self.clear_match_results();
+    }
+
+    /// Removes the matches, e.g. before they are found again in the background.
+    pub fn clear_match_results(&mut self) {
+        self.match_indices.clear();
+        self.current_match_index = 0;
+        self.total_match_count = 0;
+        self.scanned = None;
+    }
+
+    /// Appends matches found by a background search, which must follow the existing matches.
+    pub fn append_matches(&mut self, matches: &[usize]) {
+        self.match_indices.extend_from_slice(matches);
+    }
+
+    /// Clears all matches and active pattern. Hidden matches are no longer revealed.
     pub fn clear_matches(&mut self) {
         self.active_pattern = None;
         self.match_indices.clear();
         self.current_match_index = 0;
         self.total_match_count = 0;
+        self.scanned = None;
+        self.scope = SearchScope::Filtered;
+        self.fuzzy = false;
+    }
+
+    pub fn scope(&self) -> SearchScope {
+        self.scope
+    }
+
+    /// Toggles between searching the filtered lines and all lines.
+    pub fn toggle_scope(&mut self) {
+        self.scope = match self.scope {
+            SearchScope::Filtered => SearchScope::All,
+            SearchScope::All => SearchScope::Filtered,
+        };
+    }
+
+    pub fn is_fuzzy(&self) -> bool {
+        self.fuzzy
+    }
+
+    /// Toggles between plain and fuzzy matching.
+    pub fn toggle_fuzzy(&mut self) {
+        self.fuzzy = !self.fuzzy;
+    }
+
+    pub fn is_whole_word(&self) -> bool {
+        self.whole_word
+    }
+
+    /// Toggles between matching the pattern anywhere and only as a whole word.
+    pub fn toggle_whole_word(&mut self) {
+        self.whole_word = !self.whole_word;
+    }
+
+    /// Sets whether only whole words match, e.g. when restoring a session.
+    pub fn set_whole_word(&mut self, whole_word: bool) {
+        self.whole_word = whole_word;
+    }
+
+    /// Returns whether matching lines hidden by filters should be revealed.
+    pub fn reveals_hidden_matches(&self) -> bool {
+        self.scope == SearchScope::All && self.active_pattern.is_some()
+    }
+
+    /// Returns the number of matches in lines that are not visible.
+    pub fn hidden_match_count(&self) -> usize {
+        self.total_match_count.saturating_sub(self.match_indices.len())
+    }
+
+    /// Returns the indices of all lines matching the active pattern.
+    pub fn matching_line_indices<'a>(&self, lines: impl Iterator<Item = (usize, &'a str)>) -> Vec<usize> {
+        let Some(pattern) = self.active_pattern.as_deref() else {
+            return Vec::new();
+        };
+        let lines_vec: Vec<(usize, &str)> = lines.collect();
+        let matcher = self.matcher(pattern);
+
+        lines_vec
+            .par_iter()
+            .filter(|(_, line)| matcher.matches(line))
+            .map(|(index, _)| *index)
+            .collect()
     }
 
     /// Returns the active search pattern (submitted search).
@@ -83,6 +213,7 @@ impl Search {
     ) {
         self.match_indices.clear();
         self.current_match_index = 0;
+        self.scanned = None;
 
         if pattern.is_empty() {
             self.total_match_count = 0;
@@ -91,43 +222,73 @@ impl Search {
 
         let visible_vec: Vec<&str> = visible_lines.collect();
         let all_vec: Vec<&str> = all_lines.collect();
-        let case_sensitive = self.case_sensitive;
+        let matcher = self.matcher(pattern);
 
         // Update visible matches
         self.match_indices = visible_vec
             .par_iter()
             .enumerate()
-            .filter_map(|(line_index, line)| {
-                let matching = if case_sensitive {
-                    line.contains(pattern)
-                } else {
-                    contains_ignore_case(line, pattern)
-                };
-
-                if matching { Some(line_index) } else { None }
-            })
+            .filter_map(|(line_index, line)| if matcher.matches(line) { Some(line_index) } else { None })
             .collect();
 
         // Count total matches
-        self.total_match_count = all_vec
-            .par_iter()
-            .filter(|line| {
-                if case_sensitive {
-                    line.contains(pattern)
-                } else {
-                    contains_ignore_case(line, pattern)
-                }
-            })
+        self.total_match_count = all_vec.par_iter().filter(|line| matcher.matches(line)).count();
+    }
+
+    /// Updates the matches in the visible lines, given by their log index, and in all log lines. If lines were only
+    /// appended since the last update, e.g. while streaming, only the appended lines are searched and the current
+    /// match is kept.
+    pub fn update_log_line_matches(
+        &mut self,
+        pattern: &str,
+        lines: &[LogLine],
+        visible: &[usize],
+        base_generation: u64,
+    ) {
+        let matcher_key = (pattern.to_string(), self.case_sensitive, self.fuzzy, self.whole_word);
+        let scanned = self.scanned.take().filter(|scanned| {
+            scanned.matcher_key == matcher_key
+                && scanned.base_generation == base_generation
+                && scanned.line_count <= lines.len()
+                && visible.starts_with(&scanned.visible)
+        });
+        let Some(mut scanned) = scanned else {
+            self.update_matches(
+                pattern,
+                visible.iter().map(|&index| lines[index].content()),
+                lines.iter().map(LogLine::content),
+            );
+            self.scanned = Some(ScannedLines {
+                matcher_key,
+                base_generation,
+                line_count: lines.len(),
+                visible: visible.to_vec(),
+            });
+            return;
+        };
+
+        let matcher = self.matcher(pattern);
+        let first_new = scanned.visible.len();
+        self.match_indices.extend(
+            visible[first_new..]
+                .iter()
+                .enumerate()
+                .filter(|(_, index)| matcher.matches(lines[**index].content()))
+                .map(|(offset, _)| first_new + offset),
+        );
+        self.total_match_count += lines[scanned.line_count..]
+            .iter()
+            .filter(|line| matcher.matches(line.content()))
             .count();
+        scanned.line_count = lines.len();
+        scanned.visible.extend_from_slice(&visible[first_new..]);
+        self.scanned = Some(scanned);
     }
 
-    /// Appends a single line to matches if it matches the active pattern.
-    pub fn append_line(&mut self, line_index: usize, line_content: &str) {
-        if let Some(pattern) = &self.active_pattern
-            && self.matches_pattern(line_content, pattern)
-        {
-            self.match_indices.push(line_index);
-        }
+    /// Returns the byte range of the first match of the active pattern in the given line.
+    pub fn find_first_match(&self, line: &str) -> Option<(usize, usize)> {
+        let pattern = self.active_pattern.as_ref()?;
+        self.matcher(pattern).find_all(line).first().copied()
     }
 
     /// Finds the next match after the current line.
@@ -217,18 +378,9 @@ impl Search {
         }
 
         let lines_vec: Vec<&str> = lines.collect();
-        let case_sensitive = self.case_sensitive;
+        let matcher = self.matcher(pattern);
 
-        lines_vec
-            .par_iter()
-            .filter(|line| {
-                if case_sensitive {
-                    line.contains(pattern)
-                } else {
-                    contains_ignore_case(line, pattern)
-                }
-            })
-            .count()
+        lines_vec.par_iter().filter(|line| matcher.matches(line)).count()
     }
 
     /// Returns the indices of all search matches.
@@ -240,6 +392,8 @@ impl Search {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::log::LogBuffer;
+    use crate::utils::{CaseFolding, contains_ignore_case};
 
     #[test]
     fn test_apply_pattern_sets_active_pattern() {
@@ -311,25 +465,113 @@ mod tests {
         assert_eq!(total, 2);
     }
 
+    #[test]
+    fn test_search_scope() {
+        let mut search = Search::default();
+        let all_lines = ["ERROR: foo", "INFO: bar", "ERROR: baz"];
+        let visible_lines = ["ERROR: foo", "INFO: bar"];
+        search.apply_pattern("ERROR", visible_lines.iter().copied(), all_lines.iter().copied());
+
+        assert_eq!(search.hidden_match_count(), 1);
+        assert!(!search.reveals_hidden_matches());
+
+        search.toggle_scope();
+        assert_eq!(search.scope(), SearchScope::All);
+        assert!(search.reveals_hidden_matches());
+        assert_eq!(
+            search.matching_line_indices(all_lines.iter().copied().enumerate()),
+            vec![0, 2]
+        );
+
+        search.clear_matches();
+        assert_eq!(search.scope(), SearchScope::Filtered);
+    }
+
     #[test]
     fn test_contains_ignore_case_finds_different_cases() {
-        assert!(contains_ignore_case("ERROR: foo", "error"));
-        assert!(contains_ignore_case("error: foo", "ERROR"));
-        assert!(contains_ignore_case("Error: foo", "eRrOr"));
+        assert!(contains_ignore_case("ERROR: foo", "error", CaseFolding::Unicode));
+        assert!(contains_ignore_case("error: foo", "ERROR", CaseFolding::Unicode));
+        assert!(contains_ignore_case("Error: foo", "eRrOr", CaseFolding::Unicode));
     }
 
     #[test]
     fn test_contains_ignore_case_returns_false_for_no_match() {
-        assert!(!contains_ignore_case("INFO: foo", "error"));
+        assert!(!contains_ignore_case("INFO: foo", "error", CaseFolding::Unicode));
     }
 
     #[test]
     fn test_contains_ignore_case_handles_empty_needle() {
-        assert!(contains_ignore_case("foo", ""));
+        assert!(contains_ignore_case("foo", "", CaseFolding::Unicode));
     }
 
     #[test]
     fn test_contains_ignore_case_handles_needle_longer_than_haystack() {
-        assert!(!contains_ignore_case("foo", "foobar"));
+        assert!(!contains_ignore_case("foo", "foobar", CaseFolding::Unicode));
+    }
+
+    #[test]
+    fn test_find_first_match() {
+        let mut search = Search::default();
+        let lines = ["foo ERROR bar error"];
+        assert_eq!(search.find_first_match(lines[0]), None);
+
+        search.apply_pattern("error", lines.iter().copied(), lines.iter().copied());
+        assert_eq!(search.find_first_match(lines[0]), Some((4, 9)));
+
+        search.toggle_case_sensitivity();
+        assert_eq!(search.find_first_match(lines[0]), Some((14, 19)));
+    }
+
+    #[test]
+    fn test_fuzzy_search() {
+        let mut search = Search::default();
+        search.toggle_fuzzy();
+        let lines = ["connection timeout", "connect ok", "Conn reset, timed out"];
+        search.apply_pattern("contmo", lines.iter().copied(), lines.iter().copied());
+        assert_eq!(search.get_match_indices(), [0, 2]);
+        assert_eq!(search.find_first_match(lines[0]), Some((5, 6)));
+
+        search.clear_matches();
+        assert!(!search.is_fuzzy());
+    }
+
+    #[test]
+    fn test_whole_word_search() {
+        let mut search = Search::default();
+        search.toggle_whole_word();
+        let lines = ["transferred 3 files", "err: disk full", "ERR_X err"];
+        search.apply_pattern("err", lines.iter().copied(), lines.iter().copied());
+        assert_eq!(search.get_match_indices(), [1, 2]);
+        assert_eq!(search.find_first_match(lines[2]), Some((6, 9)));
+
+        // The toggle is kept for the next search
+        search.clear_matches();
+        assert!(search.is_whole_word());
+    }
+
+    #[test]
+    fn test_update_log_line_matches_searches_appended_lines() {
+        let mut buffer = LogBuffer::default();
+        buffer.init_stdin_mode();
+        for line in ["error: a", "info", "error: b"] {
+            buffer.append_line(line.to_string());
+        }
+        let mut search = Search::default();
+        search.update_log_line_matches("error", buffer.all_lines(), &[0, 1, 2], buffer.base_generation());
+        assert_eq!(search.get_match_indices(), &[0, 2]);
+        search.next_match(0);
+        assert_eq!(search.get_match_info(), (2, 2, 2));
+
+        // Appended lines are searched, keeping the current match
+        buffer.append_line("error: c".to_string());
+        buffer.append_line("error: d".to_string());
+        search.update_log_line_matches("error", buffer.all_lines(), &[0, 1, 2, 4], buffer.base_generation());
+        assert_eq!(search.get_match_indices(), &[0, 2, 3]);
+        assert_eq!(search.get_match_info(), (2, 3, 4));
+
+        // Other visible lines are searched again
+        search.update_log_line_matches("error", buffer.all_lines(), &[1, 3, 4], buffer.base_generation());
+        assert_eq!(search.get_match_indices(), &[1, 2]);
+        assert_eq!(search.get_match_info(), (1, 2, 4));
     }
 }
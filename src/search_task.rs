@@ -0,0 +1,180 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::log::LogLine;
+use crate::matcher::PatternMatcher;
+use crate::task::TaskProgress;
+use rayon::prelude::*;
+
+/// Buffers with at least this many lines are searched in the background.
+pub const BACKGROUND_SEARCH_MIN_LINES: usize = 500_000;
+
+/// Number of lines searched between progress updates and cancellation checks.
+const CHUNK_SIZE: usize = 65_536;
+
+/// Matches found by a [`SearchTask`] since its previous progress update.
+#[derive(Debug, Clone)]
+pub struct SearchProgress {
+    /// Generation of the task that found the matches.
+    pub generation: usize,
+    /// Viewport indices of the matches found, in ascending order and after those of earlier updates.
+    pub matches: Vec<usize>,
+    /// Number of matching lines including lines hidden by filters, set in the last update.
+    pub total_matches: Option<usize>,
+}
+
+/// Search running on a background thread.
+///
+/// Matches in the visible lines are reported chunk by chunk, so the first matches can be navigated
+/// before the search finishes. The task is cancelled when dropped.
+#[derive(Debug)]
+pub struct SearchTask {
+    generation: usize,
+    progress: TaskProgress,
+}
+
+impl SearchTask {
+    /// Starts searching the visible lines, given by their log line indices, followed by counting
+    /// the matches in all lines. `on_progress` is called from the background thread after each chunk.
+    pub fn spawn<F>(
+        generation: usize,
+        lines: Arc<Vec<LogLine>>,
+        visible: Vec<usize>,
+        matcher: PatternMatcher,
+        on_progress: F,
+    ) -> Self
+    where
+        F: Fn(SearchProgress) + Send + 'static,
+    {
+        let total = visible.len() + lines.len();
+        let progress = TaskProgress::spawn(total, move |cancelled, progress| {
+            let report_matches = |matches: Vec<usize>| {
+                if !matches.is_empty() {
+                    on_progress(SearchProgress {
+                        generation,
+                        matches,
+                        total_matches: None,
+                    });
+                }
+            };
+            if let Some(total_matches) = search_lines(&lines, &visible, &matcher, cancelled, progress, report_matches) {
+                on_progress(SearchProgress {
+                    generation,
+                    matches: Vec::new(),
+                    total_matches: Some(total_matches),
+                });
+            }
+        });
+
+        Self { generation, progress }
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn progress(&self) -> &TaskProgress {
+        &self.progress
+    }
+}
+
+/// Searches the visible lines in chunks, reporting the viewport indices of the matches of each
+/// chunk, then counts the matches in all lines. Returns the total count, or `None` if cancelled.
+pub fn search_lines(
+    lines: &[LogLine],
+    visible: &[usize],
+    matcher: &PatternMatcher,
+    cancelled: &AtomicBool,
+    progress: &AtomicUsize,
+    mut report_matches: impl FnMut(Vec<usize>),
+) -> Option<usize> {
+    for (chunk_index, chunk) in visible.chunks(CHUNK_SIZE).enumerate() {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let offset = chunk_index * CHUNK_SIZE;
+        let matches: Vec<usize> = chunk
+            .par_iter()
+            .enumerate()
+            .filter(|(_, log_index)| matcher.matches(lines[**log_index].content()))
+            .map(|(index, _)| offset + index)
+            .collect();
+        progress.fetch_add(chunk.len(), Ordering::Relaxed);
+        report_matches(matches);
+    }
+
+    let mut total_matches = 0;
+    for chunk in lines.chunks(CHUNK_SIZE) {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        total_matches += chunk.par_iter().filter(|line| matcher.matches(line.content())).count();
+        progress.fetch_add(chunk.len(), Ordering::Relaxed);
+    }
+
+    Some(total_matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::PlainMatch;
+    use crate::utils::CaseFolding;
+
+    fn matcher(case_sensitive: bool) -> PatternMatcher {
+        PatternMatcher::Plain(PlainMatch {
+            pattern: "error".to_string(),
+            case_sensitive,
+            whole_word: false,
+            case_folding: CaseFolding::default(),
+        })
+    }
+
+    #[test]
+    fn test_search_lines() {
+        let lines: Vec<LogLine> = (0..2 * CHUNK_SIZE + 20)
+            .map(|i| LogLine::new(if i % 1000 == 0 { "ERROR boom" } else { "INFO ok" }, i))
+            .collect();
+        // Every other line is visible
+        let visible: Vec<usize> = (0..lines.len()).step_by(2).collect();
+        let progress = AtomicUsize::new(0);
+
+        let mut reported = Vec::new();
+        let total = search_lines(
+            &lines,
+            &visible,
+            &matcher(false),
+            &AtomicBool::new(false),
+            &progress,
+            |matches| reported.push(matches),
+        );
+
+        assert_eq!(total, Some(132));
+        assert_eq!(reported.len(), 2);
+        assert_eq!(reported[0][..3], [0, 500, 1000]);
+        assert!(reported.concat().is_sorted());
+        assert_eq!(reported.concat().len(), 132);
+        assert_eq!(progress.load(Ordering::Relaxed), visible.len() + lines.len());
+
+        let cancelled = search_lines(
+            &lines,
+            &visible,
+            &matcher(false),
+            &AtomicBool::new(true),
+            &progress,
+            |_| {},
+        );
+        assert_eq!(cancelled, None);
+        assert_eq!(
+            search_lines(
+                &lines,
+                &visible,
+                &matcher(true),
+                &AtomicBool::new(false),
+                &progress,
+                |_| {}
+            ),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use crate::app::App;
+use crate::migration::{MigrationOutcome, migrate};
+use crate::persistence::{PersistedState, state_dir};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+/// Named snapshot of the opened files together with the full application state.
+///
+/// Unlike [`PersistedState`], which is saved per set of files, a session also remembers which files
+/// were open, the active search and the split view, and is only saved and loaded by name.
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    /// Absolute paths of the opened files.
+    files: Vec<String>,
+    state: PersistedState,
+    #[serde(default)]
+    search: Option<SearchState>,
+    #[serde(default)]
+    split: Option<SplitState>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SearchState {
+    pattern: String,
+    case_sensitive: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SplitState {
+    /// Position of the unfocused pane. The focused pane is the viewport of the state.
+    selected_line: usize,
+    top_line: usize,
+    focus_top: bool,
+}
+
+impl Session {
+    pub fn from_app(app: &App) -> Self {
+        let file_paths = app.file_manager.paths();
+        Self {
+            files: file_paths
+                .iter()
+                .map(|path| {
+                    fs::canonicalize(path)
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or_else(|_| path.to_string())
+                })
+                .collect(),
+            state: PersistedState::from_app(&file_paths, app),
+            search: app.search.get_active_pattern().map(|pattern| SearchState {
+                pattern: pattern.to_string(),
+                case_sensitive: app.search.is_case_sensitive(),
+            }),
+            split: app.split_viewport.as_ref().map(|split| SplitState {
+                selected_line: split.selected_line,
+                top_line: split.top_line,
+                focus_top: app.split_focus_top,
+            }),
+        }
+    }
+
+    pub fn files(&self) -> &[String] {
+        &self.files
+    }
+
+    /// Splits the session into the persisted state and the parts only sessions have.
+    pub fn into_parts(self) -> (PersistedState, Option<SearchState>, Option<SplitState>) {
+        (self.state, self.search, self.split)
+    }
+}
+
+impl SearchState {
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+}
+
+impl SplitState {
+    pub fn selected_line(&self) -> usize {
+        self.selected_line
+    }
+
+    pub fn top_line(&self) -> usize {
+        self.top_line
+    }
+
+    pub fn focus_top(&self) -> bool {
+        self.focus_top
+    }
+}
+
+/// Returns the ~/.lazylog/sessions directory where sessions are stored.
+pub fn sessions_dir() -> Option<PathBuf> {
+    Some(state_dir()?.join("sessions"))
+}
+
+/// Returns the path of the session file with the given name.
+fn session_path(name: &str) -> Result<PathBuf, String> {
+    validate_name(name)?;
+    let dir = sessions_dir().ok_or("Could not find home directory")?;
+    Ok(dir.join(format!("{}.json", name)))
+}
+
+/// Checks that a session name can be used as a file name.
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Session name is empty".to_string());
+    }
+    if name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(format!("Invalid session name: {}", name));
+    }
+    Ok(())
+}
+
+/// Saves a session under the given name, replacing an existing session with the same name.
+pub fn save_session(name: &str, session: &Session) -> Result<PathBuf, String> {
+    let path = session_path(name)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    }
+    let json = serde_json::to_string_pretty(session).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    Ok(path)
+}
+
+/// Loads the session with the given name. Returns `None` if there is no such session.
+pub fn load_session(name: &str) -> Result<Option<Session>, String> {
+    let path = session_path(name)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = fs::read_to_string(&path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    parse_session(&json).map(Some)
+}
+
+/// Parses a session, migrating its state to the current version.
+fn parse_session(json: &str) -> Result<Session, String> {
+    let mut session: Value = serde_json::from_str(json).map_err(|e| format!("Failed to parse session: {}", e))?;
+    let state = session.get_mut("state").ok_or("Session has no state")?;
+    if let MigrationOutcome::TooNew(version) = migrate(state)? {
+        return Err(format!(
+            "Session was saved by a newer version of lazylog (state version {})",
+            version
+        ));
+    }
+    serde_json::from_value(session).map_err(|e| format!("Failed to load session: {}", e))
+}
+
+/// Returns the names of all saved sessions, sorted.
+pub fn list_sessions() -> Vec<String> {
+    let Some(dir) = sessions_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration::STATE_VERSION;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("incident-42").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("  ").is_err());
+        assert!(validate_name("../other").is_err());
+        assert!(validate_name(".hidden").is_err());
+    }
+
+    #[test]
+    fn test_parse_session_migrates_state() {
+        let session = json!({
+            "files": ["/var/log/app.log", "/var/log/db.log"],
+            "state": {
+                "version": 1,
+                "log_file_paths": ["/var/log/app.log", "/var/log/db.log"],
+                "viewport": { "selected_line": 3, "top_line": 0, "horizontal_offset": 0, "center_cursor_mode": false },
+                "search_history": [],
+                "filter_history": [],
+                "filters": [{ "pattern": "ERROR", "mode": "Include", "case_sensitive": true, "enabled": true }],
+                "marks": [{ "line_index": 3, "name": "start" }],
+                "event_filters": [],
+                "options": [],
+            },
+            "search": { "pattern": "timeout", "case_sensitive": false },
+        });
+
+        let (state, search, split) = parse_session(&session.to_string()).unwrap().into_parts();
+        assert_eq!(state.filters()[0].pattern(), "ERROR");
+        assert!(!state.filters()[0].regex());
+        assert_eq!(state.marks()[0].line_index(), 3);
+        assert_eq!(search.unwrap().pattern(), "timeout");
+        assert!(split.is_none());
+
+        let too_new = json!({ "files": [], "state": { "version": STATE_VERSION + 1 } });
+        assert!(parse_session(&too_new.to_string()).is_err());
+    }
+}
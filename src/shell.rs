@@ -0,0 +1,256 @@
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Interval at which a piped command is checked for having exited.
+const PIPE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs a shell command with the given input on stdin and returns its stdout.
+///
+/// The command is executed through the system shell (`sh -c`, or `cmd /C` on Windows), so pipes and other
+/// shell syntax can be used. It is killed if it hasn't exited within `timeout`, or once `cancelled` is set.
+/// Returns an error with the stderr output if the command fails without producing any output.
+pub fn pipe_through_command(
+    command: &str,
+    input: &str,
+    timeout: Duration,
+    cancelled: &AtomicBool,
+) -> Result<String, String> {
+    let (shell, shell_arg) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+
+    let mut child = Command::new(shell)
+        .arg(shell_arg)
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run command: {}", e))?;
+
+    // Write stdin and read the output from separate threads to avoid deadlocking when a pipe fills up.
+    let mut stdin = child.stdin.take().ok_or("Failed to open stdin of command")?;
+    let input = input.to_string();
+    let writer = std::thread::spawn(move || {
+        // The command may exit without reading all input (e.g. `head`), so ignore broken pipes.
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let stdout_reader = read_in_background(child.stdout.take());
+    let stderr_reader = read_in_background(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            // The threads are left to finish on their own, as processes started by the command may keep the pipes
+            // open
+            Ok(None) if cancelled.load(Ordering::Relaxed) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("Command cancelled".to_string());
+            }
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Command timed out after {:?}", timeout));
+            }
+            Ok(None) => std::thread::sleep(PIPE_POLL_INTERVAL),
+            Err(e) => return Err(format!("Failed to run command: {}", e)),
+        }
+    };
+    let _ = writer.join();
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    let stdout = String::from_utf8_lossy(&stdout).to_string();
+    if !status.success() && stdout.is_empty() {
+        let stderr = String::from_utf8_lossy(&stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            format!("Command failed with {}", status)
+        } else {
+            stderr
+        });
+    }
+
+    Ok(stdout)
+}
+
+/// Reads a pipe of a child process to the end on a separate thread.
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
+/// Starts a shell command in the background without input or output, e.g. to open a URL in the browser.
+pub fn spawn_command(command: &str) -> Result<(), String> {
+    let (shell, shell_arg) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+
+    let mut child = Command::new(shell)
+        .arg(shell_arg)
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run command: {}", e))?;
+
+    // Reap the process when it exits
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Quotes a value so the shell passes it on as a single argument without interpreting it.
+pub fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Returns the user's preferred editor from `$VISUAL` or `$EDITOR`, falling back to `vi`.
+pub fn preferred_editor() -> String {
+    std::env::var("VISUAL")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| std::env::var("EDITOR").ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Builds the command for opening a file at the given (1-based) line in an editor.
+///
+/// The editor string may include arguments, e.g. `"emacs -nw"`.
+/// Most terminal editors accept `+line file`, while a few known editors need their own syntax.
+pub fn editor_command(editor: &str, path: &str, line: usize) -> Option<Command> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next()?;
+    let mut command = Command::new(program);
+    command.args(parts);
+
+    let name = std::path::Path::new(program)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(program);
+    match name {
+        "code" | "codium" | "subl" | "zed" => {
+            if matches!(name, "code" | "codium") {
+                command.arg("--wait").arg("-g");
+            }
+            command.arg(format!("{}:{}", path, line));
+        }
+        "hx" | "helix" => {
+            command.arg(format!("{}:{}", path, line));
+        }
+        _ => {
+            command.arg(format!("+{}", line)).arg(path);
+        }
+    }
+    Some(command)
+}
+
+/// Stops lazylog like Ctrl+Z does in a shell. Returns once it's continued, e.g. with `fg`.
+///
+/// Only lazylog itself is signalled, not its process group, which may include processes that didn't ask to be
+/// stopped, like the script that started lazylog when it runs without job control.
+pub fn suspend_job() -> Result<(), String> {
+    if cfg!(windows) {
+        return Err("Suspending is not supported on Windows".to_string());
+    }
+    let status = Command::new("kill")
+        .args(["-s", "TSTP", &std::process::id().to_string()])
+        .status()
+        .map_err(|e| format!("Failed to suspend: {}", e))?;
+    if !status.success() {
+        return Err(format!("Failed to suspend: kill exited with {}", status));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    const TIMEOUT: Duration = Duration::from_secs(10);
+    #[cfg(unix)]
+    static NOT_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pipe_through_command() {
+        let output = pipe_through_command(
+            "sort | uniq -c | sort -rn | head -n 1",
+            "b\na\nb\n",
+            TIMEOUT,
+            &NOT_CANCELLED,
+        )
+        .unwrap();
+        assert_eq!(output.trim(), "2 b");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_quote() {
+        let output = pipe_through_command(
+            &format!("printf %s {}", shell_quote("it's $HOME; `id`")),
+            "",
+            TIMEOUT,
+            &NOT_CANCELLED,
+        )
+        .unwrap();
+        assert_eq!(output, "it's $HOME; `id`");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pipe_through_failing_command() {
+        let result = pipe_through_command("echo oops >&2; exit 1", "line\n", TIMEOUT, &NOT_CANCELLED);
+        assert_eq!(result, Err("oops".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pipe_through_command_times_out() {
+        let start = Instant::now();
+        let result = pipe_through_command("exec sleep 10", "", Duration::from_millis(100), &NOT_CANCELLED);
+        assert_eq!(result, Err("Command timed out after 100ms".to_string()));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pipe_through_command_cancelled() {
+        let start = Instant::now();
+        let result = pipe_through_command("exec sleep 10", "", TIMEOUT, &AtomicBool::new(true));
+        assert_eq!(result, Err("Command cancelled".to_string()));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    fn args(command: &Command) -> Vec<String> {
+        command.get_args().map(|a| a.to_string_lossy().to_string()).collect()
+    }
+
+    #[test]
+    fn test_editor_command() {
+        let vim = editor_command("vim", "app.log", 42).unwrap();
+        assert_eq!(vim.get_program(), "vim");
+        assert_eq!(args(&vim), vec!["+42", "app.log"]);
+
+        let emacs = editor_command("emacs -nw", "app.log", 7).unwrap();
+        assert_eq!(args(&emacs), vec!["-nw", "+7", "app.log"]);
+
+        let code = editor_command("/usr/bin/code", "app.log", 3).unwrap();
+        assert_eq!(args(&code), vec!["--wait", "-g", "app.log:3"]);
+
+        let helix = editor_command("hx", "app.log", 3).unwrap();
+        assert_eq!(args(&helix), vec!["app.log:3"]);
+
+        assert!(editor_command("  ", "app.log", 1).is_none());
+    }
+}
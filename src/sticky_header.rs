@@ -0,0 +1,126 @@
+use std::cell::Cell;
+
+use regex::Regex;
+
+use crate::log::LogLine;
+
+/// Maximum number of lines searched backwards for a line matching the section pattern.
+pub const MAX_SECTION_LOOKBACK: usize = 100_000;
+
+/// A lookup of the section line of a log line.
+#[derive(Debug, Clone, Copy)]
+struct SectionLookup {
+    /// Generation of the log buffer the lookup was made in.
+    generation: u64,
+    /// Log line index the section line was looked up for.
+    index: usize,
+    /// The section line found.
+    section: Option<usize>,
+}
+
+/// Finds the line shown in the sticky header above the log view: the line giving the context of the top
+/// visible line.
+#[derive(Debug, Default)]
+pub struct StickyHeader {
+    /// Regex matching the first line of a section, e.g. a request start banner.
+    section_pattern: Option<Regex>,
+    /// Last section lookup, reused while scrolling as long as the lines are unchanged.
+    last_lookup: Cell<Option<SectionLookup>>,
+}
+
+impl StickyHeader {
+    pub fn new(section_pattern: Option<Regex>) -> Self {
+        Self {
+            section_pattern,
+            last_lookup: Cell::new(None),
+        }
+    }
+
+    /// Returns the log index of the line giving the context of the line at `index`.
+    ///
+    /// For a line continuing a multi-line record this is the first line of the record, otherwise the most recent
+    /// line matching the section pattern. Returns None if there is no such line above the line itself.
+    pub fn context_line(&self, lines: &[LogLine], index: usize, generation: u64) -> Option<usize> {
+        let line = lines.get(index)?;
+        if line.continuation {
+            return (0..index).rev().find(|&i| !lines[i].continuation);
+        }
+        self.section_line(lines, index, generation)
+            .filter(|&section| section < index)
+    }
+
+    /// Returns the most recent line at or before `index` matching the section pattern.
+    fn section_line(&self, lines: &[LogLine], index: usize, generation: u64) -> Option<usize> {
+        let pattern = self.section_pattern.as_ref()?;
+
+        // Scrolling only needs to look at the lines between the last lookup and the new one
+        let (scan_from, fallback) = match self.last_lookup.get() {
+            Some(last) if last.generation == generation && last.index <= index => (last.index + 1, last.section),
+            Some(last) if last.generation == generation && last.section.is_some_and(|s| s <= index) => {
+                return last.section;
+            }
+            _ => (0, None),
+        };
+
+        let scan_from = scan_from.max(index.saturating_sub(MAX_SECTION_LOOKBACK));
+        let section = (scan_from..=index)
+            .rev()
+            .find(|&i| pattern.is_match(lines[i].content()))
+            .or(fallback);
+        self.last_lookup.set(Some(SectionLookup {
+            generation,
+            index,
+            section,
+        }));
+        section
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(contents: &[&str]) -> Vec<LogLine> {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(i, content)| LogLine::new(content, i))
+            .collect()
+    }
+
+    #[test]
+    fn test_record_start_of_continuation_line() {
+        let mut lines = lines(&["2024-01-01 ERROR failed", "  at main.rs:1", "  at lib.rs:2", "next"]);
+        lines[1].continuation = true;
+        lines[2].continuation = true;
+        let header = StickyHeader::new(None);
+
+        assert_eq!(header.context_line(&lines, 2, 0), Some(0));
+        assert_eq!(header.context_line(&lines, 0, 0), None);
+        assert_eq!(header.context_line(&lines, 3, 0), None);
+    }
+
+    #[test]
+    fn test_most_recent_section_line() {
+        let lines = lines(&["boot", "=== request 1", "a", "b", "=== request 2", "c"]);
+        let header = StickyHeader::new(Some(Regex::new("^=== request").unwrap()));
+
+        assert_eq!(header.context_line(&lines, 0, 1), None);
+        assert_eq!(header.context_line(&lines, 1, 1), None);
+        assert_eq!(header.context_line(&lines, 3, 1), Some(1));
+        // Scrolling down and up again reuses the last lookup
+        assert_eq!(header.context_line(&lines, 5, 1), Some(4));
+        assert_eq!(header.context_line(&lines, 2, 1), Some(1));
+        assert_eq!(header.context_line(&lines, 0, 1), None);
+    }
+
+    #[test]
+    fn test_lookup_is_redone_for_changed_lines() {
+        let header = StickyHeader::new(Some(Regex::new("^===").unwrap()));
+        let old = lines(&["=== a", "x", "y"]);
+        assert_eq!(header.context_line(&old, 2, 1), Some(0));
+
+        let new = lines(&["x", "y", "z"]);
+        assert_eq!(header.context_line(&new, 2, 2), None);
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Time over which the rate of received lines is averaged.
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// Counts of the lines received from a stream, to show its throughput and notice when it stalls.
+#[derive(Debug, Default)]
+pub struct StreamStats {
+    /// Number of lines received.
+    total: usize,
+    /// Number of received lines that were dropped, e.g. over the maximum number of lines.
+    dropped: usize,
+    /// Time the first lines were received.
+    started: Option<Instant>,
+    /// Time the last lines were received.
+    last_received: Option<Instant>,
+    /// Number of lines received at a time, within the rate window.
+    recent: VecDeque<(Instant, usize)>,
+}
+
+impl StreamStats {
+    /// Records `count` lines received at `now`.
+    pub fn record(&mut self, count: usize, now: Instant) {
+        if count == 0 {
+            return;
+        }
+        self.total += count;
+        self.started.get_or_insert(now);
+        self.last_received = Some(now);
+        self.recent.push_back((now, count));
+        while self
+            .recent
+            .front()
+            .is_some_and(|(time, _)| now.duration_since(*time) > RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Records `count` received lines that were dropped.
+    pub fn record_dropped(&mut self, count: usize) {
+        self.dropped += count;
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns the average number of lines received per second over the last seconds.
+    pub fn lines_per_second(&self, now: Instant) -> f64 {
+        let Some(started) = self.started else {
+            return 0.0;
+        };
+        let count: usize = self
+            .recent
+            .iter()
+            .filter(|(time, _)| now.duration_since(*time) <= RATE_WINDOW)
+            .map(|(_, count)| count)
+            .sum();
+        // Average over the time since the start until the window is filled
+        let window = now.duration_since(started).clamp(Duration::from_secs(1), RATE_WINDOW);
+        count as f64 / window.as_secs_f64()
+    }
+
+    /// Returns the time since the last line was received.
+    pub fn idle_time(&self, now: Instant) -> Option<Duration> {
+        self.last_received.map(|time| now.duration_since(time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_stats() {
+        let start = Instant::now();
+        let mut stats = StreamStats::default();
+        assert_eq!(stats.lines_per_second(start), 0.0);
+        assert_eq!(stats.idle_time(start), None);
+
+        stats.record(10, start);
+        assert_eq!(stats.lines_per_second(start), 10.0);
+
+        for second in 1..=10 {
+            stats.record(20, start + Duration::from_secs(second));
+        }
+        stats.record_dropped(5);
+        let now = start + Duration::from_secs(10);
+        assert_eq!(stats.total(), 210);
+        assert_eq!(stats.dropped(), 5);
+        // Seconds 5 to 10 are within the window
+        assert_eq!(stats.lines_per_second(now), 24.0);
+
+        let later = now + Duration::from_secs(30);
+        assert_eq!(stats.lines_per_second(later), 0.0);
+        assert_eq!(stats.idle_time(later), Some(Duration::from_secs(30)));
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Time each frame of the progress spinner is shown.
+const SPINNER_FRAME_DURATION: Duration = Duration::from_millis(100);
+
+/// Progress and cancellation of work running on a background thread, shared by the filter, search, preview, pipe
+/// and load tasks.
+///
+/// The work is cancelled when dropped, so replacing a task with a new task cancels the old one.
+#[derive(Debug)]
+pub struct TaskProgress {
+    total: usize,
+    started: Instant,
+    progress: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TaskProgress {
+    /// Starts the work on a background thread. The work is given the flag set once the task is cancelled and the
+    /// counter of the units of work done out of `total`.
+    pub fn spawn<F>(total: usize, work: F) -> Self
+    where
+        F: FnOnce(&AtomicBool, &AtomicUsize) + Send + 'static,
+    {
+        let progress = Arc::new(AtomicUsize::new(0));
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        std::thread::spawn({
+            let progress = progress.clone();
+            let cancelled = cancelled.clone();
+            move || work(&cancelled, &progress)
+        });
+
+        Self {
+            total,
+            started: Instant::now(),
+            progress,
+            cancelled,
+        }
+    }
+
+    /// Returns the progress in percent.
+    pub fn percent(&self) -> usize {
+        if self.total == 0 {
+            return 100;
+        }
+        (self.progress.load(Ordering::Relaxed) * 100 / self.total).min(100)
+    }
+
+    /// Returns the index of the spinner frame to show, advancing while the task runs.
+    pub fn spinner_frame(&self, frame_count: usize) -> usize {
+        (self.started.elapsed().as_millis() / SPINNER_FRAME_DURATION.as_millis()) as usize % frame_count
+    }
+}
+
+impl Drop for TaskProgress {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_task_progress() {
+        let (started_tx, started_rx) = mpsc::channel();
+        let (done_tx, done_rx) = mpsc::channel();
+        let task = TaskProgress::spawn(4, move |cancelled, progress| {
+            progress.fetch_add(3, Ordering::Relaxed);
+            started_tx.send(()).unwrap();
+            while !cancelled.load(Ordering::Relaxed) {
+                std::thread::yield_now();
+            }
+            done_tx.send(()).unwrap();
+        });
+
+        started_rx.recv().unwrap();
+        assert_eq!(task.percent(), 75);
+        assert!(task.spinner_frame(4) < 4);
+
+        // Dropping the task cancels the work
+        drop(task);
+        done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(TaskProgress::spawn(0, |_, _| {}).percent(), 100);
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+/// Placeholder for masked variable parts of a line.
+const MASK: char = '#';
+
+/// Returns the template of a line: the line with numbers and ids masked, so recurring messages
+/// with different values share the same template.
+///
+/// Every word containing a digit is masked, where words are runs of alphanumeric characters and
+/// underscores. For example `took 12ms (req a1b2)` becomes `took # (req #)`.
+pub fn line_template(line: &str) -> String {
+    let mut template = String::with_capacity(line.len());
+    let mut word_start = None;
+
+    for (i, c) in line.char_indices() {
+        if c.is_alphanumeric() || c == '_' {
+            word_start.get_or_insert(i);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            push_word(&mut template, &line[start..i]);
+        }
+        template.push(c);
+    }
+    if let Some(start) = word_start {
+        push_word(&mut template, &line[start..]);
+    }
+
+    template
+}
+
+fn push_word(template: &mut String, word: &str) {
+    if word.chars().any(|c| c.is_ascii_digit()) {
+        template.push(MASK);
+    } else {
+        template.push_str(word);
+    }
+}
+
+/// A template with the number of lines sharing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateCount {
+    pub template: String,
+    pub count: usize,
+}
+
+/// Counts the lines per template, most frequent first.
+pub fn count_templates<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<TemplateCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for line in lines {
+        *counts.entry(line_template(line)).or_default() += 1;
+    }
+
+    let mut templates: Vec<TemplateCount> = counts
+        .into_iter()
+        .map(|(template, count)| TemplateCount { template, count })
+        .collect();
+    templates.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.template.cmp(&b.template)));
+    templates
+}
+
+/// Maximum number of noise suggestions, one per digit key.
+pub const MAX_NOISE_SUGGESTIONS: usize = 9;
+
+/// Minimum share of the lines, in percent, a template must make up to be suggested as noise.
+const NOISE_MIN_SHARE_PERCENT: usize = 1;
+
+/// Returns the most frequent templates as candidates for exclude filters. `templates` are sorted
+/// by count as returned by [`count_templates`] and `total_lines` is the number of lines counted.
+///
+/// Only templates shared by several lines and making up a noticeable share of the lines are
+/// suggested.
+pub fn noise_suggestions(templates: &[TemplateCount], total_lines: usize) -> Vec<TemplateCount> {
+    templates
+        .iter()
+        .take_while(|template| template.count > 1 && template.count * 100 >= total_lines * NOISE_MIN_SHARE_PERCENT)
+        .take(MAX_NOISE_SUGGESTIONS)
+        .cloned()
+        .collect()
+}
+
+/// Returns a regex matching the lines with the given template.
+///
+/// Masked words match any word. A literal `#` in the line is matched too, as it is
+/// indistinguishable from a mask in the template.
+pub fn template_regex(template: &str) -> String {
+    let literals: Vec<String> = template.split(MASK).map(regex::escape).collect();
+    format!("^{}$", literals.join(r"[\w#]+"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_template_masks_numbers_and_ids() {
+        assert_eq!(
+            line_template("2024-01-05 12:00:01.123 INFO request 42 took 12ms (id=a1b2c3)"),
+            "#-#-# #:#:#.# INFO request # took # (id=#)"
+        );
+        assert_eq!(
+            line_template("session 550e8400-e29b-41d4-a716-446655440000 closed"),
+            "session #-#-#-#-# closed"
+        );
+        assert_eq!(line_template("no variables here"), "no variables here");
+    }
+
+    #[test]
+    fn test_same_message_shares_template() {
+        assert_eq!(
+            line_template("worker 3 processed 120 jobs"),
+            line_template("worker 17 processed 5 jobs")
+        );
+        assert_ne!(
+            line_template("worker 3 processed 120 jobs"),
+            line_template("worker 3 failed 120 jobs")
+        );
+    }
+
+    #[test]
+    fn test_count_templates() {
+        let lines = [
+            "worker 3 done",
+            "cache miss",
+            "worker 17 done",
+            "worker 4 done",
+            "cache miss",
+        ];
+        let templates = count_templates(lines.into_iter());
+        assert_eq!(
+            templates,
+            [
+                TemplateCount {
+                    template: "worker # done".to_string(),
+                    count: 3
+                },
+                TemplateCount {
+                    template: "cache miss".to_string(),
+                    count: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_noise_suggestions() {
+        let mut lines = vec!["heartbeat 1"; 50];
+        lines.extend(["cache miss"; 10]);
+        lines.extend(["user 7 logged in"; 1]);
+        lines.extend((0..200).map(|_| "unique"));
+        let templates = count_templates(lines.iter().copied());
+
+        let suggestions = noise_suggestions(&templates, lines.len());
+        let suggested: Vec<(&str, usize)> = suggestions
+            .iter()
+            .map(|template| (template.template.as_str(), template.count))
+            .collect();
+        assert_eq!(suggested, [("unique", 200), ("heartbeat #", 50), ("cache miss", 10)]);
+
+        // Patterns below the minimum share are not noise
+        let suggestions = noise_suggestions(&templates, 2_000);
+        assert_eq!(suggestions.len(), 2);
+    }
+
+    #[test]
+    fn test_template_regex_matches_lines_of_template() {
+        let line = "12:00:01 GET /api/users/42 (1.5ms)";
+        let regex = regex::Regex::new(&template_regex(&line_template(line))).unwrap();
+        assert!(regex.is_match(line));
+        assert!(regex.is_match("13:10:59 GET /api/users/7 (20.25ms)"));
+        assert!(!regex.is_match("13:10:59 POST /api/users/7 (20.25ms)"));
+        assert!(!regex.is_match("prefix 13:10:59 GET /api/users/7 (20.25ms)"));
+    }
+}
@@ -0,0 +1,186 @@
+use ratatui::style::Color;
+
+use crate::ui::colors::{
+    DEFAULT_EVENT_BG, DEFAULT_EVENT_FG, EVENT_LIST_BG, EVENT_LIST_HIGHLIGHT_BG, EVENT_NAME_FG, EXPANDED_LINE_FG,
+    FILE_BORDER, FILE_ENABLED_FG, FILTER_DISABLED_FG, FILTER_ENABLED_FG, FILTER_MODE_BG, FILTER_MODE_FG, FOOTER_BG,
+    HELP_BG, MARK_INDICATOR_COLOR, MARK_NAME_FG, SCROLLBAR_FG, SEARCH_MODE_BG, SEARCH_MODE_FG, SELECTION_BG,
+};
+
+/// Names of the built-in themes.
+pub const THEME_NAMES: [&str; 3] = ["dark", "light", "solarized"];
+
+/// Colors of the UI elements that can be themed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub footer_bg: Color,
+    pub selection_bg: Color,
+    /// Background of the selected item in lists.
+    pub list_highlight_bg: Color,
+    pub scrollbar_fg: Color,
+    pub search_fg: Color,
+    pub search_bg: Color,
+    pub filter_fg: Color,
+    pub filter_bg: Color,
+    pub filter_enabled_fg: Color,
+    pub filter_disabled_fg: Color,
+    /// Colors of events without a configured style.
+    pub event_fg: Color,
+    pub event_bg: Color,
+    pub event_border: Color,
+    pub event_name_fg: Color,
+    pub mark_fg: Color,
+    pub mark_name_fg: Color,
+    /// Colors of the files and sessions lists.
+    pub file_border: Color,
+    pub file_fg: Color,
+    /// Dimmed text, e.g. expanded lines.
+    pub dimmed_fg: Color,
+    pub help_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// The default theme for dark terminals.
+    pub fn dark() -> Self {
+        Self {
+            footer_bg: FOOTER_BG,
+            selection_bg: SELECTION_BG,
+            list_highlight_bg: EVENT_LIST_HIGHLIGHT_BG,
+            scrollbar_fg: SCROLLBAR_FG,
+            search_fg: SEARCH_MODE_FG,
+            search_bg: SEARCH_MODE_BG,
+            filter_fg: FILTER_MODE_FG,
+            filter_bg: FILTER_MODE_BG,
+            filter_enabled_fg: FILTER_ENABLED_FG,
+            filter_disabled_fg: FILTER_DISABLED_FG,
+            event_fg: DEFAULT_EVENT_FG,
+            event_bg: DEFAULT_EVENT_BG,
+            event_border: EVENT_LIST_BG,
+            event_name_fg: EVENT_NAME_FG,
+            mark_fg: MARK_INDICATOR_COLOR,
+            mark_name_fg: MARK_NAME_FG,
+            file_border: FILE_BORDER,
+            file_fg: FILE_ENABLED_FG,
+            dimmed_fg: EXPANDED_LINE_FG,
+            help_bg: HELP_BG,
+        }
+    }
+
+    /// Theme for light terminals.
+    pub fn light() -> Self {
+        Self {
+            footer_bg: Color::Indexed(252),
+            selection_bg: Color::Indexed(153),
+            list_highlight_bg: Color::Indexed(252),
+            scrollbar_fg: Color::Indexed(250),
+            search_fg: Color::Black,
+            search_bg: Color::Indexed(220),
+            filter_fg: Color::Black,
+            filter_bg: Color::Indexed(117),
+            filter_enabled_fg: Color::Indexed(28),
+            filter_disabled_fg: Color::Indexed(245),
+            event_fg: Color::Black,
+            event_bg: Color::Indexed(153),
+            event_border: Color::Blue,
+            event_name_fg: Color::Indexed(130),
+            mark_fg: Color::Indexed(29),
+            mark_name_fg: Color::Indexed(130),
+            file_border: Color::Indexed(30),
+            file_fg: Color::Indexed(28),
+            dimmed_fg: Color::Indexed(245),
+            help_bg: Color::Indexed(254),
+        }
+    }
+
+    /// Solarized dark, using truecolor.
+    pub fn solarized() -> Self {
+        Self {
+            footer_bg: Color::Rgb(0x07, 0x36, 0x42),
+            selection_bg: Color::Rgb(0x58, 0x6e, 0x75),
+            list_highlight_bg: Color::Rgb(0x07, 0x36, 0x42),
+            scrollbar_fg: Color::Rgb(0x58, 0x6e, 0x75),
+            search_fg: Color::Rgb(0x00, 0x2b, 0x36),
+            search_bg: Color::Rgb(0xb5, 0x89, 0x00),
+            filter_fg: Color::Rgb(0x00, 0x2b, 0x36),
+            filter_bg: Color::Rgb(0x2a, 0xa1, 0x98),
+            filter_enabled_fg: Color::Rgb(0x85, 0x99, 0x00),
+            filter_disabled_fg: Color::Rgb(0x58, 0x6e, 0x75),
+            event_fg: Color::Rgb(0xfd, 0xf6, 0xe3),
+            event_bg: Color::Rgb(0x26, 0x8b, 0xd2),
+            event_border: Color::Rgb(0x26, 0x8b, 0xd2),
+            event_name_fg: Color::Rgb(0xb5, 0x89, 0x00),
+            mark_fg: Color::Rgb(0x85, 0x99, 0x00),
+            mark_name_fg: Color::Rgb(0xcb, 0x4b, 0x16),
+            file_border: Color::Rgb(0x2a, 0xa1, 0x98),
+            file_fg: Color::Rgb(0x85, 0x99, 0x00),
+            dimmed_fg: Color::Rgb(0x58, 0x6e, 0x75),
+            help_bg: Color::Rgb(0x07, 0x36, 0x42),
+        }
+    }
+
+    /// Returns the built-in theme with the given name.
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            _ => None,
+        }
+    }
+
+    /// Sets the color of a UI element by its config name. Returns false if there is no such element.
+    pub fn set(&mut self, element: &str, color: Color) -> bool {
+        let field = match element {
+            "footer_bg" => &mut self.footer_bg,
+            "selection_bg" => &mut self.selection_bg,
+            "list_highlight_bg" => &mut self.list_highlight_bg,
+            "scrollbar_fg" => &mut self.scrollbar_fg,
+            "search_fg" => &mut self.search_fg,
+            "search_bg" => &mut self.search_bg,
+            "filter_fg" => &mut self.filter_fg,
+            "filter_bg" => &mut self.filter_bg,
+            "filter_enabled_fg" => &mut self.filter_enabled_fg,
+            "filter_disabled_fg" => &mut self.filter_disabled_fg,
+            "event_fg" => &mut self.event_fg,
+            "event_bg" => &mut self.event_bg,
+            "event_border" => &mut self.event_border,
+            "event_name_fg" => &mut self.event_name_fg,
+            "mark_fg" => &mut self.mark_fg,
+            "mark_name_fg" => &mut self.mark_name_fg,
+            "file_border" => &mut self.file_border,
+            "file_fg" => &mut self.file_fg,
+            "dimmed_fg" => &mut self.dimmed_fg,
+            "help_bg" => &mut self.help_bg,
+            _ => return false,
+        };
+        *field = color;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_themes() {
+        for name in THEME_NAMES {
+            assert!(Theme::by_name(name).is_some(), "missing theme {}", name);
+        }
+        assert_eq!(Theme::by_name("Dark"), Some(Theme::default()));
+        assert_eq!(Theme::by_name("neon"), None);
+    }
+
+    #[test]
+    fn test_set_element() {
+        let mut theme = Theme::dark();
+        assert!(theme.set("footer_bg", Color::Rgb(1, 2, 3)));
+        assert_eq!(theme.footer_bg, Color::Rgb(1, 2, 3));
+        assert!(!theme.set("footer", Color::Red));
+    }
+}
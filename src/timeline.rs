@@ -0,0 +1,159 @@
+use chrono::{DateTime, Duration, TimeDelta, Utc};
+
+/// Maximum number of buckets, one per column of the timeline popup.
+pub const TIMELINE_MAX_BUCKETS: usize = 116;
+
+/// Bucket sizes the timeline can use, in seconds. Chosen so bucket boundaries fall on round times.
+const BUCKET_SECONDS: [i64; 18] = [
+    1, 2, 5, 10, 15, 30, 60, 120, 300, 600, 900, 1800, 3600, 7200, 10800, 21600, 43200, 86400,
+];
+
+/// A time interval in the timeline and the events within it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineBucket {
+    /// Start time of the bucket.
+    pub start: DateTime<Utc>,
+    /// Number of events in the bucket.
+    pub count: usize,
+    /// Log line index of the first event in the bucket.
+    pub first_line_index: Option<usize>,
+}
+
+/// Histogram of events bucketed by time.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    buckets: Vec<TimelineBucket>,
+    bucket_duration: Duration,
+}
+
+impl Timeline {
+    /// Buckets the given (log line index, timestamp) points into at most `max_buckets` buckets.
+    ///
+    /// Returns `None` if there are no points.
+    pub fn build(points: &[(usize, DateTime<Utc>)], max_buckets: usize) -> Option<Self> {
+        let first = points.iter().map(|(_, ts)| *ts).min()?;
+        let last = points.iter().map(|(_, ts)| *ts).max()?;
+        let max_buckets = max_buckets.max(2) as i64;
+
+        // Aligning the start to a bucket boundary can add one bucket, so leave room for it
+        let span = (last - first).num_seconds();
+        let seconds = BUCKET_SECONDS
+            .iter()
+            .copied()
+            .find(|&s| span / s + 2 <= max_buckets)
+            .unwrap_or_else(|| (span / (max_buckets - 1) / 86400 + 1) * 86400);
+        let bucket_duration = TimeDelta::seconds(seconds);
+
+        let start_secs = first.timestamp().div_euclid(seconds) * seconds;
+        let start = DateTime::from_timestamp(start_secs, 0)?;
+        let bucket_count = ((last - start).num_seconds() / seconds + 1) as usize;
+
+        let mut buckets: Vec<TimelineBucket> = (0..bucket_count)
+            .map(|i| TimelineBucket {
+                start: start + bucket_duration * i as i32,
+                count: 0,
+                first_line_index: None,
+            })
+            .collect();
+
+        for (line_index, ts) in points {
+            let i = ((*ts - start).num_seconds() / seconds) as usize;
+            let bucket = &mut buckets[i.min(bucket_count - 1)];
+            bucket.count += 1;
+            bucket.first_line_index = Some(bucket.first_line_index.map_or(*line_index, |l| l.min(*line_index)));
+        }
+
+        Some(Self {
+            buckets,
+            bucket_duration,
+        })
+    }
+
+    /// Returns the buckets in chronological order.
+    pub fn buckets(&self) -> &[TimelineBucket] {
+        &self.buckets
+    }
+
+    /// Returns the duration covered by each bucket.
+    pub fn bucket_duration(&self) -> Duration {
+        self.bucket_duration
+    }
+
+    /// Returns the highest event count of any bucket.
+    pub fn max_count(&self) -> usize {
+        self.buckets.iter().map(|b| b.count).max().unwrap_or(0)
+    }
+
+    /// Returns the index of the bucket containing the given timestamp.
+    pub fn bucket_at(&self, ts: DateTime<Utc>) -> Option<usize> {
+        let start = self.buckets.first()?.start;
+        if ts < start {
+            return None;
+        }
+        let i = ((ts - start).num_seconds() / self.bucket_duration.num_seconds()) as usize;
+        (i < self.buckets.len()).then_some(i)
+    }
+}
+
+/// Formats a bucket duration in a compact form, e.g. "30s", "5m", "2h" or "1d".
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.num_seconds();
+    if secs % 86400 == 0 {
+        format!("{}d", secs / 86400)
+    } else if secs % 3600 == 0 {
+        format!("{}h", secs / 3600)
+    } else if secs % 60 == 0 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ts(h: u32, m: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 15, h, m, s).unwrap()
+    }
+
+    #[test]
+    fn test_build_timeline() {
+        let points = [
+            (3, ts(10, 0, 5)),
+            (10, ts(10, 0, 7)),
+            (20, ts(10, 1, 0)),
+            (42, ts(10, 9, 59)),
+        ];
+        let timeline = Timeline::build(&points, 20).unwrap();
+
+        assert_eq!(timeline.bucket_duration(), TimeDelta::seconds(60));
+        assert_eq!(timeline.buckets().len(), 10);
+        assert_eq!(timeline.buckets()[0].start, ts(10, 0, 0));
+        assert_eq!(timeline.buckets()[0].count, 2);
+        assert_eq!(timeline.buckets()[0].first_line_index, Some(3));
+        assert_eq!(timeline.buckets()[1].first_line_index, Some(20));
+        assert_eq!(timeline.buckets()[5].count, 0);
+        assert_eq!(timeline.buckets()[9].first_line_index, Some(42));
+        assert_eq!(timeline.max_count(), 2);
+        assert_eq!(timeline.bucket_at(ts(10, 1, 30)), Some(1));
+        assert_eq!(timeline.bucket_at(ts(11, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_build_timeline_single_point() {
+        let timeline = Timeline::build(&[(0, ts(10, 0, 0))], 50).unwrap();
+        assert_eq!(timeline.buckets().len(), 1);
+        assert_eq!(timeline.buckets()[0].count, 1);
+        assert!(Timeline::build(&[], 50).is_none());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(TimeDelta::seconds(15)), "15s");
+        assert_eq!(format_duration(TimeDelta::seconds(300)), "5m");
+        assert_eq!(format_duration(TimeDelta::seconds(7200)), "2h");
+        assert_eq!(format_duration(TimeDelta::seconds(172800)), "2d");
+    }
+}
@@ -1,4 +1,5 @@
-use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike, NaiveDateTime, NaiveTime, TimeDelta, Utc};
 use regex::Regex;
 use std::sync::LazyLock;
 
@@ -112,6 +113,163 @@ fn try_syslog_format(line: &str) -> Option<DateTime<Utc>> {
     None
 }
 
+/// Where the timestamp of a [`TimestampFormat`] is in a line.
+#[derive(Debug, Clone)]
+enum TimestampLocation {
+    /// Regex finding the timestamp, in its first capture group or as the whole match.
+    Regex(Regex),
+    /// Character column the timestamp starts at, counted from 1. The timestamp ends where the format does.
+    Column(usize),
+}
+
+/// A timestamp layout: where the timestamp is in a line and how to parse it.
+#[derive(Debug, Clone)]
+pub struct TimestampFormat {
+    location: TimestampLocation,
+    /// strftime-style format of the timestamp, e.g. `%d/%b/%Y:%H:%M:%S %z`.
+    format: String,
+}
+
+impl TimestampFormat {
+    pub fn new(pattern: &str, format: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            location: TimestampLocation::Regex(Regex::new(pattern)?),
+            format: format.to_string(),
+        })
+    }
+
+    /// Creates a format for timestamps starting at the given column, counted from 1 like the column in the footer.
+    pub fn at_column(column: usize, format: &str) -> Self {
+        Self {
+            location: TimestampLocation::Column(column.max(1)),
+            format: format.to_string(),
+        }
+    }
+
+    /// Returns whether the strftime-style format is valid.
+    pub fn is_valid_format(format: &str) -> bool {
+        !format.is_empty() && !StrftimeItems::new(format).any(|item| item == Item::Error)
+    }
+
+    /// Parses the timestamp of the line. A timestamp without an offset is taken as UTC, and one without a year,
+    /// like syslog's, as in the current year.
+    pub fn parse(&self, line: &str) -> Option<DateTime<Utc>> {
+        let timestamp_str = match &self.location {
+            TimestampLocation::Regex(regex) => {
+                let captures = regex.captures(line)?;
+                captures.get(1).unwrap_or_else(|| captures.get_match()).as_str()
+            }
+            TimestampLocation::Column(column) => {
+                let (start, _) = line.char_indices().nth(column - 1)?;
+                &line[start..]
+            }
+        };
+
+        if let Ok((dt, _)) = DateTime::parse_and_remainder(timestamp_str, &self.format) {
+            return Some(dt.with_timezone(&Utc));
+        }
+        if let Ok((naive, _)) = NaiveDateTime::parse_and_remainder(timestamp_str, &self.format) {
+            return Some(DateTime::from_naive_utc_and_offset(naive, Utc));
+        }
+        let with_year = format!("{} {}", Utc::now().year(), timestamp_str);
+        NaiveDateTime::parse_and_remainder(&with_year, &format!("%Y {}", self.format))
+            .ok()
+            .map(|(naive, _)| DateTime::from_naive_utc_and_offset(naive, Utc))
+    }
+}
+
+/// Parses the timestamps of lines with the formats of the log format, falling back to the common formats.
+#[derive(Debug, Clone, Default)]
+pub struct TimestampParser {
+    formats: Vec<TimestampFormat>,
+}
+
+impl TimestampParser {
+    pub fn new(formats: Vec<TimestampFormat>) -> Self {
+        Self { formats }
+    }
+
+    /// Returns the timestamp of the line in the first format that parses it, or in one of the common formats.
+    pub fn parse(&self, line: &str) -> Option<DateTime<Utc>> {
+        self.formats
+            .iter()
+            .find_map(|format| format.parse(line))
+            .or_else(|| parse_timestamp(line))
+    }
+}
+
+/// Time to jump to, as typed in the go to time prompt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeTarget {
+    /// Full date and time, e.g. `2024-01-15 10:30:45`.
+    Absolute(DateTime<Utc>),
+    /// Time of day on the date of the reference time, e.g. `10:30` or `10:30:45.250`.
+    TimeOfDay(NaiveTime),
+    /// Offset from the reference time, e.g. `-5m`, `+1h30m` or `90s`.
+    Relative(TimeDelta),
+}
+
+impl TimeTarget {
+    /// Parses an absolute time, a time of day or a relative offset. Returns `None` if the input is none of them.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        if let Some(offset) = parse_offset(input) {
+            return Some(Self::Relative(offset));
+        }
+        for format in ["%H:%M:%S%.f", "%H:%M:%S", "%H:%M"] {
+            if let Ok(time) = NaiveTime::parse_from_str(input, format) {
+                return Some(Self::TimeOfDay(time));
+            }
+        }
+        parse_timestamp(input).map(Self::Absolute)
+    }
+
+    /// Returns the time this target refers to, relative to the given reference time. Returns `None` if an offset
+    /// moves it out of the range of representable times.
+    pub fn resolve(self, reference: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Absolute(time) => Some(time),
+            Self::TimeOfDay(time) => Some(reference.date_naive().and_time(time).and_utc()),
+            Self::Relative(offset) => reference.checked_add_signed(offset),
+        }
+    }
+}
+
+/// Parses a signed offset made of numbers with the units `ms`, `s`, `m`, `h` and `d`, e.g. `-1h30m`.
+fn parse_offset(input: &str) -> Option<TimeDelta> {
+    let (negative, mut rest) = match input.as_bytes().first()? {
+        b'-' => (true, &input[1..]),
+        b'+' => (false, &input[1..]),
+        _ => (false, input),
+    };
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut total = TimeDelta::zero();
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value: i64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let delta = match &rest[..unit] {
+            "ms" => TimeDelta::try_milliseconds(value)?,
+            "s" => TimeDelta::try_seconds(value)?,
+            "m" => TimeDelta::try_minutes(value)?,
+            "h" => TimeDelta::try_hours(value)?,
+            "d" => TimeDelta::try_days(value)?,
+            _ => return None,
+        };
+        total = total.checked_add(&delta)?;
+        rest = &rest[unit..];
+    }
+
+    Some(if negative { -total } else { total })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +305,55 @@ mod tests {
 
         assert!(dt1 < dt2);
     }
+
+    #[test]
+    fn test_timestamp_format() {
+        let format = TimestampFormat::new(r"\[([^\]]+)\]", "%d/%b/%Y:%H:%M:%S %z").unwrap();
+        let line = r#"127.0.0.1 - - [15/Jan/2024:12:30:45 +0200] "GET / HTTP/1.1" 200 512"#;
+        assert_eq!(format.parse(line), parse_timestamp("2024-01-15 10:30:45"));
+
+        let format = TimestampFormat::new(r"^\S+ \S+", "%Y-%m-%d %H:%M:%S,%3f").unwrap();
+        let parsed = format.parse("2024-01-15 10:30:45,250 - app - INFO - started").unwrap();
+        assert_eq!(parsed, parse_timestamp("2024-01-15 10:30:45.250").unwrap());
+
+        // Without a year the current year is assumed
+        let format = TimestampFormat::new(r"^\w{3} [ \d]\d \d{2}:\d{2}:\d{2}", "%b %e %H:%M:%S").unwrap();
+        let parsed = format.parse("Jan  5 10:30:45 host sshd[42]: accepted").unwrap();
+        assert_eq!(parsed.year(), Utc::now().year());
+
+        // The common formats are the fallback of a parser
+        let parser = TimestampParser::new(vec![format]);
+        assert!(parser.parse("2024-01-15T10:30:45Z other layout").is_some());
+        assert_eq!(parser.parse("no timestamp"), None);
+
+        // A timestamp at a column ends where the format does
+        let format = TimestampFormat::at_column(7, "%d.%m.%Y %H:%M:%S");
+        let parsed = format.parse("[app] 15.01.2024 10:30:45 started").unwrap();
+        assert_eq!(parsed, parse_timestamp("2024-01-15 10:30:45").unwrap());
+        assert_eq!(format.parse("[app]"), None);
+        assert!(!TimestampFormat::is_valid_format("%Y-%Q"));
+    }
+
+    #[test]
+    fn test_time_target() {
+        let reference = parse_timestamp("2024-01-15 10:30:45").unwrap();
+
+        let minus_five = TimeTarget::parse("-5m").unwrap().resolve(reference).unwrap();
+        assert_eq!(minus_five, parse_timestamp("2024-01-15 10:25:45").unwrap());
+        let later = TimeTarget::parse("+1h30m15s").unwrap().resolve(reference).unwrap();
+        assert_eq!(later, parse_timestamp("2024-01-15 12:01:00").unwrap());
+        let time_of_day = TimeTarget::parse("08:00").unwrap().resolve(reference).unwrap();
+        assert_eq!(time_of_day, parse_timestamp("2024-01-15 08:00:00").unwrap());
+        let absolute = TimeTarget::parse("2024-01-16 00:00:01")
+            .unwrap()
+            .resolve(reference)
+            .unwrap();
+        assert_eq!(absolute, parse_timestamp("2024-01-16 00:00:01").unwrap());
+        assert_eq!(TimeTarget::parse("+99999999d").unwrap().resolve(reference), None);
+
+        assert_eq!(TimeTarget::parse("5"), None);
+        assert_eq!(TimeTarget::parse("-5x"), None);
+        assert_eq!(TimeTarget::parse("-"), None);
+        assert_eq!(TimeTarget::parse("25:00"), None);
+    }
 }
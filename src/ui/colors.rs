@@ -4,8 +4,13 @@ use ratatui::style::Color;
 pub const RIGHT_ARROW: &str = "▶";
 /// Three-quarters block for mark indicator.
 pub const MARK_INDICATOR: &str = "▊";
+/// Symbols marking lines cut off by horizontal scrolling.
+pub const TRUNCATED_LEFT: &str = "←";
+pub const TRUNCATED_RIGHT: &str = "→";
 /// Symbol used to indicate an expanded line
 pub const EXPANSION_PREFIX: &str = "│ ";
+/// Frames of the spinner shown while a background task runs.
+pub const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
 /// Common colors
 pub const GRAY_COLOR: Color = Color::Indexed(237);
@@ -15,6 +20,7 @@ pub const WHITE_COLOR: Color = Color::White;
 
 // Footer
 pub const FOOTER_BG: Color = GRAY_COLOR;
+pub const COMPLETION_HINT_FG: Color = Color::DarkGray;
 
 // Scrollbar
 pub const SCROLLBAR_FG: Color = GRAY_COLOR;
@@ -22,6 +28,11 @@ pub const SCROLLBAR_SEARCH_INDICATOR: Color = SEARCH_MODE_BG;
 pub const SCROLLBAR_MARK_INDICATOR: Color = MARK_INDICATOR_COLOR;
 pub const SCROLLBAR_CRITICAL_EVENT_INDICATOR: Color = Color::Red;
 
+// Minimap
+pub const MINIMAP_EVENT_INDICATOR: Color = Color::Blue;
+pub const MINIMAP_VIEWPORT_BG: Color = Color::Indexed(237);
+pub const MINIMAP_TICK: &str = "■";
+
 // Search colors
 pub const SEARCH_MODE_FG: Color = BLACK_COLOR;
 pub const SEARCH_MODE_BG: Color = Color::Yellow;
@@ -29,7 +40,6 @@ pub const SEARCH_MODE_BG: Color = Color::Yellow;
 // Filter mode colors
 pub const FILTER_MODE_FG: Color = BLACK_COLOR;
 pub const FILTER_MODE_BG: Color = Color::Cyan;
-pub const FILTER_LIST_HIGHLIGHT_BG: Color = GRAY_COLOR;
 pub const FILTER_ENABLED_FG: Color = Color::Green;
 pub const FILTER_DISABLED_FG: Color = Color::DarkGray;
 pub const FILTER_CRITICAL_FG: Color = Color::Red;
@@ -39,16 +49,62 @@ pub const DEFAULT_EVENT_FG: Color = WHITE_COLOR;
 pub const DEFAULT_EVENT_BG: Color = Color::Blue;
 pub const EVENT_LIST_BG: Color = Color::Blue;
 pub const EVENT_LIST_HIGHLIGHT_BG: Color = GRAY_COLOR;
+pub const EVENT_TAGGED_FG: Color = Color::LightYellow;
 pub const EVENT_NAME_FG: Color = Color::Yellow;
 pub const EVENT_NAME_CRITICAL_FG: Color = Color::Red;
 pub const EVENT_NAME_CUSTOM_DEFAULT_FG: Color = Color::Green;
 pub const EVENT_LINE_PREVIEW: Color = Color::Gray;
 pub const EVENT_FILTERED_FG: Color = Color::DarkGray;
 
+// Severity badges in the footer
+pub const SEVERITY_ERROR_FG: Color = Color::LightRed;
+pub const SEVERITY_WARN_FG: Color = Color::Yellow;
+pub const SEVERITY_INFO_FG: Color = Color::LightBlue;
+pub const SEVERITY_DEBUG_FG: Color = Color::Gray;
+pub const SEVERITY_NONE_FG: Color = Color::DarkGray;
+
+// Timeline
+pub const TIMELINE_BORDER: Color = EVENT_LIST_BG;
+pub const TIMELINE_BAR_FG: Color = Color::Yellow;
+pub const TIMELINE_SELECTED_BAR_FG: Color = BRIGHT_WHITE_COLOR;
+pub const TIMELINE_SELECTED_BG: Color = GRAY_COLOR;
+pub const TIMELINE_AXIS_FG: Color = Color::DarkGray;
+
+// Watch expressions
+pub const WATCH_BORDER: Color = Color::Cyan;
+pub const WATCH_SPARKLINE_FG: Color = Color::Cyan;
+pub const WATCH_RANGE_FG: Color = Color::DarkGray;
+
+// Alerts
+pub const ALERT_BORDER: Color = Color::LightRed;
+pub const ALERT_FG: Color = WHITE_COLOR;
+pub const ALERT_TITLE_BG: Color = Color::Red;
+pub const ALERT_TIME_FG: Color = Color::Gray;
+pub const ALERT_NAME_FG: Color = Color::LightRed;
+
+// Correlation view
+pub const CORRELATION_ID_FG: Color = Color::LightCyan;
+
+// Restarts
+pub const RESTART_SEPARATOR_FG: Color = Color::LightBlue;
+
+// Hex view
+pub const HEX_OFFSET_FG: Color = Color::DarkGray;
+pub const HEX_BINARY_FG: Color = Color::LightRed;
+
+// Debug overlay
+pub const DEBUG_BORDER: Color = Color::Magenta;
+pub const DEBUG_LABEL_FG: Color = Color::Gray;
+
+// Notes
+pub const NOTES_BORDER: Color = MARK_INDICATOR_COLOR;
+pub const NOTES_FG: Color = WHITE_COLOR;
+pub const NOTES_REFERENCE_FG: Color = MARK_NAME_FG;
+pub const NOTES_MISSING_REFERENCE_FG: Color = Color::DarkGray;
+
 // Marks
 pub const MARK_MODE_FG: Color = Color::White;
 pub const MARK_MODE_BG: Color = MARK_INDICATOR_COLOR;
-pub const MARK_LIST_HIGHLIGHT_BG: Color = GRAY_COLOR;
 pub const MARK_INDICATOR_COLOR: Color = Color::Indexed(29);
 pub const MARK_NAME_FG: Color = Color::Yellow;
 pub const MARK_LINE_PREVIEW: Color = Color::Gray;
@@ -74,6 +130,14 @@ pub const EXPLORER_DIR_FG: Color = Color::Cyan;
 pub const EXPLORER_HIGHLIGHT_ITEM_FG: Color = WHITE_COLOR;
 pub const EXPLORER_HIGHLIGHT_DIR_FG: Color = FILE_BORDER;
 
+// Regex tester
+pub const REGEX_TESTER_BORDER: Color = Color::Magenta;
+pub const REGEX_TESTER_MATCH_FG: Color = BLACK_COLOR;
+pub const REGEX_TESTER_MATCH_BG: Color = Color::Magenta;
+pub const REGEX_TESTER_CAPTURE_FG: Color = Color::Cyan;
+pub const REGEX_TESTER_LINE_NUMBER_FG: Color = Color::DarkGray;
+pub const REGEX_TESTER_HIGHLIGHT_BG: Color = GRAY_COLOR;
+
 // Messages
 pub const MESSAGE_INFO_FG: Color = WHITE_COLOR;
 pub const MESSAGE_BORDER: Color = Color::Blue;
@@ -81,6 +145,26 @@ pub const ERROR_FG: Color = Color::Red;
 pub const ERROR_BORDER: Color = Color::Red;
 pub const MESSAGE_FATAL_FG: Color = Color::LightRed;
 pub const FATAL_BORDER: Color = Color::LightRed;
+pub const FILTER_PROGRESS_FG: Color = Color::Cyan;
+pub const STREAM_STDOUT_FG: Color = Color::DarkGray;
+pub const STREAM_STDERR_FG: Color = Color::LightRed;
+pub const FILTER_PROGRESS_BORDER: Color = Color::Cyan;
+
+// Log levels
+pub const LEVELS_BORDER: Color = WHITE_COLOR;
+pub const LEVEL_TRACE_FG: Color = Color::DarkGray;
+pub const LEVEL_DEBUG_FG: Color = Color::Blue;
+pub const LEVEL_INFO_FG: Color = Color::Green;
+pub const LEVEL_WARN_FG: Color = Color::Yellow;
+pub const LEVEL_ERROR_FG: Color = Color::LightRed;
+pub const LEVEL_HIDDEN_FG: Color = Color::DarkGray;
+
+// Columns
+pub const COLUMNS_BORDER: Color = WHITE_COLOR;
+pub const COLUMN_HIDDEN_FG: Color = Color::DarkGray;
+
+// Split view
+pub const SPLIT_FOCUS_FG: Color = Color::LightBlue;
 
 // Selection colors
 pub const SELECTION_BG: Color = Color::LightBlue;
@@ -1,14 +1,22 @@
-use crate::app::App;
+use crate::app::{App, ViewState};
+use crate::level::LogLevel;
 use crate::ui::MAX_PATH_LENGTH;
-use crate::ui::colors::{FILTER_MODE_BG, FILTER_MODE_FG, FOOTER_BG, SEARCH_MODE_BG, SEARCH_MODE_FG};
+use crate::ui::colors::{
+    COMPLETION_HINT_FG, SEVERITY_DEBUG_FG, SEVERITY_ERROR_FG, SEVERITY_INFO_FG, SEVERITY_NONE_FG, SEVERITY_WARN_FG,
+    SPINNER_FRAMES,
+};
 use num_format::{Locale, ToFormattedString};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
-    style::{Modifier, Style},
-    text::Line,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Paragraph, Widget},
 };
+use std::time::{Duration, Instant};
+
+/// Time without new lines after which the footer shows how long the stream has been quiet.
+const STREAM_IDLE_THRESHOLD: Duration = Duration::from_secs(2);
 
 impl App {
     /// Returns current line information (progression in the file).
@@ -89,64 +97,234 @@ impl App {
             } else {
                 path.to_string()
             }
+        } else if let Some(child_process) = &self.child_process {
+            let command_line = child_process.command_line();
+            if command_line.chars().count() > max_width {
+                let prefix: String = command_line.chars().take(max_width.saturating_sub(3)).collect();
+                format!("$ {}...", prefix)
+            } else {
+                format!("$ {}", command_line)
+            }
         } else {
             "".to_string()
         };
 
         let mut left_parts = vec![file_name];
+        if let Some(child_process) = &self.child_process {
+            left_parts.push(format!("[{}]", child_process.status()));
+        }
         if self.streaming_paused && self.log_buffer.streaming {
             left_parts.push("PAUSED".to_string());
         }
+        if let Some(new_lines) = self.frozen_new_line_count().filter(|_| self.log_buffer.streaming) {
+            left_parts.push(format!(
+                "FROZEN +{} new lines",
+                new_lines.to_formatted_string(&Locale::en_DK)
+            ));
+        }
         if self.viewport.follow_mode && self.log_buffer.streaming {
             left_parts.push("| follow".to_string());
         }
+        if self.log_buffer.streaming {
+            left_parts.push(self.format_stream_stats());
+        }
         if self.viewport.center_cursor_mode {
             left_parts.push("| center".to_string());
         }
         if self.show_marked_lines_only {
             left_parts.push("| marked only".to_string());
         }
+        let hidden_count = self.log_buffer.hidden_count();
+        if hidden_count > 0 {
+            left_parts.push(format!("| {} hidden", hidden_count.to_formatted_string(&Locale::en_DK)));
+        }
+        if self.search.reveals_hidden_matches() {
+            left_parts.push("| search: all lines".to_string());
+        }
+        if let Some(min_level) = self.min_level {
+            left_parts.push(format!("| level >= {}", min_level));
+        }
+        if self.viewport.horizontal_offset > 0 {
+            left_parts.push(format!(
+                "| col {}/{}",
+                self.viewport.horizontal_offset + 1,
+                self.selected_row_width.get().unwrap_or(0)
+            ));
+        }
+        if let Some(event_scope) = &self.event_scope {
+            left_parts.push(format!("| events: {}", event_scope));
+        }
+        if self.column_view.is_enabled() {
+            match self.column_view.sort() {
+                Some(sort) => left_parts.push(format!(
+                    "| columns, sorted by {} {}",
+                    self.column_view.columns()[sort.column].name,
+                    if sort.descending { "↓" } else { "↑" }
+                )),
+                None => left_parts.push("| columns".to_string()),
+            }
+        }
         let left = Line::from(left_parts.join(" "));
         let middle = Line::from("F1:View Help").centered();
 
         let (current_match, visible_matches, total_matches) = self.search.get_match_info();
         let progression_text = self.format_progression_text();
+        let pipe_status = self
+            .pipe_task
+            .as_ref()
+            .map(|task| {
+                format!(
+                    "{} running command | ",
+                    SPINNER_FRAMES[task.progress().spinner_frame(SPINNER_FRAMES.len())]
+                )
+            })
+            .unwrap_or_default();
+        let load_status = self
+            .load_task
+            .as_ref()
+            .map(|task| {
+                format!(
+                    "{} loading {}% | ",
+                    SPINNER_FRAMES[task.progress().spinner_frame(SPINNER_FRAMES.len())],
+                    task.progress().percent()
+                )
+            })
+            .unwrap_or_default();
+        let search_status = pipe_status
+            + &load_status
+            + &self
+                .search_task
+                .as_ref()
+                .map(|task| {
+                    format!(
+                        "{} searching {}% | ",
+                        SPINNER_FRAMES[task.progress().spinner_frame(SPINNER_FRAMES.len())],
+                        task.progress().percent()
+                    )
+                })
+                .unwrap_or_default();
 
-        let right = if visible_matches > 0 {
+        let right_text = if visible_matches > 0 {
             let filtered_count = total_matches.saturating_sub(visible_matches);
             if filtered_count > 0 {
-                Line::from(format!(
-                    "{}/{} ({}) | {} ",
-                    current_match, visible_matches, filtered_count, progression_text
-                ))
-                .right_aligned()
+                format!(
+                    "{}{}/{} ({} hidden by filters) | {} ",
+                    search_status, current_match, visible_matches, filtered_count, progression_text
+                )
             } else {
-                Line::from(format!("{}/{} | {} ", current_match, visible_matches, progression_text)).right_aligned()
+                format!(
+                    "{}{}/{} | {} ",
+                    search_status, current_match, visible_matches, progression_text
+                )
             }
         } else {
-            Line::from(progression_text + " ").right_aligned()
+            format!("{}{} ", search_status, progression_text)
         };
 
+        // Severity badges of the events, e.g. `E:12 W:134`, in front of the position
+        let severity_counts = self.event_tracker.severity_counts();
+        let mut right_spans = Vec::new();
+        for (severity, count) in &severity_counts {
+            let color = if *count > 0 {
+                severity_color(*severity)
+            } else {
+                SEVERITY_NONE_FG
+            };
+            right_spans.push(Span::styled(
+                format!(
+                    "{}:{}",
+                    &severity.name()[..1],
+                    count.to_formatted_string(&Locale::en_DK)
+                ),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+            right_spans.push(Span::raw(" "));
+        }
+        if !right_spans.is_empty() {
+            right_spans.push(Span::raw("| "));
+        }
+        right_spans.push(Span::raw(right_text));
+        let right = Line::from(right_spans).right_aligned();
+
+        // Remember where the badges are so they can be clicked
+        let mut x = area.right().saturating_sub(right.width() as u16).max(area.x);
+        let badge_areas = severity_counts
+            .iter()
+            .zip(right.spans.iter().step_by(2))
+            .map(|((severity, _), span)| {
+                let width = span.width() as u16;
+                let badge_area = Rect::new(x, area.y, width, 1).intersection(area);
+                x += width + 1;
+                (badge_area, *severity)
+            })
+            .collect();
+        self.severity_badge_areas.replace(badge_areas);
+
         let footer = Block::default()
             .title_bottom(left)
             .title_bottom(middle)
             .title_bottom(right)
-            .style(Style::default().bg(FOOTER_BG));
+            .style(Style::default().bg(self.theme.footer_bg));
         footer.render(area, buf);
     }
 
+    /// Formats the throughput of the stream: lines per second, received and dropped lines, and the time
+    /// since the last line if the stream is quiet.
+    fn format_stream_stats(&self) -> String {
+        let now = Instant::now();
+        let stats = &self.stream_stats;
+        let mut text = format!(
+            "| {:.0}/s, {} received",
+            stats.lines_per_second(now),
+            stats.total().to_formatted_string(&Locale::en_DK)
+        );
+        if stats.dropped() > 0 {
+            text.push_str(&format!(
+                ", {} dropped",
+                stats.dropped().to_formatted_string(&Locale::en_DK)
+            ));
+        }
+        if let Some(idle) = stats.idle_time(now).filter(|idle| *idle >= STREAM_IDLE_THRESHOLD) {
+            text.push_str(&format!(", idle {}", format_idle_time(idle)));
+        }
+        text
+    }
+
+    /// Formats the number of lines the filter or search being typed would give, or the progress of counting them.
+    fn format_preview_text(&self, singular: &str, plural: &str) -> String {
+        if let Some(task) = &self.preview_task {
+            return format!(
+                "{} counting {}% | ",
+                SPINNER_FRAMES[task.progress().spinner_frame(SPINNER_FRAMES.len())],
+                task.progress().percent()
+            );
+        }
+        match self.preview_count() {
+            Some(count) => format!(
+                "{} {} | ",
+                count.to_formatted_string(&Locale::en_DK),
+                if count == 1 { singular } else { plural }
+            ),
+            None => String::new(),
+        }
+    }
+
     pub(super) fn render_search_footer(&self, area: Rect, buf: &mut Buffer) {
         let search_prompt = Line::from(format!("{}{}", self.get_input_prefix(), self.input.value())).left_aligned();
-        let progression_text = self.format_progression_text();
+        let progression_text = self.format_preview_text("match", "matches") + &self.format_progression_text();
         let progression = Line::from(progression_text + " ").right_aligned();
+        let hint = Line::from(self.completion_hint().unwrap_or_default())
+            .centered()
+            .style(Style::default().fg(COMPLETION_HINT_FG));
 
         let search_bar = Block::default()
             .title_bottom(search_prompt)
+            .title_bottom(hint)
             .title_bottom(progression)
             .style(
                 Style::default()
-                    .fg(SEARCH_MODE_FG)
-                    .bg(SEARCH_MODE_BG)
+                    .fg(self.theme.search_fg)
+                    .bg(self.theme.search_bg)
                     .add_modifier(Modifier::BOLD),
             );
 
@@ -155,16 +333,20 @@ impl App {
 
     pub(super) fn render_filter_footer(&self, area: Rect, buf: &mut Buffer) {
         let filter_prompt = Line::from(format!("{}{}", self.get_input_prefix(), self.input.value())).left_aligned();
-        let progression_text = self.format_progression_text();
+        let progression_text = self.format_preview_text("line", "lines") + &self.format_progression_text();
         let progression = Line::from(progression_text + " ").right_aligned();
+        let hint = Line::from(self.completion_hint().unwrap_or_default())
+            .centered()
+            .style(Style::default().fg(COMPLETION_HINT_FG));
 
         let filter_bar = Block::default()
             .title_bottom(filter_prompt)
+            .title_bottom(hint)
             .title_bottom(progression)
             .style(
                 Style::default()
-                    .fg(FILTER_MODE_FG)
-                    .bg(FILTER_MODE_BG)
+                    .fg(self.theme.filter_fg)
+                    .bg(self.theme.filter_bg)
                     .add_modifier(Modifier::BOLD),
             );
 
@@ -174,19 +356,38 @@ impl App {
     pub(super) fn render_goto_line_footer(&self, area: Rect, buf: &mut Buffer) {
         let search_prompt = format!("{}{}", self.get_input_prefix(), self.input.value());
         let search_bar = Paragraph::new(search_prompt)
-            .style(Style::default().bg(FOOTER_BG))
+            .style(Style::default().bg(self.theme.footer_bg))
             .alignment(Alignment::Left);
         search_bar.render(area, buf);
+
+        if self.view_state == ViewState::GotoLineMode {
+            let hint = Line::styled(
+                "line | 50% | +100 | -100 | $ (last) ",
+                Style::default().fg(COMPLETION_HINT_FG),
+            );
+            hint.right_aligned().render(area, buf);
+        }
     }
 
     pub(super) fn render_selection_footer(&self, area: Rect, buf: &mut Buffer) {
         let selection_text = if let Some((start, end)) = self.get_selection_range() {
             let num_lines = end - start + 1;
-            format!(
-                "-- VISUAL -- {} line{} selected ('y' to copy, Esc to cancel)",
-                num_lines,
-                if num_lines == 1 { "" } else { "s" }
-            )
+            if let Some((first, last)) = self.get_block_columns() {
+                let num_columns = last - first + 1;
+                format!(
+                    "-- VISUAL BLOCK -- {} line{} x {} column{} selected ('y' to copy, Esc to cancel)",
+                    num_lines,
+                    if num_lines == 1 { "" } else { "s" },
+                    num_columns,
+                    if num_columns == 1 { "" } else { "s" }
+                )
+            } else {
+                format!(
+                    "-- VISUAL -- {} line{} selected ('y' to copy, Esc to cancel)",
+                    num_lines,
+                    if num_lines == 1 { "" } else { "s" }
+                )
+            }
         } else {
             "-- VISUAL --".to_string()
         };
@@ -198,8 +399,28 @@ impl App {
         let selection_bar = Block::default()
             .title_bottom(selection_prompt)
             .title_bottom(progression)
-            .style(Style::default().bg(FOOTER_BG));
+            .style(Style::default().bg(self.theme.footer_bg));
 
         selection_bar.render(area, buf);
     }
 }
+
+/// Returns the color of a severity badge.
+fn severity_color(severity: LogLevel) -> Color {
+    match severity {
+        LogLevel::Error => SEVERITY_ERROR_FG,
+        LogLevel::Warn => SEVERITY_WARN_FG,
+        LogLevel::Info => SEVERITY_INFO_FG,
+        LogLevel::Debug | LogLevel::Trace => SEVERITY_DEBUG_FG,
+    }
+}
+
+/// Formats the time since the last streamed line, e.g. `42s`, `3m` or `1h05m`.
+fn format_idle_time(idle: Duration) -> String {
+    let seconds = idle.as_secs();
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m{:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h{:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
@@ -0,0 +1,77 @@
+use super::colors::{EVENT_LINE_PREVIEW, WHITE_COLOR};
+use crate::app::App;
+use crate::ui::scrollable_list::ScrollableList;
+use crate::utils::wrap_columns;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+/// Maximum number of rows used to show the wrapped line above the details.
+const MAX_WRAPPED_ROWS: usize = 8;
+
+impl App {
+    /// Returns the height of the line inspector popup for the given width.
+    pub(super) fn line_inspector_height(&self, width: u16) -> u16 {
+        let wrapped_rows = wrap_columns(&self.line_inspector.content, width.saturating_sub(2) as usize).len();
+        // Borders, the wrapped line and the separator above the details
+        (wrapped_rows.min(MAX_WRAPPED_ROWS) + self.line_inspector.details.len() + 3) as u16
+    }
+
+    pub(super) fn render_line_inspector(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Line Inspector ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" Enter/y: copy value ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let wrapped: Vec<Line> = wrap_columns(&self.line_inspector.content, inner_area.width as usize)
+            .into_iter()
+            .map(Line::from)
+            .collect();
+        let [line_area, details_area] = Layout::vertical([
+            Constraint::Length(wrapped.len().min(MAX_WRAPPED_ROWS) as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(inner_area);
+
+        Paragraph::new(wrapped).render(line_area, buf);
+
+        let label_width = self.line_inspector.label_width();
+        let items: Vec<Line> = self
+            .line_inspector
+            .details
+            .iter()
+            .map(|detail| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:<width$}  ", detail.label, width = label_width),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(detail.value.as_str(), Style::default().fg(EVENT_LINE_PREVIEW)),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.inspector_list_state.selected_index(),
+                self.inspector_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(details_area, buf, Block::default().borders(Borders::TOP));
+
+        self.inspector_list_state.set_viewport_height(list_area.height as usize);
+    }
+}
@@ -1,26 +1,105 @@
 use super::colors::{
-    EVENT_LINE_PREVIEW, EVENT_LIST_BG, EVENT_LIST_HIGHLIGHT_BG, EVENT_NAME_FG, FILTER_DISABLED_FG, FILTER_ENABLED_FG,
-    FILTER_LIST_HIGHLIGHT_BG, FILTER_MODE_BG, MARK_LINE_PREVIEW, MARK_LIST_HIGHLIGHT_BG, MARK_MODE_BG, MARK_NAME_FG,
-    OPTION_DISABLED_FG, OPTION_ENABLED_FG, RIGHT_ARROW, WHITE_COLOR,
+    EVENT_LINE_PREVIEW, EVENT_TAGGED_FG, MARK_LINE_PREVIEW, MARK_MODE_BG, OPTION_DISABLED_FG, OPTION_ENABLED_FG,
+    RIGHT_ARROW, WHITE_COLOR,
 };
+use crate::app::{App, CustomEventStep, Overlay};
+use crate::clipboard::{CopyFormat, CopySource};
+use crate::debug_stats::format_bytes;
 use crate::event_mark_view::EventMarkView;
 use crate::filter::ActiveFilterMode;
-use crate::ui::MAX_PATH_LENGTH;
+use crate::format::FormatChoice;
+use crate::global_history::QueryKind;
+use crate::hex::{BYTES_PER_ROW, ascii_char};
+use crate::interval::format_elapsed;
+use crate::level::LogLevel;
+use crate::options::AppOption;
+use crate::registers::RegisterAction;
 use crate::ui::colors::{
-    EVENT_FILTERED_FG, EVENT_NAME_CRITICAL_FG, EVENT_NAME_CUSTOM_DEFAULT_FG, FILE_BORDER, FILE_DISABLED_FG,
-    FILE_ENABLED_FG, FILTER_CRITICAL_FG,
+    ALERT_NAME_FG, ALERT_TIME_FG, COLUMN_HIDDEN_FG, COLUMNS_BORDER, CORRELATION_ID_FG, HEX_BINARY_FG, HEX_OFFSET_FG,
+    LEVEL_DEBUG_FG, LEVEL_ERROR_FG, LEVEL_HIDDEN_FG, LEVEL_INFO_FG, LEVEL_TRACE_FG, LEVEL_WARN_FG, LEVELS_BORDER,
+    RESTART_SEPARATOR_FG,
+};
+use crate::ui::colors::{
+    EVENT_FILTERED_FG, EVENT_NAME_CRITICAL_FG, EVENT_NAME_CUSTOM_DEFAULT_FG, FILE_DISABLED_FG, FILTER_CRITICAL_FG,
 };
 use crate::ui::scrollable_list::ScrollableList;
-use crate::{app::App, ui::colors::MARK_INDICATOR_COLOR};
+use crate::ui::{MAX_PATH_LENGTH, popup_area};
+use num_format::{Locale, ToFormattedString};
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph, StatefulWidget, Widget},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Padding, Paragraph, StatefulWidget, Tabs, Widget},
 };
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Size bounds of the events and marks popups.
+const LIST_POPUP_MIN_WIDTH: u16 = 40;
+const LIST_POPUP_MAX_WIDTH: u16 = 118;
+const LIST_POPUP_MIN_HEIGHT: u16 = 5;
+const LIST_POPUP_MAX_HEIGHT: u16 = 35;
+/// Title of the options popup.
+const OPTIONS_TITLE: &str = " Display Options ";
+/// Default number of lines shown before and after the selected event in the preview of the events view.
+const EVENT_PREVIEW_LINES: usize = 3;
+
+/// Returns the color used for a log level.
+pub(super) fn level_color(level: LogLevel) -> Color {
+    match level {
+        LogLevel::Trace => LEVEL_TRACE_FG,
+        LogLevel::Debug => LEVEL_DEBUG_FG,
+        LogLevel::Info => LEVEL_INFO_FG,
+        LogLevel::Warn => LEVEL_WARN_FG,
+        LogLevel::Error => LEVEL_ERROR_FG,
+    }
+}
+
+/// Returns the popup (width, height) fitting a list with the given number of entries and widest entry.
+fn list_popup_size(entry_count: usize, entry_width: usize) -> (u16, u16) {
+    // Borders, highlight symbol, scrollbar and a margin column
+    let width = (entry_width + 5).min(LIST_POPUP_MAX_WIDTH as usize) as u16;
+    let height = (entry_count + 2).min(LIST_POPUP_MAX_HEIGHT as usize) as u16;
+    (width.max(LIST_POPUP_MIN_WIDTH), height.max(LIST_POPUP_MIN_HEIGHT))
+}
 
 impl App {
+    /// Returns the width of the longest of the given lines, which is only measured again when the lines or the
+    /// buffer generation changed since the last call.
+    fn max_content_width(&self, line_indices: impl Iterator<Item = usize>) -> usize {
+        let line_indices: Vec<usize> = line_indices.collect();
+        let mut hasher = DefaultHasher::new();
+        line_indices.hash(&mut hasher);
+        let key = (self.log_buffer.generation(), hasher.finish());
+        if let Some((cached_key, width)) = self.list_content_width.get()
+            && cached_key == key
+        {
+            return width;
+        }
+
+        let width = line_indices
+            .iter()
+            .filter_map(|&line_index| self.log_buffer.get_line(line_index))
+            .map(|log_line| log_line.content().chars().count())
+            .max()
+            .unwrap_or(0);
+        self.list_content_width.set(Some((key, width)));
+        width
+    }
+
+    /// Returns the width of the options popup fitting the longest option, its checkbox, the highlight symbol and
+    /// the borders.
+    pub(super) fn options_width(&self) -> u16 {
+        let longest = self
+            .options
+            .iter()
+            .map(|option| option.get_description().chars().count())
+            .max()
+            .unwrap_or(0);
+        (longest + "[x] ".len() + RIGHT_ARROW.chars().count() + 2).max(OPTIONS_TITLE.len() + 2) as u16
+    }
+
     pub(super) fn render_options(&self, area: Rect, buf: &mut Buffer) {
         Clear.render(area, buf);
 
@@ -48,7 +127,7 @@ impl App {
         let options_list = List::new(items)
             .block(
                 Block::default()
-                    .title(" Display Options ")
+                    .title(OPTIONS_TITLE)
                     .title_alignment(Alignment::Center)
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
@@ -60,6 +139,191 @@ impl App {
         StatefulWidget::render(options_list, area, buf, &mut list_state);
     }
 
+    pub(super) fn render_levels_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let selected_min_level = self
+            .levels_list_state
+            .selected_index()
+            .checked_sub(1)
+            .map(|i| LogLevel::ALL[i]);
+        let no_level_count = self.level_counts[LogLevel::ALL.len()];
+        let total: usize = self.level_counts.iter().sum();
+
+        let mut items = vec![Line::from(format!(
+            "{:<8}{:>14}",
+            "All",
+            total.to_formatted_string(&Locale::en_DK)
+        ))];
+        for level in LogLevel::ALL {
+            let count = self.level_counts[level as usize].to_formatted_string(&Locale::en_DK);
+            let hidden = selected_min_level.is_some_and(|min_level| level < min_level);
+            let fg = if hidden { LEVEL_HIDDEN_FG } else { level_color(level) };
+            items.push(Line::from(format!("{:<8}{:>14}", level.name(), count)).style(Style::default().fg(fg)));
+        }
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.levels_list_state.selected_index()));
+
+        let levels_list = List::new(items)
+            .block(
+                Block::default()
+                    .title(" Log Levels ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(
+                        Line::from(format!(
+                            " {} without level ",
+                            no_level_count.to_formatted_string(&Locale::en_DK)
+                        ))
+                        .centered(),
+                    )
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(LEVELS_BORDER)),
+            )
+            .highlight_symbol(RIGHT_ARROW)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+
+        StatefulWidget::render(levels_list, area, buf, &mut list_state);
+    }
+
+    pub(super) fn render_copy_format_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let title = match self.copy_source {
+            CopySource::Selection => " Copy Selection As ",
+            CopySource::View => " Copy View As ",
+            CopySource::Marks => " Copy Marked Lines As ",
+        };
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let items: Vec<Line> = CopyFormat::ALL
+            .iter()
+            .map(|format| Line::from(format!(" {}", format.name())))
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.copy_format_list_state.selected_index(),
+                self.copy_format_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.copy_format_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_event_scope_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Jump Between ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let event_stats = self.event_tracker.get_event_stats();
+        let name_width = event_stats
+            .iter()
+            .map(|es| es.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(10);
+        let total: usize = event_stats.iter().map(|es| es.count).sum();
+
+        let mut items = vec![Line::from(format!(
+            " {:<width$}  {:>10}",
+            "All events",
+            total.to_formatted_string(&Locale::en_DK),
+            width = name_width
+        ))];
+        for es in &event_stats {
+            let style = if self.event_scope.as_ref() == Some(&es.name) {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            items.push(
+                Line::from(format!(
+                    " {:<width$}  {:>10}",
+                    es.name,
+                    es.count.to_formatted_string(&Locale::en_DK),
+                    width = name_width
+                ))
+                .style(style),
+            );
+        }
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.event_scope_list_state.selected_index(),
+                self.event_scope_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.event_scope_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_columns_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let sort = self.column_view.sort();
+        let items: Vec<Line> = self
+            .column_view
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let checkbox = if column.visible { "[x]" } else { "[ ]" };
+                let sort_indicator = match sort {
+                    Some(sort) if sort.column == i && sort.descending => " ↓",
+                    Some(sort) if sort.column == i => " ↑",
+                    _ => "",
+                };
+                let content = format!("{} {}{}", checkbox, column.name, sort_indicator);
+                if column.visible {
+                    Line::from(content).style(Style::default().fg(OPTION_ENABLED_FG))
+                } else {
+                    Line::from(content).style(Style::default().fg(COLUMN_HIDDEN_FG))
+                }
+            })
+            .collect();
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.columns_list_state.selected_index()));
+
+        let title_bottom = if self.column_view.is_enabled() {
+            " c: show lines | s: sort "
+        } else {
+            " c: show columns | s: sort "
+        };
+        let columns_list = List::new(items)
+            .block(
+                Block::default()
+                    .title(" Columns ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(title_bottom).centered())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(COLUMNS_BORDER)),
+            )
+            .highlight_symbol(RIGHT_ARROW)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+
+        StatefulWidget::render(columns_list, area, buf, &mut list_state);
+    }
+
     pub(super) fn render_filter_list(&self, area: Rect, buf: &mut Buffer) {
         Clear.render(area, buf);
 
@@ -71,7 +335,7 @@ impl App {
             .title_style(Style::default().bold())
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(FILTER_MODE_BG));
+            .border_style(Style::default().fg(self.theme.filter_bg));
 
         if filter_patterns.is_empty() {
             let popup = Paragraph::new("No filters configured")
@@ -89,13 +353,23 @@ impl App {
                     ActiveFilterMode::Exclude => "EX",
                 };
                 let case_str = if pattern.case_sensitive { "Aa" } else { "aa" };
-
-                let content = format!(" [{}] [{}] {}", mode_str, case_str, pattern.pattern);
+                let regex_str = if pattern.is_regex() { "[re] " } else { "" };
+                let whole_word_str = if pattern.whole_word { "[word] " } else { "" };
+                let field_str = if pattern.field.is_some() { "[field] " } else { "" };
+                let file_str = pattern
+                    .file
+                    .as_ref()
+                    .map_or(String::new(), |file| format!("[{}] ", file));
+
+                let content = format!(
+                    " [{}] [{}] {}{}{}{}{}",
+                    mode_str, case_str, regex_str, whole_word_str, field_str, file_str, pattern.pattern
+                );
 
                 if pattern.enabled {
-                    Line::from(content).style(Style::default().fg(FILTER_ENABLED_FG))
+                    Line::from(content).style(Style::default().fg(self.theme.filter_enabled_fg))
                 } else {
-                    Line::from(content).style(Style::default().fg(FILTER_DISABLED_FG))
+                    Line::from(content).style(Style::default().fg(self.theme.filter_disabled_fg))
                 }
             })
             .collect();
@@ -118,7 +392,7 @@ impl App {
             .highlight_symbol(RIGHT_ARROW)
             .highlight_style(
                 Style::default()
-                    .bg(FILTER_LIST_HIGHLIGHT_BG)
+                    .bg(self.theme.list_highlight_bg)
                     .add_modifier(Modifier::BOLD),
             );
 
@@ -136,7 +410,7 @@ impl App {
                     .title_alignment(Alignment::Center)
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(FILTER_MODE_BG)),
+                    .border_style(Style::default().fg(self.theme.filter_bg)),
             )
             .style(Style::default().fg(WHITE_COLOR))
             .alignment(Alignment::Left);
@@ -144,10 +418,19 @@ impl App {
         popup.render(area, buf);
     }
 
-    pub(super) fn render_events_list(&self, area: Rect, buf: &mut Buffer) {
-        Clear.render(area, buf);
+    /// Returns the list query for the bottom border of the events or marks list, bright while typing.
+    fn list_query_title(&self) -> Option<Line<'_>> {
+        let typing = self.overlay == Some(Overlay::ListFilter);
+        if !typing && self.list_query.is_empty() {
+            return None;
+        }
+        let color = if typing { WHITE_COLOR } else { Color::DarkGray };
+        Some(Line::styled(format!(" /{} ", self.list_query), Style::default().fg(color)).left_aligned())
+    }
 
-        let title = if self.event_tracker.showing_marks() {
+    /// Renders the events list in a popup sized to its content.
+    pub(super) fn render_events_list(&self, area: Rect, buf: &mut Buffer) {
+        let title = if self.events_list_shows_marks() {
             " Log Events & Marks "
         } else {
             " Log Events "
@@ -159,23 +442,103 @@ impl App {
             .title_style(Style::default().bold())
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(EVENT_LIST_BG));
+            .border_style(Style::default().fg(self.theme.event_border));
+
+        let (all_events, filtered_indices) = self.get_events_for_all_tabs();
+        // Tabs for each event type on the first line inside the border
+        let tabs = self.events_tabs(&all_events);
+        let events = self.events_of_tab(all_events);
+        let visible_marks = self.get_listed_marks();
+        let list_items = EventMarkView::merge(&events, &visible_marks, self.events_list_shows_marks());
+
+        let show_tabs = tabs.len() > 1 || self.events_tab.is_some();
+        let block = if show_tabs {
+            block.padding(Padding::top(1))
+        } else {
+            block
+        };
+
+        let block = match self.list_query_title() {
+            Some(query_title) => block.title_bottom(query_title),
+            None => block,
+        };
+        let block = if self.tagged_events.is_empty() {
+            block
+        } else {
+            block.title_bottom(
+                Line::from(format!(" {} tagged | c: create marks ", self.tagged_events.len()))
+                    .centered()
+                    .style(Style::default().fg(EVENT_TAGGED_FG)),
+            )
+        };
+        let block = if self.event_pairs.is_empty() {
+            block
+        } else {
+            let slow_count = self.event_pairs.slow_count();
+            let pairs_style = if slow_count > 0 {
+                Style::default().fg(EVENT_NAME_CRITICAL_FG)
+            } else {
+                Style::default()
+            };
+            block.title_bottom(
+                Line::from(format!(
+                    " {} pairs, {} slow ",
+                    self.event_pairs.pairs().len(),
+                    slow_count
+                ))
+                .right_aligned()
+                .style(pairs_style),
+            )
+        };
+
+        // Calculate max name length from merged items
+        let max_name_length = list_items.iter().map(|item| item.name().len()).max().unwrap_or(0);
+
+        let max_content_length = self.max_content_width(list_items.iter().map(|item| item.line_index()));
+        let (width, height) = list_popup_size(list_items.len(), max_name_length + max_content_length + 2);
+        let height = if show_tabs { height + 1 } else { height };
 
-        let (events, filtered_indices) = self.get_events_for_list();
-        let visible_marks = self.get_visible_marks();
-        let list_items = EventMarkView::merge(&events, &visible_marks, self.event_tracker.showing_marks());
+        // The preview below the list shows the lines around the selected event, with a border around them
+        let preview_lines = self.config.event_preview_lines.unwrap_or(EVENT_PREVIEW_LINES);
+        let show_preview = self.options.is_enabled(AppOption::EventsPreview) && !list_items.is_empty();
+        let (width, height) = if show_preview {
+            (LIST_POPUP_MAX_WIDTH, height + (2 * preview_lines + 3) as u16)
+        } else {
+            (width, height)
+        };
+        let area = popup_area(area, width, height);
+        Clear.render(area, buf);
+        let (area, preview_area) = if show_preview {
+            let [list_area, preview_area] = Layout::vertical([
+                Constraint::Min(LIST_POPUP_MIN_HEIGHT),
+                Constraint::Max((2 * preview_lines + 3) as u16),
+            ])
+            .areas(area);
+            (list_area, Some(preview_area))
+        } else {
+            (area, None)
+        };
+        let tabs_area = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width.saturating_sub(2),
+            height: 1,
+        };
 
         if list_items.is_empty() {
-            let popup = Paragraph::new("No events found")
-                .block(block)
-                .alignment(Alignment::Center);
+            let message = if self.list_query.is_empty() {
+                "No events found"
+            } else {
+                "No matching events"
+            };
+            let popup = Paragraph::new(message).block(block).alignment(Alignment::Center);
             popup.render(area, buf);
+            if show_tabs {
+                self.render_events_tabs(&tabs, tabs_area, buf);
+            }
             return;
         }
 
-        // Calculate max name length from merged items
-        let max_name_length = list_items.iter().map(|item| item.name().len()).max().unwrap_or(0);
-
         let inner_area = block.inner(area);
         let list_area_width = inner_area.width.saturating_sub(1);
 
@@ -189,6 +552,26 @@ impl App {
             let log_line = self.log_buffer.get_line(item.line_index());
 
             if let Some(log_line) = log_line {
+                // Durations of the pairs the event ends, like "request 1.250s"
+                let pair_spans: Vec<Span> = if item.is_mark() {
+                    Vec::new()
+                } else {
+                    self.event_pairs
+                        .ending_at(item.line_index())
+                        .map(|pair| {
+                            let duration = pair.duration.map_or_else(|| "?".to_string(), format_elapsed);
+                            let style = if pair.slow {
+                                Style::default().fg(EVENT_NAME_CRITICAL_FG).add_modifier(Modifier::BOLD)
+                            } else {
+                                Style::default().fg(ALERT_TIME_FG)
+                            };
+                            Span::styled(format!("[{} {}] ", pair.name, duration), style)
+                        })
+                        .collect()
+                };
+                let pairs_width: usize = pair_spans.iter().map(Span::width).sum();
+                let available_width = available_width.saturating_sub(pairs_width).max(20);
+
                 let content = log_line.content();
                 let preview = if content.len() > available_width {
                     format!("{}...", &content[..available_width.saturating_sub(3)])
@@ -202,30 +585,37 @@ impl App {
                 let (name_color, line_color) = if is_filtered {
                     (EVENT_FILTERED_FG, EVENT_FILTERED_FG)
                 } else if item.is_mark() {
-                    (MARK_INDICATOR_COLOR, MARK_LINE_PREVIEW)
+                    (self.theme.mark_fg, MARK_LINE_PREVIEW)
                 } else if self.event_tracker.is_critical_event(item.name()) {
                     (EVENT_NAME_CRITICAL_FG, EVENT_LINE_PREVIEW)
                 } else if self.event_tracker.is_custom_event(item.name()) {
                     let custom_event_color = self
-                        .config
-                        .default_custom_event_bg_color_index
-                        .map(Color::Indexed)
+                        .event_tracker
+                        .custom_event_color(item.name())
+                        .or(self.config.default_custom_event_bg_color_index.map(Color::Indexed))
                         .unwrap_or(EVENT_NAME_CUSTOM_DEFAULT_FG);
                     (custom_event_color, EVENT_LINE_PREVIEW)
                 } else {
-                    (EVENT_NAME_FG, EVENT_LINE_PREVIEW)
+                    (self.theme.event_name_fg, EVENT_LINE_PREVIEW)
                 };
 
-                let spans = vec![
-                    Span::raw(" "),
+                let tag = if !item.is_mark() && self.tagged_events.contains(&item.line_index()) {
+                    Span::styled("*", Style::default().fg(EVENT_TAGGED_FG).add_modifier(Modifier::BOLD))
+                } else {
+                    Span::raw(" ")
+                };
+
+                let mut spans = vec![
+                    tag,
                     Span::raw(padding),
                     Span::styled(
                         item.name(),
                         Style::default().fg(name_color).add_modifier(Modifier::BOLD),
                     ),
                     Span::raw(" "),
-                    Span::styled(preview, Style::default().fg(line_color)),
                 ];
+                spans.extend(pair_spans);
+                spans.push(Span::styled(preview, Style::default().fg(line_color)));
 
                 items.push(Line::from(spans));
             }
@@ -240,12 +630,39 @@ impl App {
             .highlight_symbol(RIGHT_ARROW)
             .highlight_style(
                 Style::default()
-                    .bg(EVENT_LIST_HIGHLIGHT_BG)
+                    .bg(self.theme.list_highlight_bg)
                     .add_modifier(Modifier::BOLD),
             )
             .render(area, buf, block);
 
         self.events_list_state.set_viewport_height(list_area.height as usize);
+        if show_tabs {
+            self.render_events_tabs(&tabs, tabs_area, buf);
+        }
+        if let Some(preview_area) = preview_area
+            && let Some(item) = list_items.get(self.events_list_state.selected_index())
+        {
+            self.render_event_preview(item.line_index(), preview_lines, preview_area, buf);
+        }
+    }
+
+    /// Renders the tabs of the events view with their event counts, highlighting the selected one.
+    fn render_events_tabs(&self, tabs: &[(Option<String>, usize)], area: Rect, buf: &mut Buffer) {
+        let titles: Vec<String> = tabs
+            .iter()
+            .map(|(name, count)| format!("{} {}", name.as_deref().unwrap_or("All"), count))
+            .collect();
+        let selected = tabs.iter().position(|(name, _)| *name == self.events_tab);
+
+        Tabs::new(titles)
+            .select(selected)
+            .style(Style::default().fg(Color::DarkGray))
+            .highlight_style(
+                Style::default()
+                    .fg(self.theme.event_name_fg)
+                    .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            )
+            .render(area, buf);
     }
 
     pub(super) fn render_event_filter_popup(&self, area: Rect, buf: &mut Buffer) {
@@ -259,7 +676,7 @@ impl App {
             .title_style(Style::default().bold())
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(EVENT_LIST_BG));
+            .border_style(Style::default().fg(self.theme.event_border));
 
         if event_filters.is_empty() {
             let popup = Paragraph::new("No event filters available")
@@ -277,9 +694,9 @@ impl App {
                 let content = format!("{} {} ({})", checkbox, filter.name, count);
 
                 let base_color = if filter.enabled {
-                    FILTER_ENABLED_FG
+                    self.theme.filter_enabled_fg
                 } else {
-                    FILTER_DISABLED_FG
+                    self.theme.filter_disabled_fg
                 };
 
                 if self.event_tracker.is_critical_event(&filter.name) {
@@ -304,32 +721,61 @@ impl App {
             .set_viewport_height(list_area.height as usize);
     }
 
+    /// Renders the marks list in a popup sized to its content.
     pub(super) fn render_marks_list(&self, area: Rect, buf: &mut Buffer) {
-        Clear.render(area, buf);
-
+        let title = if self.marks_grouped {
+            " Marked Lines by Name "
+        } else {
+            " Marked Lines "
+        };
         let block = Block::default()
-            .title(" Marked Lines ")
+            .title(title)
             .title_alignment(Alignment::Center)
             .title_style(Style::default().bold())
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(MARK_MODE_BG));
+        let block = match self.list_query_title() {
+            Some(query_title) => block.title_bottom(query_title),
+            None => block,
+        };
+        let block = if self.selected_marks.is_empty() {
+            block
+        } else {
+            block.title_bottom(
+                Line::from(format!(
+                    " {} selected | d: delete  e: rename  X: export  ]/[: cycle ",
+                    self.selected_marks.len()
+                ))
+                .centered()
+                .style(Style::default().fg(EVENT_TAGGED_FG)),
+            )
+        };
 
-        let marks = self.get_visible_marks();
+        let marks = self.get_marks_view_list();
 
-        if marks.is_empty() {
-            let popup = Paragraph::new("No marked lines")
-                .block(block)
-                .alignment(Alignment::Center);
-            popup.render(area, buf);
-            return;
-        }
         let max_name_length = marks
             .iter()
             .filter_map(|m| m.name.as_ref().map(|n| n.len()))
             .max()
             .unwrap_or(0);
 
+        let max_content_length = self.max_content_width(marks.iter().map(|mark| mark.line_index));
+        let (width, height) = list_popup_size(marks.len(), max_name_length + max_content_length + 2);
+        let area = popup_area(area, width, height);
+        Clear.render(area, buf);
+
+        if marks.is_empty() {
+            let message = if self.list_query.is_empty() {
+                "No marked lines"
+            } else {
+                "No matching marked lines"
+            };
+            let popup = Paragraph::new(message).block(block).alignment(Alignment::Center);
+            popup.render(area, buf);
+            return;
+        }
+
         let inner_area = block.inner(area);
         let list_area_width = inner_area.width.saturating_sub(1);
 
@@ -340,7 +786,16 @@ impl App {
 
         let items: Vec<Line> = marks
             .iter()
-            .map(|mark| {
+            .enumerate()
+            .map(|(index, mark)| {
+                let selection = if self.selected_marks.contains(&mark.line_index) {
+                    Span::styled("*", Style::default().fg(EVENT_TAGGED_FG).add_modifier(Modifier::BOLD))
+                } else {
+                    Span::raw(" ")
+                };
+                // Grouped marks show their name on the first mark of each group only
+                let repeated_name = self.marks_grouped && index > 0 && marks[index - 1].name == mark.name;
+
                 let log_line = self
                     .log_buffer
                     .get_line(mark.line_index)
@@ -353,15 +808,17 @@ impl App {
                     log_line.to_string()
                 };
 
-                if let Some(name) = &mark.name {
+                if let Some(name) = mark.name.as_ref().filter(|_| !repeated_name) {
                     let padding = " ".repeat(max_name_length - name.len());
 
                     let spans = vec![
-                        Span::raw(" "),
+                        selection,
                         Span::raw(padding),
                         Span::styled(
                             name.clone(),
-                            Style::default().fg(MARK_NAME_FG).add_modifier(Modifier::BOLD),
+                            Style::default()
+                                .fg(self.theme.mark_name_fg)
+                                .add_modifier(Modifier::BOLD),
                         ),
                         Span::raw(" "),
                         Span::styled(preview, Style::default().fg(MARK_LINE_PREVIEW)),
@@ -371,7 +828,7 @@ impl App {
                     let padding = " ".repeat(max_name_length);
 
                     let spans = vec![
-                        Span::raw(" "),
+                        selection,
                         Span::raw(padding),
                         Span::raw(" "),
                         Span::styled(preview, Style::default().fg(MARK_LINE_PREVIEW)),
@@ -388,89 +845,949 @@ impl App {
             )
             .total_count(marks.len())
             .highlight_symbol(RIGHT_ARROW)
-            .highlight_style(Style::default().bg(MARK_LIST_HIGHLIGHT_BG).add_modifier(Modifier::BOLD))
+            .highlight_style(
+                Style::default()
+                    .bg(self.theme.list_highlight_bg)
+                    .add_modifier(Modifier::BOLD),
+            )
             .render(area, buf, block);
 
         self.marking_list_state.set_viewport_height(list_area.height as usize);
     }
 
-    pub(super) fn render_files_list(&self, area: Rect, buf: &mut Buffer) {
-        use super::colors::FILE_ID_COLORS;
+    pub(super) fn render_actions_list(&self, area: Rect, buf: &mut Buffer) {
         Clear.render(area, buf);
 
         let block = Block::default()
-            .title(" Files ")
+            .title(" Actions ")
             .title_alignment(Alignment::Center)
             .title_style(Style::default().bold())
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(FILE_BORDER));
-
-        if self.file_manager.count() == 0 {
-            let help = Paragraph::new("No files loaded. Press 'a' to add a file.")
-                .block(block)
-                .alignment(Alignment::Center);
-            help.render(area, buf);
-            return;
-        }
+            .border_style(Style::default().fg(WHITE_COLOR));
 
+        let name_width = self
+            .action_choices
+            .iter()
+            .map(|(index, _)| self.actions[*index].name.chars().count())
+            .max()
+            .unwrap_or(0);
         let items: Vec<Line> = self
-            .file_manager
+            .action_choices
             .iter()
-            .map(|file| {
-                let file_indicator = format!("[{}] ", file.file_id + 1);
+            .map(|(index, text)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:<width$}  ", self.actions[*index].name, width = name_width),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(text.as_str(), Style::default().fg(EVENT_LINE_PREVIEW)),
+                ])
+            })
+            .collect();
 
-                let filename = if file.get_path().chars().count() > MAX_PATH_LENGTH {
-                    let skip = file.get_path().chars().count().saturating_sub(MAX_PATH_LENGTH - 3);
-                    let suffix: String = file.get_path().chars().skip(skip).collect();
-                    format!("...{}", suffix)
-                } else {
-                    file.get_path().to_string()
-                };
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.actions_list_state.selected_index(),
+                self.actions_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
 
-                let file_color = if file.enabled {
-                    FILE_ENABLED_FG
-                } else {
-                    FILE_DISABLED_FG
-                };
+        self.actions_list_state.set_viewport_height(list_area.height as usize);
+    }
 
-                let color = FILE_ID_COLORS[file.file_id % FILE_ID_COLORS.len()];
+    pub(super) fn render_sessions_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
 
-                let spans = vec![
-                    Span::raw(" "),
-                    Span::styled(file_indicator, Style::default().fg(color).add_modifier(Modifier::BOLD)),
-                    Span::styled(filename, Style::default().fg(file_color)),
-                ];
+        let block = Block::default()
+            .title(" Sessions ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.file_border));
 
-                Line::from(spans)
+        let items: Vec<Line> = self
+            .sessions
+            .iter()
+            .map(|name| {
+                let style = if self.session_name.as_ref() == Some(name) {
+                    Style::default().fg(self.theme.file_fg).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.file_fg)
+                };
+                Line::from(vec![Span::raw(" "), Span::styled(name.as_str(), style)])
             })
             .collect();
 
         let (list_area, _) = ScrollableList::new(items)
             .selection(
-                self.files_list_state.selected_index(),
-                self.files_list_state.viewport_offset(),
+                self.sessions_list_state.selected_index(),
+                self.sessions_list_state.viewport_offset(),
             )
             .highlight_symbol(RIGHT_ARROW)
-            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
             .render(area, buf, block);
 
-        self.files_list_state.set_viewport_height(list_area.height as usize);
+        self.sessions_list_state.set_viewport_height(list_area.height as usize);
     }
 
-    pub(super) fn render_mark_name_input_popup(&self, area: Rect, buf: &mut Buffer) {
+    pub(super) fn render_history_list(&self, area: Rect, buf: &mut Buffer) {
         Clear.render(area, buf);
 
-        let input_text = self.input.value();
-        let popup = Paragraph::new(input_text)
-            .block(
-                Block::default()
-                    .title(" Name Mark ")
-                    .title_alignment(Alignment::Center)
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(MARK_MODE_BG)),
-            )
+        let block = Block::default()
+            .title(" History ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let items: Vec<Line> = self
+            .history_records()
+            .map(|record| {
+                let time = chrono::DateTime::from_timestamp(record.timestamp, 0)
+                    .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                let kind = match record.kind {
+                    QueryKind::Search => "search",
+                    QueryKind::Filter(ActiveFilterMode::Include) => "filter IN",
+                    QueryKind::Filter(ActiveFilterMode::Exclude) => "filter EX",
+                };
+                let case_str = if record.case_sensitive { "Aa" } else { "aa" };
+                Line::from(vec![
+                    Span::styled(format!(" {}  ", time), Style::default().fg(EVENT_LINE_PREVIEW)),
+                    Span::styled(format!("[{:<9}] [{}] ", kind, case_str), Style::default().bold()),
+                    Span::raw(record.pattern.as_str()),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.history_list_state.selected_index(),
+                self.history_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.history_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_scripts_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Scripts ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let name_width = self
+            .scripts
+            .iter()
+            .map(|script| script.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let items: Vec<Line> = self
+            .scripts
+            .iter()
+            .map(|script| {
+                let stream = if script.stream { " [stream]" } else { "" };
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:<width$}  ", script.name, width = name_width),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(script.command.as_str(), Style::default().fg(EVENT_LINE_PREVIEW)),
+                    Span::raw(stream),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.scripts_list_state.selected_index(),
+                self.scripts_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.scripts_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_alerts_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(format!(" Alerts ({}) ", self.alerts.firings().len()))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let rules = self.alerts.rules();
+        let name_width = rules.iter().map(|rule| rule.name.chars().count()).max().unwrap_or(0);
+        let items: Vec<Line> = self
+            .alert_firings()
+            .map(|firing| {
+                let content = self
+                    .log_buffer
+                    .get_line(firing.line_index)
+                    .map(|line| line.content())
+                    .unwrap_or_default();
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {}  ", firing.time.format("%H:%M:%S")),
+                        Style::default().fg(ALERT_TIME_FG),
+                    ),
+                    Span::styled(
+                        format!("{:<width$}  ", rules[firing.rule_index].name, width = name_width),
+                        Style::default().fg(ALERT_NAME_FG).bold(),
+                    ),
+                    Span::raw(format!("{:>7}  ", firing.line_index + 1)),
+                    Span::raw(content),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.alerts_list_state.selected_index(),
+                self.alerts_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.alerts_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_correlation_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let Some(correlation) = &self.correlation else {
+            return;
+        };
+
+        let line_count = correlation.line_indices.len();
+        let block = Block::default()
+            .title(format!(
+                " {} = {} ({} line{}) ",
+                correlation.name,
+                correlation.value,
+                line_count.to_formatted_string(&Locale::en_DK),
+                if line_count == 1 { "" } else { "s" }
+            ))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" Enter: go to line | f: filter on id ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let id_style = Style::default().fg(CORRELATION_ID_FG).bold();
+        let items: Vec<Line> = correlation
+            .line_indices
+            .iter()
+            .filter_map(|&line_index| self.log_buffer.get_line(line_index))
+            .map(|line| {
+                let mut spans = vec![Span::raw(format!("{:>7}  ", line.index + 1))];
+                let mut parts = line.content().split(correlation.value.as_str());
+                spans.extend(parts.next().map(Span::raw));
+                for part in parts {
+                    spans.push(Span::styled(correlation.value.as_str(), id_style));
+                    spans.push(Span::raw(part));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.correlation_list_state.selected_index(),
+                self.correlation_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.correlation_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_registers_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let (title, hint) = match self.register_action {
+            RegisterAction::Set => (" Save position in register ", " a-z: save | Enter: overwrite selected "),
+            RegisterAction::Jump => (
+                " Jump to register ",
+                " a-z: jump | Enter: jump to selected | Del: remove ",
+            ),
+        };
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(hint).centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        if self.registers.is_empty() {
+            Paragraph::new("No registers set")
+                .block(block)
+                .alignment(Alignment::Center)
+                .render(area, buf);
+            return;
+        }
+
+        let items: Vec<Line> = self
+            .registers
+            .iter()
+            .map(|(name, position)| {
+                let content = self
+                    .log_buffer
+                    .get_line(position.line_index)
+                    .map(|line| line.content())
+                    .unwrap_or_default();
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {}  ", name),
+                        Style::default().fg(self.theme.mark_name_fg).bold(),
+                    ),
+                    Span::raw(format!("{:>7}  ", position.line_index + 1)),
+                    Span::raw(content),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.registers_list_state.selected_index(),
+                self.registers_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.registers_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_jump_history_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Navigation history ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" Enter: go to position | Ctrl+o/Tab: back/forward ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let history = self.viewport.history();
+        let current_position = self.viewport.history_position();
+        let items: Vec<Line> = history
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(position, &line_index)| {
+                let log_line = self.log_buffer.get_line(line_index);
+                let marker = if position == current_position { RIGHT_ARROW } else { " " };
+                let timestamp = log_line
+                    .and_then(|line| line.timestamp)
+                    .map(|timestamp| timestamp.format("%H:%M:%S%.3f").to_string())
+                    .unwrap_or_default();
+                Line::from(vec![
+                    Span::styled(format!(" {} ", marker), Style::default().fg(self.theme.mark_fg)),
+                    Span::raw(format!("{:>7}  ", line_index + 1)),
+                    Span::styled(format!("{:<12}  ", timestamp), Style::default().fg(ALERT_TIME_FG)),
+                    Span::raw(log_line.map(|line| line.content()).unwrap_or_default()),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.jump_history_list_state.selected_index(),
+                self.jump_history_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.jump_history_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_restarts_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let boundaries = self.restarts.boundaries();
+        let block = Block::default()
+            .title(format!(" Restarts ({}) ", boundaries.len()))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" Enter: go to run ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let total_lines = self.log_buffer.get_total_lines_count();
+        let items: Vec<Line> = boundaries
+            .iter()
+            .enumerate()
+            .map(|(run, &line_index)| {
+                let log_line = self.log_buffer.get_line(line_index);
+                let timestamp = log_line
+                    .and_then(|line| line.timestamp)
+                    .map(|timestamp| timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+                    .unwrap_or_default();
+                let run_length = self.restarts.run_length(run, total_lines);
+                Line::from(vec![
+                    Span::styled(
+                        format!(" Run {:<4}", run + 1),
+                        Style::default().fg(RESTART_SEPARATOR_FG).bold(),
+                    ),
+                    Span::raw(format!("{:>7}  ", line_index + 1)),
+                    Span::styled(format!("{:<23}  ", timestamp), Style::default().fg(ALERT_TIME_FG)),
+                    Span::styled(
+                        format!("{:>9} lines  ", run_length.to_formatted_string(&Locale::en_DK)),
+                        Style::default().fg(self.theme.dimmed_fg),
+                    ),
+                    Span::raw(log_line.map(|line| line.content()).unwrap_or_default()),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.restarts_list_state.selected_index(),
+                self.restarts_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.restarts_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_files_list(&self, area: Rect, buf: &mut Buffer) {
+        use super::colors::FILE_ID_COLORS;
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Files ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.file_border));
+
+        if self.file_manager.count() == 0 {
+            let help = Paragraph::new("No files loaded. Press 'a' to add a file.")
+                .block(block)
+                .alignment(Alignment::Center);
+            help.render(area, buf);
+            return;
+        }
+
+        let items: Vec<Line> = self
+            .file_manager
+            .iter()
+            .map(|file| {
+                let file_indicator = format!("[{}] ", file.file_id + 1);
+
+                let filename = if file.get_path().chars().count() > MAX_PATH_LENGTH {
+                    let skip = file.get_path().chars().count().saturating_sub(MAX_PATH_LENGTH - 3);
+                    let suffix: String = file.get_path().chars().skip(skip).collect();
+                    format!("...{}", suffix)
+                } else {
+                    file.get_path().to_string()
+                };
+
+                let file_color = if file.enabled {
+                    self.theme.file_fg
+                } else {
+                    FILE_DISABLED_FG
+                };
+
+                let color = FILE_ID_COLORS[file.file_id % FILE_ID_COLORS.len()];
+
+                let spans = vec![
+                    Span::raw(" "),
+                    Span::styled(file_indicator, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                    Span::styled(filename, Style::default().fg(file_color)),
+                ];
+
+                Line::from(spans)
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.files_list_state.selected_index(),
+                self.files_list_state.viewport_offset(),
+            )
+            .highlight_symbol(RIGHT_ARROW)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .render(area, buf, block);
+
+        self.files_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_pipe_output(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(format!(" | {} ", self.pipe_command))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let items: Vec<Line> = self.pipe_output.iter().map(|line| Line::from(line.as_str())).collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.pipe_output_list_state.selected_index(),
+                self.pipe_output_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.pipe_output_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_full_line(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let length: usize = self.full_line_rows.iter().map(String::len).sum();
+        let block = Block::default()
+            .title(" Full line ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(format!(" {} ", format_bytes(length))).right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let items: Vec<Line> = self.full_line_rows.iter().map(|row| Line::from(row.as_str())).collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.full_line_list_state.selected_index(),
+                self.full_line_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.full_line_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    /// Renders the bytes of the line as rows of an offset, the bytes in hex and their ASCII characters. Binary
+    /// bytes, which the log view doesn't show as they are, are highlighted.
+    pub(super) fn render_hex_view(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let binary_count = self.hex_dump.binary_count();
+        let source = if self.hex_dump_raw { "as in file" } else { "as shown" };
+        let block = Block::default()
+            .title(" Hex view ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(
+                Line::from(format!(
+                    " {}, {} binary, {} ",
+                    format_bytes(self.hex_dump.len()),
+                    binary_count.to_formatted_string(&Locale::en_DK),
+                    source
+                ))
+                .right_aligned(),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let binary_style = Style::default().fg(HEX_BINARY_FG).add_modifier(Modifier::BOLD);
+        let items: Vec<Line> = (0..self.hex_dump.row_count())
+            .map(|row| {
+                let (bytes, binary) = self.hex_dump.row(row);
+                let mut spans = vec![Span::styled(
+                    format!("{:08x}  ", row * BYTES_PER_ROW),
+                    Style::default().fg(HEX_OFFSET_FG),
+                )];
+                for column in 0..BYTES_PER_ROW {
+                    let separator = if column == BYTES_PER_ROW / 2 - 1 { "  " } else { " " };
+                    match bytes.get(column).zip(binary.get(column)) {
+                        Some((byte, &is_binary)) => {
+                            let style = if is_binary { binary_style } else { Style::default() };
+                            spans.push(Span::styled(format!("{:02x}", byte), style));
+                            spans.push(Span::raw(separator));
+                        }
+                        None => spans.push(Span::raw(format!("  {}", separator))),
+                    }
+                }
+                spans.push(Span::raw(" |"));
+                for (byte, &is_binary) in bytes.iter().zip(binary) {
+                    let style = if is_binary { binary_style } else { Style::default() };
+                    spans.push(Span::styled(ascii_char(*byte).to_string(), style));
+                }
+                spans.push(Span::raw("|"));
+                Line::from(spans)
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.hex_list_state.selected_index(),
+                self.hex_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.hex_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    /// Renders the restored filters matching no line, with the choice to disable or delete them.
+    pub(super) fn render_stale_filters(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let count = self.stale_filters.len();
+        let block = Block::default()
+            .title(format!(
+                " {} restored filter{} match no line ",
+                count,
+                if count == 1 { "" } else { "s" }
+            ))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" Space: disable | d: delete | Esc: keep ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.filter_bg))
+            .padding(Padding::vertical(1));
+
+        let items: Vec<Line> = self
+            .stale_filters
+            .iter()
+            .filter_map(|&index| self.filter.get_pattern(index))
+            .map(|pattern| {
+                let mode_str = match pattern.mode {
+                    ActiveFilterMode::Include => "IN",
+                    ActiveFilterMode::Exclude => "EX",
+                };
+                let file_str = pattern
+                    .file
+                    .as_ref()
+                    .map_or(String::new(), |file| format!("[{}] ", file));
+                Line::from(format!(" [{}] {}{}", mode_str, file_str, pattern.pattern))
+                    .style(Style::default().fg(self.theme.filter_enabled_fg))
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.stale_filters_list_state.selected_index(),
+                self.stale_filters_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.stale_filters_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+
+    /// Renders the filters derived from the selected line, each with the key adding it.
+    pub(super) fn render_quick_filters(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let (title, hint) = match self.quick_filter_mode {
+            ActiveFilterMode::Include => (" Show only lines with ", " Tab: hide instead "),
+            ActiveFilterMode::Exclude => (" Hide lines with ", " Tab: show only instead "),
+        };
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(hint).centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.filter_bg))
+            .padding(Padding::vertical(1));
+
+        let items: Vec<Line> = self
+            .quick_filters
+            .iter()
+            .map(|filter| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {} ", filter.kind.key()),
+                        Style::default().fg(self.theme.filter_enabled_fg).bold(),
+                    ),
+                    Span::styled(
+                        format!("{:<9}", filter.kind.name()),
+                        Style::default().fg(self.theme.dimmed_fg),
+                    ),
+                    Span::raw(filter.value.clone()),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.quick_filter_list_state.selected_index(),
+                self.quick_filter_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.quick_filter_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_format_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Log format ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" Enter: parse lines with format ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let items: Vec<Line> = FormatChoice::all()
+            .into_iter()
+            .map(|choice| {
+                let marker = if choice == self.format_choice { RIGHT_ARROW } else { " " };
+                let (name, description) = match choice {
+                    FormatChoice::Auto => (
+                        "auto".to_string(),
+                        match self.detected_format {
+                            Some(format) => format!("detected: {}", format.name()),
+                            None => "no format detected".to_string(),
+                        },
+                    ),
+                    FormatChoice::None => ("none".to_string(), "common timestamps and levels".to_string()),
+                    FormatChoice::Format(format) => (format.name().to_string(), format.description().to_string()),
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {} ", marker), Style::default().fg(self.theme.mark_fg)),
+                    Span::raw(format!("{:<12}", name)),
+                    Span::styled(description, Style::default().fg(ALERT_TIME_FG)),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.format_list_state.selected_index(),
+                self.format_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.format_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_profiles_list(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Profiles ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" Enter: switch to profile ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let none = (None, "shared highlights, events and filters only".to_string());
+        let profiles = self.config.profile.iter().map(|(name, profile)| {
+            let description = format!(
+                "{} highlights, {} events, {} filters",
+                profile.highlights.len(),
+                profile.events.len(),
+                profile.filters.len()
+            );
+            (Some(name), description)
+        });
+        let items: Vec<Line> = std::iter::once(none)
+            .chain(profiles)
+            .map(|(name, description)| {
+                let marker = if name == self.profile.as_ref() {
+                    RIGHT_ARROW
+                } else {
+                    " "
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {} ", marker), Style::default().fg(self.theme.mark_fg)),
+                    Span::raw(format!("{:<16}", name.map_or("none", String::as_str))),
+                    Span::styled(description, Style::default().fg(ALERT_TIME_FG)),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.profiles_list_state.selected_index(),
+                self.profiles_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.profiles_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_query_results(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let row_count = self.query_result.rows.len();
+        let block = Block::default()
+            .title(format!(" {} ", self.query))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(
+                Line::from(format!(" {} rows ", row_count.to_formatted_string(&Locale::en_DK))).right_aligned(),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let [header_area, rows_area] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner_area);
+        if let Some(header) = self.query_table.first() {
+            Line::styled(header.as_str(), Style::default().add_modifier(Modifier::BOLD)).render(header_area, buf);
+        }
+
+        let items: Vec<Line> = self
+            .query_table
+            .iter()
+            .skip(1)
+            .map(|line| Line::from(line.as_str()))
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.query_results_list_state.selected_index(),
+                self.query_results_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(rows_area, buf, Block::default());
+
+        self.query_results_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_patterns(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Message Patterns ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(
+                Line::from(format!(
+                    " {} patterns ",
+                    self.templates.len().to_formatted_string(&Locale::en_DK)
+                ))
+                .right_aligned(),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let count_width = self
+            .templates
+            .first()
+            .map_or(0, |template| template.count.to_formatted_string(&Locale::en_DK).len());
+        let items: Vec<Line> = self
+            .templates
+            .iter()
+            .map(|template| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{:>width$}  ",
+                            template.count.to_formatted_string(&Locale::en_DK),
+                            width = count_width
+                        ),
+                        Style::default().fg(EVENT_LINE_PREVIEW),
+                    ),
+                    Span::raw(template.template.as_str()),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.templates_list_state.selected_index(),
+                self.templates_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.templates_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_noise_suggestions(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Noise ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" 1-9/x: exclude pattern ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WHITE_COLOR));
+
+        let line_count = self.noise_line_count.max(1);
+        let items: Vec<Line> = self
+            .noise_suggestions
+            .iter()
+            .enumerate()
+            .map(|(i, suggestion)| {
+                Line::from(vec![
+                    Span::styled(format!(" {} ", i + 1), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(
+                        format!("{:>5.1}%  ", suggestion.count as f64 * 100.0 / line_count as f64),
+                        Style::default().fg(EVENT_LINE_PREVIEW),
+                    ),
+                    Span::raw(suggestion.template.as_str()),
+                ])
+            })
+            .collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.noise_list_state.selected_index(),
+                self.noise_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(self.theme.list_highlight_bg))
+            .render(area, buf, block);
+
+        self.noise_list_state.set_viewport_height(list_area.height as usize);
+    }
+
+    pub(super) fn render_mark_name_input_popup(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let input_text = self.input.value();
+        let popup = Paragraph::new(input_text)
+            .block(
+                Block::default()
+                    .title(" Name Mark ")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(MARK_MODE_BG)),
+            )
             .style(Style::default().fg(WHITE_COLOR))
             .alignment(Alignment::Left);
 
@@ -480,11 +1797,16 @@ impl App {
     pub(super) fn render_add_custom_event_popup(&self, area: Rect, buf: &mut Buffer) {
         Clear.render(area, buf);
 
+        let title = match &self.custom_event_step {
+            CustomEventStep::Pattern => " Add Custom Event: pattern ",
+            CustomEventStep::Name { .. } => " Add Custom Event: name ",
+            CustomEventStep::Color { .. } => " Add Custom Event: color (Enter for default) ",
+        };
         let input_text = self.input.value();
         let popup = Paragraph::new(input_text)
             .block(
                 Block::default()
-                    .title(" Add Custom Event ")
+                    .title(title)
                     .title_alignment(Alignment::Center)
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
@@ -3,21 +3,32 @@ use std::time::Instant;
 use tracing::trace;
 
 use super::colors::{
-    EXPANDED_LINE_FG, EXPANSION_PREFIX, FILE_ID_COLORS, MARK_INDICATOR, MARK_INDICATOR_COLOR, RIGHT_ARROW,
-    SCROLLBAR_CRITICAL_EVENT_INDICATOR, SCROLLBAR_FG, SCROLLBAR_MARK_INDICATOR, SCROLLBAR_SEARCH_INDICATOR,
-    SELECTION_BG,
+    EXPANSION_PREFIX, FILE_ID_COLORS, MARK_INDICATOR, MINIMAP_EVENT_INDICATOR, MINIMAP_TICK, MINIMAP_VIEWPORT_BG,
+    RESTART_SEPARATOR_FG, RIGHT_ARROW, SCROLLBAR_CRITICAL_EVENT_INDICATOR, SCROLLBAR_MARK_INDICATOR,
+    SCROLLBAR_SEARCH_INDICATOR, SPLIT_FOCUS_FG, STREAM_STDERR_FG, STREAM_STDOUT_FG, TRUNCATED_LEFT, TRUNCATED_RIGHT,
 };
+use crate::debug_stats::format_bytes;
 use crate::highlighter::HighlightedLine;
+use crate::minimap::MinimapTick;
 use crate::options::AppOption;
 use crate::resolver::Tag;
-use crate::{app::App, log::LogLine};
-use ratatui::symbols::line::{VERTICAL, VERTICAL_LEFT};
+use crate::utils::{column_to_byte, display_width, truncate_bytes};
+use crate::viewport::{Viewport, split_heights, split_widths};
+use crate::{
+    app::App,
+    log::{LogLine, OutputStream},
+};
+use num_format::{Locale, ToFormattedString};
+use ratatui::symbols::line::{HORIZONTAL, VERTICAL, VERTICAL_LEFT};
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{List, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget},
+    widgets::{
+        Block, BorderType, Borders, List, ListState, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        StatefulWidget, Widget,
+    },
 };
 
 /// Represents an indicator to display on the scrollbar
@@ -29,16 +40,16 @@ struct ScrollbarIndicator {
 }
 
 impl App {
-    /// Renders the vertical scrollbar.
-    pub(super) fn render_scrollbar(&self, area: Rect, buf: &mut Buffer) {
-        let mut scrollbar_state = ScrollbarState::new(self.viewport.total_lines)
-            .position(self.viewport.selected_line)
+    /// Renders the vertical scrollbar of a viewport.
+    pub(super) fn render_scrollbar(&self, viewport: &Viewport, area: Rect, buf: &mut Buffer) {
+        let mut scrollbar_state = ScrollbarState::new(viewport.total_lines)
+            .position(viewport.selected_line)
             .viewport_content_length(1);
 
         let scrollbar = Scrollbar::default()
             .orientation(ScrollbarOrientation::VerticalRight)
             .track_symbol(Some(VERTICAL))
-            .track_style(Style::default().fg(SCROLLBAR_FG))
+            .track_style(Style::default().fg(self.theme.scrollbar_fg))
             .thumb_style(Style::new().bg(Color::Indexed(253)))
             .begin_symbol(None)
             .end_symbol(None);
@@ -60,6 +71,129 @@ impl App {
         }
     }
 
+    /// Splits a pane into the log view, the minimap if shown, and the scrollbar.
+    pub(super) fn split_scrollbar_area(&self, area: Rect) -> (Rect, Option<Rect>, Rect) {
+        if self.options.is_enabled(AppOption::ShowMinimap) {
+            let [log_view_area, minimap_area, scrollbar_area] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(1), Constraint::Length(1)]).areas(area);
+            (log_view_area, Some(minimap_area), scrollbar_area)
+        } else {
+            let [log_view_area, scrollbar_area] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+            (log_view_area, None, scrollbar_area)
+        }
+    }
+
+    /// Renders the minimap of a viewport, with the lines in view shaded. The area of the focused
+    /// viewport's minimap is kept for jumping to the clicked row.
+    pub(super) fn render_minimap(&self, viewport: &Viewport, focused: bool, area: Option<Rect>, buf: &mut Buffer) {
+        if focused {
+            self.minimap_area.set(area);
+        }
+        let Some(area) = area else {
+            return;
+        };
+
+        let minimap = self.build_minimap(viewport, area.height as usize);
+        let (start, end) = viewport.visible();
+        let in_view = minimap.rows_between(start, end.min(viewport.total_lines));
+
+        for (row, tick) in minimap.ticks().iter().enumerate() {
+            let mut style = Style::default();
+            if in_view.is_some_and(|(first, last)| (first..=last).contains(&row)) {
+                style = style.bg(MINIMAP_VIEWPORT_BG);
+            }
+            let symbol = match tick {
+                Some(tick) => {
+                    style = style.fg(match tick {
+                        MinimapTick::Event => MINIMAP_EVENT_INDICATOR,
+                        MinimapTick::SearchMatch => SCROLLBAR_SEARCH_INDICATOR,
+                        MinimapTick::Mark => SCROLLBAR_MARK_INDICATOR,
+                        MinimapTick::CriticalEvent => SCROLLBAR_CRITICAL_EVENT_INDICATOR,
+                    });
+                    MINIMAP_TICK
+                }
+                None => " ",
+            };
+            buf[(area.x, area.y + row as u16)].set_symbol(symbol).set_style(style);
+        }
+    }
+
+    /// Renders the two panes of the split view with a separator between them.
+    pub(super) fn render_split_view(&self, split_viewport: &Viewport, area: Rect, buf: &mut Buffer) {
+        let (top_height, _) = split_heights(area.height as usize);
+        let [top_area, separator_area, bottom_area] = Layout::vertical([
+            Constraint::Length(top_height as u16),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let (top_viewport, bottom_viewport) = if self.split_focus_top {
+            (&self.viewport, split_viewport)
+        } else {
+            (split_viewport, &self.viewport)
+        };
+        for (viewport, pane_area, focused) in [
+            (top_viewport, top_area, self.split_focus_top),
+            (bottom_viewport, bottom_area, !self.split_focus_top),
+        ] {
+            let (log_view_area, minimap_area, scrollbar_area) = self.split_scrollbar_area(pane_area);
+            self.render_log_view(viewport, focused, log_view_area, buf);
+            self.render_minimap(viewport, focused, minimap_area, buf);
+            self.render_scrollbar(viewport, scrollbar_area, buf);
+        }
+
+        let focus_symbol = if self.split_focus_top { "▲" } else { "▼" };
+        Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(self.theme.scrollbar_fg))
+            .title(Line::from(format!(" {} ", focus_symbol)).style(Style::default().fg(SPLIT_FOCUS_FG)))
+            .title(Line::from(" Ctrl+w: switch | Ctrl+x: close ").right_aligned())
+            .render(separator_area, buf);
+    }
+
+    /// Renders the focused pane with the pinned pane to its right. The pinned pane scrolls horizontally with the
+    /// focused pane, so the same columns are compared.
+    pub(super) fn render_pinned_split_view(&self, pinned_viewport: &Viewport, area: Rect, buf: &mut Buffer) {
+        let (left_width, _) = split_widths(area.width.saturating_sub(1) as usize);
+        let [left_area, separator_area, right_area] = Layout::horizontal([
+            Constraint::Length(left_width as u16 + 1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [title_area, pinned_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(right_area);
+
+        let (log_view_area, minimap_area, scrollbar_area) = self.split_scrollbar_area(left_area);
+        self.render_log_view(&self.viewport, true, log_view_area, buf);
+        self.render_minimap(&self.viewport, true, minimap_area, buf);
+        self.render_scrollbar(&self.viewport, scrollbar_area, buf);
+
+        let mut pinned = pinned_viewport.clone();
+        pinned.horizontal_offset = self.viewport.horizontal_offset;
+        let (log_view_area, minimap_area, scrollbar_area) = self.split_scrollbar_area(pinned_area);
+        self.render_log_view(&pinned, false, log_view_area, buf);
+        self.render_minimap(&pinned, false, minimap_area, buf);
+        self.render_scrollbar(&pinned, scrollbar_area, buf);
+
+        Block::default()
+            .borders(Borders::LEFT)
+            .border_style(Style::default().fg(self.theme.scrollbar_fg))
+            .render(separator_area, buf);
+        let pinned_line = self
+            .resolver
+            .get_visible_lines(self.log_buffer.all_lines())
+            .get(pinned.selected_line)
+            .map_or(String::new(), |line| format!(" at line {}", line.log_index + 1));
+        Line::from(vec![
+            Span::styled(format!(" Pinned{} ", pinned_line), Style::default().fg(SPLIT_FOCUS_FG)),
+            Span::raw("| Ctrl+x: close "),
+        ])
+        .render(title_area, buf);
+    }
+
     /// Collects all scrollbar indicators for search matches, marks, and events.
     fn collect_scrollbar_indicators(&self) -> Vec<ScrollbarIndicator> {
         let mut indicators = Vec::new();
@@ -114,13 +248,18 @@ impl App {
         indicators
     }
 
-    /// Renders the main log view.
-    pub(super) fn render_log_view(&self, area: Rect, buf: &mut Buffer) {
-        let (start, end) = self.viewport.visible();
-        let selection_range = self.get_selection_range();
+    /// Renders the log lines of a viewport. The visual selection is only shown in the focused viewport.
+    pub(super) fn render_log_view(&self, viewport: &Viewport, focused: bool, area: Rect, buf: &mut Buffer) {
+        let (start, end) = viewport.visible();
+        let selection_range = self.get_selection_range().filter(|_| focused);
+        let block_columns = self.get_block_columns().filter(|_| focused);
 
         let all_lines = self.log_buffer.all_lines();
         let visible_lines = self.resolver.get_visible_lines(all_lines);
+        if visible_lines.is_empty() && self.filters_hide_all_lines() {
+            self.render_hidden_by_filters(area, buf);
+            return;
+        }
 
         let viewport_data = if start < visible_lines.len() {
             let range_end = end.min(visible_lines.len());
@@ -129,18 +268,28 @@ impl App {
             Vec::new()
         };
 
-        let horizontal_offset = self.viewport.horizontal_offset;
+        let horizontal_offset = viewport.horizontal_offset;
         let enable_colors = !self.options.is_enabled(AppOption::DisableColors);
 
+        let column_rows = self.column_rows(viewport_data.iter().copied());
+        if focused {
+            self.selected_row_width.set(None);
+        }
+
+        // Rows of a block selection with the offset their text starts at
+        let mut block_rows = Vec::new();
         let items: Vec<Line> = viewport_data
             .iter()
             .enumerate()
             .map(|(offset, vl)| {
                 let log_line = &all_lines[vl.log_index];
-                let viewport_line = self.options.apply_to_line(log_line.content());
-                let text = viewport_line.get(horizontal_offset..).unwrap_or("");
+                let column_row = column_rows.get(offset).and_then(Option::as_deref);
+                let viewport_line = column_row.unwrap_or_else(|| self.options.apply_to_line(log_line.content()));
 
                 let viewport_line_index = start + offset;
+                if focused && viewport_line_index == viewport.selected_line {
+                    self.selected_row_width.set(Some(display_width(viewport_line)));
+                }
                 let is_selected = if let Some((sel_start, sel_end)) = selection_range {
                     viewport_line_index >= sel_start && viewport_line_index <= sel_end
                 } else {
@@ -148,43 +297,262 @@ impl App {
                 };
 
                 let mut tags = vl.tags.clone();
-                if is_selected {
+                if is_selected && block_columns.is_none() {
                     tags.insert(Tag::Selected);
                 }
 
-                self.process_line_impl(log_line, viewport_line, text, horizontal_offset, &tags, enable_colors)
+                let (line, text_offset) = self.process_line_impl(
+                    log_line,
+                    viewport_line,
+                    horizontal_offset,
+                    &tags,
+                    enable_colors,
+                    column_row.is_some(),
+                );
+                if is_selected && block_columns.is_some() {
+                    block_rows.push((offset, text_offset));
+                }
+                line
             })
             .collect();
 
         let mut list_state = ListState::default();
-        if self.viewport.selected_line >= start && self.viewport.selected_line < end {
-            list_state.select(Some(self.viewport.selected_line - start));
+        if viewport.selected_line >= start && viewport.selected_line < end {
+            list_state.select(Some(viewport.selected_line - start));
         }
 
+        // Lines wider than the view end with an arrow in the last column
+        let symbol_width = if list_state.selected().is_some() {
+            Line::from(RIGHT_ARROW).width()
+        } else {
+            0
+        };
+        let truncated_rows: Vec<usize> = items
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.width() + symbol_width > area.width as usize)
+            .map(|(row, _)| row)
+            .collect();
+
+        // Lines starting a run of the application continue with a separator to the edge of the view
+        let restart_rows: Vec<(usize, usize, usize)> = viewport_data
+            .iter()
+            .zip(&items)
+            .enumerate()
+            .filter_map(|(row, (vl, line))| {
+                let run = self.restarts.run_starting_at(vl.log_index)?;
+                Some((row, symbol_width + line.width(), run))
+            })
+            .collect();
+
         let log_list = List::new(items)
             .highlight_symbol(RIGHT_ARROW)
             .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
         StatefulWidget::render(log_list, area, buf, &mut list_state);
+
+        if let Some((first, last)) = block_columns {
+            for (row, text_offset) in block_rows.into_iter().filter(|&(row, _)| row < area.height as usize) {
+                let text_x = area.x as usize + symbol_width + text_offset;
+                for column in first.max(horizontal_offset)..=last {
+                    let x = text_x + column - horizontal_offset;
+                    if x >= area.right() as usize {
+                        break;
+                    }
+                    if let Some(cell) = buf.cell_mut((x as u16, area.y + row as u16)) {
+                        cell.set_bg(self.theme.selection_bg);
+                    }
+                }
+            }
+        }
+
+        for (row, text_width, run) in restart_rows
+            .into_iter()
+            .filter(|&(row, _, _)| row < area.height as usize)
+        {
+            let y = area.y + row as u16;
+            let start = area.x as usize + text_width + 1;
+            let label = format!(" run {} ", run);
+            let label_x = (area.right() as usize).saturating_sub(label.len() + 2);
+            let style = Style::default().fg(RESTART_SEPARATOR_FG);
+            for x in start..area.right() as usize {
+                buf[(x as u16, y)].set_symbol(HORIZONTAL).set_style(style);
+            }
+            if label_x > start {
+                buf.set_string(label_x as u16, y, label, style.add_modifier(Modifier::BOLD));
+            }
+        }
+
+        if area.width > 0 {
+            for row in truncated_rows.into_iter().filter(|&row| row < area.height as usize) {
+                if let Some(cell) = buf.cell_mut((area.right() - 1, area.y + row as u16)) {
+                    cell.set_symbol(TRUNCATED_RIGHT).set_fg(self.theme.dimmed_fg);
+                }
+            }
+        }
+    }
+
+    /// Renders a hint in the center of the log view explaining that the filters hide all lines, and how to show them.
+    fn render_hidden_by_filters(&self, area: Rect, buf: &mut Buffer) {
+        let hidden = self.log_buffer.get_total_lines_count();
+        let filters = self.filter.enabled_count();
+        let text = vec![
+            Line::from(format!(
+                "{} line{} hidden by {} filter{}",
+                hidden.to_formatted_string(&Locale::en_DK),
+                if hidden == 1 { "" } else { "s" },
+                filters,
+                if filters == 1 { "" } else { "s" }
+            ))
+            .bold(),
+            Line::from("Press F to manage the filters, Ctrl+a to toggle them").fg(self.theme.dimmed_fg),
+        ];
+        let [hint_area] = Layout::vertical([Constraint::Length(text.len() as u16)])
+            .flex(Flex::Center)
+            .areas(area);
+        Paragraph::new(text).alignment(Alignment::Center).render(hint_area, buf);
+    }
+
+    /// Renders the lines around an event for the preview of the events view, highlighted like in the log view with
+    /// the line of the event selected.
+    pub(super) fn render_event_preview(&self, line_index: usize, context: usize, area: Rect, buf: &mut Buffer) {
+        let all_lines = self.log_buffer.all_lines();
+        let start = line_index.saturating_sub(context);
+        let end = (line_index + context + 1).min(all_lines.len());
+        let enable_colors = !self.options.is_enabled(AppOption::DisableColors);
+
+        let lines: Vec<Line> = all_lines
+            .get(start..end)
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .map(|(offset, log_line)| {
+                let content = self.options.apply_to_line(log_line.content());
+                let (line, _) = self.process_line_impl(log_line, content, 0, &HashSet::new(), enable_colors, false);
+                if start + offset == line_index {
+                    line.patch_style(
+                        Style::default()
+                            .bg(self.theme.list_highlight_bg)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        let block = Block::default()
+            .title(format!(" Line {} ", line_index + 1))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.event_border));
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+
+    /// Renders the sticky header: the line giving the context of the top line in view, like the start of the
+    /// multi-line record or of the section the view is scrolled into. The row is left empty if there is none.
+    pub(super) fn render_sticky_header(&self, viewport: &Viewport, area: Rect, buf: &mut Buffer) {
+        let style = Style::default().bg(self.theme.footer_bg);
+        buf.set_style(area, style);
+
+        let all_lines = self.log_buffer.all_lines();
+        let visible_lines = self.resolver.get_visible_lines(all_lines);
+        let Some(top) = visible_lines.get(viewport.top_line) else {
+            return;
+        };
+        let Some(log_line) = self
+            .sticky_header
+            .context_line(all_lines, top.log_index, self.log_buffer.generation())
+            .and_then(|index| all_lines.get(index))
+        else {
+            return;
+        };
+
+        let content = self.options.apply_to_line(log_line.content());
+        let enable_colors = !self.options.is_enabled(AppOption::DisableColors);
+        let (line, _) = self.process_line_impl(
+            log_line,
+            content,
+            viewport.horizontal_offset,
+            &HashSet::new(),
+            enable_colors,
+            false,
+        );
+        line.patch_style(style.add_modifier(Modifier::BOLD)).render(area, buf);
     }
 
-    /// Applies syntax highlighting to a single line.
+    /// Applies syntax highlighting to a single line, scrolled horizontally by `horizontal_offset` columns.
+    /// Returns the line and the width of the mark, file id, stream and expansion indicators the text follows.
+    ///
+    /// `transformed_line` is either the line content with line transforms applied, or its fields aligned as
+    /// columns if `column_row` is set.
     fn process_line_impl<'a>(
         &self,
         log_line: &LogLine,
         transformed_line: &'a str,
-        visible_text: &'a str,
-        line_offset: usize,
+        horizontal_offset: usize,
         tags: &HashSet<Tag>,
         enable_colors: bool,
-    ) -> Line<'a> {
+        column_row: bool,
+    ) -> (Line<'a>, usize) {
+        // Overlong lines, e.g. a base64 blob, are cut off so highlighting them stays fast. Line transforms only
+        // remove a prefix, so the ANSI styles move by the removed length. Column rows are rebuilt from the fields
+        // and can be longer than the line, so there is no such offset for them.
+        let transform_offset = log_line.content.len().checked_sub(transformed_line.len());
+        let full_length = transformed_line.len();
+        let transformed_line = truncate_bytes(transformed_line, self.max_line_length);
+        let cut_length = full_length - transformed_line.len();
+
+        // Highlight ranges are byte positions, so the line is cut at the byte where the column starts.
+        // A wide character cut in half by the offset is replaced by padding to keep columns aligned.
+        // A line scrolled horizontally shows an arrow in place of its first visible column.
+        let truncated_left =
+            horizontal_offset > 0 && column_to_byte(transformed_line, horizontal_offset).0 < transformed_line.len();
+        let (line_offset, padding) = column_to_byte(transformed_line, horizontal_offset + usize::from(truncated_left));
+        let visible_text = &transformed_line[line_offset..];
+        let padding = if truncated_left {
+            Span::styled(
+                format!("{}{}", TRUNCATED_LEFT, " ".repeat(padding)),
+                Style::default().fg(self.theme.dimmed_fg),
+            )
+        } else {
+            Span::raw(" ".repeat(padding))
+        };
+
         let highlight_start = Instant::now();
-        let highlighted = self.highlighter.highlight_line(log_line.index, transformed_line);
+        let file = log_line
+            .log_file_id
+            .and_then(|id| self.file_manager.get(id))
+            .map(|entry| entry.get_filename());
+        let highlighted = if !column_row && let Some(transform_offset) = transform_offset {
+            let ansi_styles: Vec<_> = self
+                .highlighter
+                .adjust_for_viewport_offset(
+                    HighlightedLine {
+                        segments: log_line.ansi_styles.to_vec(),
+                    },
+                    transform_offset,
+                )
+                .segments
+                .into_iter()
+                .filter(|range| range.start < transformed_line.len())
+                .map(|mut range| {
+                    range.end = range.end.min(transformed_line.len());
+                    range
+                })
+                .collect();
+            self.highlighter
+                .highlight_styled_line(log_line.index, transformed_line, file, &ansi_styles)
+        } else {
+            // The column layout depends on the lines in view, so column rows are not cached
+            self.highlighter.highlight_text(transformed_line, file, &[])
+        };
         let highlighted = self.highlighter.adjust_for_viewport_offset(highlighted, line_offset);
         trace!("highlight_line took: {:?}", highlight_start.elapsed());
 
         let mark_indicator = if tags.contains(&Tag::Marked) {
-            Span::styled(MARK_INDICATOR, Style::default().fg(MARK_INDICATOR_COLOR))
+            Span::styled(MARK_INDICATOR, Style::default().fg(self.theme.mark_fg))
         } else {
             Span::raw(" ")
         };
@@ -206,19 +574,33 @@ impl App {
             Span::raw("")
         };
 
+        let stream_indicator = match log_line.stream {
+            Some(OutputStream::Stdout) => Span::styled("out ", Style::default().fg(STREAM_STDOUT_FG)),
+            Some(OutputStream::Stderr) => Span::styled("err ", Style::default().fg(STREAM_STDERR_FG)),
+            None => Span::raw(""),
+        };
+
         let is_expanded = tags.contains(&Tag::Expanded);
 
         let expansion_indicator = if is_expanded {
-            Span::styled(EXPANSION_PREFIX, Style::default().fg(EXPANDED_LINE_FG))
+            Span::styled(EXPANSION_PREFIX, Style::default().fg(self.theme.dimmed_fg))
         } else {
             Span::raw("")
         };
 
+        let indicators_width =
+            mark_indicator.width() + file_id_indicator.width() + stream_indicator.width() + expansion_indicator.width();
         let mut line = if highlighted.segments.is_empty() {
-            let mut spans = vec![mark_indicator, file_id_indicator, expansion_indicator];
+            let mut spans = vec![
+                mark_indicator,
+                file_id_indicator,
+                stream_indicator,
+                expansion_indicator,
+                padding,
+            ];
             if !visible_text.is_empty() {
                 let text_style = if is_expanded {
-                    Style::default().fg(EXPANDED_LINE_FG)
+                    Style::default().fg(self.theme.dimmed_fg)
                 } else {
                     Style::default()
                 };
@@ -231,21 +613,45 @@ impl App {
                 // Dim if the span has no explicit foreground color
                 for span in &mut line.spans {
                     if span.style.fg.is_none() {
-                        span.style = span.style.fg(EXPANDED_LINE_FG);
+                        span.style = span.style.fg(self.theme.dimmed_fg);
                     }
                 }
             }
+            line.spans.insert(0, padding);
             line.spans.insert(0, expansion_indicator);
+            line.spans.insert(0, stream_indicator);
             line.spans.insert(0, file_id_indicator);
             line.spans.insert(0, mark_indicator);
             line
         };
 
+        if cut_length > 0 {
+            line.spans.push(Span::styled(
+                format!(" … [{} more, Alt+l: full line]", format_bytes(cut_length)),
+                Style::default().fg(self.theme.dimmed_fg),
+            ));
+        }
+
+        let folded = self.folded_line_count(log_line);
+        if folded > 0 {
+            line.spans.push(Span::styled(
+                format!(" [+{} lines]", folded),
+                Style::default().fg(self.theme.dimmed_fg),
+            ));
+        }
+        let repeats = self.collapsed_repeat_count(log_line);
+        if repeats > 0 {
+            line.spans.push(Span::styled(
+                format!(" [repeated {} times]", repeats),
+                Style::default().fg(self.theme.dimmed_fg),
+            ));
+        }
+
         if tags.contains(&Tag::Selected) {
-            line = line.style(Style::default().bg(SELECTION_BG));
+            line = line.style(Style::default().bg(self.theme.selection_bg));
         }
 
-        line
+        (line, indicators_width)
     }
 }
 
@@ -285,3 +691,106 @@ pub(super) fn build_line_from_highlighted<'a>(
 
     Line::from(spans)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders the app and returns the rows of the log view, below the title bar.
+    fn render_rows(app: &App, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        (1..height - 1)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+            .collect()
+    }
+
+    /// Renders the event preview of the given line and returns its rows inside the border.
+    fn render_preview_rows(app: &App, line_index: usize, context: usize) -> Vec<String> {
+        let area = Rect::new(0, 0, 20, 2 * context as u16 + 3);
+        let mut buf = Buffer::empty(area);
+        app.render_event_preview(line_index, context, area, &mut buf);
+        (1..area.height - 1)
+            .map(|y| {
+                let row: String = (1..area.width - 1).map(|x| buf[(x, y)].symbol()).collect();
+                row.trim().to_string()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_render_event_preview_window() {
+        let lines: Vec<String> = (0..10).map(|i| format!("line {}", i)).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let app = App::with_lines(&lines, "", 39, 8);
+
+        assert_eq!(
+            render_preview_rows(&app, 5, 2),
+            ["line 3", "line 4", "line 5", "line 6", "line 7"]
+        );
+        // The window is cut off at the start and the end of the log
+        assert_eq!(render_preview_rows(&app, 0, 2), ["line 0", "line 1", "line 2", "", ""]);
+        assert_eq!(render_preview_rows(&app, 9, 2), ["line 7", "line 8", "line 9", "", ""]);
+        assert_eq!(render_preview_rows(&app, 4, 0), ["line 4"]);
+    }
+
+    #[tokio::test]
+    async fn test_render_column_view_rows_longer_than_lines() {
+        let config = "[fields]\nformat = \"logfmt\"\ncolumns = [\"a\", \"b\"]\n";
+        let mut app = App::with_lines(&["a=1 b=2", "a=1234567890 b=3"], config, 39, 8);
+        app.toggle_column_view();
+
+        let rows = render_rows(&app, 40, 10);
+        assert!(rows[0].contains("1          │ 2"), "{:?}", rows);
+        assert!(rows[1].contains("1234567890 │ 3"), "{:?}", rows);
+
+        // The selected line is as wide as its row of columns
+        assert_eq!(app.selected_line_width(), Some(14));
+        assert_eq!(app.selected_row_width.get(), Some(14));
+    }
+
+    #[tokio::test]
+    async fn test_render_scrolled_lines() {
+        let mut app = App::with_lines(&["abcdefgh", "ab"], "", 119, 8);
+        app.viewport.horizontal_offset = 3;
+
+        let area = Rect::new(0, 0, 120, 10);
+        let mut buf = Buffer::empty(area);
+        (&app).render(area, &mut buf);
+        let row = |y: u16| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>();
+
+        // Only lines with text past the offset show that they continue to the left
+        assert!(row(1).contains(&format!("{}efgh", TRUNCATED_LEFT)), "{:?}", row(1));
+        assert!(!row(2).contains(TRUNCATED_LEFT), "{:?}", row(2));
+        assert!(row(9).contains("col 4/8"), "{:?}", row(9));
+    }
+
+    #[tokio::test]
+    async fn test_render_block_selection() {
+        let mut app = App::with_lines(&["id=1234 ok", "id=5678 failed"], "", 119, 8);
+        app.marking.toggle_mark(0);
+        app.viewport.horizontal_offset = 3;
+        app.start_block_selection();
+        for _ in 0..3 {
+            app.move_selection_column(true);
+        }
+        app.viewport.selected_line = 1;
+        app.update_selection_end();
+        app.viewport.horizontal_offset = 0;
+
+        let area = Rect::new(0, 0, 120, 10);
+        let mut buf = Buffer::empty(area);
+        (&app).render(area, &mut buf);
+        let selected = |y: u16| {
+            (0..area.width)
+                .filter(|&x| buf[(x, y)].bg == app.theme.selection_bg)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+        };
+
+        // The highlighted cells follow the indicators in front of the text
+        assert_eq!(selected(1), "1234");
+        assert_eq!(selected(2), "5678");
+    }
+}
@@ -1,13 +1,22 @@
 pub mod colors;
 mod explorer;
 mod footer;
+mod inspector;
 mod lists;
 mod logview;
+mod notes;
 mod popups;
+mod regex_tester;
 mod scrollable_list;
+mod timeline;
+mod watches;
 
 use crate::app::{App, Overlay, ViewState};
-use colors::{GRAY_COLOR, WHITE_COLOR};
+use crate::clipboard::CopyFormat;
+use crate::options::AppOption;
+use crate::template::MAX_NOISE_SUGGESTIONS;
+use colors::{ALERT_TITLE_BG, WHITE_COLOR};
+pub use notes::{NOTES_POPUP_HEIGHT, NOTES_POPUP_WIDTH};
 pub use popups::popup_area;
 use ratatui::{
     buffer::Buffer,
@@ -24,29 +33,60 @@ impl Widget for &App {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let [top, middle, bottom] =
             Layout::vertical([Constraint::Length(1), Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        let middle = if self.options.is_enabled(AppOption::StickyHeader) {
+            let [header, middle] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(middle);
+            self.render_sticky_header(&self.viewport, header, buf);
+            middle
+        } else {
+            middle
+        };
 
-        let [log_view_area, scrollbar_area] =
-            Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(middle);
+        let (log_view_area, minimap_area, scrollbar_area) = self.split_scrollbar_area(middle);
 
         // Title
         let title_middle = Line::from(" Lazylog ").centered();
         let title_right = Line::from(format!("v{}", env!("CARGO_PKG_VERSION")))
             .right_aligned()
             .style(Style::default().fg(WHITE_COLOR));
+        // The title bar lights up while an alert is shown
+        let title_bg = if self.alert_toast.is_some() {
+            ALERT_TITLE_BG
+        } else {
+            self.theme.footer_bg
+        };
         let title = Block::default()
             .title_bottom(title_middle)
             .title_bottom(title_right)
-            .style(Style::default().bg(GRAY_COLOR));
+            .style(Style::default().bg(title_bg));
         title.render(top, buf);
 
         // Main view
-        self.render_log_view(log_view_area, buf);
-        self.render_scrollbar(scrollbar_area, buf);
+        if let Some(split_viewport) = self.split_viewport.as_ref().filter(|_| self.split_pinned) {
+            self.render_pinned_split_view(split_viewport, middle, buf);
+        } else if let Some(split_viewport) = &self.split_viewport {
+            self.render_split_view(split_viewport, middle, buf);
+        } else {
+            self.render_log_view(&self.viewport, true, log_view_area, buf);
+            self.render_minimap(&self.viewport, true, minimap_area, buf);
+            self.render_scrollbar(&self.viewport, scrollbar_area, buf);
+        }
+        if let Some(task) = &self.filter_task {
+            self.render_filter_progress(task.progress().percent(), log_view_area, buf);
+        }
+        if self.show_watches && !self.watches.is_empty() {
+            self.render_watches(log_view_area, buf);
+        }
+        if let Some((message, _)) = &self.alert_toast {
+            self.render_alert_toast(message, log_view_area, buf);
+        }
+        if self.show_debug_overlay {
+            self.render_debug_overlay(log_view_area, buf);
+        }
 
         // Footer
         match (&self.view_state, &self.overlay) {
             (ViewState::ActiveSearchMode, _) => self.render_search_footer(bottom, buf),
-            (ViewState::GotoLineMode, _) => self.render_goto_line_footer(bottom, buf),
+            (ViewState::GotoLineMode | ViewState::GotoTimeMode, _) => self.render_goto_line_footer(bottom, buf),
             (ViewState::ActiveFilterMode, _) => self.render_filter_footer(bottom, buf),
             (ViewState::SelectionMode, _) => self.render_selection_footer(bottom, buf),
             _ => self.render_default_footer(bottom, buf),
@@ -59,21 +99,138 @@ impl Widget for &App {
                 self.render_filter_list(filter_area, buf);
             }
             ViewState::OptionsView => {
-                let options_area = popup_area(area, 40, 9);
+                let options_area = popup_area(area, self.options_width(), self.options.count() as u16 + 2);
                 self.render_options(options_area, buf);
             }
             ViewState::EventsView => {
-                let events_area = popup_area(area, 118, 35);
-                self.render_events_list(events_area, buf);
+                self.render_events_list(area, buf);
             }
             ViewState::MarksView => {
-                let marks_area = popup_area(area, 118, 35);
-                self.render_marks_list(marks_area, buf);
+                self.render_marks_list(area, buf);
             }
             ViewState::FilesView => {
                 let files_area = popup_area(area, 100, 8);
                 self.render_files_list(files_area, buf);
             }
+            ViewState::SessionsView => {
+                let sessions_area = popup_area(area, 60, (self.sessions.len() as u16).clamp(1, 20) + 2);
+                self.render_sessions_list(sessions_area, buf);
+            }
+            ViewState::HistoryView => {
+                let history_count = self.history_list_state.item_count();
+                let history_area = popup_area(area, 100, (history_count as u16).min(20) + 2);
+                self.render_history_list(history_area, buf);
+            }
+            ViewState::ScriptsView => {
+                let scripts_area = popup_area(area, 100, (self.scripts.len() as u16).min(20) + 2);
+                self.render_scripts_list(scripts_area, buf);
+            }
+            ViewState::AlertsView => {
+                let alerts_count = self.alerts_list_state.item_count();
+                let alerts_area = popup_area(area, 118, (alerts_count as u16).min(20) + 2);
+                self.render_alerts_list(alerts_area, buf);
+            }
+            ViewState::CorrelationView => {
+                let correlation_count = self.correlation_list_state.item_count();
+                let correlation_area = popup_area(area, 118, (correlation_count as u16).min(30) + 2);
+                self.render_correlation_list(correlation_area, buf);
+            }
+            ViewState::RegistersView => {
+                let registers_count = self.registers_list_state.item_count();
+                let registers_area = popup_area(area, 100, (registers_count as u16).clamp(1, 20) + 2);
+                self.render_registers_list(registers_area, buf);
+            }
+            ViewState::JumpHistoryView => {
+                let jump_count = self.jump_history_list_state.item_count();
+                let jump_history_area = popup_area(area, 118, (jump_count as u16).min(20) + 2);
+                self.render_jump_history_list(jump_history_area, buf);
+            }
+            ViewState::FullLineView => {
+                let width = area.width.saturating_sub(4).min(118);
+                let row_count = self.full_line_list_state.item_count();
+                let full_line_area = popup_area(area, width, (row_count as u16).min(35) + 2);
+                self.render_full_line(full_line_area, buf);
+            }
+            ViewState::HexView => {
+                let row_count = self.hex_list_state.item_count().max(1);
+                let hex_area = popup_area(area, 82, row_count.min(35) as u16 + 2);
+                self.render_hex_view(hex_area, buf);
+            }
+            ViewState::FormatView => {
+                let format_count = self.format_list_state.item_count();
+                let format_area = popup_area(area, 70, format_count as u16 + 2);
+                self.render_format_list(format_area, buf);
+            }
+            ViewState::StaleFiltersView => {
+                let stale_count = self.stale_filters_list_state.item_count();
+                let stale_area = popup_area(area, 70, (stale_count as u16).min(20) + 4);
+                self.render_stale_filters(stale_area, buf);
+            }
+            ViewState::QuickFilterView => {
+                let quick_filter_count = self.quick_filter_list_state.item_count();
+                let quick_filter_area = popup_area(area, 90, quick_filter_count as u16 + 4);
+                self.render_quick_filters(quick_filter_area, buf);
+            }
+            ViewState::ProfilesView => {
+                let profile_count = self.profiles_list_state.item_count();
+                let profiles_area = popup_area(area, 70, (profile_count as u16).min(20) + 2);
+                self.render_profiles_list(profiles_area, buf);
+            }
+            ViewState::RestartsView => {
+                let restart_count = self.restarts_list_state.item_count();
+                let restarts_area = popup_area(area, 118, (restart_count as u16).min(20) + 2);
+                self.render_restarts_list(restarts_area, buf);
+            }
+            ViewState::ActionsView => {
+                let actions_area = popup_area(area, 100, (self.action_choices.len() as u16).min(20) + 2);
+                self.render_actions_list(actions_area, buf);
+            }
+            ViewState::InspectorView => {
+                let width = area.width.saturating_sub(4).min(118);
+                let inspector_area = popup_area(area, width, self.line_inspector_height(width).min(35));
+                self.render_line_inspector(inspector_area, buf);
+            }
+            ViewState::PipeOutputView => {
+                let output_area = popup_area(area, 118, 35);
+                self.render_pipe_output(output_area, buf);
+            }
+            ViewState::QueryResultsView => {
+                let results_area = popup_area(area, 118, 35);
+                self.render_query_results(results_area, buf);
+            }
+            ViewState::PatternsView => {
+                let patterns_area = popup_area(area, 118, 35);
+                self.render_patterns(patterns_area, buf);
+            }
+            ViewState::NoiseView => {
+                let noise_area = popup_area(area, 118, MAX_NOISE_SUGGESTIONS as u16 + 2);
+                self.render_noise_suggestions(noise_area, buf);
+            }
+            ViewState::NotesView => {
+                let notes_area = popup_area(area, NOTES_POPUP_WIDTH, NOTES_POPUP_HEIGHT);
+                self.render_notes(notes_area, buf);
+            }
+            ViewState::LevelsView => {
+                let levels_area = popup_area(area, 30, 9);
+                self.render_levels_list(levels_area, buf);
+            }
+            ViewState::CopyFormatView => {
+                let format_area = popup_area(area, 40, CopyFormat::ALL.len() as u16 + 2);
+                self.render_copy_format_list(format_area, buf);
+            }
+            ViewState::EventScopeView => {
+                let scope_count = self.event_scope_list_state.item_count();
+                let scope_area = popup_area(area, 50, (scope_count as u16).min(20) + 2);
+                self.render_event_scope_list(scope_area, buf);
+            }
+            ViewState::ColumnsView => {
+                let columns_area = popup_area(area, 44, self.column_view.columns().len() as u16 + 2);
+                self.render_columns_list(columns_area, buf);
+            }
+            ViewState::TimelineView => {
+                let timeline_area = popup_area(area, 118, 16);
+                self.render_timeline(timeline_area, buf);
+            }
             _ => {}
         }
 
@@ -88,15 +245,39 @@ impl Widget for &App {
                 Overlay::EventsFilter => {
                     self.render_event_filter_popup(overlay_area.unwrap(), buf);
                 }
+                Overlay::ListFilter => {
+                    // Shown in the border of the events or marks list
+                }
                 Overlay::MarkName => {
                     self.render_mark_name_input_popup(overlay_area.unwrap(), buf);
                 }
                 Overlay::SaveToFile => {
                     self.render_save_to_file_popup(overlay_area.unwrap(), buf);
                 }
+                Overlay::SaveSession => {
+                    self.render_save_session_popup(overlay_area.unwrap(), buf);
+                }
                 Overlay::AddCustomEvent => {
                     self.render_add_custom_event_popup(overlay_area.unwrap(), buf);
                 }
+                Overlay::RegexTester => {
+                    self.render_regex_tester(overlay_area.unwrap(), buf);
+                }
+                Overlay::PipeCommand => {
+                    self.render_pipe_command_popup(overlay_area.unwrap(), buf);
+                }
+                Overlay::Query => {
+                    self.render_query_popup(overlay_area.unwrap(), buf);
+                }
+                Overlay::Correlate => {
+                    self.render_correlate_popup(overlay_area.unwrap(), buf);
+                }
+                Overlay::ExportFilters => {
+                    self.render_export_filters_popup(overlay_area.unwrap(), buf);
+                }
+                Overlay::ExportReport => {
+                    self.render_export_report_popup(overlay_area.unwrap(), buf);
+                }
                 Overlay::AddFile => {
                     self.render_file_explorer(overlay_area.unwrap(), buf);
                 }
@@ -115,7 +296,7 @@ impl Widget for &App {
         // Help popup
         if self.help.is_visible() {
             let help_area = popup_area(area, 50, 32);
-            self.help.render(help_area, buf);
+            self.help.render(help_area, self.theme.help_bg, buf);
         }
     }
 }
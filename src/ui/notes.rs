@@ -0,0 +1,100 @@
+use super::colors::{NOTES_BORDER, NOTES_FG, NOTES_MISSING_REFERENCE_FG, NOTES_REFERENCE_FG};
+use crate::app::App;
+use crate::notes::find_mark_references;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+use std::collections::HashSet;
+
+pub const NOTES_POPUP_WIDTH: u16 = 100;
+pub const NOTES_POPUP_HEIGHT: u16 = 30;
+
+impl App {
+    pub(super) fn render_notes(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Notes ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" Ctrl+g: go to @mark | Esc: close ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(NOTES_BORDER));
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        if self.notes.is_empty() {
+            Paragraph::new("Write down findings here. Reference marks with @name or @{name with spaces}.")
+                .style(Style::default().fg(NOTES_MISSING_REFERENCE_FG))
+                .render(inner_area, buf);
+            return;
+        }
+
+        let (top, left) = self
+            .notes
+            .scroll_to_cursor(inner_area.height as usize, inner_area.width as usize);
+        let mark_names: HashSet<&str> = self
+            .marking
+            .get_marks()
+            .iter()
+            .filter_map(|mark| mark.name.as_deref())
+            .collect();
+
+        let lines: Vec<Line> = self
+            .notes
+            .lines()
+            .iter()
+            .skip(top)
+            .take(inner_area.height as usize)
+            .map(|line| build_notes_line(line, left, inner_area.width as usize, &mark_names))
+            .collect();
+
+        Paragraph::new(lines).render(inner_area, buf);
+    }
+}
+
+/// Builds a display line for the visible part of a notes line, with mark references highlighted.
+fn build_notes_line(line: &str, left: usize, width: usize, mark_names: &HashSet<&str>) -> Line<'static> {
+    let chars: Vec<char> = line.chars().collect();
+    let end = (left + width).min(chars.len());
+    if left >= end {
+        return Line::default();
+    }
+
+    let text_style = Style::default().fg(NOTES_FG);
+    let mut spans = Vec::new();
+    let mut pos = left;
+
+    for reference in find_mark_references(line) {
+        let start = reference.start.max(left);
+        let ref_end = reference.end.min(end);
+        if start >= ref_end {
+            continue;
+        }
+
+        if start > pos {
+            spans.push(Span::styled(chars[pos..start].iter().collect::<String>(), text_style));
+        }
+        let style = if mark_names.contains(reference.name.as_str()) {
+            Style::default()
+                .fg(NOTES_REFERENCE_FG)
+                .add_modifier(Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(NOTES_MISSING_REFERENCE_FG)
+        };
+        spans.push(Span::styled(chars[start..ref_end].iter().collect::<String>(), style));
+        pos = ref_end;
+    }
+
+    if pos < end {
+        spans.push(Span::styled(chars[pos..end].iter().collect::<String>(), text_style));
+    }
+
+    Line::from(spans)
+}
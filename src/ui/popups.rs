@@ -1,11 +1,16 @@
-use super::colors::{ERROR_BORDER, ERROR_FG, FATAL_BORDER, MESSAGE_BORDER, MESSAGE_INFO_FG, WHITE_COLOR};
-use crate::app::App;
-use ratatui::widgets::{BorderType, Padding};
+use super::colors::{
+    ALERT_BORDER, ALERT_FG, DEBUG_BORDER, DEBUG_LABEL_FG, ERROR_BORDER, ERROR_FG, FATAL_BORDER, FILTER_PROGRESS_BORDER,
+    FILTER_PROGRESS_FG, MESSAGE_BORDER, MESSAGE_INFO_FG, WHITE_COLOR,
+};
+use crate::app::{App, ViewState};
+use crate::debug_stats::{format_bytes, format_millis};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{BorderType, Padding, Wrap};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
     style::{Color, Style},
-    widgets::{Block, Borders, Clear, Paragraph, Widget},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Widget},
 };
 
 /// Calculates a centered popup area within the given rect.
@@ -43,15 +48,25 @@ impl App {
         buf: &mut Buffer,
     ) {
         let lines: Vec<&str> = message.split('\n').collect();
-        let max_line_width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let max_line_width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
 
         let popup_width = (max_line_width as u16 + 6).min(area.width.saturating_sub(4));
-        let popup_height = (lines.len() as u16 + 4).min(area.height.saturating_sub(4));
+        let text_width = popup_width.saturating_sub(4).max(1) as usize;
+        let wrapped_lines: usize = lines
+            .iter()
+            .map(|line| line.chars().count().div_ceil(text_width).max(1))
+            .sum();
+        let popup_height = (wrapped_lines as u16 + 4).min(area.height.saturating_sub(4));
         let popup_area = popup_area(area, popup_width, popup_height);
 
+        let text_height = popup_height.saturating_sub(4) as usize;
+        let max_scroll = wrapped_lines.saturating_sub(text_height);
+        self.message_max_scroll.set(max_scroll);
+        let scroll = self.message_scroll.min(max_scroll);
+
         Clear.render(popup_area, buf);
 
-        let block = Block::default()
+        let mut block = Block::default()
             .title(format!(" {} ", title))
             .title_style(Style::default().fg(title_color))
             .title_alignment(Alignment::Center)
@@ -60,7 +75,30 @@ impl App {
             .border_style(Style::default().fg(border_color))
             .padding(Padding::uniform(1));
 
-        let popup = Paragraph::new(message).block(block).alignment(Alignment::Center);
+        if let Some(status) = &self.message_status {
+            block = block.title_bottom(Line::from(format!(" {} ", status)).centered());
+        } else if max_scroll > 0 {
+            block = block.title_bottom(
+                Line::from(format!(
+                    " {}/{} | j/k: scroll | y: copy ",
+                    scroll + text_height,
+                    wrapped_lines
+                ))
+                .centered(),
+            );
+        }
+
+        // Long messages are easier to read left-aligned
+        let alignment = if max_scroll > 0 {
+            Alignment::Left
+        } else {
+            Alignment::Center
+        };
+        let popup = Paragraph::new(message)
+            .block(block)
+            .alignment(alignment)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll as u16, 0));
 
         popup.render(popup_area, buf);
     }
@@ -80,6 +118,125 @@ impl App {
         self.render_popup(error_msg, "Fatal Error", ERROR_FG, FATAL_BORDER, area, buf);
     }
 
+    /// Renders the progress of a background filter run in the top right corner of the area.
+    pub(super) fn render_filter_progress(&self, percent: usize, area: Rect, buf: &mut Buffer) {
+        let width = 30.min(area.width);
+        let progress_area = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.y,
+            width,
+            height: 3.min(area.height),
+        };
+        Clear.render(progress_area, buf);
+
+        Gauge::default()
+            .block(
+                Block::default()
+                    .title(" Filtering ")
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(FILTER_PROGRESS_BORDER)),
+            )
+            .gauge_style(Style::default().fg(FILTER_PROGRESS_FG))
+            .percent(percent.min(100) as u16)
+            .render(progress_area, buf);
+    }
+
+    /// Renders the most recently fired alert in the top right corner of the log view.
+    pub(super) fn render_alert_toast(&self, message: &str, area: Rect, buf: &mut Buffer) {
+        let width = (message.chars().count() as u16 + 4).clamp(24, 60).min(area.width);
+        let toast_area = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.y,
+            width,
+            height: 3.min(area.height),
+        };
+        Clear.render(toast_area, buf);
+
+        Paragraph::new(message)
+            .block(
+                Block::default()
+                    .title(" Alert ")
+                    .title_bottom(Line::from(" A: all alerts ").right_aligned())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(ALERT_BORDER)),
+            )
+            .style(Style::default().fg(ALERT_FG))
+            .render(toast_area, buf);
+    }
+
+    /// Renders the debug overlay with render, filter and event scan timings in the top left corner of the log view.
+    pub(super) fn render_debug_overlay(&self, area: Rect, buf: &mut Buffer) {
+        let millis = |duration: Option<std::time::Duration>| duration.map_or("-".to_string(), format_millis);
+        let frame = match self.debug_stats.frame_time_stats() {
+            Some((average, max)) => format!(
+                "{} (avg {}, max {})",
+                millis(self.debug_stats.last_frame_time()),
+                format_millis(average),
+                format_millis(max)
+            ),
+            None => "-".to_string(),
+        };
+        let (hits, misses) = self.highlighter.cache_stats();
+        let cache = if hits + misses == 0 {
+            "-".to_string()
+        } else {
+            format!(
+                "{:.1}% ({} of {})",
+                hits as f64 * 100.0 / (hits + misses) as f64,
+                hits,
+                hits + misses
+            )
+        };
+        let buffer = format!(
+            "{} ({} lines)",
+            format_bytes(
+                self.debug_stats
+                    .memory_usage(self.log_buffer.generation(), || self.log_buffer.memory_usage())
+            ),
+            self.log_buffer.get_total_lines_count()
+        );
+
+        let rows = [
+            ("Frame", frame),
+            ("Filters", millis(self.debug_stats.filter_time)),
+            ("Event scan", millis(self.event_tracker.last_scan_time())),
+            ("Highlight cache", cache),
+            ("Buffer", buffer),
+        ];
+        let lines: Vec<Line> = rows
+            .into_iter()
+            .map(|(label, value)| {
+                Line::from(vec![
+                    Span::styled(format!("{:<16}", label), Style::default().fg(DEBUG_LABEL_FG)),
+                    Span::styled(value, Style::default().fg(WHITE_COLOR)),
+                ])
+            })
+            .collect();
+
+        let width = (lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4).min(area.width);
+        let overlay_area = Rect {
+            x: area.x,
+            y: area.y,
+            width,
+            height: (lines.len() as u16 + 2).min(area.height),
+        };
+        Clear.render(overlay_area, buf);
+
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(" Debug ")
+                    .title_bottom(Line::from(" F12 ").right_aligned())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(DEBUG_BORDER))
+                    .padding(Padding::horizontal(1)),
+            )
+            .render(overlay_area, buf);
+    }
+
     /// Renders the save to file bar footer in SaveToFileMode.
     pub(super) fn render_save_to_file_popup(&self, area: Rect, buf: &mut Buffer) {
         Clear.render(area, buf);
@@ -99,4 +256,148 @@ impl App {
 
         popup.render(area, buf);
     }
+
+    /// Renders the input popup for the name to save the session under.
+    pub(super) fn render_save_session_popup(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let prompt = self.input.value();
+        let popup = Paragraph::new(prompt)
+            .block(
+                Block::default()
+                    .title(" Save session as ")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(WHITE_COLOR)),
+            )
+            .style(Style::default().fg(WHITE_COLOR))
+            .alignment(Alignment::Left);
+
+        popup.render(area, buf);
+    }
+
+    /// Renders the input popup for exporting filters to a TOML file.
+    pub(super) fn render_export_filters_popup(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let prompt = self.input.value();
+        let popup = Paragraph::new(prompt)
+            .block(
+                Block::default()
+                    .title(" Export filters to file ")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(WHITE_COLOR)),
+            )
+            .style(Style::default().fg(WHITE_COLOR))
+            .alignment(Alignment::Left);
+
+        popup.render(area, buf);
+    }
+
+    /// Renders the input popup for exporting the listed events and marks to a CSV or JSON file.
+    pub(super) fn render_export_report_popup(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let title = if self.view_state == ViewState::MarksView {
+            " Export marks to file (.csv or .json) "
+        } else {
+            " Export events to file (.csv or .json) "
+        };
+
+        let prompt = self.input.value();
+        let popup = Paragraph::new(prompt)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(WHITE_COLOR)),
+            )
+            .style(Style::default().fg(WHITE_COLOR))
+            .alignment(Alignment::Left);
+
+        popup.render(area, buf);
+    }
+
+    /// Renders the input popup for entering a shell command to pipe log lines through.
+    pub(super) fn render_pipe_command_popup(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let title = if self.view_state == ViewState::SelectionMode {
+            " Pipe selection through command "
+        } else {
+            " Pipe visible lines through command "
+        };
+
+        let prompt = self.input.value();
+        let popup = Paragraph::new(prompt)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(WHITE_COLOR)),
+            )
+            .style(Style::default().fg(WHITE_COLOR))
+            .alignment(Alignment::Left);
+
+        popup.render(area, buf);
+    }
+
+    pub(super) fn render_query_popup(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let hint = if self.column_view.is_available() {
+            " field=value AND field>1 | count by field "
+        } else {
+            " level=ERROR AND line~text | count by level "
+        };
+
+        let prompt = self.input.value();
+        let popup = Paragraph::new(prompt)
+            .block(
+                Block::default()
+                    .title(" Query visible lines ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::styled(hint, Style::default().fg(Color::DarkGray)).centered())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(WHITE_COLOR)),
+            )
+            .style(Style::default().fg(WHITE_COLOR))
+            .alignment(Alignment::Left);
+
+        popup.render(area, buf);
+    }
+
+    pub(super) fn render_correlate_popup(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let prompt = self.input.value();
+        let popup = Paragraph::new(prompt)
+            .block(
+                Block::default()
+                    .title(" Correlate lines by id ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(
+                        Line::styled(
+                            " request_id=(?P<request_id>\\w+) ",
+                            Style::default().fg(Color::DarkGray),
+                        )
+                        .centered(),
+                    )
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(WHITE_COLOR)),
+            )
+            .style(Style::default().fg(WHITE_COLOR))
+            .alignment(Alignment::Left);
+
+        popup.render(area, buf);
+    }
 }
@@ -0,0 +1,114 @@
+use super::colors::{
+    ERROR_FG, REGEX_TESTER_BORDER, REGEX_TESTER_CAPTURE_FG, REGEX_TESTER_HIGHLIGHT_BG, REGEX_TESTER_LINE_NUMBER_FG,
+    REGEX_TESTER_MATCH_BG, REGEX_TESTER_MATCH_FG, WHITE_COLOR,
+};
+use crate::app::App;
+use crate::regex_tester::RegexTestMatch;
+use crate::ui::scrollable_list::ScrollableList;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+impl App {
+    pub(super) fn render_regex_tester(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let case_str = if self.regex_tester.is_case_sensitive() {
+            "Aa"
+        } else {
+            "aa"
+        };
+        let block = Block::default()
+            .title(format!(" Regex Tester [{}] ", case_str))
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(
+                Line::from(" Alt+f: add filter | Alt+e: add event | Alt+h: add highlight | Alt+w: add watch ")
+                    .centered(),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(REGEX_TESTER_BORDER));
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let [input_area, status_area, results_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1), Constraint::Fill(1)]).areas(inner_area);
+
+        Paragraph::new(self.input.value())
+            .style(Style::default().fg(WHITE_COLOR))
+            .render(input_area, buf);
+
+        let status = if let Some(error) = self.regex_tester.error() {
+            // Only show the last line of the regex error, which describes the problem
+            let message = error.lines().last().unwrap_or(error).trim();
+            Line::from(message.to_string()).style(Style::default().fg(ERROR_FG))
+        } else if self.input.value().is_empty() {
+            Line::from("Type a regex to test it against the visible lines").style(Style::default().fg(WHITE_COLOR))
+        } else {
+            Line::from(format!(
+                "{} of {} lines match",
+                self.regex_tester.match_count(),
+                self.regex_tester.line_count()
+            ))
+            .style(Style::default().fg(WHITE_COLOR))
+        };
+        status.render(status_area, buf);
+
+        let items: Vec<Line> = self.regex_tester.matches().iter().map(build_match_line).collect();
+
+        let (list_area, _) = ScrollableList::new(items)
+            .selection(
+                self.regex_tester_list_state.selected_index(),
+                self.regex_tester_list_state.viewport_offset(),
+            )
+            .highlight_style(Style::default().bg(REGEX_TESTER_HIGHLIGHT_BG))
+            .render(results_area, buf, Block::default().borders(Borders::TOP));
+
+        self.regex_tester_list_state
+            .set_viewport_height(list_area.height as usize);
+    }
+}
+
+/// Builds a display line for a match, with the capture groups in front of the line and the matched parts highlighted.
+fn build_match_line(test_match: &RegexTestMatch) -> Line<'_> {
+    let match_style = Style::default().fg(REGEX_TESTER_MATCH_FG).bg(REGEX_TESTER_MATCH_BG);
+    let content = test_match.content.as_str();
+
+    let mut spans = vec![Span::styled(
+        format!("{:>6} ", test_match.log_index + 1),
+        Style::default().fg(REGEX_TESTER_LINE_NUMBER_FG),
+    )];
+
+    if !test_match.captures.is_empty() {
+        let captures = test_match
+            .captures
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join(" ");
+        spans.push(Span::styled(
+            format!("{} │ ", captures),
+            Style::default().fg(REGEX_TESTER_CAPTURE_FG),
+        ));
+    }
+
+    let mut pos = 0;
+    for &(start, end) in &test_match.match_ranges {
+        if start > pos {
+            spans.push(Span::raw(&content[pos..start]));
+        }
+        spans.push(Span::styled(&content[start..end], match_style));
+        pos = end;
+    }
+    if pos < content.len() {
+        spans.push(Span::raw(&content[pos..]));
+    }
+
+    Line::from(spans)
+}
@@ -0,0 +1,126 @@
+use super::colors::{
+    TIMELINE_AXIS_FG, TIMELINE_BAR_FG, TIMELINE_BORDER, TIMELINE_SELECTED_BAR_FG, TIMELINE_SELECTED_BG, WHITE_COLOR,
+};
+use crate::app::App;
+use crate::timeline::{Timeline, format_duration};
+use chrono::{DateTime, Utc};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+/// Block characters for drawing bars in eighths of a cell.
+const BAR_SYMBOLS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+impl App {
+    pub(super) fn render_timeline(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Event Timeline ")
+            .title_alignment(Alignment::Center)
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" ←/→: select time slot | Enter: go to first event ").centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TIMELINE_BORDER));
+
+        let Some(timeline) = &self.timeline else {
+            Paragraph::new("No events found")
+                .block(block)
+                .alignment(Alignment::Center)
+                .render(area, buf);
+            return;
+        };
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let [chart_area, axis_area, info_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1), Constraint::Length(1)]).areas(inner_area);
+
+        let selected = self.timeline_list_state.selected_index();
+        let column_width = (chart_area.width as usize / timeline.buckets().len().max(1)).max(1);
+        render_bars(timeline, selected, column_width, chart_area, buf);
+        render_axis(timeline, axis_area, buf);
+
+        if let Some(bucket) = timeline.buckets().get(selected) {
+            let end = bucket.start + timeline.bucket_duration();
+            let info = format!(
+                "{} - {}  {} event{}  ({} per column)",
+                format_time(timeline, bucket.start),
+                format_time(timeline, end),
+                bucket.count,
+                if bucket.count == 1 { "" } else { "s" },
+                format_duration(timeline.bucket_duration())
+            );
+            Line::from(info)
+                .style(Style::default().fg(WHITE_COLOR))
+                .centered()
+                .render(info_area, buf);
+        }
+    }
+}
+
+/// Draws one vertical bar per bucket, scaled to the bucket with the most events.
+fn render_bars(timeline: &Timeline, selected: usize, column_width: usize, area: Rect, buf: &mut Buffer) {
+    let max_count = timeline.max_count().max(1);
+    let total_eighths = area.height as usize * 8;
+
+    for (i, bucket) in timeline.buckets().iter().enumerate() {
+        let bar_eighths = if bucket.count == 0 {
+            0
+        } else {
+            (bucket.count * total_eighths).div_ceil(max_count).max(1)
+        };
+        let style = if i == selected {
+            Style::default().fg(TIMELINE_SELECTED_BAR_FG).bg(TIMELINE_SELECTED_BG)
+        } else {
+            Style::default().fg(TIMELINE_BAR_FG)
+        };
+
+        for offset in 0..column_width {
+            let x = area.x as usize + i * column_width + offset;
+            if x >= area.right() as usize {
+                return;
+            }
+            for row in 0..area.height {
+                let fill = bar_eighths.saturating_sub(row as usize * 8).min(8);
+                let y = area.bottom() - 1 - row;
+                buf[(x as u16, y)].set_symbol(BAR_SYMBOLS[fill]).set_style(style);
+            }
+        }
+    }
+}
+
+/// Draws the start and end time of the timeline below the bars.
+fn render_axis(timeline: &Timeline, area: Rect, buf: &mut Buffer) {
+    let (Some(first), Some(last)) = (timeline.buckets().first(), timeline.buckets().last()) else {
+        return;
+    };
+    let style = Style::default().fg(TIMELINE_AXIS_FG);
+
+    Line::from(format_time(timeline, first.start))
+        .style(style)
+        .render(area, buf);
+    Line::from(format_time(timeline, last.start + timeline.bucket_duration()))
+        .style(style)
+        .right_aligned()
+        .render(area, buf);
+}
+
+/// Formats a timestamp with just the time when the timeline is within a single day.
+fn format_time(timeline: &Timeline, ts: DateTime<Utc>) -> String {
+    let same_day = match (timeline.buckets().first(), timeline.buckets().last()) {
+        (Some(first), Some(last)) => first.start.date_naive() == last.start.date_naive(),
+        _ => true,
+    };
+    if same_day {
+        ts.format("%H:%M:%S").to_string()
+    } else {
+        ts.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
@@ -0,0 +1,109 @@
+use super::colors::{WATCH_BORDER, WATCH_RANGE_FG, WATCH_SPARKLINE_FG, WHITE_COLOR};
+use crate::app::App;
+use crate::watch::WatchExpression;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Sparkline, Widget},
+};
+
+/// Width of the watch panel, including borders.
+const WATCH_PANEL_WIDTH: u16 = 50;
+
+/// Number of levels a sparkline value is scaled to.
+const SPARKLINE_LEVELS: u64 = 8;
+
+impl App {
+    /// Renders the watch expression sparklines in the bottom right corner of the log view.
+    pub(super) fn render_watches(&self, area: Rect, buf: &mut Buffer) {
+        let width = WATCH_PANEL_WIDTH.min(area.width);
+        let height = (self.watches.count() as u16 * 2 + 2).min(area.height);
+        let panel_area = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.bottom().saturating_sub(height),
+            width,
+            height,
+        };
+        Clear.render(panel_area, buf);
+
+        let block = Block::default()
+            .title(" Watches ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(WATCH_BORDER));
+        let inner_area = block.inner(panel_area);
+        block.render(panel_area, buf);
+
+        for (i, watch) in self.watches.iter().enumerate() {
+            let y = inner_area.y + i as u16 * 2;
+            if y + 1 >= inner_area.bottom() {
+                break;
+            }
+            let label_area = Rect {
+                y,
+                height: 1,
+                ..inner_area
+            };
+            let sparkline_area = Rect {
+                y: y + 1,
+                height: 1,
+                ..inner_area
+            };
+            render_label(watch, label_area, buf);
+            render_sparkline(watch, sparkline_area, buf);
+        }
+    }
+}
+
+fn render_label(watch: &WatchExpression, area: Rect, buf: &mut Buffer) {
+    let value = watch.latest().map_or("-".to_string(), format_value);
+    let mut spans = vec![
+        Span::styled(format!("{}: ", watch.name), Style::default().fg(WHITE_COLOR).bold()),
+        Span::styled(value, Style::default().fg(WHITE_COLOR)),
+    ];
+    if let Some((min, max)) = watch.range() {
+        spans.push(Span::styled(
+            format!("  ({} .. {})", format_value(min), format_value(max)),
+            Style::default().fg(WATCH_RANGE_FG),
+        ));
+    }
+    Line::from(spans).render(area, buf);
+}
+
+fn render_sparkline(watch: &WatchExpression, area: Rect, buf: &mut Buffer) {
+    let values = watch.values();
+    let Some((min, max)) = watch.range() else {
+        return;
+    };
+    let span = max - min;
+
+    // Only the most recent values fit in the panel
+    let data: Vec<u64> = values
+        .iter()
+        .skip(values.len().saturating_sub(area.width as usize))
+        .map(|&v| {
+            if span > 0.0 {
+                1 + ((v - min) / span * (SPARKLINE_LEVELS - 1) as f64).round() as u64
+            } else {
+                SPARKLINE_LEVELS / 2
+            }
+        })
+        .collect();
+
+    Sparkline::default()
+        .data(&data)
+        .max(SPARKLINE_LEVELS)
+        .style(Style::default().fg(WATCH_SPARKLINE_FG))
+        .render(area, buf);
+}
+
+/// Formats a value without decimals if it is a whole number.
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
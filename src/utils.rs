@@ -1,10 +1,42 @@
-/// Returns true if the haystack contains the needle, ignoring ASCII case.
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// How case-insensitive matching folds the case of characters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaseFolding {
+    /// Folds the case of all letters, so `errör` matches `ERRÖR`.
+    #[default]
+    Unicode,
+    /// Folds the case of ASCII letters only, which is faster.
+    Ascii,
+}
+
+impl CaseFolding {
+    /// Returns the folding of all letters if enabled, or of ASCII letters only.
+    pub fn new(unicode: bool) -> Self {
+        if unicode { Self::Unicode } else { Self::Ascii }
+    }
+
+    /// Returns whether the needle can be matched by comparing bytes with ASCII case folding.
+    fn is_ascii(self, needle: &str) -> bool {
+        needle.is_ascii() || self == Self::Ascii
+    }
+}
+
+/// Returns true if the haystack contains the needle, ignoring case.
 ///
-/// Uses a sliding window approach for efficient matching.
-pub fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+/// ASCII needles use a sliding window over the bytes. Other needles are compared with Unicode
+/// lowercase folding, so `errör` matches `ERRÖR`, unless only ASCII letters are folded.
+pub fn contains_ignore_case(haystack: &str, needle: &str, folding: CaseFolding) -> bool {
     if needle.is_empty() {
         return true;
     }
+    if !folding.is_ascii(needle) {
+        let folded_needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+        return haystack
+            .char_indices()
+            .any(|(start, _)| folded_match_len(&haystack[start..], &folded_needle).is_some());
+    }
     if needle.len() > haystack.len() {
         return false;
     }
@@ -15,29 +47,340 @@ pub fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
         .any(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
 }
 
+/// Finds all occurrences of the needle in the haystack ignoring case, as byte ranges of the haystack.
+///
+/// Occurrences of ASCII needles may overlap. Occurrences of other needles don't, and their length
+/// in the haystack may differ from the needle's as the case of a character can have another length.
+pub fn find_ignore_case(haystack: &str, needle: &str, folding: CaseFolding) -> Vec<(usize, usize)> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    if !folding.is_ascii(needle) {
+        let folded_needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+        let mut ranges = Vec::new();
+        let mut next_start = 0;
+        for (start, _) in haystack.char_indices() {
+            if start < next_start {
+                continue;
+            }
+            if let Some(len) = folded_match_len(&haystack[start..], &folded_needle) {
+                ranges.push((start, start + len));
+                next_start = start + len;
+            }
+        }
+        return ranges;
+    }
+
+    let needle_bytes = needle.as_bytes();
+    if needle_bytes.len() > haystack.len() {
+        return Vec::new();
+    }
+    haystack
+        .as_bytes()
+        .windows(needle_bytes.len())
+        .enumerate()
+        .filter(|(_, window)| window.eq_ignore_ascii_case(needle_bytes))
+        .map(|(idx, _)| (idx, idx + needle_bytes.len()))
+        .collect()
+}
+
+/// Returns true if the strings are equal ignoring case, folding non-ASCII characters like
+/// [`contains_ignore_case`].
+pub fn eq_ignore_case(a: &str, b: &str, folding: CaseFolding) -> bool {
+    if folding.is_ascii(b) {
+        return a.eq_ignore_ascii_case(b);
+    }
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .eq(b.chars().flat_map(char::to_lowercase))
+}
+
+/// Returns true if the character is part of a word, like `\w` in a regex.
+pub fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Returns true if the match at `start..end` of the text is a whole word, i.e. not continued by word
+/// characters before or after it. Like `\b` in a regex, an edge of the match that is not a word
+/// character itself may border anything.
+pub fn is_whole_word(text: &str, start: usize, end: usize) -> bool {
+    let continues = |inside: Option<char>, outside: Option<char>| {
+        inside.is_some_and(is_word_char) && outside.is_some_and(is_word_char)
+    };
+    let matched = &text[start..end];
+    !continues(matched.chars().next(), text[..start].chars().next_back())
+        && !continues(matched.chars().next_back(), text[end..].chars().next())
+}
+
+/// Returns true if the haystack contains the needle as a whole word, see [`is_whole_word`].
+pub fn contains_whole_word(haystack: &str, needle: &str, case_sensitive: bool, folding: CaseFolding) -> bool {
+    find_whole_words(haystack, needle, case_sensitive, folding)
+        .next()
+        .is_some()
+}
+
+/// Finds the occurrences of the needle in the haystack that are whole words, as byte ranges.
+pub fn find_whole_words<'a>(
+    haystack: &'a str,
+    needle: &str,
+    case_sensitive: bool,
+    folding: CaseFolding,
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    let ranges: Vec<(usize, usize)> = if needle.is_empty() {
+        Vec::new()
+    } else if case_sensitive {
+        haystack
+            .match_indices(needle)
+            .map(|(start, matched)| (start, start + matched.len()))
+            .collect()
+    } else {
+        find_ignore_case(haystack, needle, folding)
+    };
+    ranges
+        .into_iter()
+        .filter(move |&(start, end)| is_whole_word(haystack, start, end))
+}
+
+/// Returns the byte length of the start of the text matching the lowercased needle, if it matches.
+fn folded_match_len(text: &str, folded_needle: &[char]) -> Option<usize> {
+    let mut remaining = folded_needle;
+    for (i, c) in text.char_indices() {
+        for folded in c.to_lowercase() {
+            match remaining.split_first() {
+                Some((first, rest)) if *first == folded => remaining = rest,
+                _ => return None,
+            }
+        }
+        if remaining.is_empty() {
+            return Some(i + c.len_utf8());
+        }
+    }
+    None
+}
+
+/// Returns the word under the terminal column of the text, or the first word after it like vim's `*`.
+pub fn word_at_column(text: &str, column: usize) -> Option<&str> {
+    let (byte, _) = column_to_byte(text, column);
+    let start = if text[byte..].starts_with(is_word_char) {
+        text[..byte]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| is_word_char(c))
+            .last()
+            .map_or(byte, |(i, _)| i)
+    } else {
+        byte + text[byte..].find(is_word_char)?
+    };
+    let end = text[start..]
+        .find(|c| !is_word_char(c))
+        .map_or(text.len(), |offset| start + offset);
+    Some(&text[start..end])
+}
+
+/// Returns the number of terminal columns the text occupies.
+pub fn display_width(text: &str) -> usize {
+    if text.is_ascii() {
+        return text.len();
+    }
+    text.width()
+}
+
+/// Returns the terminal column at which the byte position `byte` of the text is displayed.
+pub fn byte_to_column(text: &str, byte: usize) -> usize {
+    display_width(&text[..byte.min(text.len())])
+}
+
+/// Returns the part of the text displayed in the terminal columns `start..end`.
+pub fn column_slice(text: &str, start: usize, end: usize) -> &str {
+    let (start, _) = column_to_byte(text, start);
+    let (end, _) = column_to_byte(text, end);
+    &text[start..end.max(start)]
+}
+
+/// Returns the start of the text at most `max_bytes` bytes long, cut at a character boundary.
+pub fn truncate_bytes(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Splits the text into rows of at most `width` columns, breaking anywhere so no character is lost.
+pub fn wrap_columns(text: &str, width: usize) -> Vec<&str> {
+    let mut rows = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (end, _) = column_to_byte(rest, width.max(1));
+        rows.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    if rows.is_empty() {
+        rows.push("");
+    }
+    rows
+}
+
+/// Finds where the text should start when the first `column` terminal columns are scrolled out of view.
+///
+/// Returns the byte position of the first grapheme starting at or after the column, and the number of
+/// columns still visible of a wide grapheme cut by the column, to be filled with padding.
+pub fn column_to_byte(text: &str, column: usize) -> (usize, usize) {
+    if column == 0 {
+        return (0, 0);
+    }
+    if text.is_ascii() {
+        return (column.min(text.len()), 0);
+    }
+
+    let mut current_column = 0;
+    for (byte, grapheme) in text.grapheme_indices(true) {
+        if current_column >= column {
+            return (byte, current_column - column);
+        }
+        current_column += grapheme.width();
+    }
+    (text.len(), current_column.saturating_sub(column))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_column_slice() {
+        assert_eq!(column_slice("id=42 user=bob", 3, 5), "42");
+        assert_eq!(column_slice("id=42", 3, 10), "42");
+        assert_eq!(column_slice("id", 3, 10), "");
+        assert_eq!(column_slice("名前=bob", 2, 7), "前=bo");
+    }
+
+    #[test]
+    fn test_truncate_bytes() {
+        assert_eq!(truncate_bytes("abcdef", 4), "abcd");
+        assert_eq!(truncate_bytes("abc", 4), "abc");
+        // "é" is 2 bytes and is not cut in half
+        assert_eq!(truncate_bytes("caféx", 4), "caf");
+        assert_eq!(wrap_columns("abcde", 2), vec!["ab", "cd", "e"]);
+    }
+
+    #[test]
+    fn test_word_at_column() {
+        let text = "GET /api/users_v2 took 12ms";
+        assert_eq!(word_at_column(text, 0), Some("GET"));
+        assert_eq!(word_at_column(text, 3), Some("api"));
+        assert_eq!(word_at_column(text, 12), Some("users_v2"));
+        assert_eq!(word_at_column(text, 25), Some("12ms"));
+        assert_eq!(word_at_column(text, 40), None);
+        assert_eq!(word_at_column("naïve café", 7), Some("café"));
+    }
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn test_column_to_byte() {
+        assert_eq!(column_to_byte("abcdef", 2), (2, 0));
+        assert_eq!(column_to_byte("abc", 10), (3, 0));
+        // Each CJK character is 3 bytes and 2 columns wide
+        assert_eq!(column_to_byte("日本語", 2), (3, 0));
+        assert_eq!(column_to_byte("日本語", 3), (6, 1));
+        // Combining characters stay with their base character
+        assert_eq!(column_to_byte("e\u{301}x", 1), (3, 0));
+        assert_eq!(byte_to_column("日本語x", 9), 6);
+    }
+
     #[test]
     fn test_contains_ignore_case_finds_different_cases() {
-        assert!(contains_ignore_case("ERROR: foo", "error"));
-        assert!(contains_ignore_case("error: foo", "ERROR"));
-        assert!(contains_ignore_case("Error: foo", "eRrOr"));
+        assert!(contains_ignore_case("ERROR: foo", "error", CaseFolding::Unicode));
+        assert!(contains_ignore_case("error: foo", "ERROR", CaseFolding::Unicode));
+        assert!(contains_ignore_case("Error: foo", "eRrOr", CaseFolding::Unicode));
+    }
+
+    #[test]
+    fn test_ignore_case_folds_unicode() {
+        assert!(contains_ignore_case(
+            "Fatal ERRÖR in Straße",
+            "errör",
+            CaseFolding::Unicode
+        ));
+        assert!(contains_ignore_case("ΣΦΆΛΜΑ δίσκου", "σφάλμα", CaseFolding::Unicode));
+        assert!(!contains_ignore_case("error", "errör", CaseFolding::Unicode));
+
+        assert_eq!(
+            find_ignore_case("ÖL und öl", "öl", CaseFolding::Unicode),
+            vec![(0, 3), (8, 11)]
+        );
+        // The dotted capital I lowercases to two characters
+        assert_eq!(find_ignore_case("xİy", "i\u{307}y", CaseFolding::Unicode), vec![(1, 4)]);
+        assert_eq!(
+            find_ignore_case("aaa", "AA", CaseFolding::Unicode),
+            vec![(0, 2), (1, 3)]
+        );
+
+        assert!(eq_ignore_case("ÅRHUS", "århus", CaseFolding::Unicode));
+        assert!(!eq_ignore_case("ÅRHUS", "arhus", CaseFolding::Unicode));
+    }
+
+    #[test]
+    fn test_ascii_case_folding() {
+        assert!(!contains_ignore_case("Fatal ERRÖR", "errör", CaseFolding::Ascii));
+        assert!(contains_ignore_case("Fatal ERRöR", "errör", CaseFolding::Ascii));
+        assert!(find_ignore_case("ÖL", "öl", CaseFolding::Ascii).is_empty());
+        assert!(!eq_ignore_case("ÅRHUS", "århus", CaseFolding::Ascii));
+        assert!(eq_ignore_case("ERROR", "error", CaseFolding::Ascii));
+    }
+
+    #[test]
+    fn test_whole_word_matching() {
+        assert!(!contains_whole_word(
+            "transferred 3 files",
+            "err",
+            true,
+            CaseFolding::Unicode
+        ));
+        assert!(contains_whole_word(
+            "transferred, err: disk full",
+            "err",
+            true,
+            CaseFolding::Unicode
+        ));
+        assert!(contains_whole_word(
+            "ERR_TIMEOUT err",
+            "err",
+            false,
+            CaseFolding::Unicode
+        ));
+        assert!(!contains_whole_word("ERR_TIMEOUT", "err", false, CaseFolding::Unicode));
+        assert!(contains_whole_word("(err)", "err", true, CaseFolding::Unicode));
+        // Edges of the pattern that are not word characters may border anything
+        assert!(contains_whole_word("x=1", "=1", true, CaseFolding::Unicode));
+        assert!(!contains_whole_word("x=12", "=1", true, CaseFolding::Unicode));
+
+        let ranges: Vec<(usize, usize)> =
+            find_whole_words("err error err", "err", true, CaseFolding::Unicode).collect();
+        assert_eq!(ranges, vec![(0, 3), (10, 13)]);
     }
 
     #[test]
     fn test_contains_ignore_case_returns_false_for_no_match() {
-        assert!(!contains_ignore_case("INFO: foo", "error"));
+        assert!(!contains_ignore_case("INFO: foo", "error", CaseFolding::Unicode));
     }
 
     #[test]
     fn test_contains_ignore_case_handles_empty_needle() {
-        assert!(contains_ignore_case("foo", ""));
+        assert!(contains_ignore_case("foo", "", CaseFolding::Unicode));
     }
 
     #[test]
     fn test_contains_ignore_case_handles_needle_longer_than_haystack() {
-        assert!(!contains_ignore_case("foo", "foobar"));
+        assert!(!contains_ignore_case("foo", "foobar", CaseFolding::Unicode));
     }
 }
@@ -1,8 +1,30 @@
 /// Maximum number of history entries to keep.
-const MAX_HISTORY: usize = 20;
+const MAX_HISTORY: usize = 100;
+
+/// Number of columns to keep visible around a match when scrolling horizontally to it.
+const HORIZONTAL_SCROLL_MARGIN: usize = 8;
+
+/// Number of lines of the previous page kept on screen when paging up or down.
+pub const DEFAULT_PAGE_OVERLAP: usize = 1;
+
+/// Splits the log view height into the heights of the top and bottom pane, leaving one line for
+/// the separator between them.
+pub fn split_heights(height: usize) -> (usize, usize) {
+    let available = height.saturating_sub(1);
+    let top = available / 2;
+    (top, available - top)
+}
+
+/// Splits the log view width into the widths of the left and right pane, leaving one column for the separator
+/// between them and one for the scrollbar of the left pane.
+pub fn split_widths(width: usize) -> (usize, usize) {
+    let available = width.saturating_sub(2);
+    let left = available / 2;
+    (left, available - left)
+}
 
 /// Manages the visible window and cursor position for viewing log lines.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Viewport {
     /// Width of the viewport in characters.
     pub width: usize,
@@ -14,6 +36,8 @@ pub struct Viewport {
     pub selected_line: usize,
     /// Number of lines to maintain as margin when scrolling.
     pub scroll_margin: usize,
+    /// Number of lines of the previous page kept on screen when paging up or down.
+    pub page_overlap: usize,
     /// Total number of lines available to display.
     pub total_lines: usize,
     /// Horizontal scroll offset for wide lines.
@@ -45,6 +69,12 @@ impl Viewport {
         self.adjust_visible();
     }
 
+    /// Moves the view up after `count` lines before it were removed.
+    pub fn shift_up(&mut self, count: usize) {
+        self.selected_line = self.selected_line.saturating_sub(count);
+        self.top_line = self.top_line.saturating_sub(count);
+    }
+
     /// Sets the number of available lines to show.
     pub fn set_total_lines(&mut self, total_lines: usize) {
         self.total_lines = total_lines;
@@ -69,7 +99,7 @@ impl Viewport {
     /// Moves the selection up by one page and center the viewport on that line.
     pub fn page_up(&mut self) {
         if self.selected_line > 0 {
-            let page_size = self.height.saturating_sub(1);
+            let page_size = self.page_size();
             self.selected_line = self.selected_line.saturating_sub(page_size);
             self.adjust_visible();
             self.center_selected();
@@ -79,13 +109,63 @@ impl Viewport {
     /// Moves the selection down by one page and center the viewport on that line.
     pub fn page_down(&mut self) {
         if self.selected_line + 1 < self.total_lines {
-            let page_size = self.height.saturating_sub(1);
+            let page_size = self.page_size();
             self.selected_line = (self.selected_line + page_size).min(self.total_lines.saturating_sub(1));
             self.adjust_visible();
             self.center_selected();
         }
     }
 
+    /// Moves the selection and the viewport up by half a page.
+    pub fn half_page_up(&mut self) {
+        let amount = (self.height / 2).max(1);
+        self.selected_line = self.selected_line.saturating_sub(amount);
+        self.top_line = self.top_line.saturating_sub(amount);
+        self.adjust_visible();
+    }
+
+    /// Moves the selection and the viewport down by half a page.
+    pub fn half_page_down(&mut self) {
+        let amount = (self.height / 2).max(1);
+        let last_line = self.total_lines.saturating_sub(1);
+        self.selected_line = (self.selected_line + amount).min(last_line);
+        self.top_line = (self.top_line + amount).min(self.total_lines.saturating_sub(self.height));
+        self.adjust_visible();
+    }
+
+    /// Scrolls the viewport up by one line, keeping the selection unless it would leave the screen.
+    pub fn scroll_up(&mut self) {
+        if self.center_cursor_mode {
+            self.move_up();
+            return;
+        }
+        if self.top_line == 0 {
+            return;
+        }
+        self.top_line -= 1;
+        let bottom_line = (self.top_line + self.height.saturating_sub(self.scroll_margin + 1)).max(self.top_line);
+        self.selected_line = self.selected_line.min(bottom_line);
+    }
+
+    /// Scrolls the viewport down by one line, keeping the selection unless it would leave the screen.
+    pub fn scroll_down(&mut self) {
+        if self.center_cursor_mode {
+            self.move_down();
+            return;
+        }
+        if self.top_line + self.height >= self.total_lines {
+            return;
+        }
+        self.top_line += 1;
+        let top_line = (self.top_line + self.scroll_margin).min(self.total_lines - 1);
+        self.selected_line = self.selected_line.max(top_line);
+    }
+
+    /// Returns the number of lines to move when paging up or down.
+    fn page_size(&self) -> usize {
+        self.height.saturating_sub(self.page_overlap).max(1)
+    }
+
     /// Moves the selection to the first line.
     pub fn goto_top(&mut self) {
         self.selected_line = 0;
@@ -117,6 +197,31 @@ impl Viewport {
         }
     }
 
+    /// Returns the line to go to for a go to line input: a line number, a percentage of the lines like
+    /// `50%`, a move relative to the selected line like `+100` or `-100`, or `$` for the last line.
+    ///
+    /// Relative moves stop at the first and last line. Returns `None` for invalid or out of range input.
+    pub fn resolve_goto_line(&self, input: &str) -> Option<usize> {
+        let input = input.trim();
+        let last_line = self.total_lines.checked_sub(1)?;
+
+        if input == "$" {
+            Some(last_line)
+        } else if let Some(percent) = input.strip_suffix('%') {
+            let percent: usize = percent.parse().ok().filter(|&percent| percent <= 100)?;
+            Some(last_line * percent / 100)
+        } else if let Some(offset) = input.strip_prefix('+') {
+            let offset: usize = offset.parse().ok()?;
+            Some(self.selected_line.saturating_add(offset).min(last_line))
+        } else if let Some(offset) = input.strip_prefix('-') {
+            let offset: usize = offset.parse().ok()?;
+            Some(self.selected_line.saturating_sub(offset))
+        } else {
+            let line_number: usize = input.parse().ok()?;
+            Some(line_number.checked_sub(1)?).filter(|&line| line <= last_line)
+        }
+    }
+
     /// Centers the selected line in the viewport.
     pub fn center_selected(&mut self) {
         if self.total_lines == 0 {
@@ -204,6 +309,31 @@ impl Viewport {
         }
     }
 
+    /// Adjusts the horizontal offset so the given column range is visible, keeping a small margin.
+    pub fn scroll_to_columns(&mut self, start: usize, end: usize) {
+        let margin = HORIZONTAL_SCROLL_MARGIN.min(self.width / 4);
+        if start < self.horizontal_offset {
+            self.horizontal_offset = start.saturating_sub(margin);
+        } else if end + margin > self.horizontal_offset + self.width {
+            // Prefer showing the start of the match if it is wider than the viewport
+            self.horizontal_offset = (end + margin)
+                .saturating_sub(self.width)
+                .min(start.saturating_sub(margin));
+        }
+    }
+
+    /// Centers the given column range horizontally if it is not fully visible.
+    ///
+    /// The start of a range wider than the viewport is kept in view, with a small margin.
+    pub fn center_columns(&mut self, start: usize, end: usize) {
+        if start >= self.horizontal_offset && end <= self.horizontal_offset + self.width {
+            return;
+        }
+        let margin = HORIZONTAL_SCROLL_MARGIN.min(self.width / 4);
+        let center = (start + end) / 2;
+        self.horizontal_offset = center.saturating_sub(self.width / 2).min(start.saturating_sub(margin));
+    }
+
     /// Resets horizontal scroll.
     pub fn reset_horizontal(&mut self) {
         self.horizontal_offset = 0;
@@ -227,6 +357,29 @@ impl Viewport {
         }
     }
 
+    /// Removes history entries of the first `count` lines and shifts the rest after those lines were dropped.
+    pub fn drop_history_before(&mut self, count: usize) {
+        let dropped = self.history.iter().filter(|&&line_index| line_index < count).count();
+        self.history.retain(|&line_index| line_index >= count);
+        for line_index in &mut self.history {
+            *line_index -= count;
+        }
+        self.history_position = self.history_position.saturating_sub(dropped);
+    }
+
+    /// Moves history entries to new line indices, dropping entries without a new index.
+    pub fn remap_history(&mut self, new_index: impl Fn(usize) -> Option<usize>) {
+        let mut remapped = Vec::with_capacity(self.history.len());
+        for (position, &line_index) in self.history.iter().enumerate() {
+            match new_index(line_index) {
+                Some(line_index) => remapped.push(line_index),
+                None if position < self.history_position => self.history_position -= 1,
+                None => {}
+            }
+        }
+        self.history = remapped;
+    }
+
     /// Navigate back in history.
     /// Returns the log line index to jump to, or None if at the beginning.
     pub fn history_back(&mut self) -> Option<usize> {
@@ -238,6 +391,33 @@ impl Viewport {
         }
     }
 
+    /// Navigate back in history from the given line. A line moved to since the last jump is recorded first,
+    /// so navigating forward again returns to it.
+    pub fn history_back_from(&mut self, line_index: usize) -> Option<usize> {
+        if self.history.get(self.history_position) != Some(&line_index) {
+            self.push_history(line_index);
+        }
+        self.history_back()
+    }
+
+    /// Returns the log line indices in the navigation history, oldest first.
+    pub fn history(&self) -> &[usize] {
+        &self.history
+    }
+
+    /// Returns the position in the navigation history.
+    pub fn history_position(&self) -> usize {
+        self.history_position
+    }
+
+    /// Moves to the given position in the navigation history.
+    /// Returns the log line index to jump to, or None if there is no such position.
+    pub fn goto_history(&mut self, position: usize) -> Option<usize> {
+        let line_index = self.history.get(position).copied()?;
+        self.history_position = position;
+        Some(line_index)
+    }
+
     /// Navigate forward in history.
     /// Returns the log line index to jump to, or None if at the end.
     pub fn history_forward(&mut self) -> Option<usize> {
@@ -259,6 +439,7 @@ mod tests {
             width: 80,
             height,
             scroll_margin: 2,
+            page_overlap: DEFAULT_PAGE_OVERLAP,
             total_lines,
             ..Default::default()
         };
@@ -377,4 +558,179 @@ mod tests {
         assert_eq!(viewport.width, 120);
         assert_eq!(viewport.height, 25);
     }
+
+    #[test]
+    fn test_scroll_to_columns() {
+        let mut viewport = create_viewport(10, 100);
+
+        // Already visible
+        viewport.scroll_to_columns(10, 20);
+        assert_eq!(viewport.horizontal_offset, 0);
+
+        // Beyond the right edge
+        viewport.scroll_to_columns(150, 160);
+        assert_eq!(viewport.horizontal_offset, 88);
+
+        // Before the left edge
+        viewport.scroll_to_columns(40, 45);
+        assert_eq!(viewport.horizontal_offset, 32);
+
+        // Wider than the viewport shows the start of the match
+        viewport.scroll_to_columns(200, 400);
+        assert_eq!(viewport.horizontal_offset, 192);
+    }
+
+    #[test]
+    fn test_center_columns() {
+        let mut viewport = create_viewport(10, 100);
+
+        // Already visible
+        viewport.center_columns(10, 20);
+        assert_eq!(viewport.horizontal_offset, 0);
+
+        // Beyond the right edge
+        viewport.center_columns(150, 160);
+        assert_eq!(viewport.horizontal_offset, 115);
+
+        // Before the left edge
+        viewport.center_columns(40, 45);
+        assert_eq!(viewport.horizontal_offset, 2);
+
+        // Centering doesn't scroll past the start of the line
+        viewport.center_columns(150, 160);
+        viewport.center_columns(5, 10);
+        assert_eq!(viewport.horizontal_offset, 0);
+
+        // Wider than the viewport shows the start of the match
+        viewport.center_columns(200, 400);
+        assert_eq!(viewport.horizontal_offset, 192);
+    }
+
+    #[test]
+    fn test_history_back_records_moved_to_line() {
+        let mut viewport = create_viewport(10, 100);
+        viewport.push_history(5);
+        viewport.push_history(50);
+
+        // Moved on from line 50 to line 60 before going back
+        assert_eq!(viewport.history_back_from(60), Some(50));
+        assert_eq!(viewport.history_back_from(50), Some(5));
+        assert_eq!(viewport.history(), &[5, 50, 60]);
+        assert_eq!(viewport.goto_history(2), Some(60));
+        assert_eq!(viewport.history_position(), 2);
+        assert_eq!(viewport.goto_history(3), None);
+    }
+
+    #[test]
+    fn test_drop_history_before() {
+        let mut viewport = create_viewport(10, 100);
+        viewport.push_history(5);
+        viewport.push_history(50);
+        viewport.push_history(80);
+
+        viewport.drop_history_before(10);
+
+        assert_eq!(viewport.history_back(), Some(40));
+        assert_eq!(viewport.history_back(), None);
+        assert_eq!(viewport.history_forward(), Some(70));
+    }
+
+    #[test]
+    fn test_remap_history() {
+        let mut viewport = create_viewport(10, 100);
+        viewport.push_history(5);
+        viewport.push_history(50);
+        viewport.push_history(80);
+
+        viewport.remap_history(|line_index| (line_index != 50).then_some(line_index + 1));
+
+        assert_eq!(viewport.history_back(), Some(6));
+        assert_eq!(viewport.history_back(), None);
+        assert_eq!(viewport.history_forward(), Some(81));
+    }
+
+    #[test]
+    fn test_page_overlap() {
+        let mut viewport = create_viewport(10, 100);
+        viewport.page_down();
+        assert_eq!(viewport.selected_line, 9);
+
+        viewport.page_overlap = 3;
+        viewport.page_down();
+        assert_eq!(viewport.selected_line, 16);
+        viewport.page_up();
+        assert_eq!(viewport.selected_line, 9);
+    }
+
+    #[test]
+    fn test_half_page_scroll() {
+        let mut viewport = create_viewport(10, 100);
+        viewport.selected_line = 3;
+        viewport.half_page_down();
+        assert_eq!((viewport.top_line, viewport.selected_line), (5, 8));
+        viewport.half_page_up();
+        assert_eq!((viewport.top_line, viewport.selected_line), (0, 3));
+
+        viewport.goto_bottom();
+        viewport.half_page_down();
+        assert_eq!((viewport.top_line, viewport.selected_line), (90, 99));
+    }
+
+    #[test]
+    fn test_scroll_keeps_selection() {
+        let mut viewport = create_viewport(10, 100);
+        viewport.selected_line = 5;
+        viewport.scroll_down();
+        assert_eq!((viewport.top_line, viewport.selected_line), (1, 5));
+        viewport.scroll_down();
+        viewport.scroll_down();
+        viewport.scroll_down();
+        // The selection is pushed down to stay clear of the scroll margin
+        assert_eq!((viewport.top_line, viewport.selected_line), (4, 6));
+
+        viewport.scroll_up();
+        assert_eq!((viewport.top_line, viewport.selected_line), (3, 6));
+        viewport.scroll_up();
+        viewport.scroll_up();
+        viewport.scroll_up();
+        assert_eq!((viewport.top_line, viewport.selected_line), (0, 6));
+        viewport.scroll_up();
+        assert_eq!((viewport.top_line, viewport.selected_line), (0, 6));
+
+        viewport.goto_bottom();
+        viewport.scroll_down();
+        assert_eq!((viewport.top_line, viewport.selected_line), (90, 99));
+    }
+
+    #[test]
+    fn test_split_heights() {
+        assert_eq!(split_heights(21), (10, 10));
+        assert_eq!(split_heights(20), (9, 10));
+        assert_eq!(split_heights(0), (0, 0));
+        assert_eq!(split_widths(101), (49, 50));
+        assert_eq!(split_widths(1), (0, 0));
+    }
+
+    #[test]
+    fn test_resolve_goto_line() {
+        let mut viewport = create_viewport(10, 201);
+        viewport.goto_line(50, false);
+
+        assert_eq!(viewport.resolve_goto_line("10"), Some(9));
+        assert_eq!(viewport.resolve_goto_line("0"), None);
+        assert_eq!(viewport.resolve_goto_line("202"), None);
+        assert_eq!(viewport.resolve_goto_line("50%"), Some(100));
+        assert_eq!(viewport.resolve_goto_line("0%"), Some(0));
+        assert_eq!(viewport.resolve_goto_line("100%"), Some(200));
+        assert_eq!(viewport.resolve_goto_line("101%"), None);
+        assert_eq!(viewport.resolve_goto_line("+100"), Some(150));
+        assert_eq!(viewport.resolve_goto_line("+1000"), Some(200));
+        assert_eq!(viewport.resolve_goto_line("-20"), Some(30));
+        assert_eq!(viewport.resolve_goto_line("-100"), Some(0));
+        assert_eq!(viewport.resolve_goto_line("$"), Some(200));
+        assert_eq!(viewport.resolve_goto_line("+"), None);
+        assert_eq!(viewport.resolve_goto_line("1$"), None);
+
+        assert_eq!(create_viewport(10, 0).resolve_goto_line("$"), None);
+    }
 }
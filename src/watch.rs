@@ -0,0 +1,202 @@
+use std::collections::VecDeque;
+
+use regex::Regex;
+
+/// Number of recent values kept per watch expression.
+pub const WATCH_HISTORY_LEN: usize = 120;
+
+/// Numeric value extracted from matching log lines with a regex capture group.
+#[derive(Debug, Clone)]
+pub struct WatchExpression {
+    pub name: String,
+    regex: Regex,
+    /// Most recent values, oldest first.
+    values: VecDeque<f64>,
+}
+
+impl WatchExpression {
+    /// Creates a watch expression. Returns `None` if the regex has no capture group for the value.
+    pub fn new(name: &str, regex: Regex) -> Option<Self> {
+        if regex.captures_len() < 2 {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            regex,
+            values: VecDeque::with_capacity(WATCH_HISTORY_LEN),
+        })
+    }
+
+    pub fn pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// Extracts the value from the first participating capture group of a line.
+    pub fn extract(&self, line: &str) -> Option<f64> {
+        let caps = self.regex.captures(line)?;
+        let value = caps.iter().skip(1).flatten().next()?;
+        value.as_str().trim().parse().ok()
+    }
+
+    /// Records the value of a line if it matches. Returns true if a value was recorded.
+    pub fn process_line(&mut self, line: &str) -> bool {
+        let Some(value) = self.extract(line) else {
+            return false;
+        };
+        if self.values.len() == WATCH_HISTORY_LEN {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+        true
+    }
+
+    /// Fills the history with the most recent values of the given lines.
+    pub fn seed<'a>(&mut self, lines: impl DoubleEndedIterator<Item = &'a str>) {
+        let mut recent: Vec<f64> = lines
+            .rev()
+            .filter_map(|line| self.extract(line))
+            .take(WATCH_HISTORY_LEN)
+            .collect();
+        recent.reverse();
+        self.values = recent.into();
+    }
+
+    /// Returns the recorded values, oldest first.
+    pub fn values(&self) -> &VecDeque<f64> {
+        &self.values
+    }
+
+    pub fn latest(&self) -> Option<f64> {
+        self.values.back().copied()
+    }
+
+    /// Returns the smallest and largest recorded value.
+    pub fn range(&self) -> Option<(f64, f64)> {
+        let first = self.values.front()?;
+        Some(
+            self.values
+                .iter()
+                .fold((*first, *first), |(min, max), &v| (min.min(v), max.max(v))),
+        )
+    }
+
+    pub fn clear_values(&mut self) {
+        self.values.clear();
+    }
+}
+
+/// All watch expressions.
+#[derive(Debug, Default)]
+pub struct Watches {
+    expressions: Vec<WatchExpression>,
+}
+
+impl Watches {
+    pub fn new(expressions: Vec<WatchExpression>) -> Self {
+        Self { expressions }
+    }
+
+    /// Adds a watch expression. Returns false if one with the same pattern already exists.
+    pub fn add(&mut self, expression: WatchExpression) -> bool {
+        if self.expressions.iter().any(|e| e.pattern() == expression.pattern()) {
+            return false;
+        }
+        self.expressions.push(expression);
+        true
+    }
+
+    /// Records values of a new line in all matching watch expressions.
+    pub fn process_line(&mut self, line: &str) {
+        for expression in &mut self.expressions {
+            expression.process_line(line);
+        }
+    }
+
+    /// Seeds watch expressions without values from the given lines, e.g. when a file is opened.
+    pub fn seed_empty<'a, I>(&mut self, lines: I)
+    where
+        I: DoubleEndedIterator<Item = &'a str> + Clone,
+    {
+        for expression in self.expressions.iter_mut().filter(|e| e.values.is_empty()) {
+            expression.seed(lines.clone());
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &WatchExpression> {
+        self.expressions.iter()
+    }
+
+    pub fn count(&self) -> usize {
+        self.expressions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expressions.is_empty()
+    }
+
+    pub fn clear_values(&mut self) {
+        for expression in &mut self.expressions {
+            expression.clear_values();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue_watch() -> WatchExpression {
+        WatchExpression::new("queue", Regex::new(r"queue depth=(\d+(?:\.\d+)?)").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_new_requires_capture_group() {
+        assert!(WatchExpression::new("queue", Regex::new(r"queue depth=\d+").unwrap()).is_none());
+        assert!(WatchExpression::new("queue", Regex::new(r"(?:a)|depth=(\d+)").unwrap()).is_some());
+    }
+
+    #[test]
+    fn test_process_line_records_values() {
+        let mut watch = queue_watch();
+
+        assert!(watch.process_line("INFO queue depth=12"));
+        assert!(!watch.process_line("INFO unrelated line"));
+        assert!(watch.process_line("INFO queue depth=3.5 items"));
+
+        assert_eq!(watch.values().iter().copied().collect::<Vec<_>>(), vec![12.0, 3.5]);
+        assert_eq!(watch.latest(), Some(3.5));
+        assert_eq!(watch.range(), Some((3.5, 12.0)));
+    }
+
+    #[test]
+    fn test_history_keeps_most_recent_values() {
+        let mut watch = queue_watch();
+        for i in 0..WATCH_HISTORY_LEN + 5 {
+            watch.process_line(&format!("queue depth={}", i));
+        }
+
+        assert_eq!(watch.values().len(), WATCH_HISTORY_LEN);
+        assert_eq!(watch.values().front(), Some(&5.0));
+        assert_eq!(watch.latest(), Some((WATCH_HISTORY_LEN + 4) as f64));
+    }
+
+    #[test]
+    fn test_seed_uses_last_matching_lines() {
+        let lines: Vec<String> = (0..WATCH_HISTORY_LEN * 2)
+            .map(|i| {
+                if i % 2 == 0 {
+                    format!("queue depth={}", i)
+                } else {
+                    "other".to_string()
+                }
+            })
+            .collect();
+        let mut watch = queue_watch();
+
+        watch.seed(lines.iter().map(String::as_str));
+
+        assert_eq!(watch.values().len(), WATCH_HISTORY_LEN);
+        assert_eq!(watch.values().front(), Some(&0.0));
+        assert_eq!(watch.latest(), Some((WATCH_HISTORY_LEN * 2 - 2) as f64));
+    }
+}
@@ -1,6 +1,8 @@
 use lazylog::highlighter::{HighlightPattern, Highlighter, PatternStyle};
+use lazylog::live_processor::LiveProcessorHandle;
 use lazylog::matcher::{PatternMatchType, PlainMatch};
 use lazylog::options::{AppOption, AppOptions};
+use lazylog::utils::CaseFolding;
 use ratatui::style::Color;
 use regex::Regex;
 use std::time::{Duration, Instant};
@@ -128,6 +130,8 @@ fn perf_plain_match_case_sensitive() {
     let matcher = PlainMatch {
         pattern: "INFO".to_string(),
         case_sensitive: true,
+        whole_word: false,
+        case_folding: CaseFolding::default(),
     };
 
     let iterations = 10000;
@@ -147,6 +151,8 @@ fn perf_plain_match_case_insensitive() {
     let matcher = PlainMatch {
         pattern: "info".to_string(),
         case_sensitive: false,
+        whole_word: false,
+        case_folding: CaseFolding::default(),
     };
 
     let iterations = 10000;
@@ -234,3 +240,83 @@ fn perf_highlight_multiple_patterns() {
         time.as_micros() as f64 / iterations as f64
     );
 }
+
+#[test]
+fn perf_highlight_many_plain_patterns() {
+    let words = [
+        "archlinux",
+        "thread",
+        "pool",
+        "service",
+        "user",
+        "request",
+        "status",
+        "active",
+        "duration",
+        "example",
+    ];
+    let patterns: Vec<HighlightPattern> = (0..50)
+        .map(|i| {
+            HighlightPattern::new(
+                &format!("{}{}", words[i % words.len()], i / words.len()),
+                PatternMatchType::Plain(i % 2 == 0),
+                PatternStyle::new(Some(Color::Indexed(i as u8)), None, false),
+            )
+            .unwrap()
+        })
+        .chain(words.iter().map(|word| {
+            HighlightPattern::new(
+                word,
+                PatternMatchType::Plain(false),
+                PatternStyle::new(Some(Color::Yellow), None, false),
+            )
+            .unwrap()
+        }))
+        .collect();
+    let highlighter = Highlighter::new(patterns, vec![]);
+
+    let iterations = 10000;
+
+    let mut counter = 0;
+    let time = measure_time(iterations, || {
+        counter += 1;
+        highlighter.highlight_line(counter, SAMPLE_LOG_LINE)
+    });
+
+    println!(
+        "Highlight with 60 plain patterns: total={:?} ({} iterations), {:.2}µs/iteration",
+        time,
+        iterations,
+        time.as_micros() as f64 / iterations as f64
+    );
+}
+
+#[tokio::test]
+async fn perf_live_processor_batches_queued_lines() {
+    let (output_tx, mut output_rx) = tokio::sync::mpsc::unbounded_channel();
+    let processor = LiveProcessorHandle::spawn(output_tx);
+
+    let line_count = 100_000;
+    let start = Instant::now();
+    for _ in 0..line_count {
+        processor.send_line(SAMPLE_LOG_LINE.to_string());
+    }
+
+    let mut received = 0;
+    let mut batches = 0;
+    while received < line_count {
+        received += output_rx.recv().await.unwrap().len();
+        batches += 1;
+    }
+    let time = start.elapsed();
+
+    println!(
+        "live processor: total={:?} ({} lines in {} batches), {:.2}ns/line",
+        time,
+        line_count,
+        batches,
+        time.as_nanos() as f64 / line_count as f64
+    );
+    // Lines queued while processing are coalesced into large batches
+    assert!(batches <= 10);
+}